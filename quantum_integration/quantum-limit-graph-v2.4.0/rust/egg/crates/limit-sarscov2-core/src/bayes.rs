@@ -0,0 +1,257 @@
+// limit-sarscov2-core/src/bayes.rs
+// Bayesian network view of the causal subgraph: noisy-OR CPTs and exact belief updating
+
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use thiserror::Error;
+
+use crate::{
+    edges::EdgeType,
+    multi_intent_graph::MultiIntentGraph,
+};
+
+/// Free variables beyond which exact inference is refused (2ⁿ assignments)
+pub const MAX_EXACT_VARIABLES: usize = 20;
+
+/// Parents beyond which `cpt` is not tabulated
+pub const MAX_CPT_PARENTS: usize = 10;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BayesOptions {
+    pub leak: f32,                // P(node | no parent present) for nodes with parents
+}
+
+impl Default for BayesOptions {
+    fn default() -> Self {
+        Self { leak: 0.01 }
+    }
+}
+
+/// Binary variable for one intent node ("present" / "holds")
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BayesNode {
+    pub id: Uuid,
+    pub label: String,
+    pub domain: String,
+    pub parents: Vec<Uuid>,       // sorted
+    pub strengths: Vec<f32>,      // noisy-OR strength per parent: net support of its causal edges
+    pub prior: Option<f32>,       // roots: the node's confidence
+    pub leak: f32,
+}
+
+/// One CPT row: parent values in `BayesNode::parents` order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CptRow {
+    pub parents: Vec<bool>,
+    pub probability: f32,         // P(node = true | parents)
+}
+
+/// Causal edges as a DAG of binary variables. Cycles are broken by dropping
+/// the edges that close them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BayesianNetwork {
+    pub nodes: BTreeMap<Uuid, BayesNode>,
+    pub order: Vec<Uuid>,         // topological
+    pub dropped_edges: Vec<Uuid>, // causal edges left out to keep the network acyclic
+}
+
+/// Belief in one node before and after conditioning
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Belief {
+    pub node_id: Uuid,
+    pub label: String,
+    pub prior: f32,               // marginal with no evidence
+    pub posterior: f32,           // given the evidence
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "error", rename_all = "snake_case")]
+pub enum BayesError {
+    #[error("node {node} is not in the Bayesian network")]
+    UnknownNode { node: Uuid },
+    #[error("query needs {variables} free variables; exact inference is limited to {max}")]
+    TooLarge { variables: usize, max: usize },
+    #[error("the evidence has probability zero")]
+    ImpossibleEvidence,
+}
+
+impl BayesNode {
+    /// P(node = true) given parent values in `parents` order
+    pub fn probability(&self, parents: impl Iterator<Item = bool>) -> f32 {
+        match self.prior {
+            Some(prior) => prior,
+            None => {
+                let off: f32 = parents.zip(&self.strengths).filter(|(on, _)| *on).map(|(_, s)| 1.0 - s).product();
+                1.0 - (1.0 - self.leak) * off
+            }
+        }
+    }
+
+    /// Full conditional probability table; `None` above `MAX_CPT_PARENTS` parents
+    pub fn cpt(&self) -> Option<Vec<CptRow>> {
+        let k = self.parents.len();
+        if k > MAX_CPT_PARENTS {
+            return None;
+        }
+        Some((0..1usize << k)
+            .map(|bits| {
+                let parents: Vec<bool> = (0..k).map(|i| bits >> (k - 1 - i) & 1 == 1).collect();
+                CptRow { probability: self.probability(parents.iter().copied()), parents }
+            })
+            .collect())
+    }
+}
+
+impl BayesianNetwork {
+    /// `nodes` plus all their ancestors, in topological order
+    fn ancestral(&self, nodes: impl Iterator<Item = Uuid>) -> Vec<Uuid> {
+        let mut keep = BTreeSet::new();
+        let mut stack: Vec<Uuid> = nodes.collect();
+        while let Some(id) = stack.pop() {
+            if keep.insert(id) {
+                stack.extend(&self.nodes[&id].parents);
+            }
+        }
+        self.order.iter().copied().filter(|id| keep.contains(id)).collect()
+    }
+
+    /// Prior and posterior belief in each of `queries` given `evidence`
+    /// (observed true/false), by exact enumeration over the queries' and
+    /// evidence's ancestors
+    pub fn query(&self, evidence: &HashMap<Uuid, bool>, queries: &[Uuid]) -> Result<Vec<Belief>, BayesError> {
+        for id in evidence.keys().chain(queries) {
+            if !self.nodes.contains_key(id) {
+                return Err(BayesError::UnknownNode { node: *id });
+            }
+        }
+        let priors = self.marginals(&HashMap::new(), queries)?;
+        let posteriors = self.marginals(evidence, queries)?;
+        Ok(queries.iter().zip(priors).zip(posteriors)
+            .map(|((id, prior), posterior)| Belief { node_id: *id, label: self.nodes[id].label.clone(), prior, posterior })
+            .collect())
+    }
+
+    /// P(q = true | evidence) for each query
+    fn marginals(&self, evidence: &HashMap<Uuid, bool>, queries: &[Uuid]) -> Result<Vec<f32>, BayesError> {
+        let variables = self.ancestral(evidence.keys().chain(queries).copied());
+        let free: Vec<usize> = (0..variables.len()).filter(|i| !evidence.contains_key(&variables[*i])).collect();
+        if free.len() > MAX_EXACT_VARIABLES {
+            return Err(BayesError::TooLarge { variables: free.len(), max: MAX_EXACT_VARIABLES });
+        }
+        let index: HashMap<Uuid, usize> = variables.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+        let parents: Vec<Vec<usize>> = variables.iter().map(|id| self.nodes[id].parents.iter().map(|p| index[p]).collect()).collect();
+        let query_slots: Vec<usize> = queries.iter().map(|q| index[q]).collect();
+
+        // f64 accumulators: many small joint probabilities are summed
+        let mut values: Vec<bool> = variables.iter().map(|id| evidence.get(id).copied().unwrap_or(false)).collect();
+        let mut total = 0.0f64;
+        let mut query_true = vec![0.0f64; queries.len()];
+        for bits in 0..1usize << free.len() {
+            for (k, &slot) in free.iter().enumerate() {
+                values[slot] = bits >> k & 1 == 1;
+            }
+            let joint: f64 = variables.iter().enumerate()
+                .map(|(i, id)| {
+                    let p = self.nodes[id].probability(parents[i].iter().map(|&j| values[j])) as f64;
+                    if values[i] { p } else { 1.0 - p }
+                })
+                .product();
+            total += joint;
+            for (acc, &slot) in query_true.iter_mut().zip(&query_slots) {
+                if values[slot] {
+                    *acc += joint;
+                }
+            }
+        }
+        if total <= 0.0 {
+            return Err(BayesError::ImpossibleEvidence);
+        }
+        Ok(query_true.into_iter().map(|p| (p / total) as f32).collect())
+    }
+}
+
+impl MultiIntentGraph {
+    /// Project the supporting causal edges as a Bayesian network. Each node
+    /// touched by one is a binary variable; a root's prior is its confidence,
+    /// and a node with parents has a noisy-OR CPT whose per-parent strength is
+    /// the net support of the causal edges between them (noisy-OR over
+    /// parallel edges) plus `options.leak`.
+    pub fn bayesian_network(&self, options: &BayesOptions) -> BayesianNetwork {
+        let net_support = self.net_supports();
+        let mut edges: Vec<_> = self.edges.values()
+            .filter(|e| e.edge_type == EdgeType::Causal && !e.is_refuting() && e.source_id != e.target_id)
+            .filter(|e| self.intent_nodes.contains_key(&e.source_id) && self.intent_nodes.contains_key(&e.target_id))
+            .collect();
+        edges.sort_by_key(|e| e.id);
+
+        // Break cycles: depth-first from each node in ID order, dropping edges back onto the stack
+        let mut outgoing: BTreeMap<Uuid, Vec<(Uuid, Uuid)>> = BTreeMap::new();
+        for e in &edges {
+            outgoing.entry(e.source_id).or_default().push((e.id, e.target_id));
+            outgoing.entry(e.target_id).or_default();
+        }
+        #[derive(Clone, Copy, PartialEq)]
+        enum Mark { New, Active, Done }
+        let mut mark: HashMap<Uuid, Mark> = outgoing.keys().map(|id| (*id, Mark::New)).collect();
+        let mut dropped = BTreeSet::new();
+        let mut finished = vec![];
+        for &root in outgoing.keys() {
+            if mark[&root] != Mark::New {
+                continue;
+            }
+            let mut stack = vec![(root, 0usize)];
+            mark.insert(root, Mark::Active);
+            while let Some((node, next)) = stack.pop() {
+                match outgoing[&node].get(next) {
+                    Some(&(edge_id, target)) => {
+                        stack.push((node, next + 1));
+                        match mark[&target] {
+                            Mark::Active => {
+                                dropped.insert(edge_id);
+                            }
+                            Mark::New => {
+                                mark.insert(target, Mark::Active);
+                                stack.push((target, 0));
+                            }
+                            Mark::Done => {}
+                        }
+                    }
+                    None => {
+                        mark.insert(node, Mark::Done);
+                        finished.push(node);
+                    }
+                }
+            }
+        }
+        finished.reverse();
+
+        let mut strength: BTreeMap<(Uuid, Uuid), f32> = BTreeMap::new();
+        for e in edges.iter().filter(|e| !dropped.contains(&e.id)) {
+            let s = strength.entry((e.target_id, e.source_id)).or_insert(0.0);
+            *s = 1.0 - (1.0 - *s) * (1.0 - net_support.get(&e.id).copied().unwrap_or(0.0));
+        }
+        let leak = options.leak.clamp(0.0, 1.0);
+        let nodes = outgoing.keys()
+            .map(|&id| {
+                let node = &self.intent_nodes[&id];
+                let (parents, strengths): (Vec<Uuid>, Vec<f32>) = strength.range((id, Uuid::nil())..=(id, Uuid::max()))
+                    .map(|(&(_, parent), &s)| (parent, s))
+                    .unzip();
+                let prior = parents.is_empty().then(|| node.metadata.confidence.clamp(0.0, 1.0));
+                let bayes = BayesNode {
+                    id,
+                    label: node.label().to_string(),
+                    domain: format!("{:?}", node.domain),
+                    parents,
+                    strengths,
+                    prior,
+                    leak,
+                };
+                (id, bayes)
+            })
+            .collect();
+        BayesianNetwork { nodes, order: finished, dropped_edges: dropped.into_iter().collect() }
+    }
+}
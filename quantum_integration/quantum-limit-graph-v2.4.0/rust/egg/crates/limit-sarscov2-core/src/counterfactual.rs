@@ -0,0 +1,157 @@
+// limit-sarscov2-core/src/counterfactual.rs
+// Counterfactual comparison of two hypothesis paths that diverge at a node
+
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use thiserror::Error;
+
+use crate::{
+    edges::{EdgeType, GraphEdge},
+    multi_intent_graph::{HypothesisPath, MultiIntentGraph},
+};
+
+/// One path's branch after the divergence node
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BranchSummary {
+    pub path_id: Uuid,
+    pub nodes: Vec<Uuid>,         // after the divergence node
+    pub confidence: f32,          // branch edges under `path_policy.aggregator`
+    pub reachable: usize,         // outcome nodes reached from the branch
+}
+
+/// Belief in one outcome node under each branch: the strongest causal chain
+/// (product of net support) from the divergence node through the branch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutcomeComparison {
+    pub node_id: Uuid,
+    pub label: String,
+    pub factual: Option<f32>,         // `None`: not reachable from this branch
+    pub counterfactual: Option<f32>,
+    pub difference: f32,              // factual − counterfactual, unreachable counting as 0
+    pub terminal: bool,               // no outgoing causal edges
+}
+
+/// Two hypothesis paths compared past the node where they diverge (e.g.
+/// vaccinate vs. not), outcomes sorted by largest difference first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CounterfactualReport {
+    pub divergence_node: Uuid,
+    pub shared_nodes: usize,          // common prefix, ending at the divergence node
+    pub factual: BranchSummary,
+    pub counterfactual: BranchSummary,
+    pub outcomes: Vec<OutcomeComparison>,
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "error", rename_all = "snake_case")]
+pub enum CounterfactualError {
+    #[error("hypothesis path {path} not found")]
+    UnknownPath { path: Uuid },
+    #[error("hypothesis path {path} references edge {edge}, which is not in the graph")]
+    MissingEdge { path: Uuid, edge: Uuid },
+    #[error("the paths do not start at the same node")]
+    NoSharedNode,
+    #[error("the paths do not diverge: one continues the other")]
+    NotDivergent,
+}
+
+impl MultiIntentGraph {
+    /// Compare the outcomes reachable from two hypothesis paths that share a
+    /// prefix and then diverge. Each branch reaches the nodes along it, and
+    /// from those whatever its supporting causal edges lead to, outside the
+    /// shared prefix; correlative edges carry no causal effect and are not
+    /// followed. Belief in an outcome is the strongest chain's product of net
+    /// support, starting from the divergence node.
+    pub fn compare_counterfactual(&self, factual: Uuid, counterfactual: Uuid) -> Result<CounterfactualReport, CounterfactualError> {
+        let find = |id: Uuid| self.hypothesis_paths.iter().find(|p| p.id == id).ok_or(CounterfactualError::UnknownPath { path: id });
+        let (a, b) = (find(factual)?, find(counterfactual)?);
+        let shared = a.node_sequence.iter().zip(&b.node_sequence).take_while(|(x, y)| x == y).count();
+        if shared == 0 {
+            return Err(CounterfactualError::NoSharedNode);
+        }
+        if shared == a.node_sequence.len() || shared == b.node_sequence.len() {
+            return Err(CounterfactualError::NotDivergent);
+        }
+        let divergence_node = a.node_sequence[shared - 1];
+        let prefix: HashSet<Uuid> = a.node_sequence[..shared].iter().copied().collect();
+
+        let net_support = self.net_supports();
+        let mut causal: HashMap<Uuid, Vec<(Uuid, f32)>> = HashMap::new();
+        for e in self.edges.values().filter(|e| e.edge_type == EdgeType::Causal) {
+            if let Some(&s) = net_support.get(&e.id) {
+                causal.entry(e.source_id).or_default().push((e.target_id, s));
+            }
+        }
+
+        let (factual, factual_reach) = self.branch(a, shared, &prefix, &causal, &net_support)?;
+        let (counterfactual, counterfactual_reach) = self.branch(b, shared, &prefix, &causal, &net_support)?;
+
+        let reached: BTreeSet<Uuid> = factual_reach.keys().chain(counterfactual_reach.keys()).copied().collect();
+        let mut outcomes: Vec<OutcomeComparison> = reached.into_iter()
+            .map(|node_id| {
+                let f = factual_reach.get(&node_id).copied();
+                let c = counterfactual_reach.get(&node_id).copied();
+                OutcomeComparison {
+                    node_id,
+                    label: self.intent_nodes.get(&node_id).map(|n| n.label().to_string()).unwrap_or_default(),
+                    factual: f,
+                    counterfactual: c,
+                    difference: f.unwrap_or(0.0) - c.unwrap_or(0.0),
+                    terminal: causal.get(&node_id).is_none_or(Vec::is_empty),
+                }
+            })
+            .collect();
+        outcomes.sort_by(|x, y| y.difference.abs().total_cmp(&x.difference.abs()).then(x.node_id.cmp(&y.node_id)));
+
+        Ok(CounterfactualReport { divergence_node, shared_nodes: shared, factual, counterfactual, outcomes })
+    }
+
+    /// Branch of `path` after `shared` nodes and the strongest-chain belief in
+    /// every node it reaches
+    fn branch(
+        &self,
+        path: &HypothesisPath,
+        shared: usize,
+        prefix: &HashSet<Uuid>,
+        causal: &HashMap<Uuid, Vec<(Uuid, f32)>>,
+        net_support: &HashMap<Uuid, f32>,
+    ) -> Result<(BranchSummary, HashMap<Uuid, f32>), CounterfactualError> {
+        let edges: Vec<&GraphEdge> = path.edge_sequence.iter().skip(shared - 1)
+            .map(|id| self.edges.get(id).ok_or(CounterfactualError::MissingEdge { path: path.id, edge: *id }))
+            .collect::<Result<_, _>>()?;
+        let nodes: Vec<Uuid> = path.node_sequence[shared..].to_vec();
+
+        // Nodes along the branch, then best-first expansion along causal edges
+        let mut reach: HashMap<Uuid, f32> = HashMap::new();
+        let mut along = 1.0f32;
+        for (node, edge) in nodes.iter().zip(&edges) {
+            along *= net_support.get(&edge.id).copied().unwrap_or(0.0);
+            let best = reach.entry(*node).or_insert(0.0);
+            *best = best.max(along);
+        }
+        let mut settled: HashSet<Uuid> = HashSet::new();
+        while let Some((node, belief)) = reach.iter()
+            .filter(|(id, _)| !settled.contains(*id))
+            .max_by(|x, y| x.1.total_cmp(y.1).then(y.0.cmp(x.0)))
+            .map(|(id, b)| (*id, *b))
+        {
+            settled.insert(node);
+            for &(target, support) in causal.get(&node).into_iter().flatten() {
+                if prefix.contains(&target) || settled.contains(&target) {
+                    continue;
+                }
+                let best = reach.entry(target).or_insert(0.0);
+                *best = best.max(belief * support);
+            }
+        }
+
+        let summary = BranchSummary {
+            path_id: path.id,
+            nodes,
+            confidence: self.path_policy.aggregator.aggregate_with(&edges, |e| net_support.get(&e.id).copied().unwrap_or(0.0)),
+            reachable: reach.len(),
+        };
+        Ok((summary, reach))
+    }
+}
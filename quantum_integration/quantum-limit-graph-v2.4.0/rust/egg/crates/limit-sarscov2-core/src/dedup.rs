@@ -0,0 +1,348 @@
+// limit-sarscov2-core/src/dedup.rs
+// Entity resolution: score near-duplicate intent nodes, merge them and rewire their edges
+
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    edges::normalize_evidence_ref,
+    fuzzy::fuzzy_similarity,
+    lineage::PangoLineage,
+    multi_intent_graph::{IntentNode, MultiIntentGraph, NodeContent},
+    mutation::Mutation,
+    synonyms::SynonymTable,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DedupOptions {
+    pub name_weight: f32,             // label similarity
+    pub mutation_weight: f32,         // overlap of mutation sets (Genomics only)
+    pub source_weight: f32,           // overlap of `metadata.sources`
+    pub min_score: f32,               // pairs scoring below this are not candidates
+}
+
+impl Default for DedupOptions {
+    fn default() -> Self {
+        Self { name_weight: 0.5, mutation_weight: 0.3, source_weight: 0.2, min_score: 0.8 }
+    }
+}
+
+/// Two nodes of the same domain that probably describe the same entity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateCandidate {
+    pub keep: Uuid,                   // the better-evidenced node, which survives a merge
+    pub duplicate: Uuid,
+    pub keep_label: String,
+    pub duplicate_label: String,
+    pub score: f32,                   // weighted over the signals both nodes have
+    pub name_similarity: f32,
+    pub shared_mutations: Vec<String>,
+    pub shared_sources: Vec<String>,
+}
+
+/// What `merge_nodes` changed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeMerge {
+    pub kept: Uuid,
+    pub merged: Uuid,                 // removed from the graph
+    pub merged_label: String,
+    pub score: Option<f32>,           // candidate score when merged by `dedup`
+    pub edges_rewired: usize,         // edges and hyperedges now touching `kept` instead
+    pub edges_collapsed: usize,       // folded into an existing edge with the same endpoints, type and polarity
+    pub self_loops_removed: usize,    // edges between the two nodes
+}
+
+/// Lowercased alphanumeric tokens ("Omicron BA.5" → ["omicron", "ba.5"])
+fn tokens(label: &str) -> Vec<String> {
+    label.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric() && c != '.')
+        .map(|t| t.trim_matches('.'))
+        .filter(|t| !t.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Dice coefficient of character bigrams
+fn bigram_dice(a: &str, b: &str) -> f32 {
+    let bigrams = |s: &str| -> Vec<(char, char)> {
+        let chars: Vec<char> = s.chars().collect();
+        chars.windows(2).map(|w| (w[0], w[1])).collect()
+    };
+    let (a, b) = (bigrams(a), bigrams(b));
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let mut remaining = b.clone();
+    let shared = a.iter()
+        .filter(|g| remaining.iter().position(|r| r == *g).map(|i| remaining.swap_remove(i)).is_some())
+        .count();
+    2.0 * shared as f32 / (a.len() + b.len()) as f32
+}
+
+/// Similarity of two node labels in 0..=1. Labels naming Pango lineages
+/// compare by lineage: the same lineage (aliases and WHO labels resolved) is
+/// 1.0 and different lineages 0.0, so "Omicron BA.5" matches "BA.5" but not
+/// "BA.2". Otherwise token containment, character bigrams and typo-tolerant
+/// token matching (`fuzzy_similarity`, so "Omicorn BA5" matches) decide.
+pub fn name_similarity(a: &str, b: &str) -> f32 {
+    if let (Some(la), Some(lb)) = (PangoLineage::find_in(a), PangoLineage::find_in(b)) {
+        return if la.same_as(&lb) { 1.0 } else { 0.0 };
+    }
+    let (ta, tb) = (tokens(a), tokens(b));
+    if ta.is_empty() || tb.is_empty() {
+        return 0.0;
+    }
+    if ta == tb {
+        return 1.0;
+    }
+    let (short, long) = if ta.len() <= tb.len() { (&ta, &tb) } else { (&tb, &ta) };
+    let containment: f32 = if short.iter().all(|t| long.contains(t)) { 0.9 } else { 0.0 };
+    containment.max(bigram_dice(&ta.join(" "), &tb.join(" "))).max(fuzzy_similarity(a, b))
+}
+
+/// Canonical notation where parseable ("s:l452r" → "S:L452R")
+fn mutation_key(mutation: &str) -> String {
+    mutation.trim().parse::<Mutation>().map(|m| m.to_string()).unwrap_or_else(|_| mutation.trim().to_uppercase())
+}
+
+fn source_key(source: &str) -> String {
+    normalize_evidence_ref(source).to_lowercase()
+}
+
+fn mutation_set(node: &IntentNode) -> HashSet<String> {
+    match &node.content {
+        NodeContent::Variant(v) => v.notations().iter().map(|m| mutation_key(m)).collect(),
+        _ => HashSet::new(),
+    }
+}
+
+fn source_set(node: &IntentNode) -> HashSet<String> {
+    node.metadata.sources.iter().map(|s| source_key(s)).collect()
+}
+
+/// Overlap coefficient (shared / size of the smaller set), so a sparse
+/// duplicate whose few entries all appear on the richer node scores 1.0
+fn overlap(a: &HashSet<String>, b: &HashSet<String>) -> (f32, Vec<String>) {
+    let mut shared: Vec<String> = a.intersection(b).cloned().collect();
+    shared.sort();
+    let smaller = a.len().min(b.len());
+    (if smaller == 0 { 0.0 } else { shared.len() as f32 / smaller as f32 }, shared)
+}
+
+/// Which of two nodes survives a merge: more evidence, then more sources,
+/// higher confidence, earlier creation and finally the smaller ID
+fn prefer<'a>(a: &'a IntentNode, b: &'a IntentNode) -> (&'a IntentNode, &'a IntentNode) {
+    let rank = |n: &IntentNode| (std::cmp::Reverse(n.metadata.evidence_count), std::cmp::Reverse(n.metadata.sources.len()));
+    let order = rank(a).cmp(&rank(b))
+        .then(b.metadata.confidence.total_cmp(&a.metadata.confidence))
+        .then(a.metadata.created_at.cmp(&b.metadata.created_at))
+        .then(a.id.cmp(&b.id));
+    if order.is_le() { (a, b) } else { (b, a) }
+}
+
+/// Score one pair. Names compare directly and through `synonyms`. Mutation
+/// and source signals only count when both nodes have mutations or sources;
+/// the weights are renormalized over the rest.
+fn score_pair(a: &IntentNode, b: &IntentNode, options: &DedupOptions, synonyms: &SynonymTable) -> DuplicateCandidate {
+    let name = std::iter::once(a.label().to_string())
+        .chain(synonyms.equivalents(a.label()))
+        .map(|n| name_similarity(&n, b.label()))
+        .fold(0.0, f32::max);
+    let (ma, mb) = (mutation_set(a), mutation_set(b));
+    let (sa, sb) = (source_set(a), source_set(b));
+    let (mutation_score, shared_mutations) = overlap(&ma, &mb);
+    let (source_score, shared_sources) = overlap(&sa, &sb);
+
+    let mut weighted = options.name_weight * name;
+    let mut total = options.name_weight;
+    if !ma.is_empty() && !mb.is_empty() {
+        weighted += options.mutation_weight * mutation_score;
+        total += options.mutation_weight;
+    }
+    if !sa.is_empty() && !sb.is_empty() {
+        weighted += options.source_weight * source_score;
+        total += options.source_weight;
+    }
+    let (keep, duplicate) = prefer(a, b);
+    DuplicateCandidate {
+        keep: keep.id,
+        duplicate: duplicate.id,
+        keep_label: keep.label().to_string(),
+        duplicate_label: duplicate.label().to_string(),
+        score: if total > 0.0 { weighted / total } else { 0.0 },
+        name_similarity: name,
+        shared_mutations,
+        shared_sources,
+    }
+}
+
+/// Candidate duplicate pairs within each domain, best first
+pub fn find_duplicates(graph: &MultiIntentGraph, options: &DedupOptions) -> Vec<DuplicateCandidate> {
+    let mut by_domain: HashMap<String, Vec<&IntentNode>> = HashMap::new();
+    for node in graph.intent_nodes.values() {
+        by_domain.entry(format!("{:?}", node.domain)).or_default().push(node);
+    }
+    let mut candidates = vec![];
+    for nodes in by_domain.values_mut() {
+        nodes.sort_by_key(|n| n.id);
+        for (i, a) in nodes.iter().enumerate() {
+            for b in &nodes[i + 1..] {
+                let candidate = score_pair(a, b, options, &graph.synonyms);
+                if candidate.score >= options.min_score {
+                    candidates.push(candidate);
+                }
+            }
+        }
+    }
+    candidates.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.keep.cmp(&b.keep)).then(a.duplicate.cmp(&b.duplicate)));
+    candidates
+}
+
+/// Append the items of `from` whose `key` is not already in `into`
+fn union_into<T: Clone>(into: &mut Vec<T>, from: &[T], key: impl Fn(&T) -> String) {
+    let mut seen: HashSet<String> = into.iter().map(&key).collect();
+    for item in from {
+        if seen.insert(key(item)) {
+            into.push(item.clone());
+        }
+    }
+}
+
+impl MultiIntentGraph {
+    /// Merge node `duplicate` into `keep`, which must be distinct nodes of the
+    /// same domain. `keep` gains the duplicate's sources, xrefs, mutations and
+    /// governance tags and the larger evidence count and confidence. Edges and
+    /// hyperedges of the duplicate are rewired to `keep`; an edge that would
+    /// repeat an existing one (same endpoints and type) is folded into it, and
+    /// edges between the two nodes are removed. Hypothesis paths follow the
+    /// rewiring.
+    pub fn merge_nodes(&mut self, keep: Uuid, duplicate: Uuid) -> Option<NodeMerge> {
+        let domain = |id: Uuid| self.intent_nodes.get(&id).map(|n| std::mem::discriminant(&n.domain));
+        if keep == duplicate || domain(keep)? != domain(duplicate)? {
+            return None;
+        }
+        let dup = self.intent_nodes.remove(&duplicate)?;
+        let kept = self.intent_nodes.get_mut(&keep)?;
+        union_into(&mut kept.metadata.sources, &dup.metadata.sources, |s| source_key(s));
+        union_into(&mut kept.metadata.xrefs, &dup.metadata.xrefs, |x| x.curie());
+        kept.metadata.evidence_count = kept.metadata.evidence_count.max(dup.metadata.evidence_count);
+        kept.metadata.confidence = kept.metadata.confidence.max(dup.metadata.confidence);
+        match (&mut kept.content, &dup.content) {
+            (NodeContent::Variant(k), NodeContent::Variant(d)) => {
+                union_into(&mut k.mutations, &d.mutations, |m| m.to_string());
+                union_into(&mut k.unparsed_mutations, &d.unparsed_mutations, |m| mutation_key(m));
+            }
+            (NodeContent::Biology(k), NodeContent::Biology(d)) => {
+                union_into(&mut k.regions, &d.regions, |r| format!("{}:{}:{}-{}", r.gene.to_lowercase(), r.name.to_lowercase(), r.start, r.end));
+            }
+            _ => {}
+        }
+
+        let mut merge = NodeMerge {
+            kept: keep,
+            merged: duplicate,
+            merged_label: dup.label().to_string(),
+            score: None,
+            edges_rewired: 0,
+            edges_collapsed: 0,
+            self_loops_removed: 0,
+        };
+        let mut touching: Vec<Uuid> = self.edges.values()
+            .filter(|e| e.source_id == duplicate || e.target_id == duplicate)
+            .map(|e| e.id)
+            .collect();
+        touching.sort();
+        let mut replaced: HashMap<Uuid, Option<Uuid>> = HashMap::new();   // removed edge → surviving edge
+        for edge_id in touching {
+            let mut edge = self.edges.remove(&edge_id).expect("edge listed above");
+            for end in [&mut edge.source_id, &mut edge.target_id] {
+                if *end == duplicate {
+                    *end = keep;
+                }
+            }
+            if edge.source_id == edge.target_id {
+                merge.self_loops_removed += 1;
+                replaced.insert(edge_id, None);
+                continue;
+            }
+            let parallel = self.edges.values_mut()
+                .find(|e| e.source_id == edge.source_id && e.target_id == edge.target_id && e.edge_type == edge.edge_type
+                    && e.metadata.polarity == edge.metadata.polarity);
+            match parallel {
+                Some(existing) => {
+                    union_into(&mut existing.metadata.evidence_refs, &edge.metadata.evidence_refs, |r| source_key(r));
+                    existing.metadata.confidence = existing.metadata.confidence.max(edge.metadata.confidence);
+                    existing.weight = existing.weight.max(edge.weight);
+                    merge.edges_collapsed += 1;
+                    replaced.insert(edge_id, Some(existing.id));
+                }
+                None => {
+                    merge.edges_rewired += 1;
+                    self.edges.insert(edge_id, edge);
+                }
+            }
+        }
+
+        for hyperedge in self.hyperedges.values_mut().filter(|h| h.touches(duplicate)) {
+            for ids in [&mut hyperedge.source_ids, &mut hyperedge.target_ids] {
+                for id in ids.iter_mut().filter(|id| **id == duplicate) {
+                    *id = keep;
+                }
+                let mut seen = HashSet::new();
+                ids.retain(|id| seen.insert(*id));
+            }
+            merge.edges_rewired += 1;
+        }
+
+        // Paths through a removed self-loop no longer exist; the rest are rewritten
+        self.hypothesis_paths.retain(|p| !p.edge_sequence.iter().any(|e| replaced.get(e) == Some(&None)));
+        for path in &mut self.hypothesis_paths {
+            for node in &mut path.node_sequence {
+                if *node == duplicate {
+                    *node = keep;
+                }
+            }
+            for edge in &mut path.edge_sequence {
+                if let Some(Some(survivor)) = replaced.get(edge) {
+                    *edge = *survivor;
+                }
+            }
+        }
+        if let Some(tags) = self.governance_tags.remove(&duplicate) {
+            self.governance_tags.entry(keep).or_default().extend(tags);
+        }
+        for removed in replaced.keys() {
+            self.governance_tags.remove(removed);
+        }
+
+        self.metadata.total_nodes = self.intent_nodes.len();
+        self.metadata.total_edges = self.edges.len();
+        self.invalidate_confidence(keep);
+        self.recompute_paths();
+        Some(merge)
+    }
+
+    /// Merge every candidate from `find_duplicates`, best first. Chains resolve
+    /// to the final survivor (A ← B, B ← C merges C into A).
+    pub fn dedup(&mut self, options: &DedupOptions) -> Vec<NodeMerge> {
+        let mut merged_into: HashMap<Uuid, Uuid> = HashMap::new();
+        let resolve = |merged_into: &HashMap<Uuid, Uuid>, mut id: Uuid| {
+            while let Some(&next) = merged_into.get(&id) {
+                id = next;
+            }
+            id
+        };
+        let mut merges = vec![];
+        for candidate in find_duplicates(self, options) {
+            let (keep, duplicate) = (resolve(&merged_into, candidate.keep), resolve(&merged_into, candidate.duplicate));
+            if let Some(mut merge) = self.merge_nodes(keep, duplicate) {
+                merge.score = Some(candidate.score);
+                merged_into.insert(duplicate, keep);
+                merges.push(merge);
+            }
+        }
+        merges
+    }
+}
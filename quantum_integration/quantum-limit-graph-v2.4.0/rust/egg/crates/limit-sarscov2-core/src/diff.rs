@@ -0,0 +1,235 @@
+// limit-sarscov2-core/src/diff.rs
+// Differences between two versions of a graph as a curator-facing changelog
+
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use std::collections::{BTreeSet, HashMap};
+
+use crate::{
+    edges::{EdgeType, GraphEdge, HyperEdge, Polarity},
+    multi_intent_graph::{IntentNode, MultiIntentGraph},
+    provenance::GovernanceTag,
+    validate::SubjectKind,
+};
+
+/// Confidence moves smaller than this are not reported
+const CONFIDENCE_EPSILON: f32 = 1e-3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// One changelog line
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeEntry {
+    pub kind: ChangeKind,
+    pub subject_id: Uuid,
+    pub subject: SubjectKind,
+    pub summary: String,          // "Added causal edge BA.5 → immune escape (conf 0.85, 2 refs)"
+}
+
+/// What changed from an older to a newer version of a graph
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GraphDiff {
+    pub entries: Vec<ChangeEntry>,    // added, removed, then changed; nodes before edges and hyperedges
+}
+
+impl GraphDiff {
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn count(&self, kind: ChangeKind) -> usize {
+        self.entries.iter().filter(|e| e.kind == kind).count()
+    }
+
+    /// Markdown changelog with one section per kind of change
+    pub fn to_markdown(&self, title: &str) -> String {
+        let mut out = format!("# {}\n", title);
+        if self.is_empty() {
+            out.push_str("\nNo changes.\n");
+            return out;
+        }
+        for (kind, heading) in [(ChangeKind::Added, "Added"), (ChangeKind::Removed, "Removed"), (ChangeKind::Changed, "Changed")] {
+            let lines: Vec<&ChangeEntry> = self.entries.iter().filter(|e| e.kind == kind).collect();
+            if lines.is_empty() {
+                continue;
+            }
+            out.push_str(&format!("\n## {}\n\n", heading));
+            for entry in lines {
+                out.push_str(&format!("- {}\n", entry.summary));
+            }
+        }
+        out
+    }
+}
+
+fn edge_type_name(edge_type: &EdgeType) -> String {
+    edge_type.name().to_lowercase()
+}
+
+fn plural(n: usize, one: &str) -> String {
+    format!("{} {}{}", n, one, if n == 1 { "" } else { "s" })
+}
+
+/// Node label, or its ID if neither version has it
+fn node_name(id: Uuid, graphs: [&MultiIntentGraph; 2]) -> String {
+    graphs.iter().find_map(|g| g.intent_nodes.get(&id)).map_or_else(|| id.to_string(), |n| n.label().to_string())
+}
+
+fn describe_node(node: &IntentNode) -> String {
+    format!("{:?} node {}", node.domain, node.label())
+}
+
+fn describe_edge(edge: &GraphEdge, graphs: [&MultiIntentGraph; 2]) -> String {
+    let refuting = if edge.metadata.polarity == Polarity::Refutes { "refuting " } else { "" };
+    format!(
+        "{}{} edge {} → {}",
+        refuting, edge_type_name(&edge.edge_type), node_name(edge.source_id, graphs), node_name(edge.target_id, graphs),
+    )
+}
+
+fn describe_hyperedge(hyperedge: &HyperEdge, graphs: [&MultiIntentGraph; 2]) -> String {
+    let names = |ids: &[Uuid]| ids.iter().map(|id| node_name(*id, graphs)).collect::<Vec<_>>().join(" + ");
+    format!(
+        "{} hyperedge {} → {}",
+        edge_type_name(&hyperedge.edge_type), names(&hyperedge.source_ids), names(&hyperedge.target_ids),
+    )
+}
+
+fn confidence_change(what: &str, old: f32, new: f32) -> Option<String> {
+    if (new - old).abs() < CONFIDENCE_EPSILON {
+        return None;
+    }
+    let direction = if new > old { "raised" } else { "lowered" };
+    Some(format!("Confidence on {} {} from {:.2} to {:.2}", what, direction, old, new))
+}
+
+fn evidence_change(what: &str, old: &[String], new: &[String]) -> Option<String> {
+    let old: BTreeSet<&String> = old.iter().collect();
+    let new: BTreeSet<&String> = new.iter().collect();
+    let added: Vec<&str> = new.difference(&old).map(|r| r.as_str()).collect();
+    let removed: Vec<&str> = old.difference(&new).map(|r| r.as_str()).collect();
+    let mut parts = vec![];
+    if !added.is_empty() {
+        parts.push(format!("added {}", added.join(", ")));
+    }
+    if !removed.is_empty() {
+        parts.push(format!("removed {}", removed.join(", ")));
+    }
+    (!parts.is_empty()).then(|| format!("Evidence on {}: {}", what, parts.join("; ")))
+}
+
+/// Name of a node, edge or hyperedge for tag entries
+fn describe_subject(id: Uuid, graphs: [&MultiIntentGraph; 2]) -> Option<(SubjectKind, String)> {
+    graphs.iter().find_map(|g| {
+        if let Some(n) = g.intent_nodes.get(&id) {
+            Some((SubjectKind::Node, describe_node(n)))
+        } else if let Some(e) = g.edges.get(&id) {
+            Some((SubjectKind::Edge, describe_edge(e, graphs)))
+        } else {
+            g.hyperedges.get(&id).map(|h| (SubjectKind::Hyperedge, describe_hyperedge(h, graphs)))
+        }
+    })
+}
+
+fn tag_outcome(tag: &GovernanceTag) -> &'static str {
+    if tag.passed { "passed" } else { "failed" }
+}
+
+impl MultiIntentGraph {
+    /// Changelog from this version of the graph to `newer`: nodes, edges and
+    /// hyperedges added or removed, and changes to labels, confidence,
+    /// evidence and governance tags
+    pub fn diff(&self, newer: &MultiIntentGraph) -> GraphDiff {
+        let graphs = [newer, self];
+        let mut entries = vec![];
+        let mut push = |kind, subject_id, subject, summary: String| entries.push(ChangeEntry { kind, subject_id, subject, summary });
+
+        for node in newer.intent_nodes.values().filter(|n| !self.intent_nodes.contains_key(&n.id)) {
+            push(ChangeKind::Added, node.id, SubjectKind::Node, format!("Added {} (conf {:.2})", describe_node(node), node.metadata.confidence));
+        }
+        for node in self.intent_nodes.values().filter(|n| !newer.intent_nodes.contains_key(&n.id)) {
+            push(ChangeKind::Removed, node.id, SubjectKind::Node, format!("Removed {}", describe_node(node)));
+        }
+        for (old, new) in self.intent_nodes.values().filter_map(|o| newer.intent_nodes.get(&o.id).map(|n| (o, n))) {
+            if old.label() != new.label() {
+                push(ChangeKind::Changed, new.id, SubjectKind::Node, format!("Renamed {:?} node {} to {}", new.domain, old.label(), new.label()));
+            }
+            if let Some(summary) = confidence_change(&describe_node(new), old.metadata.confidence, new.metadata.confidence) {
+                push(ChangeKind::Changed, new.id, SubjectKind::Node, summary);
+            }
+        }
+
+        for edge in newer.edges.values().filter(|e| !self.edges.contains_key(&e.id)) {
+            let summary = format!(
+                "Added {} (conf {:.2}, {})",
+                describe_edge(edge, graphs), edge.metadata.confidence, plural(edge.distinct_evidence().len(), "ref"),
+            );
+            push(ChangeKind::Added, edge.id, SubjectKind::Edge, summary);
+        }
+        for edge in self.edges.values().filter(|e| !newer.edges.contains_key(&e.id)) {
+            push(ChangeKind::Removed, edge.id, SubjectKind::Edge, format!("Removed {}", describe_edge(edge, graphs)));
+        }
+        for (old, new) in self.edges.values().filter_map(|o| newer.edges.get(&o.id).map(|n| (o, n))) {
+            let what = describe_edge(new, graphs);
+            if old.edge_type != new.edge_type {
+                push(ChangeKind::Changed, new.id, SubjectKind::Edge, format!("Retyped {} from {}", what, edge_type_name(&old.edge_type)));
+            }
+            if let Some(summary) = confidence_change(&what, old.metadata.confidence, new.metadata.confidence) {
+                push(ChangeKind::Changed, new.id, SubjectKind::Edge, summary);
+            }
+            if let Some(summary) = evidence_change(&what, &old.metadata.evidence_refs, &new.metadata.evidence_refs) {
+                push(ChangeKind::Changed, new.id, SubjectKind::Edge, summary);
+            }
+        }
+
+        for h in newer.hyperedges.values().filter(|h| !self.hyperedges.contains_key(&h.id)) {
+            let summary = format!("Added {} (conf {:.2}, {})", describe_hyperedge(h, graphs), h.confidence, plural(h.evidence_refs.len(), "ref"));
+            push(ChangeKind::Added, h.id, SubjectKind::Hyperedge, summary);
+        }
+        for h in self.hyperedges.values().filter(|h| !newer.hyperedges.contains_key(&h.id)) {
+            push(ChangeKind::Removed, h.id, SubjectKind::Hyperedge, format!("Removed {}", describe_hyperedge(h, graphs)));
+        }
+        for (old, new) in self.hyperedges.values().filter_map(|o| newer.hyperedges.get(&o.id).map(|n| (o, n))) {
+            let what = describe_hyperedge(new, graphs);
+            if let Some(summary) = confidence_change(&what, old.confidence, new.confidence) {
+                push(ChangeKind::Changed, new.id, SubjectKind::Hyperedge, summary);
+            }
+            if let Some(summary) = evidence_change(&what, &old.evidence_refs, &new.evidence_refs) {
+                push(ChangeKind::Changed, new.id, SubjectKind::Hyperedge, summary);
+            }
+        }
+
+        let tags = |g: &MultiIntentGraph| -> HashMap<(Uuid, Uuid), GovernanceTag> {
+            g.governance_tags.iter().flat_map(|(subject, ts)| ts.iter().map(move |t| ((*subject, t.id), t.clone()))).collect()
+        };
+        let (old_tags, new_tags) = (tags(self), tags(newer));
+        for ((subject_id, tag_id), tag) in &new_tags {
+            let Some((subject, what)) = describe_subject(*subject_id, graphs) else { continue };
+            match old_tags.get(&(*subject_id, *tag_id)) {
+                None => push(ChangeKind::Changed, *subject_id, subject, format!("Tagged {} {:?} ({})", what, tag.label, tag_outcome(tag))),
+                Some(old) if old.passed != tag.passed => push(
+                    ChangeKind::Changed, *subject_id, subject,
+                    format!("Tag {:?} on {} now {} (was {})", tag.label, what, tag_outcome(tag), tag_outcome(old)),
+                ),
+                Some(_) => {}
+            }
+        }
+        for ((subject_id, tag_id), tag) in &old_tags {
+            // Tags of removed subjects go with them
+            if new_tags.contains_key(&(*subject_id, *tag_id)) || describe_subject(*subject_id, [newer, newer]).is_none() {
+                continue;
+            }
+            let Some((subject, what)) = describe_subject(*subject_id, graphs) else { continue };
+            push(ChangeKind::Changed, *subject_id, subject, format!("Removed tag {:?} from {}", tag.label, what));
+        }
+
+        entries.sort_by(|a, b| (a.kind, a.subject as u8, &a.summary).cmp(&(b.kind, b.subject as u8, &b.summary)));
+        GraphDiff { entries }
+    }
+}
@@ -20,6 +20,12 @@ pub struct SarsCov2Graph {
     pub genomics: Vec<GenomicsNode>,
     pub treatment: Vec<TreatmentNode>,
     pub public_health: Vec<PublicHealthNode>,
+    #[serde(default)]
+    pub clinical_trials: Vec<ClinicalTrialNode>,
+    #[serde(default)]
+    pub host_factors: Vec<HostFactorNode>,
+    #[serde(default)]
+    pub vaccines: Vec<VaccineNode>,
 }
 
 impl SarsCov2Graph {
@@ -32,6 +38,9 @@ impl SarsCov2Graph {
             genomics: vec![],
             treatment: vec![],
             public_health: vec![],
+            clinical_trials: vec![],
+            host_factors: vec![],
+            vaccines: vec![],
         }
     }
 
@@ -40,4 +49,7 @@ impl SarsCov2Graph {
     pub fn add_genomics(&mut self, node: GenomicsNode) { self.genomics.push(node); }
     pub fn add_treatment(&mut self, node: TreatmentNode) { self.treatment.push(node); }
     pub fn add_public_health(&mut self, node: PublicHealthNode) { self.public_health.push(node); }
+    pub fn add_clinical_trial(&mut self, node: ClinicalTrialNode) { self.clinical_trials.push(node); }
+    pub fn add_host_factor(&mut self, node: HostFactorNode) { self.host_factors.push(node); }
+    pub fn add_vaccine(&mut self, node: VaccineNode) { self.vaccines.push(node); }
 }
@@ -0,0 +1,565 @@
+// limit-sarscov2-core/src/edges.rs
+// Causal and correlative edges for SARS-CoV-2 knowledge graph
+
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use std::collections::{BTreeMap, HashSet};
+use thiserror::Error;
+
+/// Edge types representing different relationship semantics. Serialized as a
+/// plain string: the built-in names ("Causal") or a custom name ("Cleaves").
+/// Contradicting a relationship is `Polarity::Refutes`, not a type of its own.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum EdgeType {
+    Causal,           // mutation → immune escape
+    Correlative,      // treatment → reduced hospitalization
+    Mechanistic,      // spike protein → ACE2 binding
+    Temporal,         // variant emergence → policy change
+    Inhibitory,       // antibody → viral replication
+    Preventive,       // vaccination → infection
+    Synergistic,      // nirmatrelvir → ritonavir boosting
+    Custom(String),   // project-specific; registered in the graph's `EdgeVocabulary`
+}
+
+/// Longest custom edge type name
+pub const MAX_EDGE_TYPE_LEN: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum EdgeTypeError {
+    #[error("edge type must not be empty")]
+    Empty,
+    #[error("edge type {0:?} must start with a letter and use only letters, digits, '_' and '-' (at most 64)")]
+    InvalidName(String),
+    #[error("edge type {0:?} is built in")]
+    BuiltIn(String),
+}
+
+impl EdgeType {
+    pub const BUILT_IN: [EdgeType; 7] = [
+        EdgeType::Causal,
+        EdgeType::Correlative,
+        EdgeType::Mechanistic,
+        EdgeType::Temporal,
+        EdgeType::Inhibitory,
+        EdgeType::Preventive,
+        EdgeType::Synergistic,
+    ];
+
+    /// Edge type named `name`. Built-in names match case-insensitively and in
+    /// snake_case ("causal"); other names become `Custom` if they start with a
+    /// letter and use only letters, digits, '_' and '-', so they are safe in
+    /// queries, CSV cells and RDF IRIs.
+    pub fn parse(name: &str) -> Result<Self, EdgeTypeError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(EdgeTypeError::Empty);
+        }
+        let key = name.replace(['_', '-'], "").to_lowercase();
+        if let Some(built_in) = Self::BUILT_IN.iter().find(|t| t.name().to_lowercase() == key) {
+            return Ok(built_in.clone());
+        }
+        let valid = name.len() <= MAX_EDGE_TYPE_LEN
+            && name.starts_with(|c: char| c.is_ascii_alphabetic())
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        if !valid {
+            return Err(EdgeTypeError::InvalidName(name.to_string()));
+        }
+        Ok(EdgeType::Custom(name.to_string()))
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            EdgeType::Causal => "Causal",
+            EdgeType::Correlative => "Correlative",
+            EdgeType::Mechanistic => "Mechanistic",
+            EdgeType::Temporal => "Temporal",
+            EdgeType::Inhibitory => "Inhibitory",
+            EdgeType::Preventive => "Preventive",
+            EdgeType::Synergistic => "Synergistic",
+            EdgeType::Custom(name) => name,
+        }
+    }
+
+    pub fn is_custom(&self) -> bool {
+        matches!(self, EdgeType::Custom(_))
+    }
+}
+
+impl std::fmt::Display for EdgeType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for EdgeType {
+    type Err = EdgeTypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        EdgeType::parse(s)
+    }
+}
+
+impl Serialize for EdgeType {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for EdgeType {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        EdgeType::parse(&name).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Custom edge types a graph accepts, each with what it means. Built-in
+/// types are always accepted.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct EdgeVocabulary {
+    types: BTreeMap<String, String>,  // custom type name → description
+}
+
+impl EdgeVocabulary {
+    /// Add (or redescribe) a custom type, returning it
+    pub fn register(&mut self, name: &str, description: impl Into<String>) -> Result<EdgeType, EdgeTypeError> {
+        let edge_type = EdgeType::parse(name)?;
+        if !edge_type.is_custom() {
+            return Err(EdgeTypeError::BuiltIn(edge_type.name().to_string()));
+        }
+        self.types.insert(edge_type.name().to_string(), description.into());
+        Ok(edge_type)
+    }
+
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.types.remove(name.trim()).is_some()
+    }
+
+    /// Built-in, or a registered custom type
+    pub fn accepts(&self, edge_type: &EdgeType) -> bool {
+        !edge_type.is_custom() || self.types.contains_key(edge_type.name())
+    }
+
+    pub fn description(&self, name: &str) -> Option<&str> {
+        self.types.get(name).map(String::as_str)
+    }
+
+    /// Registered custom types and their descriptions, by name
+    pub fn custom_types(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.types.iter().map(|(name, description)| (name.as_str(), description.as_str()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty()
+    }
+}
+
+/// Causal edge: A causes or leads to B
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CausalEdge {
+    pub id: Uuid,
+    pub source_id: Uuid,
+    pub target_id: Uuid,
+    pub source_domain: String,    // e.g., "Genomics"
+    pub target_domain: String,    // e.g., "Immunology"
+    pub relationship: String,     // "mutation → immune escape"
+    pub evidence_strength: f32,   // 0.0 to 1.0
+    pub source_refs: Vec<String>, // DOIs, papers
+}
+
+/// Correlative edge: A is associated with B
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorrelativeEdge {
+    pub id: Uuid,
+    pub source_id: Uuid,
+    pub target_id: Uuid,
+    pub source_domain: String,
+    pub target_domain: String,
+    pub correlation: String,      // "treatment → reduced hospitalization"
+    pub correlation_coeff: f32,   // statistical correlation
+    pub source_refs: Vec<String>,
+}
+
+/// Unified graph edge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphEdge {
+    pub id: Uuid,
+    pub edge_type: EdgeType,
+    pub source_id: Uuid,
+    pub target_id: Uuid,
+    pub label: String,
+    pub weight: f32,              // importance/strength
+    pub metadata: EdgeMetadata,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EdgeMetadata {
+    pub source_domain: String,
+    pub target_domain: String,
+    pub evidence_refs: Vec<String>,
+    pub confidence: f32,
+    pub created_at: String,
+    #[serde(default)]
+    pub polarity: Polarity,
+}
+
+/// Whether an edge's evidence supports or contradicts its relationship
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Polarity {
+    #[default]
+    Supports,
+    Refutes,      // contradicting study; never traversed as a relationship
+}
+
+impl GraphEdge {
+    pub fn new_causal(
+        source_id: Uuid,
+        target_id: Uuid,
+        label: String,
+        source_domain: String,
+        target_domain: String,
+        evidence_refs: Vec<String>,
+        confidence: f32,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            edge_type: EdgeType::Causal,
+            source_id,
+            target_id,
+            label,
+            weight: confidence,
+            metadata: EdgeMetadata {
+                source_domain,
+                target_domain,
+                evidence_refs,
+                confidence,
+                created_at: chrono::Utc::now().to_rfc3339(),
+                polarity: Polarity::Supports,
+            },
+        }
+    }
+
+    pub fn new_correlative(
+        source_id: Uuid,
+        target_id: Uuid,
+        label: String,
+        source_domain: String,
+        target_domain: String,
+        evidence_refs: Vec<String>,
+        correlation: f32,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            edge_type: EdgeType::Correlative,
+            source_id,
+            target_id,
+            label,
+            weight: correlation.abs(),
+            metadata: EdgeMetadata {
+                source_domain,
+                target_domain,
+                evidence_refs,
+                confidence: correlation.abs(),
+                created_at: chrono::Utc::now().to_rfc3339(),
+                polarity: Polarity::Supports,
+            },
+        }
+    }
+
+    /// Edge contradicting `edge`'s relationship: same endpoints, type and
+    /// domains, `Polarity::Refutes`, with `confidence` in the refuting evidence
+    pub fn refuting(edge: &GraphEdge, label: String, evidence_refs: Vec<String>, confidence: f32) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            edge_type: edge.edge_type.clone(),
+            source_id: edge.source_id,
+            target_id: edge.target_id,
+            label,
+            weight: confidence,
+            metadata: EdgeMetadata {
+                source_domain: edge.metadata.source_domain.clone(),
+                target_domain: edge.metadata.target_domain.clone(),
+                evidence_refs,
+                confidence,
+                created_at: chrono::Utc::now().to_rfc3339(),
+                polarity: Polarity::Refutes,
+            },
+        }
+    }
+
+    pub fn is_refuting(&self) -> bool {
+        self.metadata.polarity == Polarity::Refutes
+    }
+
+    pub fn is_cross_domain(&self) -> bool {
+        self.metadata.source_domain != self.metadata.target_domain
+    }
+
+    /// Distinct evidence references. DOIs are compared without their
+    /// "doi:" / resolver prefix and case, so "doi:10.1/X" and
+    /// "https://doi.org/10.1/x" count once.
+    pub fn distinct_evidence(&self) -> HashSet<String> {
+        self.metadata.evidence_refs.iter()
+            .map(|r| normalize_evidence_ref(r))
+            .filter(|r| !r.is_empty())
+            .collect()
+    }
+}
+
+/// Relationship among groups of nodes that pairwise edges cannot express,
+/// e.g. "S:L452R + S:F486V jointly confer immune escape". Path finding
+/// traverses it as a reified node between its sources and targets.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HyperEdge {
+    pub id: Uuid,
+    pub edge_type: EdgeType,
+    pub source_ids: Vec<Uuid>,    // jointly necessary
+    pub target_ids: Vec<Uuid>,
+    pub label: String,
+    pub weight: f32,
+    pub evidence_refs: Vec<String>,
+    pub confidence: f32,
+    pub created_at: String,
+}
+
+impl HyperEdge {
+    pub fn new(
+        edge_type: EdgeType,
+        source_ids: Vec<Uuid>,
+        target_ids: Vec<Uuid>,
+        label: String,
+        evidence_refs: Vec<String>,
+        confidence: f32,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            edge_type,
+            source_ids,
+            target_ids,
+            label,
+            weight: confidence,
+            evidence_refs,
+            confidence,
+            created_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Sources then targets
+    pub fn endpoints(&self) -> impl Iterator<Item = &Uuid> {
+        self.source_ids.iter().chain(&self.target_ids)
+    }
+
+    pub fn touches(&self, node_id: Uuid) -> bool {
+        self.endpoints().any(|&id| id == node_id)
+    }
+
+    /// Every (source, target) pair, as used for adjacency and structure metrics
+    pub fn pairs(&self) -> impl Iterator<Item = (Uuid, Uuid)> + '_ {
+        self.source_ids.iter().flat_map(move |&s| self.target_ids.iter().map(move |&t| (s, t)))
+    }
+}
+
+/// Comparable form of an evidence reference: DOIs become lowercase
+/// "doi:10.x/y" whatever their prefix; anything else is only trimmed
+pub fn normalize_evidence_ref(reference: &str) -> String {
+    let trimmed = reference.trim();
+    let lower = trimmed.to_lowercase();
+    for prefix in ["doi:", "https://doi.org/", "http://doi.org/", "https://dx.doi.org/", "http://dx.doi.org/"] {
+        if let Some(doi) = lower.strip_prefix(prefix) {
+            return format!("doi:{}", doi.trim());
+        }
+    }
+    if lower.starts_with("10.") && lower.contains('/') {
+        return format!("doi:{}", lower);
+    }
+    trimmed.to_string()
+}
+
+/// Example edge builders for common SARS-CoV-2 relationships
+pub mod builders {
+    use super::*;
+    use crate::nodes::{ClinicalTrialNode, VaccineNode};
+
+    fn direction(effect: f32, up: &str, down: &str) -> String {
+        if effect < 0.0 { down } else { up }.to_string()
+    }
+
+    pub fn mutation_to_immune_escape(
+        mutation_id: Uuid,
+        immune_id: Uuid,
+        mutation_name: &str,
+        evidence: Vec<String>,
+        strength: f32,
+    ) -> GraphEdge {
+        GraphEdge::new_causal(
+            mutation_id,
+            immune_id,
+            format!("{} → immune escape", mutation_name),
+            "Genomics".into(),
+            "Immunology".into(),
+            evidence,
+            strength,
+        )
+    }
+
+    pub fn treatment_to_outcome(
+        treatment_id: Uuid,
+        outcome_id: Uuid,
+        treatment_name: &str,
+        evidence: Vec<String>,
+        correlation: f32,
+    ) -> GraphEdge {
+        GraphEdge::new_correlative(
+            treatment_id,
+            outcome_id,
+            format!("{} → reduced hospitalization", treatment_name),
+            "Treatment".into(),
+            "PublicHealth".into(),
+            evidence,
+            correlation,
+        )
+    }
+
+    pub fn variant_to_transmissibility(
+        variant_id: Uuid,
+        virology_id: Uuid,
+        variant_name: &str,
+        evidence: Vec<String>,
+        strength: f32,
+    ) -> GraphEdge {
+        GraphEdge::new_causal(
+            variant_id,
+            virology_id,
+            format!("{} → increased transmissibility", variant_name),
+            "Genomics".into(),
+            "Virology".into(),
+            evidence,
+            strength,
+        )
+    }
+
+    /// Trial evidence that a treatment works. Only a randomized controlled
+    /// trial supports a causal claim; non-randomized and observational designs
+    /// give a correlative edge.
+    pub fn trial_supports_treatment(
+        trial: &ClinicalTrialNode,
+        treatment_id: Uuid,
+        treatment_name: &str,
+        evidence: Vec<String>,
+        strength: f32,
+    ) -> GraphEdge {
+        let label = format!("{} ({}) → {} efficacy", trial.registry_id, if trial.is_randomized() { "RCT" } else { "observational" }, treatment_name);
+        if trial.is_randomized() {
+            GraphEdge::new_causal(trial.id, treatment_id, label, "Treatment".into(), "Treatment".into(), evidence, strength)
+        } else {
+            GraphEdge::new_correlative(trial.id, treatment_id, label, "Treatment".into(), "Treatment".into(), evidence, strength)
+        }
+    }
+
+    /// Protection a vaccine confers against an outcome (infection, severe
+    /// disease), labelled with its VE estimates and variant context
+    pub fn vaccine_to_efficacy(
+        vaccine: &VaccineNode,
+        outcome_id: Uuid,
+        evidence: Vec<String>,
+        strength: f32,
+    ) -> GraphEdge {
+        let mut estimates: Vec<String> = [("infection", vaccine.ve_infection), ("severe", vaccine.ve_severe)].iter()
+            .filter_map(|(what, ve)| ve.map(|ve| format!("VE {} {:.0}%", what, ve * 100.0)))
+            .collect();
+        if let Some(variant) = &vaccine.variant_context {
+            estimates.push(format!("vs {}", variant));
+        }
+        let label = if estimates.is_empty() {
+            format!("{} → protection", vaccine.name)
+        } else {
+            format!("{} → protection ({})", vaccine.name, estimates.join(", "))
+        };
+        GraphEdge::new_causal(vaccine.id, outcome_id, label, "Immunology".into(), "PublicHealth".into(), evidence, strength)
+    }
+
+    /// Variant escaping vaccine-induced immunity: an inhibitory edge onto the vaccine
+    pub fn variant_reduces_ve(
+        variant_id: Uuid,
+        vaccine_id: Uuid,
+        variant_name: &str,
+        vaccine_name: &str,
+        evidence: Vec<String>,
+        strength: f32,
+    ) -> GraphEdge {
+        GraphEdge {
+            edge_type: EdgeType::Inhibitory,
+            ..GraphEdge::new_causal(
+                variant_id,
+                vaccine_id,
+                format!("{} → reduced {} effectiveness", variant_name, vaccine_name),
+                "Genomics".into(),
+                "Immunology".into(),
+                evidence,
+                strength,
+            )
+        }
+    }
+
+    /// Host factor raising (positive `effect`) or lowering (negative) the
+    /// risk of an outcome such as severe disease; an observational association
+    pub fn host_factor_modifies_outcome(
+        factor_id: Uuid,
+        outcome_id: Uuid,
+        factor_name: &str,
+        outcome_name: &str,
+        evidence: Vec<String>,
+        effect: f32,
+    ) -> GraphEdge {
+        GraphEdge::new_correlative(
+            factor_id,
+            outcome_id,
+            format!("{} → {} risk of {}", factor_name, direction(effect, "higher", "lower"), outcome_name),
+            "PublicHealth".into(),
+            "PublicHealth".into(),
+            evidence,
+            effect,
+        )
+    }
+
+    /// Host factor strengthening (positive `effect`) or weakening (negative)
+    /// the response to a treatment
+    pub fn host_factor_modifies_treatment_response(
+        factor_id: Uuid,
+        treatment_id: Uuid,
+        factor_name: &str,
+        treatment_name: &str,
+        evidence: Vec<String>,
+        effect: f32,
+    ) -> GraphEdge {
+        GraphEdge::new_correlative(
+            factor_id,
+            treatment_id,
+            format!("{} → {} response to {}", factor_name, direction(effect, "stronger", "weaker"), treatment_name),
+            "PublicHealth".into(),
+            "Treatment".into(),
+            evidence,
+            effect,
+        )
+    }
+
+    pub fn policy_to_transmission(
+        policy_id: Uuid,
+        outcome_id: Uuid,
+        policy_name: &str,
+        evidence: Vec<String>,
+        correlation: f32,
+    ) -> GraphEdge {
+        GraphEdge::new_correlative(
+            policy_id,
+            outcome_id,
+            format!("{} → reduced transmission", policy_name),
+            "PublicHealth".into(),
+            "Virology".into(),
+            evidence,
+            correlation,
+        )
+    }
+}
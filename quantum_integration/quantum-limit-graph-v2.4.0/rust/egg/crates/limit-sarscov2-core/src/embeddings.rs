@@ -0,0 +1,214 @@
+// limit-sarscov2-core/src/embeddings.rs
+// Structural node embeddings: node2vec random walks + skip-gram with negative sampling
+
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use std::collections::HashMap;
+
+use crate::{
+    multi_intent_graph::MultiIntentGraph,
+    quantum::SplitMix64,
+};
+
+/// Size of the table negative samples are drawn from
+const NEGATIVE_TABLE_SIZE: usize = 100_000;
+
+/// Walk and training parameters. With `return_param` and `in_out_param` at 1
+/// the walks are uniform over edge weights, i.e. DeepWalk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EmbeddingOptions {
+    pub dimensions: usize,
+    pub walks_per_node: usize,
+    pub walk_length: usize,
+    pub window: usize,            // context nodes on each side of a walk position
+    pub negatives: usize,         // negative samples per context pair
+    pub epochs: usize,
+    pub learning_rate: f32,       // decays linearly to near zero over training
+    pub return_param: f32,        // node2vec p: higher makes walks less likely to step back
+    pub in_out_param: f32,        // node2vec q: higher keeps walks local (BFS-like), lower lets them roam
+    pub seed: u64,
+}
+
+impl Default for EmbeddingOptions {
+    fn default() -> Self {
+        Self {
+            dimensions: 32,
+            walks_per_node: 10,
+            walk_length: 20,
+            window: 5,
+            negatives: 5,
+            epochs: 1,
+            learning_rate: 0.025,
+            return_param: 1.0,
+            in_out_param: 1.0,
+            seed: 7,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddingReport {
+    pub embedded: usize,          // nodes given an embedding
+    pub isolated: usize,          // nodes without supporting edges; their embedding is cleared
+    pub dimensions: usize,
+    pub walks: usize,
+    pub pairs: usize,             // (node, context) pairs trained on
+}
+
+impl MultiIntentGraph {
+    /// Embed every node with a supporting edge from the graph's structure:
+    /// node2vec walks over supporting edges (weighted by net support, as
+    /// undirected links), then skip-gram with negative sampling over the
+    /// walks. Vectors are stored unit-length in `metadata.embedding`, so the
+    /// dot product of two is their cosine similarity. Deterministic for a
+    /// given seed and graph; recompute after structural changes.
+    pub fn compute_embeddings(&mut self, options: &EmbeddingOptions) -> EmbeddingReport {
+        let links = self.support_links();
+        let mut order: Vec<Uuid> = self.intent_nodes.keys().copied().collect();
+        order.sort();
+        let index: HashMap<Uuid, usize> = order.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+        let n = order.len();
+        let mut neighbors: Vec<Vec<(usize, f64)>> = vec![vec![]; n];
+        for ((a, b), edge) in &links {
+            let (i, j, w) = (index[a], index[b], self.net_support(edge) as f64);
+            neighbors[i].push((j, w));
+            neighbors[j].push((i, w));
+        }
+        for list in neighbors.iter_mut() {
+            list.sort_by_key(|(j, _)| *j);
+        }
+
+        let mut rng = SplitMix64(options.seed);
+        let walks = random_walks(&neighbors, options, &mut rng);
+        let (vectors, pairs) = skip_gram(n, &walks, options, &mut rng);
+
+        let mut report = EmbeddingReport { embedded: 0, isolated: 0, dimensions: options.dimensions, walks: walks.len(), pairs };
+        for (i, id) in order.iter().enumerate() {
+            let node = self.intent_nodes.get_mut(id).expect("node listed from the graph");
+            if neighbors[i].is_empty() || options.dimensions == 0 {
+                node.metadata.embedding = None;
+                report.isolated += 1;
+                continue;
+            }
+            let norm = vectors[i].iter().map(|x| x * x).sum::<f32>().sqrt();
+            node.metadata.embedding = Some(vectors[i].iter().map(|x| if norm > 0.0 { x / norm } else { 0.0 }).collect());
+            report.embedded += 1;
+        }
+        self.update_timestamp();
+        report
+    }
+
+    /// Cosine similarity of two nodes' embeddings; `None` unless both have one
+    pub fn embedding_similarity(&self, a: Uuid, b: Uuid) -> Option<f32> {
+        let a = self.intent_nodes.get(&a)?.metadata.embedding.as_ref()?;
+        let b = self.intent_nodes.get(&b)?.metadata.embedding.as_ref()?;
+        Some(cosine_similarity(a, b))
+    }
+}
+
+/// Cosine similarity; 0 for mismatched lengths or zero vectors
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norms = a.iter().map(|x| x * x).sum::<f32>().sqrt() * b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norms > 0.0 { dot / norms } else { 0.0 }
+}
+
+/// Second-order (node2vec) walks from every non-isolated node, in node order,
+/// `walks_per_node` rounds
+fn random_walks(neighbors: &[Vec<(usize, f64)>], options: &EmbeddingOptions, rng: &mut SplitMix64) -> Vec<Vec<usize>> {
+    let p = options.return_param.max(1e-6) as f64;
+    let q = options.in_out_param.max(1e-6) as f64;
+    let adjacent = |a: usize, b: usize| neighbors[a].binary_search_by_key(&b, |(j, _)| *j).is_ok();
+    let mut walks = vec![];
+    for _ in 0..options.walks_per_node {
+        for start in (0..neighbors.len()).filter(|i| !neighbors[*i].is_empty()) {
+            let mut walk = vec![start];
+            while walk.len() < options.walk_length.max(1) {
+                let current = walk[walk.len() - 1];
+                let previous = walk.len().checked_sub(2).map(|i| walk[i]);
+                let weights: Vec<f64> = neighbors[current].iter()
+                    .map(|(next, w)| match previous {
+                        None => *w,
+                        Some(prev) if prev == *next => w / p,
+                        Some(prev) if adjacent(prev, *next) => *w,
+                        Some(_) => w / q,
+                    })
+                    .collect();
+                let mut u = rng.next_f64() * weights.iter().sum::<f64>();
+                let pick = weights.iter().position(|w| { u -= w; u < 0.0 }).unwrap_or(weights.len() - 1);
+                walk.push(neighbors[current][pick].0);
+            }
+            walks.push(walk);
+        }
+    }
+    walks
+}
+
+/// Skip-gram with negative sampling over the walks; returns the input
+/// vectors and the number of (node, context) pairs trained
+fn skip_gram(n: usize, walks: &[Vec<usize>], options: &EmbeddingOptions, rng: &mut SplitMix64) -> (Vec<Vec<f32>>, usize) {
+    let d = options.dimensions;
+    let mut input: Vec<Vec<f32>> = (0..n).map(|_| (0..d).map(|_| (rng.next_f64() as f32 - 0.5) / d.max(1) as f32).collect()).collect();
+    let mut output: Vec<Vec<f32>> = vec![vec![0.0; d]; n];
+    if d == 0 || walks.is_empty() {
+        return (input, 0);
+    }
+
+    // Negatives follow walk frequency^0.75, as in word2vec
+    let mut frequency = vec![0usize; n];
+    walks.iter().flatten().for_each(|i| frequency[*i] += 1);
+    let weights: Vec<f64> = frequency.iter().map(|f| (*f as f64).powf(0.75)).collect();
+    let total: f64 = weights.iter().sum();
+    let mut table = Vec::with_capacity(NEGATIVE_TABLE_SIZE);
+    for (i, w) in weights.iter().enumerate() {
+        let slots = (w / total * NEGATIVE_TABLE_SIZE as f64).ceil() as usize;
+        table.extend(std::iter::repeat_n(i, slots));
+    }
+
+    let steps = (options.epochs.max(1) * walks.iter().map(Vec::len).sum::<usize>()) as f32;
+    let mut step = 0.0f32;
+    let mut pairs = 0;
+    let mut gradient = vec![0.0f32; d];
+    for _ in 0..options.epochs.max(1) {
+        for walk in walks {
+            for (pos, &center) in walk.iter().enumerate() {
+                let rate = options.learning_rate * (1.0 - step / steps).max(1e-4);
+                step += 1.0;
+                let lo = pos.saturating_sub(options.window);
+                let hi = (pos + options.window + 1).min(walk.len());
+                for (ctx_pos, &context) in walk.iter().enumerate().take(hi).skip(lo) {
+                    if ctx_pos == pos {
+                        continue;
+                    }
+                    pairs += 1;
+                    gradient.iter_mut().for_each(|g| *g = 0.0);
+                    for s in 0..=options.negatives {
+                        let (target, label) = if s == 0 {
+                            (context, 1.0)
+                        } else {
+                            let t = table[(rng.next_f64() * table.len() as f64) as usize % table.len()];
+                            if t == context { continue; }
+                            (t, 0.0)
+                        };
+                        let dot: f32 = input[center].iter().zip(&output[target]).map(|(a, b)| a * b).sum();
+                        let g = (label - sigmoid(dot)) * rate;
+                        for k in 0..d {
+                            gradient[k] += g * output[target][k];
+                            output[target][k] += g * input[center][k];
+                        }
+                    }
+                    input[center].iter_mut().zip(&gradient).for_each(|(x, g)| *x += g);
+                }
+            }
+        }
+    }
+    (input, pairs)
+}
+
+fn sigmoid(x: f32) -> f32 {
+    1.0 / (1.0 + (-x.clamp(-6.0, 6.0)).exp())
+}
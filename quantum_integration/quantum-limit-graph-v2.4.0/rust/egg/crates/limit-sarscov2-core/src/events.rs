@@ -0,0 +1,185 @@
+// limit-sarscov2-core/src/events.rs
+// Graph mutations as events: applying a graph's events in order rebuilds it
+
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+
+use crate::{
+    confidence::ConfidenceModel,
+    embeddings::EmbeddingOptions,
+    edges::{EdgeTypeError, GraphEdge, HyperEdge},
+    multi_intent_graph::{DanglingEdges, HypothesisPath, IntentNode, MultiIntentGraph},
+    propagation::PropagationPolicy,
+    provenance::GovernanceTag,
+    rd::RDCurve,
+    serendipity_trace::SerendipityTrace,
+    synonyms::SynonymError,
+};
+
+/// One mutation of a multi-intent graph. Events carry everything needed to
+/// repeat the mutation, so replay does not depend on the clock or on IDs
+/// generated at the time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GraphEvent {
+    GraphCreated { graph: Box<MultiIntentGraph> },   // whole graph as first stored; replay starts here
+    NodeAdded { node: IntentNode },
+    EdgeAdded { edge: GraphEdge },
+    HyperedgeAdded { hyperedge: HyperEdge },
+    NodeRemoved { node_id: Uuid, dangling: DanglingEdges },
+    EdgeRemoved { edge_id: Uuid },
+    NodesMerged { kept: Uuid, merged: Uuid },
+    TraceRecorded { trace: SerendipityTrace },
+    HypothesisPathAdded { path: HypothesisPath },
+    TagAttached { subject_id: Uuid, tag: GovernanceTag },
+    TagDetached { subject_id: Uuid, tag_id: Uuid },
+    EvidenceDated { reference: String, date: String },
+    ConfidenceRecomputed { model: ConfidenceModel, at: DateTime<Utc> },
+    ConfidencePropagated { policy: Option<PropagationPolicy>, full: bool },
+    RdCurveSet { intent: String, curve: RDCurve },
+    EdgeTypeRegistered { name: String, description: String },
+    SynonymsRegistered { canonical: String, aliases: Vec<String> },
+    SynonymsRemoved { canonical: String },
+    EmbeddingsComputed { options: EmbeddingOptions },
+}
+
+/// An event that does not fit the graph it is applied to
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum EventError {
+    #[error("{event}: {id} is not in the graph")]
+    Missing { event: &'static str, id: Uuid },
+    #[error("{event}: unreadable date {date:?} for {reference}")]
+    EvidenceDate { event: &'static str, reference: String, date: String },
+    #[error("{event}: {source}")]
+    EdgeType { event: &'static str, source: EdgeTypeError },
+    #[error("{event}: {source}")]
+    Synonym { event: &'static str, source: SynonymError },
+    #[error("history of graph {0} does not start with graph_created")]
+    NoGraph(Uuid),
+}
+
+impl GraphEvent {
+    /// Serialized `type` tag ("node_added")
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::GraphCreated { .. } => "graph_created",
+            Self::NodeAdded { .. } => "node_added",
+            Self::EdgeAdded { .. } => "edge_added",
+            Self::HyperedgeAdded { .. } => "hyperedge_added",
+            Self::NodeRemoved { .. } => "node_removed",
+            Self::EdgeRemoved { .. } => "edge_removed",
+            Self::NodesMerged { .. } => "nodes_merged",
+            Self::TraceRecorded { .. } => "trace_recorded",
+            Self::HypothesisPathAdded { .. } => "hypothesis_path_added",
+            Self::TagAttached { .. } => "tag_attached",
+            Self::TagDetached { .. } => "tag_detached",
+            Self::EvidenceDated { .. } => "evidence_dated",
+            Self::ConfidenceRecomputed { .. } => "confidence_recomputed",
+            Self::ConfidencePropagated { .. } => "confidence_propagated",
+            Self::RdCurveSet { .. } => "rd_curve_set",
+            Self::EdgeTypeRegistered { .. } => "edge_type_registered",
+            Self::SynonymsRegistered { .. } => "synonyms_registered",
+            Self::SynonymsRemoved { .. } => "synonyms_removed",
+            Self::EmbeddingsComputed { .. } => "embeddings_computed",
+        }
+    }
+
+    /// The `*_added` event for node, edge or hyperedge `id` as it now is in
+    /// `graph`, e.g. after a bulk import; `None` if it is none of them
+    pub fn added(graph: &MultiIntentGraph, id: Uuid) -> Option<Self> {
+        if let Some(node) = graph.intent_nodes.get(&id) {
+            return Some(Self::NodeAdded { node: node.clone() });
+        }
+        if let Some(edge) = graph.edges.get(&id) {
+            return Some(Self::EdgeAdded { edge: edge.clone() });
+        }
+        graph.hyperedges.get(&id).map(|h| Self::HyperedgeAdded { hyperedge: h.clone() })
+    }
+
+    /// Rebuild a graph from its history, oldest first. The first event must
+    /// be `GraphCreated`; a later one starts over from its graph.
+    pub fn replay<'a>(graph_id: Uuid, events: impl IntoIterator<Item = &'a GraphEvent>) -> Result<MultiIntentGraph, EventError> {
+        let mut events = events.into_iter();
+        let mut graph = match events.next() {
+            Some(Self::GraphCreated { graph }) => (**graph).clone(),
+            _ => return Err(EventError::NoGraph(graph_id)),
+        };
+        for event in events {
+            graph.apply(event)?;
+        }
+        Ok(graph)
+    }
+}
+
+impl MultiIntentGraph {
+    /// Repeat the mutation `event` describes. `GraphCreated` replaces the
+    /// whole graph. Apart from `metadata.last_updated`, applying the events
+    /// recorded for a graph's mutations to its earlier state reproduces it.
+    pub fn apply(&mut self, event: &GraphEvent) -> Result<(), EventError> {
+        let missing = |id: Uuid| EventError::Missing { event: event.name(), id };
+        match event {
+            GraphEvent::GraphCreated { graph } => *self = (**graph).clone(),
+            GraphEvent::NodeAdded { node } => self.add_node(node.clone()),
+            GraphEvent::EdgeAdded { edge } => self.add_edge(edge.clone()),
+            GraphEvent::HyperedgeAdded { hyperedge } => self.add_hyperedge(hyperedge.clone()),
+            GraphEvent::NodeRemoved { node_id, dangling } => {
+                self.remove_node(*node_id, *dangling).ok_or(missing(*node_id))?;
+            }
+            GraphEvent::EdgeRemoved { edge_id } => {
+                self.remove_edge(*edge_id).ok_or(missing(*edge_id))?;
+            }
+            GraphEvent::NodesMerged { kept, merged } => {
+                self.merge_nodes(*kept, *merged).ok_or(missing(*merged))?;
+            }
+            GraphEvent::TraceRecorded { trace } => self.add_trace(trace.clone()),
+            GraphEvent::HypothesisPathAdded { path } => {
+                self.add_hypothesis_path(path.clone());
+            }
+            GraphEvent::TagAttached { subject_id, tag } => {
+                if !self.attach_tag(*subject_id, tag.clone()) {
+                    return Err(missing(*subject_id));
+                }
+            }
+            GraphEvent::TagDetached { subject_id, tag_id } => {
+                self.detach_tag(*subject_id, *tag_id).ok_or(missing(*tag_id))?;
+            }
+            GraphEvent::EvidenceDated { reference, date } => {
+                self.set_evidence_date(reference, date).ok_or_else(|| EventError::EvidenceDate {
+                    event: event.name(),
+                    reference: reference.clone(),
+                    date: date.clone(),
+                })?;
+            }
+            GraphEvent::ConfidenceRecomputed { model, at } => {
+                self.recompute_confidence(model, *at);
+            }
+            GraphEvent::ConfidencePropagated { policy, full } => {
+                if let Some(policy) = policy {
+                    self.propagation = policy.clone();
+                }
+                if policy.is_some() || *full {
+                    self.invalidate_all_confidence();
+                }
+                self.propagate_confidence();
+            }
+            GraphEvent::RdCurveSet { intent, curve } => self.add_rd_curve(intent.clone(), curve.clone()),
+            GraphEvent::EdgeTypeRegistered { name, description } => {
+                self.edge_vocabulary.register(name, description.clone())
+                    .map_err(|source| EventError::EdgeType { event: event.name(), source })?;
+            }
+            GraphEvent::SynonymsRegistered { canonical, aliases } => {
+                self.synonyms.register(canonical, aliases)
+                    .map_err(|source| EventError::Synonym { event: event.name(), source })?;
+            }
+            GraphEvent::SynonymsRemoved { canonical } => {
+                self.synonyms.remove(canonical);
+            }
+            GraphEvent::EmbeddingsComputed { options } => {
+                self.compute_embeddings(options);
+            }
+        }
+        Ok(())
+    }
+}
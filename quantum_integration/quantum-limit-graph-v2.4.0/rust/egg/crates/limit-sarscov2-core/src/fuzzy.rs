@@ -0,0 +1,161 @@
+// limit-sarscov2-core/src/fuzzy.rs
+// Typo-tolerant name matching: edit distance, Jaro-Winkler and node lookup by approximate name
+
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+
+use crate::{
+    dedup::name_similarity,
+    domain::ResearchDomain,
+    multi_intent_graph::MultiIntentGraph,
+    synonyms::SynonymTable,
+};
+
+/// Lowest `name_similarity` at which a node resolves a name
+pub const MIN_NAME_SIMILARITY: f32 = 0.85;
+/// Token pairs scoring below this Jaro-Winkler similarity count as unrelated
+const MIN_TOKEN_SIMILARITY: f32 = 0.8;
+
+/// Edit distance counting insertions, deletions, substitutions and swaps of
+/// adjacent characters ("omicorn" → "omicron" is 1)
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    let mut rows = vec![(0..=b.len()).collect::<Vec<usize>>()];
+    for i in 1..=a.len() {
+        let mut row = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            row[j] = (rows[i - 1][j] + 1).min(row[j - 1] + 1).min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                row[j] = row[j].min(rows[i - 2][j - 2] + 1);
+            }
+        }
+        rows.push(row);
+    }
+    rows[a.len()][b.len()]
+}
+
+/// Jaro-Winkler similarity in 0..=1, favouring a shared prefix of up to 4 characters
+pub fn jaro_winkler(a: &str, b: &str) -> f32 {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let window = (a.len().max(b.len()) / 2).saturating_sub(1);
+    let mut b_used = vec![false; b.len()];
+    let mut a_matched = vec![];
+    for (i, ca) in a.iter().enumerate() {
+        let (lo, hi) = (i.saturating_sub(window), (i + window + 1).min(b.len()));
+        if let Some(j) = (lo..hi).find(|&j| !b_used[j] && b[j] == *ca) {
+            b_used[j] = true;
+            a_matched.push(*ca);
+        }
+    }
+    if a_matched.is_empty() {
+        return 0.0;
+    }
+    let b_matched = b.iter().zip(&b_used).filter(|(_, used)| **used).map(|(c, _)| c);
+    let transpositions = a_matched.iter().zip(b_matched).filter(|(x, y)| x != y).count() / 2;
+    let m = a_matched.len() as f32;
+    let jaro = (m / a.len() as f32 + m / b.len() as f32 + (m - transpositions as f32) / m) / 3.0;
+    let prefix = a.iter().zip(&b).take(4).take_while(|(x, y)| x == y).count() as f32;
+    jaro + prefix * 0.1 * (1.0 - jaro)
+}
+
+/// Edits tolerated in a word of `len` characters: none up to 3, one up to 7, then two
+pub fn max_edits(len: usize) -> usize {
+    match len {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    }
+}
+
+/// Whether `word` is `term` give or take `max_edits` typos. Words with digits
+/// (lineages, positions) must match exactly, since a changed digit names
+/// something else.
+pub fn is_near(term: &str, word: &str) -> bool {
+    if term == word {
+        return true;
+    }
+    let len = term.chars().count();
+    if term.chars().chain(word.chars()).any(|c| c.is_ascii_digit()) || len.abs_diff(word.chars().count()) > max_edits(len) {
+        return false;
+    }
+    levenshtein(term, word) <= max_edits(len)
+}
+
+/// Lowercase tokens with punctuation inside them dropped ("Omicron BA.5" → ["omicron", "ba5"])
+pub fn name_tokens(name: &str) -> Vec<String> {
+    name.split(|c: char| c.is_whitespace() || ",;/()+".contains(c))
+        .map(|t| t.chars().filter(|c| c.is_alphanumeric()).collect::<String>().to_lowercase())
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+/// Similarity of two names in 0..=1, tolerant of case, punctuation and typos.
+/// Each token of the shorter name is paired with its closest token in the
+/// longer one by Jaro-Winkler (tokens with digits only pair exactly); the mean
+/// is discounted by 10% when the names differ in length, as for containment.
+pub fn fuzzy_similarity(a: &str, b: &str) -> f32 {
+    let (ta, tb) = (name_tokens(a), name_tokens(b));
+    if ta.is_empty() || tb.is_empty() {
+        return 0.0;
+    }
+    if ta.concat() == tb.concat() {
+        return 1.0;
+    }
+    let (short, long) = if ta.len() <= tb.len() { (&ta, &tb) } else { (&tb, &ta) };
+    let pair = |x: &String, y: &String| {
+        let score = if x.chars().chain(y.chars()).any(|c| c.is_ascii_digit()) {
+            if x == y { 1.0 } else { 0.0 }
+        } else {
+            jaro_winkler(x, y)
+        };
+        if score < MIN_TOKEN_SIMILARITY { 0.0 } else { score }
+    };
+    let mean = short.iter()
+        .map(|x| long.iter().map(|y| pair(x, y)).fold(0.0, f32::max))
+        .sum::<f32>() / short.len() as f32;
+    if short.len() < long.len() { mean * 0.9 } else { mean }
+}
+
+/// A node whose label approximately matches a name
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NameMatch {
+    pub node_id: Uuid,
+    pub domain: ResearchDomain,
+    pub label: String,
+    pub score: f32,                       // `name_similarity` to the looked-up name or its best synonym
+}
+
+/// Candidates scoring at least `MIN_NAME_SIMILARITY` against `name` or one of
+/// its `synonyms`, best first, ties by ID; `domain` restricts the candidates
+/// when given
+pub fn resolve_name<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = (Uuid, &'a ResearchDomain, &'a str)>,
+    domain: Option<&ResearchDomain>,
+    synonyms: &SynonymTable,
+) -> Vec<NameMatch> {
+    let names: Vec<String> = std::iter::once(name.to_string()).chain(synonyms.equivalents(name)).collect();
+    let similarity = |label: &str| names.iter().map(|n| name_similarity(n, label)).fold(0.0, f32::max);
+    let mut matches: Vec<NameMatch> = candidates.into_iter()
+        .filter(|(_, d, _)| domain.is_none_or(|want| std::mem::discriminant(want) == std::mem::discriminant(*d)))
+        .map(|(node_id, d, label)| NameMatch { node_id, domain: d.clone(), label: label.to_string(), score: similarity(label) })
+        .filter(|m| m.score >= MIN_NAME_SIMILARITY)
+        .collect();
+    matches.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.node_id.cmp(&b.node_id)));
+    matches
+}
+
+impl MultiIntentGraph {
+    /// Nodes whose label approximately matches `name` or one of its synonyms
+    /// ("Omicorn BA5" finds "Omicron BA.5", "Omicron" finds "B.1.1.529"), best first
+    pub fn resolve_name(&self, name: &str, domain: Option<&ResearchDomain>) -> Vec<NameMatch> {
+        resolve_name(name, self.intent_nodes.values().map(|n| (n.id, &n.domain, n.label())), domain, &self.synonyms)
+    }
+}
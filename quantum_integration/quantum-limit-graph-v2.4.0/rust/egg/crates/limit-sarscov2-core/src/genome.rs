@@ -0,0 +1,573 @@
+// limit-sarscov2-core/src/genome.rs
+// FASTA parsing, alignment against Wuhan-Hu-1, and nucleotide / amino-acid mutation calling
+
+use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    mutation::{Mutation, MutationKind, GENOME_LENGTH, NUC},
+    nodes::GenomicsNode,
+};
+
+/// GenBank accession of the reference the gene coordinates refer to
+pub const WUHAN_HU_1_ACCESSION: &str = "MN908947.3";
+
+/// Coding sequences of Wuhan-Hu-1 (1-based, inclusive, stop codon included)
+pub const WUHAN_HU_1_GENES: &[(&str, u32, u32)] = &[
+    ("ORF1a", 266, 13_483),
+    ("ORF1b", 13_468, 21_555),      // after the -1 ribosomal frameshift
+    ("S", 21_563, 25_384),
+    ("ORF3a", 25_393, 26_220),
+    ("E", 26_245, 26_472),
+    ("M", 26_523, 27_191),
+    ("ORF6", 27_202, 27_387),
+    ("ORF7a", 27_394, 27_759),
+    ("ORF7b", 27_756, 27_887),
+    ("ORF8", 27_894, 28_259),
+    ("N", 28_274, 29_533),
+    ("ORF9b", 28_284, 28_577),
+    ("ORF10", 29_558, 29_674),
+];
+
+#[derive(Debug, Error, PartialEq)]
+pub enum GenomeError {
+    #[error("no FASTA records found")]
+    EmptyFasta,
+    #[error("reference is {found} nt, expected {expected} nt ({accession})", accession = WUHAN_HU_1_ACCESSION)]
+    ReferenceLength { expected: u32, found: usize },
+    #[error("invalid character '{ch}' in sequence {record}")]
+    InvalidCharacter { record: String, ch: char },
+    #[error("sequence {0} could not be placed on the reference")]
+    Unplaceable(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FastaRecord {
+    pub id: String,
+    pub description: String,
+    pub sequence: String,         // uppercase, U → T, alignment gaps removed
+}
+
+/// Parse FASTA text. Characters other than IUPAC nucleotide codes are rejected.
+pub fn parse_fasta(text: &str) -> Result<Vec<FastaRecord>, GenomeError> {
+    let mut records: Vec<FastaRecord> = vec![];
+    for line in text.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with(';')) {
+        if let Some(header) = line.strip_prefix('>') {
+            let (id, description) = header.split_once(char::is_whitespace).unwrap_or((header, ""));
+            records.push(FastaRecord { id: id.into(), description: description.trim().into(), sequence: String::new() });
+            continue;
+        }
+        let Some(record) = records.last_mut() else {
+            return Err(GenomeError::EmptyFasta);
+        };
+        for c in line.chars().filter(|c| !c.is_whitespace() && *c != '-' && *c != '.') {
+            let c = match c.to_ascii_uppercase() {
+                'U' => 'T',
+                c @ ('A' | 'C' | 'G' | 'T' | 'N' | 'R' | 'Y' | 'K' | 'M' | 'S' | 'W' | 'B' | 'D' | 'H' | 'V') => c,
+                _ => return Err(GenomeError::InvalidCharacter { record: record.id.clone(), ch: c }),
+            };
+            record.sequence.push(c);
+        }
+    }
+    if records.is_empty() {
+        return Err(GenomeError::EmptyFasta);
+    }
+    Ok(records)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeneAnnotation {
+    pub name: String,
+    pub start: u32,
+    pub end: u32,
+}
+
+/// Reference genome with its gene annotation
+#[derive(Debug, Clone)]
+pub struct ReferenceGenome {
+    pub id: String,
+    sequence: Vec<u8>,
+    pub genes: Vec<GeneAnnotation>,
+    kmers: HashMap<Vec<u8>, usize>,   // unique ANCHOR_K-mers → 0-based position
+}
+
+impl ReferenceGenome {
+    /// Wuhan-Hu-1 from its sequence (e.g. the MN908947.3 / NC_045512.2 FASTA)
+    pub fn wuhan_hu_1(record: &FastaRecord) -> Result<Self, GenomeError> {
+        if record.sequence.len() != GENOME_LENGTH as usize {
+            return Err(GenomeError::ReferenceLength { expected: GENOME_LENGTH, found: record.sequence.len() });
+        }
+        Ok(Self::new(&record.id, &record.sequence, WUHAN_HU_1_GENES.iter()
+            .map(|(name, start, end)| GeneAnnotation { name: name.to_string(), start: *start, end: *end })
+            .collect()))
+    }
+
+    /// Parse the first record of a FASTA file as Wuhan-Hu-1
+    pub fn from_fasta(text: &str) -> Result<Self, GenomeError> {
+        let records = parse_fasta(text)?;
+        Self::wuhan_hu_1(&records[0])
+    }
+
+    pub fn new(id: &str, sequence: &str, genes: Vec<GeneAnnotation>) -> Self {
+        let sequence = sequence.as_bytes().to_vec();
+        let mut kmers: HashMap<Vec<u8>, usize> = HashMap::new();
+        let mut repeated = vec![];
+        for (i, w) in sequence.windows(ANCHOR_K).enumerate() {
+            if kmers.insert(w.to_vec(), i).is_some() {
+                repeated.push(w.to_vec());
+            }
+        }
+        for w in repeated {
+            kmers.remove(&w);
+        }
+        Self { id: id.into(), sequence, genes, kmers }
+    }
+
+    pub fn sequence(&self) -> &str {
+        std::str::from_utf8(&self.sequence).unwrap_or_default()
+    }
+
+    /// Diagonal (reference position - query position) agreed on by most query k-mers
+    fn place(&self, query: &[u8]) -> Option<isize> {
+        let mut offsets: Vec<isize> = query.windows(ANCHOR_K)
+            .enumerate()
+            .step_by(ANCHOR_STEP)
+            .filter_map(|(i, w)| self.kmers.get(w).map(|&r| r as isize - i as isize))
+            .collect();
+        if offsets.is_empty() {
+            return None;
+        }
+        offsets.sort_unstable();
+        Some(offsets[offsets.len() / 2])
+    }
+}
+
+const ANCHOR_K: usize = 21;
+const ANCHOR_STEP: usize = 50;
+
+/// Nucleotide and amino-acid mutations of one sequence relative to the reference
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MutationCalls {
+    pub sequence_id: String,
+    pub nucleotide: Vec<Mutation>,
+    pub amino_acid: Vec<Mutation>,
+    pub aligned_range: (u32, u32),     // reference coordinates covered by the sequence
+    pub missing: Vec<(u32, u32)>,      // N / ambiguous runs inside the aligned range
+    pub coverage: f32,                 // share of the reference with a called base
+    pub frameshifts: Vec<String>,      // genes whose amino acids were not called
+    pub alignment_score: i32,
+}
+
+impl MutationCalls {
+    /// Genomics node carrying the amino-acid calls
+    pub fn to_genomics_node(&self, variant: &str) -> GenomicsNode {
+        GenomicsNode {
+            id: Uuid::new_v4(),
+            variant: variant.into(),
+            mutations: self.amino_acid.clone(),
+            unparsed_mutations: vec![],
+        }
+    }
+}
+
+/// Aligns sequences to a reference and calls mutations.
+///
+/// Genomes (or fragments such as a spike amplicon) are placed on the reference
+/// with k-mer anchors, aligned with a banded affine-gap alignment, and each
+/// annotated gene is then translated and re-aligned at the protein level so
+/// deletions that straddle codon boundaries come out as residue changes.
+#[derive(Debug, Clone)]
+pub struct MutationCaller {
+    pub reference: ReferenceGenome,
+    pub band: usize,                   // max net indel length (nt) the alignment tolerates
+}
+
+impl MutationCaller {
+    pub fn new(reference: ReferenceGenome) -> Self {
+        Self { reference, band: 300 }
+    }
+
+    pub fn call(&self, record: &FastaRecord) -> Result<MutationCalls, GenomeError> {
+        let query = record.sequence.trim_matches('N').as_bytes();
+        let reference = &self.reference.sequence;
+        let diagonal = self.reference.place(query).ok_or_else(|| GenomeError::Unplaceable(record.id.clone()))?;
+        let aln = align(reference, query, &NUCLEOTIDE_SCORING, diagonal, self.band, true)
+            .ok_or_else(|| GenomeError::Unplaceable(record.id.clone()))?;
+
+        // Query base aligned to each reference position (None = not covered) and insertions after it
+        let mut aligned: Vec<Option<u8>> = vec![None; reference.len() + 1];
+        let mut inserted: HashMap<usize, Vec<u8>> = HashMap::new();
+        let mut pos = aln.ref_start;  // 1-based position of the last reference base consumed
+        for &(r, q) in &aln.pairs {
+            if r == GAP {
+                inserted.entry(pos).or_default().push(q);
+            } else {
+                pos += 1;
+                aligned[pos] = Some(q);
+            }
+        }
+        let range = (aln.ref_start as u32 + 1, pos as u32);
+
+        let nucleotide = call_nucleotides(reference, &aligned, &inserted, range);
+        let missing = missing_runs(&aligned, range);
+        let called = aligned.iter().filter(|b| b.is_some_and(is_base)).count();
+
+        let mut amino_acid = vec![];
+        let mut frameshifts = vec![];
+        for gene in &self.reference.genes {
+            match call_gene(reference, &aligned, &inserted, gene) {
+                GeneCalls::Uncovered => {}
+                GeneCalls::Frameshift => frameshifts.push(gene.name.clone()),
+                GeneCalls::Called(mutations) => amino_acid.extend(mutations),
+            }
+        }
+
+        Ok(MutationCalls {
+            sequence_id: record.id.clone(),
+            nucleotide,
+            amino_acid,
+            aligned_range: range,
+            missing,
+            coverage: called as f32 / reference.len() as f32,
+            frameshifts,
+            alignment_score: aln.score,
+        })
+    }
+
+    /// Call every record in a FASTA file
+    pub fn call_fasta(&self, text: &str) -> Result<Vec<Result<MutationCalls, GenomeError>>, GenomeError> {
+        Ok(parse_fasta(text)?.iter().map(|r| self.call(r)).collect())
+    }
+}
+
+const GAP: u8 = b'-';
+
+fn is_base(b: u8) -> bool {
+    matches!(b, b'A' | b'C' | b'G' | b'T')
+}
+
+fn call_nucleotides(
+    reference: &[u8],
+    aligned: &[Option<u8>],
+    inserted: &HashMap<usize, Vec<u8>>,
+    (start, end): (u32, u32),
+) -> Vec<Mutation> {
+    let mut calls = vec![];
+    let mut pos = start as usize;
+    while pos <= end as usize {
+        let r = reference[pos - 1];
+        match aligned[pos] {
+            Some(GAP) => {
+                let first = pos;
+                while pos < end as usize && aligned[pos + 1] == Some(GAP) {
+                    pos += 1;
+                }
+                calls.push(Mutation {
+                    gene: Some(NUC.into()),
+                    position: first as u32,
+                    reference: String::from_utf8_lossy(&reference[first - 1..pos]).into_owned(),
+                    alternate: String::new(),
+                    kind: MutationKind::Deletion,
+                    end_position: (pos > first).then_some(pos as u32),
+                });
+            }
+            Some(q) if is_base(q) && q != r => calls.push(Mutation {
+                gene: Some(NUC.into()),
+                position: pos as u32,
+                reference: (r as char).to_string(),
+                alternate: (q as char).to_string(),
+                kind: MutationKind::Nucleotide,
+                end_position: None,
+            }),
+            _ => {}
+        }
+        if let Some(ins) = inserted.get(&pos).filter(|_| pos < end as usize) {
+            calls.push(Mutation {
+                gene: Some(NUC.into()),
+                position: pos as u32,
+                reference: String::new(),
+                alternate: String::from_utf8_lossy(ins).into_owned(),
+                kind: MutationKind::Insertion,
+                end_position: None,
+            });
+        }
+        pos += 1;
+    }
+    calls
+}
+
+fn missing_runs(aligned: &[Option<u8>], (start, end): (u32, u32)) -> Vec<(u32, u32)> {
+    let mut runs: Vec<(u32, u32)> = vec![];
+    for pos in start..=end {
+        let missing = aligned[pos as usize].is_some_and(|b| b != GAP && !is_base(b));
+        match runs.last_mut() {
+            Some(run) if missing && run.1 + 1 == pos => run.1 = pos,
+            _ if missing => runs.push((pos, pos)),
+            _ => {}
+        }
+    }
+    runs
+}
+
+enum GeneCalls {
+    Uncovered,
+    Frameshift,
+    Called(Vec<Mutation>),
+}
+
+fn call_gene(
+    reference: &[u8],
+    aligned: &[Option<u8>],
+    inserted: &HashMap<usize, Vec<u8>>,
+    gene: &GeneAnnotation,
+) -> GeneCalls {
+    let (start, end) = (gene.start as usize, gene.end as usize);
+    if (start..=end).all(|p| aligned[p].is_none()) {
+        return GeneCalls::Uncovered;
+    }
+
+    // Query CDS: uncovered or ambiguous bases become N so their codons translate to X
+    let mut cds = vec![];
+    for (p, base) in aligned.iter().enumerate().take(end + 1).skip(start) {
+        match *base {
+            Some(GAP) => {}
+            Some(b) if is_base(b) => cds.push(b),
+            _ => cds.push(b'N'),
+        }
+        if p < end {
+            if let Some(ins) = inserted.get(&p) {
+                cds.extend(ins.iter().map(|&b| if is_base(b) { b } else { b'N' }));
+            }
+        }
+    }
+    if cds.len() % 3 != 0 {
+        return GeneCalls::Frameshift;
+    }
+
+    let ref_protein = translate(&reference[start - 1..end]);
+    let query_protein = translate(&cds);
+    let diagonal = 0;
+    let band = ref_protein.len().abs_diff(query_protein.len()) + PROTEIN_BAND;
+    let Some(aln) = align(&ref_protein, &query_protein, &PROTEIN_SCORING, diagonal, band, false) else {
+        return GeneCalls::Frameshift;
+    };
+
+    // The terminal stop codon is not part of the protein's numbering
+    let protein_length = ref_protein.len() - 1;
+    let mut calls: Vec<Mutation> = vec![];
+    let mut pos = 0;
+    for &(r, q) in &aln.pairs {
+        if r == GAP {
+            if q != WILDCARD_AA && pos < protein_length {
+                match calls.last_mut() {
+                    Some(m) if m.kind == MutationKind::Insertion && m.position == pos as u32 => m.alternate.push(q as char),
+                    _ => calls.push(Mutation {
+                        gene: Some(gene.name.clone()),
+                        position: pos as u32,
+                        reference: String::new(),
+                        alternate: (q as char).to_string(),
+                        kind: MutationKind::Insertion,
+                        end_position: None,
+                    }),
+                }
+            }
+            continue;
+        }
+        pos += 1;
+        if pos > protein_length || q == r || q == WILDCARD_AA {
+            continue;
+        }
+        if q == GAP {
+            match calls.last_mut() {
+                Some(m) if m.kind == MutationKind::Deletion && m.end() + 1 == pos as u32 => {
+                    m.reference.push(r as char);
+                    m.end_position = Some(pos as u32);
+                }
+                _ => calls.push(Mutation {
+                    gene: Some(gene.name.clone()),
+                    position: pos as u32,
+                    reference: (r as char).to_string(),
+                    alternate: String::new(),
+                    kind: MutationKind::Deletion,
+                    end_position: None,
+                }),
+            }
+        } else {
+            calls.push(Mutation {
+                gene: Some(gene.name.clone()),
+                position: pos as u32,
+                reference: (r as char).to_string(),
+                alternate: (q as char).to_string(),
+                kind: MutationKind::Substitution,
+                end_position: None,
+            });
+        }
+    }
+    GeneCalls::Called(calls)
+}
+
+const WILDCARD_AA: u8 = b'X';
+const PROTEIN_BAND: usize = 50;
+
+/// Standard genetic code; codons containing anything but ACGT translate to X
+fn translate(cds: &[u8]) -> Vec<u8> {
+    const AA: &[u8; 64] = b"KNKNTTTTRSRSIIMIQHQHPPPPRRRRLLLLEDEDAAAAGGGGVVVV*Y*YSSSS*CWCLFLF";
+    cds.chunks_exact(3)
+        .map(|c| {
+            let index = c.iter().try_fold(0usize, |acc, b| {
+                let v = match b { b'A' => 0, b'C' => 1, b'G' => 2, b'T' => 3, _ => return None };
+                Some(acc * 4 + v)
+            });
+            index.map_or(WILDCARD_AA, |i| AA[i])
+        })
+        .collect()
+}
+
+struct Scoring {
+    matched: i32,
+    mismatch: i32,
+    gap_open: i32,       // cost of a gap's first position
+    gap_extend: i32,
+    wildcard: u8,        // scores 0 against anything
+}
+
+const NUCLEOTIDE_SCORING: Scoring = Scoring { matched: 3, mismatch: -2, gap_open: 10, gap_extend: 1, wildcard: b'N' };
+const PROTEIN_SCORING: Scoring = Scoring { matched: 4, mismatch: -2, gap_open: 8, gap_extend: 1, wildcard: WILDCARD_AA };
+
+impl Scoring {
+    fn score(&self, r: u8, q: u8) -> i32 {
+        // IUPAC ambiguity codes are as uninformative as N
+        let ambiguous = self.wildcard == b'N' && !is_base(q);
+        if q == self.wildcard || ambiguous {
+            0
+        } else if r == q {
+            self.matched
+        } else {
+            self.mismatch
+        }
+    }
+}
+
+struct Alignment {
+    ref_start: usize,         // reference bases skipped before the first aligned column
+    pairs: Vec<(u8, u8)>,     // (reference, query) columns, GAP for gaps
+    score: i32,
+}
+
+const NEG: i32 = i32::MIN / 4;
+const FROM_M: u8 = 0;
+const FROM_X: u8 = 1;
+const FROM_Y: u8 = 2;
+
+/// Banded global alignment with affine gaps (Gotoh). `X` consumes query only
+/// (insertion), `Y` consumes reference only (deletion). With `free_ref_ends`
+/// unaligned reference at either end costs nothing, so fragments align in place.
+fn align(reference: &[u8], query: &[u8], s: &Scoring, diagonal: isize, band: usize, free_ref_ends: bool) -> Option<Alignment> {
+    let (n, m) = (query.len(), reference.len());
+    let w = band as isize;
+    let width = 2 * band + 1;
+    let lo = |i: usize| (i as isize + diagonal - w).max(0) as usize;
+    let hi = |i: usize| (i as isize + diagonal + w).min(m as isize);
+    let k = |i: usize, j: usize| (j as isize - (i as isize + diagonal - w)) as usize;
+    if n == 0 || hi(0) < lo(0) as isize || hi(n) < lo(n) as isize {
+        return None;
+    }
+
+    // One byte per cell: predecessor state of M (bits 0-1), X (2-3) and Y (4-5)
+    let mut trace = vec![0u8; (n + 1) * width];
+    let (mut pm, mut px, mut py) = (vec![NEG; width], vec![NEG; width], vec![NEG; width]);
+
+    for j in lo(0)..=hi(0) as usize {
+        let kk = k(0, j);
+        if free_ref_ends || j == 0 {
+            pm[kk] = 0;
+        } else {
+            py[kk] = -(s.gap_open + (j as i32 - 1) * s.gap_extend);
+            trace[kk] = if j == 1 { FROM_M << 4 } else { FROM_Y << 4 };
+        }
+    }
+
+    for i in 1..=n {
+        let (mut cm, mut cx, mut cy) = (vec![NEG; width], vec![NEG; width], vec![NEG; width]);
+        if hi(i) < lo(i) as isize {
+            return None;
+        }
+        for j in lo(i)..=hi(i) as usize {
+            let kk = k(i, j);
+            let mut t = 0u8;
+            if j > 0 {
+                let (best, from) = best3(pm[kk], px[kk], py[kk]);
+                cm[kk] = best + s.score(reference[j - 1], query[i - 1]);
+                t |= from;
+            }
+            let (up_m, up_x, up_y) = if kk + 1 < width { (pm[kk + 1], px[kk + 1], py[kk + 1]) } else { (NEG, NEG, NEG) };
+            let (best, from) = best3(up_m - s.gap_open, up_x - s.gap_extend, up_y - s.gap_open);
+            cx[kk] = best;
+            t |= from << 2;
+            if j > 0 && kk > 0 {
+                let (best, from) = best3(cm[kk - 1] - s.gap_open, cx[kk - 1] - s.gap_open, cy[kk - 1] - s.gap_extend);
+                cy[kk] = best;
+                t |= from << 4;
+            }
+            trace[i * width + kk] = t;
+        }
+        (pm, px, py) = (cm, cx, cy);
+    }
+
+    // End cell: anywhere on the last row with free reference ends, else (n, m)
+    let ends: Vec<usize> = if free_ref_ends { (lo(n)..=hi(n) as usize).collect() } else { vec![m] };
+    let (mut score, mut j, mut state) = (NEG, 0, FROM_M);
+    for e in ends {
+        if hi(n) < e as isize || e < lo(n) {
+            continue;
+        }
+        let kk = k(n, e);
+        let candidates = if free_ref_ends { [(pm[kk], FROM_M), (px[kk], FROM_X), (NEG, FROM_Y)] } else { [(pm[kk], FROM_M), (px[kk], FROM_X), (py[kk], FROM_Y)] };
+        for (v, st) in candidates {
+            if v > score {
+                (score, j, state) = (v, e, st);
+            }
+        }
+    }
+    if score <= NEG / 2 {
+        return None;
+    }
+
+    let mut pairs = vec![];
+    let mut i = n;
+    while i > 0 || (!free_ref_ends && j > 0) {
+        let t = trace[i * width + k(i, j)];
+        match state {
+            FROM_M => {
+                pairs.push((reference[j - 1], query[i - 1]));
+                state = t & 3;
+                i -= 1;
+                j -= 1;
+            }
+            FROM_X => {
+                pairs.push((GAP, query[i - 1]));
+                state = (t >> 2) & 3;
+                i -= 1;
+            }
+            _ => {
+                pairs.push((reference[j - 1], GAP));
+                state = (t >> 4) & 3;
+                j -= 1;
+            }
+        }
+    }
+    pairs.reverse();
+    Some(Alignment { ref_start: j, pairs, score })
+}
+
+fn best3(m: i32, x: i32, y: i32) -> (i32, u8) {
+    if m >= x && m >= y {
+        (m, FROM_M)
+    } else if x >= y {
+        (x, FROM_X)
+    } else {
+        (y, FROM_Y)
+    }
+}
@@ -0,0 +1,99 @@
+// limit-sarscov2-core/src/geo.rs
+// Geographic scope of nodes: ISO 3166-1 country with an optional ISO 3166-2 subdivision
+
+use serde::{Serialize, Deserialize};
+use std::{fmt, str::FromStr};
+use thiserror::Error;
+
+/// Where a finding or policy applies, e.g. `ID` (Indonesia) or `US-CA`
+/// (California). Codes are kept upper-case.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "GeoScopeRepr", into = "String")]
+pub struct GeoScope {
+    pub country: String,              // ISO 3166-1 alpha-2: "ID", "US"
+    pub subdivision: Option<String>,  // ISO 3166-2 suffix: "JK", "CA"
+}
+
+/// A scope is written as its code ("US-CA") or as `{"country", "subdivision"}`
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum GeoScopeRepr {
+    Code(String),
+    Fields { country: String, #[serde(default)] subdivision: Option<String> },
+}
+
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum GeoScopeError {
+    #[error("country code {0:?} is not two letters (ISO 3166-1 alpha-2)")]
+    Country(String),
+    #[error("subdivision code {0:?} is not 1-3 letters or digits (ISO 3166-2)")]
+    Subdivision(String),
+}
+
+impl GeoScope {
+    pub fn country(code: &str) -> Result<Self, GeoScopeError> {
+        let country = code.trim().to_ascii_uppercase();
+        if country.len() != 2 || !country.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(GeoScopeError::Country(code.to_string()));
+        }
+        Ok(Self { country, subdivision: None })
+    }
+
+    pub fn subdivision(country: &str, subdivision: &str) -> Result<Self, GeoScopeError> {
+        let code = subdivision.trim().to_ascii_uppercase();
+        if code.is_empty() || code.len() > 3 || !code.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(GeoScopeError::Subdivision(subdivision.to_string()));
+        }
+        Ok(Self { subdivision: Some(code), ..Self::country(country)? })
+    }
+
+    /// The country this scope lies in
+    pub fn country_scope(&self) -> GeoScope {
+        Self { country: self.country.clone(), subdivision: None }
+    }
+
+    /// Whether this scope lies inside `other`: the same scope, or a
+    /// subdivision of the country `other` names
+    pub fn within(&self, other: &GeoScope) -> bool {
+        self.country == other.country && (other.subdivision.is_none() || self.subdivision == other.subdivision)
+    }
+}
+
+impl fmt::Display for GeoScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.subdivision {
+            Some(sub) => write!(f, "{}-{}", self.country, sub),
+            None => write!(f, "{}", self.country),
+        }
+    }
+}
+
+impl FromStr for GeoScope {
+    type Err = GeoScopeError;
+
+    /// "ID", "us-ca"
+    fn from_str(code: &str) -> Result<Self, GeoScopeError> {
+        match code.trim().split_once('-') {
+            Some((country, sub)) => Self::subdivision(country, sub),
+            None => Self::country(code),
+        }
+    }
+}
+
+impl TryFrom<GeoScopeRepr> for GeoScope {
+    type Error = GeoScopeError;
+
+    fn try_from(repr: GeoScopeRepr) -> Result<Self, GeoScopeError> {
+        match repr {
+            GeoScopeRepr::Code(code) => code.parse(),
+            GeoScopeRepr::Fields { country, subdivision: Some(sub) } => Self::subdivision(&country, &sub),
+            GeoScopeRepr::Fields { country, subdivision: None } => Self::country(&country),
+        }
+    }
+}
+
+impl From<GeoScope> for String {
+    fn from(scope: GeoScope) -> Self {
+        scope.to_string()
+    }
+}
@@ -0,0 +1,643 @@
+// limit-sarscov2-core/src/graph_query.rs
+// Cypher-lite pattern queries over a multi-intent graph: MATCH ... WHERE ... RETURN ...
+
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use thiserror::Error;
+
+use crate::{
+    edges::{EdgeType, GraphEdge},
+    multi_intent_graph::{IntentNode, MultiIntentGraph},
+};
+
+
+pub(crate) const DOMAINS: [&str; 5] = ["Virology", "Immunology", "Genomics", "Treatment", "PublicHealth"];
+
+/// Properties usable in WHERE, and whether they are numeric
+const NODE_PROPERTIES: [(&str, bool); 7] = [
+    ("confidence", true),
+    ("evidence_count", true),
+    ("intent", false),
+    ("domain", false),
+    ("label", false),
+    ("region", false),
+    ("country", false),
+];
+const EDGE_PROPERTIES: [(&str, bool); 6] = [
+    ("confidence", true),
+    ("weight", true),
+    ("label", false),
+    ("type", false),
+    ("source_domain", false),
+    ("target_domain", false),
+];
+
+#[derive(Debug, Error, PartialEq)]
+pub enum QueryError {
+    #[error("syntax error at offset {offset}: {message}")]
+    Syntax { offset: usize, message: String },
+    #[error("unknown node label '{0}' (expected one of Virology, Immunology, Genomics, Treatment, PublicHealth)")]
+    UnknownLabel(String),
+    #[error("unknown relationship type '{0}' (expected a built-in type such as Causal or a custom type name)")]
+    UnknownEdgeType(String),
+    #[error("variable '{0}' is not bound in MATCH")]
+    UnboundVariable(String),
+    #[error("variable '{0}' is bound twice in MATCH")]
+    DuplicateVariable(String),
+    #[error("'{property}' is not a property of {subject}")]
+    UnknownProperty { subject: String, property: String },
+    #[error("'{property}' is {expected}; cannot compare it with {op} {value}")]
+    TypeMismatch { property: String, expected: &'static str, op: &'static str, value: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodePattern {
+    pub var: Option<String>,
+    pub label: Option<String>,    // research domain
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    Out,       // -[]->
+    In,        // <-[]-
+    Either,    // -[]-
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RelPattern {
+    pub var: Option<String>,
+    pub types: Vec<EdgeType>,     // empty matches any type
+    pub direction: Direction,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl CmpOp {
+    fn symbol(&self) -> &'static str {
+        match self {
+            Self::Eq => "=",
+            Self::Ne => "!=",
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Literal {
+    Number(f64),
+    Text(String),
+}
+
+/// `[var.]property op literal`. Without a variable the condition applies to
+/// every edge of the match (or every node, for node-only properties or
+/// patterns without relationships).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Condition {
+    pub var: Option<String>,
+    pub property: String,
+    pub op: CmpOp,
+    pub value: Literal,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReturnClause {
+    Paths,
+    Nodes,
+    Edges,
+    Count,
+    Vars(Vec<String>),
+}
+
+/// A parsed query: `MATCH pattern [WHERE cond [AND cond]...] RETURN what [LIMIT n]`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphQuery {
+    pub nodes: Vec<NodePattern>,
+    pub rels: Vec<RelPattern>,    // rels[i] joins nodes[i] and nodes[i + 1]
+    pub conditions: Vec<Condition>,
+    pub returns: ReturnClause,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchedPath {
+    pub node_ids: Vec<Uuid>,
+    pub edge_ids: Vec<Uuid>,
+    pub labels: Vec<String>,      // node labels, for reading the path at a glance
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum QueryResult {
+    Paths(Vec<MatchedPath>),
+    Nodes(Vec<IntentNode>),
+    Edges(Vec<GraphEdge>),
+    Count(usize),
+    Rows(Vec<BTreeMap<String, Uuid>>),    // variable -> node or edge ID
+}
+
+impl GraphQuery {
+    pub fn parse(text: &str) -> Result<Self, QueryError> {
+        let tokens = tokenize(text)?;
+        let query = Parser { tokens, pos: 0, len: text.len() }.query()?;
+        query.validate()?;
+        Ok(query)
+    }
+
+    fn validate(&self) -> Result<(), QueryError> {
+        let mut bound: HashMap<&str, bool> = HashMap::new();   // var -> is edge
+        let vars = self.nodes.iter().map(|n| (&n.var, false)).chain(self.rels.iter().map(|r| (&r.var, true)));
+        for (var, is_edge) in vars {
+            if let Some(v) = var {
+                if bound.insert(v, is_edge).is_some() {
+                    return Err(QueryError::DuplicateVariable(v.clone()));
+                }
+            }
+        }
+        for label in self.nodes.iter().filter_map(|n| n.label.as_ref()) {
+            if !DOMAINS.iter().any(|d| d.eq_ignore_ascii_case(label)) {
+                return Err(QueryError::UnknownLabel(label.clone()));
+            }
+        }
+        for c in &self.conditions {
+            let (subject, properties): (String, &[(&str, bool)]) = match &c.var {
+                Some(v) => match bound.get(v.as_str()) {
+                    Some(true) => (format!("relationship '{}'", v), &EDGE_PROPERTIES),
+                    Some(false) => (format!("node '{}'", v), &NODE_PROPERTIES),
+                    None => return Err(QueryError::UnboundVariable(v.clone())),
+                },
+                None if self.unqualified_on_edges(&c.property) => ("relationships".into(), &EDGE_PROPERTIES),
+                None => ("nodes".into(), &NODE_PROPERTIES),
+            };
+            let Some(&(_, numeric)) = properties.iter().find(|(p, _)| *p == c.property) else {
+                return Err(QueryError::UnknownProperty { subject, property: c.property.clone() });
+            };
+            let ordered = !matches!(c.op, CmpOp::Eq | CmpOp::Ne);
+            let mismatch = match &c.value {
+                Literal::Number(_) => !numeric,
+                Literal::Text(_) => numeric || ordered,
+            };
+            if mismatch {
+                return Err(QueryError::TypeMismatch {
+                    property: c.property.clone(),
+                    expected: if numeric { "numeric" } else { "text" },
+                    op: c.op.symbol(),
+                    value: match &c.value {
+                        Literal::Number(n) => n.to_string(),
+                        Literal::Text(t) => format!("'{}'", t),
+                    },
+                });
+            }
+        }
+        if let ReturnClause::Vars(vars) = &self.returns {
+            if let Some(v) = vars.iter().find(|v| !bound.contains_key(v.as_str())) {
+                return Err(QueryError::UnboundVariable(v.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Unqualified properties go to edges when the pattern has any and edges have the property
+    fn unqualified_on_edges(&self, property: &str) -> bool {
+        !self.rels.is_empty() && EDGE_PROPERTIES.iter().any(|(p, _)| *p == property)
+    }
+
+    /// Every match of the pattern, as (node IDs, edge IDs). Matches are found
+    /// from start nodes in ID order; an edge is used at most once per match.
+    /// Refuting edges assert nothing and never match.
+    fn matches(&self, graph: &MultiIntentGraph) -> Vec<(Vec<Uuid>, Vec<Uuid>)> {
+        let mut edges: Vec<&GraphEdge> = graph.edges.values().filter(|e| !e.is_refuting()).collect();
+        edges.sort_by_key(|e| e.id);
+        let mut adjacency: HashMap<Uuid, Vec<(&GraphEdge, Uuid, Direction)>> = HashMap::new();
+        for e in edges {
+            adjacency.entry(e.source_id).or_default().push((e, e.target_id, Direction::Out));
+            if e.source_id != e.target_id {
+                adjacency.entry(e.target_id).or_default().push((e, e.source_id, Direction::In));
+            }
+        }
+
+        let mut starts: Vec<&IntentNode> = graph.intent_nodes.values().filter(|n| self.node_fits(0, n)).collect();
+        starts.sort_by_key(|n| n.id);
+        let mut found = vec![];
+        for start in starts {
+            let mut nodes = vec![start.id];
+            let mut path_edges = vec![];
+            self.extend(graph, &adjacency, &mut nodes, &mut path_edges, &mut found);
+        }
+        found
+    }
+
+    fn extend(
+        &self,
+        graph: &MultiIntentGraph,
+        adjacency: &HashMap<Uuid, Vec<(&GraphEdge, Uuid, Direction)>>,
+        nodes: &mut Vec<Uuid>,
+        path_edges: &mut Vec<Uuid>,
+        found: &mut Vec<(Vec<Uuid>, Vec<Uuid>)>,
+    ) {
+        let step = path_edges.len();
+        if step == self.rels.len() {
+            if self.conditions_hold(graph, nodes, path_edges) {
+                found.push((nodes.clone(), path_edges.clone()));
+            }
+            return;
+        }
+        let rel = &self.rels[step];
+        let here = *nodes.last().expect("path starts with a node");
+        for (edge, other, direction) in adjacency.get(&here).into_iter().flatten() {
+            let direction_ok = rel.direction == Direction::Either || rel.direction == *direction;
+            let type_ok = rel.types.is_empty() || rel.types.contains(&edge.edge_type);
+            let fits = graph.intent_nodes.get(other).is_some_and(|n| self.node_fits(step + 1, n));
+            if !direction_ok || !type_ok || !fits || path_edges.contains(&edge.id) {
+                continue;
+            }
+            nodes.push(*other);
+            path_edges.push(edge.id);
+            self.extend(graph, adjacency, nodes, path_edges, found);
+            nodes.pop();
+            path_edges.pop();
+        }
+    }
+
+    fn node_fits(&self, index: usize, node: &IntentNode) -> bool {
+        self.nodes[index].label.as_ref().is_none_or(|l| format!("{:?}", node.domain).eq_ignore_ascii_case(l))
+    }
+
+    fn conditions_hold(&self, graph: &MultiIntentGraph, nodes: &[Uuid], edges: &[Uuid]) -> bool {
+        self.conditions.iter().all(|c| {
+            let node = |id: &Uuid| Subject::Node(&graph.intent_nodes[id]);
+            let edge = |id: &Uuid| Subject::Edge(&graph.edges[id]);
+            let subjects: Vec<Subject> = match &c.var {
+                Some(v) => match self.nodes.iter().position(|n| n.var.as_ref() == Some(v)) {
+                    Some(i) => vec![node(&nodes[i])],
+                    None => self.rels.iter().position(|r| r.var.as_ref() == Some(v)).map(|i| edge(&edges[i])).into_iter().collect(),
+                },
+                None if self.unqualified_on_edges(&c.property) => edges.iter().map(edge).collect(),
+                None => nodes.iter().map(node).collect(),
+            };
+            subjects.iter().all(|s| s.satisfies(c))
+        })
+    }
+
+    /// Run the query against `graph`
+    pub fn execute(&self, graph: &MultiIntentGraph) -> QueryResult {
+        let mut matches = self.matches(graph);
+        if self.returns == ReturnClause::Count {
+            return QueryResult::Count(matches.len());
+        }
+        let limit = self.limit.unwrap_or(usize::MAX);
+        match &self.returns {
+            ReturnClause::Paths => {
+                matches.truncate(limit);
+                QueryResult::Paths(matches.into_iter()
+                    .map(|(node_ids, edge_ids)| MatchedPath {
+                        labels: node_ids.iter().map(|id| graph.intent_nodes[id].label().to_string()).collect(),
+                        node_ids,
+                        edge_ids,
+                    })
+                    .collect())
+            }
+            ReturnClause::Nodes => QueryResult::Nodes(
+                distinct(matches.iter().flat_map(|(n, _)| n), limit).into_iter().map(|id| graph.intent_nodes[&id].clone()).collect(),
+            ),
+            ReturnClause::Edges => QueryResult::Edges(
+                distinct(matches.iter().flat_map(|(_, e)| e), limit).into_iter().map(|id| graph.edges[&id].clone()).collect(),
+            ),
+            ReturnClause::Vars(vars) => {
+                let rows = matches.iter()
+                    .map(|(node_ids, edge_ids)| {
+                        vars.iter()
+                            .map(|v| {
+                                let id = match self.nodes.iter().position(|n| n.var.as_ref() == Some(v)) {
+                                    Some(i) => node_ids[i],
+                                    None => edge_ids[self.rels.iter().position(|r| r.var.as_ref() == Some(v)).expect("validated")],
+                                };
+                                (v.clone(), id)
+                            })
+                            .collect::<BTreeMap<_, _>>()
+                    })
+                    .collect::<Vec<_>>();
+                let mut seen = HashSet::new();
+                QueryResult::Rows(rows.into_iter().filter(|r| seen.insert(r.clone())).take(limit).collect())
+            }
+            ReturnClause::Count => unreachable!("handled above"),
+        }
+    }
+}
+
+impl MultiIntentGraph {
+    /// Parse and run a Cypher-lite query, e.g.
+    /// `MATCH (g:Genomics)-[:Causal]->(i:Immunology) WHERE confidence > 0.8 RETURN paths`
+    pub fn query(&self, text: &str) -> Result<QueryResult, QueryError> {
+        Ok(GraphQuery::parse(text)?.execute(self))
+    }
+}
+
+fn distinct<'a>(ids: impl Iterator<Item = &'a Uuid>, limit: usize) -> Vec<Uuid> {
+    let mut seen = HashSet::new();
+    ids.filter(|id| seen.insert(**id)).take(limit).copied().collect()
+}
+
+enum Subject<'a> {
+    Node(&'a IntentNode),
+    Edge(&'a GraphEdge),
+}
+
+impl Subject<'_> {
+    fn satisfies(&self, c: &Condition) -> bool {
+        let value = match (self, c.property.as_str()) {
+            (Self::Node(n), "confidence") => Literal::Number(n.metadata.confidence as f64),
+            (Self::Node(n), "evidence_count") => Literal::Number(n.metadata.evidence_count as f64),
+            (Self::Node(n), "intent") => Literal::Text(n.intent.clone()),
+            (Self::Node(n), "domain") => Literal::Text(format!("{:?}", n.domain)),
+            (Self::Node(n), "label") => Literal::Text(n.label().to_string()),
+            // Nodes without a region satisfy no condition on it, `!=` included
+            (Self::Node(n), "region") => match n.region() {
+                Some(r) => Literal::Text(r.to_string()),
+                None => return false,
+            },
+            (Self::Node(n), "country") => match n.region() {
+                Some(r) => Literal::Text(r.country.clone()),
+                None => return false,
+            },
+            (Self::Edge(e), "confidence") => Literal::Number(e.metadata.confidence as f64),
+            (Self::Edge(e), "weight") => Literal::Number(e.weight as f64),
+            (Self::Edge(e), "label") => Literal::Text(e.label.clone()),
+            (Self::Edge(e), "type") => Literal::Text(e.edge_type.name().to_string()),
+            (Self::Edge(e), "source_domain") => Literal::Text(e.metadata.source_domain.clone()),
+            (Self::Edge(e), "target_domain") => Literal::Text(e.metadata.target_domain.clone()),
+            _ => return false,
+        };
+        match (value, &c.value) {
+            // Graph values are f32; compare at that precision so `confidence >= 0.9` holds for 0.9
+            (Literal::Number(a), Literal::Number(b)) => {
+                let (a, b) = (a as f32, *b as f32);
+                match c.op {
+                    CmpOp::Eq => a == b,
+                    CmpOp::Ne => a != b,
+                    CmpOp::Lt => a < b,
+                    CmpOp::Le => a <= b,
+                    CmpOp::Gt => a > b,
+                    CmpOp::Ge => a >= b,
+                }
+            }
+            (Literal::Text(a), Literal::Text(b)) => match c.op {
+                CmpOp::Ne => !a.eq_ignore_ascii_case(b),
+                _ => a.eq_ignore_ascii_case(b),
+            },
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Number(f64),
+    Text(String),
+    Op(CmpOp),
+    Punct(&'static str),    // ( ) [ ] : , . | * - -> <-
+}
+
+fn tokenize(text: &str) -> Result<Vec<(usize, Token)>, QueryError> {
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let mut tokens: Vec<(usize, Token)> = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let (offset, c) = chars[i];
+        let next = chars.get(i + 1).map(|&(_, c)| c);
+        let after_op = matches!(tokens.last(), Some((_, Token::Op(_))));
+        let (token, width) = match c {
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '(' | ')' | '[' | ']' | ':' | ',' | '.' | '|' | '*' => {
+                let p = match c { '(' => "(", ')' => ")", '[' => "[", ']' => "]", ':' => ":", ',' => ",", '.' => ".", '|' => "|", _ => "*" };
+                (Token::Punct(p), 1)
+            }
+            '-' if next == Some('>') => (Token::Punct("->"), 2),
+            '<' if next == Some('-') && !after_op => (Token::Punct("<-"), 2),
+            '-' if after_op && next.is_some_and(|n| n.is_ascii_digit()) => number(&chars, i)?,
+            '-' => (Token::Punct("-"), 1),
+            '<' if next == Some('=') => (Token::Op(CmpOp::Le), 2),
+            '<' if next == Some('>') => (Token::Op(CmpOp::Ne), 2),
+            '<' => (Token::Op(CmpOp::Lt), 1),
+            '>' if next == Some('=') => (Token::Op(CmpOp::Ge), 2),
+            '>' => (Token::Op(CmpOp::Gt), 1),
+            '!' if next == Some('=') => (Token::Op(CmpOp::Ne), 2),
+            '=' => (Token::Op(CmpOp::Eq), 1),
+            '\'' | '"' => {
+                let end = chars[i + 1..].iter().position(|&(_, q)| q == c).ok_or(QueryError::Syntax {
+                    offset,
+                    message: "unterminated string".into(),
+                })?;
+                (Token::Text(chars[i + 1..i + 1 + end].iter().map(|&(_, c)| c).collect()), end + 2)
+            }
+            c if c.is_ascii_digit() => number(&chars, i)?,
+            c if c.is_alphanumeric() || c == '_' => {
+                let len = chars[i..].iter().take_while(|&&(_, c)| c.is_alphanumeric() || c == '_').count();
+                (Token::Word(chars[i..i + len].iter().map(|&(_, c)| c).collect()), len)
+            }
+            other => return Err(QueryError::Syntax { offset, message: format!("unexpected character '{}'", other) }),
+        };
+        tokens.push((offset, token));
+        i += width;
+    }
+    Ok(tokens)
+}
+
+fn number(chars: &[(usize, char)], i: usize) -> Result<(Token, usize), QueryError> {
+    let len = 1 + chars[i + 1..].iter().take_while(|&&(_, c)| c.is_ascii_digit() || c == '.').count();
+    let text: String = chars[i..i + len].iter().map(|&(_, c)| c).collect();
+    let value = text.parse().map_err(|_| QueryError::Syntax { offset: chars[i].0, message: format!("invalid number '{}'", text) })?;
+    Ok((Token::Number(value), len))
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+    len: usize,    // query length, reported as the offset of "end of input"
+}
+
+impl Parser {
+    fn offset(&self) -> usize {
+        self.tokens.get(self.pos).map_or(self.len, |(o, _)| *o)
+    }
+
+    fn error<T>(&self, message: impl Into<String>) -> Result<T, QueryError> {
+        Err(QueryError::Syntax { offset: self.offset(), message: message.into() })
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(_, t)| t)
+    }
+
+    fn eat_punct(&mut self, p: &str) -> bool {
+        let hit = matches!(self.peek(), Some(Token::Punct(q)) if *q == p);
+        self.pos += hit as usize;
+        hit
+    }
+
+    fn expect_punct(&mut self, p: &str) -> Result<(), QueryError> {
+        if self.eat_punct(p) { Ok(()) } else { self.error(format!("expected '{}'", p)) }
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let hit = matches!(self.peek(), Some(Token::Word(w)) if w.eq_ignore_ascii_case(keyword));
+        self.pos += hit as usize;
+        hit
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<(), QueryError> {
+        if self.eat_keyword(keyword) { Ok(()) } else { self.error(format!("expected {}", keyword)) }
+    }
+
+    fn word(&mut self, what: &str) -> Result<String, QueryError> {
+        match self.peek() {
+            Some(Token::Word(w)) => {
+                let w = w.clone();
+                self.pos += 1;
+                Ok(w)
+            }
+            _ => self.error(format!("expected {}", what)),
+        }
+    }
+
+    fn query(mut self) -> Result<GraphQuery, QueryError> {
+        self.expect_keyword("MATCH")?;
+        let mut nodes = vec![self.node()?];
+        let mut rels = vec![];
+        while matches!(self.peek(), Some(Token::Punct("-" | "<-"))) {
+            rels.push(self.rel()?);
+            nodes.push(self.node()?);
+        }
+
+        let mut conditions = vec![];
+        if self.eat_keyword("WHERE") {
+            conditions.push(self.condition()?);
+            while self.eat_keyword("AND") {
+                conditions.push(self.condition()?);
+            }
+        }
+
+        self.expect_keyword("RETURN")?;
+        let first = self.word("paths, nodes, edges, count or variables")?;
+        let returns = match first.to_ascii_lowercase().as_str() {
+            "paths" => ReturnClause::Paths,
+            "nodes" => ReturnClause::Nodes,
+            "edges" => ReturnClause::Edges,
+            "count" => {
+                // COUNT and COUNT(*) both work
+                if self.eat_punct("(") {
+                    self.eat_punct("*");
+                    self.expect_punct(")")?;
+                }
+                ReturnClause::Count
+            }
+            _ => {
+                let mut vars = vec![first];
+                while self.eat_punct(",") {
+                    vars.push(self.word("variable")?);
+                }
+                ReturnClause::Vars(vars)
+            }
+        };
+
+        let limit = if self.eat_keyword("LIMIT") {
+            match self.peek() {
+                Some(Token::Number(n)) if *n >= 0.0 && n.fract() == 0.0 => {
+                    let n = *n as usize;
+                    self.pos += 1;
+                    Some(n)
+                }
+                _ => return self.error("expected a whole number after LIMIT"),
+            }
+        } else {
+            None
+        };
+        if self.pos < self.tokens.len() {
+            return self.error("unexpected input after query");
+        }
+        Ok(GraphQuery { nodes, rels, conditions, returns, limit })
+    }
+
+    /// `(var:Label)`, `(var)`, `(:Label)` or `()`
+    fn node(&mut self) -> Result<NodePattern, QueryError> {
+        self.expect_punct("(")?;
+        let var = match self.peek() {
+            Some(Token::Word(_)) => Some(self.word("variable")?),
+            _ => None,
+        };
+        let label = if self.eat_punct(":") { Some(self.word("label")?) } else { None };
+        self.expect_punct(")")?;
+        Ok(NodePattern { var, label })
+    }
+
+    /// `-[r:Causal|Mechanistic]->`, `<-[...]-` or `-[...]-`; the brackets may be omitted
+    fn rel(&mut self) -> Result<RelPattern, QueryError> {
+        let incoming = self.eat_punct("<-");
+        if !incoming {
+            self.expect_punct("-")?;
+        }
+        let (mut var, mut types) = (None, vec![]);
+        if self.eat_punct("[") {
+            if let Some(Token::Word(_)) = self.peek() {
+                var = Some(self.word("variable")?);
+            }
+            if self.eat_punct(":") {
+                loop {
+                    let name = self.word("relationship type")?;
+                    let edge_type = EdgeType::parse(&name).map_err(|_| QueryError::UnknownEdgeType(name))?;
+                    types.push(edge_type);
+                    if !self.eat_punct("|") {
+                        break;
+                    }
+                }
+            }
+            self.expect_punct("]")?;
+        }
+        let direction = if incoming {
+            self.expect_punct("-")?;
+            Direction::In
+        } else if self.eat_punct("->") {
+            Direction::Out
+        } else {
+            self.expect_punct("-")?;
+            Direction::Either
+        };
+        Ok(RelPattern { var, types, direction })
+    }
+
+    fn condition(&mut self) -> Result<Condition, QueryError> {
+        let first = self.word("property")?;
+        let (var, property) = if self.eat_punct(".") { (Some(first), self.word("property")?) } else { (None, first) };
+        let op = match self.peek() {
+            Some(Token::Op(op)) => *op,
+            _ => return self.error("expected a comparison (=, !=, <, <=, >, >=)"),
+        };
+        self.pos += 1;
+        let value = match self.peek() {
+            Some(Token::Number(n)) => Literal::Number(*n),
+            Some(Token::Text(t)) => Literal::Text(t.clone()),
+            Some(Token::Word(w)) => Literal::Text(w.clone()),    // bare words, e.g. type = Causal
+            _ => return self.error("expected a number or quoted string"),
+        };
+        self.pos += 1;
+        Ok(Condition { var, property: property.to_ascii_lowercase(), op, value })
+    }
+}
@@ -0,0 +1,113 @@
+// limit-sarscov2-core/src/intervention.rs
+// Intervention (do-operator) queries: fix a node, cut its causes, re-evaluate downstream
+
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+
+use crate::{
+    edges::EdgeType,
+    multi_intent_graph::MultiIntentGraph,
+    propagation::NodeConfidenceChange,
+};
+
+/// Effect of an intervention on the stretch of a hypothesis path downstream
+/// of the intervened node: its run of causal edges, scaled by the node's
+/// confidence
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathEffect {
+    pub path_id: Uuid,
+    pub from_step: usize,         // position of the intervened node in `node_sequence`
+    pub causal_steps: usize,      // causal edges before the path leaves the causal subgraph
+    pub observed: f32,            // node's observational confidence × downstream confidence
+    pub intervened: f32,          // intervened value × downstream confidence
+}
+
+/// Outcome of `MultiIntentGraph::intervene`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Intervention {
+    pub node_id: Uuid,
+    pub value: f32,
+    pub cut_edges: Vec<Uuid>,                 // incoming causal edges and hyperedges severed
+    pub nodes: Vec<NodeConfidenceChange>,     // `previous` is the observational confidence
+    pub paths: Vec<PathEffect>,
+}
+
+impl MultiIntentGraph {
+    /// Copy of the graph keeping only causal edges and hyperedges, with
+    /// confidence propagated along them
+    fn causal_view(&self) -> MultiIntentGraph {
+        let mut view = self.clone();
+        view.edges.retain(|_, e| e.edge_type == EdgeType::Causal);
+        view.hyperedges.retain(|_, h| h.edge_type == EdgeType::Causal);
+        view.invalidate_all_confidence();
+        view.propagate_confidence();
+        view
+    }
+
+    /// What-if query do(`node_id` = `value`), e.g. "deploy this treatment
+    /// everywhere": the node's confidence is fixed at `value`, its incoming
+    /// causal edges are cut and confidence is propagated downstream along
+    /// causal edges only. Correlative edges carry no causal effect and are
+    /// left out. Downstream nodes are compared with the same causal
+    /// propagation without the intervention; hypothesis paths through the
+    /// node are re-evaluated over their causal stretch after it. The graph
+    /// itself is unchanged. `None` if the node is not in the graph.
+    pub fn intervene(&self, node_id: Uuid, value: f32) -> Option<Intervention> {
+        self.intent_nodes.get(&node_id)?;
+        let value = value.clamp(0.0, 1.0);
+        let observed = self.causal_view();
+
+        let mut intervened = observed.clone();
+        let mut cut_edges: Vec<Uuid> = intervened.edges.values().filter(|e| e.target_id == node_id).map(|e| e.id).collect();
+        intervened.edges.retain(|_, e| e.target_id != node_id);
+        for h in intervened.hyperedges.values_mut().filter(|h| h.target_ids.contains(&node_id)) {
+            h.target_ids.retain(|t| *t != node_id);
+            cut_edges.push(h.id);
+        }
+        intervened.hyperedges.retain(|_, h| !h.target_ids.is_empty());
+        cut_edges.sort();
+        intervened.intent_nodes.get_mut(&node_id).expect("checked above").metadata.confidence = value;
+        intervened.invalidate_confidence(node_id);
+        intervened.propagate_confidence();
+
+        let mut nodes: Vec<NodeConfidenceChange> = intervened.intent_nodes.values()
+            .filter_map(|node| {
+                let previous = observed.intent_nodes[&node.id].metadata.confidence;
+                let confidence = node.metadata.confidence;
+                ((confidence - previous).abs() > f32::EPSILON).then(|| NodeConfidenceChange {
+                    node_id: node.id,
+                    previous,
+                    confidence,
+                    incoming: intervened.edges.values().filter(|e| e.target_id == node.id && !e.is_refuting()).count()
+                        + intervened.hyperedges.values().filter(|h| h.target_ids.contains(&node.id)).count(),
+                })
+            })
+            .collect();
+        nodes.sort_by_key(|c| c.node_id);
+
+        let node_confidence = observed.intent_nodes[&node_id].metadata.confidence;
+        let net_support = observed.net_supports();
+        let paths = self.hypothesis_paths.iter()
+            .filter_map(|path| {
+                let from_step = path.node_sequence.iter().position(|id| *id == node_id)?;
+                let downstream: Vec<_> = path.edge_sequence.iter().skip(from_step)
+                    .map_while(|id| observed.edges.get(id).filter(|e| !e.is_refuting()))
+                    .collect();
+                if downstream.is_empty() {
+                    return None;
+                }
+                let confidence = self.path_policy.aggregator
+                    .aggregate_with(&downstream, |e| net_support.get(&e.id).copied().unwrap_or(0.0));
+                Some(PathEffect {
+                    path_id: path.id,
+                    from_step,
+                    causal_steps: downstream.len(),
+                    observed: node_confidence * confidence,
+                    intervened: value * confidence,
+                })
+            })
+            .collect();
+
+        Some(Intervention { node_id, value, cut_edges, nodes, paths })
+    }
+}
@@ -0,0 +1,102 @@
+// limit-sarscov2-core/src/lib.rs
+// Graph core for the SARS-CoV-2 multi-intent knowledge graph: no IO, no server
+
+pub mod domain;
+pub mod nodes;
+pub mod provenance;
+pub mod metrics;
+pub mod rd;
+pub mod multi_intent_graph;
+pub mod serendipity_trace;
+pub mod trace_cluster;
+pub mod trace_diagnostics;
+pub mod edges;
+pub mod scheduler;
+pub mod mutation;
+pub mod lineage;
+pub mod lineage_tree;
+pub mod genome;
+pub mod graph_query;
+pub mod dedup;
+pub mod confidence;
+pub mod support;
+pub mod propagation;
+pub mod bayes;
+pub mod intervention;
+pub mod counterfactual;
+pub mod geo;
+pub mod temporal;
+pub mod validate;
+pub mod events;
+pub mod diff;
+pub mod search;
+pub mod fuzzy;
+pub mod synonyms;
+pub mod ontology;
+pub mod summary;
+pub mod quantum;
+pub mod qubo;
+pub mod embeddings;
+pub mod link_prediction;
+pub mod similarity;
+mod parallel;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use domain::{ResearchDomain, SarsCov2Graph};
+pub use nodes::{VirusNode, VirologyNode, ImmunologyNode, GenomicsNode, TreatmentNode, PublicHealthNode, ClinicalTrialNode, HostFactorNode, VaccineNode};
+pub use provenance::{ProvenanceNote, NoteSignature, GovernanceTag, ProvenanceIndex, ProvenanceLineage, Citation, CitationKind};
+pub use metrics::{DomainCoverage, Serendipity, SARSCoV2Metrics};
+pub use rd::{RDPoint, RDCurve, rd_from_batches, Knee, KneeOptions, KneeMethod};
+pub use multi_intent_graph::{MultiIntentGraph, HypothesisPath, IntentNode, StructuralMetrics, PathIssue, PathPolicy, ConfidenceAggregator, HypothesisDomains, Removal, DanglingEdges};
+pub use serendipity_trace::{SerendipityTrace, ExplorationStep, HypothesisType, TraceComparison, TraceMatch, TraceMergeError, TraceSearch, search_traces};
+pub use trace_cluster::{TraceCluster, TraceClusterOptions, cluster_traces};
+pub use trace_diagnostics::{DiagnosticOptions, TraceDiagnostics, TraceIssue, TraceIssueKind};
+pub use edges::{EdgeType, EdgeTypeError, EdgeVocabulary, CausalEdge, CorrelativeEdge, GraphEdge, HyperEdge, Polarity, normalize_evidence_ref};
+pub use scheduler::{BudgetDecision, ExplorationScheduler, SchedulerPolicy, SchedulingDecision, ScheduleTarget};
+pub use mutation::{Mutation, MutationKind, MutationParseError, parse_mutations};
+pub use lineage::{PangoLineage, AliasTable, LineageError};
+pub use lineage_tree::{LineageTree, LineageTreeNode};
+pub use graph_query::{GraphQuery, QueryError, QueryResult, MatchedPath};
+pub use dedup::{DedupOptions, DuplicateCandidate, NodeMerge, find_duplicates, name_similarity};
+pub use confidence::{ConfidenceModel, ConfidenceUpdate, EvidenceKind, EvidenceWeight, parse_evidence_date};
+pub use support::RelationshipSupport;
+pub use propagation::{ConfidenceCombiner, NodeConfidenceChange, PropagationPolicy};
+pub use bayes::{BayesError, BayesNode, BayesOptions, BayesianNetwork, Belief, CptRow};
+pub use intervention::{Intervention, PathEffect};
+pub use counterfactual::{BranchSummary, CounterfactualError, CounterfactualReport, OutcomeComparison};
+pub use geo::{GeoScope, GeoScopeError};
+pub use temporal::{TimeBucket, TimeBucketStatistics, Timeline};
+pub use validate::{SubjectKind, ValidationIssue, ValidationReport, ValidationRule, validate_edge, validate_hyperedge, validate_node};
+pub use events::{EventError, GraphEvent};
+pub use diff::{ChangeEntry, ChangeKind, GraphDiff};
+pub use search::{FieldMatch, SearchHit, SearchOptions, SearchResults};
+pub use fuzzy::{NameMatch, fuzzy_similarity, jaro_winkler, levenshtein};
+pub use synonyms::{SynonymError, SynonymGroup, SynonymTable};
+pub use ontology::{OntologyRef, OntologyRefError};
+pub use summary::{ChainSummary, GraphSummary, SummaryFilter};
+pub use quantum::{QuantumWalkError, QuantumWalkOptions, QuantumWalkReport, WalkHit, WalkPath, MAX_WALK_NODES};
+pub use qubo::{PathSelection, PathSelectionOptions, PathSelectionProblem, Qubo, QuboError, QuboSolver, SelectionReport, SimulatedAnnealing, encode_path_selection, select_hypothesis_paths, MAX_QUBO_VARIABLES};
+pub use embeddings::{EmbeddingOptions, EmbeddingReport, cosine_similarity};
+pub use link_prediction::{LinkPredictionOptions, LinkPredictor, SuggestedEdge};
+pub use similarity::{SimilarNode, SimilarityOptions};
+pub use genome::{FastaRecord, GenomeError, MutationCalls, MutationCaller, ReferenceGenome, parse_fasta};
+
+/// Everything needed to build and inspect a multi-intent graph.
+///
+/// `use limit_sarscov2_core::prelude::*;` is covered by the crate's semver
+/// guarantee: items are only removed or changed in a major release.
+pub mod prelude {
+    pub use crate::domain::{ResearchDomain, SarsCov2Graph};
+    pub use crate::nodes::*;
+    pub use crate::edges::{builders as edge_builders, EdgeType, GraphEdge};
+    pub use crate::multi_intent_graph::{
+        GraphStatistics, HypothesisPath, IntentNode, MultiIntentGraph, MultiIntentGraphBuilder, NodeContent,
+    };
+    pub use crate::serendipity_trace::{ExplorationStep, HypothesisType, SerendipityTrace, StepBuilder};
+    pub use crate::rd::{rd_from_batches, RDCurve, RDPoint};
+    pub use crate::metrics::SARSCoV2Metrics;
+    pub use crate::provenance::{GovernanceTag, ProvenanceNote};
+    pub use crate::mutation::Mutation;
+    pub use crate::lineage::PangoLineage;
+}
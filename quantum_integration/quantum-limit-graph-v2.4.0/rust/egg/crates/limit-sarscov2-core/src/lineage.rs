@@ -0,0 +1,218 @@
+// limit-sarscov2-core/src/lineage.rs
+// Pango lineage names, alias resolution, and ancestor/descendant queries
+
+use serde::{Serialize, Deserialize};
+use std::{collections::HashMap, fmt, str::FromStr};
+use thiserror::Error;
+
+/// A Pango lineage such as "BA.5.2.1", stored in the (possibly aliased) form it was given
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct PangoLineage {
+    pub prefix: String,       // "BA", "B", "XBB"
+    pub path: Vec<u32>,       // [5, 2, 1]
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum LineageError {
+    #[error("invalid Pango lineage name: {0}")]
+    Invalid(String),
+}
+
+/// Alias prefixes and the lineage each one abbreviates (BA = B.1.1.529)
+#[derive(Debug, Clone)]
+pub struct AliasTable {
+    aliases: HashMap<String, PangoLineage>,
+}
+
+impl PangoLineage {
+    pub fn parse(name: &str) -> Result<Self, LineageError> {
+        let name = name.trim();
+        let mut parts = name.split('.');
+        let prefix = parts.next().unwrap_or_default();
+        if prefix.is_empty() || prefix.len() > 3 || !prefix.chars().all(|c| c.is_ascii_uppercase()) {
+            return Err(LineageError::Invalid(name.into()));
+        }
+        let path = parts
+            .map(|p| p.parse::<u32>().map_err(|_| LineageError::Invalid(name.into())))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { prefix: prefix.into(), path })
+    }
+
+    /// Find the first lineage mentioned in free text ("Omicron BA.5" → BA.5).
+    /// Bare tokens must be dotted (`BA.5`) or recombinants (`XBB`); WHO labels
+    /// map to their parent lineage.
+    pub fn find_in(text: &str) -> Option<Self> {
+        let tokens: Vec<&str> = text
+            .split(|c: char| c.is_whitespace() || ",;()/".contains(c))
+            .map(|t| t.trim_end_matches(['.', ':', '*']))
+            .filter(|t| !t.is_empty())
+            .collect();
+
+        tokens.iter()
+            .filter(|t| t.contains('.') || t.starts_with('X'))
+            .find_map(|t| Self::parse(t).ok())
+            .or_else(|| tokens.iter().find_map(|t| who_label(t)))
+    }
+
+    /// True for A, B and recombinant (X*) roots, which have no single parent
+    pub fn is_root(&self) -> bool {
+        self.path.is_empty() && (self.prefix == "A" || self.prefix == "B" || self.is_recombinant())
+    }
+
+    pub fn is_recombinant(&self) -> bool {
+        self.prefix.starts_with('X')
+    }
+
+    /// Fully unaliased form using the default alias table ("BA.5" → "B.1.1.529.5")
+    pub fn unaliased(&self) -> Self {
+        AliasTable::default().expand(self)
+    }
+
+    /// Immediate parent, expressed in the shortest alias form
+    pub fn parent(&self) -> Option<Self> {
+        AliasTable::default().parent(self)
+    }
+
+    /// All ancestors from the parent up to the root
+    pub fn ancestors(&self) -> Vec<Self> {
+        let table = AliasTable::default();
+        let mut out = vec![];
+        let mut current = table.parent(self);
+        while let Some(p) = current {
+            current = table.parent(&p);
+            out.push(p);
+        }
+        out
+    }
+
+    /// Same lineage regardless of aliasing (BA.5 == B.1.1.529.5)
+    pub fn same_as(&self, other: &Self) -> bool {
+        self.unaliased() == other.unaliased()
+    }
+
+    pub fn is_ancestor_of(&self, other: &Self) -> bool {
+        let (a, b) = (self.unaliased(), other.unaliased());
+        a.prefix == b.prefix && a.path.len() < b.path.len() && b.path.starts_with(&a.path)
+    }
+
+    pub fn is_descendant_of(&self, other: &Self) -> bool {
+        other.is_ancestor_of(self)
+    }
+}
+
+impl AliasTable {
+    pub fn new() -> Self {
+        Self { aliases: HashMap::new() }
+    }
+
+    /// Register an alias prefix for a lineage (e.g. "KP" for "JN.1.11.1")
+    pub fn insert(&mut self, alias: &str, lineage: &str) -> Result<(), LineageError> {
+        let target = self.expand(&PangoLineage::parse(lineage)?);
+        self.aliases.insert(alias.into(), target);
+        Ok(())
+    }
+
+    /// Replace alias prefixes until reaching A, B, or a recombinant root
+    pub fn expand(&self, lineage: &PangoLineage) -> PangoLineage {
+        let mut current = lineage.clone();
+        while let Some(target) = self.aliases.get(&current.prefix) {
+            let mut path = target.path.clone();
+            path.extend(&current.path);
+            current = PangoLineage { prefix: target.prefix.clone(), path };
+        }
+        current
+    }
+
+    /// Shortest aliased form of a lineage ("B.1.1.529.5.2.1.1" → "BF.1").
+    /// The alias covering the longest prefix of the full path wins.
+    pub fn compress(&self, lineage: &PangoLineage) -> PangoLineage {
+        let full = self.expand(lineage);
+        self.aliases.iter()
+            .filter(|(_, t)| t.prefix == full.prefix && full.path.len() > t.path.len() && full.path.starts_with(&t.path))
+            .max_by_key(|(alias, t)| (t.path.len(), std::cmp::Reverse(alias.len())))
+            .map(|(alias, t)| PangoLineage { prefix: alias.clone(), path: full.path[t.path.len()..].to_vec() })
+            .unwrap_or(full)
+    }
+
+    pub fn parent(&self, lineage: &PangoLineage) -> Option<PangoLineage> {
+        let mut full = self.expand(lineage);
+        full.path.pop()?;
+        Some(self.compress(&full))
+    }
+}
+
+impl Default for AliasTable {
+    /// Aliases for the major lineage families in circulation
+    fn default() -> Self {
+        let mut table = Self::new();
+        let aliases = [
+            ("C", "B.1.1.1"),
+            ("D", "B.1.1.25"),
+            ("P", "B.1.1.28"),
+            ("Q", "B.1.1.7"),
+            ("AY", "B.1.617.2"),
+            ("BA", "B.1.1.529"),
+            ("BE", "BA.5.3.1"),
+            ("BF", "BA.5.2.1"),
+            ("BQ", "BE.1.1.1"),
+            ("BN", "BA.2.75.5"),
+            ("CH", "BA.2.75.3.4.1.1"),
+            ("JN", "BA.2.86.1"),
+            ("KP", "JN.1.11.1"),
+            ("EG", "XBB.1.9.2"),
+            ("FL", "XBB.1.9.1"),
+            ("HV", "EG.5.1.6"),
+        ];
+        for (alias, lineage) in aliases {
+            // Targets only reference aliases registered earlier in the list
+            let _ = table.insert(alias, lineage);
+        }
+        table
+    }
+}
+
+impl fmt::Display for PangoLineage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.prefix)?;
+        for n in &self.path {
+            write!(f, ".{}", n)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for PangoLineage {
+    type Err = LineageError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<String> for PangoLineage {
+    type Error = LineageError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::parse(&s)
+    }
+}
+
+impl From<PangoLineage> for String {
+    fn from(l: PangoLineage) -> Self {
+        l.to_string()
+    }
+}
+
+/// WHO variant-of-concern labels and the lineage each one designates
+fn who_label(token: &str) -> Option<PangoLineage> {
+    let lineage = match token.to_ascii_lowercase().as_str() {
+        "alpha" => "B.1.1.7",
+        "beta" => "B.1.351",
+        "gamma" => "P.1",
+        "delta" => "B.1.617.2",
+        "omicron" => "B.1.1.529",
+        _ => return None,
+    };
+    PangoLineage::parse(lineage).ok()
+}
@@ -0,0 +1,213 @@
+// limit-sarscov2-core/src/lineage_tree.rs
+// Lineage tree: parent/child Pango lineages with defining mutations and the variant nodes placed on them
+
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use std::collections::{BTreeMap, HashSet};
+
+use crate::{
+    lineage::{AliasTable, PangoLineage},
+    multi_intent_graph::{IntentNode, MultiIntentGraph, NodeContent},
+    mutation::Mutation,
+};
+
+/// One lineage on the tree
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LineageTreeNode {
+    pub lineage: PangoLineage,            // shortest alias form
+    pub parent: Option<PangoLineage>,     // None for A, B and recombinant roots
+    pub children: Vec<PangoLineage>,
+    pub defining_mutations: Vec<Mutation>, // gained on the branch from `parent`
+    pub variant_nodes: Vec<Uuid>,         // variant intent nodes placed on this lineage
+}
+
+/// Pango lineages as a forest. Inserting a lineage inserts its ancestors,
+/// so "descendant of BA.2" can be answered from the tree.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LineageTree {
+    pub nodes: BTreeMap<String, LineageTreeNode>,  // keyed by unaliased name ("B.1.1.529.2")
+}
+
+fn key(lineage: &PangoLineage) -> String {
+    lineage.unaliased().to_string()
+}
+
+/// Tree order: unaliased prefix, then numeric path (BA.2 before BA.10)
+fn order(lineage: &PangoLineage) -> (String, Vec<u32>) {
+    let full = lineage.unaliased();
+    (full.prefix, full.path)
+}
+
+impl LineageTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The lineage's tree node, inserting it and any missing ancestors
+    pub fn insert(&mut self, lineage: &PangoLineage) -> &mut LineageTreeNode {
+        let k = key(lineage);
+        if !self.nodes.contains_key(&k) {
+            let lineage = AliasTable::default().compress(lineage);
+            let parent = lineage.parent();
+            if let Some(p) = &parent {
+                let children = &mut self.insert(p).children;
+                children.push(lineage.clone());
+                children.sort_by_key(order);
+            }
+            self.nodes.insert(k.clone(), LineageTreeNode { lineage, parent, children: vec![], defining_mutations: vec![], variant_nodes: vec![] });
+        }
+        self.nodes.get_mut(&k).expect("inserted above")
+    }
+
+    pub fn get(&self, lineage: &PangoLineage) -> Option<&LineageTreeNode> {
+        self.nodes.get(&key(lineage))
+    }
+
+    pub fn roots(&self) -> Vec<&LineageTreeNode> {
+        let mut roots: Vec<&LineageTreeNode> = self.nodes.values().filter(|n| n.parent.is_none()).collect();
+        roots.sort_by_key(|n| order(&n.lineage));
+        roots
+    }
+
+    /// Parent first, up to the root; empty if the lineage is not on the tree
+    pub fn ancestors(&self, lineage: &PangoLineage) -> Vec<&LineageTreeNode> {
+        let mut out = vec![];
+        let mut current = self.get(lineage).and_then(|n| n.parent.as_ref());
+        while let Some(node) = current.and_then(|p| self.get(p)) {
+            out.push(node);
+            current = node.parent.as_ref();
+        }
+        out
+    }
+
+    /// Every lineage below `lineage`, depth-first in tree order
+    pub fn descendants(&self, lineage: &PangoLineage) -> Vec<&LineageTreeNode> {
+        let mut out = vec![];
+        let mut stack: Vec<&PangoLineage> = self.get(lineage).map(|n| n.children.iter().rev().collect()).unwrap_or_default();
+        while let Some(child) = stack.pop() {
+            if let Some(node) = self.get(child) {
+                stack.extend(node.children.iter().rev());
+                out.push(node);
+            }
+        }
+        out
+    }
+
+    pub fn is_descendant_of(&self, lineage: &PangoLineage, ancestor: &PangoLineage) -> bool {
+        let k = key(ancestor);
+        self.ancestors(lineage).iter().any(|n| key(&n.lineage) == k)
+    }
+
+    /// Defining mutations from the root down to `lineage`
+    pub fn mutations_from_root(&self, lineage: &PangoLineage) -> Vec<Mutation> {
+        let mut path = self.ancestors(lineage);
+        path.reverse();
+        path.extend(self.get(lineage));
+        path.iter().flat_map(|n| n.defining_mutations.iter().cloned()).collect()
+    }
+
+    /// Newick, one tree per root; branch lengths count defining mutations
+    pub fn to_newick(&self) -> String {
+        fn write(tree: &LineageTree, node: &LineageTreeNode, out: &mut String) {
+            if !node.children.is_empty() {
+                out.push('(');
+                for (i, child) in node.children.iter().filter_map(|c| tree.get(c)).enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    write(tree, child, out);
+                }
+                out.push(')');
+            }
+            out.push_str(&node.lineage.to_string());
+            if node.parent.is_some() {
+                out.push_str(&format!(":{}", node.defining_mutations.len()));
+            }
+        }
+        let mut out = String::new();
+        for root in self.roots() {
+            write(self, root, &mut out);
+            out.push_str(";\n");
+        }
+        out
+    }
+
+    /// Graphviz DOT digraph; branches are labelled with their defining mutations
+    pub fn to_dot(&self) -> String {
+        const SHOWN_MUTATIONS: usize = 5;
+        let mut out = String::from("digraph \"lineage_tree\" {\n    rankdir=LR;\n    node [shape=box, style=rounded];\n");
+        for (k, node) in &self.nodes {
+            let label = match node.variant_nodes.len() {
+                0 => node.lineage.to_string(),
+                n => format!("{}\\n{} variant node{}", node.lineage, n, if n == 1 { "" } else { "s" }),
+            };
+            let style = if node.variant_nodes.is_empty() { "" } else { ", penwidth=2" };
+            out.push_str(&format!("    \"{}\" [label=\"{}\"{}];\n", k, label, style));
+        }
+        for (k, node) in &self.nodes {
+            let Some(parent) = &node.parent else { continue };
+            let mut shown: Vec<String> = node.defining_mutations.iter().take(SHOWN_MUTATIONS).map(|m| m.to_string()).collect();
+            if node.defining_mutations.len() > SHOWN_MUTATIONS {
+                shown.push(format!("+{} more", node.defining_mutations.len() - SHOWN_MUTATIONS));
+            }
+            out.push_str(&format!("    \"{}\" -> \"{}\" [label=\"{}\"];\n", key(parent), k, shown.join("\\n")));
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+impl MultiIntentGraph {
+    /// Lineage tree of the variant nodes whose `variant` names a Pango lineage
+    /// (`GenomicsNode::lineage`). A lineage's defining mutations are those its
+    /// variant nodes carry and the variant nodes of its nearest placed
+    /// ancestor do not.
+    pub fn lineage_tree(&self) -> LineageTree {
+        let mut tree = LineageTree::new();
+        let mut variants: Vec<(&IntentNode, PangoLineage)> = self.intent_nodes.values()
+            .filter_map(|n| match &n.content {
+                NodeContent::Variant(v) => v.lineage().map(|l| (n, l)),
+                _ => None,
+            })
+            .collect();
+        variants.sort_by_key(|(n, _)| n.id);
+        for (node, lineage) in &variants {
+            tree.insert(lineage).variant_nodes.push(node.id);
+        }
+
+        let carried = |tree: &LineageTree, k: &str| -> Vec<Mutation> {
+            let mut seen = HashSet::new();
+            tree.nodes[k].variant_nodes.iter()
+                .filter_map(|id| match &self.intent_nodes[id].content {
+                    NodeContent::Variant(v) => Some(&v.mutations),
+                    _ => None,
+                })
+                .flatten()
+                .filter(|m| seen.insert(m.to_string()))
+                .cloned()
+                .collect()
+        };
+        let placed: Vec<String> = tree.nodes.iter().filter(|(_, n)| !n.variant_nodes.is_empty()).map(|(k, _)| k.clone()).collect();
+        for k in placed {
+            let ancestor = tree.ancestors(&tree.nodes[&k].lineage).into_iter()
+                .find(|n| !n.variant_nodes.is_empty())
+                .map(|n| key(&n.lineage));
+            let inherited: HashSet<String> = ancestor.map(|a| carried(&tree, &a).iter().map(|m| m.to_string()).collect()).unwrap_or_default();
+            let defining = carried(&tree, &k).into_iter().filter(|m| !inherited.contains(&m.to_string())).collect();
+            tree.nodes.get_mut(&k).expect("placed lineage").defining_mutations = defining;
+        }
+        tree
+    }
+
+    /// Variant nodes on lineages strictly below `lineage` ("descendants of
+    /// BA.2"), sorted by ID
+    pub fn descendant_variants(&self, lineage: &PangoLineage) -> Vec<&IntentNode> {
+        let tree = self.lineage_tree();
+        let mut nodes: Vec<&IntentNode> = tree.descendants(lineage).iter()
+            .flat_map(|n| &n.variant_nodes)
+            .filter_map(|id| self.intent_nodes.get(id))
+            .collect();
+        nodes.sort_by_key(|n| n.id);
+        nodes
+    }
+}
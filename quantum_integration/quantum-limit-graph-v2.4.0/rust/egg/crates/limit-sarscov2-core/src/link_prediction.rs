@@ -0,0 +1,136 @@
+// limit-sarscov2-core/src/link_prediction.rs
+// Link prediction: score unobserved node pairs and propose the likeliest as edges
+
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use crate::{
+    edges::GraphEdge,
+    embeddings::cosine_similarity,
+    multi_intent_graph::MultiIntentGraph,
+};
+
+/// Confidence of a suggested edge at the best possible score; a prediction is
+/// not evidence, so suggestions stay well below curated edges
+const MAX_SUGGESTION_CONFIDENCE: f32 = 0.5;
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkPredictor {
+    /// `Embedding` once any node has an embedding, `AdamicAdar` otherwise
+    #[default]
+    Auto,
+    /// Cosine similarity of the endpoints' `compute_embeddings` vectors
+    Embedding,
+    /// Shared supporting neighbors, each weighted by 1 / ln(its degree)
+    AdamicAdar,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LinkPredictionOptions {
+    pub method: LinkPredictor,
+    pub min_score: f32,           // pairs scoring at or below this are dropped
+    pub cross_domain_only: bool,  // only suggest links between nodes of different domains
+}
+
+/// A plausible relationship the graph does not have yet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuggestedEdge {
+    pub source_id: Uuid,
+    pub target_id: Uuid,
+    pub score: f32,
+    pub method: LinkPredictor,            // never `Auto`
+    pub common_neighbors: Vec<Uuid>,      // supporting neighbors both endpoints share
+    pub edge: GraphEdge,                  // correlative edge, without evidence, to propose for review
+}
+
+impl MultiIntentGraph {
+    /// `suggest_edges_with` under the default options
+    pub fn suggest_edges(&self, top_k: usize) -> Vec<SuggestedEdge> {
+        self.suggest_edges_with(top_k, &LinkPredictionOptions::default())
+    }
+
+    /// The `top_k` highest-scoring pairs of nodes with no edge between them
+    /// in either direction, refuting edges included. Structure comes from the
+    /// supporting edges, as in `compute_embeddings`; pairs the method cannot
+    /// score (no shared neighbor, or a missing embedding) are left out. Each
+    /// suggestion carries an edge meant for the approval queue, not for
+    /// `add_edge`.
+    pub fn suggest_edges_with(&self, top_k: usize, options: &LinkPredictionOptions) -> Vec<SuggestedEdge> {
+        let method = match options.method {
+            LinkPredictor::Auto if self.intent_nodes.values().any(|n| n.metadata.embedding.is_some()) => LinkPredictor::Embedding,
+            LinkPredictor::Auto => LinkPredictor::AdamicAdar,
+            method => method,
+        };
+        let observed: HashSet<(Uuid, Uuid)> = self.edges.values()
+            .map(|e| (e.source_id.min(e.target_id), e.source_id.max(e.target_id)))
+            .collect();
+        let mut neighbors: BTreeMap<Uuid, BTreeSet<Uuid>> = BTreeMap::new();
+        for (a, b) in self.support_links().into_keys() {
+            neighbors.entry(a).or_default().insert(b);
+            neighbors.entry(b).or_default().insert(a);
+        }
+        let common = |a: &Uuid, b: &Uuid| -> Vec<Uuid> {
+            match (neighbors.get(a), neighbors.get(b)) {
+                (Some(x), Some(y)) => x.intersection(y).copied().collect(),
+                _ => vec![],
+            }
+        };
+
+        let mut candidates: BTreeSet<(Uuid, Uuid)> = BTreeSet::new();
+        match method {
+            LinkPredictor::Embedding => {
+                let embedded: BTreeSet<Uuid> = self.intent_nodes.values().filter(|n| n.metadata.embedding.is_some()).map(|n| n.id).collect();
+                for a in &embedded {
+                    candidates.extend(embedded.range(a..).skip(1).map(|b| (*a, *b)));
+                }
+            }
+            _ => {
+                for around in neighbors.values() {
+                    for a in around {
+                        candidates.extend(around.range(a..).skip(1).map(|b| (*a, *b)));
+                    }
+                }
+            }
+        }
+
+        let mut suggestions: Vec<SuggestedEdge> = candidates.into_iter()
+            .filter(|pair| !observed.contains(pair))
+            .filter_map(|(a, b)| {
+                let (source, target) = (self.intent_nodes.get(&a)?, self.intent_nodes.get(&b)?);
+                if options.cross_domain_only && std::mem::discriminant(&source.domain) == std::mem::discriminant(&target.domain) {
+                    return None;
+                }
+                let shared = common(&a, &b);
+                let (score, strength) = match method {
+                    LinkPredictor::Embedding => {
+                        let score = cosine_similarity(source.metadata.embedding.as_ref()?, target.metadata.embedding.as_ref()?);
+                        (score, score.max(0.0))
+                    }
+                    _ => {
+                        let score: f32 = shared.iter().map(|z| 1.0 / (neighbors[z].len() as f32).ln()).sum();
+                        (score, 1.0 - (-score).exp())
+                    }
+                };
+                if score <= options.min_score {
+                    return None;
+                }
+                let edge = GraphEdge::new_correlative(
+                    a,
+                    b,
+                    format!("{} may be related to {}", source.label(), target.label()),
+                    format!("{:?}", source.domain),
+                    format!("{:?}", target.domain),
+                    vec![],
+                    (MAX_SUGGESTION_CONFIDENCE * strength).clamp(0.0, 1.0),
+                );
+                Some(SuggestedEdge { source_id: a, target_id: b, score, method, common_neighbors: shared, edge })
+            })
+            .collect();
+        suggestions.sort_by(|x, y| y.score.total_cmp(&x.score).then((x.source_id, x.target_id).cmp(&(y.source_id, y.target_id))));
+        suggestions.truncate(top_k);
+        suggestions
+    }
+}
@@ -1,4 +1,4 @@
-// limit-sarscov2/src/metrics.rs
+// limit-sarscov2-core/src/metrics.rs
 use serde::{Serialize, Deserialize};
 use crate::domain::SarsCov2Graph;
 
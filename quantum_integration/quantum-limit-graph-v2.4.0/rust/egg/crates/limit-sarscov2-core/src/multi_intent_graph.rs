@@ -0,0 +1,1348 @@
+// limit-sarscov2-core/src/multi_intent_graph.rs
+// Multi-intent knowledge graph with nodes, edges, and hypothesis paths
+
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use std::collections::{hash_map::Entry, BTreeMap, HashMap, HashSet, VecDeque};
+use thiserror::Error;
+
+use crate::{
+    domain::{SarsCov2Graph, ResearchDomain},
+    nodes::*,
+    edges::{GraphEdge, EdgeType, EdgeVocabulary, HyperEdge},
+    serendipity_trace::{SerendipityTrace, HypothesisType},
+    rd::RDCurve,
+    provenance::GovernanceTag,
+    propagation::PropagationPolicy,
+    mutation::Mutation,
+    geo::GeoScope,
+    ontology::OntologyRef,
+    synonyms::SynonymTable,
+    parallel,
+};
+
+/// Intent-specific node in the multi-intent graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntentNode {
+    pub id: Uuid,
+    pub intent: String,           // "transmissibility", "vaccine_efficacy", etc.
+    pub domain: ResearchDomain,
+    pub content: NodeContent,
+    pub metadata: NodeMetadata,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum NodeContent {
+    Biology(VirologyNode),
+    Immunology(ImmunologyNode),
+    Variant(GenomicsNode),
+    Treatment(TreatmentNode),
+    PublicHealth(PublicHealthNode),
+    ClinicalTrial(ClinicalTrialNode),
+    HostFactor(HostFactorNode),
+    Vaccine(VaccineNode),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeMetadata {
+    pub evidence_count: usize,
+    pub confidence: f32,
+    pub sources: Vec<String>,
+    pub created_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<GeoScope>,  // None: not tied to a place
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub xrefs: Vec<OntologyRef>,   // terms in other ontologies: CHEBI for drugs, GO for processes, MONDO/HP for outcomes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<Vec<f32>>,  // unit-length structural embedding from `compute_embeddings`
+}
+
+impl IntentNode {
+    /// Human-readable name of the node's subject
+    pub fn label(&self) -> &str {
+        match &self.content {
+            NodeContent::Biology(n) => &n.topic,
+            NodeContent::Immunology(n) => &n.topic,
+            NodeContent::Variant(n) => &n.variant,
+            NodeContent::Treatment(n) => &n.therapy,
+            NodeContent::PublicHealth(n) => &n.policy,
+            NodeContent::ClinicalTrial(n) => &n.registry_id,
+            NodeContent::HostFactor(n) => &n.factor,
+            NodeContent::Vaccine(n) => &n.name,
+        }
+    }
+
+    /// Geographic scope: the metadata's, else a public-health policy's own
+    pub fn region(&self) -> Option<&GeoScope> {
+        match (&self.metadata.region, &self.content) {
+            (Some(region), _) => Some(region),
+            (None, NodeContent::PublicHealth(n)) => n.region.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// Hypothesis exploration path through the graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HypothesisPath {
+    pub id: Uuid,
+    pub hypothesis_type: HypothesisType,
+    pub description: String,
+    pub node_sequence: Vec<Uuid>,     // ordered node IDs
+    pub edge_sequence: Vec<Uuid>,     // ordered edge IDs
+    pub total_confidence: f32,
+    pub evidence_coverage: f32,
+}
+
+/// Multi-intent knowledge graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiIntentGraph {
+    pub id: Uuid,
+    pub base_graph: SarsCov2Graph,
+    pub intent_nodes: HashMap<Uuid, IntentNode>,
+    pub edges: HashMap<Uuid, GraphEdge>,
+    pub hypothesis_paths: Vec<HypothesisPath>,
+    pub serendipity_traces: Vec<SerendipityTrace>,
+    pub rd_curves: HashMap<String, RDCurve>,  // keyed by intent, or `rd_curve_key(intent, domain)`
+    pub metadata: GraphMetadata,
+    #[serde(default)]
+    pub path_policy: PathPolicy,
+    #[serde(default)]
+    pub governance_tags: HashMap<Uuid, Vec<GovernanceTag>>,  // keyed by node, edge or hyperedge ID
+    #[serde(default)]
+    pub hyperedges: HashMap<Uuid, HyperEdge>,
+    #[serde(default)]
+    pub evidence_dates: HashMap<String, String>,  // publication date by normalized evidence reference
+    #[serde(default)]
+    pub propagation: PropagationPolicy,
+    #[serde(default)]
+    pub edge_vocabulary: EdgeVocabulary,  // custom edge types the graph accepts
+    #[serde(default)]
+    pub synonyms: SynonymTable,           // custom aliases consulted by search, name resolution and dedup
+    #[serde(skip)]
+    pub(crate) stale_confidence: Option<HashSet<Uuid>>,  // nodes to re-propagate from; `None` = all
+}
+
+/// Rules applied when hypothesis paths are added
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathPolicy {
+    pub strict: bool,             // reject paths that fail `validate_path`
+    #[serde(default)]
+    pub aggregator: ConfidenceAggregator,
+    #[serde(default = "default_min_evidence_refs")]
+    pub min_evidence_refs: usize, // distinct references an edge needs to count as covered
+    #[serde(default)]
+    pub custom_hypotheses: HashMap<String, HypothesisDomains>,  // keyed by custom hypothesis name
+}
+
+impl Default for PathPolicy {
+    fn default() -> Self {
+        Self {
+            strict: false,
+            aggregator: ConfidenceAggregator::default(),
+            min_evidence_refs: default_min_evidence_refs(),
+            custom_hypotheses: HashMap::new(),
+        }
+    }
+}
+
+/// Domains a hypothesis path starts from and ends in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HypothesisDomains {
+    pub from: Vec<ResearchDomain>,
+    pub to: Vec<ResearchDomain>,
+}
+
+fn default_min_evidence_refs() -> usize {
+    1
+}
+
+/// How edge confidences combine into a path's `total_confidence`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfidenceAggregator {
+    /// Every link must hold: Π cᵢ
+    #[default]
+    Product,
+    /// Weakest link
+    Min,
+    /// At least one link holds: 1 − Π (1 − cᵢ)
+    NoisyOr,
+    /// Mean weighted by each edge's evidence reference count (minimum 1)
+    WeightedMean,
+}
+
+impl ConfidenceAggregator {
+    /// Combined confidence of `edges`; 0.0 for an empty chain
+    pub fn aggregate(&self, edges: &[&GraphEdge]) -> f32 {
+        self.aggregate_with(edges, |e| e.metadata.confidence)
+    }
+
+    /// `aggregate` over per-edge confidences taken from `confidence`, e.g.
+    /// `MultiIntentGraph::net_support`
+    pub fn aggregate_with(&self, edges: &[&GraphEdge], confidence: impl Fn(&GraphEdge) -> f32) -> f32 {
+        if edges.is_empty() {
+            return 0.0;
+        }
+        let confidences = edges.iter().map(|e| confidence(e).clamp(0.0, 1.0));
+        match self {
+            Self::Product => confidences.product(),
+            Self::Min => confidences.fold(1.0, f32::min),
+            Self::NoisyOr => 1.0 - confidences.map(|c| 1.0 - c).product::<f32>(),
+            Self::WeightedMean => {
+                let weights: Vec<f32> = edges.iter().map(|e| e.metadata.evidence_refs.len().max(1) as f32).collect();
+                confidences.zip(&weights).map(|(c, w)| c * w).sum::<f32>() / weights.iter().sum::<f32>()
+            }
+        }
+    }
+}
+
+/// What `remove_node` does with edges that lose an endpoint
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DanglingEdges {
+    #[default]
+    Delete,
+    Flag,         // keep them, tagged `DANGLING_EDGE_TAG`
+}
+
+/// Failed governance tag put on edges left without an endpoint
+pub const DANGLING_EDGE_TAG: &str = "dangling-edge";
+
+/// What `remove_node` or `remove_edge` changed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Removal {
+    pub removed_nodes: Vec<Uuid>,
+    pub removed_edges: Vec<Uuid>,
+    pub flagged_edges: Vec<Uuid>,     // kept with a dangling endpoint
+    pub removed_paths: Vec<Uuid>,     // hypothesis paths through a removed node or edge
+}
+
+/// Structural problem found in a hypothesis path
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Error)]
+#[serde(tag = "issue", rename_all = "snake_case")]
+pub enum PathIssue {
+    #[error("path has no nodes")]
+    Empty,
+    #[error("{nodes} nodes need {} edges, found {edges}", nodes.saturating_sub(1))]
+    LengthMismatch { nodes: usize, edges: usize },
+    #[error("node {node} is not in the graph")]
+    MissingNode { node: Uuid },
+    #[error("edge {edge} is not in the graph")]
+    MissingEdge { edge: Uuid },
+    #[error("edge {edge} at step {step} does not connect {from} to {to}")]
+    Disconnected { step: usize, edge: Uuid, from: Uuid, to: Uuid },
+    #[error("node {node} appears more than once")]
+    RepeatedNode { node: Uuid },
+    #[error("confidence {confidence} is outside 0..=1")]
+    ConfidenceOutOfRange { confidence: f32 },
+    #[error("edge {edge} at step {step} refutes its relationship")]
+    RefutingEdge { step: usize, edge: Uuid },
+}
+
+/// Mutations of one variant node inside a protein region
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegionVariant {
+    pub node_id: Uuid,
+    pub variant: String,
+    pub mutations: Vec<Mutation>,
+}
+
+/// A protein region annotated on virology nodes and the variant mutations
+/// falling inside it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegionMutations {
+    pub region: ProteinRegion,
+    pub annotated_by: Vec<Uuid>,      // virology nodes carrying the region
+    pub variants: Vec<RegionVariant>, // only variants with a mutation in the region
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphMetadata {
+    pub created_at: String,
+    pub last_updated: String,
+    pub total_nodes: usize,
+    pub total_edges: usize,
+    pub domains_covered: HashSet<String>,
+}
+
+impl MultiIntentGraph {
+    pub fn new(base_graph: SarsCov2Graph) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            base_graph,
+            intent_nodes: HashMap::new(),
+            edges: HashMap::new(),
+            hypothesis_paths: vec![],
+            serendipity_traces: vec![],
+            rd_curves: HashMap::new(),
+            metadata: GraphMetadata {
+                created_at: chrono::Utc::now().to_rfc3339(),
+                last_updated: chrono::Utc::now().to_rfc3339(),
+                total_nodes: 0,
+                total_edges: 0,
+                domains_covered: HashSet::new(),
+            },
+            path_policy: PathPolicy::default(),
+            governance_tags: HashMap::new(),
+            hyperedges: HashMap::new(),
+            evidence_dates: HashMap::new(),
+            propagation: PropagationPolicy::default(),
+            edge_vocabulary: EdgeVocabulary::default(),
+            synonyms: SynonymTable::default(),
+            stale_confidence: None,
+        }
+    }
+
+    /// Add an intent node
+    pub fn add_node(&mut self, node: IntentNode) {
+        self.metadata.domains_covered.insert(format!("{:?}", node.domain));
+        self.invalidate_confidence(node.id);
+        self.intent_nodes.insert(node.id, node);
+        self.metadata.total_nodes = self.intent_nodes.len();
+        self.update_timestamp();
+    }
+
+    /// Add an edge between nodes
+    /// Add an edge between nodes. Replacing an existing edge refreshes the
+    /// confidence and evidence coverage of hypothesis paths that use it;
+    /// adding a refuting edge refreshes every path.
+    pub fn add_edge(&mut self, edge: GraphEdge) {
+        let edge_id = edge.id;
+        let refuting = edge.is_refuting();
+        self.invalidate_confidence(edge.target_id);
+        let replaced = match self.edges.insert(edge.id, edge) {
+            Some(old) => {
+                self.invalidate_confidence(old.target_id);
+                true
+            }
+            None => false,
+        };
+        self.metadata.total_edges = self.edges.len();
+        if refuting {
+            self.refresh_paths(|_| true);
+        } else if replaced {
+            self.refresh_paths(|p| p.edge_sequence.contains(&edge_id));
+        }
+        self.update_timestamp();
+    }
+
+    /// Add a hyperedge. Hyperedges are kept apart from `edges` and not counted
+    /// in `metadata.total_edges`.
+    pub fn add_hyperedge(&mut self, hyperedge: HyperEdge) {
+        for &target in &hyperedge.target_ids {
+            self.invalidate_confidence(target);
+        }
+        self.hyperedges.insert(hyperedge.id, hyperedge);
+        self.update_timestamp();
+    }
+
+    /// Hyperedges with `node_id` among their sources or targets
+    pub fn hyperedges_touching(&self, node_id: Uuid) -> Vec<&HyperEdge> {
+        self.hyperedges.values().filter(|h| h.touches(node_id)).collect()
+    }
+
+    /// Remove an edge or hyperedge, the hypothesis paths through it and its governance tags
+    pub fn remove_edge(&mut self, edge_id: Uuid) -> Option<Removal> {
+        let (refuting, targets) = match self.edges.remove(&edge_id) {
+            Some(edge) => (edge.is_refuting(), vec![edge.target_id]),
+            None => (false, self.hyperedges.remove(&edge_id)?.target_ids),
+        };
+        for target in targets {
+            self.invalidate_confidence(target);
+        }
+        let mut removal = Removal { removed_edges: vec![edge_id], ..Default::default() };
+        self.governance_tags.remove(&edge_id);
+        removal.removed_paths = self.remove_paths(|p| p.edge_sequence.contains(&edge_id));
+        if refuting {
+            self.refresh_paths(|_| true);
+        }
+        self.refresh_counts();
+        Some(removal)
+    }
+
+    /// Remove a node with its governance tags and the hypothesis paths through
+    /// it. Edges and hyperedges touching the node are removed too, or under
+    /// `DanglingEdges::Flag` kept with a failed `DANGLING_EDGE_TAG` for a
+    /// curator to repoint; paths through them are removed either way.
+    pub fn remove_node(&mut self, node_id: Uuid, dangling: DanglingEdges) -> Option<Removal> {
+        self.intent_nodes.remove(&node_id)?;
+        let mut removal = Removal { removed_nodes: vec![node_id], ..Default::default() };
+        self.governance_tags.remove(&node_id);
+        let mut touching: Vec<Uuid> = self.edges.values()
+            .filter(|e| e.source_id == node_id || e.target_id == node_id)
+            .map(|e| e.id)
+            .chain(self.hyperedges.values().filter(|h| h.touches(node_id)).map(|h| h.id))
+            .collect();
+        touching.sort();
+        let downstream: Vec<Uuid> = self.edges.values().filter(|e| e.source_id == node_id).map(|e| e.target_id)
+            .chain(self.hyperedges.values().filter(|h| h.source_ids.contains(&node_id)).flat_map(|h| h.target_ids.clone()))
+            .collect();
+        for target in downstream {
+            self.invalidate_confidence(target);
+        }
+        for edge_id in &touching {
+            match dangling {
+                DanglingEdges::Delete => {
+                    self.edges.remove(edge_id);
+                    self.hyperedges.remove(edge_id);
+                    self.governance_tags.remove(edge_id);
+                }
+                DanglingEdges::Flag => {
+                    self.attach_tag(*edge_id, GovernanceTag {
+                        id: Uuid::new_v4(),
+                        label: DANGLING_EDGE_TAG.into(),
+                        passed: false,
+                        details: Some(format!("endpoint {} was removed", node_id)),
+                    });
+                }
+            }
+        }
+        match dangling {
+            DanglingEdges::Delete => removal.removed_edges = touching.clone(),
+            DanglingEdges::Flag => removal.flagged_edges = touching.clone(),
+        }
+        removal.removed_paths = self.remove_paths(|p| p.node_sequence.contains(&node_id) || p.edge_sequence.iter().any(|e| touching.contains(e)));
+        self.metadata.domains_covered = self.intent_nodes.values().map(|n| format!("{:?}", n.domain)).collect();
+        self.refresh_counts();
+        Some(removal)
+    }
+
+    fn remove_paths(&mut self, through: impl Fn(&HypothesisPath) -> bool) -> Vec<Uuid> {
+        let removed: Vec<Uuid> = self.hypothesis_paths.iter().filter(|p| through(p)).map(|p| p.id).collect();
+        self.hypothesis_paths.retain(|p| !through(p));
+        removed
+    }
+
+    fn refresh_counts(&mut self) {
+        self.metadata.total_nodes = self.intent_nodes.len();
+        self.metadata.total_edges = self.edges.len();
+        self.update_timestamp();
+    }
+
+    /// Recompute confidence and evidence coverage of every hypothesis path,
+    /// e.g. after changing `path_policy` or editing edges in place
+    pub fn recompute_paths(&mut self) {
+        self.refresh_paths(|_| true);
+        self.update_timestamp();
+    }
+
+    fn refresh_paths(&mut self, affected: impl Fn(&HypothesisPath) -> bool) {
+        let updates: Vec<(usize, Option<f32>, Option<f32>)> = self.hypothesis_paths.iter().enumerate()
+            .filter(|(_, p)| affected(p))
+            .map(|(i, p)| (i, self.path_confidence(p), self.path_evidence_coverage(p)))
+            .collect();
+        for (i, confidence, coverage) in updates {
+            let path = &mut self.hypothesis_paths[i];
+            if let Some(c) = confidence {
+                path.total_confidence = c;
+            }
+            if let Some(c) = coverage {
+                path.evidence_coverage = c;
+            }
+        }
+    }
+
+    /// Add a hypothesis path, returning any structural issues found.
+    /// When all of its edges are in the graph, `total_confidence` and
+    /// `evidence_coverage` are recomputed from them. Under a strict `path_policy`
+    /// a path with issues is not added.
+    pub fn add_hypothesis_path(&mut self, mut path: HypothesisPath) -> Vec<PathIssue> {
+        if let Some(confidence) = self.path_confidence(&path) {
+            path.total_confidence = confidence;
+        }
+        if let Some(coverage) = self.path_evidence_coverage(&path) {
+            path.evidence_coverage = coverage;
+        }
+        let issues = self.validate_path(&path);
+        if self.path_policy.strict && !issues.is_empty() {
+            return issues;
+        }
+        self.hypothesis_paths.push(path);
+        self.update_timestamp();
+        issues
+    }
+
+    /// Confidence of `path` from the net support of its edges (see
+    /// `net_support`) under `path_policy.aggregator`; `None` if it has no
+    /// edges or references an edge not in the graph
+    pub fn path_confidence(&self, path: &HypothesisPath) -> Option<f32> {
+        self.path_edges(path).map(|e| self.path_policy.aggregator.aggregate_with(&e, |edge| self.net_support(edge)))
+    }
+
+    /// Share of the path's edges with at least `path_policy.min_evidence_refs`
+    /// distinct references (DOIs deduplicated); `None` if it has no edges or
+    /// references an edge not in the graph
+    pub fn path_evidence_coverage(&self, path: &HypothesisPath) -> Option<f32> {
+        self.path_edges(path).map(|e| self.evidence_coverage(&e))
+    }
+
+    fn path_edges(&self, path: &HypothesisPath) -> Option<Vec<&GraphEdge>> {
+        let edges: Option<Vec<&GraphEdge>> = path.edge_sequence.iter().map(|id| self.edges.get(id)).collect();
+        edges.filter(|e| !e.is_empty())
+    }
+
+    fn evidence_coverage(&self, edges: &[&GraphEdge]) -> f32 {
+        let covered = edges.iter().filter(|e| e.distinct_evidence().len() >= self.path_policy.min_evidence_refs).count();
+        covered as f32 / edges.len() as f32
+    }
+
+    /// Check that a path's nodes and edges exist and that each edge joins
+    /// consecutive nodes. Correlative edges may be traversed either way round;
+    /// refuting edges may not be traversed at all.
+    pub fn validate_path(&self, path: &HypothesisPath) -> Vec<PathIssue> {
+        let mut issues = vec![];
+        if path.node_sequence.is_empty() {
+            issues.push(PathIssue::Empty);
+        } else if path.edge_sequence.len() + 1 != path.node_sequence.len() {
+            issues.push(PathIssue::LengthMismatch { nodes: path.node_sequence.len(), edges: path.edge_sequence.len() });
+        }
+        if !(0.0..=1.0).contains(&path.total_confidence) {
+            issues.push(PathIssue::ConfidenceOutOfRange { confidence: path.total_confidence });
+        }
+
+        let mut seen = HashSet::new();
+        for id in &path.node_sequence {
+            if !self.intent_nodes.contains_key(id) {
+                issues.push(PathIssue::MissingNode { node: *id });
+            }
+            if !seen.insert(*id) {
+                issues.push(PathIssue::RepeatedNode { node: *id });
+            }
+        }
+
+        for (step, edge_id) in path.edge_sequence.iter().enumerate() {
+            let Some(edge) = self.edges.get(edge_id) else {
+                issues.push(PathIssue::MissingEdge { edge: *edge_id });
+                continue;
+            };
+            let (Some(&from), Some(&to)) = (path.node_sequence.get(step), path.node_sequence.get(step + 1)) else {
+                continue;
+            };
+            let forward = edge.source_id == from && edge.target_id == to;
+            let backward = edge.source_id == to && edge.target_id == from;
+            if !(forward || (backward && edge.edge_type == EdgeType::Correlative)) {
+                issues.push(PathIssue::Disconnected { step, edge: *edge_id, from, to });
+            }
+            if edge.is_refuting() {
+                issues.push(PathIssue::RefutingEdge { step, edge: *edge_id });
+            }
+        }
+        issues
+    }
+
+    /// Add a serendipity trace
+    pub fn add_trace(&mut self, trace: SerendipityTrace) {
+        self.serendipity_traces.push(trace);
+        self.update_timestamp();
+    }
+
+    /// Add rate-distortion curve for an intent
+    pub fn add_rd_curve(&mut self, intent: String, curve: RDCurve) {
+        self.rd_curves.insert(intent, curve);
+        self.update_timestamp();
+    }
+
+    /// Add rate-distortion curve for one domain of an intent
+    pub fn add_domain_rd_curve(&mut self, intent: &str, domain: &ResearchDomain, curve: RDCurve) {
+        self.add_rd_curve(rd_curve_key(intent, domain), curve);
+    }
+
+    pub fn domain_rd_curve(&self, intent: &str, domain: &ResearchDomain) -> Option<&RDCurve> {
+        self.rd_curves.get(&rd_curve_key(intent, domain))
+    }
+
+    /// Get all edges of a specific type
+    pub fn edges_by_type(&self, edge_type: EdgeType) -> Vec<&GraphEdge> {
+        self.edges.values()
+            .filter(|e| e.edge_type == edge_type)
+            .collect()
+    }
+
+    /// Get all nodes in a specific domain
+    pub fn nodes_by_domain(&self, domain: ResearchDomain) -> Vec<&IntentNode> {
+        self.intent_nodes.values()
+            .filter(|n| std::mem::discriminant(&n.domain) == std::mem::discriminant(&domain))
+            .collect()
+    }
+
+    /// Nodes scoped to `region` or a place inside it, sorted by ID; a
+    /// country matches its subdivisions too
+    pub fn nodes_in_region(&self, region: &GeoScope) -> Vec<&IntentNode> {
+        let mut nodes: Vec<&IntentNode> = self.intent_nodes.values()
+            .filter(|n| n.region().is_some_and(|r| r.within(region)))
+            .collect();
+        nodes.sort_by_key(|n| n.id);
+        nodes
+    }
+
+    /// Variant nodes with a mutation in `start..=end` of `gene`, sorted by ID;
+    /// e.g. `("S", 319, 541)` for changes in the receptor-binding domain
+    pub fn variants_with_changes_in(&self, gene: &str, start: u32, end: u32) -> Vec<&IntentNode> {
+        let mut nodes: Vec<&IntentNode> = self.intent_nodes.values()
+            .filter(|n| matches!(&n.content, NodeContent::Variant(v) if v.has_change_in(gene, start, end)))
+            .collect();
+        nodes.sort_by_key(|n| n.id);
+        nodes
+    }
+
+    /// Every protein region annotated on a virology node, with the variant
+    /// mutations inside it, ordered by gene and start residue. Regions with
+    /// the same gene, name and residue range are listed once.
+    pub fn mutations_by_region(&self) -> Vec<RegionMutations> {
+        let mut regions: BTreeMap<(String, u32, u32, String), RegionMutations> = BTreeMap::new();
+        for node in self.intent_nodes.values() {
+            let NodeContent::Biology(v) = &node.content else { continue };
+            for region in &v.regions {
+                let key = (region.gene.to_uppercase(), region.start, region.end, region.name.to_lowercase());
+                let entry = regions.entry(key).or_insert_with(|| RegionMutations { region: region.clone(), annotated_by: vec![], variants: vec![] });
+                entry.annotated_by.push(node.id);
+                if entry.region.pdb_id.is_none() {
+                    entry.region.pdb_id = region.pdb_id.clone();
+                }
+            }
+        }
+        let mut variants: Vec<(&IntentNode, &GenomicsNode)> = self.intent_nodes.values()
+            .filter_map(|n| match &n.content {
+                NodeContent::Variant(v) => Some((n, v)),
+                _ => None,
+            })
+            .collect();
+        variants.sort_by_key(|(n, _)| n.id);
+        regions.into_values()
+            .map(|mut entry| {
+                entry.annotated_by.sort();
+                entry.annotated_by.dedup();
+                entry.variants = variants.iter()
+                    .filter_map(|(node, v)| {
+                        let mutations: Vec<Mutation> = v.mutations.iter().filter(|m| entry.region.contains(m)).cloned().collect();
+                        (!mutations.is_empty()).then(|| RegionVariant { node_id: node.id, variant: v.variant.clone(), mutations })
+                    })
+                    .collect();
+                entry
+            })
+            .collect()
+    }
+
+    /// Get cross-domain edges
+    pub fn cross_domain_edges(&self) -> Vec<&GraphEdge> {
+        self.edges.values()
+            .filter(|e| e.is_cross_domain())
+            .collect()
+    }
+
+    /// Induced subgraph on `node_ids`: those nodes, the edges and hyperedges
+    /// between them, their governance tags and the hypothesis paths lying
+    /// entirely inside. Traces, RD curves, evidence dates and the path policy
+    /// carry over; the subgraph gets a new ID.
+    pub fn subgraph(&self, node_ids: &[Uuid]) -> MultiIntentGraph {
+        self.induced(node_ids, |_| true)
+    }
+
+    /// `subgraph`, keeping only edges and hyperedges whose `created_at` passes `edge_created`
+    pub(crate) fn induced(&self, node_ids: &[Uuid], edge_created: impl Fn(&str) -> bool) -> MultiIntentGraph {
+        let keep: HashSet<Uuid> = node_ids.iter().copied().filter(|id| self.intent_nodes.contains_key(id)).collect();
+        let mut sub = MultiIntentGraph::new(self.base_graph.clone());
+        sub.path_policy = self.path_policy.clone();
+        sub.serendipity_traces = self.serendipity_traces.clone();
+        sub.rd_curves = self.rd_curves.clone();
+        sub.evidence_dates = self.evidence_dates.clone();
+        for id in &keep {
+            sub.add_node(self.intent_nodes[id].clone());
+        }
+        for edge in self.edges.values().filter(|e| keep.contains(&e.source_id) && keep.contains(&e.target_id) && edge_created(&e.metadata.created_at)) {
+            sub.add_edge(edge.clone());
+        }
+        for hyperedge in self.hyperedges.values().filter(|h| h.endpoints().all(|n| keep.contains(n)) && edge_created(&h.created_at)) {
+            sub.add_hyperedge(hyperedge.clone());
+        }
+        sub.hypothesis_paths = self.hypothesis_paths.iter()
+            .filter(|p| p.node_sequence.iter().all(|n| keep.contains(n)) && p.edge_sequence.iter().all(|e| sub.edges.contains_key(e)))
+            .cloned()
+            .collect();
+        sub.governance_tags = self.governance_tags.iter()
+            .filter(|(subject, _)| keep.contains(subject) || sub.edges.contains_key(subject) || sub.hyperedges.contains_key(subject))
+            .map(|(subject, tags)| (*subject, tags.clone()))
+            .collect();
+        sub
+    }
+
+    /// `center` and every node within `hops` edges of it, edges taken as
+    /// undirected; empty if `center` is not in the graph
+    pub fn neighborhood(&self, center: Uuid, hops: usize) -> Vec<Uuid> {
+        if !self.intent_nodes.contains_key(&center) {
+            return vec![];
+        }
+        let adjacency = self.undirected_adjacency();
+        let mut distance: HashMap<Uuid, usize> = HashMap::from([(center, 0)]);
+        let mut queue = VecDeque::from([center]);
+        while let Some(node) = queue.pop_front() {
+            let d = distance[&node];
+            if d == hops {
+                continue;
+            }
+            for &next in &adjacency[&node] {
+                if let Entry::Vacant(slot) = distance.entry(next) {
+                    slot.insert(d + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+        let mut nodes: Vec<Uuid> = distance.into_keys().collect();
+        nodes.sort();
+        nodes
+    }
+
+    /// Find paths between two nodes. A hyperedge is traversed as a reified
+    /// node: a path through one lists its ID between the source it leaves
+    /// and the target it reaches, and that step counts toward `max_depth`.
+    pub fn find_paths(&self, start_id: Uuid, end_id: Uuid, max_depth: usize) -> Vec<Vec<Uuid>> {
+        let mut paths = vec![];
+        let mut current_path = vec![start_id];
+        let mut visited = HashSet::new();
+        
+        self.dfs_paths(start_id, end_id, &mut current_path, &mut visited, &mut paths, max_depth);
+        paths
+    }
+
+    fn dfs_paths(
+        &self,
+        current: Uuid,
+        target: Uuid,
+        path: &mut Vec<Uuid>,
+        visited: &mut HashSet<Uuid>,
+        paths: &mut Vec<Vec<Uuid>>,
+        max_depth: usize,
+    ) {
+        if path.len() > max_depth {
+            return;
+        }
+
+        if current == target {
+            paths.push(path.clone());
+            return;
+        }
+
+        visited.insert(current);
+
+        // Outgoing edges; from a reified hyperedge, its targets
+        let next: Vec<Uuid> = match self.hyperedges.get(&current) {
+            Some(hyperedge) => hyperedge.target_ids.clone(),
+            None => self.edges.values()
+                .filter(|e| e.source_id == current && !e.is_refuting())
+                .map(|e| e.target_id)
+                .chain(self.hyperedges.values().filter(|h| h.source_ids.contains(&current)).map(|h| h.id))
+                .collect(),
+        };
+        for next in next {
+            if !visited.contains(&next) {
+                path.push(next);
+                self.dfs_paths(next, target, path, visited, paths, max_depth);
+                path.pop();
+            }
+        }
+
+        visited.remove(&current);
+    }
+
+    /// Enumerate causal/correlative chains that fit `hypothesis_type` (e.g.
+    /// Genomics → … → Immunology for immune escape) and materialize them as
+    /// hypothesis paths, strongest first. Path confidence combines the edge
+    /// confidences with `path_policy.aggregator`; chains below `min_confidence` are dropped.
+    /// Paths get stable IDs derived from their edges, so regenerating is idempotent.
+    pub fn generate_hypothesis_paths(&self, hypothesis_type: HypothesisType, min_confidence: f32) -> Vec<HypothesisPath> {
+        let HypothesisDomains { from, to } = self.hypothesis_domains(&hypothesis_type);
+        let in_domains = |id: &Uuid, domains: &[ResearchDomain]| self.intent_nodes.get(id)
+            .is_some_and(|n| domains.iter().any(|d| std::mem::discriminant(d) == std::mem::discriminant(&n.domain)));
+
+        let mut outgoing: HashMap<Uuid, Vec<&GraphEdge>> = HashMap::new();
+        for edge in self.edges.values() {
+            if matches!(edge.edge_type, EdgeType::Causal | EdgeType::Correlative)
+                && !edge.is_refuting()
+                && self.intent_nodes.contains_key(&edge.target_id)
+            {
+                outgoing.entry(edge.source_id).or_default().push(edge);
+            }
+        }
+        for edges in outgoing.values_mut() {
+            edges.sort_by_key(|e| e.id);
+        }
+
+        let mut starts: Vec<Uuid> = self.intent_nodes.keys().filter(|id| in_domains(id, &from)).copied().collect();
+        starts.sort();
+
+        let mut chains = vec![];
+        for start in starts {
+            let mut stack: Vec<(Uuid, Vec<&GraphEdge>)> = vec![(start, vec![])];
+            while let Some((node, chain)) = stack.pop() {
+                if !chain.is_empty() && in_domains(&node, &to) {
+                    chains.push(chain);
+                    continue;
+                }
+                if chain.len() >= MAX_GENERATED_PATH_EDGES {
+                    continue;
+                }
+                for edge in outgoing.get(&node).into_iter().flatten() {
+                    let revisits = edge.target_id == start || chain.iter().any(|e| e.target_id == edge.target_id);
+                    if !revisits {
+                        let mut next = chain.clone();
+                        next.push(edge);
+                        stack.push((edge.target_id, next));
+                    }
+                }
+            }
+        }
+
+        let mut paths: Vec<HypothesisPath> = chains.into_iter()
+            .filter_map(|chain| {
+                let total_confidence = self.path_policy.aggregator.aggregate_with(&chain, |e| self.net_support(e));
+                if total_confidence < min_confidence {
+                    return None;
+                }
+                let node_sequence: Vec<Uuid> = std::iter::once(chain[0].source_id)
+                    .chain(chain.iter().map(|e| e.target_id))
+                    .collect();
+                let edge_sequence: Vec<Uuid> = chain.iter().map(|e| e.id).collect();
+                let labels: Vec<&str> = node_sequence.iter()
+                    .filter_map(|id| self.intent_nodes.get(id).map(|n| n.label()))
+                    .collect();
+                let key = edge_sequence.iter().map(|e| e.to_string()).collect::<Vec<_>>().join(",");
+
+                Some(HypothesisPath {
+                    id: Uuid::new_v5(&Uuid::NAMESPACE_OID, format!("{}:{}", hypothesis_type, key).as_bytes()),
+                    hypothesis_type: hypothesis_type.clone(),
+                    description: format!("{}: {}", hypothesis_type, labels.join(" → ")),
+                    node_sequence,
+                    evidence_coverage: self.evidence_coverage(&chain),
+                    edge_sequence,
+                    total_confidence,
+                })
+            })
+            .collect();
+
+        paths.sort_by(|a, b| b.total_confidence.total_cmp(&a.total_confidence).then_with(|| a.id.cmp(&b.id)));
+        paths
+    }
+
+    /// Domains a hypothesis of each type starts from and ends in. Built-in types
+    /// follow the edge builders (mutation → immune escape, treatment → outcome,
+    /// ...); custom types use `path_policy.custom_hypotheses`, or any domain
+    /// if they are not registered there.
+    pub fn hypothesis_domains(&self, hypothesis_type: &HypothesisType) -> HypothesisDomains {
+        use ResearchDomain::*;
+        let (from, to) = match hypothesis_type {
+            HypothesisType::Transmissibility => (vec![Genomics], vec![Virology]),
+            HypothesisType::VaccineEfficacy | HypothesisType::ImmuneEscape => (vec![Genomics], vec![Immunology]),
+            HypothesisType::TreatmentResponse => (vec![Treatment], vec![PublicHealth, Genomics]),
+            HypothesisType::PublicHealthImpact => (vec![PublicHealth], vec![Virology]),
+            HypothesisType::Custom(name) => match self.path_policy.custom_hypotheses.get(name) {
+                Some(domains) => return domains.clone(),
+                None => (ALL_DOMAINS.to_vec(), ALL_DOMAINS.to_vec()),
+            },
+        };
+        HypothesisDomains { from, to }
+    }
+
+    /// Attach `tag` to a node, edge or hyperedge, replacing any tag with the
+    /// same ID. Returns false, attaching nothing, if `subject` is none of them.
+    pub fn attach_tag(&mut self, subject: Uuid, tag: GovernanceTag) -> bool {
+        if !self.intent_nodes.contains_key(&subject) && !self.edges.contains_key(&subject) && !self.hyperedges.contains_key(&subject) {
+            return false;
+        }
+        let tags = self.governance_tags.entry(subject).or_default();
+        tags.retain(|t| t.id != tag.id);
+        tags.push(tag);
+        self.update_timestamp();
+        true
+    }
+
+    pub fn detach_tag(&mut self, subject: Uuid, tag_id: Uuid) -> Option<GovernanceTag> {
+        let tags = self.governance_tags.get_mut(&subject)?;
+        let index = tags.iter().position(|t| t.id == tag_id)?;
+        let tag = tags.remove(index);
+        if tags.is_empty() {
+            self.governance_tags.remove(&subject);
+        }
+        self.update_timestamp();
+        Some(tag)
+    }
+
+    pub fn tags_for(&self, subject: Uuid) -> &[GovernanceTag] {
+        self.governance_tags.get(&subject).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// (subject, tag) pairs, optionally only those with `label`, sorted by subject
+    pub fn tagged(&self, label: Option<&str>) -> Vec<(Uuid, &GovernanceTag)> {
+        let mut out: Vec<(Uuid, &GovernanceTag)> = self.governance_tags.iter()
+            .flat_map(|(subject, tags)| tags.iter().map(move |t| (*subject, t)))
+            .filter(|(_, t)| label.is_none_or(|l| t.label == l))
+            .collect();
+        out.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.label.cmp(&b.1.label)));
+        out
+    }
+
+    /// Whether a node or edge carries any tag that did not pass
+    pub fn has_failed_tag(&self, subject: Uuid) -> bool {
+        self.tags_for(subject).iter().any(|t| !t.passed)
+    }
+
+    /// Calculate graph statistics
+    pub fn statistics(&self) -> GraphStatistics {
+        let supporting = |edge_type| self.edges_by_type(edge_type).iter().filter(|e| !e.is_refuting()).count();
+        let causal_edges = supporting(EdgeType::Causal);
+        let correlative_edges = supporting(EdgeType::Correlative);
+        let mut edge_types: BTreeMap<String, usize> = BTreeMap::new();
+        for edge in self.edges.values().filter(|e| !e.is_refuting()) {
+            *edge_types.entry(edge.edge_type.name().to_string()).or_default() += 1;
+        }
+        let cross_domain = self.cross_domain_edges().len();
+        let tagged = |ids: &HashSet<&Uuid>| self.governance_tags.keys().filter(|id| ids.contains(id)).count();
+        let node_ids: HashSet<&Uuid> = self.intent_nodes.keys().collect();
+        let edge_ids: HashSet<&Uuid> = self.edges.keys().chain(self.hyperedges.keys()).collect();
+
+        let mut regions: BTreeMap<String, RegionStatistics> = BTreeMap::new();
+        for node in self.intent_nodes.values() {
+            if let Some(region) = node.region() {
+                let entry = regions.entry(region.to_string()).or_default();
+                entry.nodes += 1;
+                *entry.domains.entry(format!("{:?}", node.domain)).or_default() += 1;
+            }
+        }
+        let scope = |id: &Uuid| self.intent_nodes.get(id).and_then(IntentNode::region).map(GeoScope::to_string);
+        for edge in self.edges.values() {
+            let mut touched = vec![scope(&edge.source_id), scope(&edge.target_id)];
+            touched.dedup();
+            for region in touched.into_iter().flatten() {
+                regions.get_mut(&region).expect("scope of a node").edges += 1;
+            }
+        }
+        
+        let avg_trace_diversity = if !self.serendipity_traces.is_empty() {
+            parallel::map(&self.serendipity_traces, SerendipityTrace::diversity_score)
+                .iter()
+                .sum::<f32>() / self.serendipity_traces.len() as f32
+        } else {
+            0.0
+        };
+
+        GraphStatistics {
+            total_nodes: self.metadata.total_nodes,
+            total_edges: self.metadata.total_edges,
+            causal_edges,
+            correlative_edges,
+            edge_types,
+            cross_domain_edges: cross_domain,
+            hypothesis_paths: self.hypothesis_paths.len(),
+            serendipity_traces: self.serendipity_traces.len(),
+            avg_trace_diversity,
+            domains_covered: self.metadata.domains_covered.len(),
+            tagged_nodes: tagged(&node_ids),
+            tagged_edges: tagged(&edge_ids),
+            failed_tags: self.governance_tags.values().flatten().filter(|t| !t.passed).count(),
+            hyperedges: self.hyperedges.len(),
+            refuting_edges: self.edges.values().filter(|e| e.is_refuting()).count(),
+            structure: self.structural_metrics(),
+            unscoped_nodes: self.intent_nodes.values().filter(|n| n.region().is_none()).count(),
+            regions,
+        }
+    }
+
+    /// Neighbour sets of the intent graph read as undirected and simple:
+    /// direction, parallel edges, self-loops and refuting edges are ignored.
+    /// A hyperedge links each of its sources to each of its targets.
+    fn undirected_adjacency(&self) -> HashMap<Uuid, HashSet<Uuid>> {
+        let mut adjacency: HashMap<Uuid, HashSet<Uuid>> = self.intent_nodes.keys().map(|&id| (id, HashSet::new())).collect();
+        let links = self.edges.values()
+            .filter(|e| !e.is_refuting())
+            .map(|e| (e.source_id, e.target_id))
+            .chain(self.hyperedges.values().flat_map(|h| h.pairs()));
+        for (source, target) in links {
+            if source == target || !self.intent_nodes.contains_key(&source) || !self.intent_nodes.contains_key(&target) {
+                continue;
+            }
+            adjacency.get_mut(&source).expect("node present").insert(target);
+            adjacency.get_mut(&target).expect("node present").insert(source);
+        }
+        adjacency
+    }
+
+    pub fn structural_metrics(&self) -> StructuralMetrics {
+        let adjacency = self.undirected_adjacency();
+        let n = adjacency.len();
+        let links = adjacency.values().map(HashSet::len).sum::<usize>() / 2;
+        let density = if n > 1 { 2.0 * links as f32 / (n * (n - 1)) as f32 } else { 0.0 };
+
+        let mut degree_histogram = BTreeMap::new();
+        for neighbours in adjacency.values() {
+            *degree_histogram.entry(neighbours.len()).or_insert(0) += 1;
+        }
+
+        let mut nodes: Vec<Uuid> = adjacency.keys().copied().collect();
+        nodes.sort();
+
+        // Local clustering: share of a node's neighbour pairs that are linked; 0 below degree 2.
+        // Summed in node order so the mean does not depend on hashing or threads.
+        let avg_clustering = if n > 0 {
+            parallel::map(&nodes, |id| {
+                let neighbours = &adjacency[id];
+                let k = neighbours.len();
+                if k < 2 {
+                    return 0.0;
+                }
+                let closed = neighbours.iter()
+                    .map(|a| adjacency[a].iter().filter(|b| neighbours.contains(b)).count())
+                    .sum::<usize>() / 2;
+                2.0 * closed as f32 / (k * (k - 1)) as f32
+            })
+            .iter()
+            .sum::<f32>() / n as f32
+        } else {
+            0.0
+        };
+
+        // Components, and per component a double-sweep BFS: the farthest node from an
+        // arbitrary start, then the farthest from that. Exact on trees, a lower bound otherwise.
+        let mut seen: HashSet<Uuid> = HashSet::new();
+        let mut connected_components = 0;
+        let mut diameter_estimate = 0;
+        for start in nodes {
+            if seen.contains(&start) {
+                continue;
+            }
+            connected_components += 1;
+            let (far, _, component) = bfs_farthest(&adjacency, start);
+            let (_, eccentricity, _) = bfs_farthest(&adjacency, far);
+            diameter_estimate = diameter_estimate.max(eccentricity);
+            seen.extend(component);
+        }
+
+        StructuralMetrics { density, avg_clustering, degree_histogram, connected_components, diameter_estimate }
+    }
+
+    pub(crate) fn update_timestamp(&mut self) {
+        self.metadata.last_updated = chrono::Utc::now().to_rfc3339();
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphStatistics {
+    pub total_nodes: usize,
+    pub total_edges: usize,
+    pub causal_edges: usize,
+    pub correlative_edges: usize,
+    #[serde(default)]
+    pub edge_types: BTreeMap<String, usize>,  // supporting edges per type name, built-in and custom
+    pub cross_domain_edges: usize,
+    pub hypothesis_paths: usize,
+    pub serendipity_traces: usize,
+    pub avg_trace_diversity: f32,
+    pub domains_covered: usize,
+    #[serde(default)]
+    pub tagged_nodes: usize,
+    #[serde(default)]
+    pub tagged_edges: usize,
+    #[serde(default)]
+    pub failed_tags: usize,       // governance tags with `passed == false`
+    #[serde(default)]
+    pub structure: StructuralMetrics,
+    #[serde(default)]
+    pub hyperedges: usize,
+    #[serde(default)]
+    pub refuting_edges: usize,    // `Polarity::Refutes`; not counted in `causal_edges`/`correlative_edges`
+    #[serde(default)]
+    pub unscoped_nodes: usize,    // nodes without a `region`
+    #[serde(default)]
+    pub regions: BTreeMap<String, RegionStatistics>,  // keyed by scope code ("ID", "US-CA"), as recorded
+}
+
+/// Nodes and edges of one geographic scope
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RegionStatistics {
+    pub nodes: usize,
+    pub domains: BTreeMap<String, usize>,  // nodes per research domain
+    pub edges: usize,                      // edges with an endpoint in the scope
+}
+
+/// Shape of the intent graph, taken as undirected and simple
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StructuralMetrics {
+    pub density: f32,                             // linked pairs / possible pairs
+    pub avg_clustering: f32,                      // mean local clustering coefficient
+    pub degree_histogram: BTreeMap<usize, usize>, // degree -> node count
+    pub connected_components: usize,
+    pub diameter_estimate: usize,                 // hops; double-sweep lower bound per component
+}
+
+/// Farthest node from `start` by BFS, its distance, and every node reached
+fn bfs_farthest(adjacency: &HashMap<Uuid, HashSet<Uuid>>, start: Uuid) -> (Uuid, usize, Vec<Uuid>) {
+    let mut distance: HashMap<Uuid, usize> = HashMap::from([(start, 0)]);
+    let mut queue = VecDeque::from([start]);
+    let mut reached = vec![];
+    let (mut far, mut far_distance) = (start, 0);
+    while let Some(node) = queue.pop_front() {
+        let d = distance[&node];
+        if d > far_distance || (d == far_distance && node < far) {
+            (far, far_distance) = (node, d);
+        }
+        reached.push(node);
+        let mut next: Vec<Uuid> = adjacency[&node].iter().filter(|n| !distance.contains_key(n)).copied().collect();
+        next.sort();
+        for n in next {
+            distance.insert(n, d + 1);
+            queue.push_back(n);
+        }
+    }
+    (far, far_distance, reached)
+}
+
+/// Key of a per-domain curve in `rd_curves`, e.g. "immune_escape/Genomics"
+pub fn rd_curve_key(intent: &str, domain: &ResearchDomain) -> String {
+    format!("{}/{:?}", intent, domain)
+}
+
+/// Longest chain `generate_hypothesis_paths` will follow
+const MAX_GENERATED_PATH_EDGES: usize = 4;
+
+const ALL_DOMAINS: [ResearchDomain; 5] = [
+    ResearchDomain::Virology,
+    ResearchDomain::Immunology,
+    ResearchDomain::Genomics,
+    ResearchDomain::Treatment,
+    ResearchDomain::PublicHealth,
+];
+
+/// Builder for constructing multi-intent graphs
+pub struct MultiIntentGraphBuilder {
+    graph: MultiIntentGraph,
+}
+
+impl MultiIntentGraphBuilder {
+    pub fn new(base_graph: SarsCov2Graph) -> Self {
+        Self {
+            graph: MultiIntentGraph::new(base_graph),
+        }
+    }
+
+    pub fn with_biology_node(mut self, virology: VirologyNode, intent: &str, evidence: usize, confidence: f32) -> Self {
+        let node = IntentNode {
+            id: virology.id,
+            intent: intent.into(),
+            domain: ResearchDomain::Virology,
+            content: NodeContent::Biology(virology),
+            metadata: NodeMetadata {
+                evidence_count: evidence,
+                confidence,
+                sources: vec![],
+                created_at: chrono::Utc::now().to_rfc3339(),
+                region: None,
+                xrefs: vec![],
+                embedding: None,
+            },
+        };
+        self.graph.add_node(node);
+        self
+    }
+
+    pub fn with_immunology_node(mut self, immunology: ImmunologyNode, intent: &str, evidence: usize, confidence: f32) -> Self {
+        let node = IntentNode {
+            id: immunology.id,
+            intent: intent.into(),
+            domain: ResearchDomain::Immunology,
+            content: NodeContent::Immunology(immunology),
+            metadata: NodeMetadata {
+                evidence_count: evidence,
+                confidence,
+                sources: vec![],
+                created_at: chrono::Utc::now().to_rfc3339(),
+                region: None,
+                xrefs: vec![],
+                embedding: None,
+            },
+        };
+        self.graph.add_node(node);
+        self
+    }
+
+    pub fn with_variant_node(mut self, genomics: GenomicsNode, intent: &str, evidence: usize, confidence: f32) -> Self {
+        let node = IntentNode {
+            id: genomics.id,
+            intent: intent.into(),
+            domain: ResearchDomain::Genomics,
+            content: NodeContent::Variant(genomics),
+            metadata: NodeMetadata {
+                evidence_count: evidence,
+                confidence,
+                sources: vec![],
+                created_at: chrono::Utc::now().to_rfc3339(),
+                region: None,
+                xrefs: vec![],
+                embedding: None,
+            },
+        };
+        self.graph.add_node(node);
+        self
+    }
+
+    pub fn with_treatment_node(mut self, treatment: TreatmentNode, intent: &str, evidence: usize, confidence: f32) -> Self {
+        let node = IntentNode {
+            id: treatment.id,
+            intent: intent.into(),
+            domain: ResearchDomain::Treatment,
+            content: NodeContent::Treatment(treatment),
+            metadata: NodeMetadata {
+                evidence_count: evidence,
+                confidence,
+                sources: vec![],
+                created_at: chrono::Utc::now().to_rfc3339(),
+                region: None,
+                xrefs: vec![],
+                embedding: None,
+            },
+        };
+        self.graph.add_node(node);
+        self
+    }
+
+    pub fn with_public_health_node(mut self, ph: PublicHealthNode, intent: &str, evidence: usize, confidence: f32) -> Self {
+        let node = IntentNode {
+            id: ph.id,
+            intent: intent.into(),
+            domain: ResearchDomain::PublicHealth,
+            content: NodeContent::PublicHealth(ph),
+            metadata: NodeMetadata {
+                evidence_count: evidence,
+                confidence,
+                sources: vec![],
+                created_at: chrono::Utc::now().to_rfc3339(),
+                region: None,
+                xrefs: vec![],
+                embedding: None,
+            },
+        };
+        self.graph.add_node(node);
+        self
+    }
+
+    /// Vaccines sit in the Immunology domain
+    pub fn with_vaccine_node(mut self, vaccine: VaccineNode, intent: &str, evidence: usize, confidence: f32) -> Self {
+        let node = IntentNode {
+            id: vaccine.id,
+            intent: intent.into(),
+            domain: ResearchDomain::Immunology,
+            content: NodeContent::Vaccine(vaccine),
+            metadata: NodeMetadata {
+                evidence_count: evidence,
+                confidence,
+                sources: vec![],
+                created_at: chrono::Utc::now().to_rfc3339(),
+                region: None,
+                xrefs: vec![],
+                embedding: None,
+            },
+        };
+        self.graph.add_node(node);
+        self
+    }
+
+    /// Trials sit in the Treatment domain
+    pub fn with_clinical_trial_node(mut self, trial: ClinicalTrialNode, intent: &str, evidence: usize, confidence: f32) -> Self {
+        let node = IntentNode {
+            id: trial.id,
+            intent: intent.into(),
+            domain: ResearchDomain::Treatment,
+            content: NodeContent::ClinicalTrial(trial),
+            metadata: NodeMetadata {
+                evidence_count: evidence,
+                confidence,
+                sources: vec![],
+                created_at: chrono::Utc::now().to_rfc3339(),
+                region: None,
+                xrefs: vec![],
+                embedding: None,
+            },
+        };
+        self.graph.add_node(node);
+        self
+    }
+
+    /// Host factors sit in the PublicHealth domain
+    pub fn with_host_factor_node(mut self, factor: HostFactorNode, intent: &str, evidence: usize, confidence: f32) -> Self {
+        let node = IntentNode {
+            id: factor.id,
+            intent: intent.into(),
+            domain: ResearchDomain::PublicHealth,
+            content: NodeContent::HostFactor(factor),
+            metadata: NodeMetadata {
+                evidence_count: evidence,
+                confidence,
+                sources: vec![],
+                created_at: chrono::Utc::now().to_rfc3339(),
+                region: None,
+                xrefs: vec![],
+                embedding: None,
+            },
+        };
+        self.graph.add_node(node);
+        self
+    }
+
+    pub fn with_edge(mut self, edge: GraphEdge) -> Self {
+        self.graph.add_edge(edge);
+        self
+    }
+
+    pub fn with_hyperedge(mut self, hyperedge: HyperEdge) -> Self {
+        self.graph.add_hyperedge(hyperedge);
+        self
+    }
+
+    /// Reject hypothesis paths that fail validation from here on
+    pub fn with_strict_paths(mut self) -> Self {
+        self.graph.path_policy.strict = true;
+        self
+    }
+
+    pub fn with_confidence_aggregator(mut self, aggregator: ConfidenceAggregator) -> Self {
+        self.graph.path_policy.aggregator = aggregator;
+        self
+    }
+
+    pub fn with_min_evidence_refs(mut self, min_refs: usize) -> Self {
+        self.graph.path_policy.min_evidence_refs = min_refs;
+        self
+    }
+
+    /// Accept the custom edge type `name`. Panics if `name` is built in or not
+    /// a valid edge type name; see `EdgeType::parse`.
+    pub fn with_edge_type(mut self, name: &str, description: &str) -> Self {
+        if let Err(e) = self.graph.edge_vocabulary.register(name, description) {
+            panic!("with_edge_type: {}", e);
+        }
+        self
+    }
+
+    /// Register the start and end domains of a custom hypothesis type
+    pub fn with_custom_hypothesis(mut self, name: &str, from: Vec<ResearchDomain>, to: Vec<ResearchDomain>) -> Self {
+        self.graph.path_policy.custom_hypotheses.insert(name.trim().to_string(), HypothesisDomains { from, to });
+        self
+    }
+
+    pub fn with_hypothesis_path(mut self, path: HypothesisPath) -> Self {
+        self.graph.add_hypothesis_path(path);
+        self
+    }
+
+    pub fn with_trace(mut self, trace: SerendipityTrace) -> Self {
+        self.graph.add_trace(trace);
+        self
+    }
+
+    pub fn with_rd_curve(mut self, intent: String, curve: RDCurve) -> Self {
+        self.graph.add_rd_curve(intent, curve);
+        self
+    }
+
+    pub fn build(self) -> MultiIntentGraph {
+        self.graph
+    }
+}
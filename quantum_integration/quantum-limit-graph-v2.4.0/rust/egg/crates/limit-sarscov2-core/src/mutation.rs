@@ -0,0 +1,273 @@
+// limit-sarscov2-core/src/mutation.rs
+// Structured SARS-CoV-2 mutations and a parser for the common notations
+
+use serde::{Serialize, Deserialize};
+use regex::Regex;
+use std::{fmt, str::FromStr, sync::OnceLock};
+use thiserror::Error;
+
+/// Gene name used for genome (nucleotide) coordinates, as in Nextstrain
+pub const NUC: &str = "nuc";
+
+/// Wuhan-Hu-1 genome length (MN908947.3)
+pub const GENOME_LENGTH: u32 = 29_903;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum MutationKind {
+    Substitution,     // L452R
+    Deletion,         // HV69-70del, Δ69-70, Y144del
+    Insertion,        // ins214EPE
+    Nucleotide,       // nuc:C241T, A23403G
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Mutation {
+    pub gene: Option<String>,     // "S", "ORF1a", "nuc"; None when not stated
+    pub position: u32,            // first affected residue / nucleotide
+    pub reference: String,        // "L"; deleted residues ("HV") or "" if unstated
+    pub alternate: String,        // "R"; inserted residues; "" for deletions
+    pub kind: MutationKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_position: Option<u32>, // last residue of a multi-residue deletion
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum MutationParseError {
+    #[error("unrecognized mutation notation: {0}")]
+    Unrecognized(String),
+    #[error("unknown gene '{0}'")]
+    UnknownGene(String),
+    #[error("position {position} outside {gene} (length {length})")]
+    OutOfRange { gene: String, position: u32, length: u32 },
+    #[error("reference and alternate are identical in {0}")]
+    Synonymous(String),
+    #[error("deleted residues '{residues}' do not span positions {start}-{end}")]
+    SpanMismatch { residues: String, start: u32, end: u32 },
+}
+
+impl Mutation {
+    /// Last position touched by this mutation
+    pub fn end(&self) -> u32 {
+        self.end_position.unwrap_or(self.position)
+    }
+
+    /// Whether the mutation touches any position in `start..=end` of `gene`
+    pub fn overlaps(&self, gene: &str, start: u32, end: u32) -> bool {
+        self.gene.as_deref().is_some_and(|g| g.eq_ignore_ascii_case(gene))
+            && self.position <= end
+            && self.end() >= start
+    }
+
+    fn validate(self, raw: &str) -> Result<Self, MutationParseError> {
+        let gene = self.gene.as_deref().unwrap_or("");
+        let length = match self.gene.as_deref() {
+            Some(g) => gene_length(g).ok_or_else(|| MutationParseError::UnknownGene(g.into()))?,
+            None => MAX_PROTEIN_LENGTH,
+        };
+        if self.position == 0 || self.end() > length || self.end() < self.position {
+            return Err(MutationParseError::OutOfRange {
+                gene: if gene.is_empty() { "any gene".into() } else { gene.into() },
+                position: self.end().max(self.position),
+                length,
+            });
+        }
+        match self.kind {
+            MutationKind::Substitution | MutationKind::Nucleotide if self.reference == self.alternate => {
+                Err(MutationParseError::Synonymous(raw.into()))
+            }
+            MutationKind::Deletion if !self.reference.is_empty()
+                && self.reference.len() as u32 != self.end() - self.position + 1 =>
+            {
+                Err(MutationParseError::SpanMismatch {
+                    residues: self.reference.clone(),
+                    start: self.position,
+                    end: self.end(),
+                })
+            }
+            _ => Ok(self),
+        }
+    }
+}
+
+impl fmt::Display for Mutation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Nucleotide substitutions past the longest protein are unambiguous without the `nuc:` prefix
+        let implied = self.kind == MutationKind::Nucleotide && self.position > MAX_PROTEIN_LENGTH;
+        if let Some(g) = self.gene.as_deref().filter(|_| !implied) {
+            write!(f, "{}:", g)?;
+        }
+        match self.kind {
+            MutationKind::Substitution | MutationKind::Nucleotide => {
+                write!(f, "{}{}{}", self.reference, self.position, self.alternate)
+            }
+            MutationKind::Deletion => match self.end_position {
+                Some(end) => write!(f, "{}{}-{}del", self.reference, self.position, end),
+                None => write!(f, "{}{}del", self.reference, self.position),
+            },
+            MutationKind::Insertion => write!(f, "ins{}{}", self.position, self.alternate),
+        }
+    }
+}
+
+impl FromStr for Mutation {
+    type Err = MutationParseError;
+
+    /// Accepts `L452R`, `S:L452R`, `Q27*`, `nuc:C241T`, `A23403G`, `23403A>G`,
+    /// `HV69-70del`, `H69del`, `Δ69-70`, `ΔY144`, `del69/70`, `F157-`,
+    /// `ins214EPE`, `214insEPE` and `ins_22204:GAGCCAGAA`. A gene-less
+    /// substitution is nucleotide only past the longest protein: `C241T` could
+    /// be Cys241Thr, so it stays an amino-acid substitution.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let raw = s.trim();
+        let p = patterns();
+
+        let (gene, body) = match p.gene_prefix.captures(raw) {
+            Some(c) => (Some(canonical_gene(&c["gene"])), &raw[c.get(0).unwrap().end()..]),
+            None => (None, raw),
+        };
+
+        let m = if let Some(c) = p.substitution.captures(body) {
+            let (reference, alternate) = (c["ref"].to_string(), c["alt"].to_string());
+            let position = c["pos"].parse().unwrap_or(0);
+            let nucleotide = gene.as_deref() == Some(NUC)
+                || (gene.is_none() && position > MAX_PROTEIN_LENGTH && is_nucleotide(&reference) && is_nucleotide(&alternate));
+            Mutation {
+                gene: if nucleotide { Some(NUC.into()) } else { gene },
+                position,
+                reference,
+                alternate,
+                kind: if nucleotide { MutationKind::Nucleotide } else { MutationKind::Substitution },
+                end_position: None,
+            }
+        } else if let Some(c) = p.hgvs.captures(body) {
+            Mutation {
+                gene: Some(NUC.into()),
+                position: c["pos"].parse().unwrap_or(0),
+                reference: c["ref"].to_string(),
+                alternate: c["alt"].to_string(),
+                kind: MutationKind::Nucleotide,
+                end_position: None,
+            }
+        } else if let Some(c) = p.deletion_suffix.captures(body).or_else(|| p.deletion_prefix.captures(body)) {
+            let position = c["start"].parse().unwrap_or(0);
+            let end_position = c.name("end").and_then(|e| e.as_str().parse().ok()).filter(|e| *e != position);
+            Mutation {
+                gene,
+                position,
+                reference: c.name("ref").map(|r| r.as_str().to_string()).unwrap_or_default(),
+                alternate: String::new(),
+                kind: MutationKind::Deletion,
+                end_position,
+            }
+        } else if let Some(c) = p.insertion.captures(body) {
+            let position = c.name("pos").or_else(|| c.name("pos2")).map(|m| m.as_str()).unwrap_or("0");
+            let alternate = c.name("alt").or_else(|| c.name("alt2")).map(|m| m.as_str()).unwrap_or("");
+            Mutation {
+                gene,
+                position: position.parse().unwrap_or(0),
+                reference: String::new(),
+                alternate: alternate.to_string(),
+                kind: MutationKind::Insertion,
+                end_position: None,
+            }
+        } else if let Some(c) = p.nuc_insertion.captures(body) {
+            Mutation {
+                gene: Some(NUC.into()),
+                position: c["pos"].parse().unwrap_or(0),
+                reference: String::new(),
+                alternate: c["alt"].to_string(),
+                kind: MutationKind::Insertion,
+                end_position: None,
+            }
+        } else {
+            return Err(MutationParseError::Unrecognized(raw.into()));
+        };
+
+        m.validate(raw)
+    }
+}
+
+/// Extract every valid mutation mentioned in free text, in order of first
+/// appearance and without duplicates. Tokens that look like mutations but fail
+/// validation (unknown gene, out-of-range position) are skipped.
+pub fn parse_mutations(text: &str) -> Vec<Mutation> {
+    let mut found: Vec<Mutation> = vec![];
+    for token in text.split(|c: char| c.is_whitespace() || ",;()[]{}\"'".contains(c)) {
+        let token = token.trim_end_matches(['.', ':']);
+        if token.len() < 3 || !token.chars().any(|c| c.is_ascii_digit()) {
+            continue;
+        }
+        if let Ok(m) = token.parse::<Mutation>() {
+            if !found.contains(&m) {
+                found.push(m);
+            }
+        }
+    }
+    found
+}
+
+/// Longest SARS-CoV-2 protein (ORF1ab polyprotein), used when no gene is given
+const MAX_PROTEIN_LENGTH: u32 = 7_096;
+
+/// Protein lengths (residues) for Wuhan-Hu-1; genome length for `nuc`
+pub fn gene_length(gene: &str) -> Option<u32> {
+    let g = gene.to_ascii_uppercase();
+    if let Some(n) = g.strip_prefix("NSP") {
+        return n.parse::<u8>().ok().filter(|n| (1..=16).contains(n)).map(|_| MAX_PROTEIN_LENGTH);
+    }
+    Some(match g.as_str() {
+        "NUC" => GENOME_LENGTH,
+        "S" => 1273,
+        "E" => 75,
+        "M" => 222,
+        "N" => 419,
+        "ORF1A" => 4405,
+        "ORF1B" => 2695,
+        "ORF1AB" => MAX_PROTEIN_LENGTH,
+        "ORF3A" => 275,
+        "ORF6" => 61,
+        "ORF7A" => 121,
+        "ORF7B" => 43,
+        "ORF8" => 121,
+        "ORF9B" => 97,
+        "ORF10" => 38,
+        _ => return None,
+    })
+}
+
+fn canonical_gene(gene: &str) -> String {
+    match gene.to_ascii_uppercase().as_str() {
+        "SPIKE" => "S".into(),
+        "NUC" => NUC.into(),
+        g if g.starts_with("ORF") => format!("ORF{}", &gene[3..].to_ascii_lowercase()),
+        g if g.starts_with("NSP") => format!("nsp{}", &gene[3..]),
+        g => g.to_string(),
+    }
+}
+
+fn is_nucleotide(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| matches!(c, 'A' | 'C' | 'G' | 'T'))
+}
+
+struct Patterns {
+    gene_prefix: Regex,
+    substitution: Regex,
+    hgvs: Regex,
+    deletion_suffix: Regex,
+    deletion_prefix: Regex,
+    insertion: Regex,
+    nuc_insertion: Regex,
+}
+
+fn patterns() -> &'static Patterns {
+    static PATTERNS: OnceLock<Patterns> = OnceLock::new();
+    PATTERNS.get_or_init(|| Patterns {
+        gene_prefix: Regex::new(r"^(?i)(?P<gene>spike|nuc|s|e|m|n|orf\d+[ab]?|nsp\d+)[:_]").unwrap(),
+        substitution: Regex::new(r"^(?P<ref>[ACDEFGHIKLMNPQRSTVWY])(?P<pos>\d{1,5})(?P<alt>[ACDEFGHIKLMNPQRSTVWY*])$").unwrap(),
+        hgvs: Regex::new(r"^(?:g\.)?(?P<pos>\d{1,5})(?P<ref>[ACGT])>(?P<alt>[ACGT])$").unwrap(),
+        deletion_suffix: Regex::new(r"^(?P<ref>[A-Z]*)(?P<start>\d{1,5})(?:[-/_](?P<end>\d{1,5}))?(?:del|-)$").unwrap(),
+        deletion_prefix: Regex::new(r"^(?:Δ|del)(?P<ref>[A-Z]*)(?P<start>\d{1,5})(?:[-/_](?P<end>\d{1,5}))?$").unwrap(),
+        insertion: Regex::new(r"^(?:ins(?P<pos>\d{1,5})(?P<alt>[A-Z]+)|(?P<pos2>\d{1,5})ins(?P<alt2>[A-Z]+))$").unwrap(),
+        nuc_insertion: Regex::new(r"^ins_(?P<pos>\d{1,5}):(?P<alt>[ACGT]+)$").unwrap(),
+    })
+}
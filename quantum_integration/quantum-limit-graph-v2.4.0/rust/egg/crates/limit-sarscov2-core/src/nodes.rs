@@ -0,0 +1,270 @@
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+
+use crate::{geo::GeoScope, lineage::PangoLineage, mutation::Mutation};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VirusNode {
+    pub id: Uuid,
+    pub name: String,         // "SARS-CoV-2"
+    pub genome_kb: f32,       // ~30.0
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VirologyNode {
+    pub id: Uuid,
+    pub topic: String,        // "Spike-ACE2 binding"
+    pub details: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub regions: Vec<ProteinRegion>,  // protein domains the node is about
+}
+
+/// Annotated stretch of a viral protein, e.g. the spike RBD (S 319–541)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ProteinRegion {
+    pub gene: String,         // "S", "ORF1a", "N"
+    pub name: String,         // "RBD", "NTD", "furin cleavage site"
+    pub start: u32,           // first residue
+    pub end: u32,             // last residue, inclusive
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pdb_id: Option<String>,  // structure reference, e.g. "6M0J"
+}
+
+impl ProteinRegion {
+    pub fn new(gene: impl Into<String>, name: impl Into<String>, start: u32, end: u32) -> Self {
+        Self { gene: gene.into(), name: name.into(), start, end, pdb_id: None }
+    }
+
+    pub fn with_pdb(mut self, pdb_id: impl Into<String>) -> Self {
+        self.pdb_id = Some(pdb_id.into());
+        self
+    }
+
+    /// Whether `mutation` touches any residue of the region
+    pub fn contains(&self, mutation: &Mutation) -> bool {
+        mutation.overlaps(&self.gene, self.start, self.end)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImmunologyNode {
+    pub id: Uuid,
+    pub topic: String,        // "Antibody neutralization", "T-cell response"
+    pub details: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "GenomicsNodeRepr")]
+pub struct GenomicsNode {
+    pub id: Uuid,
+    pub variant: String,      // "Alpha", "Delta", "Omicron"
+    pub mutations: Vec<Mutation>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unparsed_mutations: Vec<String>,  // notations that did not parse, kept verbatim
+}
+
+/// Serialized `GenomicsNode`; mutations may also be notation strings ("S:L452R"),
+/// as written before they were structured
+#[derive(Deserialize)]
+struct GenomicsNodeRepr {
+    id: Uuid,
+    variant: String,
+    #[serde(default)]
+    mutations: Vec<MutationRepr>,
+    #[serde(default)]
+    unparsed_mutations: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum MutationRepr {
+    Structured(Mutation),
+    Notation(String),
+}
+
+impl From<GenomicsNodeRepr> for GenomicsNode {
+    fn from(repr: GenomicsNodeRepr) -> Self {
+        let mut node = GenomicsNode { id: repr.id, variant: repr.variant, mutations: vec![], unparsed_mutations: repr.unparsed_mutations };
+        for m in repr.mutations {
+            match m {
+                MutationRepr::Structured(m) => node.mutations.push(m),
+                MutationRepr::Notation(n) => node.push_notation(&n),
+            }
+        }
+        node
+    }
+}
+
+impl GenomicsNode {
+    /// Node with mutations parsed from notations; unparseable ones go to `unparsed_mutations`
+    pub fn from_notations<S: AsRef<str>>(id: Uuid, variant: impl Into<String>, notations: impl IntoIterator<Item = S>) -> Self {
+        let mut node = GenomicsNode { id, variant: variant.into(), mutations: vec![], unparsed_mutations: vec![] };
+        for n in notations {
+            node.push_notation(n.as_ref());
+        }
+        node
+    }
+
+    fn push_notation(&mut self, notation: &str) {
+        match notation.parse::<Mutation>() {
+            Ok(m) => self.mutations.push(m),
+            Err(_) => self.unparsed_mutations.push(notation.to_string()),
+        }
+    }
+
+    /// Every mutation as notation, unparsed ones last
+    pub fn notations(&self) -> Vec<String> {
+        self.mutations.iter().map(|m| m.to_string()).chain(self.unparsed_mutations.iter().cloned()).collect()
+    }
+
+    /// Whether any mutation touches `start..=end` of `gene`, e.g. the spike RBD (S 319–541)
+    pub fn has_change_in(&self, gene: &str, start: u32, end: u32) -> bool {
+        self.mutations.iter().any(|m| m.overlaps(gene, start, end))
+    }
+
+    /// Pango lineage named by the free-text `variant` ("Omicron BA.5" → BA.5)
+    pub fn lineage(&self) -> Option<PangoLineage> {
+        PangoLineage::find_in(&self.variant)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TreatmentNode {
+    pub id: Uuid,
+    pub therapy: String,      // "Paxlovid", "Remdesivir", "mAbs"
+    pub mechanism: String,    // "Protease inhibitor", etc.
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublicHealthNode {
+    pub id: Uuid,
+    pub policy: String,       // "Mask mandate", "Ventilation"
+    pub effect: String,       // "Reduced transmission", etc.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<GeoScope>,  // where the policy applies
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VaccinePlatform {
+    Mrna,              // BNT162b2, mRNA-1273
+    ViralVector,       // ChAdOx1, Ad26.COV2.S
+    Inactivated,       // CoronaVac, BBIBP-CorV
+    ProteinSubunit,    // NVX-CoV2373
+    LiveAttenuated,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaccineNode {
+    pub id: Uuid,
+    pub name: String,                     // "BNT162b2 bivalent BA.4/5"
+    pub platform: VaccinePlatform,
+    pub doses: u8,                        // primary series
+    pub ve_infection: Option<f32>,        // vaccine effectiveness against infection, 0..1
+    pub ve_severe: Option<f32>,           // against hospitalization or death, 0..1
+    pub variant_context: Option<String>,  // variant the VE estimates were measured against
+}
+
+/// What kind of host factor a `HostFactorNode` is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HostFactorCategory {
+    Demographic,       // age, sex
+    Comorbidity,       // diabetes, obesity, chronic kidney disease
+    Immunological,     // immunosuppression, prior infection, vaccination status
+    Genetic,           // HLA type, IFN pathway variants
+    Behavioral,        // smoking
+}
+
+/// Host characteristic that modifies severity or treatment response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostFactorNode {
+    pub id: Uuid,
+    pub factor: String,       // "Age ≥ 65", "Type 2 diabetes", "Solid organ transplant"
+    pub category: HostFactorCategory,
+    pub details: String,
+}
+
+/// Clinical development phase
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrialPhase {
+    #[serde(rename = "phase_1")]
+    Phase1,
+    #[serde(rename = "phase_2")]
+    Phase2,            // also phase 1/2
+    #[serde(rename = "phase_3")]
+    Phase3,            // also phase 2/3
+    #[serde(rename = "phase_4")]
+    Phase4,            // post-marketing
+    NotApplicable,     // observational studies
+}
+
+/// How participants were assigned to arms
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StudyDesign {
+    RandomizedControlled,
+    NonRandomized,     // single-arm or allocated by clinicians
+    Observational,     // cohort, case-control, registry
+}
+
+impl StudyDesign {
+    pub fn is_randomized(&self) -> bool {
+        matches!(self, Self::RandomizedControlled)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrialStatus {
+    NotYetRecruiting,
+    Recruiting,
+    ActiveNotRecruiting,
+    Completed,
+    Terminated,
+    Withdrawn,
+}
+
+/// Result on the primary endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrialOutcome {
+    #[default]
+    Pending,
+    MetPrimaryEndpoint,
+    MissedPrimaryEndpoint,
+    Inconclusive,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrialArm {
+    pub name: String,             // "Nirmatrelvir/ritonavir", "Placebo"
+    pub treatment_id: Option<Uuid>,   // `TreatmentNode` given in this arm; `None` for placebo / standard of care
+    pub participants: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClinicalTrialNode {
+    pub id: Uuid,
+    pub registry_id: String,      // "NCT04960202", "ISRCTN86534580"
+    pub title: String,
+    pub phase: TrialPhase,
+    pub design: StudyDesign,
+    pub arms: Vec<TrialArm>,
+    pub outcome: TrialOutcome,
+    pub status: TrialStatus,
+}
+
+impl ClinicalTrialNode {
+    /// Whether the trial's evidence is from randomized allocation (an RCT)
+    /// rather than observational data
+    pub fn is_randomized(&self) -> bool {
+        self.design.is_randomized()
+    }
+
+    /// Treatments given in any arm
+    pub fn treatment_ids(&self) -> impl Iterator<Item = Uuid> + '_ {
+        self.arms.iter().filter_map(|a| a.treatment_id)
+    }
+}
@@ -0,0 +1,135 @@
+// limit-sarscov2-core/src/ontology.rs
+// Ontology cross-references: CURIEs linking nodes to terms in other biomedical KGs
+
+use serde::{Serialize, Deserialize};
+use std::{fmt, str::FromStr};
+use thiserror::Error;
+
+use crate::multi_intent_graph::{IntentNode, MultiIntentGraph};
+
+/// Base of OBO Foundry term IRIs (`CHEBI:170007` → `…/obo/CHEBI_170007`)
+pub const OBO_PURL: &str = "http://purl.obolibrary.org/obo/";
+
+/// Prefixes whose local IDs are checked, in their canonical case, with the
+/// number of digits a local ID has (`None`: any number)
+pub const KNOWN_PREFIXES: &[(&str, Option<usize>)] = &[
+    ("CHEBI", None),          // chemical entities: drugs, metabolites
+    ("GO", Some(7)),          // biological processes, functions, components
+    ("MONDO", Some(7)),       // diseases
+    ("HP", Some(7)),          // phenotypes and clinical outcomes
+    ("DOID", None),           // diseases
+    ("UBERON", Some(7)),      // anatomy
+    ("CL", Some(7)),          // cell types
+    ("NCBITaxon", None),      // organisms
+];
+
+/// A term in another ontology, written as a CURIE: `CHEBI:170007`
+/// (nirmatrelvir), `GO:0046718` (viral entry into host cell),
+/// `MONDO:0100096` (COVID-19), `HP:0002098` (respiratory distress). Known
+/// prefixes are kept in their canonical case and their local IDs checked;
+/// other prefixes only need CURIE syntax.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct OntologyRef {
+    pub prefix: String,               // "CHEBI"
+    pub local_id: String,             // "170007"
+}
+
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum OntologyRefError {
+    #[error("{0:?} is not a CURIE (prefix:local_id)")]
+    NotCurie(String),
+    #[error("CURIE prefix {0:?} must start with a letter and use only letters, digits, '_', '-' and '.'")]
+    Prefix(String),
+    #[error("CURIE local ID {0:?} must use only letters, digits, '_', '-' and '.'")]
+    LocalId(String),
+    #[error("{prefix} IDs are {expected}, not {local_id:?}")]
+    KnownFormat { prefix: String, local_id: String, expected: String },
+}
+
+impl OntologyRef {
+    pub fn new(prefix: &str, local_id: &str) -> Result<Self, OntologyRefError> {
+        let (prefix, local_id) = (prefix.trim(), local_id.trim());
+        let mut chars = prefix.chars();
+        if !chars.next().is_some_and(|c| c.is_ascii_alphabetic()) || !chars.all(|c| c.is_ascii_alphanumeric() || "_-.".contains(c)) {
+            return Err(OntologyRefError::Prefix(prefix.to_string()));
+        }
+        if local_id.is_empty() || !local_id.chars().all(|c| c.is_ascii_alphanumeric() || "_-.".contains(c)) {
+            return Err(OntologyRefError::LocalId(local_id.to_string()));
+        }
+        let Some((canonical, digits)) = KNOWN_PREFIXES.iter().find(|(p, _)| p.eq_ignore_ascii_case(prefix)) else {
+            return Ok(Self { prefix: prefix.to_string(), local_id: local_id.to_string() });
+        };
+        let numeric = local_id.chars().all(|c| c.is_ascii_digit());
+        if !numeric || digits.is_some_and(|n| local_id.len() != n) {
+            return Err(OntologyRefError::KnownFormat {
+                prefix: canonical.to_string(),
+                local_id: local_id.to_string(),
+                expected: match digits {
+                    Some(n) => format!("{} digits", n),
+                    None => "digits".into(),
+                },
+            });
+        }
+        Ok(Self { prefix: canonical.to_string(), local_id: local_id.to_string() })
+    }
+
+    pub fn curie(&self) -> String {
+        self.to_string()
+    }
+
+    /// Whether the prefix is one of `KNOWN_PREFIXES`
+    pub fn is_known(&self) -> bool {
+        KNOWN_PREFIXES.iter().any(|(p, _)| *p == self.prefix)
+    }
+
+    /// OBO PURL of a known term; `None` for other prefixes
+    pub fn iri(&self) -> Option<String> {
+        self.is_known().then(|| format!("{}{}_{}", OBO_PURL, self.prefix, self.local_id))
+    }
+}
+
+impl FromStr for OntologyRef {
+    type Err = OntologyRefError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        // OBO PURLs and their `CHEBI_170007` local form are accepted too
+        let s = s.strip_prefix(OBO_PURL).unwrap_or(s);
+        let (prefix, local_id) = s.split_once(':')
+            .or_else(|| s.split_once('_').filter(|(p, _)| KNOWN_PREFIXES.iter().any(|(k, _)| k.eq_ignore_ascii_case(p))))
+            .ok_or_else(|| OntologyRefError::NotCurie(s.to_string()))?;
+        Self::new(prefix, local_id)
+    }
+}
+
+impl TryFrom<String> for OntologyRef {
+    type Error = OntologyRefError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<OntologyRef> for String {
+    fn from(xref: OntologyRef) -> Self {
+        xref.curie()
+    }
+}
+
+impl fmt::Display for OntologyRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.prefix, self.local_id)
+    }
+}
+
+impl MultiIntentGraph {
+    /// Nodes cross-referenced to `xref`, by ID
+    pub fn nodes_with_xref(&self, xref: &OntologyRef) -> Vec<&IntentNode> {
+        let mut nodes: Vec<&IntentNode> = self.intent_nodes.values()
+            .filter(|n| n.metadata.xrefs.contains(xref))
+            .collect();
+        nodes.sort_by_key(|n| n.id);
+        nodes
+    }
+}
@@ -0,0 +1,24 @@
+// limit-sarscov2-core/src/parallel.rs
+// Order-preserving map over a slice: rayon under feature "parallel", a plain iterator otherwise
+
+/// `f` applied to each item, results in item order. Callers reduce the
+/// returned `Vec` sequentially, so float sums come out bit-identical with and
+/// without the `parallel` feature and across thread counts.
+#[cfg(feature = "parallel")]
+pub(crate) fn map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync + Send,
+{
+    use rayon::prelude::*;
+    items.par_iter().map(f).collect()
+}
+
+#[cfg(not(feature = "parallel"))]
+pub(crate) fn map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    F: Fn(&T) -> R,
+{
+    items.iter().map(f).collect()
+}
@@ -0,0 +1,180 @@
+// limit-sarscov2-core/src/propagation.rs
+// Node confidence propagated downstream along causal chains from incoming edges
+
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+use crate::multi_intent_graph::MultiIntentGraph;
+
+/// How the contributions of a node's incoming edges combine
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfidenceCombiner {
+    /// Any incoming link suffices: 1 − Π (1 − cᵢ)
+    #[default]
+    NoisyOr,
+    /// Strongest incoming link
+    Max,
+    /// Mean weighted by each edge's `weight`
+    WeightedMean,
+}
+
+impl ConfidenceCombiner {
+    /// Combined `(contribution, weight)` pairs; `None` without any
+    pub fn combine(&self, contributions: &[(f32, f32)]) -> Option<f32> {
+        if contributions.is_empty() {
+            return None;
+        }
+        let values = contributions.iter().map(|(c, _)| c.clamp(0.0, 1.0));
+        Some(match self {
+            Self::NoisyOr => 1.0 - values.map(|c| 1.0 - c).product::<f32>(),
+            Self::Max => values.fold(0.0, f32::max),
+            Self::WeightedMean => {
+                let total: f32 = contributions.iter().map(|(_, w)| w.max(0.0)).sum();
+                if total > 0.0 {
+                    contributions.iter().map(|(c, w)| c.clamp(0.0, 1.0) * w.max(0.0)).sum::<f32>() / total
+                } else {
+                    values.sum::<f32>() / contributions.len() as f32
+                }
+            }
+        })
+    }
+}
+
+/// Rules for `MultiIntentGraph::propagate_confidence`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PropagationPolicy {
+    pub combiner: ConfidenceCombiner,
+    pub max_iterations: usize,        // passes over a cycle before giving up on convergence
+    pub tolerance: f32,               // largest change that counts as converged
+}
+
+impl Default for PropagationPolicy {
+    fn default() -> Self {
+        Self { combiner: ConfidenceCombiner::default(), max_iterations: 50, tolerance: 1e-4 }
+    }
+}
+
+/// Node confidence before and after propagating
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeConfidenceChange {
+    pub node_id: Uuid,
+    pub previous: f32,
+    pub confidence: f32,
+    pub incoming: usize,              // edges and hyperedges contributing
+}
+
+impl MultiIntentGraph {
+    /// Links into each node as (sources, factor, weight), in link ID order: a
+    /// supporting edge is its source with its net support; a hyperedge is its
+    /// sources with its confidence. Self-loops are left out.
+    fn incoming_links(&self) -> HashMap<Uuid, Vec<(Vec<Uuid>, f32, f32)>> {
+        let net_support = self.net_supports();
+        let mut links: Vec<(Uuid, Uuid, Vec<Uuid>, f32, f32)> = self.edges.values()
+            .filter(|e| e.source_id != e.target_id)
+            .filter_map(|e| Some((e.id, e.target_id, vec![e.source_id], *net_support.get(&e.id)?, e.weight)))
+            .collect();
+        for h in self.hyperedges.values() {
+            for &target in h.target_ids.iter().filter(|t| !h.source_ids.contains(t)) {
+                links.push((h.id, target, h.source_ids.clone(), h.confidence.clamp(0.0, 1.0), h.weight));
+            }
+        }
+        links.sort_by_key(|(id, ..)| *id);
+        let mut incoming: HashMap<Uuid, Vec<(Vec<Uuid>, f32, f32)>> = HashMap::new();
+        for (_, target, sources, factor, weight) in links {
+            incoming.entry(target).or_default().push((sources, factor, weight));
+        }
+        incoming
+    }
+
+    /// Mark `node_id`'s confidence, and everything downstream of it, for
+    /// recomputation by the next `propagate_confidence`
+    pub fn invalidate_confidence(&mut self, node_id: Uuid) {
+        if let Some(stale) = &mut self.stale_confidence {
+            stale.insert(node_id);
+        }
+    }
+
+    /// Mark every node for recomputation, e.g. after editing `edges` directly
+    pub fn invalidate_all_confidence(&mut self) {
+        self.stale_confidence = None;
+    }
+
+    /// Recompute the confidence of nodes downstream of a changed edge or node
+    /// from their incoming edges under `propagation`. A node with incoming
+    /// links gets their combined contributions; a node without keeps its own
+    /// confidence and only feeds its successors. Cycles are iterated until
+    /// they converge. The first call after construction or deserialization
+    /// recomputes the whole graph. Returns the nodes whose confidence changed.
+    pub fn propagate_confidence(&mut self) -> Vec<NodeConfidenceChange> {
+        let seeds: Vec<Uuid> = match self.stale_confidence.take() {
+            Some(stale) => stale.into_iter().collect(),
+            None => self.intent_nodes.keys().copied().collect(),
+        };
+        self.stale_confidence = Some(HashSet::new());
+
+        let links = self.incoming_links();
+        let mut downstream: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+        for (target, node_links) in &links {
+            for source in node_links.iter().flat_map(|(sources, ..)| sources) {
+                downstream.entry(*source).or_default().push(*target);
+            }
+        }
+
+        // Downstream closure of the seeds, in ID order for reproducible passes
+        let mut affected = BTreeSet::new();
+        let mut queue: VecDeque<Uuid> = seeds.into_iter().collect();
+        while let Some(node) = queue.pop_front() {
+            if self.intent_nodes.contains_key(&node) && affected.insert(node) {
+                queue.extend(downstream.get(&node).into_iter().flatten());
+            }
+        }
+        if affected.is_empty() {
+            return vec![];
+        }
+
+        let policy = self.propagation.clone();
+        let previous: HashMap<Uuid, f32> = affected.iter().map(|id| (*id, self.intent_nodes[id].metadata.confidence)).collect();
+        for _ in 0..policy.max_iterations.max(1) {
+            let mut largest_change = 0.0f32;
+            for &node in &affected {
+                // Contribution: product of the link's source confidences × its factor
+                let contributions: Vec<(f32, f32)> = links.get(&node).into_iter().flatten()
+                    .filter_map(|(sources, factor, weight)| {
+                        let sources: Option<Vec<f32>> = sources.iter()
+                            .map(|id| self.intent_nodes.get(id).map(|n| n.metadata.confidence.clamp(0.0, 1.0)))
+                            .collect();
+                        Some((sources?.iter().product::<f32>() * factor, *weight))
+                    })
+                    .collect();
+                if let Some(confidence) = policy.combiner.combine(&contributions) {
+                    let slot = &mut self.intent_nodes.get_mut(&node).expect("affected nodes exist").metadata.confidence;
+                    largest_change = largest_change.max((confidence - *slot).abs());
+                    *slot = confidence;
+                }
+            }
+            if largest_change <= policy.tolerance {
+                break;
+            }
+        }
+
+        let changes: Vec<NodeConfidenceChange> = affected.into_iter()
+            .filter_map(|node_id| {
+                let confidence = self.intent_nodes[&node_id].metadata.confidence;
+                let previous = previous[&node_id];
+                ((confidence - previous).abs() > f32::EPSILON).then(|| NodeConfidenceChange {
+                    node_id,
+                    previous,
+                    confidence,
+                    incoming: links.get(&node_id).map_or(0, Vec::len),
+                })
+            })
+            .collect();
+        if !changes.is_empty() {
+            self.update_timestamp();
+        }
+        changes
+    }
+}
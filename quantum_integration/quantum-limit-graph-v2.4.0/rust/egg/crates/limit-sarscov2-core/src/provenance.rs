@@ -0,0 +1,287 @@
+// limit-sarscov2-core/src/provenance.rs
+// Provenance notes indexed by the node or edge they describe, and governance tags
+
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use std::collections::HashMap;
+
+use crate::{
+    edges::normalize_evidence_ref,
+    multi_intent_graph::MultiIntentGraph,
+    serendipity_trace::SerendipityTrace,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProvenanceNote {
+    pub id: Uuid,
+    #[serde(default)]
+    pub graph_id: Uuid,        // nil when the note is not about a graph
+    #[serde(default)]
+    pub subject_id: Uuid,      // node, edge or other record the note describes
+    pub source: String,        // paper DOI, dataset, lab report
+    pub operation: String,     // add/merge/split
+    pub rationale: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,   // correlation ID of the API request that made the note
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,        // identity bound to that request's API key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<NoteSignature>,
+}
+
+/// Detached signature over `ProvenanceNote::canonical_bytes`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NoteSignature {
+    pub key_id: String,        // which signer key; verifiers look it up in their trusted set
+    pub algorithm: String,     // "ed25519"
+    pub signature: String,     // hex
+}
+
+impl ProvenanceNote {
+    pub fn new(graph_id: Uuid, subject_id: Uuid, source: impl Into<String>, operation: impl Into<String>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            graph_id,
+            subject_id,
+            source: source.into(),
+            operation: operation.into(),
+            rationale: None,
+            request_id: None,
+            actor: None,
+            signature: None,
+        }
+    }
+
+    pub fn with_rationale(mut self, rationale: impl Into<String>) -> Self {
+        self.rationale = Some(rationale.into());
+        self
+    }
+
+    /// Byte string a signature covers: every field except `signature`, in a
+    /// fixed order, with text fields length-prefixed so no two notes collide.
+    /// The request ID and actor are appended only when set, so older signatures still verify.
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        let text = |s: &str| format!("{}:{}", s.len(), s);
+        let mut bytes = format!(
+            "provenance-note/v1\n{}\n{}\n{}\n{}\n{}\n{}",
+            self.id,
+            self.graph_id,
+            self.subject_id,
+            text(&self.source),
+            text(&self.operation),
+            self.rationale.as_deref().map(text).unwrap_or_else(|| "-".into()),
+        );
+        if let Some(request_id) = &self.request_id {
+            bytes.push_str(&format!("\nrequest {}", text(request_id)));
+        }
+        if let Some(actor) = &self.actor {
+            bytes.push_str(&format!("\nactor {}", text(actor)));
+        }
+        bytes.into_bytes()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GovernanceTag {
+    pub id: Uuid,
+    pub label: String,         // "evidence-complete", "unsafe-merge-blocked"
+    pub passed: bool,
+    pub details: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CitationKind {
+    Node,
+    Edge,
+    TraceStep,
+}
+
+/// A node, edge or trace step that cites a given source
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Citation {
+    pub kind: CitationKind,
+    pub id: Uuid,
+    pub label: String,                // node label, edge label or step query
+    pub trace_id: Option<Uuid>,       // for trace steps
+    pub reference: String,            // the reference as written
+}
+
+/// Whether `reference` names `source`, comparing DOIs case- and prefix-insensitively
+pub fn cites(reference: &str, source: &str) -> bool {
+    normalize_evidence_ref(reference).eq_ignore_ascii_case(&normalize_evidence_ref(source))
+}
+
+impl SerendipityTrace {
+    /// Steps whose `evidence_refs` include `source`
+    pub fn citations_of(&self, source: &str) -> Vec<Citation> {
+        self.steps.iter()
+            .filter_map(|step| {
+                let reference = step.evidence_refs.iter().find(|r| cites(r, source))?;
+                Some(Citation {
+                    kind: CitationKind::TraceStep,
+                    id: step.id,
+                    label: step.query.clone(),
+                    trace_id: Some(self.id),
+                    reference: reference.clone(),
+                })
+            })
+            .collect()
+    }
+}
+
+impl MultiIntentGraph {
+    /// Nodes (by `metadata.sources`), edges (by `evidence_refs`) and trace
+    /// steps of this graph that cite `source`, sorted within each kind by ID
+    pub fn citations_of(&self, source: &str) -> Vec<Citation> {
+        let mut nodes: Vec<Citation> = self.intent_nodes.values()
+            .filter_map(|n| {
+                let reference = n.metadata.sources.iter().find(|r| cites(r, source))?;
+                Some(Citation { kind: CitationKind::Node, id: n.id, label: n.label().to_string(), trace_id: None, reference: reference.clone() })
+            })
+            .collect();
+        nodes.sort_by_key(|c| c.id);
+        let mut edges: Vec<Citation> = self.edges.values()
+            .filter_map(|e| {
+                let reference = e.metadata.evidence_refs.iter().find(|r| cites(r, source))?;
+                Some(Citation { kind: CitationKind::Edge, id: e.id, label: e.label.clone(), trace_id: None, reference: reference.clone() })
+            })
+            .collect();
+        edges.sort_by_key(|c| c.id);
+        nodes.into_iter()
+            .chain(edges)
+            .chain(self.serendipity_traces.iter().flat_map(|t| t.citations_of(source)))
+            .collect()
+    }
+}
+
+/// Provenance notes in recording order, indexed by subject. Serializes as
+/// the plain list of notes.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(from = "Vec<ProvenanceNote>", into = "Vec<ProvenanceNote>")]
+pub struct ProvenanceIndex {
+    notes: Vec<ProvenanceNote>,
+    by_subject: HashMap<Uuid, Vec<usize>>,
+}
+
+/// How a related subject's notes bear on the one asked about
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LineageRelation {
+    SourceNode,       // the edge starts here
+    TargetNode,       // the edge ends here
+    IncidentEdge,     // an edge touching the node
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelatedProvenance {
+    pub subject_id: Uuid,
+    pub relation: LineageRelation,
+    pub notes: Vec<ProvenanceNote>,
+}
+
+/// Everything recorded about a node or edge, plus its neighbours' notes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProvenanceLineage {
+    pub graph_id: Uuid,
+    pub subject_id: Uuid,
+    pub kind: Option<String>,         // "node" or "edge"; None if the graph no longer has it
+    pub notes: Vec<ProvenanceNote>,
+    pub related: Vec<RelatedProvenance>,  // only subjects with notes
+}
+
+impl ProvenanceIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, note: ProvenanceNote) {
+        self.by_subject.entry(note.subject_id).or_default().push(self.notes.len());
+        self.notes.push(note);
+    }
+
+    pub fn extend(&mut self, notes: impl IntoIterator<Item = ProvenanceNote>) {
+        for note in notes {
+            self.add(note);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.notes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.notes.is_empty()
+    }
+
+    pub fn notes(&self) -> &[ProvenanceNote] {
+        &self.notes
+    }
+
+    /// Notes about `subject_id`, oldest first
+    pub fn for_subject(&self, subject_id: Uuid) -> Vec<&ProvenanceNote> {
+        self.by_subject.get(&subject_id)
+            .map(|ix| ix.iter().map(|&i| &self.notes[i]).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn for_graph(&self, graph_id: Uuid) -> Vec<&ProvenanceNote> {
+        self.notes.iter().filter(|n| n.graph_id == graph_id).collect()
+    }
+
+    /// Notes made by the API request with correlation ID `request_id`, oldest first
+    pub fn for_request(&self, request_id: &str) -> Vec<&ProvenanceNote> {
+        self.notes.iter().filter(|n| n.request_id.as_deref() == Some(request_id)).collect()
+    }
+
+    /// Lineage of a node or edge of `graph`. For an edge the related notes are
+    /// its endpoints'; for a node, those of every edge touching it.
+    pub fn lineage(&self, graph: &MultiIntentGraph, subject_id: Uuid) -> ProvenanceLineage {
+        let own = |id: Uuid| -> Vec<ProvenanceNote> {
+            self.for_subject(id).into_iter().filter(|n| n.graph_id == graph.id).cloned().collect()
+        };
+        let (kind, neighbours): (Option<&str>, Vec<(Uuid, LineageRelation)>) = if let Some(edge) = graph.edges.get(&subject_id) {
+            (Some("edge"), vec![(edge.source_id, LineageRelation::SourceNode), (edge.target_id, LineageRelation::TargetNode)])
+        } else if graph.intent_nodes.contains_key(&subject_id) {
+            let mut incident: Vec<Uuid> = graph.edges.values()
+                .filter(|e| e.source_id == subject_id || e.target_id == subject_id)
+                .map(|e| e.id)
+                .collect();
+            incident.sort();
+            (Some("node"), incident.into_iter().map(|id| (id, LineageRelation::IncidentEdge)).collect())
+        } else {
+            (None, vec![])
+        };
+
+        ProvenanceLineage {
+            graph_id: graph.id,
+            subject_id,
+            kind: kind.map(str::to_string),
+            notes: own(subject_id),
+            related: neighbours.into_iter()
+                .map(|(id, relation)| RelatedProvenance { subject_id: id, relation, notes: own(id) })
+                .filter(|r| !r.notes.is_empty())
+                .collect(),
+        }
+    }
+}
+
+impl From<Vec<ProvenanceNote>> for ProvenanceIndex {
+    fn from(notes: Vec<ProvenanceNote>) -> Self {
+        notes.into_iter().collect()
+    }
+}
+
+impl From<ProvenanceIndex> for Vec<ProvenanceNote> {
+    fn from(index: ProvenanceIndex) -> Self {
+        index.notes
+    }
+}
+
+impl FromIterator<ProvenanceNote> for ProvenanceIndex {
+    fn from_iter<I: IntoIterator<Item = ProvenanceNote>>(iter: I) -> Self {
+        let mut index = Self::new();
+        index.extend(iter);
+        index
+    }
+}
@@ -0,0 +1,303 @@
+// limit-sarscov2-core/src/quantum.rs
+// Simulated continuous-time quantum walks over the intent graph, compared with classical random walks
+
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use std::collections::{hash_map::Entry, HashMap, VecDeque};
+use thiserror::Error;
+
+use crate::{
+    edges::GraphEdge,
+    multi_intent_graph::MultiIntentGraph,
+};
+
+/// Nodes beyond which the walk is refused (dense n×n eigendecomposition)
+pub const MAX_WALK_NODES: usize = 256;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuantumWalkOptions {
+    pub time: f32,                // walk duration
+    pub samples: usize,           // time points in (0, time] the probabilities are averaged over
+    pub shots: usize,             // measurements drawn from the averaged quantum distribution
+    pub seed: u64,
+    pub hops: Option<usize>,      // walk the start's neighborhood only; None: its whole component
+    pub min_advantage: f32,       // quantum / classical hit probability for a node to be proposed
+    pub max_paths: usize,
+}
+
+impl Default for QuantumWalkOptions {
+    fn default() -> Self {
+        Self { time: 3.0, samples: 32, shots: 1000, seed: 7, hops: None, min_advantage: 1.5, max_paths: 5 }
+    }
+}
+
+/// Where the two walks end up, for one node
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalkHit {
+    pub node_id: Uuid,
+    pub label: String,
+    pub domain: String,
+    pub distance: usize,          // hops from the start
+    pub quantum: f32,             // time-averaged hit probability of the quantum walk
+    pub classical: f32,           // same for the classical random walk
+    pub advantage: f32,           // quantum / classical
+    pub shots: usize,             // measurements that landed here
+}
+
+/// Shortest route from the start to a node the quantum walk favours
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalkPath {
+    pub target: Uuid,
+    pub node_sequence: Vec<Uuid>,
+    pub edge_sequence: Vec<Uuid>, // strongest supporting edge per step
+    pub labels: Vec<String>,
+    pub quantum: f32,
+    pub classical: f32,
+    pub advantage: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuantumWalkReport {
+    pub start: Uuid,
+    pub nodes: usize,             // nodes the walk ran over
+    pub time: f32,
+    pub samples: usize,
+    pub shots: usize,
+    pub total_variation: f32,     // distance between the two averaged distributions
+    pub hits: Vec<WalkHit>,       // highest quantum probability first
+    pub paths: Vec<WalkPath>,     // non-obvious hypothesis paths, highest quantum probability first
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "error", rename_all = "snake_case")]
+pub enum QuantumWalkError {
+    #[error("node {node} is not in the graph")]
+    UnknownNode { node: Uuid },
+    #[error("the walk would cover {nodes} nodes; it is limited to {max}")]
+    TooLarge { nodes: usize, max: usize },
+    #[error("time must be positive and samples at least 1")]
+    InvalidOptions,
+}
+
+impl MultiIntentGraph {
+    /// Continuous-time quantum walk from `start` under the Hamiltonian given
+    /// by the weighted adjacency matrix (supporting edges, undirected, weight
+    /// = net support), next to the classical continuous-time random walk on
+    /// its Laplacian. Both are solved exactly by eigendecomposition and
+    /// averaged over `samples` time points; `shots` measurements are then
+    /// drawn from the quantum distribution. Nodes at least two hops away that
+    /// the quantum walk reaches `min_advantage` times more often than the
+    /// classical one are proposed as paths: interference favours them in a
+    /// way diffusion does not, which makes them candidates for non-obvious links.
+    pub fn quantum_walk(&self, start: Uuid, options: &QuantumWalkOptions) -> Result<QuantumWalkReport, QuantumWalkError> {
+        if !self.intent_nodes.contains_key(&start) {
+            return Err(QuantumWalkError::UnknownNode { node: start });
+        }
+        if options.time.is_nan() || options.time <= 0.0 || options.samples == 0 {
+            return Err(QuantumWalkError::InvalidOptions);
+        }
+
+        let links = self.support_links();
+        let mut neighbors: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+        for (a, b) in links.keys() {
+            neighbors.entry(*a).or_default().push(*b);
+            neighbors.entry(*b).or_default().push(*a);
+        }
+        for list in neighbors.values_mut() {
+            list.sort();
+        }
+
+        // Breadth-first from the start: distances and shortest-path predecessors
+        let mut distance: HashMap<Uuid, usize> = HashMap::from([(start, 0)]);
+        let mut previous: HashMap<Uuid, Uuid> = HashMap::new();
+        let mut queue = VecDeque::from([start]);
+        while let Some(node) = queue.pop_front() {
+            let d = distance[&node];
+            if options.hops.is_some_and(|h| d >= h) {
+                continue;
+            }
+            for &next in neighbors.get(&node).into_iter().flatten() {
+                if let Entry::Vacant(slot) = distance.entry(next) {
+                    slot.insert(d + 1);
+                    previous.insert(next, node);
+                    queue.push_back(next);
+                }
+            }
+        }
+        let mut order: Vec<Uuid> = distance.keys().copied().collect();
+        order.sort();
+        let n = order.len();
+        if n > MAX_WALK_NODES {
+            return Err(QuantumWalkError::TooLarge { nodes: n, max: MAX_WALK_NODES });
+        }
+        let index: HashMap<Uuid, usize> = order.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+        let s = index[&start];
+
+        let mut adjacency = vec![vec![0.0f64; n]; n];
+        for ((a, b), edge) in &links {
+            if let (Some(&i), Some(&j)) = (index.get(a), index.get(b)) {
+                let w = self.net_support(edge) as f64;
+                adjacency[i][j] = w;
+                adjacency[j][i] = w;
+            }
+        }
+        let mut laplacian: Vec<Vec<f64>> = adjacency.iter().map(|row| row.iter().map(|w| -w).collect()).collect();
+        for (i, row) in laplacian.iter_mut().enumerate() {
+            row[i] = adjacency[i].iter().sum();
+        }
+        let (quantum_values, quantum_vectors) = symmetric_eigen(adjacency);
+        let (classical_values, classical_vectors) = symmetric_eigen(laplacian);
+
+        let mut quantum = vec![0.0f64; n];
+        let mut classical = vec![0.0f64; n];
+        for m in 1..=options.samples {
+            let t = options.time as f64 * m as f64 / options.samples as f64;
+            for j in 0..n {
+                let (mut re, mut im, mut p) = (0.0, 0.0, 0.0);
+                for k in 0..n {
+                    let q = quantum_vectors[j][k] * quantum_vectors[s][k];
+                    re += q * (quantum_values[k] * t).cos();
+                    im -= q * (quantum_values[k] * t).sin();
+                    p += classical_vectors[j][k] * classical_vectors[s][k] * (-classical_values[k] * t).exp();
+                }
+                quantum[j] += re * re + im * im;
+                classical[j] += p.max(0.0);
+            }
+        }
+        for dist in [&mut quantum, &mut classical] {
+            let total: f64 = dist.iter().sum();
+            if total > 0.0 {
+                dist.iter_mut().for_each(|p| *p /= total);
+            }
+        }
+
+        let mut shots = vec![0usize; n];
+        let mut rng = SplitMix64(options.seed);
+        for _ in 0..options.shots {
+            let mut u = rng.next_f64();
+            let j = quantum.iter().position(|p| { u -= p; u < 0.0 }).unwrap_or(n - 1);
+            shots[j] += 1;
+        }
+
+        let advantage = |q: f64, c: f64| if c > 0.0 { (q / c) as f32 } else if q > 0.0 { f32::INFINITY } else { 1.0 };
+        let mut hits: Vec<WalkHit> = order.iter().enumerate()
+            .map(|(j, id)| {
+                let node = &self.intent_nodes[id];
+                WalkHit {
+                    node_id: *id,
+                    label: node.label().to_string(),
+                    domain: format!("{:?}", node.domain),
+                    distance: distance[id],
+                    quantum: quantum[j] as f32,
+                    classical: classical[j] as f32,
+                    advantage: advantage(quantum[j], classical[j]),
+                    shots: shots[j],
+                }
+            })
+            .collect();
+        hits.sort_by(|a, b| b.quantum.total_cmp(&a.quantum).then(a.node_id.cmp(&b.node_id)));
+
+        let paths: Vec<WalkPath> = hits.iter()
+            .filter(|h| h.distance >= 2 && h.advantage >= options.min_advantage)
+            .take(options.max_paths)
+            .map(|h| {
+                let mut node_sequence = vec![h.node_id];
+                while let Some(prev) = previous.get(node_sequence.last().expect("non-empty")) {
+                    node_sequence.push(*prev);
+                }
+                node_sequence.reverse();
+                WalkPath {
+                    target: h.node_id,
+                    edge_sequence: node_sequence.windows(2).map(|w| links[&(w[0].min(w[1]), w[0].max(w[1]))].id).collect(),
+                    labels: node_sequence.iter().map(|id| self.intent_nodes[id].label().to_string()).collect(),
+                    node_sequence,
+                    quantum: h.quantum,
+                    classical: h.classical,
+                    advantage: h.advantage,
+                }
+            })
+            .collect();
+
+        Ok(QuantumWalkReport {
+            start,
+            nodes: n,
+            time: options.time,
+            samples: options.samples,
+            shots: options.shots,
+            total_variation: (quantum.iter().zip(&classical).map(|(q, c)| (q - c).abs()).sum::<f64>() / 2.0) as f32,
+            hits,
+            paths,
+        })
+    }
+}
+
+impl MultiIntentGraph {
+    /// Strongest supporting edge per unordered node pair (smaller ID first);
+    /// its net support, always positive, weighs the pair in walks
+    pub(crate) fn support_links(&self) -> HashMap<(Uuid, Uuid), &GraphEdge> {
+        let mut links: HashMap<(Uuid, Uuid), &GraphEdge> = HashMap::new();
+        for edge in self.edges.values().filter(|e| !e.is_refuting() && e.source_id != e.target_id) {
+            if !self.intent_nodes.contains_key(&edge.source_id) || !self.intent_nodes.contains_key(&edge.target_id) || self.net_support(edge) <= 0.0 {
+                continue;
+            }
+            let key = (edge.source_id.min(edge.target_id), edge.source_id.max(edge.target_id));
+            let stronger = links.get(&key).is_none_or(|e| self.net_support(edge) > self.net_support(e) || (self.net_support(edge) == self.net_support(e) && edge.id < e.id));
+            if stronger {
+                links.insert(key, edge);
+            }
+        }
+        links
+    }
+}
+
+/// Eigenvalues and eigenvectors (as columns) of a symmetric matrix by cyclic Jacobi rotations
+fn symmetric_eigen(mut a: Vec<Vec<f64>>) -> (Vec<f64>, Vec<Vec<f64>>) {
+    let n = a.len();
+    let mut v: Vec<Vec<f64>> = (0..n).map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect()).collect();
+    for _ in 0..100 {
+        let off: f64 = (0..n).flat_map(|i| (0..n).filter(move |j| *j != i).map(move |j| (i, j))).map(|(i, j)| a[i][j] * a[i][j]).sum();
+        if off < 1e-18 {
+            break;
+        }
+        for p in 0..n {
+            for q in p + 1..n {
+                if a[p][q].abs() < 1e-300 {
+                    continue;
+                }
+                let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let (c, s) = (1.0 / (t * t + 1.0).sqrt(), t / (t * t + 1.0).sqrt());
+                for row in a.iter_mut() {
+                    let (akp, akq) = (row[p], row[q]);
+                    row[p] = c * akp - s * akq;
+                    row[q] = s * akp + c * akq;
+                }
+                let (upper, lower) = a.split_at_mut(q);
+                for (apk, aqk) in upper[p].iter_mut().zip(lower[0].iter_mut()) {
+                    (*apk, *aqk) = (c * *apk - s * *aqk, s * *apk + c * *aqk);
+                }
+                for row in v.iter_mut() {
+                    let (vkp, vkq) = (row[p], row[q]);
+                    row[p] = c * vkp - s * vkq;
+                    row[q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+    ((0..n).map(|i| a[i][i]).collect(), v)
+}
+
+/// Small deterministic generator for measurement shots and annealing
+pub(crate) struct SplitMix64(pub(crate) u64);
+
+impl SplitMix64 {
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
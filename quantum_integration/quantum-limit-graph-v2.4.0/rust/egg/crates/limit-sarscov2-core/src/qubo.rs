@@ -0,0 +1,269 @@
+// limit-sarscov2-core/src/qubo.rs
+// Hypothesis-path selection as a QUBO: encoder, simulated-annealing solver and a hook for quantum backends
+
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use std::collections::{HashMap, HashSet};
+use thiserror::Error;
+
+use crate::{
+    multi_intent_graph::{HypothesisPath, MultiIntentGraph},
+    quantum::SplitMix64,
+};
+
+/// Binary variables (candidate paths) beyond which encoding is refused
+pub const MAX_QUBO_VARIABLES: usize = 512;
+
+/// Quadratic unconstrained binary optimization problem: minimize
+/// `offset + Σ linear[i]·xᵢ + Σ w·xᵢ·xⱼ` over `xᵢ ∈ {0, 1}`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Qubo {
+    pub linear: Vec<f64>,
+    pub quadratic: Vec<(usize, usize, f64)>,  // (i, j, w) with i < j
+    pub offset: f64,
+}
+
+impl Qubo {
+    pub fn variables(&self) -> usize {
+        self.linear.len()
+    }
+
+    pub fn energy(&self, x: &[bool]) -> f64 {
+        let linear: f64 = self.linear.iter().zip(x).filter(|(_, on)| **on).map(|(h, _)| h).sum();
+        let quadratic: f64 = self.quadratic.iter().filter(|(i, j, _)| x[*i] && x[*j]).map(|(_, _, w)| w).sum();
+        self.offset + linear + quadratic
+    }
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "error", rename_all = "snake_case")]
+pub enum QuboError {
+    #[error("{variables} candidate paths; QUBO encoding is limited to {max}")]
+    TooLarge { variables: usize, max: usize },
+    #[error("solver returned a sample of {got} variables, expected {expected}")]
+    InvalidSample { expected: usize, got: usize },
+    #[error("solver failed: {0}")]
+    Solver(String),
+}
+
+/// Solves a QUBO, returning candidate assignments (any order, repeats
+/// allowed). Implement this to send the problem to a quantum annealer or a
+/// QAOA runtime; `SimulatedAnnealing` is the in-crate classical solver.
+pub trait QuboSolver {
+    fn name(&self) -> &str;
+
+    fn solve(&self, qubo: &Qubo) -> Result<Vec<Vec<bool>>, QuboError>;
+}
+
+/// Metropolis annealing with a geometric temperature schedule, restarted
+/// `reads` times from random assignments. Each sweep tries flipping every
+/// variable, then as many swaps (one variable off, another on), which move
+/// between assignments with the same number of variables set without
+/// paying a cardinality penalty on the way.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SimulatedAnnealing {
+    pub sweeps: usize,            // passes over all variables per read
+    pub reads: usize,
+    pub initial_temperature: f64, // relative to the largest coefficient
+    pub final_temperature: f64,
+    pub seed: u64,
+}
+
+impl Default for SimulatedAnnealing {
+    fn default() -> Self {
+        Self { sweeps: 1000, reads: 16, initial_temperature: 2.0, final_temperature: 0.001, seed: 7 }
+    }
+}
+
+impl QuboSolver for SimulatedAnnealing {
+    fn name(&self) -> &str {
+        "simulated-annealing"
+    }
+
+    fn solve(&self, qubo: &Qubo) -> Result<Vec<Vec<bool>>, QuboError> {
+        let n = qubo.variables();
+        let mut couplings: Vec<Vec<(usize, f64)>> = vec![vec![]; n];
+        for &(i, j, w) in &qubo.quadratic {
+            couplings[i].push((j, w));
+            couplings[j].push((i, w));
+        }
+        let scale = qubo.linear.iter().map(|h| h.abs())
+            .chain(qubo.quadratic.iter().map(|(_, _, w)| w.abs()))
+            .fold(0.0, f64::max)
+            .max(1e-9);
+        let (t0, t1) = (self.initial_temperature * scale, self.final_temperature * scale);
+        let mut rng = SplitMix64(self.seed);
+
+        let mut samples = vec![];
+        let field = |x: &[bool], i: usize| qubo.linear[i] + couplings[i].iter().filter(|(j, _)| x[*j]).map(|(_, w)| w).sum::<f64>();
+        let coupling = |i: usize, j: usize| couplings[i].iter().filter(|(k, _)| *k == j).map(|(_, w)| w).sum::<f64>();
+        for _ in 0..self.reads.max(1) {
+            let mut x: Vec<bool> = (0..n).map(|_| rng.next_f64() < 0.5).collect();
+            let sweeps = self.sweeps.max(1);
+            for sweep in 0..sweeps {
+                let t = t0 * (t1 / t0).powf(sweep as f64 / (sweeps - 1).max(1) as f64);
+                let accept = |delta: f64, rng: &mut SplitMix64| delta <= 0.0 || rng.next_f64() < (-delta / t).exp();
+                for i in 0..n {
+                    let delta = if x[i] { -field(&x, i) } else { field(&x, i) };
+                    if accept(delta, &mut rng) {
+                        x[i] = !x[i];
+                    }
+                }
+                for _ in 0..n {
+                    let i = (rng.next_f64() * n as f64) as usize;
+                    let j = (rng.next_f64() * n as f64) as usize;
+                    if !x[i] || x[j] {
+                        continue;
+                    }
+                    // Turn i off, then j on without i
+                    let delta = -field(&x, i) + field(&x, j) - coupling(i, j);
+                    if accept(delta, &mut rng) {
+                        x[i] = false;
+                        x[j] = true;
+                    }
+                }
+            }
+            samples.push(x);
+        }
+        Ok(samples)
+    }
+}
+
+/// What "a good set of hypothesis paths" means
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PathSelectionOptions {
+    pub k: usize,                 // paths per selection
+    pub diversity: f32,           // weight of the node-overlap penalty between two selected paths
+    pub penalty: Option<f32>,     // weight of the "exactly k" constraint; None picks one that dominates the objective
+    pub min_confidence: f32,      // candidates weaker than this are left out
+    pub max_selections: usize,    // distinct selections returned, best first
+}
+
+impl Default for PathSelectionOptions {
+    fn default() -> Self {
+        Self { k: 3, diversity: 1.0, penalty: None, min_confidence: 0.0, max_selections: 5 }
+    }
+}
+
+/// The QUBO for a set of candidate paths; variable `i` selects `candidates[i]`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathSelectionProblem {
+    pub candidates: Vec<Uuid>,
+    pub qubo: Qubo,
+}
+
+/// One set of paths a solver found
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathSelection {
+    pub rank: usize,              // 1 is the lowest energy
+    pub paths: Vec<HypothesisPath>,
+    pub energy: f64,
+    pub total_confidence: f32,
+    pub mean_overlap: f32,        // node Jaccard between selected paths, averaged over pairs
+    pub feasible: bool,           // exactly `k` paths
+    pub occurrences: usize,       // samples that returned this set
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectionReport {
+    pub solver: String,
+    pub k: usize,
+    pub problem: PathSelectionProblem,
+    pub selections: Vec<PathSelection>,   // feasible sets first, then by energy
+}
+
+/// Encode "choose `k` high-confidence, mutually diverse paths" over
+/// `candidates` as `−Σ cᵢxᵢ + diversity·Σ overlapᵢⱼxᵢxⱼ + P(Σxᵢ − k)²`, where
+/// `cᵢ` is a path's confidence and `overlapᵢⱼ` the Jaccard similarity of two
+/// paths' node sets
+pub fn encode_path_selection(candidates: &[HypothesisPath], options: &PathSelectionOptions) -> Result<PathSelectionProblem, QuboError> {
+    let n = candidates.len();
+    if n > MAX_QUBO_VARIABLES {
+        return Err(QuboError::TooLarge { variables: n, max: MAX_QUBO_VARIABLES });
+    }
+    let k = options.k as f64;
+    let diversity = options.diversity as f64;
+    let overlaps = overlap_matrix(candidates);
+    let max_gain = candidates.iter().map(|p| p.total_confidence as f64).fold(0.0, f64::max);
+    // Large enough that no path's confidence or overlap savings pay for breaking "exactly k"
+    let penalty = options.penalty.map(f64::from).unwrap_or(max_gain + diversity * k + 1.0);
+
+    let linear = candidates.iter().map(|p| -(p.total_confidence as f64) + penalty * (1.0 - 2.0 * k)).collect();
+    let quadratic = overlaps.iter().enumerate()
+        .flat_map(|(i, row)| row.iter().enumerate().skip(i + 1).map(move |(j, overlap)| (i, j, diversity * overlap + 2.0 * penalty)))
+        .collect();
+    Ok(PathSelectionProblem {
+        candidates: candidates.iter().map(|p| p.id).collect(),
+        qubo: Qubo { linear, quadratic, offset: penalty * k * k },
+    })
+}
+
+/// Encode, solve and rank: each distinct sample becomes a `PathSelection`,
+/// feasible ones (exactly `k` paths) ahead of the rest, then by energy
+pub fn select_hypothesis_paths<S: QuboSolver + ?Sized>(
+    candidates: &[HypothesisPath],
+    options: &PathSelectionOptions,
+    solver: &S,
+) -> Result<SelectionReport, QuboError> {
+    let candidates: Vec<HypothesisPath> = candidates.iter().filter(|p| p.total_confidence >= options.min_confidence).cloned().collect();
+    let problem = encode_path_selection(&candidates, options)?;
+    let samples = solver.solve(&problem.qubo)?;
+    let overlaps = overlap_matrix(&candidates);
+
+    let mut seen: HashMap<Vec<bool>, usize> = HashMap::new();
+    let mut order = vec![];
+    for sample in samples {
+        if sample.len() != candidates.len() {
+            return Err(QuboError::InvalidSample { expected: candidates.len(), got: sample.len() });
+        }
+        let count = seen.entry(sample.clone()).or_insert(0);
+        if *count == 0 {
+            order.push(sample);
+        }
+        *count += 1;
+    }
+
+    let mut selections: Vec<PathSelection> = order.into_iter()
+        .map(|sample| {
+            let chosen: Vec<usize> = (0..sample.len()).filter(|i| sample[*i]).collect();
+            let pairs: Vec<f64> = chosen.iter().enumerate().flat_map(|(a, i)| chosen[a + 1..].iter().map(|j| overlaps[*i][*j])).collect();
+            PathSelection {
+                rank: 0,
+                paths: chosen.iter().map(|i| candidates[*i].clone()).collect(),
+                energy: problem.qubo.energy(&sample),
+                total_confidence: chosen.iter().map(|i| candidates[*i].total_confidence).sum(),
+                mean_overlap: if pairs.is_empty() { 0.0 } else { (pairs.iter().sum::<f64>() / pairs.len() as f64) as f32 },
+                feasible: chosen.len() == options.k,
+                occurrences: seen[&sample],
+            }
+        })
+        .collect();
+    selections.sort_by(|a, b| b.feasible.cmp(&a.feasible).then(a.energy.total_cmp(&b.energy)));
+    selections.truncate(options.max_selections);
+    for (i, selection) in selections.iter_mut().enumerate() {
+        selection.rank = i + 1;
+    }
+    Ok(SelectionReport { solver: solver.name().to_string(), k: options.k, problem, selections })
+}
+
+impl MultiIntentGraph {
+    /// `select_hypothesis_paths` over the graph's stored hypothesis paths
+    pub fn select_paths<S: QuboSolver + ?Sized>(&self, options: &PathSelectionOptions, solver: &S) -> Result<SelectionReport, QuboError> {
+        select_hypothesis_paths(&self.hypothesis_paths, options, solver)
+    }
+}
+
+fn overlap_matrix(paths: &[HypothesisPath]) -> Vec<Vec<f64>> {
+    let sets: Vec<HashSet<Uuid>> = paths.iter().map(|p| p.node_sequence.iter().copied().collect()).collect();
+    sets.iter()
+        .map(|a| sets.iter()
+            .map(|b| {
+                let union = a.union(b).count();
+                if union == 0 { 0.0 } else { a.intersection(b).count() as f64 / union as f64 }
+            })
+            .collect())
+        .collect()
+}
+
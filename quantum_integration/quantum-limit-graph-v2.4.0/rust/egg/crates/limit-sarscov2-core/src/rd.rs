@@ -0,0 +1,348 @@
+// limit-sarscov2-core/src/rd.rs
+// Rate-distortion curves: retrieval coverage (rate) vs. noise/redundancy (distortion)
+
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct RDPoint {
+    pub rate: f32,          // documents/nodes retrieved
+    pub distortion: f32,    // redundancy or noise in results
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RDCurve {
+    pub points: Vec<RDPoint>,   // sorted by rate
+}
+
+impl RDCurve {
+    pub fn new(mut points: Vec<RDPoint>) -> Self {
+        points.sort_by(|a, b| a.rate.total_cmp(&b.rate));
+        Self { points }
+    }
+
+    /// Add measurements, keeping points sorted by rate. A new point at a rate
+    /// already on the curve replaces the old measurement.
+    pub fn merge(&mut self, points: impl IntoIterator<Item = RDPoint>) {
+        for p in points {
+            if p.rate.is_nan() || p.distortion.is_nan() {
+                continue;
+            }
+            let i = self.points.partition_point(|q| q.rate < p.rate);
+            match self.points.get_mut(i) {
+                Some(q) if q.rate == p.rate => *q = p,
+                _ => self.points.insert(i, p),
+            }
+        }
+    }
+
+    /// Kneedle knee with default options. Falls back to the point after the
+    /// steepest single drop in distortion when there is no knee (fewer than
+    /// three points, or a straight line), so non-empty curves always have one.
+    pub fn knee(&self) -> Option<RDPoint> {
+        self.detect_knee(&KneeOptions::default())
+            .map(|k| k.point)
+            .or_else(|| self.steepest_drop())
+    }
+
+    /// Knee of the curve, or `None` if it has fewer than three distinct
+    /// rates or no point stands out from the straight line between its ends
+    pub fn detect_knee(&self, options: &KneeOptions) -> Option<Knee> {
+        let xs: Vec<f32> = self.points.iter().map(|p| p.rate).collect();
+        let ys = options.smoothing.apply(&self.points.iter().map(|p| p.distortion).collect::<Vec<_>>());
+        if xs.len() < 3 || xs[0] == xs[xs.len() - 1] {
+            return None;
+        }
+        let (nx, ny) = match options.normalization {
+            Normalization::MinMax => (min_max(&xs), min_max(&ys)),
+            Normalization::None => (xs.clone(), ys.clone()),
+        };
+        let curvature = curvatures(&nx, &ny);
+
+        let (index, score) = match options.method {
+            KneeMethod::Kneedle => {
+                let (x0, x1) = (nx[0], nx[nx.len() - 1]);
+                let (y0, y1) = (ny[0], ny[ny.len() - 1]);
+                // Distance from the chord between the end points, signed so
+                // that the knee side of a convex/concave curve is positive
+                let diff: Vec<f32> = nx.iter().zip(&ny)
+                    .map(|(&x, &y)| {
+                        let chord = y0 + (y1 - y0) * (x - x0) / (x1 - x0);
+                        match options.shape {
+                            CurveShape::Convex => chord - y,
+                            CurveShape::Concave => y - chord,
+                        }
+                    })
+                    .collect();
+                let (index, &max) = diff.iter().enumerate()
+                    .max_by(|a, b| a.1.total_cmp(b.1).then_with(|| b.0.cmp(&a.0)))?;
+                if max <= KNEE_TOLERANCE || index == 0 || index == diff.len() - 1 {
+                    return None;
+                }
+                // Kneedle acceptance: after the knee the difference curve must
+                // fall below max − S·mean(Δx)
+                let mean_step = (x1 - x0).abs() / (nx.len() - 1) as f32;
+                let threshold = max - options.sensitivity * mean_step;
+                if diff[index + 1..].iter().all(|&d| d >= threshold) {
+                    return None;
+                }
+                (index, max)
+            }
+            KneeMethod::MaxCurvature => {
+                let (index, &max) = curvature.iter().enumerate()
+                    .skip(1)
+                    .take(curvature.len() - 2)
+                    .max_by(|a, b| a.1.total_cmp(b.1).then_with(|| b.0.cmp(&a.0)))?;
+                if max <= KNEE_TOLERANCE {
+                    return None;
+                }
+                (index, max)
+            }
+        };
+
+        Some(Knee { point: self.points[index], index, score, curvature: curvature[index] })
+    }
+
+    fn steepest_drop(&self) -> Option<RDPoint> {
+        if self.points.len() < 2 {
+            return self.points.first().copied();
+        }
+        self.points.windows(2)
+            .max_by(|a, b| {
+                let drop_a = a[0].distortion - a[1].distortion;
+                let drop_b = b[0].distortion - b[1].distortion;
+                drop_a.total_cmp(&drop_b)
+            })
+            .map(|w| w[1])
+    }
+
+    /// Pareto-optimal points: those no other point beats on both rate and
+    /// distortion. Along increasing rate, each kept point lowers distortion.
+    pub fn pareto_frontier(&self) -> RDCurve {
+        let mut frontier: Vec<RDPoint> = vec![];
+        for p in &self.points {
+            match frontier.last_mut() {
+                // Same rate: keep the lower distortion
+                Some(last) if last.rate == p.rate => {
+                    if p.distortion < last.distortion {
+                        *last = *p;
+                    }
+                }
+                Some(last) if p.distortion >= last.distortion => {}
+                _ => frontier.push(*p),
+            }
+        }
+        RDCurve { points: frontier }
+    }
+
+    /// Distortion at `rate` by linear interpolation between the neighbouring
+    /// measurements; `None` outside the measured range
+    pub fn interpolate(&self, rate: f32) -> Option<f32> {
+        let first = self.points.first()?;
+        let last = self.points.last()?;
+        if rate < first.rate || rate > last.rate || rate.is_nan() {
+            return None;
+        }
+        let i = self.points.partition_point(|p| p.rate < rate);
+        let hi = self.points[i];
+        if hi.rate == rate || i == 0 {
+            return Some(hi.distortion);
+        }
+        let lo = self.points[i - 1];
+        Some(lo.distortion + (hi.distortion - lo.distortion) * (rate - lo.rate) / (hi.rate - lo.rate))
+    }
+
+    /// Distortion reduction per unit of rate over the measured segment that
+    /// starts at or contains `rate` (the first segment below the curve's
+    /// start); `None` at or past the last measurement
+    pub fn marginal_gain(&self, rate: f32) -> Option<f32> {
+        if rate.is_nan() || self.points.len() < 2 {
+            return None;
+        }
+        let i = self.points.partition_point(|p| p.rate <= rate).max(1);
+        let (lo, hi) = (self.points[i - 1], *self.points.get(i)?);
+        Some((lo.distortion - hi.distortion) / (hi.rate - lo.rate))
+    }
+
+    /// Marginal gain over the segment leading into the knee: once retrieval
+    /// earns less than this per unit of rate, it is past the knee
+    pub fn knee_gain(&self) -> Option<f32> {
+        let knee = self.knee()?;
+        let i = self.points.iter().position(|p| p.rate == knee.rate)?;
+        self.marginal_gain(self.points.get(i.checked_sub(1)?)?.rate)
+    }
+
+    /// Lower envelope of `curves`: at every rate, the lowest distortion any
+    /// curve reaches there (interpolated), including the rates where two
+    /// curves cross. Each curve only contributes within its measured range.
+    pub fn envelope(curves: &[RDCurve]) -> RDCurve {
+        let mut rates: Vec<f32> = curves.iter().flat_map(|c| c.points.iter().map(|p| p.rate)).collect();
+        rates.sort_by(f32::total_cmp);
+        rates.dedup();
+
+        let mut samples = rates.clone();
+        for w in rates.windows(2) {
+            let (r0, r1) = (w[0], w[1]);
+            let segments: Vec<(f32, f32)> = curves.iter()
+                .filter_map(|c| Some((c.interpolate(r0)?, c.interpolate(r1)?)))
+                .collect();
+            for (i, a) in segments.iter().enumerate() {
+                for b in &segments[i + 1..] {
+                    // a and b are straight on [r0, r1]; they cross where their difference changes sign
+                    let (d0, d1) = (a.0 - b.0, a.1 - b.1);
+                    if d0 * d1 < 0.0 {
+                        samples.push(r0 + (r1 - r0) * d0 / (d0 - d1));
+                    }
+                }
+            }
+        }
+
+        RDCurve::new(
+            samples.into_iter()
+                .filter_map(|rate| {
+                    let distortion = curves.iter().filter_map(|c| c.interpolate(rate)).min_by(f32::total_cmp)?;
+                    Some(RDPoint { rate, distortion })
+                })
+                .collect(),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum KneeMethod {
+    /// Satopää et al. 2011: furthest point from the chord between the ends
+    #[default]
+    Kneedle,
+    /// Largest discrete curvature κ = |y″| / (1 + y′²)^3/2
+    MaxCurvature,
+}
+
+/// Shape of the curve around the knee. RD curves (distortion falling fast,
+/// then flattening) are convex.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CurveShape {
+    #[default]
+    Convex,
+    Concave,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Normalization {
+    /// Scale rate and distortion to [0, 1] so units do not skew the knee
+    #[default]
+    MinMax,
+    None,
+}
+
+/// Applied to distortion before detection, for noisy measurements
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum Smoothing {
+    #[default]
+    None,
+    /// Centered moving average over `window` points
+    MovingAverage { window: usize },
+    /// Closest monotone sequence (pool adjacent violators), following the
+    /// overall direction of the curve
+    Isotonic,
+}
+
+impl Smoothing {
+    fn apply(&self, ys: &[f32]) -> Vec<f32> {
+        match *self {
+            Smoothing::None => ys.to_vec(),
+            Smoothing::MovingAverage { window } if window > 1 => {
+                let half = window / 2;
+                (0..ys.len())
+                    .map(|i| {
+                        let lo = i.saturating_sub(half);
+                        let hi = (i + half + 1).min(ys.len());
+                        ys[lo..hi].iter().sum::<f32>() / (hi - lo) as f32
+                    })
+                    .collect()
+            }
+            Smoothing::MovingAverage { .. } => ys.to_vec(),
+            Smoothing::Isotonic => {
+                let decreasing = ys.last() < ys.first();
+                let sign = if decreasing { -1.0 } else { 1.0 };
+                let mut blocks: Vec<(f32, usize)> = vec![];   // (mean, size)
+                for &y in ys {
+                    blocks.push((sign * y, 1));
+                    while blocks.len() > 1 && blocks[blocks.len() - 2].0 > blocks[blocks.len() - 1].0 {
+                        let (m2, n2) = blocks.pop().unwrap();
+                        let (m1, n1) = blocks.pop().unwrap();
+                        blocks.push(((m1 * n1 as f32 + m2 * n2 as f32) / (n1 + n2) as f32, n1 + n2));
+                    }
+                }
+                blocks.into_iter().flat_map(|(m, n)| std::iter::repeat_n(sign * m, n)).collect()
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KneeOptions {
+    pub method: KneeMethod,
+    pub shape: CurveShape,
+    pub normalization: Normalization,
+    pub smoothing: Smoothing,
+    pub sensitivity: f32,         // Kneedle S: higher is more conservative
+}
+
+impl Default for KneeOptions {
+    fn default() -> Self {
+        Self {
+            method: KneeMethod::Kneedle,
+            shape: CurveShape::Convex,
+            normalization: Normalization::MinMax,
+            smoothing: Smoothing::None,
+            sensitivity: 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct Knee {
+    pub point: RDPoint,
+    pub index: usize,             // into `RDCurve::points`
+    pub score: f32,               // Kneedle difference or curvature, in the chosen normalization
+    pub curvature: f32,           // discrete curvature at the knee, in the chosen normalization
+}
+
+/// Scores at or below this (in normalized units) count as a straight line
+const KNEE_TOLERANCE: f32 = 1e-4;
+
+fn min_max(values: &[f32]) -> Vec<f32> {
+    let (lo, hi) = values.iter().fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), &v| (lo.min(v), hi.max(v)));
+    if hi - lo <= f32::EPSILON {
+        return vec![0.0; values.len()];
+    }
+    values.iter().map(|v| (v - lo) / (hi - lo)).collect()
+}
+
+/// Curvature at each point from non-uniform central differences; 0 at the ends
+fn curvatures(xs: &[f32], ys: &[f32]) -> Vec<f32> {
+    let mut k = vec![0.0; xs.len()];
+    for i in 1..xs.len().saturating_sub(1) {
+        let (h1, h2) = (xs[i] - xs[i - 1], xs[i + 1] - xs[i]);
+        if h1 <= 0.0 || h2 <= 0.0 {
+            continue;
+        }
+        let d1 = (ys[i + 1] - ys[i - 1]) / (h1 + h2);
+        let d2 = 2.0 * (h1 * ys[i + 1] - (h1 + h2) * ys[i] + h2 * ys[i - 1]) / (h1 * h2 * (h1 + h2));
+        k[i] = d2.abs() / (1.0 + d1 * d1).powf(1.5);
+    }
+    k
+}
+
+/// Build a curve from retrieval batch sizes and the distortion measured at each
+pub fn rd_from_batches(batch_sizes: &[usize], distortions: &[f32]) -> RDCurve {
+    RDCurve::new(
+        batch_sizes.iter()
+            .zip(distortions.iter())
+            .map(|(k, d)| RDPoint { rate: *k as f32, distortion: *d })
+            .collect(),
+    )
+}
@@ -0,0 +1,215 @@
+// limit-sarscov2-core/src/scheduler.rs
+// Domain-balanced scheduling of exploration steps across intents
+
+use serde::{Serialize, Deserialize};
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    rd::RDCurve,
+    serendipity_trace::SerendipityTrace,
+};
+
+/// Policy deciding which intent/domain the next exploration step targets
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SchedulerPolicy {
+    RoundRobin,           // cycle through targets in order
+    EntropyMaximizing,    // pick the domain that most evens out coverage
+    RdKneeAware,          // favour intents still short of their R-D knee
+}
+
+/// A candidate the scheduler can direct exploration towards
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleTarget {
+    pub intent: String,           // "transmissibility", "vaccine_efficacy", etc.
+    pub domain: String,           // "Virology", "Genomics", etc.
+}
+
+/// Scheduling decision recorded in the trace alongside the step it produced
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchedulingDecision {
+    pub step_number: usize,
+    pub policy: SchedulerPolicy,
+    pub intent: String,
+    pub domain: String,
+    pub reason: String,
+    pub timestamp: String,
+}
+
+/// Budget check of one intent against its R-D knee, recorded in the trace
+/// after each step the intent took
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetDecision {
+    pub step_number: usize,
+    pub intent: String,
+    pub rate: f32,                    // documents retrieved for the intent so far
+    pub marginal_gain: Option<f32>,   // distortion reduction per document at `rate`; None past the curve
+    pub knee_rate: f32,
+    pub knee_gain: f32,               // gain per document leading into the knee
+    pub exhausted: bool,              // the scheduler stops picking the intent
+    pub reason: String,
+    pub timestamp: String,
+}
+
+/// Per-session exploration scheduler
+#[derive(Debug, Clone)]
+pub struct ExplorationScheduler {
+    pub policy: SchedulerPolicy,
+    targets: Vec<ScheduleTarget>,
+    rd_curves: HashMap<String, RDCurve>,  // keyed by intent
+    exhausted: HashSet<String>,           // intents no longer scheduled
+    cursor: usize,
+}
+
+impl ExplorationScheduler {
+    pub fn new(policy: SchedulerPolicy, targets: Vec<ScheduleTarget>) -> Self {
+        Self { policy, targets, rd_curves: HashMap::new(), exhausted: HashSet::new(), cursor: 0 }
+    }
+
+    /// Attach rate-distortion curves (keyed by intent) used by `RdKneeAware`
+    pub fn with_rd_curves(mut self, curves: HashMap<String, RDCurve>) -> Self {
+        self.rd_curves = curves;
+        self
+    }
+
+    pub fn targets(&self) -> &[ScheduleTarget] {
+        &self.targets
+    }
+
+    /// Stop scheduling targets of `intent`, e.g. once retrieval for it is past its R-D knee
+    pub fn exhaust(&mut self, intent: &str) {
+        self.exhausted.insert(intent.to_string());
+    }
+
+    pub fn is_exhausted(&self, intent: &str) -> bool {
+        self.exhausted.contains(intent)
+    }
+
+    /// Decide the next target given the exploration so far; `None` when there
+    /// are no targets or every target's intent is exhausted
+    pub fn next(&mut self, trace: &SerendipityTrace) -> Option<SchedulingDecision> {
+        if !(0..self.targets.len()).any(|i| self.is_live(i)) {
+            return None;
+        }
+
+        let (index, reason) = match self.policy {
+            SchedulerPolicy::RoundRobin => self.round_robin(),
+            SchedulerPolicy::EntropyMaximizing => self.entropy_maximizing(trace),
+            SchedulerPolicy::RdKneeAware => self.rd_knee_aware(trace),
+        };
+        let target = &self.targets[index];
+
+        Some(SchedulingDecision {
+            step_number: trace.steps.len() + 1,
+            policy: self.policy,
+            intent: target.intent.clone(),
+            domain: target.domain.clone(),
+            reason,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        })
+    }
+
+    /// Decide the next target and record the decision in the trace
+    pub fn schedule(&mut self, trace: &mut SerendipityTrace) -> Option<SchedulingDecision> {
+        let decision = self.next(trace)?;
+        trace.record_decision(decision.clone());
+        Some(decision)
+    }
+
+    fn is_live(&self, index: usize) -> bool {
+        !self.exhausted.contains(&self.targets[index].intent)
+    }
+
+    fn round_robin(&mut self) -> (usize, String) {
+        while !self.is_live(self.cursor % self.targets.len()) {
+            self.cursor += 1;
+        }
+        let index = self.cursor % self.targets.len();
+        self.cursor += 1;
+        (index, format!("round-robin slot {} of {}", index + 1, self.targets.len()))
+    }
+
+    fn entropy_maximizing(&self, trace: &SerendipityTrace) -> (usize, String) {
+        let counts = domain_step_counts(trace);
+        let (index, entropy) = self.best_by_entropy(&counts);
+        (index, format!("maximizes domain entropy ({:.3} nats after step)", entropy))
+    }
+
+    fn rd_knee_aware(&self, trace: &SerendipityTrace) -> (usize, String) {
+        let evidence = domain_evidence(trace);
+
+        // Remaining fraction of the knee budget per target, for targets with a curve
+        let below_knee: Vec<(usize, f32, f32)> = self.targets.iter()
+            .enumerate()
+            .filter(|(i, _)| self.is_live(*i))
+            .filter_map(|(i, t)| {
+                let knee = self.rd_curves.get(&t.intent)?.knee()?;
+                let spent = *evidence.get(&t.domain).unwrap_or(&0) as f32;
+                (knee.rate > 0.0 && spent < knee.rate).then(|| (i, (knee.rate - spent) / knee.rate, knee.rate))
+            })
+            .collect();
+
+        if let Some((index, remaining, knee_rate)) = below_knee.into_iter()
+            .max_by(|a, b| a.1.total_cmp(&b.1).then(b.0.cmp(&a.0)))
+        {
+            return (index, format!(
+                "{:.0}% of R-D knee budget remaining (knee rate {:.1})",
+                remaining * 100.0,
+                knee_rate,
+            ));
+        }
+
+        let counts = domain_step_counts(trace);
+        let (index, entropy) = self.best_by_entropy(&counts);
+        (index, format!(
+            "all intents at or past their R-D knee (or no curve); entropy fallback ({:.3} nats)",
+            entropy,
+        ))
+    }
+
+    /// Target whose domain, if explored next, yields the highest domain entropy.
+    /// Ties go to the earliest target.
+    fn best_by_entropy(&self, counts: &HashMap<String, usize>) -> (usize, f32) {
+        let mut best: Option<(usize, f32)> = None;
+        for i in (0..self.targets.len()).filter(|i| self.is_live(*i)) {
+            let mut hypothetical = counts.clone();
+            *hypothetical.entry(self.targets[i].domain.clone()).or_insert(0) += 1;
+            let h = entropy(&hypothetical);
+            if best.is_none_or(|(_, b)| h > b + 1e-5) {
+                best = Some((i, h));
+            }
+        }
+        best.unwrap_or((0, 0.0))
+    }
+}
+
+fn domain_step_counts(trace: &SerendipityTrace) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for step in &trace.steps {
+        for d in &step.domains_explored {
+            *counts.entry(d.clone()).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+fn domain_evidence(trace: &SerendipityTrace) -> HashMap<String, usize> {
+    let mut evidence = HashMap::new();
+    for step in &trace.steps {
+        for d in &step.domains_explored {
+            *evidence.entry(d.clone()).or_insert(0) += step.evidence_found;
+        }
+    }
+    evidence
+}
+
+fn entropy(counts: &HashMap<String, usize>) -> f32 {
+    let total = counts.values().sum::<usize>() as f32;
+    if total == 0.0 {
+        return 0.0;
+    }
+    counts.values()
+        .map(|c| *c as f32 / total)
+        .filter(|p| *p > 0.0)
+        .map(|p| p * (1.0 / p).ln())
+        .sum::<f32>()
+}
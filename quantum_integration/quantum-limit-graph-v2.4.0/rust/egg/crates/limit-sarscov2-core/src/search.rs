@@ -0,0 +1,294 @@
+// limit-sarscov2-core/src/search.rs
+// Full-text search over node content, ranked, with highlighted matches
+
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+
+use crate::{
+    domain::ResearchDomain,
+    fuzzy::is_near,
+    multi_intent_graph::{IntentNode, MultiIntentGraph, NodeContent},
+};
+
+/// Marks wrapped around matched words in `FieldMatch::highlighted`
+pub const HIGHLIGHT_OPEN: &str = "<mark>";
+pub const HIGHLIGHT_CLOSE: &str = "</mark>";
+
+/// Weight of a match in a node's name field (topic, variant, therapy, ...)
+const NAME_WEIGHT: f32 = 2.0;
+/// Weight of a match in a mutation notation or protein region
+const NOTATION_WEIGHT: f32 = 1.5;
+/// Weight of a match in descriptive text (details, mechanism, effect, ...)
+const TEXT_WEIGHT: f32 = 1.0;
+/// Score of a word that only starts with the query term, relative to a whole-word match
+const PREFIX_FACTOR: f32 = 0.5;
+/// Score of a word within `max_edits` typos of the query term, relative to a whole-word match
+const FUZZY_FACTOR: f32 = 0.4;
+/// Added when a field holds the whole query as written
+const PHRASE_BONUS: f32 = 1.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchOptions {
+    #[serde(default = "default_limit")]
+    pub limit: usize,                     // hits returned, best first
+    #[serde(default)]
+    pub domain: Option<ResearchDomain>,   // only nodes of this domain
+    #[serde(default = "default_fuzzy")]
+    pub fuzzy: bool,                      // also match misspelt words and unpunctuated names ("omicorn", "ba5")
+}
+
+fn default_fuzzy() -> bool {
+    true
+}
+
+fn default_limit() -> usize {
+    20
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self { limit: default_limit(), domain: None, fuzzy: default_fuzzy() }
+    }
+}
+
+/// Where in a field the query matched
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldMatch {
+    pub field: String,                    // "topic", "mutations", ...
+    pub text: String,
+    pub spans: Vec<(usize, usize)>,       // byte ranges of matched words in `text`
+    pub highlighted: String,              // `text` with matches in <mark>…</mark>
+}
+
+/// One matching node
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchHit {
+    pub node_id: Uuid,
+    pub domain: ResearchDomain,
+    pub label: String,
+    pub score: f32,
+    pub matches: Vec<FieldMatch>,         // fields with a match, by field order
+}
+
+/// Hits for one query; `total` counts matches before `limit`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SearchResults {
+    pub query: String,
+    pub total: usize,
+    pub hits: Vec<SearchHit>,
+}
+
+/// Searchable text of a node as (field, text, weight)
+pub fn node_fields(node: &IntentNode) -> Vec<(&'static str, String, f32)> {
+    let mut fields = vec![];
+    let mut add = |field, text: &str, weight| {
+        if !text.trim().is_empty() {
+            fields.push((field, text.to_string(), weight));
+        }
+    };
+    match &node.content {
+        NodeContent::Biology(n) => {
+            add("topic", &n.topic, NAME_WEIGHT);
+            add("details", &n.details, TEXT_WEIGHT);
+            for region in &n.regions {
+                add("regions", &format!("{} {}:{}-{}", region.name, region.gene, region.start, region.end), NOTATION_WEIGHT);
+            }
+        }
+        NodeContent::Immunology(n) => {
+            add("topic", &n.topic, NAME_WEIGHT);
+            add("details", &n.details, TEXT_WEIGHT);
+        }
+        NodeContent::Variant(n) => {
+            add("variant", &n.variant, NAME_WEIGHT);
+            for notation in n.notations() {
+                add("mutations", &notation, NOTATION_WEIGHT);
+            }
+        }
+        NodeContent::Treatment(n) => {
+            add("therapy", &n.therapy, NAME_WEIGHT);
+            add("mechanism", &n.mechanism, TEXT_WEIGHT);
+        }
+        NodeContent::PublicHealth(n) => {
+            add("policy", &n.policy, NAME_WEIGHT);
+            add("effect", &n.effect, TEXT_WEIGHT);
+        }
+        NodeContent::ClinicalTrial(n) => {
+            add("registry_id", &n.registry_id, NAME_WEIGHT);
+            add("title", &n.title, TEXT_WEIGHT);
+            for arm in &n.arms {
+                add("arms", &arm.name, TEXT_WEIGHT);
+            }
+        }
+        NodeContent::HostFactor(n) => {
+            add("factor", &n.factor, NAME_WEIGHT);
+            add("details", &n.details, TEXT_WEIGHT);
+        }
+        NodeContent::Vaccine(n) => {
+            add("name", &n.name, NAME_WEIGHT);
+            if let Some(variant) = &n.variant_context {
+                add("variant_context", variant, TEXT_WEIGHT);
+            }
+        }
+    }
+    add("intent", &node.intent, TEXT_WEIGHT * 0.5);
+    fields
+}
+
+/// Lowercase alphanumeric runs of `text` with their byte ranges
+fn words(text: &str) -> Vec<(usize, usize, String)> {
+    let mut out = vec![];
+    let mut start = None;
+    for (i, c) in text.char_indices().chain(std::iter::once((text.len(), ' '))) {
+        match (c.is_alphanumeric(), start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                out.push((s, i, text[s..i].to_lowercase()));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+/// Query terms: lowercase alphanumeric runs, without repeats
+pub fn query_terms(query: &str) -> Vec<String> {
+    let mut terms: Vec<String> = vec![];
+    for (_, _, word) in words(query) {
+        if !terms.contains(&word) {
+            terms.push(word);
+        }
+    }
+    terms
+}
+
+/// Runs of two or more words joined only by punctuation, concatenated
+/// ("BA.5" → "ba5", "B.1.1.7" → "b117"), so unpunctuated query terms match them
+fn joined_words(text: &str, words: &[(usize, usize, String)]) -> Vec<(usize, usize, String)> {
+    let mut out = vec![];
+    for i in 0..words.len() {
+        let mut joined = words[i].2.clone();
+        for j in i + 1..words.len() {
+            if text[words[j - 1].1..words[j].0].chars().any(char::is_whitespace) {
+                break;
+            }
+            joined.push_str(&words[j].2);
+            out.push((words[i].0, words[j].1, joined.clone()));
+        }
+    }
+    out
+}
+
+/// Wrap each span of `text` in the highlight marks; overlapping spans merge
+fn highlight(text: &str, spans: &[(usize, usize)]) -> String {
+    let mut out = String::with_capacity(text.len() + spans.len() * (HIGHLIGHT_OPEN.len() + HIGHLIGHT_CLOSE.len()));
+    let mut at = 0;
+    for &(start, end) in spans {
+        out.push_str(&text[at..start]);
+        out.push_str(HIGHLIGHT_OPEN);
+        out.push_str(&text[start..end]);
+        out.push_str(HIGHLIGHT_CLOSE);
+        at = end;
+    }
+    out.push_str(&text[at..]);
+    out
+}
+
+/// Sorted spans with overlapping ones merged
+fn merge_spans(mut spans: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
+    spans.sort();
+    let mut merged: Vec<(usize, usize)> = vec![];
+    for (start, end) in spans {
+        match merged.last_mut() {
+            Some(last) if start < last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+/// Score `node` against `terms`: each term counts its best field, whole
+/// words over prefixes over near misses; `None` unless every term matches
+/// somewhere
+fn score_node(node: &IntentNode, terms: &[String], phrase: &str, fuzzy: bool) -> Option<(f32, Vec<FieldMatch>)> {
+    let fields = node_fields(node);
+    let mut best = vec![0.0f32; terms.len()];
+    let mut matches = vec![];
+    let mut phrase_bonus = 0.0;
+    for (field, text, weight) in fields {
+        let mut spans = vec![];
+        let mut candidates = words(&text);
+        if fuzzy {
+            candidates.extend(joined_words(&text, &candidates));
+        }
+        for (start, end, word) in candidates {
+            let mut matched = false;
+            for (t, term) in terms.iter().enumerate() {
+                let score = if word == *term {
+                    weight
+                } else if word.starts_with(term.as_str()) {
+                    weight * PREFIX_FACTOR
+                } else if fuzzy && is_near(term, &word) {
+                    weight * FUZZY_FACTOR
+                } else {
+                    continue;
+                };
+                best[t] = best[t].max(score);
+                matched = true;
+            }
+            if matched {
+                spans.push((start, end));
+            }
+        }
+        if spans.is_empty() {
+            continue;
+        }
+        if terms.len() > 1 && text.to_lowercase().contains(phrase) {
+            phrase_bonus = PHRASE_BONUS;
+        }
+        let spans = merge_spans(spans);
+        let highlighted = highlight(&text, &spans);
+        matches.push(FieldMatch { field: field.to_string(), text, spans, highlighted });
+    }
+    if best.contains(&0.0) {
+        return None;
+    }
+    Some((best.iter().sum::<f32>() + phrase_bonus, matches))
+}
+
+impl MultiIntentGraph {
+    /// Nodes whose content matches every word of `query` (case-insensitive;
+    /// a word matches itself or any longer word it starts, and with
+    /// `options.fuzzy` a near miss or a punctuated run like "BA.5"), best
+    /// first. Synonyms in the query also match as their equivalents in
+    /// `self.synonyms` ("Omicron" finds "B.1.1.529"). Name fields outrank
+    /// notations, which outrank descriptive text; ties go to the more
+    /// confident node.
+    pub fn search(&self, query: &str, options: &SearchOptions) -> SearchResults {
+        if query_terms(query).is_empty() {
+            return SearchResults { query: query.to_string(), ..Default::default() };
+        }
+        let variants: Vec<(Vec<String>, String)> = self.synonyms.expand_query(query).into_iter()
+            .map(|q| (query_terms(&q), q.to_lowercase()))
+            .collect();
+        let mut hits: Vec<(f32, SearchHit)> = self.intent_nodes.values()
+            .filter(|n| options.domain.as_ref().is_none_or(|d| std::mem::discriminant(d) == std::mem::discriminant(&n.domain)))
+            .filter_map(|node| {
+                let (score, matches) = variants.iter()
+                    .filter_map(|(terms, phrase)| score_node(node, terms, phrase, options.fuzzy))
+                    .max_by(|a, b| a.0.total_cmp(&b.0))?;
+                let hit = SearchHit { node_id: node.id, domain: node.domain.clone(), label: node.label().to_string(), score, matches };
+                Some((node.metadata.confidence, hit))
+            })
+            .collect();
+        hits.sort_by(|(ca, a), (cb, b)| {
+            b.score.total_cmp(&a.score).then(cb.total_cmp(ca)).then(a.node_id.cmp(&b.node_id))
+        });
+        let total = hits.len();
+        SearchResults {
+            query: query.to_string(),
+            total,
+            hits: hits.into_iter().take(options.limit).map(|(_, hit)| hit).collect(),
+        }
+    }
+}
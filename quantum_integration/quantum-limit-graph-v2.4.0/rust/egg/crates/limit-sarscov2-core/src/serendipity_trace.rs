@@ -0,0 +1,678 @@
+// limit-sarscov2-core/src/serendipity_trace.rs
+// Serendipity traces to visualize agent exploration of multiple hypotheses
+
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use std::collections::{HashMap, HashSet};
+use thiserror::Error;
+
+use crate::scheduler::{BudgetDecision, SchedulingDecision};
+
+/// Type of hypothesis being explored. Serialized as a plain string: the
+/// built-in names ("ImmuneEscape") or the custom name ("long COVID risk").
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum HypothesisType {
+    Transmissibility,      // "mutation X increases transmissibility"
+    VaccineEfficacy,       // "mutation X affects vaccine efficacy"
+    ImmuneEscape,          // "mutation X enables immune escape"
+    TreatmentResponse,     // "variant X responds to treatment Y"
+    PublicHealthImpact,    // "policy X reduces transmission"
+    Custom(String),        // "long COVID risk", "animal reservoir", ...
+}
+
+impl HypothesisType {
+    pub const BUILT_IN: [HypothesisType; 5] = [
+        HypothesisType::Transmissibility,
+        HypothesisType::VaccineEfficacy,
+        HypothesisType::ImmuneEscape,
+        HypothesisType::TreatmentResponse,
+        HypothesisType::PublicHealthImpact,
+    ];
+
+    /// Hypothesis type named `name`. Built-in names match case-insensitively
+    /// and in snake_case ("immune_escape"), so they never become `Custom`.
+    pub fn custom(name: &str) -> Self {
+        let name = name.trim();
+        let key = name.replace(['_', ' ', '-'], "").to_lowercase();
+        Self::BUILT_IN.iter()
+            .find(|t| t.name().to_lowercase() == key)
+            .cloned()
+            .unwrap_or_else(|| HypothesisType::Custom(name.to_string()))
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            HypothesisType::Transmissibility => "Transmissibility",
+            HypothesisType::VaccineEfficacy => "VaccineEfficacy",
+            HypothesisType::ImmuneEscape => "ImmuneEscape",
+            HypothesisType::TreatmentResponse => "TreatmentResponse",
+            HypothesisType::PublicHealthImpact => "PublicHealthImpact",
+            HypothesisType::Custom(name) => name,
+        }
+    }
+
+    pub fn is_custom(&self) -> bool {
+        matches!(self, HypothesisType::Custom(_))
+    }
+}
+
+impl std::fmt::Display for HypothesisType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for HypothesisType {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(HypothesisType::custom(s))
+    }
+}
+
+impl Serialize for HypothesisType {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for HypothesisType {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        if name.trim().is_empty() {
+            return Err(serde::de::Error::custom("hypothesis type must not be empty"));
+        }
+        Ok(HypothesisType::custom(&name))
+    }
+}
+
+/// Single step in exploration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExplorationStep {
+    pub id: Uuid,
+    pub step_number: usize,
+    pub hypothesis: HypothesisType,
+    pub query: String,
+    pub domains_explored: Vec<String>,
+    pub evidence_found: usize,
+    pub confidence: f32,
+    pub timestamp: String,
+    #[serde(default)]
+    pub evidence_refs: Vec<String>,   // DOIs, PMIDs or URLs the evidence came from
+}
+
+/// Complete serendipity trace for a research session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SerendipityTrace {
+    pub id: Uuid,
+    pub session_id: String,
+    pub question: String,
+    pub steps: Vec<ExplorationStep>,
+    pub hypotheses_explored: HashMap<HypothesisType, usize>,  // count per type
+    pub total_evidence: usize,
+    pub cross_domain_jumps: usize,
+    pub created_at: String,
+    #[serde(default)]
+    pub scheduling_decisions: Vec<SchedulingDecision>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub budget_decisions: Vec<BudgetDecision>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub merged_from: Vec<Uuid>,       // traces stitched into this one by `merge`, in order
+}
+
+/// Why `SerendipityTrace::merge` refused its traces
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum TraceMergeError {
+    #[error("trace {other} explores {found:?}, not {expected:?}")]
+    DifferentQuestion { other: Uuid, expected: String, found: String },
+    #[error("trace {0} is given more than once")]
+    Duplicate(Uuid),
+}
+
+impl SerendipityTrace {
+    pub fn new(session_id: String, question: String) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            session_id,
+            question,
+            steps: vec![],
+            hypotheses_explored: HashMap::new(),
+            total_evidence: 0,
+            cross_domain_jumps: 0,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            scheduling_decisions: vec![],
+            budget_decisions: vec![],
+            merged_from: vec![],
+        }
+    }
+
+    /// One campaign-level trace from this trace and `others`, which must
+    /// explore the same question (same terms, ignoring case and punctuation).
+    /// Sessions are concatenated oldest first; steps are renumbered from 1 and
+    /// scheduler and budget decisions shifted to match, and the hypothesis
+    /// counts, evidence and cross-domain jumps are recomputed over the joined
+    /// steps, so a change of domains between two sessions counts as a jump.
+    /// The result has a new ID, the earliest `created_at`, the distinct
+    /// session IDs joined with "+", and the source traces in `merged_from`.
+    pub fn merge(&self, others: &[SerendipityTrace]) -> Result<SerendipityTrace, TraceMergeError> {
+        let terms = query_tokens(&self.question);
+        let mut seen = HashSet::from([self.id]);
+        for other in others {
+            if !seen.insert(other.id) {
+                return Err(TraceMergeError::Duplicate(other.id));
+            }
+            if query_tokens(&other.question) != terms {
+                return Err(TraceMergeError::DifferentQuestion {
+                    other: other.id,
+                    expected: self.question.clone(),
+                    found: other.question.clone(),
+                });
+            }
+        }
+        let mut sessions: Vec<&SerendipityTrace> = std::iter::once(self).chain(others).collect();
+        sessions.sort_by(|a, b| a.created_at.cmp(&b.created_at));   // stable: ties keep the given order
+
+        let mut session_ids: Vec<&str> = vec![];
+        for trace in &sessions {
+            if !session_ids.contains(&trace.session_id.as_str()) {
+                session_ids.push(&trace.session_id);
+            }
+        }
+        let mut merged = SerendipityTrace::new(session_ids.join("+"), self.question.clone());
+        merged.created_at = sessions[0].created_at.clone();
+        for trace in &sessions {
+            let offset = merged.steps.len();
+            for (i, step) in trace.steps.iter().enumerate() {
+                merged.add_step(ExplorationStep { step_number: offset + i + 1, ..step.clone() });
+            }
+            merged.scheduling_decisions.extend(trace.scheduling_decisions.iter().map(|d| SchedulingDecision { step_number: offset + d.step_number, ..d.clone() }));
+            merged.budget_decisions.extend(trace.budget_decisions.iter().map(|d| BudgetDecision { step_number: offset + d.step_number, ..d.clone() }));
+            merged.merged_from.push(trace.id);
+        }
+        Ok(merged)
+    }
+
+    /// Record the scheduler decision that selected the next step's target
+    pub fn record_decision(&mut self, decision: SchedulingDecision) {
+        self.scheduling_decisions.push(decision);
+    }
+
+    /// Record an R-D budget check of an intent
+    pub fn record_budget(&mut self, decision: BudgetDecision) {
+        self.budget_decisions.push(decision);
+    }
+
+    pub fn add_step(&mut self, step: ExplorationStep) {
+        // Track hypothesis type
+        *self.hypotheses_explored.entry(step.hypothesis.clone()).or_insert(0) += 1;
+        
+        // Track evidence
+        self.total_evidence += step.evidence_found;
+        
+        // Detect cross-domain jumps
+        if !self.steps.is_empty() {
+            let prev_domains = &self.steps.last().unwrap().domains_explored;
+            let curr_domains = &step.domains_explored;
+            if prev_domains != curr_domains {
+                self.cross_domain_jumps += 1;
+            }
+        }
+        
+        self.steps.push(step);
+    }
+
+    pub fn branching_factor(&self) -> f32 {
+        if self.steps.is_empty() {
+            return 0.0;
+        }
+        self.hypotheses_explored.len() as f32 / self.steps.len() as f32
+    }
+
+    pub fn diversity_score(&self) -> f32 {
+        // Shannon entropy of hypothesis distribution
+        let total = self.steps.len() as f32;
+        if total == 0.0 {
+            return 0.0;
+        }
+        
+        let mut entropy = 0.0;
+        for count in self.hypotheses_explored.values() {
+            let p = *count as f32 / total;
+            if p > 0.0 {
+                entropy -= p * p.ln();
+            }
+        }
+        entropy
+    }
+
+    pub fn exploration_depth(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn avg_confidence(&self) -> f32 {
+        if self.steps.is_empty() {
+            return 0.0;
+        }
+        self.steps.iter().map(|s| s.confidence).sum::<f32>() / self.steps.len() as f32
+    }
+
+    /// Whether step `i` moved to a different set of domains than step `i - 1`
+    pub fn is_cross_domain_jump(&self, i: usize) -> bool {
+        i > 0 && i < self.steps.len() && self.steps[i - 1].domains_explored != self.steps[i].domains_explored
+    }
+
+    /// Mermaid flowchart of the exploration: one node per step, cross-domain
+    /// jumps drawn as dotted, highlighted links
+    pub fn to_mermaid(&self) -> String {
+        let escape = |s: &str| s.replace('"', "#quot;");
+        let mut out = format!("---\ntitle: \"{}\"\n---\nflowchart TD\n", escape(&self.question));
+        for (i, step) in self.steps.iter().enumerate() {
+            out.push_str(&format!("    s{}[\"{}\"]\n", i, escape(&step_label(step)).replace('\n', "<br/>")));
+        }
+        let mut jumps = vec![];
+        for i in 0..self.steps.len().saturating_sub(1) {
+            let (from, to) = (i, i + 1);
+            if self.is_cross_domain_jump(to) {
+                out.push_str(&format!("    s{} -. cross-domain .-> s{}\n", from, to));
+                jumps.push(i);
+            } else {
+                out.push_str(&format!("    s{} --> s{}\n", from, to));
+            }
+        }
+        for i in jumps {
+            out.push_str(&format!("    linkStyle {} stroke:#d9480f,stroke-width:2px\n", i));
+        }
+        out
+    }
+
+    /// Graphviz DOT digraph of the exploration; cross-domain jumps are dashed and red
+    pub fn to_dot(&self) -> String {
+        let escape = |s: &str| s.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n");
+        let mut out = format!(
+            "digraph \"trace_{}\" {{\n    label=\"{}\";\n    labelloc=t;\n    rankdir=TB;\n    node [shape=box, style=rounded];\n",
+            self.id.simple(), escape(&self.question),
+        );
+        for (i, step) in self.steps.iter().enumerate() {
+            out.push_str(&format!("    s{} [label=\"{}\"];\n", i, escape(&step_label(step))));
+        }
+        for i in 0..self.steps.len().saturating_sub(1) {
+            let (from, to) = (i, i + 1);
+            if self.is_cross_domain_jump(to) {
+                out.push_str(&format!("    s{} -> s{} [label=\"cross-domain\", color=\"#d9480f\", style=dashed, penwidth=2];\n", from, to));
+            } else {
+                out.push_str(&format!("    s{} -> s{};\n", from, to));
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// How differently `other` explored: hypothesis overlap, query wording
+    /// similarity and the first step where the two explorations part ways
+    pub fn compare(&self, other: &SerendipityTrace) -> TraceComparison {
+        // Weighted Jaccard over per-type step counts
+        let types: HashSet<&HypothesisType> = self.hypotheses_explored.keys().chain(other.hypotheses_explored.keys()).collect();
+        let (mut shared, mut union) = (0usize, 0usize);
+        for t in &types {
+            let a = self.hypotheses_explored.get(*t).copied().unwrap_or(0);
+            let b = other.hypotheses_explored.get(*t).copied().unwrap_or(0);
+            shared += a.min(b);
+            union += a.max(b);
+        }
+        let mut shared_hypotheses: Vec<HypothesisType> = types.into_iter()
+            .filter(|t| self.hypotheses_explored.contains_key(*t) && other.hypotheses_explored.contains_key(*t))
+            .cloned()
+            .collect();
+        shared_hypotheses.sort_by(|a, b| a.name().cmp(b.name()));
+
+        // Steps match when they pursue the same hypothesis over the same domains
+        let same_step = |a: &ExplorationStep, b: &ExplorationStep| {
+            a.hypothesis == b.hypothesis
+                && a.domains_explored.iter().collect::<HashSet<_>>() == b.domains_explored.iter().collect::<HashSet<_>>()
+        };
+        let shared_prefix = self.steps.iter().zip(&other.steps).take_while(|(a, b)| same_step(a, b)).count();
+        let divergence_step = (shared_prefix < self.steps.len().max(other.steps.len())).then_some(shared_prefix);
+
+        TraceComparison {
+            trace_a: self.id,
+            trace_b: other.id,
+            same_question: query_tokens(&self.question) == query_tokens(&other.question),
+            hypothesis_overlap: if union == 0 { 1.0 } else { shared as f32 / union as f32 },
+            shared_hypotheses,
+            query_similarity: (best_match_similarity(&self.steps, &other.steps)
+                + best_match_similarity(&other.steps, &self.steps)) / 2.0,
+            shared_prefix,
+            divergence_step,
+            step_counts: (self.steps.len(), other.steps.len()),
+        }
+    }
+
+    pub fn summary(&self) -> SerendipitySummary {
+        SerendipitySummary {
+            trace_id: self.id,
+            question: self.question.clone(),
+            total_steps: self.steps.len(),
+            unique_hypotheses: self.hypotheses_explored.len(),
+            custom_hypotheses: self.hypotheses_explored.keys().filter(|t| t.is_custom()).count(),
+            branching_factor: self.branching_factor(),
+            diversity_score: self.diversity_score(),
+            cross_domain_jumps: self.cross_domain_jumps,
+            total_evidence: self.total_evidence,
+            avg_confidence: self.avg_confidence(),
+        }
+    }
+
+    /// How well this trace answers `search`, or `None` when it fails a filter
+    /// or, given search text, matches none of its terms. A term matches a word
+    /// of the question or a step query that starts with it ("vaccin" finds
+    /// "vaccine"); question matches weigh twice as much as step matches.
+    pub fn search_match(&self, search: &TraceSearch) -> Option<TraceMatch> {
+        if let Some(hypothesis) = &search.hypothesis {
+            if !self.hypotheses_explored.contains_key(hypothesis) {
+                return None;
+            }
+        }
+        let summary = self.summary();
+        if search.min_diversity.is_some_and(|min| summary.diversity_score < min) {
+            return None;
+        }
+
+        let mut terms: Vec<String> = search.q.as_deref().map(query_tokens).unwrap_or_default().into_iter().collect();
+        terms.sort();
+        let found = |term: &String, tokens: &HashSet<String>| tokens.iter().any(|t| t.starts_with(term.as_str()));
+        let question = query_tokens(&self.question);
+        let step_tokens: Vec<HashSet<String>> = self.steps.iter().map(|s| query_tokens(&s.query)).collect();
+
+        let mut matched_terms = vec![];
+        let mut weight = 0.0;
+        for term in &terms {
+            let in_question = found(term, &question);
+            let in_steps = step_tokens.iter().any(|tokens| found(term, tokens));
+            if in_question || in_steps {
+                matched_terms.push(term.clone());
+            }
+            if in_question {
+                weight += 2.0;
+            }
+            if in_steps {
+                weight += 1.0;
+            }
+        }
+        if !terms.is_empty() && matched_terms.is_empty() {
+            return None;
+        }
+        let matching_steps = self.steps.iter().zip(&step_tokens)
+            .filter(|(step, tokens)| {
+                matched_terms.iter().any(|term| found(term, tokens)) || search.hypothesis.as_ref() == Some(&step.hypothesis)
+            })
+            .map(|(step, _)| step.step_number)
+            .collect();
+
+        Some(TraceMatch {
+            trace_id: self.id,
+            session_id: self.session_id.clone(),
+            question: self.question.clone(),
+            created_at: self.created_at.clone(),
+            score: if terms.is_empty() { 1.0 } else { weight / (3.0 * terms.len() as f32) },
+            matched_terms,
+            matching_steps,
+            summary,
+        })
+    }
+}
+
+/// Matches of `search` among `traces`, best score first, then newest first
+pub fn search_traces<'a>(traces: impl IntoIterator<Item = &'a SerendipityTrace>, search: &TraceSearch) -> Vec<TraceMatch> {
+    let mut matches: Vec<TraceMatch> = traces.into_iter().filter_map(|t| t.search_match(search)).collect();
+    matches.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| b.created_at.cmp(&a.created_at)));
+    matches
+}
+
+/// Three-line step label shared by the Mermaid and DOT renderings
+fn step_label(step: &ExplorationStep) -> String {
+    format!(
+        "{}. {}\n{}\n{} · {} evidence · {:.2}",
+        step.step_number, step.hypothesis, step.query, step.domains_explored.join(", "), step.evidence_found, step.confidence,
+    )
+}
+
+/// Lowercased alphanumeric query terms
+fn query_tokens(query: &str) -> HashSet<String> {
+    query.split(|c: char| !c.is_alphanumeric() && c != '.')
+        .map(|t| t.trim_matches('.').to_lowercase())
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+/// Mean over `a`'s steps of the best token-Jaccard match among `b`'s queries
+fn best_match_similarity(a: &[ExplorationStep], b: &[ExplorationStep]) -> f32 {
+    if a.is_empty() || b.is_empty() {
+        return if a.is_empty() && b.is_empty() { 1.0 } else { 0.0 };
+    }
+    let b_tokens: Vec<HashSet<String>> = b.iter().map(|s| query_tokens(&s.query)).collect();
+    a.iter()
+        .map(|s| {
+            let tokens = query_tokens(&s.query);
+            b_tokens.iter()
+                .map(|other| {
+                    let union = tokens.union(other).count();
+                    if union == 0 { 0.0 } else { tokens.intersection(other).count() as f32 / union as f32 }
+                })
+                .fold(0.0, f32::max)
+        })
+        .sum::<f32>() / a.len() as f32
+}
+
+/// Result of `SerendipityTrace::compare`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceComparison {
+    pub trace_a: Uuid,
+    pub trace_b: Uuid,
+    pub same_question: bool,              // same question terms, ignoring case and punctuation
+    pub hypothesis_overlap: f32,          // weighted Jaccard of per-type step counts, 0.0 to 1.0
+    pub shared_hypotheses: Vec<HypothesisType>,
+    pub query_similarity: f32,            // symmetric mean best-match Jaccard of query terms
+    pub shared_prefix: usize,             // leading steps with the same hypothesis and domains
+    pub divergence_step: Option<usize>,   // index of the first differing step; None if identical
+    pub step_counts: (usize, usize),
+}
+
+/// Free text and filters for finding traces relevant to a question
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TraceSearch {
+    pub q: Option<String>,                    // matched against the question and step queries
+    pub hypothesis: Option<HypothesisType>,   // trace explored this type
+    pub min_diversity: Option<f32>,           // minimum `diversity_score`
+}
+
+/// A trace found by `SerendipityTrace::search_match`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceMatch {
+    pub trace_id: Uuid,
+    pub session_id: String,
+    pub question: String,
+    pub created_at: String,
+    pub score: f32,                       // 1.0 when every term is in the question and a step; 1.0 without search text
+    pub matched_terms: Vec<String>,
+    pub matching_steps: Vec<usize>,       // step numbers matching a term or the hypothesis filter
+    pub summary: SerendipitySummary,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SerendipitySummary {
+    pub trace_id: Uuid,
+    pub question: String,
+    pub total_steps: usize,
+    pub unique_hypotheses: usize,
+    #[serde(default)]
+    pub custom_hypotheses: usize,         // of which user-defined
+    pub branching_factor: f32,
+    pub diversity_score: f32,
+    pub cross_domain_jumps: usize,
+    pub total_evidence: usize,
+    pub avg_confidence: f32,
+}
+
+/// Builder for creating exploration steps
+pub struct StepBuilder {
+    step_number: usize,
+    hypothesis: HypothesisType,
+    query: String,
+    domains: Vec<String>,
+    evidence: usize,
+    confidence: f32,
+    evidence_refs: Vec<String>,
+}
+
+impl StepBuilder {
+    pub fn new(step_number: usize, hypothesis: HypothesisType, query: String) -> Self {
+        Self {
+            step_number,
+            hypothesis,
+            query,
+            domains: vec![],
+            evidence: 0,
+            confidence: 0.0,
+            evidence_refs: vec![],
+        }
+    }
+
+    pub fn domains(mut self, domains: Vec<String>) -> Self {
+        self.domains = domains;
+        self
+    }
+
+    pub fn evidence(mut self, count: usize) -> Self {
+        self.evidence = count;
+        self
+    }
+
+    pub fn confidence(mut self, conf: f32) -> Self {
+        self.confidence = conf;
+        self
+    }
+
+    pub fn evidence_refs(mut self, refs: Vec<String>) -> Self {
+        self.evidence_refs = refs;
+        self
+    }
+
+    pub fn build(self) -> ExplorationStep {
+        ExplorationStep {
+            id: Uuid::new_v4(),
+            step_number: self.step_number,
+            hypothesis: self.hypothesis,
+            query: self.query,
+            domains_explored: self.domains,
+            evidence_found: self.evidence,
+            confidence: self.confidence,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            evidence_refs: self.evidence_refs,
+        }
+    }
+}
+
+/// Example trace scenarios
+pub mod examples {
+    use super::*;
+
+    pub fn omicron_exploration_trace() -> SerendipityTrace {
+        let mut trace = SerendipityTrace::new(
+            "session-001".into(),
+            "How does Omicron BA.5 affect vaccine efficacy and transmissibility?".into(),
+        );
+
+        // Step 1: Explore transmissibility hypothesis
+        let step1 = StepBuilder::new(
+            1,
+            HypothesisType::Transmissibility,
+            "Omicron BA.5 transmissibility mutations".into(),
+        )
+        .domains(vec!["Genomics".into(), "Virology".into()])
+        .evidence(12)
+        .confidence(0.85)
+        .build();
+        trace.add_step(step1);
+
+        // Step 2: Explore vaccine efficacy hypothesis
+        let step2 = StepBuilder::new(
+            2,
+            HypothesisType::VaccineEfficacy,
+            "BA.5 spike mutations vaccine escape".into(),
+        )
+        .domains(vec!["Immunology".into(), "Genomics".into()])
+        .evidence(8)
+        .confidence(0.72)
+        .build();
+        trace.add_step(step2);
+
+        // Step 3: Explore immune escape mechanism
+        let step3 = StepBuilder::new(
+            3,
+            HypothesisType::ImmuneEscape,
+            "BA.5 antibody neutralization resistance".into(),
+        )
+        .domains(vec!["Immunology".into()])
+        .evidence(15)
+        .confidence(0.88)
+        .build();
+        trace.add_step(step3);
+
+        // Step 4: Public health implications
+        let step4 = StepBuilder::new(
+            4,
+            HypothesisType::PublicHealthImpact,
+            "BA.5 breakthrough infections policy response".into(),
+        )
+        .domains(vec!["PublicHealth".into(), "Immunology".into()])
+        .evidence(6)
+        .confidence(0.65)
+        .build();
+        trace.add_step(step4);
+
+        trace
+    }
+
+    pub fn paxlovid_treatment_trace() -> SerendipityTrace {
+        let mut trace = SerendipityTrace::new(
+            "session-002".into(),
+            "What is Paxlovid's effectiveness against different variants?".into(),
+        );
+
+        let step1 = StepBuilder::new(
+            1,
+            HypothesisType::TreatmentResponse,
+            "Paxlovid mechanism protease inhibition".into(),
+        )
+        .domains(vec!["Treatment".into(), "Virology".into()])
+        .evidence(10)
+        .confidence(0.90)
+        .build();
+        trace.add_step(step1);
+
+        let step2 = StepBuilder::new(
+            2,
+            HypothesisType::TreatmentResponse,
+            "Paxlovid efficacy Delta variant".into(),
+        )
+        .domains(vec!["Treatment".into(), "Genomics".into()])
+        .evidence(7)
+        .confidence(0.82)
+        .build();
+        trace.add_step(step2);
+
+        let step3 = StepBuilder::new(
+            3,
+            HypothesisType::TreatmentResponse,
+            "Paxlovid efficacy Omicron variants".into(),
+        )
+        .domains(vec!["Treatment".into(), "Genomics".into()])
+        .evidence(9)
+        .confidence(0.78)
+        .build();
+        trace.add_step(step3);
+
+        trace
+    }
+}
@@ -0,0 +1,125 @@
+// limit-sarscov2-core/src/similarity.rs
+// "Similar nodes" recommendations: embedding, shared mutations/sources and shared neighborhood
+
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use std::collections::{BTreeSet, HashMap};
+
+use crate::{
+    embeddings::cosine_similarity,
+    multi_intent_graph::{IntentNode, MultiIntentGraph, NodeContent},
+};
+
+/// How many nodes to recommend and how much each signal counts. Flat so it
+/// reads straight from a query string.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SimilarityOptions {
+    pub k: usize,
+    pub embedding_weight: f32,    // cosine similarity of the nodes' embeddings
+    pub attribute_weight: f32,    // Jaccard of mutations and evidence sources
+    pub neighborhood_weight: f32, // Jaccard of supporting neighbors
+    pub same_domain: bool,        // only recommend nodes in the node's own domain
+}
+
+impl Default for SimilarityOptions {
+    fn default() -> Self {
+        Self { k: 5, embedding_weight: 0.5, attribute_weight: 0.25, neighborhood_weight: 0.25, same_domain: false }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimilarNode {
+    pub node_id: Uuid,
+    pub label: String,
+    pub domain: String,
+    pub score: f32,                       // weighted mean of the signals available for the pair
+    pub embedding: Option<f32>,           // None unless both nodes have an embedding
+    pub attributes: f32,
+    pub neighborhood: f32,
+    pub shared_mutations: Vec<String>,
+    pub shared_sources: Vec<String>,
+    pub shared_neighbors: Vec<Uuid>,
+}
+
+impl MultiIntentGraph {
+    /// The `k` nodes most like `node_id`, best first; `None` for an unknown
+    /// node. A pair's score averages its signals by weight, leaving out the
+    /// embedding signal when either node has no embedding, so graphs without
+    /// `compute_embeddings` still get recommendations from attributes and
+    /// structure. Nodes scoring 0 are not recommended.
+    pub fn similar_nodes(&self, node_id: Uuid, options: &SimilarityOptions) -> Option<Vec<SimilarNode>> {
+        let node = self.intent_nodes.get(&node_id)?;
+        let mut neighbors: HashMap<Uuid, BTreeSet<Uuid>> = HashMap::new();
+        for (a, b) in self.support_links().into_keys() {
+            neighbors.entry(a).or_default().insert(b);
+            neighbors.entry(b).or_default().insert(a);
+        }
+        let empty = BTreeSet::new();
+        let around = neighbors.get(&node_id).unwrap_or(&empty);
+        let (mutations, sources) = (mutations_of(node), sources_of(node));
+
+        let mut similar: Vec<SimilarNode> = self.intent_nodes.values()
+            .filter(|other| other.id != node_id)
+            .filter(|other| !options.same_domain || std::mem::discriminant(&other.domain) == std::mem::discriminant(&node.domain))
+            .filter_map(|other| {
+                let (other_mutations, other_sources) = (mutations_of(other), sources_of(other));
+                let shared_mutations: Vec<String> = mutations.intersection(&other_mutations).cloned().collect();
+                let shared_sources: Vec<String> = sources.intersection(&other_sources).cloned().collect();
+                let attributes = jaccard(shared_mutations.len() + shared_sources.len(), mutations.len() + sources.len() + other_mutations.len() + other_sources.len());
+                let other_around = neighbors.get(&other.id).unwrap_or(&empty);
+                let shared_neighbors: Vec<Uuid> = around.intersection(other_around).copied().collect();
+                let neighborhood = jaccard(shared_neighbors.len(), around.len() + other_around.len());
+                let embedding = match (&node.metadata.embedding, &other.metadata.embedding) {
+                    (Some(a), Some(b)) => Some(cosine_similarity(a, b).max(0.0)),
+                    _ => None,
+                };
+
+                let signals = [
+                    (options.attribute_weight, Some(attributes)),
+                    (options.neighborhood_weight, Some(neighborhood)),
+                    (options.embedding_weight, embedding),
+                ];
+                let weight: f32 = signals.iter().filter(|(_, s)| s.is_some()).map(|(w, _)| w.max(0.0)).sum();
+                let score = if weight > 0.0 {
+                    signals.iter().filter_map(|(w, s)| Some(w.max(0.0) * (*s)?)).sum::<f32>() / weight
+                } else {
+                    0.0
+                };
+                (score > 0.0).then(|| SimilarNode {
+                    node_id: other.id,
+                    label: other.label().to_string(),
+                    domain: format!("{:?}", other.domain),
+                    score,
+                    embedding,
+                    attributes,
+                    neighborhood,
+                    shared_mutations,
+                    shared_sources,
+                    shared_neighbors,
+                })
+            })
+            .collect();
+        similar.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.node_id.cmp(&b.node_id)));
+        similar.truncate(options.k);
+        Some(similar)
+    }
+}
+
+/// Mutations in notation ("S:L452R"); only variant nodes have any
+fn mutations_of(node: &IntentNode) -> BTreeSet<String> {
+    match &node.content {
+        NodeContent::Variant(v) => v.mutations.iter().map(|m| m.to_string()).collect(),
+        _ => BTreeSet::new(),
+    }
+}
+
+fn sources_of(node: &IntentNode) -> BTreeSet<String> {
+    node.metadata.sources.iter().map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect()
+}
+
+/// Jaccard index from the intersection size and the two sets' summed sizes
+fn jaccard(shared: usize, total: usize) -> f32 {
+    let union = total - shared;
+    if union == 0 { 0.0 } else { shared as f32 / union as f32 }
+}
@@ -0,0 +1,226 @@
+// limit-sarscov2-core/src/summary.rs
+// Templated natural-language summaries of a graph or subgraph, for reports
+
+use serde::{Serialize, Deserialize};
+use std::collections::{BTreeMap, HashSet};
+use uuid::Uuid;
+
+use crate::{
+    domain::ResearchDomain,
+    edges::EdgeType,
+    multi_intent_graph::{HypothesisPath, MultiIntentGraph},
+    serendipity_trace::HypothesisType,
+};
+
+/// Most start or end labels named per chain group in the narrative
+const MAX_NAMED: usize = 3;
+
+/// Which part of the graph to summarize
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SummaryFilter {
+    #[serde(default)]
+    pub nodes: Vec<Uuid>,                         // focus nodes; empty summarizes the whole graph
+    #[serde(default)]
+    pub hops: usize,                              // neighborhood radius around the focus nodes
+    #[serde(default)]
+    pub domains: Vec<ResearchDomain>,             // keep only nodes in these domains; empty keeps all
+    #[serde(default)]
+    pub hypothesis_type: Option<HypothesisType>,  // only chains of this type; None: built-in and registered custom types
+    #[serde(default)]
+    pub min_confidence: f32,                      // chains weaker than this are left out
+}
+
+/// Hypothesis chains of one type found in the summarized subgraph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainSummary {
+    pub hypothesis_type: HypothesisType,
+    pub chains: usize,
+    pub strongest: f32,                // confidence of the strongest chain
+    pub strongest_path: Vec<String>,   // its node labels
+    pub from: Vec<String>,             // distinct start labels, strongest chains first
+    pub to: Vec<String>,               // distinct end labels
+    pub sources: usize,                // distinct evidence references across the chains' edges
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphSummary {
+    pub graph_id: Uuid,
+    pub nodes: usize,
+    pub edges: usize,
+    pub causal_edges: usize,
+    pub correlative_edges: usize,
+    pub refuting_edges: usize,
+    pub domains: BTreeMap<String, usize>,   // nodes per domain
+    pub sources: usize,                     // distinct evidence references across all edges
+    pub chains: Vec<ChainSummary>,          // strongest group first
+    pub narrative: String,
+}
+
+impl MultiIntentGraph {
+    /// Deterministic narrative of the subgraph `filter` selects: its size and
+    /// evidence, then one sentence per hypothesis type with chains, e.g. "3
+    /// chains link BA.5 to Vaccine efficacy (vaccine efficacy), strongest at
+    /// 0.88 confidence, supported by 5 sources." Chains are the stored
+    /// hypothesis paths inside the subgraph plus those `generate_hypothesis_paths` finds.
+    pub fn summarize(&self, filter: &SummaryFilter) -> GraphSummary {
+        let mut keep: Vec<Uuid> = if filter.nodes.is_empty() {
+            self.intent_nodes.keys().copied().collect()
+        } else {
+            let mut ids: Vec<Uuid> = filter.nodes.iter().flat_map(|n| self.neighborhood(*n, filter.hops)).collect();
+            ids.sort();
+            ids.dedup();
+            ids
+        };
+        keep.retain(|id| filter.domains.is_empty()
+            || filter.domains.iter().any(|d| std::mem::discriminant(d) == std::mem::discriminant(&self.intent_nodes[id].domain)));
+        let sub = self.subgraph(&keep);
+
+        let mut domains: BTreeMap<String, usize> = BTreeMap::new();
+        for node in sub.intent_nodes.values() {
+            *domains.entry(format!("{:?}", node.domain)).or_insert(0) += 1;
+        }
+        let sources = distinct_sources(&sub, sub.edges.keys());
+
+        let types: Vec<HypothesisType> = match &filter.hypothesis_type {
+            Some(t) => vec![t.clone()],
+            None => {
+                let mut custom: Vec<&String> = sub.path_policy.custom_hypotheses.keys().collect();
+                custom.sort();
+                HypothesisType::BUILT_IN.iter().cloned().chain(custom.into_iter().map(|c| HypothesisType::Custom(c.clone()))).collect()
+            }
+        };
+        let mut chains: Vec<ChainSummary> = types.into_iter()
+            .filter_map(|t| {
+                let mut paths = sub.generate_hypothesis_paths(t.clone(), filter.min_confidence);
+                for stored in sub.hypothesis_paths.iter().filter(|p| p.hypothesis_type == t && p.total_confidence >= filter.min_confidence) {
+                    if !paths.iter().any(|p| p.id == stored.id || p.node_sequence == stored.node_sequence) {
+                        paths.push(stored.clone());
+                    }
+                }
+                paths.sort_by(|a, b| b.total_confidence.total_cmp(&a.total_confidence).then(a.id.cmp(&b.id)));
+                chain_summary(&sub, t, &paths)
+            })
+            .collect();
+        chains.sort_by(|a, b| b.strongest.total_cmp(&a.strongest).then(b.chains.cmp(&a.chains)));
+
+        let mut summary = GraphSummary {
+            graph_id: self.id,
+            nodes: sub.intent_nodes.len(),
+            edges: sub.edges.len(),
+            causal_edges: sub.edges.values().filter(|e| e.edge_type == EdgeType::Causal).count(),
+            correlative_edges: sub.edges.values().filter(|e| e.edge_type == EdgeType::Correlative).count(),
+            refuting_edges: sub.edges.values().filter(|e| e.is_refuting()).count(),
+            domains,
+            sources,
+            chains,
+            narrative: String::new(),
+        };
+        let whole = filter.nodes.is_empty() && filter.domains.is_empty();
+        summary.narrative = narrative(&summary, whole, filter.min_confidence);
+        summary
+    }
+}
+
+fn chain_summary(graph: &MultiIntentGraph, hypothesis_type: HypothesisType, paths: &[HypothesisPath]) -> Option<ChainSummary> {
+    let strongest = paths.first()?;
+    let label = |id: &Uuid| graph.intent_nodes.get(id).map(|n| n.label().to_string());
+    let mut from: Vec<String> = vec![];
+    let mut to: Vec<String> = vec![];
+    for path in paths {
+        for (list, end) in [(&mut from, path.node_sequence.first()), (&mut to, path.node_sequence.last())] {
+            if let Some(name) = end.and_then(label) {
+                if !list.contains(&name) {
+                    list.push(name);
+                }
+            }
+        }
+    }
+    Some(ChainSummary {
+        hypothesis_type,
+        chains: paths.len(),
+        strongest: strongest.total_confidence,
+        strongest_path: strongest.node_sequence.iter().filter_map(label).collect(),
+        from,
+        to,
+        sources: distinct_sources(graph, paths.iter().flat_map(|p| &p.edge_sequence)),
+    })
+}
+
+fn distinct_sources<'a>(graph: &MultiIntentGraph, edges: impl Iterator<Item = &'a Uuid>) -> usize {
+    edges.filter_map(|id| graph.edges.get(id))
+        .flat_map(|e| e.distinct_evidence())
+        .collect::<HashSet<String>>()
+        .len()
+}
+
+fn narrative(summary: &GraphSummary, whole: bool, min_confidence: f32) -> String {
+    let mut sentences = vec![];
+    if summary.nodes == 0 {
+        return "The selection contains no nodes.".into();
+    }
+    let domains: Vec<String> = summary.domains.iter().map(|(d, n)| format!("{} {}", n, d)).collect();
+    sentences.push(format!(
+        "The {} has {} across {} ({}) and {}: {} causal, {} correlative, {} contradicted by evidence, citing {}.",
+        if whole { "graph" } else { "subgraph" },
+        plural(summary.nodes, "node"),
+        plural(summary.domains.len(), "domain"),
+        domains.join(", "),
+        plural(summary.edges, "edge"),
+        summary.causal_edges,
+        summary.correlative_edges,
+        summary.refuting_edges,
+        plural(summary.sources, "source"),
+    ));
+    for chain in &summary.chains {
+        sentences.push(format!(
+            "{} {} {} to {} ({}), strongest at {:.2} confidence ({}), supported by {}.",
+            plural(chain.chains, "chain"),
+            if chain.chains == 1 { "links" } else { "link" },
+            named(&chain.from),
+            named(&chain.to),
+            humanize(chain.hypothesis_type.name()),
+            chain.strongest,
+            chain.strongest_path.join(" → "),
+            plural(chain.sources, "source"),
+        ));
+    }
+    if summary.chains.is_empty() {
+        sentences.push(if min_confidence > 0.0 {
+            format!("No hypothesis chain reaches {:.2} confidence.", min_confidence)
+        } else {
+            "No hypothesis chains were found.".into()
+        });
+    }
+    sentences.join(" ")
+}
+
+fn plural(n: usize, noun: &str) -> String {
+    format!("{} {}{}", n, noun, if n == 1 { "" } else { "s" })
+}
+
+/// "A", "A and B", "A, B, C and 2 more"
+fn named(labels: &[String]) -> String {
+    let shown = &labels[..labels.len().min(MAX_NAMED)];
+    let rest = labels.len() - shown.len();
+    match (shown, rest) {
+        ([], _) => "unnamed nodes".into(),
+        ([only], 0) => only.clone(),
+        (_, 0) => format!("{} and {}", shown[..shown.len() - 1].join(", "), shown[shown.len() - 1]),
+        _ => format!("{} and {} more", shown.join(", "), rest),
+    }
+}
+
+/// "ImmuneEscape" → "immune escape"; custom names are kept as written
+fn humanize(name: &str) -> String {
+    if HypothesisType::BUILT_IN.iter().all(|t| t.name() != name) {
+        return name.to_string();
+    }
+    let mut out = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            out.push(' ');
+        }
+        out.extend(c.to_lowercase());
+    }
+    out
+}
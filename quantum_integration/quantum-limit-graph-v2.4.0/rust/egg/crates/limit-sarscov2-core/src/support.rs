@@ -0,0 +1,126 @@
+// limit-sarscov2-core/src/support.rs
+// Net support of relationships: supporting edges weighed against refuting ones
+
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{
+    edges::{EdgeType, GraphEdge},
+    multi_intent_graph::MultiIntentGraph,
+};
+
+/// Every edge asserting or contradicting one relationship: same endpoints
+/// and type (either way round for correlative edges)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelationshipSupport {
+    pub source_id: Uuid,
+    pub target_id: Uuid,
+    pub edge_type: EdgeType,
+    pub supporting: Vec<Uuid>,
+    pub refuting: Vec<Uuid>,
+    pub support: f32,             // noisy-OR of supporting confidences
+    pub refutation: f32,          // noisy-OR of refuting confidences
+    pub net_support: f32,         // support × (1 − refutation)
+}
+
+impl RelationshipSupport {
+    pub fn is_contested(&self) -> bool {
+        !self.supporting.is_empty() && !self.refuting.is_empty()
+    }
+}
+
+type RelationshipKey = (Uuid, Uuid, String);
+
+fn relationship_key(edge: &GraphEdge) -> RelationshipKey {
+    let (mut source, mut target) = (edge.source_id, edge.target_id);
+    if edge.edge_type == EdgeType::Correlative && target < source {
+        std::mem::swap(&mut source, &mut target);
+    }
+    (source, target, edge.edge_type.name().to_string())
+}
+
+fn noisy_or(confidences: impl Iterator<Item = f32>) -> f32 {
+    1.0 - confidences.map(|c| 1.0 - c.clamp(0.0, 1.0)).product::<f32>()
+}
+
+impl MultiIntentGraph {
+    /// Relationships that have at least one edge, sorted by source, target and type
+    pub fn relationships(&self) -> Vec<RelationshipSupport> {
+        let mut grouped: BTreeMap<RelationshipKey, Vec<&GraphEdge>> = BTreeMap::new();
+        for edge in self.edges.values() {
+            grouped.entry(relationship_key(edge)).or_default().push(edge);
+        }
+        grouped.into_iter()
+            .map(|((source_id, target_id, _), mut edges)| {
+                edges.sort_by_key(|e| e.id);
+                let (refuting, supporting): (Vec<&GraphEdge>, Vec<&GraphEdge>) = edges.iter().partition(|e| e.is_refuting());
+                let support = noisy_or(supporting.iter().map(|e| e.metadata.confidence));
+                let refutation = noisy_or(refuting.iter().map(|e| e.metadata.confidence));
+                RelationshipSupport {
+                    source_id,
+                    target_id,
+                    edge_type: edges[0].edge_type.clone(),
+                    supporting: supporting.iter().map(|e| e.id).collect(),
+                    refuting: refuting.iter().map(|e| e.id).collect(),
+                    support,
+                    refutation,
+                    net_support: support * (1.0 - refutation),
+                }
+            })
+            .collect()
+    }
+
+    /// The relationship `edge_id` supports or refutes
+    pub fn relationship_support(&self, edge_id: Uuid) -> Option<RelationshipSupport> {
+        let key = relationship_key(self.edges.get(&edge_id)?);
+        self.relationships().into_iter()
+            .find(|r| (r.source_id, r.target_id) == (key.0, key.1) && r.edge_type.name() == key.2)
+    }
+
+    /// Edges contradicting `edge`'s relationship
+    pub fn refutations_of(&self, edge: &GraphEdge) -> Vec<&GraphEdge> {
+        let key = relationship_key(edge);
+        let mut refuting: Vec<&GraphEdge> = self.edges.values()
+            .filter(|e| e.is_refuting() && e.id != edge.id && relationship_key(e) == key)
+            .collect();
+        refuting.sort_by_key(|e| e.id);
+        refuting
+    }
+
+    /// Confidence of a supporting edge discounted by the evidence against its
+    /// relationship: confidence × (1 − refutation). 0 for a refuting edge.
+    pub fn net_support(&self, edge: &GraphEdge) -> f32 {
+        if edge.is_refuting() {
+            return 0.0;
+        }
+        let refutation = noisy_or(self.refutations_of(edge).iter().map(|e| e.metadata.confidence));
+        edge.metadata.confidence.clamp(0.0, 1.0) * (1.0 - refutation)
+    }
+
+    /// `net_support` of every supporting edge, computed in one pass
+    pub fn net_supports(&self) -> HashMap<Uuid, f32> {
+        let mut refutation: HashMap<RelationshipKey, f32> = HashMap::new();
+        for edge in self.edges.values().filter(|e| e.is_refuting()) {
+            let against = refutation.entry(relationship_key(edge)).or_insert(0.0);
+            *against = 1.0 - (1.0 - *against) * (1.0 - edge.metadata.confidence.clamp(0.0, 1.0));
+        }
+        self.edges.values()
+            .filter(|e| !e.is_refuting())
+            .map(|e| {
+                let against = refutation.get(&relationship_key(e)).copied().unwrap_or(0.0);
+                (e.id, e.metadata.confidence.clamp(0.0, 1.0) * (1.0 - against))
+            })
+            .collect()
+    }
+
+    /// Attach a study contradicting edge `edge_id`'s relationship; `None` if
+    /// there is no such edge or it is itself a refutation
+    pub fn add_refutation(&mut self, edge_id: Uuid, label: String, evidence_refs: Vec<String>, confidence: f32) -> Option<Uuid> {
+        let edge = self.edges.get(&edge_id).filter(|e| !e.is_refuting())?;
+        let refuting = GraphEdge::refuting(edge, label, evidence_refs, confidence.clamp(0.0, 1.0));
+        let id = refuting.id;
+        self.add_edge(refuting);
+        Some(id)
+    }
+}
@@ -0,0 +1,179 @@
+// limit-sarscov2-core/src/synonyms.rs
+// Alias and synonym registry: names that refer to the same variant, therapy or concept
+
+use serde::{Serialize, Deserialize};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+use crate::fuzzy::name_tokens;
+
+/// Most names in one custom synonym group
+pub const MAX_ALIASES: usize = 32;
+
+/// Synonym groups every table knows: (canonical name, aliases)
+pub const BUILT_IN: &[(&str, &[&str])] = &[
+    ("Alpha", &["B.1.1.7"]),
+    ("Beta", &["B.1.351"]),
+    ("Gamma", &["P.1"]),
+    ("Delta", &["B.1.617.2"]),
+    ("Omicron", &["B.1.1.529"]),
+    ("Paxlovid", &["nirmatrelvir/ritonavir", "nirmatrelvir-ritonavir"]),
+    ("Lagevrio", &["molnupiravir"]),
+    ("Veklury", &["remdesivir"]),
+    ("Comirnaty", &["BNT162b2", "Pfizer-BioNTech"]),
+    ("Spikevax", &["mRNA-1273", "Moderna"]),
+    ("ACE2", &["angiotensin-converting enzyme 2"]),
+    ("TMPRSS2", &["transmembrane serine protease 2"]),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SynonymError {
+    #[error("synonym name must contain a letter or digit")]
+    Empty,
+    #[error("synonym group {0:?} needs at least one alias other than itself")]
+    NoAliases(String),
+    #[error("synonym group {0:?} has more than {MAX_ALIASES} aliases")]
+    TooManyAliases(String),
+}
+
+/// Comparison key: case and punctuation ignored ("Nirmatrelvir/Ritonavir" → "nirmatrelvirritonavir")
+pub fn synonym_key(name: &str) -> String {
+    name_tokens(name).concat()
+}
+
+/// A synonym group as listed by `SynonymTable::groups`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SynonymGroup {
+    pub canonical: String,
+    pub aliases: Vec<String>,
+    pub built_in: bool,
+}
+
+/// Custom synonym groups on top of `BUILT_IN`. A custom group whose canonical
+/// name is a built-in one extends it. Names are equivalent when some group
+/// holds both; equivalence is not chained across groups.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SynonymTable {
+    groups: BTreeMap<String, Vec<String>>,  // canonical name → aliases
+}
+
+impl SynonymTable {
+    /// Add (or replace) the custom group `canonical` with `aliases`, dropping
+    /// blank aliases and repeats of the canonical name
+    pub fn register(&mut self, canonical: &str, aliases: &[String]) -> Result<SynonymGroup, SynonymError> {
+        let canonical = canonical.trim();
+        if synonym_key(canonical).is_empty() {
+            return Err(SynonymError::Empty);
+        }
+        let mut kept: Vec<String> = vec![];
+        for alias in aliases.iter().map(|a| a.trim()) {
+            let key = synonym_key(alias);
+            if !key.is_empty() && key != synonym_key(canonical) && !kept.iter().any(|k| synonym_key(k) == key) {
+                kept.push(alias.to_string());
+            }
+        }
+        if kept.is_empty() {
+            return Err(SynonymError::NoAliases(canonical.to_string()));
+        }
+        if kept.len() > MAX_ALIASES {
+            return Err(SynonymError::TooManyAliases(canonical.to_string()));
+        }
+        self.groups.insert(canonical.to_string(), kept.clone());
+        Ok(SynonymGroup { canonical: canonical.to_string(), aliases: kept, built_in: false })
+    }
+
+    /// Drop the custom group `canonical`; built-in groups stay
+    pub fn remove(&mut self, canonical: &str) -> bool {
+        let key = synonym_key(canonical);
+        let before = self.groups.len();
+        self.groups.retain(|name, _| synonym_key(name) != key);
+        self.groups.len() < before
+    }
+
+    /// Built-in groups, then custom ones by canonical name
+    pub fn groups(&self) -> Vec<SynonymGroup> {
+        let built_in = BUILT_IN.iter().map(|(canonical, aliases)| SynonymGroup {
+            canonical: canonical.to_string(),
+            aliases: aliases.iter().map(|a| a.to_string()).collect(),
+            built_in: true,
+        });
+        let custom = self.groups.iter().map(|(canonical, aliases)| SynonymGroup {
+            canonical: canonical.clone(),
+            aliases: aliases.clone(),
+            built_in: false,
+        });
+        built_in.chain(custom).collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// Each group's names, canonical first; custom groups extending a
+    /// built-in one are merged into it
+    fn name_sets(&self) -> Vec<Vec<&str>> {
+        let extension = |canonical: &str| self.groups.iter().find(|(c, _)| synonym_key(c) == synonym_key(canonical));
+        let built_in = BUILT_IN.iter().map(|(canonical, aliases)| {
+            let extra = extension(canonical).map(|(_, a)| a.iter().map(String::as_str).collect()).unwrap_or_else(Vec::new);
+            std::iter::once(*canonical).chain(aliases.iter().copied()).chain(extra).collect()
+        });
+        let custom = self.groups.iter()
+            .filter(|(canonical, _)| !BUILT_IN.iter().any(|(b, _)| synonym_key(b) == synonym_key(canonical)))
+            .map(|(canonical, aliases)| std::iter::once(canonical.as_str()).chain(aliases.iter().map(String::as_str)).collect());
+        built_in.chain(custom).collect()
+    }
+
+    /// Other names for `name`, canonical names first, without `name` itself
+    pub fn equivalents(&self, name: &str) -> Vec<String> {
+        let key = synonym_key(name);
+        if key.is_empty() {
+            return vec![];
+        }
+        let mut out: Vec<String> = vec![];
+        for set in self.name_sets().into_iter().filter(|set| set.iter().any(|n| synonym_key(n) == key)) {
+            for n in set {
+                let k = synonym_key(n);
+                if k != key && !out.iter().any(|o| synonym_key(o) == k) {
+                    out.push(n.to_string());
+                }
+            }
+        }
+        out
+    }
+
+    /// Canonical name of the group holding `name`, if any
+    pub fn canonical(&self, name: &str) -> Option<String> {
+        let key = synonym_key(name);
+        self.name_sets().into_iter().find(|set| set.iter().any(|n| synonym_key(n) == key)).map(|set| set[0].to_string())
+    }
+
+    /// Whether `a` and `b` name the same thing, ignoring case and punctuation
+    pub fn same(&self, a: &str, b: &str) -> bool {
+        let (ka, kb) = (synonym_key(a), synonym_key(b));
+        !ka.is_empty() && (ka == kb || self.equivalents(a).iter().any(|n| synonym_key(n) == kb))
+    }
+
+    /// `query` and its rewrites with each synonym it mentions, as whole words,
+    /// replaced by each equivalent ("omicron escape" → "B.1.1.529 escape")
+    pub fn expand_query(&self, query: &str) -> Vec<String> {
+        let words: Vec<&str> = query.split_whitespace().collect();
+        let mut out = vec![query.trim().to_string()];
+        for start in 0..words.len() {
+            for end in start + 1..=words.len() {
+                let phrase = words[start..end].join(" ");
+                for equivalent in self.equivalents(&phrase) {
+                    let rewritten = words[..start].iter().copied()
+                        .chain(std::iter::once(equivalent.as_str()))
+                        .chain(words[end..].iter().copied())
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    if !out.contains(&rewritten) {
+                        out.push(rewritten);
+                    }
+                }
+            }
+        }
+        out
+    }
+}
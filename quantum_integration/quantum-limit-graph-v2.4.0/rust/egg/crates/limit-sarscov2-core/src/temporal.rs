@@ -0,0 +1,159 @@
+// limit-sarscov2-core/src/temporal.rs
+// Time windows over the multi-intent graph: creation timestamps, slices and bucketed statistics
+
+use chrono::{DateTime, Datelike, Duration, Months, NaiveDate, Utc};
+use serde::{Serialize, Deserialize};
+use std::collections::BTreeMap;
+
+use crate::{
+    confidence::parse_evidence_date,
+    edges::{EdgeMetadata, HyperEdge},
+    multi_intent_graph::{MultiIntentGraph, NodeMetadata},
+};
+
+impl NodeMetadata {
+    /// `created_at` parsed; `None` if it is not RFC 3339, `YYYY-MM-DD` or a year
+    pub fn created(&self) -> Option<DateTime<Utc>> {
+        parse_evidence_date(&self.created_at)
+    }
+}
+
+impl EdgeMetadata {
+    /// `created_at` parsed, as for `NodeMetadata::created`
+    pub fn created(&self) -> Option<DateTime<Utc>> {
+        parse_evidence_date(&self.created_at)
+    }
+}
+
+impl HyperEdge {
+    /// `created_at` parsed, as for `NodeMetadata::created`
+    pub fn created(&self) -> Option<DateTime<Utc>> {
+        parse_evidence_date(&self.created_at)
+    }
+}
+
+/// Width of a `Timeline` bucket; weeks start on Monday
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeBucket {
+    Day,
+    Week,
+    #[default]
+    Month,
+    Year,
+}
+
+impl TimeBucket {
+    /// First instant of the bucket holding `t`
+    pub fn start_of(&self, t: DateTime<Utc>) -> DateTime<Utc> {
+        let date = t.date_naive();
+        let start = match self {
+            Self::Day => date,
+            Self::Week => date - Duration::days(date.weekday().num_days_from_monday() as i64),
+            Self::Month => date.with_day(1).expect("day 1 exists"),
+            Self::Year => NaiveDate::from_ymd_opt(date.year(), 1, 1).expect("January 1 exists"),
+        };
+        start.and_hms_opt(0, 0, 0).expect("midnight exists").and_utc()
+    }
+
+    /// Start of the bucket after the one starting at `start`
+    pub fn next(&self, start: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            Self::Day => start + Duration::days(1),
+            Self::Week => start + Duration::days(7),
+            Self::Month => start.checked_add_months(Months::new(1)).expect("date in range"),
+            Self::Year => start.checked_add_months(Months::new(12)).expect("date in range"),
+        }
+    }
+}
+
+/// What was added to the graph in one bucket
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeBucketStatistics {
+    pub start: DateTime<Utc>,
+    pub nodes_added: usize,
+    pub edges_added: usize,               // edges and hyperedges
+    pub domains: BTreeMap<String, usize>, // nodes added per research domain
+    pub total_nodes: usize,               // dated nodes up to the end of the bucket
+    pub total_edges: usize,
+}
+
+/// Growth of the graph bucket by bucket, from the first dated node or edge
+/// to the last, empty buckets included
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Timeline {
+    pub bucket: TimeBucket,
+    pub buckets: Vec<TimeBucketStatistics>,
+    pub undated_nodes: usize,             // `created_at` does not parse
+    pub undated_edges: usize,
+}
+
+impl MultiIntentGraph {
+    /// The graph as built within `from..=to` (either end open): nodes created
+    /// in the window and the edges and hyperedges created in it between them,
+    /// with their tags and the hypothesis paths lying entirely inside.
+    /// `slice(None, Some(t))` is the graph as it stood at `t`. Nodes and
+    /// edges whose `created_at` does not parse are left out.
+    pub fn slice(&self, from: Option<DateTime<Utc>>, to: Option<DateTime<Utc>>) -> MultiIntentGraph {
+        let in_window = |created: &str| parse_evidence_date(created)
+            .is_some_and(|t| from.is_none_or(|f| t >= f) && to.is_none_or(|u| t <= u));
+        let nodes: Vec<_> = self.intent_nodes.values()
+            .filter(|n| in_window(&n.metadata.created_at))
+            .map(|n| n.id)
+            .collect();
+        self.induced(&nodes, in_window)
+    }
+
+    /// Nodes and edges added per `bucket`, with running totals
+    pub fn timeline(&self, bucket: TimeBucket) -> Timeline {
+        fn entry(buckets: &mut BTreeMap<DateTime<Utc>, TimeBucketStatistics>, start: DateTime<Utc>) -> &mut TimeBucketStatistics {
+            buckets.entry(start).or_insert_with(|| TimeBucketStatistics {
+                start,
+                nodes_added: 0,
+                edges_added: 0,
+                domains: BTreeMap::new(),
+                total_nodes: 0,
+                total_edges: 0,
+            })
+        }
+        let mut buckets: BTreeMap<DateTime<Utc>, TimeBucketStatistics> = BTreeMap::new();
+        let (mut undated_nodes, mut undated_edges) = (0, 0);
+        for node in self.intent_nodes.values() {
+            match node.metadata.created() {
+                Some(t) => {
+                    let stats = entry(&mut buckets, bucket.start_of(t));
+                    stats.nodes_added += 1;
+                    *stats.domains.entry(format!("{:?}", node.domain)).or_default() += 1;
+                }
+                None => undated_nodes += 1,
+            }
+        }
+        let edge_times = self.edges.values().map(|e| e.metadata.created()).chain(self.hyperedges.values().map(HyperEdge::created));
+        for created in edge_times {
+            match created {
+                Some(t) => entry(&mut buckets, bucket.start_of(t)).edges_added += 1,
+                None => undated_edges += 1,
+            }
+        }
+
+        // Fill the gaps between the first and last bucket, then accumulate
+        if let (Some(&first), Some(&last)) = (buckets.keys().next(), buckets.keys().next_back()) {
+            let mut start = bucket.next(first);
+            while start < last {
+                entry(&mut buckets, start);
+                start = bucket.next(start);
+            }
+        }
+        let (mut total_nodes, mut total_edges) = (0, 0);
+        let buckets = buckets.into_values()
+            .map(|mut stats| {
+                total_nodes += stats.nodes_added;
+                total_edges += stats.edges_added;
+                stats.total_nodes = total_nodes;
+                stats.total_edges = total_edges;
+                stats
+            })
+            .collect();
+        Timeline { bucket, buckets, undated_nodes, undated_edges }
+    }
+}
@@ -0,0 +1,181 @@
+// limit-sarscov2-core/src/trace_cluster.rs
+// Group serendipity traces by hypothesis mix and domain-sequence n-grams
+
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use std::collections::HashMap;
+
+use crate::{
+    parallel,
+    serendipity_trace::{HypothesisType, SerendipityTrace},
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceClusterOptions {
+    pub ngram: usize,                 // length of domain-sequence n-grams
+    pub hypothesis_weight: f32,       // share of similarity from the hypothesis mix; the rest from n-grams
+    pub min_similarity: f32,          // clusters stop merging below this average-linkage similarity
+}
+
+impl Default for TraceClusterOptions {
+    fn default() -> Self {
+        Self { ngram: 2, hypothesis_weight: 0.5, min_similarity: 0.6 }
+    }
+}
+
+/// A recurring exploration strategy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceCluster {
+    pub representative: Uuid,         // medoid: the member most similar to the rest
+    pub members: Vec<Uuid>,
+    pub cohesion: f32,                // mean pairwise similarity of members (1.0 for singletons)
+    pub hypothesis_mix: Vec<(HypothesisType, f32)>,  // share of steps per type, largest first
+    pub top_ngrams: Vec<(String, usize)>,            // most frequent domain n-grams, e.g. "Genomics → Immunology"
+}
+
+/// Sparse feature vectors for one trace
+struct TraceFeatures {
+    hypotheses: HashMap<HypothesisType, f32>,
+    ngrams: HashMap<String, f32>,
+}
+
+impl TraceFeatures {
+    fn new(trace: &SerendipityTrace, n: usize) -> Self {
+        let total = trace.steps.len().max(1) as f32;
+        let mut hypotheses = HashMap::new();
+        for step in &trace.steps {
+            *hypotheses.entry(step.hypothesis.clone()).or_insert(0.0) += 1.0 / total;
+        }
+        let mut ngrams = HashMap::new();
+        for gram in domain_ngrams(trace, n) {
+            *ngrams.entry(gram).or_insert(0.0) += 1.0;
+        }
+        Self { hypotheses, ngrams }
+    }
+
+    fn similarity(&self, other: &TraceFeatures, hypothesis_weight: f32) -> f32 {
+        let w = hypothesis_weight.clamp(0.0, 1.0);
+        w * cosine(&self.hypotheses, &other.hypotheses) + (1.0 - w) * cosine(&self.ngrams, &other.ngrams)
+    }
+}
+
+/// Domain-sequence n-grams of a trace. Each step is the sorted set of its
+/// domains ("Genomics+Virology"); traces shorter than `n` yield one gram.
+pub fn domain_ngrams(trace: &SerendipityTrace, n: usize) -> Vec<String> {
+    let sequence: Vec<String> = trace.steps.iter()
+        .map(|s| {
+            let mut domains = s.domains_explored.clone();
+            domains.sort();
+            domains.dedup();
+            domains.join("+")
+        })
+        .collect();
+    if sequence.is_empty() {
+        return vec![];
+    }
+    let n = n.clamp(1, sequence.len());
+    sequence.windows(n).map(|w| w.join(" → ")).collect()
+}
+
+/// Cluster `traces` by average-linkage agglomeration over a blend of
+/// hypothesis-distribution and domain n-gram cosine similarity. Clusters are
+/// returned largest first.
+pub fn cluster_traces(traces: &[SerendipityTrace], options: &TraceClusterOptions) -> Vec<TraceCluster> {
+    let features = parallel::map(traces, |t| TraceFeatures::new(t, options.ngram));
+    let n = traces.len();
+    let rows: Vec<usize> = (0..n).collect();
+    let upper = parallel::map(&rows, |&i| {
+        (i + 1..n).map(|j| features[i].similarity(&features[j], options.hypothesis_weight)).collect::<Vec<f32>>()
+    });
+    let mut sim = vec![vec![1.0f32; n]; n];
+    for (i, row) in upper.into_iter().enumerate() {
+        for (j, s) in (i + 1..n).zip(row) {
+            sim[i][j] = s;
+            sim[j][i] = s;
+        }
+    }
+
+    let mut clusters: Vec<Vec<usize>> = (0..n).map(|i| vec![i]).collect();
+    loop {
+        // Best partner per cluster, then the best pair in cluster order: the
+        // first strictly highest linkage wins, as in a single scan
+        let indices: Vec<usize> = (0..clusters.len()).collect();
+        let candidates = parallel::map(&indices, |&a| {
+            let mut best: Option<(usize, usize, f32)> = None;
+            for b in a + 1..clusters.len() {
+                let linkage = average_linkage(&sim, &clusters[a], &clusters[b]);
+                if linkage >= options.min_similarity && best.is_none_or(|(_, _, s)| linkage > s) {
+                    best = Some((a, b, linkage));
+                }
+            }
+            best
+        });
+        let best = candidates.into_iter().flatten()
+            .fold(None, |best: Option<(usize, usize, f32)>, c| if best.is_none_or(|(_, _, s)| c.2 > s) { Some(c) } else { best });
+        let Some((a, b, _)) = best else { break };
+        let merged = clusters.swap_remove(b);
+        clusters[a].extend(merged);
+    }
+
+    let mut result: Vec<TraceCluster> = clusters.into_iter()
+        .map(|members| describe_cluster(traces, &sim, members, options.ngram))
+        .collect();
+    result.sort_by(|a, b| b.members.len().cmp(&a.members.len()).then_with(|| b.cohesion.total_cmp(&a.cohesion)));
+    result
+}
+
+fn describe_cluster(traces: &[SerendipityTrace], sim: &[Vec<f32>], members: Vec<usize>, n: usize) -> TraceCluster {
+    let mean_to_others = |i: usize| {
+        if members.len() == 1 {
+            return 1.0;
+        }
+        members.iter().filter(|&&j| j != i).map(|&j| sim[i][j]).sum::<f32>() / (members.len() - 1) as f32
+    };
+    let representative = *members.iter()
+        .max_by(|&&a, &&b| mean_to_others(a).total_cmp(&mean_to_others(b)))
+        .expect("clusters are never empty");
+    let cohesion = members.iter().map(|&i| mean_to_others(i)).sum::<f32>() / members.len() as f32;
+
+    let mut hypothesis_counts: HashMap<HypothesisType, usize> = HashMap::new();
+    let mut ngram_counts: HashMap<String, usize> = HashMap::new();
+    for &i in &members {
+        for step in &traces[i].steps {
+            *hypothesis_counts.entry(step.hypothesis.clone()).or_insert(0) += 1;
+        }
+        for gram in domain_ngrams(&traces[i], n) {
+            *ngram_counts.entry(gram).or_insert(0) += 1;
+        }
+    }
+    let total_steps = hypothesis_counts.values().sum::<usize>().max(1) as f32;
+    let mut hypothesis_mix: Vec<(HypothesisType, f32)> = hypothesis_counts.into_iter()
+        .map(|(t, c)| (t, c as f32 / total_steps))
+        .collect();
+    hypothesis_mix.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.name().cmp(b.0.name())));
+    let mut top_ngrams: Vec<(String, usize)> = ngram_counts.into_iter().collect();
+    top_ngrams.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    top_ngrams.truncate(5);
+
+    TraceCluster {
+        representative: traces[representative].id,
+        members: members.iter().map(|&i| traces[i].id).collect(),
+        cohesion,
+        hypothesis_mix,
+        top_ngrams,
+    }
+}
+
+fn average_linkage(sim: &[Vec<f32>], a: &[usize], b: &[usize]) -> f32 {
+    let total: f32 = a.iter().flat_map(|&i| b.iter().map(move |&j| sim[i][j])).sum();
+    total / (a.len() * b.len()) as f32
+}
+
+fn cosine<K: Eq + std::hash::Hash>(a: &HashMap<K, f32>, b: &HashMap<K, f32>) -> f32 {
+    let dot: f32 = a.iter().filter_map(|(k, x)| b.get(k).map(|y| x * y)).sum();
+    let norm = |m: &HashMap<K, f32>| m.values().map(|x| x * x).sum::<f32>().sqrt();
+    let denom = norm(a) * norm(b);
+    if denom == 0.0 {
+        // Two empty traces look alike; an empty and a non-empty one do not
+        return if a.is_empty() && b.is_empty() { 1.0 } else { 0.0 };
+    }
+    dot / denom
+}
@@ -0,0 +1,180 @@
+// limit-sarscov2-core/src/trace_diagnostics.rs
+// Flag exploration anti-patterns in serendipity traces: dead ends, confidence collapse, repeated queries, fixation
+
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use std::collections::HashMap;
+
+use crate::serendipity_trace::{ExplorationStep, HypothesisType, SerendipityTrace};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DiagnosticOptions {
+    pub dead_end_steps: usize,        // consecutive zero-evidence steps that make a dead end
+    pub confidence_drop: f32,         // total fall over a run of falling confidences that counts as a collapse
+    pub fixation_share: f32,          // share of steps one hypothesis type may take before it is fixation
+    pub fixation_min_steps: usize,    // shorter traces are not checked for fixation
+}
+
+impl Default for DiagnosticOptions {
+    fn default() -> Self {
+        Self { dead_end_steps: 2, confidence_drop: 0.4, fixation_share: 0.8, fixation_min_steps: 5 }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TraceIssueKind {
+    DeadEnd,
+    ConfidenceCollapse,
+    RepeatedQuery,
+    HypothesisFixation,
+}
+
+/// One anti-pattern found in a trace, with what to do about it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceIssue {
+    pub kind: TraceIssueKind,
+    pub steps: Vec<usize>,            // step numbers involved
+    pub message: String,
+    pub suggestion: String,
+}
+
+/// Result of `SerendipityTrace::diagnose`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceDiagnostics {
+    pub trace_id: Uuid,
+    pub total_steps: usize,
+    pub healthy: bool,                // no issues found
+    pub issues: Vec<TraceIssue>,      // in step order within each kind
+}
+
+impl SerendipityTrace {
+    /// Exploration anti-patterns in this trace's steps
+    pub fn diagnose(&self, options: &DiagnosticOptions) -> TraceDiagnostics {
+        let mut issues = dead_ends(&self.steps, options);
+        issues.extend(confidence_collapses(&self.steps, options));
+        issues.extend(repeated_queries(&self.steps));
+        issues.extend(fixation(self, options));
+        TraceDiagnostics { trace_id: self.id, total_steps: self.steps.len(), healthy: issues.is_empty(), issues }
+    }
+}
+
+fn numbers(steps: &[ExplorationStep]) -> Vec<usize> {
+    steps.iter().map(|s| s.step_number).collect()
+}
+
+/// Maximal runs of at least `dead_end_steps` consecutive steps finding no evidence
+fn dead_ends(steps: &[ExplorationStep], options: &DiagnosticOptions) -> Vec<TraceIssue> {
+    let min_run = options.dead_end_steps.max(1);
+    let mut issues = vec![];
+    let mut start = 0;
+    while start < steps.len() {
+        let run = steps[start..].iter().take_while(|s| s.evidence_found == 0).count();
+        if run >= min_run {
+            let run_steps = &steps[start..start + run];
+            let mut domains: Vec<&str> = run_steps.iter().flat_map(|s| s.domains_explored.iter().map(String::as_str)).collect();
+            domains.sort();
+            domains.dedup();
+            issues.push(TraceIssue {
+                kind: TraceIssueKind::DeadEnd,
+                steps: numbers(run_steps),
+                message: format!("{} consecutive steps found no evidence", run),
+                suggestion: match domains.is_empty() {
+                    true => "Broaden or rephrase the queries, or switch hypothesis".into(),
+                    false => format!("Broaden or rephrase the queries, or move away from {}", domains.join(", ")),
+                },
+            });
+        }
+        start += run.max(1);
+    }
+    issues
+}
+
+/// Runs of strictly falling confidence whose total fall reaches `confidence_drop`
+fn confidence_collapses(steps: &[ExplorationStep], options: &DiagnosticOptions) -> Vec<TraceIssue> {
+    let mut issues = vec![];
+    let mut start = 0;
+    while start < steps.len() {
+        let mut end = start;
+        while end + 1 < steps.len() && steps[end + 1].confidence < steps[end].confidence {
+            end += 1;
+        }
+        let fall = steps[start].confidence - steps[end].confidence;
+        if end > start && fall >= options.confidence_drop {
+            issues.push(TraceIssue {
+                kind: TraceIssueKind::ConfidenceCollapse,
+                steps: numbers(&steps[start..=end]),
+                message: format!(
+                    "Confidence fell from {:.2} to {:.2} over {} steps",
+                    steps[start].confidence, steps[end].confidence, end - start + 1,
+                ),
+                suggestion: format!(
+                    "Revisit the evidence behind step {} before building on it, or return to step {}'s line of inquiry",
+                    steps[start + 1].step_number, steps[start].step_number,
+                ),
+            });
+        }
+        start = end + 1;
+    }
+    issues
+}
+
+/// The query with case and whitespace normalized
+fn normalized(query: &str) -> String {
+    query.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+/// Queries asked more than once, in order of first asking
+fn repeated_queries(steps: &[ExplorationStep]) -> Vec<TraceIssue> {
+    let mut order: Vec<String> = vec![];
+    let mut groups: HashMap<String, Vec<&ExplorationStep>> = HashMap::new();
+    for step in steps {
+        let key = normalized(&step.query);
+        if key.is_empty() {
+            continue;
+        }
+        if !groups.contains_key(&key) {
+            order.push(key.clone());
+        }
+        groups.entry(key).or_default().push(step);
+    }
+    order.into_iter()
+        .filter_map(|key| {
+            let group = &groups[&key];
+            (group.len() > 1).then(|| TraceIssue {
+                kind: TraceIssueKind::RepeatedQuery,
+                steps: group.iter().map(|s| s.step_number).collect(),
+                message: format!("Query {:?} was run {} times", group[0].query.trim(), group.len()),
+                suggestion: "Reuse the first run's results, or refine the query with new terms".into(),
+            })
+        })
+        .collect()
+}
+
+/// One hypothesis type taking more than `fixation_share` of the steps
+fn fixation(trace: &SerendipityTrace, options: &DiagnosticOptions) -> Option<TraceIssue> {
+    let total = trace.steps.len();
+    if total == 0 || total < options.fixation_min_steps {
+        return None;
+    }
+    let (hypothesis, count) = trace.hypotheses_explored.iter()
+        .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.name().cmp(a.0.name())))?;
+    let share = *count as f32 / total as f32;
+    if share <= options.fixation_share {
+        return None;
+    }
+    let unexplored: Vec<&str> = HypothesisType::BUILT_IN.iter()
+        .filter(|t| !trace.hypotheses_explored.contains_key(*t))
+        .map(HypothesisType::name)
+        .collect();
+    Some(TraceIssue {
+        kind: TraceIssueKind::HypothesisFixation,
+        steps: trace.steps.iter().filter(|s| &s.hypothesis == hypothesis).map(|s| s.step_number).collect(),
+        message: format!("{} takes {:.0}% of the steps ({} of {})", hypothesis, share * 100.0, count, total),
+        suggestion: match unexplored.is_empty() {
+            true => format!("Give the other hypothesis types more steps than {}", hypothesis),
+            false => format!("Try an unexplored hypothesis type: {}", unexplored.join(", ")),
+        },
+    })
+}
@@ -0,0 +1,175 @@
+// limit-sarscov2-core/src/validate.rs
+// Schema validation: invariants every intent node, edge and hyperedge must hold
+
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use std::fmt;
+use thiserror::Error;
+
+use crate::{
+    edges::{EdgeType, GraphEdge, HyperEdge},
+    graph_query::DOMAINS,
+    multi_intent_graph::{IntentNode, MultiIntentGraph},
+};
+
+/// Broken invariant
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Error)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub enum ValidationRule {
+    #[error("confidence {confidence} is outside [0, 1]")]
+    ConfidenceRange { confidence: f32 },
+    #[error("weight {weight} is outside [0, 1]")]
+    WeightRange { weight: f32 },
+    #[error("label is empty")]
+    EmptyLabel,
+    #[error("{field} {domain:?} is not a research domain")]
+    UnknownDomain { field: String, domain: String },
+    #[error("created_at {created_at:?} is not an RFC 3339 timestamp")]
+    Timestamp { created_at: String },
+    #[error("causal relationship cites no evidence")]
+    CausalWithoutEvidence,
+    #[error("edge type {edge_type:?} is not in the graph's edge vocabulary")]
+    UnregisteredEdgeType { edge_type: String },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubjectKind {
+    Node,
+    Edge,
+    Hyperedge,
+}
+
+impl fmt::Display for SubjectKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Node => "node",
+            Self::Edge => "edge",
+            Self::Hyperedge => "hyperedge",
+        })
+    }
+}
+
+/// One broken invariant on one node, edge or hyperedge
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Error)]
+#[error("{kind} {subject_id}: {rule}")]
+pub struct ValidationIssue {
+    pub subject_id: Uuid,
+    pub kind: SubjectKind,
+    #[serde(flatten)]
+    pub rule: ValidationRule,
+}
+
+/// Result of `MultiIntentGraph::validate`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ValidationReport {
+    pub valid: bool,
+    pub nodes_checked: usize,
+    pub edges_checked: usize,
+    pub hyperedges_checked: usize,
+    pub issues: Vec<ValidationIssue>,   // by kind, then subject ID
+}
+
+fn in_unit_range(x: f32) -> bool {
+    (0.0..=1.0).contains(&x)
+}
+
+fn is_rfc3339(created_at: &str) -> bool {
+    chrono::DateTime::parse_from_rfc3339(created_at).is_ok()
+}
+
+/// Invariants shared by edges and hyperedges
+fn relationship_rules(edge_type: &EdgeType, label: &str, weight: f32, confidence: f32, evidence_refs: &[String], created_at: &str) -> Vec<ValidationRule> {
+    let mut rules = vec![];
+    if !in_unit_range(confidence) {
+        rules.push(ValidationRule::ConfidenceRange { confidence });
+    }
+    if !in_unit_range(weight) {
+        rules.push(ValidationRule::WeightRange { weight });
+    }
+    if label.trim().is_empty() {
+        rules.push(ValidationRule::EmptyLabel);
+    }
+    if !is_rfc3339(created_at) {
+        rules.push(ValidationRule::Timestamp { created_at: created_at.to_string() });
+    }
+    if *edge_type == EdgeType::Causal && evidence_refs.iter().all(|r| r.trim().is_empty()) {
+        rules.push(ValidationRule::CausalWithoutEvidence);
+    }
+    rules
+}
+
+fn issues(subject_id: Uuid, kind: SubjectKind, rules: Vec<ValidationRule>) -> Vec<ValidationIssue> {
+    rules.into_iter().map(|rule| ValidationIssue { subject_id, kind, rule }).collect()
+}
+
+pub fn validate_node(node: &IntentNode) -> Vec<ValidationIssue> {
+    let mut rules = vec![];
+    let confidence = node.metadata.confidence;
+    if !in_unit_range(confidence) {
+        rules.push(ValidationRule::ConfidenceRange { confidence });
+    }
+    if node.label().trim().is_empty() {
+        rules.push(ValidationRule::EmptyLabel);
+    }
+    if !is_rfc3339(&node.metadata.created_at) {
+        rules.push(ValidationRule::Timestamp { created_at: node.metadata.created_at.clone() });
+    }
+    issues(node.id, SubjectKind::Node, rules)
+}
+
+/// Edge invariants; domains must be spelled as `ResearchDomain` variants ("PublicHealth")
+pub fn validate_edge(edge: &GraphEdge) -> Vec<ValidationIssue> {
+    let m = &edge.metadata;
+    let mut rules = relationship_rules(&edge.edge_type, &edge.label, edge.weight, m.confidence, &m.evidence_refs, &m.created_at);
+    for (field, domain) in [("source_domain", &m.source_domain), ("target_domain", &m.target_domain)] {
+        if !DOMAINS.contains(&domain.as_str()) {
+            rules.push(ValidationRule::UnknownDomain { field: field.into(), domain: domain.clone() });
+        }
+    }
+    issues(edge.id, SubjectKind::Edge, rules)
+}
+
+pub fn validate_hyperedge(hyperedge: &HyperEdge) -> Vec<ValidationIssue> {
+    let h = hyperedge;
+    let rules = relationship_rules(&h.edge_type, &h.label, h.weight, h.confidence, &h.evidence_refs, &h.created_at);
+    issues(h.id, SubjectKind::Hyperedge, rules)
+}
+
+impl MultiIntentGraph {
+    /// Issue for a relationship of a custom type that `edge_vocabulary` does not list
+    pub fn check_edge_type(&self, subject_id: Uuid, kind: SubjectKind, edge_type: &EdgeType) -> Option<ValidationIssue> {
+        (!self.edge_vocabulary.accepts(edge_type)).then(|| ValidationIssue {
+            subject_id,
+            kind,
+            rule: ValidationRule::UnregisteredEdgeType { edge_type: edge_type.name().to_string() },
+        })
+    }
+
+    /// Check every node, edge and hyperedge against the schema invariants and
+    /// the graph's edge vocabulary
+    pub fn validate(&self) -> ValidationReport {
+        let mut nodes: Vec<&IntentNode> = self.intent_nodes.values().collect();
+        nodes.sort_by_key(|n| n.id);
+        let mut edges: Vec<&GraphEdge> = self.edges.values().collect();
+        edges.sort_by_key(|e| e.id);
+        let mut hyperedges: Vec<&HyperEdge> = self.hyperedges.values().collect();
+        hyperedges.sort_by_key(|h| h.id);
+
+        let issues: Vec<ValidationIssue> = nodes.iter().flat_map(|n| validate_node(n))
+            .chain(edges.iter().flat_map(|e| {
+                validate_edge(e).into_iter().chain(self.check_edge_type(e.id, SubjectKind::Edge, &e.edge_type))
+            }))
+            .chain(hyperedges.iter().flat_map(|h| {
+                validate_hyperedge(h).into_iter().chain(self.check_edge_type(h.id, SubjectKind::Hyperedge, &h.edge_type))
+            }))
+            .collect();
+        ValidationReport {
+            valid: issues.is_empty(),
+            nodes_checked: nodes.len(),
+            edges_checked: edges.len(),
+            hyperedges_checked: hyperedges.len(),
+            issues,
+        }
+    }
+}
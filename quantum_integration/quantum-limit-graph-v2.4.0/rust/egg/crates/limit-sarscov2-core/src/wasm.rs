@@ -0,0 +1,108 @@
+// limit-sarscov2-core/src/wasm.rs
+// wasm-bindgen wrappers for in-browser analysis (feature "wasm")
+
+use serde::Serialize;
+use uuid::Uuid;
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    edges::GraphEdge,
+    metrics::SARSCoV2Metrics,
+    multi_intent_graph::{IntentNode, MultiIntentGraph},
+    serendipity_trace::HypothesisType,
+};
+
+/// Plain JS objects and arrays (not `Map`s), so results look like the REST JSON
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, JsError> {
+    value.serialize(&serde_wasm_bindgen::Serializer::json_compatible()).map_err(|e| JsError::new(&e.to_string()))
+}
+
+fn from_js<T: serde::de::DeserializeOwned>(value: JsValue) -> Result<T, JsError> {
+    serde_wasm_bindgen::from_value(value).map_err(|e| JsError::new(&e.to_string()))
+}
+
+fn parse_id(id: &str) -> Result<Uuid, JsError> {
+    id.parse().map_err(|_| JsError::new(&format!("{:?} is not a UUID", id)))
+}
+
+/// A multi-intent graph held in WASM memory. Construct it from the JSON the
+/// server returns (e.g. `JSON.parse` of a stored graph).
+#[wasm_bindgen(js_name = Graph)]
+pub struct WasmGraph {
+    inner: MultiIntentGraph,
+}
+
+#[wasm_bindgen(js_class = Graph)]
+impl WasmGraph {
+    #[wasm_bindgen(constructor)]
+    pub fn new(graph: JsValue) -> Result<WasmGraph, JsError> {
+        Ok(Self { inner: from_js(graph)? })
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn id(&self) -> String {
+        self.inner.id.to_string()
+    }
+
+    #[wasm_bindgen(js_name = toJSON)]
+    pub fn to_json(&self) -> Result<JsValue, JsError> {
+        to_js(&self.inner)
+    }
+
+    #[wasm_bindgen(js_name = addNode)]
+    pub fn add_node(&mut self, node: JsValue) -> Result<(), JsError> {
+        self.inner.add_node(from_js::<IntentNode>(node)?);
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = addEdge)]
+    pub fn add_edge(&mut self, edge: JsValue) -> Result<(), JsError> {
+        self.inner.add_edge(from_js::<GraphEdge>(edge)?);
+        Ok(())
+    }
+
+    /// `GraphStatistics`, including `structure`
+    pub fn statistics(&self) -> Result<JsValue, JsError> {
+        to_js(&self.inner.statistics())
+    }
+
+    /// Domain coverage and serendipity of the base graph
+    pub fn metrics(&self) -> Result<JsValue, JsError> {
+        to_js(&SARSCoV2Metrics::compute(&self.inner.base_graph))
+    }
+
+    /// Node-ID paths from `start` to `end`, at most `max_depth` edges long
+    #[wasm_bindgen(js_name = findPaths)]
+    pub fn find_paths(&self, start: &str, end: &str, max_depth: usize) -> Result<JsValue, JsError> {
+        to_js(&self.inner.find_paths(parse_id(start)?, parse_id(end)?, max_depth))
+    }
+
+    /// Paths for a built-in hypothesis name ("ImmuneEscape") or a custom one
+    #[wasm_bindgen(js_name = generateHypothesisPaths)]
+    pub fn generate_hypothesis_paths(&self, hypothesis: &str, min_confidence: f32) -> Result<JsValue, JsError> {
+        to_js(&self.inner.generate_hypothesis_paths(HypothesisType::custom(hypothesis), min_confidence))
+    }
+
+    /// Cypher-lite query; see `graph_query`
+    pub fn query(&self, query: &str) -> Result<JsValue, JsError> {
+        to_js(&self.inner.query(query).map_err(|e| JsError::new(&e.to_string()))?)
+    }
+
+    /// Induced subgraph on the given node IDs
+    pub fn subgraph(&self, node_ids: Vec<String>) -> Result<WasmGraph, JsError> {
+        let ids = node_ids.iter().map(|id| parse_id(id)).collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { inner: self.inner.subgraph(&ids) })
+    }
+
+    /// Subgraph of everything within `hops` edges of `center`
+    pub fn neighborhood(&self, center: &str, hops: usize) -> Result<WasmGraph, JsError> {
+        let ids = self.inner.neighborhood(parse_id(center)?, hops);
+        Ok(Self { inner: self.inner.subgraph(&ids) })
+    }
+
+    /// `SerendipitySummary` of every trace
+    #[wasm_bindgen(js_name = traceSummaries)]
+    pub fn trace_summaries(&self) -> Result<JsValue, JsError> {
+        to_js(&self.inner.serendipity_traces.iter().map(|t| t.summary()).collect::<Vec<_>>())
+    }
+}
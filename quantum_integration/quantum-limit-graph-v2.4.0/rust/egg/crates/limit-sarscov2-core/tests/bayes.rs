@@ -0,0 +1,75 @@
+// limit-sarscov2-core/tests/bayes.rs
+// Noisy-OR belief updating on a two-node causal network, checked against hand-worked posteriors
+
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use limit_sarscov2_core::{
+    multi_intent_graph::MultiIntentGraphBuilder,
+    nodes::{GenomicsNode, VirologyNode, VirusNode},
+    BayesError, BayesOptions, GraphEdge, MultiIntentGraph, SarsCov2Graph,
+};
+
+/// Omicron (confidence 0.9) →causal 0.8→ spike binding
+fn network() -> (MultiIntentGraph, Uuid, Uuid) {
+    let root = VirusNode { id: Uuid::new_v4(), name: "SARS-CoV-2".into(), genome_kb: 30.0 };
+    let omicron = GenomicsNode { id: Uuid::new_v4(), variant: "Omicron".into(), mutations: vec![], unparsed_mutations: vec![] };
+    let spike = VirologyNode { id: Uuid::new_v4(), topic: "Spike-ACE2 binding".into(), details: "RBD affinity".into(), regions: vec![] };
+    let alters = GraphEdge::new_causal(omicron.id, spike.id, "alters".into(), "Genomics".into(), "Virology".into(), vec!["PMID:1".into()], 0.8);
+    let ids = (omicron.id, spike.id);
+    let graph = MultiIntentGraphBuilder::new(SarsCov2Graph::new(root))
+        .with_variant_node(omicron, "immune_escape", 4, 0.9)
+        .with_biology_node(spike, "immune_escape", 3, 0.5)
+        .with_edge(alters)
+        .build();
+    (graph, ids.0, ids.1)
+}
+
+fn close(actual: f32, expected: f32) -> bool {
+    (actual - expected).abs() < 1e-5
+}
+
+#[test]
+fn noisy_or_cpt_has_leak_and_strength() {
+    let (graph, omicron, spike) = network();
+    let bn = graph.bayesian_network(&BayesOptions::default());
+    assert_eq!(bn.order, [omicron, spike]);
+    assert_eq!(bn.nodes[&omicron].prior, Some(0.9));
+
+    // P(spike | omicron) = 1 - (1 - 0.01)(1 - 0.8) = 0.802; P(spike | ¬omicron) = leak
+    let cpt = bn.nodes[&spike].cpt().unwrap();
+    assert_eq!(cpt.iter().map(|r| r.parents.clone()).collect::<Vec<_>>(), [vec![false], vec![true]]);
+    assert!(close(cpt[0].probability, 0.01));
+    assert!(close(cpt[1].probability, 0.802));
+}
+
+#[test]
+fn posteriors_match_hand_computation() {
+    let (graph, omicron, spike) = network();
+    let bn = graph.bayesian_network(&BayesOptions::default());
+
+    // P(spike) = 0.9 × 0.802 + 0.1 × 0.01 = 0.7228
+    // P(omicron | spike) = 0.7218 / 0.7228
+    let beliefs = bn.query(&HashMap::from([(spike, true)]), &[omicron, spike]).unwrap();
+    assert!(close(beliefs[0].prior, 0.9));
+    assert!(close(beliefs[0].posterior, 0.7218 / 0.7228), "{}", beliefs[0].posterior);
+    assert!(close(beliefs[1].prior, 0.7228), "{}", beliefs[1].prior);
+    assert_eq!(beliefs[1].posterior, 1.0);
+
+    // P(omicron | ¬spike) = 0.9 × 0.198 / (0.9 × 0.198 + 0.1 × 0.99) = 0.1782 / 0.2772
+    let beliefs = bn.query(&HashMap::from([(spike, false)]), &[omicron]).unwrap();
+    assert!(close(beliefs[0].posterior, 0.1782 / 0.2772), "{}", beliefs[0].posterior);
+}
+
+#[test]
+fn impossible_evidence_and_unknown_nodes_are_errors() {
+    let (graph, omicron, spike) = network();
+    let bn = graph.bayesian_network(&BayesOptions { leak: 0.0 });
+    let mut certain = bn.clone();
+    certain.nodes.get_mut(&omicron).unwrap().prior = Some(0.0);
+    // Without the cause and without leak, the effect cannot occur
+    assert_eq!(certain.query(&HashMap::from([(spike, true)]), &[omicron]).unwrap_err(), BayesError::ImpossibleEvidence);
+
+    let stranger = Uuid::new_v4();
+    assert_eq!(bn.query(&HashMap::new(), &[stranger]).unwrap_err(), BayesError::UnknownNode { node: stranger });
+}
@@ -0,0 +1,134 @@
+// limit-sarscov2-core/tests/counterfactual.rs
+// Counterfactual outcomes of two diverging hypothesis paths against the factual branch
+
+use uuid::Uuid;
+
+use limit_sarscov2_core::{
+    counterfactual::CounterfactualError,
+    multi_intent_graph::MultiIntentGraphBuilder,
+    nodes::{ImmunologyNode, PublicHealthNode, TreatmentNode, VirologyNode, VirusNode},
+    GraphEdge, HypothesisPath, HypothesisType, MultiIntentGraph, SarsCov2Graph,
+};
+
+/// Campaign →0.9→ neutralization →0.8→ hospitalization (factual: vaccinate),
+/// campaign →0.4→ Paxlovid →0.5→ hospitalization (counterfactual: treat instead),
+/// and neutralization →0.5→ reduced shedding, off both paths
+struct Scenario {
+    graph: MultiIntentGraph,
+    campaign: Uuid,
+    antibody: Uuid,
+    paxlovid: Uuid,
+    hospital: Uuid,
+    shedding: Uuid,
+    vaccinate: Uuid,
+    treat: Uuid,
+}
+
+fn causal(source: Uuid, target: Uuid, label: &str, confidence: f32) -> GraphEdge {
+    GraphEdge::new_causal(source, target, label.into(), "A".into(), "B".into(), vec![format!("PMID:{}", label)], confidence)
+}
+
+fn path(nodes: Vec<Uuid>, edges: Vec<Uuid>) -> HypothesisPath {
+    HypothesisPath {
+        id: Uuid::new_v4(),
+        hypothesis_type: HypothesisType::PublicHealthImpact,
+        description: String::new(),
+        node_sequence: nodes,
+        edge_sequence: edges,
+        total_confidence: 0.0,
+        evidence_coverage: 0.0,
+    }
+}
+
+fn scenario() -> Scenario {
+    let root = VirusNode { id: Uuid::new_v4(), name: "SARS-CoV-2".into(), genome_kb: 30.0 };
+    let campaign = PublicHealthNode { id: Uuid::new_v4(), policy: "Vaccination campaign".into(), effect: "Coverage".into(), region: None };
+    let hospital = PublicHealthNode { id: Uuid::new_v4(), policy: "Hospital capacity".into(), effect: "Fewer admissions".into(), region: None };
+    let antibody = ImmunologyNode { id: Uuid::new_v4(), topic: "Antibody neutralization".into(), details: "RBD-directed".into() };
+    let paxlovid = TreatmentNode { id: Uuid::new_v4(), therapy: "Paxlovid".into(), mechanism: "Protease inhibitor".into() };
+    let shedding = VirologyNode { id: Uuid::new_v4(), topic: "Viral shedding".into(), details: "Reduced".into(), regions: vec![] };
+
+    let induces = causal(campaign.id, antibody.id, "induces", 0.9);
+    let protects = causal(antibody.id, hospital.id, "protects", 0.8);
+    let prescribes = causal(campaign.id, paxlovid.id, "prescribes", 0.4);
+    let treats = causal(paxlovid.id, hospital.id, "treats", 0.5);
+    let clears = causal(antibody.id, shedding.id, "clears", 0.5);
+    let vaccinate = path(vec![campaign.id, antibody.id, hospital.id], vec![induces.id, protects.id]);
+    let treat = path(vec![campaign.id, paxlovid.id, hospital.id], vec![prescribes.id, treats.id]);
+
+    let ids = (campaign.id, antibody.id, paxlovid.id, hospital.id, shedding.id, vaccinate.id, treat.id);
+    let graph = MultiIntentGraphBuilder::new(SarsCov2Graph::new(root))
+        .with_public_health_node(campaign, "vaccination", 2, 0.9)
+        .with_public_health_node(hospital, "vaccination", 2, 0.5)
+        .with_immunology_node(antibody, "vaccination", 2, 0.5)
+        .with_treatment_node(paxlovid, "vaccination", 2, 0.5)
+        .with_biology_node(shedding, "vaccination", 2, 0.5)
+        .with_edge(induces)
+        .with_edge(protects)
+        .with_edge(prescribes)
+        .with_edge(treats)
+        .with_edge(clears)
+        .with_hypothesis_path(vaccinate)
+        .with_hypothesis_path(treat)
+        .build();
+    let (campaign, antibody, paxlovid, hospital, shedding, vaccinate, treat) = ids;
+    Scenario { graph, campaign, antibody, paxlovid, hospital, shedding, vaccinate, treat }
+}
+
+fn close(actual: f32, expected: f32) -> bool {
+    (actual - expected).abs() < 1e-5
+}
+
+#[test]
+fn counterfactual_outcomes_are_compared_with_the_factual_branch() {
+    let s = scenario();
+    let report = s.graph.compare_counterfactual(s.vaccinate, s.treat).unwrap();
+    assert_eq!(report.divergence_node, s.campaign);
+    assert_eq!(report.shared_nodes, 1);
+
+    assert_eq!(report.factual.nodes, [s.antibody, s.hospital]);
+    assert!(close(report.factual.confidence, 0.9 * 0.8));
+    assert_eq!(report.factual.reachable, 3);
+    assert_eq!(report.counterfactual.nodes, [s.paxlovid, s.hospital]);
+    assert!(close(report.counterfactual.confidence, 0.4 * 0.5));
+    assert_eq!(report.counterfactual.reachable, 2);
+
+    // (node, factual, counterfactual, terminal), largest difference first
+    let expected = [
+        (s.antibody, Some(0.9), None, false),
+        (s.hospital, Some(0.72), Some(0.2), true),
+        (s.shedding, Some(0.45), None, true),
+        (s.paxlovid, None, Some(0.4), false),
+    ];
+    assert_eq!(report.outcomes.len(), expected.len());
+    for (outcome, (id, factual, counterfactual, terminal)) in report.outcomes.iter().zip(expected) {
+        assert_eq!(outcome.node_id, id, "{:?}", outcome);
+        assert_eq!(outcome.factual.is_some(), factual.is_some(), "{:?}", outcome);
+        assert!(close(outcome.factual.unwrap_or(0.0), factual.unwrap_or(0.0)), "{:?}", outcome);
+        assert_eq!(outcome.counterfactual.is_some(), counterfactual.is_some(), "{:?}", outcome);
+        assert!(close(outcome.counterfactual.unwrap_or(0.0), counterfactual.unwrap_or(0.0)), "{:?}", outcome);
+        assert!(close(outcome.difference, factual.unwrap_or(0.0) - counterfactual.unwrap_or(0.0)));
+        assert_eq!(outcome.terminal, terminal);
+    }
+    let hospital = &report.outcomes[1];
+    assert_eq!(hospital.label, "Hospital capacity");
+}
+
+#[test]
+fn swapping_the_paths_negates_the_differences() {
+    let s = scenario();
+    let forward = s.graph.compare_counterfactual(s.vaccinate, s.treat).unwrap();
+    let backward = s.graph.compare_counterfactual(s.treat, s.vaccinate).unwrap();
+    for (f, b) in forward.outcomes.iter().zip(&backward.outcomes) {
+        assert_eq!(f.node_id, b.node_id);
+        assert!(close(f.difference, -b.difference));
+    }
+}
+
+#[test]
+fn paths_must_exist_and_diverge() {
+    let s = scenario();
+    let stranger = Uuid::new_v4();
+    assert_eq!(s.graph.compare_counterfactual(s.vaccinate, stranger).unwrap_err(), CounterfactualError::UnknownPath { path: stranger });
+    assert_eq!(s.graph.compare_counterfactual(s.vaccinate, s.vaccinate).unwrap_err(), CounterfactualError::NotDivergent);
+}
@@ -0,0 +1,151 @@
+// limit-sarscov2-core/tests/dedup.rs
+// Near-duplicate nodes are found and merged with their edges rewired; distinct nodes stay apart
+
+use uuid::Uuid;
+
+use limit_sarscov2_core::{
+    find_duplicates, name_similarity,
+    multi_intent_graph::MultiIntentGraphBuilder,
+    mutation::Mutation,
+    nodes::{GenomicsNode, ImmunologyNode, VirologyNode, VirusNode},
+    DedupOptions, GraphEdge, MultiIntentGraph, SarsCov2Graph,
+};
+
+/// "Omicron BA.5" and its sparser duplicate "B.1.1.529.5", a distinct BA.2,
+/// two spellings of spike binding, furin cleavage, and an immunology node
+/// labelled like the spike nodes
+struct Fixture {
+    graph: MultiIntentGraph,
+    ba5: Uuid,
+    ba5_dup: Uuid,
+    ba2: Uuid,
+    spike: Uuid,
+    spike_dup: Uuid,
+    furin: Uuid,
+    antibody: Uuid,
+    alters: Uuid,
+}
+
+fn fixture() -> Fixture {
+    let mutations = |list: &[&str]| list.iter().map(|m| m.parse::<Mutation>().unwrap()).collect::<Vec<_>>();
+    let root = VirusNode { id: Uuid::new_v4(), name: "SARS-CoV-2".into(), genome_kb: 30.0 };
+    let ba5 = GenomicsNode { id: Uuid::new_v4(), variant: "Omicron BA.5".into(), mutations: mutations(&["S:L452R", "S:F486V"]), unparsed_mutations: vec![] };
+    let ba5_dup = GenomicsNode { id: Uuid::new_v4(), variant: "B.1.1.529.5".into(), mutations: mutations(&["S:L452R"]), unparsed_mutations: vec![] };
+    let ba2 = GenomicsNode { id: Uuid::new_v4(), variant: "BA.2".into(), mutations: mutations(&["S:L452R", "S:Q493R"]), unparsed_mutations: vec![] };
+    let spike = VirologyNode { id: Uuid::new_v4(), topic: "Spike-ACE2 binding".into(), details: "RBD affinity".into(), regions: vec![] };
+    let spike_dup = VirologyNode { id: Uuid::new_v4(), topic: "spike ACE2 binding".into(), details: "".into(), regions: vec![] };
+    let furin = VirologyNode { id: Uuid::new_v4(), topic: "Furin cleavage".into(), details: "S1/S2 site".into(), regions: vec![] };
+    let antibody = ImmunologyNode { id: Uuid::new_v4(), topic: "Spike-ACE2 binding".into(), details: "Blocking antibodies".into() };
+    let alters = GraphEdge::new_causal(ba5.id, spike.id, "alters".into(), "Genomics".into(), "Virology".into(), vec!["PMID:1".into()], 0.6);
+    // Repeats `alters` once the duplicate is merged
+    let alters_dup = GraphEdge::new_causal(ba5_dup.id, spike.id, "alters".into(), "Genomics".into(), "Virology".into(), vec!["PMID:2".into()], 0.9);
+    // Between the two duplicates: a self-loop after merging
+    let same = GraphEdge::new_correlative(ba5.id, ba5_dup.id, "same lineage".into(), "Genomics".into(), "Genomics".into(), vec!["PMID:3".into()], 0.5);
+    let escapes = GraphEdge::new_causal(ba5_dup.id, antibody.id, "escapes".into(), "Genomics".into(), "Immunology".into(), vec!["PMID:4".into()], 0.7);
+    let ids = (ba5.id, ba5_dup.id, ba2.id, spike.id, spike_dup.id, furin.id, antibody.id, alters.id);
+    let graph = MultiIntentGraphBuilder::new(SarsCov2Graph::new(root))
+        .with_variant_node(ba5, "immune_escape", 4, 0.8)
+        .with_variant_node(ba5_dup, "immune_escape", 1, 0.9)
+        .with_variant_node(ba2, "immune_escape", 3, 0.8)
+        .with_biology_node(spike, "immune_escape", 3, 0.7)
+        .with_biology_node(spike_dup, "immune_escape", 1, 0.5)
+        .with_biology_node(furin, "immune_escape", 2, 0.6)
+        .with_immunology_node(antibody, "immune_escape", 2, 0.6)
+        .with_edge(alters)
+        .with_edge(alters_dup)
+        .with_edge(same)
+        .with_edge(escapes)
+        .build();
+    let (ba5, ba5_dup, ba2, spike, spike_dup, furin, antibody, alters) = ids;
+    Fixture { graph, ba5, ba5_dup, ba2, spike, spike_dup, furin, antibody, alters }
+}
+
+fn close(actual: f32, expected: f32) -> bool {
+    (actual - expected).abs() < 1e-5
+}
+
+#[test]
+fn names_compare_by_lineage_and_tokens() {
+    assert!(close(name_similarity("Omicron BA.5", "B.1.1.529.5"), 1.0));
+    assert!(close(name_similarity("Omicron BA.5", "BA.2"), 0.0));
+    assert!(close(name_similarity("Spike-ACE2 binding", "spike ACE2 binding"), 1.0));
+    assert!(close(name_similarity("ACE2 binding", "Spike ACE2 binding"), 0.9));
+    assert!(name_similarity("Spike-ACE2 binding", "Furin cleavage") < 0.5);
+    assert!(close(name_similarity("", "Furin cleavage"), 0.0));
+}
+
+#[test]
+fn finds_near_duplicates_within_a_domain_only() {
+    let f = fixture();
+    let candidates = find_duplicates(&f.graph, &DedupOptions::default());
+    let mut pairs: Vec<(Uuid, Uuid)> = candidates.iter().map(|c| (c.keep, c.duplicate)).collect();
+    pairs.sort();
+    let mut expected = vec![(f.ba5, f.ba5_dup), (f.spike, f.spike_dup)];
+    expected.sort();
+    assert_eq!(pairs, expected);
+
+    // Same lineage, and the duplicate's one mutation is on the richer node
+    let variant = candidates.iter().find(|c| c.keep == f.ba5).unwrap();
+    assert!(close(variant.score, 1.0));
+    assert_eq!(variant.shared_mutations, ["S:L452R"]);
+    assert_eq!(variant.duplicate_label, "B.1.1.529.5");
+
+    // BA.2 shares a mutation with BA.5 but names another lineage:
+    // (0.5 · 0 + 0.3 · 0.5) / 0.8 stays below the threshold
+    let options = DedupOptions { min_score: 0.0, ..DedupOptions::default() };
+    let all = find_duplicates(&f.graph, &options);
+    let ba2 = all.iter().find(|c| c.keep == f.ba5 && c.duplicate == f.ba2).unwrap();
+    assert!(close(ba2.score, 0.1875));
+    assert!(all.iter().all(|c| c.keep != f.antibody && c.duplicate != f.antibody));
+}
+
+#[test]
+fn merging_rewires_collapses_and_drops_self_loops() {
+    let mut f = fixture();
+    let merge = f.graph.merge_nodes(f.ba5, f.ba5_dup).unwrap();
+    assert_eq!((merge.kept, merge.merged), (f.ba5, f.ba5_dup));
+    assert_eq!(merge.merged_label, "B.1.1.529.5");
+    assert_eq!((merge.edges_rewired, merge.edges_collapsed, merge.self_loops_removed), (1, 1, 1));
+
+    assert!(!f.graph.intent_nodes.contains_key(&f.ba5_dup));
+    assert_eq!(f.graph.edges.len(), 2);
+    assert!(f.graph.edges.values().all(|e| e.source_id != f.ba5_dup && e.target_id != f.ba5_dup));
+    // The repeated edge folded into the kept one, taking its evidence and confidence
+    let alters = &f.graph.edges[&f.alters];
+    assert_eq!(alters.metadata.evidence_refs, ["PMID:1", "PMID:2"]);
+    assert!(close(alters.metadata.confidence, 0.9));
+    assert!(f.graph.edges.values().any(|e| e.source_id == f.ba5 && e.target_id == f.antibody));
+
+    let kept = &f.graph.intent_nodes[&f.ba5];
+    assert_eq!(kept.metadata.evidence_count, 4);
+    assert!(close(kept.metadata.confidence, 0.9));
+}
+
+#[test]
+fn dedup_merges_duplicates_and_keeps_distinct_nodes() {
+    let mut f = fixture();
+    let merges = f.graph.dedup(&DedupOptions::default());
+    let mut merged: Vec<(Uuid, Uuid)> = merges.iter().map(|m| (m.kept, m.merged)).collect();
+    merged.sort();
+    let mut expected = vec![(f.ba5, f.ba5_dup), (f.spike, f.spike_dup)];
+    expected.sort();
+    assert_eq!(merged, expected);
+    assert!(merges.iter().all(|m| m.score.is_some_and(|s| close(s, 1.0))));
+
+    for id in [f.ba5, f.ba2, f.spike, f.furin, f.antibody] {
+        assert!(f.graph.intent_nodes.contains_key(&id));
+    }
+    assert_eq!(f.graph.intent_nodes.len(), 5);
+
+    // Nothing left to merge
+    assert!(f.graph.dedup(&DedupOptions::default()).is_empty());
+}
+
+#[test]
+fn refuses_to_merge_across_domains_or_into_itself() {
+    let mut f = fixture();
+    assert!(f.graph.merge_nodes(f.spike, f.antibody).is_none());
+    assert!(f.graph.merge_nodes(f.ba5, f.ba5).is_none());
+    assert!(f.graph.merge_nodes(f.ba5, Uuid::new_v4()).is_none());
+    assert_eq!(f.graph.intent_nodes.len(), 7);
+}
@@ -0,0 +1,119 @@
+// limit-sarscov2-core/tests/diff.rs
+// Nodes and edges added, removed and modified between two versions of a graph
+
+use uuid::Uuid;
+
+use limit_sarscov2_core::{
+    multi_intent_graph::MultiIntentGraphBuilder,
+    nodes::{GenomicsNode, ImmunologyNode, TreatmentNode, VirologyNode, VirusNode},
+    validate::SubjectKind,
+    ChangeKind, GraphEdge, MultiIntentGraph, SarsCov2Graph,
+};
+
+/// An older and a newer version. Paxlovid and its edge are new; the antibody
+/// node and its edge are gone; the Omicron edge lost confidence and gained a
+/// reference; spike binding gained confidence.
+struct Versions {
+    older: MultiIntentGraph,
+    newer: MultiIntentGraph,
+    spike: Uuid,
+    antibody: Uuid,
+    paxlovid: Uuid,
+    alters: Uuid,
+    escapes: Uuid,
+    treats: Uuid,
+}
+
+fn versions() -> Versions {
+    let root = VirusNode { id: Uuid::new_v4(), name: "SARS-CoV-2".into(), genome_kb: 30.0 };
+    let omicron = GenomicsNode { id: Uuid::new_v4(), variant: "Omicron".into(), mutations: vec![], unparsed_mutations: vec![] };
+    let spike = VirologyNode { id: Uuid::new_v4(), topic: "Spike-ACE2 binding".into(), details: "RBD affinity".into(), regions: vec![] };
+    let antibody = ImmunologyNode { id: Uuid::new_v4(), topic: "Antibody neutralization".into(), details: "RBD-directed".into() };
+    let paxlovid = TreatmentNode { id: Uuid::new_v4(), therapy: "Paxlovid".into(), mechanism: "Protease inhibitor".into() };
+    let alters = GraphEdge::new_causal(omicron.id, spike.id, "alters".into(), "Genomics".into(), "Virology".into(), vec!["PMID:1".into()], 0.9);
+    let escapes = GraphEdge::new_causal(spike.id, antibody.id, "escapes".into(), "Virology".into(), "Immunology".into(), vec!["PMID:2".into()], 0.8);
+    let treats = GraphEdge::new_correlative(paxlovid.id, spike.id, "associated".into(), "Treatment".into(), "Virology".into(), vec!["PMID:3".into()], 0.7);
+    let ids = (spike.id, antibody.id, paxlovid.id, alters.id, escapes.id, treats.id);
+    let full = MultiIntentGraphBuilder::new(SarsCov2Graph::new(root))
+        .with_variant_node(omicron, "immune_escape", 4, 0.9)
+        .with_biology_node(spike, "immune_escape", 3, 0.3)
+        .with_immunology_node(antibody, "immune_escape", 2, 0.2)
+        .with_treatment_node(paxlovid, "therapeutics", 1, 0.6)
+        .with_edge(alters)
+        .with_edge(escapes)
+        .with_edge(treats)
+        .build();
+    let (spike, antibody, paxlovid, alters, escapes, treats) = ids;
+
+    let mut older = full.clone();
+    older.intent_nodes.remove(&paxlovid);
+    older.edges.remove(&treats);
+
+    let mut newer = full;
+    newer.intent_nodes.remove(&antibody);
+    newer.edges.remove(&escapes);
+    let edge = newer.edges.get_mut(&alters).unwrap();
+    edge.metadata.confidence = 0.6;
+    edge.metadata.evidence_refs.push("PMID:4".into());
+    newer.intent_nodes.get_mut(&spike).unwrap().metadata.confidence = 0.5;
+
+    Versions { older, newer, spike, antibody, paxlovid, alters, escapes, treats }
+}
+
+#[test]
+fn reports_added_removed_and_changed_nodes_and_edges() {
+    let v = versions();
+    let diff = v.older.diff(&v.newer);
+    let entries: Vec<(ChangeKind, Uuid, SubjectKind, &str)> =
+        diff.entries.iter().map(|e| (e.kind, e.subject_id, e.subject, e.summary.as_str())).collect();
+    assert_eq!(entries, [
+        (ChangeKind::Added, v.paxlovid, SubjectKind::Node, "Added Treatment node Paxlovid (conf 0.60)"),
+        (ChangeKind::Added, v.treats, SubjectKind::Edge, "Added correlative edge Paxlovid → Spike-ACE2 binding (conf 0.70, 1 ref)"),
+        (ChangeKind::Removed, v.antibody, SubjectKind::Node, "Removed Immunology node Antibody neutralization"),
+        (ChangeKind::Removed, v.escapes, SubjectKind::Edge, "Removed causal edge Spike-ACE2 binding → Antibody neutralization"),
+        (ChangeKind::Changed, v.spike, SubjectKind::Node, "Confidence on Virology node Spike-ACE2 binding raised from 0.30 to 0.50"),
+        (ChangeKind::Changed, v.alters, SubjectKind::Edge, "Confidence on causal edge Omicron → Spike-ACE2 binding lowered from 0.90 to 0.60"),
+        (ChangeKind::Changed, v.alters, SubjectKind::Edge, "Evidence on causal edge Omicron → Spike-ACE2 binding: added PMID:4"),
+    ]);
+    assert_eq!((diff.count(ChangeKind::Added), diff.count(ChangeKind::Removed), diff.count(ChangeKind::Changed)), (2, 2, 3));
+}
+
+#[test]
+fn reversing_the_versions_swaps_added_and_removed() {
+    let v = versions();
+    let diff = v.newer.diff(&v.older);
+    let ids = |kind: ChangeKind| {
+        let mut ids: Vec<Uuid> = diff.entries.iter().filter(|e| e.kind == kind).map(|e| e.subject_id).collect();
+        ids.sort();
+        ids
+    };
+    let sorted = |mut ids: Vec<Uuid>| {
+        ids.sort();
+        ids
+    };
+    assert_eq!(ids(ChangeKind::Added), sorted(vec![v.antibody, v.escapes]));
+    assert_eq!(ids(ChangeKind::Removed), sorted(vec![v.paxlovid, v.treats]));
+    assert!(diff.entries.iter().any(|e| e.summary == "Evidence on causal edge Omicron → Spike-ACE2 binding: removed PMID:4"));
+}
+
+#[test]
+fn identical_versions_have_no_changes() {
+    let v = versions();
+    let diff = v.newer.diff(&v.newer.clone());
+    assert!(diff.is_empty());
+    assert_eq!(diff.to_markdown("Release 2"), "# Release 2\n\nNo changes.\n");
+
+    // Confidence moves below 0.001 are not reported
+    let mut nudged = v.newer.clone();
+    nudged.edges.get_mut(&v.alters).unwrap().metadata.confidence += 1e-4;
+    assert!(v.newer.diff(&nudged).is_empty());
+}
+
+#[test]
+fn markdown_groups_entries_by_kind() {
+    let v = versions();
+    let markdown = v.older.diff(&v.newer).to_markdown("Release 2");
+    assert!(markdown.starts_with("# Release 2\n\n## Added\n\n- Added Treatment node Paxlovid (conf 0.60)\n"));
+    let (added, removed, changed) = (markdown.find("## Added").unwrap(), markdown.find("## Removed").unwrap(), markdown.find("## Changed").unwrap());
+    assert!(added < removed && removed < changed);
+}
@@ -0,0 +1,146 @@
+// limit-sarscov2-core/tests/genome.rs
+// Mutation calling against a small synthetic reference with one annotated gene
+
+use limit_sarscov2_core::genome::{FastaRecord, GeneAnnotation, GenomeError, MutationCaller, ReferenceGenome};
+use limit_sarscov2_core::mutation::{Mutation, MutationKind};
+
+const FLANK: usize = 100;       // untranslated bases either side of the gene
+const CODONS: usize = 120;      // gene length without its stop codon
+const GENE_START: usize = FLANK + 1;
+
+/// Codons fixed at the given (1-based) amino-acid positions: HV69-70 in the
+/// spike's context, and the neighbours of the substitution and insertion sites
+const FIXED: &[(usize, &str)] = &[
+    (50, "CTG"),                // L
+    (67, "GCT"), (68, "ATA"), (69, "CAT"), (70, "GTC"), (71, "TCT"),   // A I H V S
+    (100, "TTC"), (101, "TGG"), // F W
+];
+
+/// Deterministic pseudo-random sequence of `n` bases
+fn bases(seed: u64, n: usize) -> String {
+    let mut x = seed;
+    (0..n)
+        .map(|_| {
+            x = x.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            b"ACGT"[(x >> 33) as usize % 4] as char
+        })
+        .collect()
+}
+
+/// Pseudo-random sense codon: stops become TAC or TGC
+fn codon(i: usize) -> String {
+    let c = bases(i as u64 * 7919 + 13, 3);
+    match c.as_str() {
+        "TAA" | "TAG" | "TGA" => c[..2].to_string() + "C",
+        _ => c,
+    }
+}
+
+fn gene() -> String {
+    let mut cds = String::from("ATG");
+    for aa in 2..=CODONS {
+        match FIXED.iter().find(|(p, _)| *p == aa) {
+            Some((_, c)) => cds.push_str(c),
+            None => cds.push_str(&codon(aa)),
+        }
+    }
+    cds + "TAA"
+}
+
+fn reference_sequence() -> String {
+    bases(1, FLANK) + &gene() + &bases(2, FLANK)
+}
+
+fn caller() -> MutationCaller {
+    let end = (GENE_START + (CODONS + 1) * 3 - 1) as u32;
+    let genes = vec![GeneAnnotation { name: "S".into(), start: GENE_START as u32, end }];
+    MutationCaller::new(ReferenceGenome::new("synthetic", &reference_sequence(), genes))
+}
+
+/// 1-based genome position of the first base of codon `aa`
+fn codon_start(aa: usize) -> usize {
+    GENE_START + (aa - 1) * 3
+}
+
+fn record(sequence: String) -> FastaRecord {
+    FastaRecord { id: "query".into(), description: String::new(), sequence }
+}
+
+fn names(mutations: &[Mutation]) -> Vec<String> {
+    mutations.iter().map(Mutation::to_string).collect()
+}
+
+#[test]
+fn identical_sequence_has_no_mutations() {
+    let calls = caller().call(&record(reference_sequence())).unwrap();
+    assert!(calls.nucleotide.is_empty(), "{:?}", names(&calls.nucleotide));
+    assert!(calls.amino_acid.is_empty(), "{:?}", names(&calls.amino_acid));
+    assert_eq!(calls.aligned_range, (1, reference_sequence().len() as u32));
+    assert!(calls.missing.is_empty());
+    assert_eq!(calls.coverage, 1.0);
+}
+
+#[test]
+fn substitution_is_called_in_genome_and_protein_coordinates() {
+    let mut sequence = reference_sequence().into_bytes();
+    let pos = codon_start(50) + 1;          // CTG → CGG: L50R
+    sequence[pos - 1] = b'G';
+    let calls = caller().call(&record(String::from_utf8(sequence).unwrap())).unwrap();
+
+    assert_eq!(names(&calls.nucleotide), [format!("nuc:T{}G", pos)]);
+    assert_eq!(calls.nucleotide[0].kind, MutationKind::Nucleotide);
+    assert_eq!(names(&calls.amino_acid), ["S:L50R"]);
+    assert!(calls.frameshifts.is_empty());
+}
+
+#[test]
+fn codon_straddling_deletion_is_one_protein_deletion() {
+    // ATA CAT GTC → AT[ACATGT]C: six bases from the last of codon 68, leaving ATC (still I)
+    let reference = reference_sequence();
+    let cut = codon_start(68) + 2;
+    let sequence = format!("{}{}", &reference[..cut - 1], &reference[cut - 1 + 6..]);
+    let calls = caller().call(&record(sequence)).unwrap();
+
+    assert_eq!(calls.nucleotide.len(), 1, "{:?}", names(&calls.nucleotide));
+    let deletion = &calls.nucleotide[0];
+    assert_eq!(deletion.kind, MutationKind::Deletion);
+    assert_eq!(deletion.end() - deletion.position + 1, 6);
+    // Deleting TACATG from one base earlier gives the same sequence
+    assert!((cut as u32 - 1..=cut as u32).contains(&deletion.position), "{}", deletion);
+    assert_eq!(names(&calls.amino_acid), ["S:HV69-70del"]);
+    assert!(calls.frameshifts.is_empty());
+}
+
+#[test]
+fn in_frame_insertion_is_called_after_its_codon() {
+    let reference = reference_sequence();
+    let after = codon_start(100) + 2;       // last base of codon 100
+    let sequence = format!("{}GAGCCAGAA{}", &reference[..after], &reference[after..]);
+    let calls = caller().call(&record(sequence)).unwrap();
+
+    assert_eq!(names(&calls.nucleotide), [format!("nuc:ins{}GAGCCAGAA", after)]);
+    assert_eq!(calls.nucleotide[0].kind, MutationKind::Insertion);
+    assert_eq!(names(&calls.amino_acid), ["S:ins100EPE"]);
+}
+
+#[test]
+fn n_run_is_missing_not_mutated() {
+    let mut sequence = reference_sequence().into_bytes();
+    let (first, last) = (codon_start(30), codon_start(33) + 2);
+    for b in &mut sequence[first - 1..last] {
+        *b = b'N';
+    }
+    let calls = caller().call(&record(String::from_utf8(sequence).unwrap())).unwrap();
+
+    assert!(calls.nucleotide.is_empty(), "{:?}", names(&calls.nucleotide));
+    assert!(calls.amino_acid.is_empty(), "{:?}", names(&calls.amino_acid));
+    assert_eq!(calls.missing, [(first as u32, last as u32)]);
+    let expected = 1.0 - 12.0 / reference_sequence().len() as f32;
+    assert!((calls.coverage - expected).abs() < 1e-6, "coverage {}", calls.coverage);
+}
+
+#[test]
+fn unrelated_fragment_is_unplaceable() {
+    let error = caller().call(&record(bases(99, 300))).unwrap_err();
+    assert_eq!(error, GenomeError::Unplaceable("query".into()));
+}
@@ -0,0 +1,185 @@
+// limit-sarscov2-core/tests/graph_query.rs
+// Cypher-lite parsing and matching over a four-node graph
+
+use uuid::Uuid;
+
+use limit_sarscov2_core::{
+    graph_query::{Direction, GraphQuery, QueryError, QueryResult},
+    multi_intent_graph::MultiIntentGraphBuilder,
+    nodes::{GenomicsNode, ImmunologyNode, TreatmentNode, VirologyNode, VirusNode},
+    GraphEdge, MultiIntentGraph, SarsCov2Graph,
+};
+
+/// Omicron →causal→ spike binding →causal→ neutralization, Paxlovid ~correlative~ spike binding,
+/// and a refutation of the spike → neutralization edge
+struct Fixture {
+    graph: MultiIntentGraph,
+    omicron: Uuid,
+    spike: Uuid,
+    antibody: Uuid,
+    paxlovid: Uuid,
+    alters: Uuid,
+    escapes: Uuid,
+}
+
+fn fixture() -> Fixture {
+    let root = VirusNode { id: Uuid::new_v4(), name: "SARS-CoV-2".into(), genome_kb: 30.0 };
+    let omicron = GenomicsNode { id: Uuid::new_v4(), variant: "Omicron".into(), mutations: vec![], unparsed_mutations: vec![] };
+    let spike = VirologyNode { id: Uuid::new_v4(), topic: "Spike-ACE2 binding".into(), details: "RBD affinity".into(), regions: vec![] };
+    let antibody = ImmunologyNode { id: Uuid::new_v4(), topic: "Antibody neutralization".into(), details: "RBD-directed".into() };
+    let paxlovid = TreatmentNode { id: Uuid::new_v4(), therapy: "Paxlovid".into(), mechanism: "Protease inhibitor".into() };
+    let alters = GraphEdge::new_causal(omicron.id, spike.id, "alters".into(), "Genomics".into(), "Virology".into(), vec!["PMID:1".into()], 0.9);
+    let escapes = GraphEdge::new_causal(spike.id, antibody.id, "escapes".into(), "Virology".into(), "Immunology".into(), vec!["PMID:2".into()], 0.8);
+    let associated = GraphEdge::new_correlative(paxlovid.id, spike.id, "associated".into(), "Treatment".into(), "Virology".into(), vec!["PMID:3".into()], 0.5);
+    let refutation = GraphEdge::refuting(&escapes, "no escape".into(), vec!["PMID:4".into()], 0.6);
+    let ids = (omicron.id, spike.id, antibody.id, paxlovid.id, alters.id, escapes.id);
+    let graph = MultiIntentGraphBuilder::new(SarsCov2Graph::new(root))
+        .with_variant_node(omicron, "immune_escape", 4, 0.9)
+        .with_biology_node(spike, "immune_escape", 3, 0.8)
+        .with_immunology_node(antibody, "immune_escape", 2, 0.7)
+        .with_treatment_node(paxlovid, "therapeutics", 1, 0.6)
+        .with_edge(alters)
+        .with_edge(escapes)
+        .with_edge(associated)
+        .with_edge(refutation)
+        .build();
+    let (omicron, spike, antibody, paxlovid, alters, escapes) = ids;
+    Fixture { graph, omicron, spike, antibody, paxlovid, alters, escapes }
+}
+
+fn query(f: &Fixture, text: &str) -> QueryResult {
+    f.graph.query(text).unwrap_or_else(|e| panic!("{}: {}", text, e))
+}
+
+fn count(f: &Fixture, text: &str) -> usize {
+    match query(f, text) {
+        QueryResult::Count(n) => n,
+        other => panic!("{}: expected a count, got {:?}", text, other),
+    }
+}
+
+fn node_ids(f: &Fixture, text: &str) -> Vec<Uuid> {
+    match query(f, text) {
+        QueryResult::Nodes(nodes) => nodes.into_iter().map(|n| n.id).collect(),
+        other => panic!("{}: expected nodes, got {:?}", text, other),
+    }
+}
+
+fn syntax_offset(text: &str) -> usize {
+    match GraphQuery::parse(text) {
+        Err(QueryError::Syntax { offset, .. }) => offset,
+        other => panic!("{}: expected a syntax error, got {:?}", text, other),
+    }
+}
+
+#[test]
+fn syntax_errors_report_their_offset() {
+    assert_eq!(syntax_offset("FIND (n) RETURN nodes"), 0);
+    assert_eq!(syntax_offset("MATCH (n RETURN nodes"), 9);
+    assert_eq!(syntax_offset("MATCH (n) WHERE n.label = 'open RETURN nodes"), 26);
+    assert_eq!(syntax_offset("MATCH (n) WHERE n.confidence 0.5 RETURN nodes"), 29);
+    assert_eq!(syntax_offset("MATCH (n) RETURN nodes LIMIT -1"), 29);
+    assert_eq!(syntax_offset("MATCH (n) RETURN nodes LIMIT 2.5"), 29);
+    assert_eq!(syntax_offset("MATCH (n) RETURN nodes nodes"), 23);
+    assert_eq!(syntax_offset("MATCH (n) RETURN"), 16, "end of input");
+    assert_eq!(syntax_offset("MATCH (n) WHERE n.label = #"), 26);
+}
+
+#[test]
+fn invalid_patterns_are_rejected() {
+    let cases = [
+        ("MATCH (n:Astrology) RETURN nodes", QueryError::UnknownLabel("Astrology".into())),
+        ("MATCH (a)-[:Télépathie]->(b) RETURN paths", QueryError::UnknownEdgeType("Télépathie".into())),
+        ("MATCH (a)-[r]->(a) RETURN paths", QueryError::DuplicateVariable("a".into())),
+        ("MATCH (a) WHERE b.confidence > 0.5 RETURN nodes", QueryError::UnboundVariable("b".into())),
+        ("MATCH (a)-[r]->(b) RETURN a, c", QueryError::UnboundVariable("c".into())),
+        (
+            "MATCH (a) WHERE a.weight > 0.5 RETURN nodes",
+            QueryError::UnknownProperty { subject: "node 'a'".into(), property: "weight".into() },
+        ),
+        (
+            "MATCH (a) WHERE a.confidence > 'high' RETURN nodes",
+            QueryError::TypeMismatch { property: "confidence".into(), expected: "numeric", op: ">", value: "'high'".into() },
+        ),
+        (
+            "MATCH (a) WHERE a.label > 'M' RETURN nodes",
+            QueryError::TypeMismatch { property: "label".into(), expected: "text", op: ">", value: "'M'".into() },
+        ),
+    ];
+    for (text, error) in cases {
+        assert_eq!(GraphQuery::parse(text), Err(error), "{}", text);
+    }
+}
+
+#[test]
+fn relationship_directions_parse() {
+    let q = GraphQuery::parse("MATCH (a)-[r:Causal|Correlative]->(b)<-[]-(c)--(d) RETURN count(*)").unwrap();
+    let directions: Vec<Direction> = q.rels.iter().map(|r| r.direction).collect();
+    assert_eq!(directions, [Direction::Out, Direction::In, Direction::Either]);
+    assert_eq!(q.rels[0].types.len(), 2);
+    assert_eq!(q.rels[0].var.as_deref(), Some("r"));
+    assert!(q.rels[2].types.is_empty());
+}
+
+#[test]
+fn chain_of_causal_edges_is_one_path() {
+    let f = fixture();
+    let QueryResult::Paths(paths) = query(&f, "MATCH (g:Genomics)-[:Causal]->(v)-[:Causal]->(i:Immunology) RETURN paths") else {
+        panic!("expected paths");
+    };
+    assert_eq!(paths.len(), 1);
+    assert_eq!(paths[0].node_ids, [f.omicron, f.spike, f.antibody]);
+    assert_eq!(paths[0].edge_ids, [f.alters, f.escapes]);
+    assert_eq!(paths[0].labels, ["Omicron", "Spike-ACE2 binding", "Antibody neutralization"]);
+}
+
+#[test]
+fn incoming_and_undirected_relationships() {
+    let f = fixture();
+    assert_eq!(node_ids(&f, "MATCH (i:Immunology)<-[:Causal]-(v) RETURN nodes"), [f.antibody, f.spike]);
+    assert_eq!(count(&f, "MATCH (i:Immunology)-[:Causal]->(v) RETURN count"), 0);
+    // Spike binding touches three supporting edges either way round; the refutation never matches
+    assert_eq!(count(&f, "MATCH (v:Virology)-[r]-(x) RETURN count"), 3);
+    assert_eq!(count(&f, "MATCH (v:Virology)<-[r]-(x) RETURN count"), 2);
+    assert_eq!(count(&f, "MATCH (t:Treatment)-[:Correlative]-(v:Virology) RETURN count"), 1);
+    assert_eq!(count(&f, "MATCH (a)-[r]->(b) RETURN count"), 3);
+    // A custom type is valid but no edge has it
+    assert_eq!(count(&f, "MATCH (a)-[:Telepathic]->(b) RETURN count"), 0);
+}
+
+#[test]
+fn quoted_strings_and_bare_words_compare_as_text() {
+    let f = fixture();
+    assert_eq!(node_ids(&f, "MATCH (n) WHERE n.label = \"Spike-ACE2 binding\" RETURN nodes"), [f.spike]);
+    assert_eq!(node_ids(&f, "MATCH (n) WHERE n.label = 'spike-ace2 BINDING' RETURN nodes"), [f.spike], "case-insensitive");
+    assert_eq!(node_ids(&f, "MATCH (n) WHERE domain = Treatment RETURN nodes"), [f.paxlovid]);
+    assert_eq!(count(&f, "MATCH (n) WHERE n.intent != 'immune_escape' RETURN count"), 1);
+    assert_eq!(count(&f, "MATCH (a)-[r]->(b) WHERE r.type = Causal AND r.label = 'escapes' RETURN count"), 1);
+}
+
+#[test]
+fn numeric_conditions_compare_at_graph_precision() {
+    let f = fixture();
+    assert_eq!(node_ids(&f, "MATCH (n) WHERE n.confidence >= 0.9 RETURN nodes"), [f.omicron]);
+    assert_eq!(count(&f, "MATCH (n) WHERE evidence_count < 3 RETURN count"), 2);
+    // Unqualified edge properties apply to every edge of the match
+    assert_eq!(count(&f, "MATCH (a)-[]->(b)-[]->(c) WHERE confidence > 0.85 RETURN count"), 0);
+    assert_eq!(count(&f, "MATCH (a)-[]->(b)-[]->(c) WHERE confidence >= 0.8 RETURN count"), 1);
+}
+
+#[test]
+fn limit_caps_every_result_but_count() {
+    let f = fixture();
+    assert_eq!(node_ids(&f, "MATCH (n) RETURN nodes LIMIT 2").len(), 2);
+    assert!(node_ids(&f, "MATCH (n) RETURN nodes LIMIT 0").is_empty());
+    assert_eq!(count(&f, "MATCH (n) RETURN count LIMIT 1"), 4);
+    let QueryResult::Rows(rows) = query(&f, "MATCH (a)-[r]->(b) RETURN a, r LIMIT 2") else {
+        panic!("expected rows");
+    };
+    assert_eq!(rows.len(), 2);
+    assert!(rows.iter().all(|row| row.keys().eq(["a", "r"].iter())));
+    let QueryResult::Edges(edges) = query(&f, "MATCH (a)-[r]->(b) RETURN edges LIMIT 1") else {
+        panic!("expected edges");
+    };
+    assert_eq!(edges.len(), 1);
+}
@@ -0,0 +1,81 @@
+// limit-sarscov2-core/tests/intervention.rs
+// do() on the middle of a causal chain: incoming edges are cut, downstream propagation is kept
+
+use uuid::Uuid;
+
+use limit_sarscov2_core::{
+    multi_intent_graph::MultiIntentGraphBuilder,
+    nodes::{GenomicsNode, ImmunologyNode, TreatmentNode, VirologyNode, VirusNode},
+    GraphEdge, MultiIntentGraph, SarsCov2Graph,
+};
+
+/// Omicron →causal 0.9→ spike binding →causal 0.8→ neutralization, plus Paxlovid ~correlative~ spike binding
+struct Chain {
+    graph: MultiIntentGraph,
+    omicron: Uuid,
+    spike: Uuid,
+    antibody: Uuid,
+    alters: Uuid,
+}
+
+fn chain() -> Chain {
+    let root = VirusNode { id: Uuid::new_v4(), name: "SARS-CoV-2".into(), genome_kb: 30.0 };
+    let omicron = GenomicsNode { id: Uuid::new_v4(), variant: "Omicron".into(), mutations: vec![], unparsed_mutations: vec![] };
+    let spike = VirologyNode { id: Uuid::new_v4(), topic: "Spike-ACE2 binding".into(), details: "RBD affinity".into(), regions: vec![] };
+    let antibody = ImmunologyNode { id: Uuid::new_v4(), topic: "Antibody neutralization".into(), details: "RBD-directed".into() };
+    let paxlovid = TreatmentNode { id: Uuid::new_v4(), therapy: "Paxlovid".into(), mechanism: "Protease inhibitor".into() };
+    let alters = GraphEdge::new_causal(omicron.id, spike.id, "alters".into(), "Genomics".into(), "Virology".into(), vec!["PMID:1".into()], 0.9);
+    let escapes = GraphEdge::new_causal(spike.id, antibody.id, "escapes".into(), "Virology".into(), "Immunology".into(), vec!["PMID:2".into()], 0.8);
+    let associated = GraphEdge::new_correlative(paxlovid.id, spike.id, "associated".into(), "Treatment".into(), "Virology".into(), vec!["PMID:3".into()], 0.7);
+    let ids = (omicron.id, spike.id, antibody.id, alters.id);
+    let graph = MultiIntentGraphBuilder::new(SarsCov2Graph::new(root))
+        .with_variant_node(omicron, "immune_escape", 4, 0.9)
+        .with_biology_node(spike, "immune_escape", 3, 0.3)
+        .with_immunology_node(antibody, "immune_escape", 2, 0.2)
+        .with_treatment_node(paxlovid, "therapeutics", 1, 0.6)
+        .with_edge(alters)
+        .with_edge(escapes)
+        .with_edge(associated)
+        .build();
+    let (omicron, spike, antibody, alters) = ids;
+    Chain { graph, omicron, spike, antibody, alters }
+}
+
+fn close(actual: f32, expected: f32) -> bool {
+    (actual - expected).abs() < 1e-5
+}
+
+#[test]
+fn do_cuts_incoming_edges_and_keeps_downstream_propagation() {
+    let c = chain();
+    let result = c.graph.intervene(c.spike, 0.5).unwrap();
+
+    // Only the causal edge into the spike node is cut; the correlative one was never causal
+    assert_eq!(result.cut_edges, [c.alters]);
+
+    // Observationally spike = 0.9 × 0.9 and neutralization = 0.81 × 0.8; under
+    // do(spike = 0.5) the spike → neutralization edge still carries 0.8
+    let mut expected = vec![(c.spike, 0.81, 0.5, 0), (c.antibody, 0.648, 0.4, 1)];
+    expected.sort_by_key(|e| e.0);
+    assert_eq!(result.nodes.len(), 2);
+    for (change, (id, previous, confidence, incoming)) in result.nodes.iter().zip(expected) {
+        assert_eq!(change.node_id, id);
+        assert!(close(change.previous, previous), "{:?}", change);
+        assert!(close(change.confidence, confidence), "{:?}", change);
+        assert_eq!(change.incoming, incoming);
+    }
+
+    // Upstream is untouched, and so is the graph
+    assert!(result.nodes.iter().all(|n| n.node_id != c.omicron));
+    assert_eq!(c.graph.intent_nodes[&c.spike].metadata.confidence, 0.3);
+}
+
+#[test]
+fn do_at_the_observed_value_changes_nothing_downstream() {
+    let c = chain();
+    let result = c.graph.intervene(c.spike, 0.81).unwrap();
+    assert_eq!(result.cut_edges, [c.alters]);
+    assert!(result.nodes.is_empty(), "{:?}", result.nodes);
+
+    assert!(c.graph.intervene(Uuid::new_v4(), 0.5).is_none());
+}
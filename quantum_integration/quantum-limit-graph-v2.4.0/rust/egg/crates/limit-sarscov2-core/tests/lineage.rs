@@ -0,0 +1,97 @@
+// limit-sarscov2-core/tests/lineage.rs
+// Pango alias expansion and compression, recombinant roots, unknown aliases, ancestry
+
+use limit_sarscov2_core::{AliasTable, LineageError, PangoLineage};
+
+fn lineage(name: &str) -> PangoLineage {
+    PangoLineage::parse(name).unwrap()
+}
+
+fn names(lineages: &[PangoLineage]) -> Vec<String> {
+    lineages.iter().map(PangoLineage::to_string).collect()
+}
+
+#[test]
+fn aliases_expand_to_their_full_lineage() {
+    assert_eq!(lineage("BA.5").unaliased().to_string(), "B.1.1.529.5");
+    assert_eq!(lineage("BQ.1.1").unaliased().to_string(), "B.1.1.529.5.3.1.1.1.1.1.1");
+    assert_eq!(lineage("KP.2").unaliased().to_string(), "B.1.1.529.2.86.1.1.11.1.2");
+    assert_eq!(lineage("B.1.617.2").unaliased().to_string(), "B.1.617.2");
+    assert!(lineage("BA.5").same_as(&lineage("B.1.1.529.5")));
+    assert!(!lineage("BA.5").same_as(&lineage("BA.2")));
+}
+
+#[test]
+fn full_lineages_compress_to_the_longest_alias() {
+    let table = AliasTable::default();
+    assert_eq!(table.compress(&lineage("B.1.1.529.5")).to_string(), "BA.5");
+    assert_eq!(table.compress(&lineage("B.1.1.529.5.2.1.1")).to_string(), "BF.1");
+    assert_eq!(table.compress(&lineage("BA.5.3.1.1.1.1.1.1")).to_string(), "BQ.1.1");
+    // An alias target is not shortened to the bare alias
+    assert_eq!(table.compress(&lineage("B.1.1.529")).to_string(), "B.1.1.529");
+    assert_eq!(table.compress(&lineage("B.1.351")).to_string(), "B.1.351");
+}
+
+#[test]
+fn parents_and_ancestors_walk_back_through_aliases() {
+    assert_eq!(lineage("BA.5").parent(), Some(lineage("B.1.1.529")));
+    assert_eq!(lineage("BF.1").parent(), Some(lineage("BA.5.2.1")));
+    assert_eq!(
+        names(&lineage("BQ.1.1").ancestors()),
+        ["BQ.1", "BE.1.1.1", "BE.1.1", "BE.1", "BA.5.3.1", "BA.5.3", "BA.5", "B.1.1.529", "B.1.1", "B.1", "B"],
+    );
+    assert_eq!(lineage("B").parent(), None);
+    assert!(lineage("B").is_root());
+}
+
+#[test]
+fn xbb_recombinants_stop_at_the_recombinant_root() {
+    let xbb = lineage("XBB");
+    assert!(xbb.is_recombinant());
+    assert!(xbb.is_root());
+    assert_eq!(xbb.parent(), None);
+
+    assert_eq!(lineage("EG.5.1").unaliased().to_string(), "XBB.1.9.2.5.1");
+    assert_eq!(names(&lineage("EG.5.1").ancestors()), ["EG.5", "XBB.1.9.2", "XBB.1.9", "XBB.1", "XBB"]);
+    assert!(xbb.is_ancestor_of(&lineage("HV.1")));
+    // Recombinants are not descendants of either of their parental lineages
+    assert!(!lineage("BA.2").is_ancestor_of(&lineage("XBB.1.5")));
+}
+
+#[test]
+fn unknown_aliases_are_kept_as_given() {
+    let unknown = lineage("ZZ.1");
+    assert_eq!(unknown.unaliased(), unknown);
+    assert_eq!(AliasTable::default().compress(&unknown), unknown);
+    assert!(!unknown.is_root());
+    assert_eq!(names(&unknown.ancestors()), ["ZZ"]);
+    assert!(!lineage("BA.5").is_ancestor_of(&unknown));
+
+    // Registering the alias makes it resolvable
+    let mut table = AliasTable::default();
+    table.insert("ZZ", "BA.2.86.1.1").unwrap();
+    assert_eq!(table.expand(&unknown).to_string(), "B.1.1.529.2.86.1.1.1");
+    assert_eq!(table.compress(&lineage("JN.1.1")).to_string(), "ZZ.1");
+}
+
+#[test]
+fn ancestry_compares_aliased_and_unaliased_names() {
+    let (ba2, jn1) = (lineage("BA.2"), lineage("JN.1"));
+    assert!(ba2.is_ancestor_of(&jn1));
+    assert!(jn1.is_descendant_of(&lineage("B.1.1.529.2")));
+    assert!(!jn1.is_ancestor_of(&ba2));
+    assert!(!ba2.is_ancestor_of(&ba2));
+    assert!(!lineage("BA.2").is_ancestor_of(&lineage("BA.5")));
+    assert!(!lineage("A").is_ancestor_of(&lineage("B.1")));
+}
+
+#[test]
+fn parses_and_rejects_names() {
+    assert_eq!(lineage(" BA.5.2.1 "), PangoLineage { prefix: "BA".into(), path: vec![5, 2, 1] });
+    for bad in ["", "ba.5", "BA.x", "ABCD.1", "BA..1"] {
+        assert_eq!(PangoLineage::parse(bad), Err(LineageError::Invalid(bad.into())));
+    }
+    assert_eq!(PangoLineage::find_in("Omicron BA.5 (spike L452R)"), Some(lineage("BA.5")));
+    assert_eq!(PangoLineage::find_in("Delta wave"), Some(lineage("B.1.617.2")));
+    assert_eq!(PangoLineage::find_in("no lineage here"), None);
+}
@@ -0,0 +1,117 @@
+// limit-sarscov2-core/tests/lineage_tree.rs
+// Lineage tree construction, parent/child links, traversal and common ancestors
+
+use uuid::Uuid;
+
+use limit_sarscov2_core::{
+    multi_intent_graph::MultiIntentGraphBuilder,
+    mutation::Mutation,
+    nodes::{GenomicsNode, VirusNode},
+    LineageTree, LineageTreeNode, PangoLineage, SarsCov2Graph,
+};
+
+fn lineage(name: &str) -> PangoLineage {
+    PangoLineage::parse(name).unwrap()
+}
+
+fn names(nodes: &[&LineageTreeNode]) -> Vec<String> {
+    nodes.iter().map(|n| n.lineage.to_string()).collect()
+}
+
+/// BA.5.2 (→ BF.1), BQ.1.1 and JN.1, with every ancestor inserted along the way
+fn tree() -> LineageTree {
+    let mut tree = LineageTree::new();
+    for name in ["BF.1", "BQ.1.1", "JN.1", "BA.5.2"] {
+        tree.insert(&lineage(name));
+    }
+    tree
+}
+
+/// Deepest lineage that is `a` or one of its ancestors and also `b` or one of its ancestors
+fn common_ancestor<'t>(tree: &'t LineageTree, a: &PangoLineage, b: &PangoLineage) -> Option<&'t LineageTreeNode> {
+    let on_b: Vec<String> = tree.get(b).into_iter().chain(tree.ancestors(b)).map(|n| n.lineage.to_string()).collect();
+    tree.get(a).into_iter().chain(tree.ancestors(a)).find(|n| on_b.contains(&n.lineage.to_string()))
+}
+
+#[test]
+fn inserting_a_lineage_links_it_to_its_parent() {
+    let tree = tree();
+    assert_eq!(names(&tree.roots()), ["B"]);
+
+    let ba5 = tree.get(&lineage("B.1.1.529.5")).expect("inserted as an ancestor");
+    assert_eq!(ba5.lineage, lineage("BA.5"));
+    assert_eq!(ba5.parent, Some(lineage("B.1.1.529")));
+    assert_eq!(ba5.children, [lineage("BA.5.2"), lineage("BA.5.3")]);
+
+    let bf1 = tree.get(&lineage("BF.1")).unwrap();
+    assert_eq!(bf1.parent, Some(lineage("BA.5.2.1")));
+    assert!(bf1.children.is_empty());
+    assert_eq!(tree.get(&lineage("BA.5.2.1")).unwrap().children, [lineage("BF.1")]);
+
+    // Re-inserting, under either name, does not duplicate the child
+    let mut again = tree.clone();
+    again.insert(&lineage("B.1.1.529.5.2"));
+    assert_eq!(again.nodes.len(), tree.nodes.len());
+    assert_eq!(again.get(&lineage("BA.5")).unwrap().children.len(), 2);
+}
+
+#[test]
+fn ancestors_and_descendants_follow_the_tree() {
+    let tree = tree();
+    assert_eq!(names(&tree.ancestors(&lineage("JN.1"))), ["BA.2.86.1", "BA.2.86", "BA.2", "B.1.1.529", "B.1.1", "B.1", "B"]);
+    assert_eq!(
+        names(&tree.descendants(&lineage("BA.5"))),
+        ["BA.5.2", "BA.5.2.1", "BF.1", "BA.5.3", "BA.5.3.1", "BE.1", "BE.1.1", "BE.1.1.1", "BQ.1", "BQ.1.1"],
+    );
+    assert!(tree.is_descendant_of(&lineage("BQ.1.1"), &lineage("BA.5")));
+    assert!(!tree.is_descendant_of(&lineage("JN.1"), &lineage("BA.5")));
+    assert!(!tree.is_descendant_of(&lineage("BA.5"), &lineage("BA.5")));
+    // Lineages not on the tree have no relatives
+    assert!(tree.ancestors(&lineage("XBB.1.5")).is_empty());
+    assert!(tree.descendants(&lineage("XBB")).is_empty());
+}
+
+#[test]
+fn most_recent_common_ancestor() {
+    let tree = tree();
+    let mrca = |a: &str, b: &str| common_ancestor(&tree, &lineage(a), &lineage(b)).map(|n| n.lineage.to_string());
+    assert_eq!(mrca("BQ.1.1", "BF.1").as_deref(), Some("BA.5"));
+    assert_eq!(mrca("BF.1", "BA.5.2").as_deref(), Some("BA.5.2"));
+    assert_eq!(mrca("BQ.1.1", "JN.1").as_deref(), Some("B.1.1.529"));
+    assert_eq!(mrca("BE.1", "B.1.1.529.5.3.1.1").as_deref(), Some("BE.1"));
+    assert_eq!(mrca("JN.1", "XBB.1.5"), None);
+}
+
+#[test]
+fn variant_nodes_define_the_mutations_their_ancestors_lack() {
+    let mutations = |list: &[&str]| list.iter().map(|m| m.parse::<Mutation>().unwrap()).collect::<Vec<_>>();
+    let root = VirusNode { id: Uuid::new_v4(), name: "SARS-CoV-2".into(), genome_kb: 30.0 };
+    let ba2 = GenomicsNode { id: Uuid::new_v4(), variant: "Omicron BA.2".into(), mutations: mutations(&["S:N501Y", "S:Q493R"]), unparsed_mutations: vec![] };
+    let ba5 = GenomicsNode { id: Uuid::new_v4(), variant: "BA.5".into(), mutations: mutations(&["S:N501Y", "S:L452R"]), unparsed_mutations: vec![] };
+    let bf7 = GenomicsNode { id: Uuid::new_v4(), variant: "BF.7".into(), mutations: mutations(&["S:N501Y", "S:L452R", "S:R346T"]), unparsed_mutations: vec![] };
+    let unplaced = GenomicsNode { id: Uuid::new_v4(), variant: "Unnamed variant".into(), mutations: vec![], unparsed_mutations: vec![] };
+    let (ba2_id, ba5_id, bf7_id) = (ba2.id, ba5.id, bf7.id);
+    let graph = MultiIntentGraphBuilder::new(SarsCov2Graph::new(root))
+        .with_variant_node(ba2, "immune_escape", 3, 0.8)
+        .with_variant_node(ba5, "immune_escape", 3, 0.8)
+        .with_variant_node(bf7, "immune_escape", 2, 0.7)
+        .with_variant_node(unplaced, "immune_escape", 1, 0.5)
+        .build();
+
+    let tree = graph.lineage_tree();
+    let node = |name: &str| tree.get(&lineage(name)).unwrap();
+    assert_eq!(node("BA.2").variant_nodes, [ba2_id]);
+    assert_eq!(node("BA.5").variant_nodes, [ba5_id]);
+    assert_eq!(node("BF.7").variant_nodes, [bf7_id]);
+    // BA.2 and BA.5 are siblings with no placed ancestor; BF.7 inherits from BA.5
+    assert_eq!(node("BA.2").defining_mutations, mutations(&["S:N501Y", "S:Q493R"]));
+    assert_eq!(node("BA.5").defining_mutations, mutations(&["S:N501Y", "S:L452R"]));
+    assert_eq!(node("BF.7").defining_mutations, mutations(&["S:R346T"]));
+    assert!(node("BA.5.2").defining_mutations.is_empty());
+
+    let below: Vec<Uuid> = graph.descendant_variants(&lineage("B.1.1.529.5")).iter().map(|n| n.id).collect();
+    assert_eq!(below, [bf7_id]);
+    let mut omicron = vec![ba2_id, ba5_id, bf7_id];
+    omicron.sort();
+    assert_eq!(graph.descendant_variants(&lineage("B.1.1.529")).iter().map(|n| n.id).collect::<Vec<_>>(), omicron);
+}
@@ -0,0 +1,61 @@
+// limit-sarscov2-core/tests/mutation.rs
+// Parsing each documented mutation notation, and Display round trips
+
+use limit_sarscov2_core::mutation::{Mutation, MutationKind, MutationParseError};
+
+/// Mutation with the given fields
+fn mutation(gene: Option<&str>, position: u32, reference: &str, alternate: &str, kind: MutationKind, end_position: Option<u32>) -> Mutation {
+    Mutation { gene: gene.map(String::from), position, reference: reference.into(), alternate: alternate.into(), kind, end_position }
+}
+
+/// Each documented notation, what it parses to, and how that displays
+fn forms() -> Vec<(&'static str, Mutation, &'static str)> {
+    use MutationKind::*;
+    vec![
+        ("L452R", mutation(None, 452, "L", "R", Substitution, None), "L452R"),
+        ("S:L452R", mutation(Some("S"), 452, "L", "R", Substitution, None), "S:L452R"),
+        ("Spike_N501Y", mutation(Some("S"), 501, "N", "Y", Substitution, None), "S:N501Y"),
+        ("ORF1a:T3255I", mutation(Some("ORF1a"), 3255, "T", "I", Substitution, None), "ORF1a:T3255I"),
+        ("N:Q27*", mutation(Some("N"), 27, "Q", "*", Substitution, None), "N:Q27*"),
+        // Gene-less A/C/G/T within protein range: Cys241Thr as much as C241T
+        ("C241T", mutation(None, 241, "C", "T", Substitution, None), "C241T"),
+        ("nuc:C241T", mutation(Some("nuc"), 241, "C", "T", Nucleotide, None), "nuc:C241T"),
+        ("A23403G", mutation(Some("nuc"), 23403, "A", "G", Nucleotide, None), "A23403G"),
+        ("nuc:A23403G", mutation(Some("nuc"), 23403, "A", "G", Nucleotide, None), "A23403G"),
+        ("23403A>G", mutation(Some("nuc"), 23403, "A", "G", Nucleotide, None), "A23403G"),
+        ("g.241C>T", mutation(Some("nuc"), 241, "C", "T", Nucleotide, None), "nuc:C241T"),
+        ("S:HV69-70del", mutation(Some("S"), 69, "HV", "", Deletion, Some(70)), "S:HV69-70del"),
+        ("H69del", mutation(None, 69, "H", "", Deletion, None), "H69del"),
+        ("S:Δ69-70", mutation(Some("S"), 69, "", "", Deletion, Some(70)), "S:69-70del"),
+        ("ΔY144", mutation(None, 144, "Y", "", Deletion, None), "Y144del"),
+        ("del69/70", mutation(None, 69, "", "", Deletion, Some(70)), "69-70del"),
+        ("F157-", mutation(None, 157, "F", "", Deletion, None), "F157del"),
+        ("S:ins214EPE", mutation(Some("S"), 214, "", "EPE", Insertion, None), "S:ins214EPE"),
+        ("214insEPE", mutation(None, 214, "", "EPE", Insertion, None), "ins214EPE"),
+        ("ins_22204:GAGCCAGAA", mutation(Some("nuc"), 22204, "", "GAGCCAGAA", Insertion, None), "nuc:ins22204GAGCCAGAA"),
+    ]
+}
+
+#[test]
+fn documented_forms_parse_and_round_trip() {
+    for (notation, expected, display) in forms() {
+        let m: Mutation = notation.parse().unwrap_or_else(|e| panic!("{}: {}", notation, e));
+        assert_eq!(m, expected, "{}", notation);
+        assert_eq!(m.to_string(), display, "{}", notation);
+        assert_eq!(display.parse::<Mutation>().as_ref(), Ok(&m), "{} → {}", notation, display);
+    }
+}
+
+#[test]
+fn invalid_forms_are_rejected() {
+    let cases = [
+        ("L452L", MutationParseError::Synonymous("L452L".into())),
+        ("HV69-71del", MutationParseError::SpanMismatch { residues: "HV".into(), start: 69, end: 71 }),
+        ("E:L100R", MutationParseError::OutOfRange { gene: "E".into(), position: 100, length: 75 }),
+        ("L8000R", MutationParseError::OutOfRange { gene: "any gene".into(), position: 8000, length: 7_096 }),
+        ("spike", MutationParseError::Unrecognized("spike".into())),
+    ];
+    for (notation, error) in cases {
+        assert_eq!(notation.parse::<Mutation>(), Err(error), "{}", notation);
+    }
+}
@@ -0,0 +1,124 @@
+// limit-sarscov2-core/tests/quantum.rs
+// Quantum vs classical walk on a three-node path, checked against the closed-form solutions
+
+use uuid::Uuid;
+
+use limit_sarscov2_core::{
+    multi_intent_graph::MultiIntentGraphBuilder,
+    nodes::{GenomicsNode, ImmunologyNode, VirologyNode, VirusNode},
+    GraphEdge, MultiIntentGraph, QuantumWalkError, QuantumWalkOptions, SarsCov2Graph,
+};
+
+/// Omicron —0.5— spike binding —0.5— neutralization, nothing refuted
+struct Path {
+    graph: MultiIntentGraph,
+    omicron: Uuid,
+    spike: Uuid,
+    antibody: Uuid,
+    alters: Uuid,
+    escapes: Uuid,
+}
+
+fn path() -> Path {
+    let root = VirusNode { id: Uuid::new_v4(), name: "SARS-CoV-2".into(), genome_kb: 30.0 };
+    let omicron = GenomicsNode { id: Uuid::new_v4(), variant: "Omicron".into(), mutations: vec![], unparsed_mutations: vec![] };
+    let spike = VirologyNode { id: Uuid::new_v4(), topic: "Spike-ACE2 binding".into(), details: "RBD affinity".into(), regions: vec![] };
+    let antibody = ImmunologyNode { id: Uuid::new_v4(), topic: "Antibody neutralization".into(), details: "RBD-directed".into() };
+    let alters = GraphEdge::new_causal(omicron.id, spike.id, "alters".into(), "Genomics".into(), "Virology".into(), vec!["PMID:1".into()], 0.5);
+    let escapes = GraphEdge::new_causal(spike.id, antibody.id, "escapes".into(), "Virology".into(), "Immunology".into(), vec!["PMID:2".into()], 0.5);
+    let ids = (omicron.id, spike.id, antibody.id, alters.id, escapes.id);
+    let graph = MultiIntentGraphBuilder::new(SarsCov2Graph::new(root))
+        .with_variant_node(omicron, "immune_escape", 4, 0.9)
+        .with_biology_node(spike, "immune_escape", 3, 0.8)
+        .with_immunology_node(antibody, "immune_escape", 2, 0.7)
+        .with_edge(alters)
+        .with_edge(escapes)
+        .build();
+    let (omicron, spike, antibody, alters, escapes) = ids;
+    Path { graph, omicron, spike, antibody, alters, escapes }
+}
+
+fn close(actual: f32, expected: f32) -> bool {
+    (actual - expected).abs() < 1e-5
+}
+
+/// On a path of three with edge weight w, the amplitude at the far end is
+/// (cos(√2·w·t) − 1) / 2, so at t = π / (√2·w) the quantum walk has moved
+/// there completely. The classical walk only gets 1/3 − e^{−wt}/2 + e^{−3wt}/6.
+#[test]
+fn quantum_walk_transfers_perfectly_along_a_path_of_three() {
+    let p = path();
+    let options = QuantumWalkOptions {
+        time: std::f32::consts::PI * std::f32::consts::SQRT_2,
+        samples: 1,
+        ..QuantumWalkOptions::default()
+    };
+    let report = p.graph.quantum_walk(p.omicron, &options).unwrap();
+
+    assert_eq!(report.start, p.omicron);
+    assert_eq!(report.nodes, 3);
+    assert_eq!(report.hits[0].node_id, p.antibody);
+
+    let hit = |id: Uuid| report.hits.iter().find(|h| h.node_id == id).unwrap();
+    assert_eq!(hit(p.omicron).distance, 0);
+    assert_eq!(hit(p.spike).distance, 1);
+    assert!(close(hit(p.antibody).quantum, 1.0));
+    assert!(close(hit(p.omicron).quantum, 0.0));
+    assert!(close(hit(p.spike).quantum, 0.0));
+    assert!(close(hit(p.omicron).classical, 0.387_772));
+    assert!(close(hit(p.spike).classical, 0.332_908));
+    assert!(close(hit(p.antibody).classical, 0.279_320));
+    assert!((hit(p.antibody).advantage - 3.580_13).abs() < 1e-3);
+    assert!(close(report.total_variation, 0.720_680));
+
+    // Every measurement lands on the far end
+    assert_eq!(hit(p.antibody).shots, options.shots);
+    assert_eq!(report.hits.iter().map(|h| h.shots).sum::<usize>(), options.shots);
+
+    // Two hops away and 3.6× the classical probability: proposed as a path
+    assert_eq!(report.paths.len(), 1);
+    let proposed = &report.paths[0];
+    assert_eq!(proposed.target, p.antibody);
+    assert_eq!(proposed.node_sequence, vec![p.omicron, p.spike, p.antibody]);
+    assert_eq!(proposed.edge_sequence, vec![p.alters, p.escapes]);
+}
+
+#[test]
+fn averaging_over_samples_keeps_a_distribution() {
+    let p = path();
+    let report = p.graph.quantum_walk(p.spike, &QuantumWalkOptions::default()).unwrap();
+    assert!(close(report.hits.iter().map(|h| h.quantum).sum(), 1.0));
+    assert!(close(report.hits.iter().map(|h| h.classical).sum(), 1.0));
+    // Walking from the middle, both ends are equally likely
+    let end = |id: Uuid| report.hits.iter().find(|h| h.node_id == id).unwrap().quantum;
+    assert!(close(end(p.omicron), end(p.antibody)));
+    // Nothing is two hops from the middle
+    assert!(report.paths.is_empty());
+}
+
+#[test]
+fn hops_limit_the_neighbourhood_walked() {
+    let p = path();
+    let options = QuantumWalkOptions { hops: Some(1), ..QuantumWalkOptions::default() };
+    let report = p.graph.quantum_walk(p.omicron, &options).unwrap();
+    assert_eq!(report.nodes, 2);
+    assert!(report.hits.iter().all(|h| h.node_id != p.antibody));
+    assert!(report.paths.is_empty());
+}
+
+#[test]
+fn rejects_unknown_starts_and_empty_time_windows() {
+    let p = path();
+    let stranger = Uuid::new_v4();
+    assert!(matches!(
+        p.graph.quantum_walk(stranger, &QuantumWalkOptions::default()),
+        Err(QuantumWalkError::UnknownNode { node }) if node == stranger
+    ));
+    for options in [
+        QuantumWalkOptions { time: 0.0, ..QuantumWalkOptions::default() },
+        QuantumWalkOptions { time: f32::NAN, ..QuantumWalkOptions::default() },
+        QuantumWalkOptions { samples: 0, ..QuantumWalkOptions::default() },
+    ] {
+        assert!(matches!(p.graph.quantum_walk(p.omicron, &options), Err(QuantumWalkError::InvalidOptions)));
+    }
+}
@@ -0,0 +1,111 @@
+// limit-sarscov2-core/tests/qubo.rs
+// Path-selection QUBO: matrix entries for three paths, the annealer and a plugged-in solver finding the known optimum
+
+use uuid::Uuid;
+
+use limit_sarscov2_core::{
+    qubo::{encode_path_selection, select_hypothesis_paths, PathSelectionOptions, Qubo, QuboError, QuboSolver, SimulatedAnnealing},
+    HypothesisPath, HypothesisType,
+};
+
+fn path(nodes: &[Uuid], confidence: f32) -> HypothesisPath {
+    HypothesisPath {
+        id: Uuid::new_v4(),
+        hypothesis_type: HypothesisType::ImmuneEscape,
+        description: String::new(),
+        node_sequence: nodes.to_vec(),
+        edge_sequence: vec![],
+        total_confidence: confidence,
+        evidence_coverage: 1.0,
+    }
+}
+
+/// a–b (0.9) and b–c (0.6) share node b; d–e (0.5) shares nothing
+fn candidates() -> Vec<HypothesisPath> {
+    let [a, b, c, d, e] = [(); 5].map(|_| Uuid::new_v4());
+    vec![path(&[a, b], 0.9), path(&[b, c], 0.6), path(&[d, e], 0.5)]
+}
+
+fn options() -> PathSelectionOptions {
+    PathSelectionOptions { k: 2, diversity: 1.0, penalty: Some(2.0), ..Default::default() }
+}
+
+fn close(actual: f64, expected: f64) -> bool {
+    (actual - expected).abs() < 1e-6
+}
+
+/// Tries every assignment; stands in for an external annealer
+struct Exhaustive;
+
+impl QuboSolver for Exhaustive {
+    fn name(&self) -> &str {
+        "exhaustive"
+    }
+
+    fn solve(&self, qubo: &Qubo) -> Result<Vec<Vec<bool>>, QuboError> {
+        let n = qubo.variables();
+        let assignments = (0..1usize << n).map(|bits| (0..n).map(|i| bits >> i & 1 == 1).collect::<Vec<bool>>());
+        Ok(vec![assignments.min_by(|x, y| qubo.energy(x).total_cmp(&qubo.energy(y))).unwrap()])
+    }
+}
+
+#[test]
+fn matrix_entries_follow_the_encoding() {
+    let paths = candidates();
+    let problem = encode_path_selection(&paths, &options()).unwrap();
+    assert_eq!(problem.candidates, paths.iter().map(|p| p.id).collect::<Vec<_>>());
+
+    // linear = −cᵢ + P(1 − 2k) = −cᵢ − 6; quadratic = overlap + 2P; offset = Pk²
+    let q = &problem.qubo;
+    for (h, expected) in q.linear.iter().zip([-6.9, -6.6, -6.5]) {
+        assert!(close(*h, expected), "{:?}", q.linear);
+    }
+    let quadratic: Vec<(usize, usize)> = q.quadratic.iter().map(|(i, j, _)| (*i, *j)).collect();
+    assert_eq!(quadratic, [(0, 1), (0, 2), (1, 2)]);
+    for ((_, _, w), expected) in q.quadratic.iter().zip([1.0 / 3.0 + 4.0, 4.0, 4.0]) {
+        assert!(close(*w, expected), "{:?}", q.quadratic);
+    }
+    assert!(close(q.offset, 8.0));
+    assert!(close(q.energy(&[true, false, true]), -1.4));
+
+    // Without a penalty, one is picked that outweighs any confidence: max c + diversity·k + 1
+    let default = encode_path_selection(&paths, &PathSelectionOptions { penalty: None, ..options() }).unwrap();
+    assert!(close(default.qubo.offset, (0.9f32 as f64 + 3.0) * 4.0));
+}
+
+#[test]
+fn annealer_finds_the_diverse_optimum() {
+    let paths = candidates();
+    let report = select_hypothesis_paths(&paths, &options(), &SimulatedAnnealing::default()).unwrap();
+    assert_eq!(report.solver, "simulated-annealing");
+    let best = &report.selections[0];
+    assert_eq!(best.rank, 1);
+    assert!(best.feasible);
+    // {a–b, d–e} at −1.4 beats the stronger but overlapping {a–b, b–c} at −7/6
+    assert_eq!(best.paths.iter().map(|p| p.id).collect::<Vec<_>>(), [paths[0].id, paths[2].id]);
+    assert!(close(best.energy, -1.4));
+    assert_eq!(best.mean_overlap, 0.0);
+    assert!((best.total_confidence - 1.4).abs() < 1e-6);
+}
+
+#[test]
+fn plugged_in_solver_is_used_and_its_samples_checked() {
+    let paths = candidates();
+    let report = select_hypothesis_paths(&paths, &options(), &Exhaustive).unwrap();
+    assert_eq!(report.solver, "exhaustive");
+    assert_eq!(report.selections.len(), 1);
+    assert_eq!(report.selections[0].paths.iter().map(|p| p.id).collect::<Vec<_>>(), [paths[0].id, paths[2].id]);
+    assert_eq!(report.selections[0].occurrences, 1);
+
+    struct Short;
+    impl QuboSolver for Short {
+        fn name(&self) -> &str {
+            "short"
+        }
+        fn solve(&self, _: &Qubo) -> Result<Vec<Vec<bool>>, QuboError> {
+            Ok(vec![vec![true]])
+        }
+    }
+    let e = select_hypothesis_paths(&paths, &options(), &Short).unwrap_err();
+    assert_eq!(e, QuboError::InvalidSample { expected: 3, got: 1 });
+}
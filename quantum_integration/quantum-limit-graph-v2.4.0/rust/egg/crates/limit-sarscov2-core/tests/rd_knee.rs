@@ -0,0 +1,110 @@
+// limit-sarscov2-core/tests/rd_knee.rs
+// Knee detection on monotone, noisy and degenerate RD curves
+
+use limit_sarscov2_core::rd::{
+    CurveShape, KneeMethod, KneeOptions, Normalization, RDCurve, RDPoint, Smoothing,
+};
+
+fn curve(rates: impl IntoIterator<Item = f32>, f: impl Fn(f32) -> f32) -> RDCurve {
+    RDCurve::new(rates.into_iter().map(|r| RDPoint { rate: r, distortion: f(r) }).collect())
+}
+
+/// Deterministic noise in [-amplitude, amplitude]
+fn noise(i: usize, amplitude: f32) -> f32 {
+    let x = ((i as u64).wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407) >> 33) as f32;
+    (x / (1u64 << 31) as f32 * 2.0 - 1.0) * amplitude
+}
+
+fn exp_decay() -> RDCurve {
+    curve((0..=30).map(|r| r as f32), |r| (-r / 3.0).exp())
+}
+
+#[test]
+fn kneedle_finds_knee_of_monotone_convex_curve() {
+    let c = curve((1..=20).map(|r| r as f32), |r| 1.0 / r);
+    let knee = c.detect_knee(&KneeOptions::default()).expect("1/x has a knee");
+    assert!((2.0..=5.0).contains(&knee.point.rate), "knee at {:?}", knee.point);
+    assert!(knee.score > 0.0);
+    assert!(knee.curvature > 0.0);
+    assert_eq!(c.points[knee.index], knee.point);
+}
+
+#[test]
+fn kneedle_and_max_curvature_agree_on_exponential_decay() {
+    let c = exp_decay();
+    let kneedle = c.detect_knee(&KneeOptions::default()).unwrap();
+    let curvature = c.detect_knee(&KneeOptions { method: KneeMethod::MaxCurvature, ..Default::default() }).unwrap();
+    assert!((3.0..=8.0).contains(&kneedle.point.rate), "kneedle at {:?}", kneedle.point);
+    assert!((kneedle.index as i64 - curvature.index as i64).abs() <= 3);
+    assert_eq!(curvature.score, curvature.curvature);
+}
+
+#[test]
+fn min_max_normalization_ignores_units() {
+    let c = exp_decay();
+    let scaled = RDCurve::new(c.points.iter().map(|p| RDPoint { rate: p.rate * 1000.0, distortion: p.distortion * 0.01 }).collect());
+    let a = c.detect_knee(&KneeOptions::default()).unwrap();
+    let b = scaled.detect_knee(&KneeOptions::default()).unwrap();
+    assert_eq!(a.index, b.index);
+}
+
+#[test]
+fn raw_normalization_follows_units() {
+    // In raw units a rate axis 1000× longer than the distortion axis flattens the curve away
+    let c = curve((0..=30).map(|r| r as f32 * 1000.0), |r| (-r / 3000.0).exp());
+    let raw = c.detect_knee(&KneeOptions { normalization: Normalization::None, method: KneeMethod::MaxCurvature, ..Default::default() });
+    let normalized = c.detect_knee(&KneeOptions { method: KneeMethod::MaxCurvature, ..Default::default() });
+    assert!(raw.is_none());
+    assert!(normalized.is_some());
+}
+
+#[test]
+fn smoothing_recovers_knee_of_noisy_curve() {
+    let clean = exp_decay().detect_knee(&KneeOptions::default()).unwrap();
+    let noisy = RDCurve::new(
+        exp_decay().points.iter().enumerate()
+            .map(|(i, p)| RDPoint { rate: p.rate, distortion: p.distortion + noise(i, 0.04) })
+            .collect(),
+    );
+    for smoothing in [Smoothing::Isotonic, Smoothing::MovingAverage { window: 3 }] {
+        let knee = noisy.detect_knee(&KneeOptions { smoothing, ..Default::default() })
+            .unwrap_or_else(|| panic!("no knee with {:?}", smoothing));
+        assert!((knee.index as i64 - clean.index as i64).abs() <= 2, "{:?}: {} vs {}", smoothing, knee.index, clean.index);
+    }
+}
+
+#[test]
+fn concave_curves_need_concave_shape() {
+    // Coverage rising quickly then saturating
+    let c = curve((0..=20).map(|r| r as f32), |r| 1.0 - (-r / 2.0).exp());
+    let concave = c.detect_knee(&KneeOptions { shape: CurveShape::Concave, ..Default::default() }).unwrap();
+    assert!((1.0..=6.0).contains(&concave.point.rate), "knee at {:?}", concave.point);
+    assert!(c.detect_knee(&KneeOptions::default()).is_none());
+}
+
+#[test]
+fn straight_line_has_no_knee_but_knee_falls_back() {
+    let c = curve((0..10).map(|r| r as f32), |r| 10.0 - r);
+    assert!(c.detect_knee(&KneeOptions::default()).is_none());
+    assert!(c.detect_knee(&KneeOptions { method: KneeMethod::MaxCurvature, ..Default::default() }).is_none());
+    assert!(c.knee().is_some());
+}
+
+#[test]
+fn short_curves() {
+    assert!(RDCurve::new(vec![]).knee().is_none());
+    let one = RDCurve::new(vec![RDPoint { rate: 1.0, distortion: 0.5 }]);
+    assert_eq!(one.knee(), Some(one.points[0]));
+    let two = RDCurve::new(vec![RDPoint { rate: 2.0, distortion: 0.1 }, RDPoint { rate: 1.0, distortion: 0.9 }]);
+    assert!(two.detect_knee(&KneeOptions::default()).is_none());
+    assert_eq!(two.knee(), Some(RDPoint { rate: 2.0, distortion: 0.1 }));
+}
+
+#[test]
+fn sensitivity_makes_detection_more_conservative() {
+    let c = curve((0..=10).map(|r| r as f32), |r| 1.0 - 0.1 * r + 0.004 * r * r);
+    let lenient = c.detect_knee(&KneeOptions { sensitivity: 0.1, ..Default::default() });
+    let strict = c.detect_knee(&KneeOptions { sensitivity: 10.0, ..Default::default() });
+    assert!(lenient.is_some());
+    assert!(strict.is_none());
+}
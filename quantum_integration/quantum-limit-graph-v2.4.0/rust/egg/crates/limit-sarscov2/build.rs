@@ -0,0 +1,8 @@
+// limit-sarscov2/build.rs
+// Generates the gRPC service from proto/sarscov2.proto when the "grpc" feature is enabled
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    #[cfg(feature = "grpc")]
+    tonic_build::compile_protos("proto/sarscov2.proto")?;
+    Ok(())
+}
@@ -2,12 +2,12 @@
 // Comprehensive demo of SARS-CoV-2 multi-intent knowledge graph
 
 use limit_sarscov2::{
-    domain::{SarsCov2Graph, ResearchDomain},
+    domain::SarsCov2Graph,
     nodes::*,
-    edges::{GraphEdge, builders},
+    edges::builders,
     multi_intent_graph::{MultiIntentGraphBuilder, HypothesisPath},
-    serendipity_trace::{SerendipityTrace, StepBuilder, HypothesisType, examples},
-    rd::{RDCurve, rd_from_batches},
+    serendipity_trace::{HypothesisType, examples},
+    rd::rd_from_batches,
     retrieval::{CorpusDoc, RetrievalBackend},
     metrics::SARSCoV2Metrics,
 };
@@ -31,6 +31,7 @@ fn main() {
         id: Uuid::new_v4(),
         topic: "Spike protein S1/S2 structure".into(),
         details: "RBD binds ACE2 receptor with high affinity".into(),
+        regions: vec![ProteinRegion::new("S", "RBD", 319, 541).with_pdb("6M0J")],
     };
     base_graph.add_virology(spike_node.clone());
 
@@ -43,11 +44,7 @@ fn main() {
     base_graph.add_immunology(antibody_node.clone());
 
     // Variants: Omicron
-    let omicron_node = GenomicsNode {
-        id: Uuid::new_v4(),
-        variant: "Omicron BA.5".into(),
-        mutations: vec!["L452R".into(), "F486V".into(), "R493Q".into()],
-    };
+    let omicron_node = GenomicsNode::from_notations(Uuid::new_v4(), "Omicron BA.5", ["S:L452R", "S:F486V", "S:R493Q"]);
     base_graph.add_genomics(omicron_node.clone());
 
     // Treatment: Paxlovid
@@ -63,6 +60,7 @@ fn main() {
         id: Uuid::new_v4(),
         policy: "Indoor mask mandates".into(),
         effect: "Reduced transmission by 20-30% in controlled studies".into(),
+        region: None,
     };
     base_graph.add_public_health(mask_node.clone());
 
@@ -208,18 +206,21 @@ fn main() {
             domain: "Virology".into(),
             text: "Spike protein RBD mutations enhance ACE2 binding affinity".into(),
             source: "doi:10.1038/s41586-022-04980-y".into(),
+            mesh: vec![],
         },
         CorpusDoc {
             id: Uuid::new_v4(),
             domain: "Genomics".into(),
             text: "Omicron BA.5 contains L452R, F486V mutations conferring immune escape".into(),
             source: "doi:10.1016/j.cell.2022.06.005".into(),
+            mesh: vec![],
         },
         CorpusDoc {
             id: Uuid::new_v4(),
             domain: "Treatment".into(),
             text: "Paxlovid protease inhibitor reduces hospitalization by 89%".into(),
             source: "doi:10.1056/NEJMoa2118542".into(),
+            mesh: vec![],
         },
     ];
 
@@ -1,78 +1,2454 @@
 // limit-sarscov2/src/api.rs
 use axum::{
-    routing::{get, post},
-    extract::{Path, State},
+    body::Body,
+    routing::{delete, get, post},
+    extract::{Extension, Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    middleware,
     Json, Router,
 };
-use std::sync::{Arc, Mutex};
+use futures_util::StreamExt;
+use std::{collections::HashMap, path::PathBuf, sync::{Arc, Mutex}};
 use uuid::Uuid;
 
-use crate::{domain::SarsCov2Graph, metrics::SARSCoV2Metrics, provenance::ProvenanceNote, rd::RDCurve, governance::{EvidenceThresholds, check_merge_allowed}};
+use crate::{domain::SarsCov2Graph, metrics::SARSCoV2Metrics, provenance::{Citation, GovernanceTag, ProvenanceIndex, ProvenanceLineage, ProvenanceNote, cites}, rd::{Knee, KneeOptions, RDCurve, RDPoint}, governance::{EdgeRules, EvidenceThresholds, GovernanceDecision, GovernancePolicy, check_edges, check_merge_allowed}};
+use crate::{multi_intent_graph::{DanglingEdges, MultiIntentGraph, RegionMutations, Removal}, export::{ExportProfile, RedactionReport, plan_redactions, sanitize}};
+use crate::{retrieval::{CorpusDoc, RetrievalBackend}, replay::{TraceReplay, replay_trace}};
+use crate::prov::{ProvFormat, export_prov};
+use crate::signing::{NoteSigner, TrustedKeys, VerificationReport, verify_notes};
+use crate::audit::{AuditEntry, AuditLog, AuditQuery, snapshot_hash};
+use crate::events::{EventError, GraphEvent};
+use crate::diff::GraphDiff;
+use crate::search::{SearchOptions, SearchResults};
+use crate::summary::{GraphSummary, SummaryFilter};
+use crate::fuzzy::NameMatch;
+use crate::ontology::{OntologyRef, OntologyRefError};
+use crate::synonyms::SynonymGroup;
+use crate::event_log::{EventLog, HistoryError, RecordedEvent};
+use crate::approval::{is_approver, ApprovalError, ChangeQueue, ChangeStatus, PendingChange, ProposedChange, Reviewer};
+use crate::metrics_history::{MetricsHistory, MetricsSnapshot, SnapshotTrigger};
+use crate::executor::{QueryExecution, QueryExecutor};
+use crate::explorer::{Exploration, ExplorationAgent, ExplorationBudget, default_targets};
+use crate::scheduler::{ExplorationScheduler, SchedulerPolicy, ScheduleTarget};
+use crate::queries::MultiIntentQuestion;
+use crate::graph_query::QueryResult;
+use crate::rdf::{project, to_ntriples};
+use crate::sparql::{SparqlQuery, RESULTS_CONTENT_TYPE};
+use crate::import::{ImportSummary, JsonlReader, import_records};
+use crate::csv_tables::{write_edges_csv, write_nodes_csv};
+use crate::cytoscape::to_cytoscape;
+use crate::gexf::{GEXF_CONTENT_TYPE, to_gexf};
+use crate::dedup::{DedupOptions, DuplicateCandidate, NodeMerge, find_duplicates};
+use crate::edges::{EdgeType, GraphEdge, HyperEdge};
+use crate::confidence::{ConfidenceModel, ConfidenceUpdate, parse_evidence_date};
+use crate::support::RelationshipSupport;
+use crate::propagation::{NodeConfidenceChange, PropagationPolicy};
+use crate::bayes::{BayesError, BayesOptions, BayesianNetwork, Belief};
+use crate::quantum::{QuantumWalkError, QuantumWalkOptions, QuantumWalkReport};
+use crate::qubo::{PathSelectionOptions, QuboError, SelectionReport, SimulatedAnnealing, select_hypothesis_paths};
+use crate::embeddings::{EmbeddingOptions, EmbeddingReport};
+use crate::link_prediction::{LinkPredictionOptions, SuggestedEdge};
+use crate::similarity::{SimilarNode, SimilarityOptions};
+use crate::intervention::Intervention;
+use crate::counterfactual::{CounterfactualError, CounterfactualReport};
+use crate::temporal::{TimeBucket, Timeline};
+use crate::lineage::PangoLineage;
+use crate::multi_intent_graph::IntentNode;
+use crate::error::{ApiError, Payload};
+use crate::validate::{SubjectKind, ValidationIssue, ValidationReport, validate_edge, validate_hyperedge, validate_node};
+use crate::decompose::{Decomposition, QuestionDecomposer};
+use crate::extraction::{ExtractionPipeline, ExtractionReport};
+use crate::telemetry::{GraphGauges, MultiGraphGauges, RequestMetrics, StoreGauges, render_prometheus, track_requests};
+use crate::serendipity_trace::{HypothesisType, SerendipitySummary, SerendipityTrace, StepBuilder, TraceMatch, TraceSearch, search_traces};
+use crate::trace_store::TraceStore;
+use crate::trace_diagnostics::{DiagnosticOptions, TraceDiagnostics};
+use crate::workspace::Caller;
 
+/// Stores shared by every request. A handler holding more than one store
+/// lock at a time takes them in this order: `governance`, `graphs`,
+/// `rd_curves`, `multi_graphs`, `retrieval`, `changes`, `events`, then
+/// `traces`, `provenance`, `audit` and `metrics_history`. Two handlers
+/// taking the same pair in opposite orders can deadlock.
 #[derive(Clone)]
 pub struct AppState {
     pub graphs: Arc<Mutex<Vec<SarsCov2Graph>>>,
-    pub provenance: Arc<Mutex<Vec<ProvenanceNote>>>,
-    pub rd_curves: Arc<Mutex<Vec<(Uuid, RDCurve)>>>,
+    pub provenance: Arc<Mutex<ProvenanceIndex>>,
+    pub rd_curves: Arc<Mutex<HashMap<Uuid, HashMap<String, RDCurve>>>>,   // graph → intent → curve
+    pub multi_graphs: Arc<Mutex<Vec<MultiIntentGraph>>>,
+    pub retrieval: Arc<Mutex<RetrievalBackend>>,
+    pub traces: Arc<Mutex<TraceStore>>,              // recorded over HTTP
+    pub governance: Arc<Mutex<GovernancePolicy>>,
+    pub governance_path: Option<PathBuf>,            // file reloaded by an empty POST /governance/policy
+    pub audit: Arc<Mutex<AuditLog>>,
+    pub changes: Arc<Mutex<ChangeQueue>>,          // proposed node/edge additions, persisted with their reviews
+    pub events: Arc<Mutex<EventLog>>,              // every multi-intent graph mutation, replayed on restart
+    pub signer: Option<Arc<NoteSigner>>,           // signs provenance notes recorded by the server
+    pub trusted_keys: Arc<TrustedKeys>,
+    pub requests: Arc<RequestMetrics>,             // per-route counts and latencies for GET /metrics
+    pub metrics_history: Arc<Mutex<MetricsHistory>>,
+}
+
+impl AppState {
+    /// Trace `id` from the recorded traces or any multi-intent graph
+    pub(crate) fn find_trace(&self, id: Uuid) -> Result<Option<SerendipityTrace>, ApiError> {
+        if let Some(t) = self.traces.lock()?.get(id) {
+            return Ok(Some(t.clone()));
+        }
+        let graphs = self.multi_graphs.lock()?;
+        Ok(graphs.iter().flat_map(|g| &g.serendipity_traces).find(|t| t.id == id).cloned())
+    }
+
+    /// Recorded traces, then the ones every multi-intent graph carries
+    pub(crate) fn all_traces(&self) -> Result<Vec<SerendipityTrace>, ApiError> {
+        let mut traces: Vec<SerendipityTrace> = self.traces.lock()?.iter().cloned().collect();
+        let graphs = self.multi_graphs.lock()?;
+        traces.extend(graphs.iter().flat_map(|g| &g.serendipity_traces).cloned());
+        Ok(traces)
+    }
+
+    /// Traces recorded against graph `graph_id`, then the ones the graph carries;
+    /// `None` when there is no such graph
+    pub(crate) fn traces_of_graph(&self, graph_id: Uuid) -> Result<Option<Vec<SerendipityTrace>>, ApiError> {
+        let mut traces: Vec<SerendipityTrace> = self.traces.lock()?.for_graph(graph_id).into_iter().cloned().collect();
+        let graphs = self.multi_graphs.lock()?;
+        let Some(graph) = graphs.iter().find(|g| g.id == graph_id) else {
+            return Ok(None);
+        };
+        traces.extend(graph.serendipity_traces.iter().cloned());
+        Ok(Some(traces))
+    }
+
+    /// Record a provenance note, stamped with the request's correlation ID and
+    /// actor and signed when the server has a signing key
+    pub(crate) fn record_provenance(&self, mut note: ProvenanceNote) -> Result<(), ApiError> {
+        if note.request_id.is_none() {
+            note.request_id = crate::correlation::current();
+        }
+        if note.actor.is_none() {
+            note.actor = crate::correlation::current_actor();
+        }
+        if let Some(signer) = &self.signer {
+            signer.sign(&mut note);
+        }
+        self.provenance.lock()?.add(note);
+        Ok(())
+    }
+
+    /// Append one operation's mutations of graph `graph_id` to the event log,
+    /// made by `(actor, session)`. Call while still holding `multi_graphs`, so
+    /// the log keeps the order they were made in.
+    pub(crate) fn record_events(&self, (actor, session): (&str, &str), graph_id: Uuid, events: impl IntoIterator<Item = GraphEvent>) -> Result<(), ApiError> {
+        let mut events = events.into_iter().peekable();
+        if events.peek().is_none() {
+            return Ok(());
+        }
+        self.events.lock()?.record(graph_id, actor, session, events).map_err(|e| ApiError::internal(format!("{:#}", e)))?;
+        Ok(())
+    }
+
+    /// Apply `edit` to a copy of multi-intent graph `id`, append the events it
+    /// returns to the event log, and only then swap the copy in: a failed edit
+    /// or log write leaves the stored graph as it was.
+    pub(crate) fn edit_graph<T>(
+        &self,
+        editor: (&str, &str),
+        id: Uuid,
+        edit: impl FnOnce(&mut MultiIntentGraph) -> Result<(T, Vec<GraphEvent>), ApiError>,
+    ) -> Result<T, ApiError> {
+        let mut graphs = self.multi_graphs.lock()?;
+        let graph = graphs.iter_mut().find(|g| g.id == id).ok_or_else(|| ApiError::not_found("graph", id))?;
+        let mut edited = graph.clone();
+        let (value, events) = edit(&mut edited)?;
+        self.record_events(editor, id, events)?;
+        *graph = edited;
+        Ok(value)
+    }
+
+    /// Store a newly built multi-intent graph once its creation is logged
+    pub(crate) fn add_graph(&self, editor: (&str, &str), graph: MultiIntentGraph) -> Result<(), ApiError> {
+        let mut graphs = self.multi_graphs.lock()?;
+        self.record_events(editor, graph.id, [GraphEvent::GraphCreated { graph: Box::new(graph.clone()) }])?;
+        graphs.push(graph);
+        Ok(())
+    }
+
+    /// RD curves of graph `id`: the graph's own, overridden by curves submitted over the API
+    pub(crate) fn rd_curves_of(&self, id: Uuid) -> Result<HashMap<String, RDCurve>, ApiError> {
+        let mut curves: HashMap<String, RDCurve> = self.multi_graphs.lock()?.iter()
+            .find(|g| g.id == id)
+            .map(|g| g.rd_curves.clone())
+            .unwrap_or_default();
+        if let Some(submitted) = self.rd_curves.lock()?.get(&id) {
+            curves.extend(submitted.iter().map(|(intent, c)| (intent.clone(), c.clone())));
+        }
+        Ok(curves)
+    }
+
+    /// Append a governance decision to the audit log; the caller is named by the `x-actor` header.
+    /// Best effort: failures are logged, not returned.
+    fn audit(&self, headers: &HeaderMap, graph_id: Uuid, action: &str, decision: &GovernanceDecision, policy_hash: String) {
+        let actor = header_str(headers, "x-actor").unwrap_or("anonymous");
+        let entry = AuditEntry::new(graph_id, actor, action, decision.clone(), policy_hash);
+        let Ok(mut log) = self.audit.lock() else {
+            tracing::warn!("audit log lock poisoned; entry dropped");
+            return;
+        };
+        if let Err(e) = log.record(entry) {
+            tracing::warn!(error = %format!("{:#}", e), "failed to persist audit entry");
+        }
+    }
+
+    /// Record a metrics snapshot of graph `id` (base or multi-intent), if it
+    /// exists. Best effort: a poisoned store is logged and skipped.
+    pub fn snapshot_metrics(&self, id: Uuid, trigger: SnapshotTrigger) {
+        let record = || -> Result<(), ApiError> {
+            let snapshot = self.multi_graphs.lock()?.iter().find(|g| g.id == id)
+                .map(|g| MetricsSnapshot::of_multi_graph(g, trigger));
+            let snapshot = match snapshot {
+                Some(s) => Some(s),
+                None => self.graphs.lock()?.iter().find(|g| g.id == id).map(|g| MetricsSnapshot::of_graph(g, trigger)),
+            };
+            if let Some(snapshot) = snapshot {
+                self.metrics_history.lock()?.record(snapshot);
+            }
+            Ok(())
+        };
+        let _ = record();   // ApiError::internal has logged any failure
+    }
+
+    /// Snapshot every stored graph; best effort like `snapshot_metrics`
+    pub fn snapshot_all_metrics(&self, trigger: SnapshotTrigger) {
+        let record = || -> Result<(), ApiError> {
+            let mut snapshots: Vec<MetricsSnapshot> = self.graphs.lock()?.iter().map(|g| MetricsSnapshot::of_graph(g, trigger)).collect();
+            snapshots.extend(self.multi_graphs.lock()?.iter().map(|g| MetricsSnapshot::of_multi_graph(g, trigger)));
+            let mut history = self.metrics_history.lock()?;
+            for snapshot in snapshots {
+                history.record(snapshot);
+            }
+            Ok(())
+        };
+        let _ = record();
+    }
+}
+
+/// Snapshot every graph's metrics every `every` until the runtime shuts down
+pub fn spawn_metrics_sampler(state: AppState, every: std::time::Duration) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticks = tokio::time::interval(every);
+        loop {
+            ticks.tick().await;
+            state.snapshot_all_metrics(SnapshotTrigger::Schedule);
+        }
+    })
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name).and_then(|v| v.to_str().ok())
+}
+
+/// Caller named by `x-actor` and their editing session: `x-session`, else the actor
+fn editor(headers: &HeaderMap) -> (&str, &str) {
+    let actor = header_str(headers, "x-actor").unwrap_or("anonymous");
+    (actor, header_str(headers, "x-session").unwrap_or(actor))
+}
+
+/// Source of a provenance note for a change with no evidence of its own: the `x-actor` caller
+fn curator(headers: &HeaderMap) -> String {
+    format!("curator:{}", header_str(headers, "x-actor").unwrap_or("anonymous"))
 }
 
 pub fn router(state: AppState) -> Router {
     Router::new()
         .route("/graph/:id", get(get_graph))
+        .route("/provenance/verify", get(get_provenance_verify))
+        .route("/provenance/by-source", get(get_provenance_by_source))
+        .route("/provenance/by-request/:request_id", get(get_provenance_by_request))
         .route("/provenance/:id", get(get_provenance))
-        .route("/traces/:id", get(get_traces))        // placeholder: returns provenance as “traces”
+        .route("/questions/decompose", post(post_decompose_question))
+        .route("/multi-graph/execute", post(post_execute_question))
+        .route("/multi-graph/explore", post(post_explore_question))
+        .route("/multi-graph/:id/lineage/:subject_id", get(get_lineage))
+        .route("/multi-graph/:id/prov", get(get_prov))
+        .route("/multi-graph/:id/query", post(post_graph_query))
+        .route("/multi-graph/:id/search", get(get_search))
+        .route("/multi-graph/:id/resolve", get(get_resolve))
+        .route("/multi-graph/:id/summary", get(get_summary))
+        .route("/multi-graph/:id/xrefs/:curie", get(get_nodes_by_xref))
+        .route("/multi-graph/:id/rdf", get(get_rdf))
+        .route("/multi-graph/:id/sparql", get(get_sparql).post(post_sparql))
+        .route("/multi-graph/:id/import", post(post_import))
+        .route("/multi-graph/:id/dedup", post(post_dedup))
+        .route("/multi-graph/:id/nodes/:node_id", delete(delete_node))
+        .route("/multi-graph/:id/edges/:edge_id", delete(delete_edge))
+        .route("/multi-graph/:id/edges/:edge_id/support", get(get_edge_support))
+        .route("/multi-graph/:id/edges/:edge_id/refutations", post(post_refutation))
+        .route("/multi-graph/:id/hyperedges", post(post_hyperedge))
+        .route("/multi-graph/:id/edge-types", get(get_edge_types).post(post_edge_type))
+        .route("/multi-graph/:id/synonyms", get(get_synonyms).post(post_synonyms))
+        .route("/multi-graph/:id/synonyms/:canonical", delete(delete_synonyms))
+        .route("/retrieval/synonyms", get(get_retrieval_synonyms).post(post_retrieval_synonyms))
+        .route("/retrieval/synonyms/:canonical", delete(delete_retrieval_synonyms))
+        .route("/retrieval/mesh/:term", get(get_docs_by_mesh))
+        .route("/multi-graph/:id/confidence", post(post_confidence))
+        .route("/multi-graph/:id/confidence/propagate", post(post_propagate_confidence))
+        .route("/multi-graph/:id/bayes", get(get_bayes_network))
+        .route("/multi-graph/:id/bayes/query", post(post_bayes_query))
+        .route("/multi-graph/:id/quantum-walk/:node", get(get_quantum_walk))
+        .route("/multi-graph/:id/hypotheses/select", post(post_select_hypotheses))
+        .route("/multi-graph/:id/embeddings", post(post_embeddings))
+        .route("/multi-graph/:id/nodes/:node_id/intervene", post(post_intervene))
+        .route("/multi-graph/:id/nodes/:node_id/similar", get(get_similar_nodes))
+        .route("/multi-graph/:id/counterfactual", post(post_counterfactual))
+        .route("/multi-graph/:id/regions", get(get_region_mutations))
+        .route("/multi-graph/:id/slice", get(get_slice))
+        .route("/multi-graph/:id/timeline", get(get_timeline))
+        .route("/multi-graph/:id/lineage-tree", get(get_lineage_tree))
+        .route("/multi-graph/:id/lineage-tree/:lineage/descendants", get(get_lineage_descendants))
+        .route("/multi-graph/:id/validate", post(post_validate))
+        .route("/multi-graph/:id/events", get(get_events))
+        .route("/multi-graph/:id/events/replay", get(get_event_replay))
+        .route("/multi-graph/:id/diff", get(get_diff))
+        .route("/multi-graph/:id/undo", post(post_undo))
+        .route("/multi-graph/:id/redo", post(post_redo))
+        .route("/traces", get(get_trace_list).post(post_trace))
+        .route("/traces/search", get(get_trace_search))
+        .route("/traces/merge", post(post_trace_merge))
+        .route("/traces/:id", get(get_traces))
+        .route("/traces/:id/steps", post(post_trace_step))
+        .route("/traces/:id/summary", get(get_trace_summary))
+        .route("/traces/:id/diagnostics", get(get_trace_diagnostics))
+        .route("/traces/:id/export", get(get_trace_export))
+        .route("/traces/:id/replay", post(post_trace_replay))
+        .route("/metrics", get(get_prometheus_metrics))
         .route("/metrics/:id", get(get_metrics))
-        .route("/rd/:id", get(get_rd))
-        .route("/governance/check/:id", post(post_governance_check))
+        .route("/metrics/:id/history", get(get_metrics_history))
+        .route("/rd/:graph_id", get(get_rd))
+        .route("/rd/:graph_id/:intent", post(post_rd_points))
+        .route("/governance/check/:id", post(post_governance_check).get(get_governance_check))
+        .route("/governance/check-all", post(post_governance_check_all))
+        .route("/governance/edges/:id", post(post_governance_edges))
+        .route("/governance/audit", get(get_governance_audit))
+        .route("/governance/changes", get(get_changes))
+        .route("/governance/approve/:change_id", post(post_approve_change))
+        .route("/governance/reject/:change_id", post(post_reject_change))
+        .route("/multi-graph/:id/proposals", post(post_proposal))
+        .route("/multi-graph/:id/extract", post(post_extract_relations))
+        .route("/multi-graph/:id/suggested-edges", post(post_suggest_edges))
+        .route("/multi-graph/:id/tags", get(get_tags))
+        .route("/multi-graph/:id/tags/:subject_id", post(post_tag))
+        .route("/multi-graph/:id/tags/:subject_id/:tag_id", delete(delete_tag))
+        .route("/governance/policy", get(get_governance_policy).post(post_governance_policy))
+        .route("/multi-graph/:id/export", get(get_export))
+        .route("/multi-graph/:id/export/preview", get(get_export_preview))
+        .route_layer(middleware::from_fn_with_state(state.requests.clone(), track_requests))
         .with_state(state)
 }
 
-async fn get_graph(State(state): State<AppState>, Path(id): Path<Uuid>) -> Json<Option<SarsCov2Graph>> {
-    let graphs = state.graphs.lock().unwrap();
-    Json(graphs.iter().find(|g| g.id == id).cloned())
+async fn get_graph(State(state): State<AppState>, Path(id): Path<Uuid>) -> Result<Json<SarsCov2Graph>, ApiError> {
+    let graphs = state.graphs.lock()?;
+    graphs.iter().find(|g| g.id == id).cloned().map(Json).ok_or_else(|| ApiError::not_found("graph", id))
+}
+
+/// Notes whose subject is `id`, or every note of graph `id`
+async fn get_provenance(State(state): State<AppState>, Path(id): Path<Uuid>) -> Result<Json<Vec<ProvenanceNote>>, ApiError> {
+    let prov = state.provenance.lock()?;
+    let about = prov.for_subject(id);
+    let notes = if about.is_empty() { prov.for_graph(id) } else { about };
+    Ok(Json(notes.into_iter().cloned().collect()))
+}
+
+#[derive(serde::Deserialize)]
+struct ProvParams {
+    #[serde(default)]
+    format: ProvFormat,
+}
+
+/// The graph's provenance chain as W3C PROV-O (`?format=turtle|jsonld`)
+async fn get_prov(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<ProvParams>,
+) -> Result<([(header::HeaderName, &'static str); 1], String), ApiError> {
+    let graphs = state.multi_graphs.lock()?;
+    let graph = graphs.iter().find(|g| g.id == id).ok_or_else(|| ApiError::not_found("graph", id))?;
+    let body = export_prov(graph, state.provenance.lock()?.notes(), params.format);
+    Ok(([(header::CONTENT_TYPE, params.format.content_type())], body))
+}
+
+#[derive(serde::Deserialize)]
+struct GraphQueryPayload {
+    query: String,
 }
 
-async fn get_provenance(State(state): State<AppState>, Path(id): Path<Uuid>) -> Json<Vec<ProvenanceNote>> {
-    let prov = state.provenance.lock().unwrap();
-    Json(prov.iter().filter(|p| p.source.contains(&id.to_string())).cloned().collect())
+/// Run a Cypher-lite query (`{"query": "MATCH (g:Genomics)-[:Causal]->(i:Immunology) RETURN paths"}`)
+async fn post_graph_query(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Payload(payload): Payload<GraphQueryPayload>,
+) -> Result<Json<QueryResult>, ApiError> {
+    let graphs = state.multi_graphs.lock()?;
+    let graph = graphs.iter().find(|g| g.id == id).ok_or_else(|| ApiError::not_found("graph", id))?;
+    graph.query(&payload.query).map(Json).map_err(|e| ApiError::BadRequest(e.to_string()))
 }
 
-async fn get_traces(State(state): State<AppState>, Path(id): Path<Uuid>) -> Json<Vec<ProvenanceNote>> {
-    // For now, reuse provenance as “serendipity traces”
-    get_provenance(State(state), Path(id)).await
+#[derive(serde::Deserialize)]
+struct SearchParams {
+    #[serde(default)]
+    q: String,
+    limit: Option<usize>,
+    domain: Option<crate::domain::ResearchDomain>,
+    fuzzy: Option<bool>,
 }
 
-async fn get_metrics(State(state): State<AppState>, Path(id): Path<Uuid>) -> Json<Option<SARSCoV2Metrics>> {
-    let graphs = state.graphs.lock().unwrap();
-    let g = graphs.iter().find(|g| g.id == id).cloned();
-    Json(g.map(|graph| SARSCoV2Metrics::compute(&graph)))
+/// Nodes whose content matches `?q=`, ranked, with highlighted matches (`?limit=`, `?domain=`, `?fuzzy=false`)
+async fn get_search(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<SearchParams>,
+) -> Result<Json<SearchResults>, ApiError> {
+    if params.q.trim().is_empty() {
+        return Err(ApiError::BadRequest("pass ?q= with at least one word".into()));
+    }
+    let graphs = state.multi_graphs.lock()?;
+    let graph = graphs.iter().find(|g| g.id == id).ok_or_else(|| ApiError::not_found("graph", id))?;
+    let defaults = SearchOptions::default();
+    let options = SearchOptions {
+        limit: params.limit.unwrap_or(defaults.limit),
+        domain: params.domain,
+        fuzzy: params.fuzzy.unwrap_or(defaults.fuzzy),
+    };
+    Ok(Json(graph.search(&params.q, &options)))
 }
 
-async fn get_rd(State(state): State<AppState>, Path(id): Path<Uuid>) -> Json<Option<RDCurve>> {
-    let curves = state.rd_curves.lock().unwrap();
-    Json(curves.iter().find(|(gid, _)| *gid == id).map(|(_, c)| c.clone()))
+/// Nodes of graph `id` cross-referenced to ontology term `curie` (`CHEBI:170007`, `MONDO:0100096`)
+async fn get_nodes_by_xref(
+    State(state): State<AppState>,
+    Path((id, curie)): Path<(Uuid, String)>,
+) -> Result<Json<Vec<IntentNode>>, ApiError> {
+    let xref: OntologyRef = curie.parse().map_err(|e: OntologyRefError| ApiError::BadRequest(e.to_string()))?;
+    let graphs = state.multi_graphs.lock()?;
+    let graph = graphs.iter().find(|g| g.id == id).ok_or_else(|| ApiError::not_found("graph", id))?;
+    Ok(Json(graph.nodes_with_xref(&xref).into_iter().cloned().collect()))
 }
 
 #[derive(serde::Deserialize)]
-struct ThresholdsPayload {
-    virology_min: usize,
-    genomics_min: usize,
-    treatment_min: usize,
+struct ResolveParams {
+    #[serde(default)]
+    name: String,
+    domain: Option<crate::domain::ResearchDomain>,
 }
 
-async fn post_governance_check(
+/// Nodes whose label approximately matches `?name=` (typos, case and punctuation tolerated), best first
+async fn get_resolve(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
-    Json(payload): Json<ThresholdsPayload>,
-) -> Json<Option<crate::governance::GovernanceDecision>> {
-    let graphs = state.graphs.lock().unwrap();
-    let g = graphs.iter().find(|g| g.id == id).cloned();
-    Json(g.map(|graph| {
-        let t = EvidenceThresholds {
-            virology_min: payload.virology_min,
-            genomics_min: payload.genomics_min,
-            treatment_min: payload.treatment_min,
-        };
-        check_merge_allowed(&graph, &t)
-    }))
+    Query(params): Query<ResolveParams>,
+) -> Result<Json<Vec<NameMatch>>, ApiError> {
+    if params.name.trim().is_empty() {
+        return Err(ApiError::BadRequest("pass ?name= with the name to resolve".into()));
+    }
+    let graphs = state.multi_graphs.lock()?;
+    let graph = graphs.iter().find(|g| g.id == id).ok_or_else(|| ApiError::not_found("graph", id))?;
+    Ok(Json(graph.resolve_name(&params.name, params.domain.as_ref())))
+}
+
+/// N-Triples of the graph's RDF projection (the vocabulary SPARQL queries run against)
+async fn get_rdf(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<([(header::HeaderName, &'static str); 1], String), ApiError> {
+    let graphs = state.multi_graphs.lock()?;
+    let graph = graphs.iter().find(|g| g.id == id).ok_or_else(|| ApiError::not_found("graph", id))?;
+    Ok(([(header::CONTENT_TYPE, "application/n-triples")], to_ntriples(&project(graph))))
+}
+
+/// `application/sparql-results+json` body
+type SparqlResponse = ([(header::HeaderName, &'static str); 1], String);
+
+#[derive(serde::Deserialize)]
+struct SparqlParams {
+    query: String,
+}
+
+/// SPARQL protocol GET: `?query=SELECT ?g WHERE { ?g a sc2:Genomics }`
+async fn get_sparql(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<SparqlParams>,
+) -> Result<SparqlResponse, ApiError> {
+    run_sparql(&state, id, &params.query)
+}
+
+/// SPARQL protocol POST with the query as the raw body
+async fn post_sparql(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    query: String,
+) -> Result<SparqlResponse, ApiError> {
+    run_sparql(&state, id, &query)
+}
+
+fn run_sparql(state: &AppState, id: Uuid, query: &str) -> Result<SparqlResponse, ApiError> {
+    let query = SparqlQuery::parse(query).map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    let graphs = state.multi_graphs.lock()?;
+    let graph = graphs.iter().find(|g| g.id == id).ok_or_else(|| ApiError::not_found("graph", id))?;
+    let result = query.execute(&project(graph));
+    Ok(([(header::CONTENT_TYPE, RESULTS_CONTENT_TYPE)], result.to_json().to_string()))
+}
+
+/// Bulk-load newline-delimited `IntentNode` and `GraphEdge` records. The body
+/// is parsed as it streams in; invalid records are reported, not fatal.
+async fn post_import(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    body: Body,
+) -> Result<Json<ImportSummary>, ApiError> {
+    if !state.multi_graphs.lock()?.iter().any(|g| g.id == id) {
+        return Err(ApiError::not_found("graph", id));
+    }
+    let mut reader = JsonlReader::new();
+    let mut chunks = body.into_data_stream();
+    while let Some(chunk) = chunks.next().await {
+        let chunk = chunk.map_err(|e| match crate::limits::is_too_large(&e) {
+            true => ApiError::PayloadTooLarge("import body is over the size limit".into()),
+            false => ApiError::BadRequest(format!("failed to read body: {}", e)),
+        })?;
+        reader.push(&chunk);
+    }
+    let records = reader.finish();
+
+    let (summary, notes) = state.edit_graph(editor(&headers), id, |graph| {
+        let (summary, notes) = import_records(graph, records);
+        let events = notes.iter().filter_map(|n| GraphEvent::added(graph, n.subject_id)).collect();
+        Ok(((summary, notes), events))
+    })?;
+    for note in notes {
+        state.record_provenance(note)?;
+    }
+    if summary.nodes_accepted + summary.edges_accepted > 0 {
+        state.snapshot_metrics(id, SnapshotTrigger::Mutation);
+    }
+    Ok(Json(summary))
+}
+
+#[derive(serde::Deserialize)]
+struct DedupPayload {
+    #[serde(flatten)]
+    options: DedupOptions,
+    #[serde(default)]
+    dry_run: bool,        // only report candidates
+}
+
+#[derive(serde::Serialize)]
+struct DedupResponse {
+    candidates: Vec<DuplicateCandidate>,
+    merges: Vec<NodeMerge>,
+}
+
+/// Find near-duplicate nodes and, unless `dry_run`, merge them. Each merge is
+/// recorded as a "merge" provenance note on the surviving node citing the
+/// merged one; notes about the merged node are kept.
+async fn post_dedup(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Payload(payload): Payload<DedupPayload>,
+) -> Result<Json<DedupResponse>, ApiError> {
+    let (candidates, merges) = state.edit_graph(editor(&headers), id, |graph| {
+        let candidates = find_duplicates(graph, &payload.options);
+        let merges = if payload.dry_run { vec![] } else { graph.dedup(&payload.options) };
+        let events = merges.iter().map(|m| GraphEvent::NodesMerged { kept: m.kept, merged: m.merged }).collect();
+        Ok(((candidates, merges), events))
+    })?;
+    for merge in &merges {
+        let rationale = format!(
+            "Merged duplicate {:?} (score {:.2}): {} edges rewired, {} collapsed, {} removed",
+            merge.merged_label, merge.score.unwrap_or(1.0), merge.edges_rewired, merge.edges_collapsed, merge.self_loops_removed,
+        );
+        state.record_provenance(ProvenanceNote::new(id, merge.kept, crate::prov::uuid_iri(merge.merged), "merge").with_rationale(rationale))?;
+    }
+    if !merges.is_empty() {
+        state.snapshot_metrics(id, SnapshotTrigger::Mutation);
+    }
+    Ok(Json(DedupResponse { candidates, merges }))
+}
+
+#[derive(serde::Deserialize)]
+struct RemoveParams {
+    #[serde(default)]
+    dangling: DanglingEdges,      // nodes only: delete or flag their edges
+    reason: Option<String>,
+}
+
+/// Remove a node or edge of graph `id` as `event` describes and record a
+/// "remove" provenance note for everything removed, sourced to the `x-actor` caller
+fn apply_removal(
+    state: &AppState,
+    id: Uuid,
+    headers: &HeaderMap,
+    reason: Option<String>,
+    event: GraphEvent,
+    remove: impl FnOnce(&mut MultiIntentGraph) -> Option<Removal>,
+) -> Result<Json<Removal>, ApiError> {
+    let removal = state.edit_graph(editor(headers), id, |graph| {
+        let removal = remove(graph).ok_or(ApiError::NotFound("no such node or edge".into()))?;
+        Ok((removal, vec![event]))
+    })?;
+    let source = curator(headers);
+    for subject in removal.removed_nodes.iter().chain(&removal.removed_edges) {
+        let mut note = ProvenanceNote::new(id, *subject, source.clone(), "remove");
+        note.rationale = reason.clone();
+        state.record_provenance(note)?;
+    }
+    state.snapshot_metrics(id, SnapshotTrigger::Mutation);
+    Ok(Json(removal))
+}
+
+async fn delete_node(
+    State(state): State<AppState>,
+    Path((id, node_id)): Path<(Uuid, Uuid)>,
+    Query(params): Query<RemoveParams>,
+    headers: HeaderMap,
+) -> Result<Json<Removal>, ApiError> {
+    let event = GraphEvent::NodeRemoved { node_id, dangling: params.dangling };
+    apply_removal(&state, id, &headers, params.reason, event, |g| g.remove_node(node_id, params.dangling))
+}
+
+async fn delete_edge(
+    State(state): State<AppState>,
+    Path((id, edge_id)): Path<(Uuid, Uuid)>,
+    Query(params): Query<RemoveParams>,
+    headers: HeaderMap,
+) -> Result<Json<Removal>, ApiError> {
+    apply_removal(&state, id, &headers, params.reason, GraphEvent::EdgeRemoved { edge_id }, |g| g.remove_edge(edge_id))
+}
+
+/// Supporting and refuting edges of the relationship `edge_id` belongs to, with its net support
+async fn get_edge_support(
+    State(state): State<AppState>,
+    Path((id, edge_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<RelationshipSupport>, ApiError> {
+    let graphs = state.multi_graphs.lock()?;
+    let graph = graphs.iter().find(|g| g.id == id).ok_or_else(|| ApiError::not_found("graph", id))?;
+    graph.relationship_support(edge_id).map(Json).ok_or(ApiError::not_found("edge", edge_id))
+}
+
+#[derive(serde::Deserialize)]
+struct RefutationPayload {
+    label: String,
+    evidence_refs: Vec<String>,
+    confidence: f32,      // strength of the contradicting evidence
+}
+
+/// Attach a contradicting study to edge `edge_id`'s relationship as a refuting
+/// edge, recording an "add" provenance note citing its first reference
+async fn post_refutation(
+    State(state): State<AppState>,
+    Path((id, edge_id)): Path<(Uuid, Uuid)>,
+    headers: HeaderMap,
+    Payload(payload): Payload<RefutationPayload>,
+) -> Result<(StatusCode, Json<RelationshipSupport>), ApiError> {
+    let (refutation_id, support) = state.edit_graph(editor(&headers), id, |graph| {
+        match graph.edges.get(&edge_id) {
+            None => return Err(ApiError::not_found("edge", edge_id)),
+            Some(edge) if edge.is_refuting() => return Err(ApiError::BadRequest(format!("edge {} is itself a refutation", edge_id))),
+            Some(edge) => {
+                let refutation = GraphEdge::refuting(edge, payload.label.clone(), payload.evidence_refs.clone(), payload.confidence);
+                check_schema(validate_edge(&refutation))?;
+            }
+        }
+        let refutation_id = graph.add_refutation(edge_id, payload.label.clone(), payload.evidence_refs.clone(), payload.confidence)
+            .expect("supporting edge present");
+        let events = GraphEvent::added(graph, refutation_id).into_iter().collect();
+        Ok(((refutation_id, graph.relationship_support(edge_id).expect("edge present")), events))
+    })?;
+    let source = payload.evidence_refs.first().cloned()
+        .unwrap_or_else(|| curator(&headers));
+    let rationale = format!("Refutes edge {}: {}", edge_id, payload.label);
+    state.record_provenance(ProvenanceNote::new(id, refutation_id, source, "add").with_rationale(rationale))?;
+    state.snapshot_metrics(id, SnapshotTrigger::Mutation);
+    Ok((StatusCode::CREATED, Json(support)))
+}
+
+#[derive(serde::Deserialize)]
+struct HyperEdgePayload {
+    edge_type: EdgeType,
+    source_ids: Vec<Uuid>,
+    target_ids: Vec<Uuid>,
+    label: String,
+    #[serde(default)]
+    evidence_refs: Vec<String>,
+    confidence: f32,
+}
+
+/// Add a hyperedge between existing nodes of graph `id` and record an "add"
+/// provenance note citing its first evidence reference (or the `x-actor` caller)
+async fn post_hyperedge(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Payload(payload): Payload<HyperEdgePayload>,
+) -> Result<(StatusCode, Json<HyperEdge>), ApiError> {
+    if payload.source_ids.is_empty() || payload.target_ids.is_empty() {
+        return Err(ApiError::BadRequest("a hyperedge needs at least one source and one target".into()));
+    }
+    let hyperedge = HyperEdge::new(
+        payload.edge_type,
+        payload.source_ids,
+        payload.target_ids,
+        payload.label,
+        payload.evidence_refs,
+        payload.confidence,
+    );
+    check_schema(validate_hyperedge(&hyperedge))?;
+    state.edit_graph(editor(&headers), id, |graph| {
+        if let Some(missing) = hyperedge.endpoints().find(|n| !graph.intent_nodes.contains_key(n)) {
+            return Err(ApiError::not_found("node", missing));
+        }
+        check_schema(graph.check_edge_type(hyperedge.id, SubjectKind::Hyperedge, &hyperedge.edge_type).into_iter().collect())?;
+        graph.add_hyperedge(hyperedge.clone());
+        Ok(((), vec![GraphEvent::HyperedgeAdded { hyperedge: hyperedge.clone() }]))
+    })?;
+    let source = hyperedge.evidence_refs.first().cloned()
+        .unwrap_or_else(|| curator(&headers));
+    state.record_provenance(ProvenanceNote::new(id, hyperedge.id, source, "add").with_rationale(hyperedge.label.clone()))?;
+    state.snapshot_metrics(id, SnapshotTrigger::Mutation);
+    Ok((StatusCode::CREATED, Json(hyperedge)))
+}
+
+/// A built-in or registered custom edge type
+#[derive(serde::Serialize)]
+struct EdgeTypeInfo {
+    name: String,
+    custom: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+}
+
+/// Edge types graph `id` accepts: the built-in ones, then its custom types by name
+async fn get_edge_types(State(state): State<AppState>, Path(id): Path<Uuid>) -> Result<Json<Vec<EdgeTypeInfo>>, ApiError> {
+    let graphs = state.multi_graphs.lock()?;
+    let graph = graphs.iter().find(|g| g.id == id).ok_or_else(|| ApiError::not_found("graph", id))?;
+    let built_in = EdgeType::BUILT_IN.iter().map(|t| EdgeTypeInfo { name: t.name().to_string(), custom: false, description: None });
+    let custom = graph.edge_vocabulary.custom_types()
+        .map(|(name, description)| EdgeTypeInfo { name: name.to_string(), custom: true, description: Some(description.to_string()) });
+    Ok(Json(built_in.chain(custom).collect()))
+}
+
+#[derive(serde::Deserialize)]
+struct EdgeTypePayload {
+    name: String,
+    #[serde(default)]
+    description: String,
+}
+
+/// Add a custom edge type to graph `id`'s vocabulary, so edges and hyperedges of that type are accepted
+async fn post_edge_type(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Payload(payload): Payload<EdgeTypePayload>,
+) -> Result<(StatusCode, Json<EdgeTypeInfo>), ApiError> {
+    let name = state.edit_graph(editor(&headers), id, |graph| {
+        let edge_type = graph.edge_vocabulary.register(&payload.name, payload.description.clone())
+            .map_err(|e| ApiError::Invalid(e.to_string()))?;
+        let name = edge_type.name().to_string();
+        let event = GraphEvent::EdgeTypeRegistered { name: name.clone(), description: payload.description.clone() };
+        Ok((name, vec![event]))
+    })?;
+    let rationale = format!("Edge type {}: {}", name, payload.description);
+    state.record_provenance(ProvenanceNote::new(id, Uuid::nil(), curator(&headers), "register-edge-type").with_rationale(rationale))?;
+    Ok((StatusCode::CREATED, Json(EdgeTypeInfo { name, custom: true, description: Some(payload.description) })))
+}
+
+/// Synonym groups graph `id` consults: the built-in ones, then its own by canonical name
+async fn get_synonyms(State(state): State<AppState>, Path(id): Path<Uuid>) -> Result<Json<Vec<SynonymGroup>>, ApiError> {
+    let graphs = state.multi_graphs.lock()?;
+    let graph = graphs.iter().find(|g| g.id == id).ok_or_else(|| ApiError::not_found("graph", id))?;
+    Ok(Json(graph.synonyms.groups()))
+}
+
+#[derive(serde::Deserialize)]
+struct SynonymPayload {
+    canonical: String,
+    aliases: Vec<String>,
+}
+
+/// Add or replace a synonym group of graph `id`, used by its search, name resolution and dedup
+async fn post_synonyms(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Payload(payload): Payload<SynonymPayload>,
+) -> Result<(StatusCode, Json<SynonymGroup>), ApiError> {
+    let group = state.edit_graph(editor(&headers), id, |graph| {
+        let group = graph.synonyms.register(&payload.canonical, &payload.aliases)
+            .map_err(|e| ApiError::Invalid(e.to_string()))?;
+        let event = GraphEvent::SynonymsRegistered { canonical: group.canonical.clone(), aliases: group.aliases.clone() };
+        Ok((group, vec![event]))
+    })?;
+    let rationale = format!("{}: {}", group.canonical, group.aliases.join(", "));
+    state.record_provenance(ProvenanceNote::new(id, Uuid::nil(), curator(&headers), "register-synonyms").with_rationale(rationale))?;
+    Ok((StatusCode::CREATED, Json(group)))
+}
+
+/// Remove synonym group `canonical` from graph `id`; built-in groups cannot be removed
+async fn delete_synonyms(
+    State(state): State<AppState>,
+    Path((id, canonical)): Path<(Uuid, String)>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<SynonymGroup>>, ApiError> {
+    let groups = state.edit_graph(editor(&headers), id, |graph| {
+        if !graph.synonyms.remove(&canonical) {
+            return Err(ApiError::not_found("synonym group", format!("{:?}", canonical)));
+        }
+        Ok((graph.synonyms.groups(), vec![GraphEvent::SynonymsRemoved { canonical: canonical.clone() }]))
+    })?;
+    state.record_provenance(ProvenanceNote::new(id, Uuid::nil(), curator(&headers), "drop-synonyms").with_rationale(canonical))?;
+    Ok(Json(groups))
+}
+
+/// Synonym groups the retrieval backend searches the corpus with
+async fn get_retrieval_synonyms(State(state): State<AppState>) -> Result<Json<Vec<SynonymGroup>>, ApiError> {
+    Ok(Json(state.retrieval.lock()?.synonyms().groups()))
+}
+
+/// Add or replace a synonym group of the retrieval backend
+async fn post_retrieval_synonyms(
+    State(state): State<AppState>,
+    Payload(payload): Payload<SynonymPayload>,
+) -> Result<(StatusCode, Json<SynonymGroup>), ApiError> {
+    let mut backend = state.retrieval.lock()?;
+    let mut synonyms = backend.synonyms().clone();
+    let group = synonyms.register(&payload.canonical, &payload.aliases)
+        .map_err(|e| ApiError::Invalid(e.to_string()))?;
+    backend.set_synonyms(synonyms);
+    Ok((StatusCode::CREATED, Json(group)))
+}
+
+/// Remove synonym group `canonical` from the retrieval backend
+async fn delete_retrieval_synonyms(
+    State(state): State<AppState>,
+    Path(canonical): Path<String>,
+) -> Result<Json<Vec<SynonymGroup>>, ApiError> {
+    let mut backend = state.retrieval.lock()?;
+    let mut synonyms = backend.synonyms().clone();
+    if !synonyms.remove(&canonical) {
+        return Err(ApiError::not_found("synonym group", format!("{:?}", canonical)));
+    }
+    backend.set_synonyms(synonyms);
+    Ok(Json(backend.synonyms().groups()))
+}
+
+#[derive(serde::Deserialize)]
+struct MeshParams {
+    domain: Option<String>,
+    q: Option<String>,
+}
+
+/// Corpus docs tagged with MeSH descriptor `term` (UI or heading), optionally
+/// narrowed to `?domain=` and, within it, a keyword search for `?q=`
+async fn get_docs_by_mesh(
+    State(state): State<AppState>,
+    Path(term): Path<String>,
+    Query(params): Query<MeshParams>,
+) -> Result<Json<Vec<CorpusDoc>>, ApiError> {
+    let backend = state.retrieval.lock()?;
+    let docs = match (params.domain.as_deref(), params.q.as_deref().filter(|q| !q.trim().is_empty())) {
+        (Some(domain), Some(q)) => backend.keyword_search_mesh(domain, q, &term),
+        (None, Some(_)) => return Err(ApiError::BadRequest("?q= needs ?domain=".into())),
+        (Some(domain), None) => backend.filter_domain(domain).into_iter().filter(|d| d.has_mesh(&term)).collect(),
+        (None, None) => backend.filter_mesh(&term),
+    };
+    Ok(Json(docs.into_iter().cloned().collect()))
+}
+
+#[derive(serde::Deserialize)]
+struct ConfidencePayload {
+    #[serde(flatten)]
+    model: ConfidenceModel,
+    #[serde(default)]
+    evidence_dates: HashMap<String, String>,  // publication dates to record first, by reference
+    #[serde(default)]
+    dry_run: bool,        // only report what would change
+}
+
+/// Recompute every edge and hyperedge confidence from the count, kind and
+/// age of its evidence. Each changed confidence gets a "rescore-confidence"
+/// provenance note sourced to the `x-actor` caller.
+async fn post_confidence(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Payload(payload): Payload<ConfidencePayload>,
+) -> Result<Json<Vec<ConfidenceUpdate>>, ApiError> {
+    let now = chrono::Utc::now();
+    if let Some((reference, date)) = payload.evidence_dates.iter().find(|(_, d)| parse_evidence_date(d).is_none()) {
+        return Err(ApiError::BadRequest(format!("unreadable date {:?} for {}", date, reference)));
+    }
+    let apply_dates = |g: &mut MultiIntentGraph| for (reference, date) in &payload.evidence_dates {
+        g.set_evidence_date(reference, date);
+    };
+    let updates = if payload.dry_run {
+        let graphs = state.multi_graphs.lock()?;
+        let mut preview = graphs.iter().find(|g| g.id == id).ok_or_else(|| ApiError::not_found("graph", id))?.clone();
+        apply_dates(&mut preview);
+        preview.assess_confidence(&payload.model, now)
+    } else {
+        state.edit_graph(editor(&headers), id, |graph| {
+            apply_dates(graph);
+            let updates = graph.recompute_confidence(&payload.model, now);
+            let dated = payload.evidence_dates.iter()
+                .map(|(reference, date)| GraphEvent::EvidenceDated { reference: reference.clone(), date: date.clone() });
+            let recomputed = GraphEvent::ConfidenceRecomputed { model: payload.model.clone(), at: now };
+            Ok((updates, dated.chain([recomputed]).collect()))
+        })?
+    };
+    if !payload.dry_run {
+        let source = curator(&headers);
+        for update in updates.iter().filter(|u| (u.confidence - u.previous).abs() > 1e-4) {
+            let rationale = format!("Confidence {:.2} → {:.2} from {} references", update.previous, update.confidence, update.evidence.len());
+            state.record_provenance(ProvenanceNote::new(id, update.edge_id, source.clone(), "rescore-confidence").with_rationale(rationale))?;
+        }
+        state.snapshot_metrics(id, SnapshotTrigger::Mutation);
+    }
+    Ok(Json(updates))
+}
+
+#[derive(Default, serde::Deserialize)]
+#[serde(default)]
+struct PropagatePayload {
+    policy: Option<PropagationPolicy>,    // replaces the graph's `propagation` policy
+    full: bool,           // recompute every node, not only those downstream of changes
+}
+
+/// Propagate node confidence downstream from incoming edges. Each changed
+/// node gets a "propagate-confidence" provenance note sourced to the `x-actor` caller.
+async fn post_propagate_confidence(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    payload: Option<Json<PropagatePayload>>,
+) -> Result<Json<Vec<NodeConfidenceChange>>, ApiError> {
+    let Json(payload) = payload.unwrap_or_default();
+    let changes = state.edit_graph(editor(&headers), id, |graph| {
+        let event = GraphEvent::ConfidencePropagated { policy: payload.policy.clone(), full: payload.full };
+        if let Some(policy) = payload.policy {
+            graph.propagation = policy;
+            graph.invalidate_all_confidence();
+        }
+        if payload.full {
+            graph.invalidate_all_confidence();
+        }
+        Ok((graph.propagate_confidence(), vec![event]))
+    })?;
+    if !changes.is_empty() {
+        let source = curator(&headers);
+        for change in &changes {
+            let rationale = format!("Confidence {:.2} → {:.2} from {} incoming links", change.previous, change.confidence, change.incoming);
+            state.record_provenance(ProvenanceNote::new(id, change.node_id, source.clone(), "propagate-confidence").with_rationale(rationale))?;
+        }
+        state.snapshot_metrics(id, SnapshotTrigger::Mutation);
+    }
+    Ok(Json(changes))
+}
+
+/// Causal subgraph as a Bayesian network: `?leak=` sets the noisy-OR leak
+async fn get_bayes_network(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(options): Query<BayesOptions>,
+) -> Result<Json<BayesianNetwork>, ApiError> {
+    let graphs = state.multi_graphs.lock()?;
+    let graph = graphs.iter().find(|g| g.id == id).ok_or_else(|| ApiError::not_found("graph", id))?;
+    Ok(Json(graph.bayesian_network(&options)))
+}
+
+#[derive(serde::Deserialize)]
+struct BayesQueryPayload {
+    #[serde(default)]
+    evidence: HashMap<Uuid, bool>,    // observed nodes, e.g. a variant being present
+    query: Vec<Uuid>,                 // outcomes to report belief in
+    #[serde(flatten)]
+    options: BayesOptions,
+}
+
+/// Condition the causal Bayesian network on observed nodes and return prior
+/// and posterior belief in each queried node
+async fn post_bayes_query(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Payload(payload): Payload<BayesQueryPayload>,
+) -> Result<Json<Vec<Belief>>, ApiError> {
+    let network = {
+        let graphs = state.multi_graphs.lock()?;
+        let graph = graphs.iter().find(|g| g.id == id).ok_or_else(|| ApiError::not_found("graph", id))?;
+        graph.bayesian_network(&payload.options)
+    };
+    network.query(&payload.evidence, &payload.query).map(Json).map_err(|e| match e {
+        BayesError::UnknownNode { .. } => ApiError::BadRequest(e.to_string()),
+        BayesError::TooLarge { .. } | BayesError::ImpossibleEvidence => ApiError::Invalid(e.to_string()),
+    })
+}
+
+/// Quantum vs classical walk from a node (`?time=&samples=&shots=&seed=&hops=&min_advantage=&max_paths=`)
+/// with the non-obvious paths it suggests
+async fn get_quantum_walk(
+    State(state): State<AppState>,
+    Path((id, node_id)): Path<(Uuid, Uuid)>,
+    Query(options): Query<QuantumWalkOptions>,
+) -> Result<Json<QuantumWalkReport>, ApiError> {
+    let graphs = state.multi_graphs.lock()?;
+    let graph = graphs.iter().find(|g| g.id == id).ok_or_else(|| ApiError::not_found("graph", id))?;
+    graph.quantum_walk(node_id, &options).map(Json).map_err(|e| match e {
+        QuantumWalkError::UnknownNode { node } => ApiError::not_found("node", node),
+        QuantumWalkError::TooLarge { .. } | QuantumWalkError::InvalidOptions => ApiError::Invalid(e.to_string()),
+    })
+}
+
+/// Nodes most like this one by embedding, shared mutations and sources, and shared neighbors
+async fn get_similar_nodes(
+    State(state): State<AppState>,
+    Path((id, node_id)): Path<(Uuid, Uuid)>,
+    Query(options): Query<SimilarityOptions>,
+) -> Result<Json<Vec<SimilarNode>>, ApiError> {
+    let graphs = state.multi_graphs.lock()?;
+    let graph = graphs.iter().find(|g| g.id == id).ok_or_else(|| ApiError::not_found("graph", id))?;
+    graph.similar_nodes(node_id, &options).map(Json).ok_or_else(|| ApiError::not_found("node", node_id))
+}
+
+#[derive(serde::Deserialize, Default)]
+struct SelectPathsPayload {
+    #[serde(flatten)]
+    options: PathSelectionOptions,
+    #[serde(default)]
+    annealing: SimulatedAnnealing,
+    #[serde(default)]
+    generate: bool,               // also consider paths `generate_hypothesis_paths` finds for built-in types
+}
+
+/// Pick the best `k` mutually diverse hypothesis paths by simulated annealing
+/// on the QUBO encoding; the encoded problem is returned for external solvers
+async fn post_select_hypotheses(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    payload: Option<Json<SelectPathsPayload>>,
+) -> Result<Json<SelectionReport>, ApiError> {
+    let Json(payload) = payload.unwrap_or_default();
+    let mut candidates = {
+        let graphs = state.multi_graphs.lock()?;
+        let graph = graphs.iter().find(|g| g.id == id).ok_or_else(|| ApiError::not_found("graph", id))?;
+        let mut candidates = graph.hypothesis_paths.clone();
+        if payload.generate {
+            for t in HypothesisType::BUILT_IN {
+                for path in graph.generate_hypothesis_paths(t, payload.options.min_confidence) {
+                    if !candidates.iter().any(|p| p.id == path.id) {
+                        candidates.push(path);
+                    }
+                }
+            }
+        }
+        candidates
+    };
+    candidates.sort_by_key(|p| p.id);
+    select_hypothesis_paths(&candidates, &payload.options, &payload.annealing).map(Json).map_err(|e| match e {
+        QuboError::TooLarge { .. } => ApiError::Invalid(e.to_string()),
+        QuboError::InvalidSample { .. } | QuboError::Solver(_) => ApiError::internal(e),
+    })
+}
+
+/// Recompute structural embeddings for every node; isolated nodes lose theirs
+async fn post_embeddings(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    payload: Option<Json<EmbeddingOptions>>,
+) -> Result<Json<EmbeddingReport>, ApiError> {
+    let Json(options) = payload.unwrap_or_default();
+    let report = state.edit_graph(editor(&headers), id, |graph| {
+        let report = graph.compute_embeddings(&options);
+        Ok((report, vec![GraphEvent::EmbeddingsComputed { options }]))
+    })?;
+    let rationale = format!("{} nodes embedded, {} isolated", report.embedded, report.isolated);
+    state.record_provenance(ProvenanceNote::new(id, Uuid::nil(), curator(&headers), "compute-embeddings").with_rationale(rationale))?;
+    state.snapshot_metrics(id, SnapshotTrigger::Mutation);
+    Ok(Json(report))
+}
+
+#[derive(serde::Deserialize)]
+struct InterventionPayload {
+    value: f32,           // confidence the node is fixed at
+}
+
+/// do(node = value): cut the node's incoming causal edges and report the
+/// downstream nodes and hypothesis paths whose confidence changes. The graph
+/// is not modified.
+async fn post_intervene(
+    State(state): State<AppState>,
+    Path((id, node_id)): Path<(Uuid, Uuid)>,
+    Payload(payload): Payload<InterventionPayload>,
+) -> Result<Json<Intervention>, ApiError> {
+    let graphs = state.multi_graphs.lock()?;
+    let graph = graphs.iter().find(|g| g.id == id).ok_or_else(|| ApiError::not_found("graph", id))?;
+    graph.intervene(node_id, payload.value)
+        .map(Json)
+        .ok_or(ApiError::not_found("node", node_id))
+}
+
+#[derive(serde::Deserialize)]
+struct CounterfactualPayload {
+    factual: Uuid,            // hypothesis path IDs
+    counterfactual: Uuid,
+}
+
+/// Compare the outcomes reachable from two hypothesis paths past the node
+/// where they diverge
+async fn post_counterfactual(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Payload(payload): Payload<CounterfactualPayload>,
+) -> Result<Json<CounterfactualReport>, ApiError> {
+    let graphs = state.multi_graphs.lock()?;
+    let graph = graphs.iter().find(|g| g.id == id).ok_or_else(|| ApiError::not_found("graph", id))?;
+    graph.compare_counterfactual(payload.factual, payload.counterfactual).map(Json).map_err(|e| match e {
+        CounterfactualError::UnknownPath { .. } => ApiError::NotFound(e.to_string()),
+        _ => ApiError::Invalid(e.to_string()),
+    })
+}
+
+#[derive(serde::Deserialize)]
+struct RegionParams {
+    gene: Option<String>,
+}
+
+/// Protein regions annotated on virology nodes and the variant mutations in each
+async fn get_region_mutations(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<RegionParams>,
+) -> Result<Json<Vec<RegionMutations>>, ApiError> {
+    let graphs = state.multi_graphs.lock()?;
+    let graph = graphs.iter().find(|g| g.id == id).ok_or_else(|| ApiError::not_found("graph", id))?;
+    let mut regions = graph.mutations_by_region();
+    if let Some(gene) = params.gene {
+        regions.retain(|r| r.region.gene.eq_ignore_ascii_case(&gene));
+    }
+    Ok(Json(regions))
+}
+
+#[derive(serde::Deserialize)]
+struct SummaryParams {
+    nodes: Option<String>,            // comma-separated focus node IDs
+    #[serde(default)]
+    hops: usize,
+    domain: Option<crate::domain::ResearchDomain>,
+    hypothesis_type: Option<String>,
+    #[serde(default)]
+    min_confidence: f32,
+}
+
+/// Templated narrative of the graph, or of the neighborhood of `?nodes=`
+async fn get_summary(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<SummaryParams>,
+) -> Result<Json<GraphSummary>, ApiError> {
+    let nodes = params.nodes.as_deref().unwrap_or("")
+        .split(',')
+        .map(str::trim)
+        .filter(|n| !n.is_empty())
+        .map(|n| n.parse::<Uuid>().map_err(|_| ApiError::BadRequest(format!("unreadable node ID {:?}", n))))
+        .collect::<Result<Vec<Uuid>, _>>()?;
+    let filter = SummaryFilter {
+        nodes,
+        hops: params.hops,
+        domains: params.domain.into_iter().collect(),
+        hypothesis_type: params.hypothesis_type.as_deref().map(HypothesisType::custom),
+        min_confidence: params.min_confidence,
+    };
+    let graphs = state.multi_graphs.lock()?;
+    let graph = graphs.iter().find(|g| g.id == id).ok_or_else(|| ApiError::not_found("graph", id))?;
+    Ok(Json(graph.summarize(&filter)))
+}
+
+#[derive(serde::Deserialize)]
+struct SliceParams {
+    from: Option<String>,     // RFC 3339, YYYY-MM-DD or a year
+    to: Option<String>,
+}
+
+/// The graph as built within a time window
+async fn get_slice(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<SliceParams>,
+) -> Result<Json<MultiIntentGraph>, ApiError> {
+    let bound = |value: Option<String>| value
+        .map(|v| parse_evidence_date(&v).ok_or(ApiError::BadRequest(format!("unreadable date {:?}", v))))
+        .transpose();
+    let (from, to) = (bound(params.from)?, bound(params.to)?);
+    let graphs = state.multi_graphs.lock()?;
+    let graph = graphs.iter().find(|g| g.id == id).ok_or_else(|| ApiError::not_found("graph", id))?;
+    Ok(Json(graph.slice(from, to)))
+}
+
+#[derive(serde::Deserialize)]
+struct TimelineParams {
+    #[serde(default)]
+    bucket: TimeBucket,
+}
+
+/// Nodes and edges added per day, week, month or year
+async fn get_timeline(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<TimelineParams>,
+) -> Result<Json<Timeline>, ApiError> {
+    let graphs = state.multi_graphs.lock()?;
+    let graph = graphs.iter().find(|g| g.id == id).ok_or_else(|| ApiError::not_found("graph", id))?;
+    Ok(Json(graph.timeline(params.bucket)))
+}
+
+#[derive(serde::Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+enum LineageTreeFormat {
+    #[default]
+    Json,
+    Newick,
+    Dot,
+}
+
+#[derive(serde::Deserialize)]
+struct LineageTreeParams {
+    #[serde(default)]
+    format: LineageTreeFormat,
+}
+
+/// Lineage tree of the graph's variant nodes as JSON, Newick or Graphviz DOT
+async fn get_lineage_tree(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<LineageTreeParams>,
+) -> Result<([(header::HeaderName, &'static str); 1], String), ApiError> {
+    let graphs = state.multi_graphs.lock()?;
+    let graph = graphs.iter().find(|g| g.id == id).ok_or_else(|| ApiError::not_found("graph", id))?;
+    let tree = graph.lineage_tree();
+    Ok(match params.format {
+        LineageTreeFormat::Json => {
+            let body = serde_json::to_string(&tree).map_err(ApiError::internal)?;
+            ([(header::CONTENT_TYPE, "application/json")], body)
+        }
+        LineageTreeFormat::Newick => ([(header::CONTENT_TYPE, "text/x-nh; charset=utf-8")], tree.to_newick()),
+        LineageTreeFormat::Dot => ([(header::CONTENT_TYPE, "text/vnd.graphviz; charset=utf-8")], tree.to_dot()),
+    })
+}
+
+/// Variant nodes on lineages below `lineage`
+async fn get_lineage_descendants(
+    State(state): State<AppState>,
+    Path((id, lineage)): Path<(Uuid, String)>,
+) -> Result<Json<Vec<IntentNode>>, ApiError> {
+    let lineage = PangoLineage::parse(&lineage).map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    let graphs = state.multi_graphs.lock()?;
+    let graph = graphs.iter().find(|g| g.id == id).ok_or_else(|| ApiError::not_found("graph", id))?;
+    Ok(Json(graph.descendant_variants(&lineage).into_iter().cloned().collect()))
+}
+
+/// 422 listing every broken schema invariant of a node or edge being written
+fn check_schema(issues: Vec<ValidationIssue>) -> Result<(), ApiError> {
+    if issues.is_empty() {
+        return Ok(());
+    }
+    let messages: Vec<String> = issues.iter().map(ToString::to_string).collect();
+    Err(ApiError::Invalid(messages.join("; ")))
+}
+
+/// Check every node, edge and hyperedge of graph `id` against the schema
+async fn post_validate(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ValidationReport>, ApiError> {
+    let graphs = state.multi_graphs.lock()?;
+    let graph = graphs.iter().find(|g| g.id == id).ok_or_else(|| ApiError::not_found("graph", id))?;
+    Ok(Json(graph.validate()))
+}
+
+#[derive(serde::Deserialize)]
+struct EventParams {
+    since: Option<u64>,   // only events after this sequence number
+}
+
+/// Mutation history of graph `id`, oldest first
+async fn get_events(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<EventParams>,
+) -> Result<Json<Vec<RecordedEvent>>, ApiError> {
+    if !state.multi_graphs.lock()?.iter().any(|g| g.id == id) {
+        return Err(ApiError::not_found("graph", id));
+    }
+    let log = state.events.lock()?;
+    Ok(Json(log.for_graph(id, params.since).into_iter().cloned().collect()))
+}
+
+#[derive(serde::Deserialize)]
+struct ReplayParams {
+    until: Option<u64>,   // state after this sequence number; the latest if unset
+}
+
+/// Graph `id` rebuilt from its event history
+async fn get_event_replay(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<ReplayParams>,
+) -> Result<Json<MultiIntentGraph>, ApiError> {
+    state.events.lock()?.replay(id, params.until).map(Json).map_err(|e| replay_error(id, e))
+}
+
+fn replay_error(id: Uuid, e: EventError) -> ApiError {
+    match e {
+        EventError::NoGraph(_) => ApiError::NotFound(format!("no history of graph {} up to that event", id)),
+        e => ApiError::internal(e),
+    }
+}
+
+#[derive(serde::Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+enum DiffFormat {
+    #[default]
+    Json,
+    Markdown,
+}
+
+#[derive(serde::Deserialize)]
+struct DiffParams {
+    from: Option<u64>,    // event sequence number of the older version; the graph as created if unset
+    to: Option<u64>,      // of the newer version; the latest if unset
+    #[serde(default)]
+    format: DiffFormat,
+}
+
+#[derive(serde::Serialize)]
+struct DiffResponse {
+    graph_id: Uuid,
+    from: u64,
+    to: u64,
+    #[serde(flatten)]
+    diff: GraphDiff,
+}
+
+/// Changelog between two versions of graph `id` rebuilt from its event
+/// history, as JSON or Markdown
+async fn get_diff(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<DiffParams>,
+) -> Result<([(header::HeaderName, &'static str); 1], String), ApiError> {
+    let log = state.events.lock()?;
+    let history = log.for_graph(id, None);
+    let (Some(first), Some(last)) = (history.first(), history.last()) else {
+        return Err(ApiError::NotFound(format!("no history of graph {}", id)));
+    };
+    let (from, to) = (params.from.unwrap_or(first.seq), params.to.unwrap_or(last.seq));
+    if from > to {
+        return Err(ApiError::BadRequest(format!("from ({}) is after to ({})", from, to)));
+    }
+    let older = log.replay(id, Some(from)).map_err(|e| replay_error(id, e))?;
+    let newer = log.replay(id, Some(to)).map_err(|e| replay_error(id, e))?;
+    drop(log);
+    let diff = older.diff(&newer);
+    Ok(match params.format {
+        DiffFormat::Json => {
+            let body = serde_json::to_string(&DiffResponse { graph_id: id, from, to, diff }).map_err(ApiError::internal)?;
+            ([(header::CONTENT_TYPE, "application/json")], body)
+        }
+        DiffFormat::Markdown => {
+            let title = format!("Changes to graph {} (events {} → {})", id, from, to);
+            ([(header::CONTENT_TYPE, "text/markdown; charset=utf-8")], diff.to_markdown(&title))
+        }
+    })
+}
+
+#[derive(serde::Deserialize)]
+struct StepParams {
+    #[serde(default = "one_step")]
+    steps: usize,         // operations to undo or redo
+}
+
+fn one_step() -> usize {
+    1
+}
+
+impl From<HistoryError> for ApiError {
+    fn from(e: HistoryError) -> Self {
+        match e {
+            HistoryError::Nothing { .. } | HistoryError::Conflict { .. } => Self::Conflict(e.to_string()),
+            HistoryError::Persist(e) => Self::internal(format!("{:#}", e)),
+        }
+    }
+}
+
+/// Undo or redo the caller's last operations on graph `id`, replacing the
+/// graph with its replayed history. The caller must name its session, so
+/// anonymous callers cannot undo each other's work.
+fn step_history(
+    state: &AppState,
+    id: Uuid,
+    headers: &HeaderMap,
+    action: &str,
+    step: impl FnOnce(&mut EventLog, &str, &str) -> Result<(MultiIntentGraph, Vec<RecordedEvent>), HistoryError>,
+) -> Result<Json<Vec<RecordedEvent>>, ApiError> {
+    if header_str(headers, "x-session").is_none() && header_str(headers, "x-actor").is_none() {
+        return Err(ApiError::BadRequest(format!("{} needs an x-session or x-actor header", action)));
+    }
+    let (actor, session) = editor(headers);
+    let affected = {
+        let mut graphs = state.multi_graphs.lock()?;
+        let graph = graphs.iter_mut().find(|g| g.id == id).ok_or_else(|| ApiError::not_found("graph", id))?;
+        let (rebuilt, affected) = step(&mut *state.events.lock()?, actor, session)?;
+        *graph = rebuilt;
+        affected
+    };
+    let rationale = format!("{} of {} events", action, affected.len());
+    state.record_provenance(ProvenanceNote::new(id, Uuid::nil(), format!("session:{}", session), action).with_rationale(rationale))?;
+    state.snapshot_metrics(id, SnapshotTrigger::Mutation);
+    Ok(Json(affected))
+}
+
+/// Revert the last `?steps=` (default 1) operations of the caller's session;
+/// returns their events
+async fn post_undo(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<StepParams>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<RecordedEvent>>, ApiError> {
+    step_history(&state, id, &headers, "undo", |log, actor, session| log.undo(id, actor, session, params.steps))
+}
+
+async fn post_redo(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<StepParams>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<RecordedEvent>>, ApiError> {
+    step_history(&state, id, &headers, "redo", |log, actor, session| log.redo(id, actor, session, params.steps))
+}
+
+#[derive(serde::Deserialize)]
+struct DecomposePayload {
+    question: String,
+}
+
+/// Split a free-text question into domain-tagged intent queries and a plan
+async fn post_decompose_question(Payload(payload): Payload<DecomposePayload>) -> Json<Decomposition> {
+    Json(QuestionDecomposer::default().decompose(&payload.question))
+}
+
+#[derive(serde::Deserialize)]
+struct ExecutePayload {
+    question: MultiIntentQuestion,
+    #[serde(default = "default_session")]
+    session_id: String,
+}
+
+fn default_session() -> String {
+    "api".into()
+}
+
+/// Run a question's intent queries against the retrieval backend; the built
+/// graph is stored (with its trace) and its provenance recorded
+async fn post_execute_question(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Payload(payload): Payload<ExecutePayload>,
+) -> Result<(StatusCode, Json<QueryExecution>), ApiError> {
+    let root = crate::nodes::VirusNode { id: Uuid::new_v4(), name: "SARS-CoV-2".into(), genome_kb: 30.0 };
+    let execution = {
+        let backend = state.retrieval.lock()?;
+        QueryExecutor::new(&backend)
+            .execute(&payload.question, &payload.session_id, SarsCov2Graph::new(root))
+            .map_err(|e| ApiError::BadRequest(format!("{:#}", e)))?
+    };
+    for note in &execution.provenance {
+        state.record_provenance(note.clone())?;
+    }
+    state.add_graph(editor(&headers), execution.graph.clone())?;
+    state.snapshot_metrics(execution.graph.id, SnapshotTrigger::Mutation);
+    Ok((StatusCode::CREATED, Json(execution)))
+}
+
+#[derive(serde::Deserialize)]
+struct ExplorePayload {
+    question: String,
+    #[serde(default = "default_session")]
+    session_id: String,
+    #[serde(default = "default_explore_policy")]
+    policy: SchedulerPolicy,
+    #[serde(default = "default_targets")]
+    targets: Vec<ScheduleTarget>,
+    #[serde(default)]
+    budget: ExplorationBudget,
+    rd_graph: Option<Uuid>,       // budget each intent by this graph's R-D curves
+}
+
+fn default_explore_policy() -> SchedulerPolicy {
+    SchedulerPolicy::EntropyMaximizing
+}
+
+/// Let the exploration agent work a question until its budget runs out; the
+/// built graph is stored (with the agent's trace) and its provenance recorded
+async fn post_explore_question(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Payload(payload): Payload<ExplorePayload>,
+) -> Result<(StatusCode, Json<Exploration>), ApiError> {
+    if payload.question.trim().is_empty() {
+        return Err(ApiError::BadRequest("question is empty".into()));
+    }
+    let rd_curves = match payload.rd_graph {
+        Some(id) => state.rd_curves_of(id)?,
+        None => HashMap::new(),
+    };
+    let root = crate::nodes::VirusNode { id: Uuid::new_v4(), name: "SARS-CoV-2".into(), genome_kb: 30.0 };
+    let exploration = {
+        let backend = state.retrieval.lock()?;
+        ExplorationAgent::new(&backend, ExplorationScheduler::new(payload.policy, payload.targets).with_rd_curves(rd_curves.clone()))
+            .with_budget(payload.budget)
+            .with_rd_curves(rd_curves)
+            .explore(&payload.question, &payload.session_id, SarsCov2Graph::new(root))
+            .map_err(|e| ApiError::BadRequest(format!("{:#}", e)))?
+    };
+    for note in &exploration.provenance {
+        state.record_provenance(note.clone())?;
+    }
+    state.add_graph(editor(&headers), exploration.graph.clone())?;
+    state.snapshot_metrics(exploration.graph.id, SnapshotTrigger::Mutation);
+    Ok((StatusCode::CREATED, Json(exploration)))
+}
+
+#[derive(serde::Deserialize)]
+struct SourceParams {
+    doi: Option<String>,
+    source: Option<String>,       // any other reference: PMID, URL, dataset handle
+}
+
+#[derive(serde::Serialize)]
+struct GraphCitations {
+    graph_id: Uuid,
+    citations: Vec<Citation>,
+}
+
+#[derive(serde::Serialize)]
+struct SourceReport {
+    source: String,
+    graphs: Vec<GraphCitations>,          // multi-intent graphs with at least one citing element
+    recorded_traces: Vec<Citation>,       // steps of traces recorded over HTTP
+    provenance: Vec<ProvenanceNote>,      // notes whose source is the reference
+}
+
+/// Everything built on a source (`?doi=10.1038/...` or `?source=pmid:...`), e.g. after a retraction
+async fn get_provenance_by_source(
+    State(state): State<AppState>,
+    Query(params): Query<SourceParams>,
+) -> Result<Json<SourceReport>, ApiError> {
+    let source = params.doi.map(|d| format!("doi:{}", d.trim().trim_start_matches("doi:")))
+        .or(params.source)
+        .filter(|s| !s.trim().is_empty())
+        .ok_or(ApiError::BadRequest("pass ?doi= or ?source=".into()))?;
+
+    let graphs = state.multi_graphs.lock()?.iter()
+        .map(|g| GraphCitations { graph_id: g.id, citations: g.citations_of(&source) })
+        .filter(|g| !g.citations.is_empty())
+        .collect();
+    let recorded_traces = state.traces.lock()?.iter().flat_map(|t| t.citations_of(&source)).collect();
+    let provenance = state.provenance.lock()?.notes().iter().filter(|n| cites(&n.source, &source)).cloned().collect();
+    Ok(Json(SourceReport { source, graphs, recorded_traces, provenance }))
+}
+
+/// Notes made by one API request, found by its `x-request-id`
+async fn get_provenance_by_request(
+    State(state): State<AppState>,
+    Path(request_id): Path<String>,
+) -> Result<Json<Vec<ProvenanceNote>>, ApiError> {
+    let prov = state.provenance.lock()?;
+    Ok(Json(prov.for_request(&request_id).into_iter().cloned().collect()))
+}
+
+#[derive(serde::Deserialize)]
+struct VerifyParams {
+    graph_id: Option<Uuid>,
+}
+
+/// Check note signatures against the trusted keys; tampered notes are listed by ID
+async fn get_provenance_verify(
+    State(state): State<AppState>,
+    Query(params): Query<VerifyParams>,
+) -> Result<Json<VerificationReport>, ApiError> {
+    let prov = state.provenance.lock()?;
+    let notes = prov.notes().iter().filter(|n| params.graph_id.is_none_or(|g| n.graph_id == g));
+    Ok(Json(verify_notes(notes, &state.trusted_keys)))
+}
+
+/// Provenance of a node or edge together with its neighbours' notes
+async fn get_lineage(
+    State(state): State<AppState>,
+    Path((id, subject_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ProvenanceLineage>, ApiError> {
+    let graphs = state.multi_graphs.lock()?;
+    let graph = graphs.iter().find(|g| g.id == id).ok_or_else(|| ApiError::not_found("graph", id))?;
+    Ok(Json(state.provenance.lock()?.lineage(graph, subject_id)))
+}
+
+#[derive(serde::Deserialize)]
+struct TraceViewParams {
+    #[serde(default)]
+    summary: bool,                // summaries instead of full traces
+}
+
+#[derive(serde::Deserialize)]
+struct TraceListParams {
+    graph_id: Option<Uuid>,
+    session_id: Option<String>,
+    #[serde(default)]
+    summary: bool,
+}
+
+#[derive(serde::Serialize)]
+#[serde(untagged)]
+enum TraceListing {
+    Traces(Vec<SerendipityTrace>),
+    Summaries(Vec<SerendipitySummary>),
+}
+
+impl TraceListing {
+    fn new(traces: Vec<SerendipityTrace>, summary: bool) -> Self {
+        match summary {
+            true => Self::Summaries(traces.iter().map(SerendipityTrace::summary).collect()),
+            false => Self::Traces(traces),
+        }
+    }
+}
+
+/// Trace `id`, or every trace of graph `id`: those recorded against it, then
+/// the ones the graph carries
+async fn get_traces(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<TraceViewParams>,
+) -> Result<Json<TraceListing>, ApiError> {
+    let traces = match state.find_trace(id)? {
+        Some(trace) => vec![trace],
+        None => state.traces_of_graph(id)?.ok_or_else(|| ApiError::not_found("trace or graph", id))?,
+    };
+    Ok(Json(TraceListing::new(traces, params.summary)))
+}
+
+/// Recorded traces and those carried by graphs, narrowed to one graph and/or session
+async fn get_trace_list(State(state): State<AppState>, Query(params): Query<TraceListParams>) -> Result<Json<TraceListing>, ApiError> {
+    let mut traces = match (params.graph_id, &params.session_id) {
+        (Some(graph_id), _) => state.traces_of_graph(graph_id)?.ok_or_else(|| ApiError::not_found("graph", graph_id))?,
+        (None, Some(session_id)) => {
+            let mut traces: Vec<SerendipityTrace> = state.traces.lock()?.for_session(session_id).into_iter().cloned().collect();
+            let graphs = state.multi_graphs.lock()?;
+            traces.extend(graphs.iter().flat_map(|g| &g.serendipity_traces).cloned());
+            traces
+        }
+        (None, None) => state.all_traces()?,
+    };
+    if let Some(session_id) = &params.session_id {
+        traces.retain(|t| &t.session_id == session_id);
+    }
+    Ok(Json(TraceListing::new(traces, params.summary)))
+}
+
+#[derive(serde::Deserialize)]
+struct TraceSearchParams {
+    q: Option<String>,
+    hypothesis: Option<HypothesisType>,
+    min_diversity: Option<f32>,
+    #[serde(default = "default_trace_limit")]
+    limit: usize,
+}
+
+fn default_trace_limit() -> usize {
+    20
+}
+
+/// Recorded and graph-carried traces relevant to a question, best match first
+async fn get_trace_search(State(state): State<AppState>, Query(params): Query<TraceSearchParams>) -> Result<Json<Vec<TraceMatch>>, ApiError> {
+    let search = TraceSearch { q: params.q, hypothesis: params.hypothesis, min_diversity: params.min_diversity };
+    let mut matches = search_traces(&state.all_traces()?, &search);
+    matches.truncate(params.limit);
+    Ok(Json(matches))
+}
+
+#[derive(serde::Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+enum TraceFormat {
+    #[default]
+    Mermaid,
+    Dot,
+}
+
+#[derive(serde::Deserialize)]
+struct TraceExportParams {
+    #[serde(default)]
+    format: TraceFormat,
+}
+
+#[derive(serde::Deserialize)]
+struct NewTracePayload {
+    session_id: String,
+    question: String,
+    graph_id: Option<Uuid>,       // graph the session explores
+}
+
+/// Start recording a trace, with an "add-trace" provenance note sourced to its session
+async fn post_trace(State(state): State<AppState>, Payload(payload): Payload<NewTracePayload>) -> Result<(StatusCode, Json<SerendipityTrace>), ApiError> {
+    if let Some(graph_id) = payload.graph_id {
+        if !state.multi_graphs.lock()?.iter().any(|g| g.id == graph_id) {
+            return Err(ApiError::not_found("graph", graph_id));
+        }
+    }
+    let trace = SerendipityTrace::new(payload.session_id, payload.question);
+    state.traces.lock()?.insert(trace.clone(), payload.graph_id);
+    let note = ProvenanceNote::new(payload.graph_id.unwrap_or_default(), trace.id, format!("session:{}", trace.session_id), "add-trace");
+    state.record_provenance(note.with_rationale(trace.question.clone()))?;
+    Ok((StatusCode::CREATED, Json(trace)))
+}
+
+#[derive(serde::Deserialize)]
+struct TraceMergePayload {
+    trace_ids: Vec<Uuid>,         // recorded or graph-carried traces, at least two
+    graph_id: Option<Uuid>,       // graph the campaign explored
+}
+
+/// Stitch traces exploring the same question into one campaign-level trace,
+/// recorded like a new one, with a "merge" provenance note per source trace
+async fn post_trace_merge(State(state): State<AppState>, Payload(payload): Payload<TraceMergePayload>) -> Result<(StatusCode, Json<SerendipityTrace>), ApiError> {
+    if payload.trace_ids.len() < 2 {
+        return Err(ApiError::BadRequest("trace_ids needs at least two traces".into()));
+    }
+    if let Some(graph_id) = payload.graph_id {
+        if !state.multi_graphs.lock()?.iter().any(|g| g.id == graph_id) {
+            return Err(ApiError::not_found("graph", graph_id));
+        }
+    }
+    let mut traces = vec![];
+    for &id in &payload.trace_ids {
+        traces.push(state.find_trace(id)?.ok_or_else(|| ApiError::not_found("trace", id))?);
+    }
+    let merged = traces[0].merge(&traces[1..]).map_err(|e| ApiError::Invalid(e.to_string()))?;
+    state.traces.lock()?.insert(merged.clone(), payload.graph_id);
+    for source in &merged.merged_from {
+        let note = ProvenanceNote::new(payload.graph_id.unwrap_or_default(), merged.id, crate::prov::uuid_iri(*source), "merge");
+        state.record_provenance(note.with_rationale(format!("Merged {} traces exploring {:?}", merged.merged_from.len(), merged.question)))?;
+    }
+    Ok((StatusCode::CREATED, Json(merged)))
+}
+
+#[derive(serde::Deserialize)]
+struct StepPayload {
+    hypothesis: HypothesisType,
+    query: String,
+    #[serde(default)]
+    domains: Vec<String>,
+    #[serde(default)]
+    evidence: usize,
+    #[serde(default)]
+    confidence: f32,
+    #[serde(default)]
+    evidence_refs: Vec<String>,
+    step_number: Option<usize>,   // defaults to the next step
+}
+
+/// Append a step to a recorded trace, with an "add-trace-step" provenance note
+/// citing its first evidence reference (or the `x-actor` caller)
+async fn post_trace_step(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Payload(payload): Payload<StepPayload>,
+) -> Result<Json<SerendipitySummary>, ApiError> {
+    let source = payload.evidence_refs.first().cloned()
+        .unwrap_or_else(|| curator(&headers));
+    let mut traces = state.traces.lock()?;
+    let graph_id = traces.graph_of(id).unwrap_or_default();
+    let trace = traces.get_mut(id).ok_or_else(|| ApiError::not_found("recorded trace", id))?;
+    let step = StepBuilder::new(payload.step_number.unwrap_or(trace.steps.len() + 1), payload.hypothesis, payload.query)
+        .domains(payload.domains)
+        .evidence(payload.evidence)
+        .confidence(payload.confidence)
+        .evidence_refs(payload.evidence_refs)
+        .build();
+    let note = ProvenanceNote::new(graph_id, step.id, source, "add-trace-step").with_rationale(step.query.clone());
+    trace.add_step(step);
+    let summary = trace.summary();
+    drop(traces);
+    state.record_provenance(note)?;
+    Ok(Json(summary))
+}
+
+async fn get_trace_summary(State(state): State<AppState>, Path(id): Path<Uuid>) -> Result<Json<SerendipitySummary>, ApiError> {
+    let trace = state.find_trace(id)?.ok_or_else(|| ApiError::not_found("trace", id))?;
+    Ok(Json(trace.summary()))
+}
+
+/// Exploration anti-patterns in trace `id`, thresholds from the query string
+async fn get_trace_diagnostics(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(options): Query<DiagnosticOptions>,
+) -> Result<Json<TraceDiagnostics>, ApiError> {
+    let trace = state.find_trace(id)?.ok_or_else(|| ApiError::not_found("trace", id))?;
+    Ok(Json(trace.diagnose(&options)))
+}
+
+/// Serendipity trace `id` as a Mermaid or DOT diagram
+async fn get_trace_export(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<TraceExportParams>,
+) -> Result<([(header::HeaderName, &'static str); 1], String), ApiError> {
+    let trace = state.find_trace(id)?.ok_or_else(|| ApiError::not_found("trace", id))?;
+    Ok(match params.format {
+        TraceFormat::Mermaid => ([(header::CONTENT_TYPE, "text/vnd.mermaid; charset=utf-8")], trace.to_mermaid()),
+        TraceFormat::Dot => ([(header::CONTENT_TYPE, "text/vnd.graphviz; charset=utf-8")], trace.to_dot()),
+    })
+}
+
+/// Re-run trace `id`'s queries against the current corpus
+async fn post_trace_replay(State(state): State<AppState>, Path(id): Path<Uuid>) -> Result<Json<TraceReplay>, ApiError> {
+    let trace = state.find_trace(id)?.ok_or_else(|| ApiError::not_found("trace", id))?;
+    let backend = state.retrieval.lock()?;
+    Ok(Json(replay_trace(&trace, &backend)))
+}
+
+/// Server and graph metrics in Prometheus text format
+async fn get_prometheus_metrics(State(state): State<AppState>) -> Result<([(header::HeaderName, &'static str); 1], String), ApiError> {
+    // One store at a time: each guard is dropped at the end of its own statement
+    let graphs = state.graphs.lock()?.iter().map(GraphGauges::of).collect();
+    let multi_graphs = state.multi_graphs.lock()?.iter().map(MultiGraphGauges::of).collect();
+    let recorded_traces = state.traces.lock()?.len();
+    let provenance_notes = state.provenance.lock()?.len();
+    let pending_changes = state.changes.lock()?.list(None, Some(ChangeStatus::Proposed)).len();
+    let store = StoreGauges { graphs, multi_graphs, recorded_traces, provenance_notes, pending_changes };
+    Ok(([(header::CONTENT_TYPE, crate::telemetry::CONTENT_TYPE)], render_prometheus(&state.requests, &store)))
+}
+
+async fn get_metrics(State(state): State<AppState>, Path(id): Path<Uuid>) -> Result<Json<SARSCoV2Metrics>, ApiError> {
+    let graphs = state.graphs.lock()?;
+    let graph = graphs.iter().find(|g| g.id == id).ok_or_else(|| ApiError::not_found("graph", id))?;
+    Ok(Json(SARSCoV2Metrics::compute(graph)))
+}
+
+#[derive(serde::Deserialize)]
+struct HistoryParams {
+    since: Option<chrono::DateTime<chrono::Utc>>,   // RFC 3339
+}
+
+/// Recorded metrics snapshots of a graph, oldest first (`?since=`)
+async fn get_metrics_history(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<HistoryParams>,
+) -> Result<Json<Vec<MetricsSnapshot>>, ApiError> {
+    let history = state.metrics_history.lock()?;
+    Ok(Json(history.since(id, params.since).into_iter().cloned().collect()))
+}
+
+/// A stored curve with its knee precomputed
+#[derive(serde::Serialize)]
+struct IntentCurve {
+    intent: String,
+    curve: RDCurve,
+    knee: Option<Knee>,
+}
+
+impl IntentCurve {
+    fn new(intent: String, curve: RDCurve) -> Self {
+        let knee = curve.detect_knee(&KneeOptions::default());
+        Self { intent, curve, knee }
+    }
+}
+
+/// Every intent's curve for a graph: submitted curves, plus those stored on
+/// a multi-intent graph with that ID
+async fn get_rd(State(state): State<AppState>, Path(graph_id): Path<Uuid>) -> Result<Json<Vec<IntentCurve>>, ApiError> {
+    let mut listed: Vec<IntentCurve> = state.rd_curves_of(graph_id)?.into_iter().map(|(intent, c)| IntentCurve::new(intent, c)).collect();
+    listed.sort_by(|a, b| a.intent.cmp(&b.intent));
+    Ok(Json(listed))
+}
+
+#[derive(serde::Deserialize)]
+struct RdPointsPayload {
+    points: Vec<RDPoint>,
+    #[serde(default)]
+    replace: bool,              // discard earlier points instead of merging
+}
+
+/// Merge points into a graph's curve for `intent`; points at an existing
+/// rate replace the earlier measurement
+async fn post_rd_points(
+    State(state): State<AppState>,
+    Path((graph_id, intent)): Path<(Uuid, String)>,
+    headers: HeaderMap,
+    Payload(payload): Payload<RdPointsPayload>,
+) -> Result<Json<IntentCurve>, ApiError> {
+    let points = payload.points.len();
+    let curve = {
+        let mut store = state.rd_curves.lock()?;
+        let curve = store.entry(graph_id).or_default()
+            .entry(intent.clone())
+            .or_insert_with(|| RDCurve::new(vec![]));
+        if payload.replace {
+            *curve = RDCurve::new(vec![]);
+        }
+        curve.merge(payload.points);
+        curve.clone()
+    };
+    // Keep a multi-intent graph's own copy in step
+    if state.multi_graphs.lock()?.iter().any(|g| g.id == graph_id) {
+        state.edit_graph(editor(&headers), graph_id, |g| {
+            g.add_rd_curve(intent.clone(), curve.clone());
+            Ok(((), vec![GraphEvent::RdCurveSet { intent: intent.clone(), curve: curve.clone() }]))
+        })?;
+    }
+    let rationale = format!("{} {} points for {}", if payload.replace { "Replaced with" } else { "Merged" }, points, intent);
+    state.record_provenance(ProvenanceNote::new(graph_id, Uuid::nil(), curator(&headers), "set-rd-curve").with_rationale(rationale))?;
+    state.snapshot_metrics(graph_id, SnapshotTrigger::Mutation);
+    Ok(Json(IntentCurve::new(intent, curve)))
+}
+
+#[derive(serde::Deserialize)]
+struct ThresholdsPayload {
+    virology_min: usize,
+    genomics_min: usize,
+    treatment_min: usize,
+    #[serde(default)]
+    immunology_min: usize,
+    #[serde(default)]
+    public_health_min: usize,
+}
+
+async fn post_governance_check(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Payload(payload): Payload<ThresholdsPayload>,
+) -> Result<Json<GovernanceDecision>, ApiError> {
+    let t = EvidenceThresholds {
+        virology_min: payload.virology_min,
+        genomics_min: payload.genomics_min,
+        treatment_min: payload.treatment_min,
+        immunology_min: payload.immunology_min,
+        public_health_min: payload.public_health_min,
+    };
+    let decision = state.graphs.lock()?.iter().find(|g| g.id == id)
+        .map(|graph| check_merge_allowed(graph, &t))
+        .ok_or_else(|| ApiError::not_found("graph", id))?;
+    state.audit(&headers, id, "merge_check", &decision, snapshot_hash(&t));
+    Ok(Json(decision))
+}
+
+/// Apply the loaded governance policy to a base or multi-intent graph
+async fn get_governance_check(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<Json<GovernanceDecision>, ApiError> {
+    let policy = state.governance.lock()?.clone();
+    let base = state.graphs.lock()?.iter().find(|g| g.id == id).map(|g| policy.check_graph(g));
+    let decision = match base {
+        Some(decision) => decision,
+        None => state.multi_graphs.lock()?.iter().find(|g| g.id == id)
+            .map(|g| policy.check_multi_graph(g))
+            .ok_or_else(|| ApiError::not_found("graph", id))?,
+    };
+    state.audit(&headers, id, "policy_check", &decision, snapshot_hash(&policy));
+    Ok(Json(decision))
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+enum GraphKind {
+    Base,
+    Multi,
+}
+
+#[derive(serde::Serialize)]
+struct GraphCheck {
+    graph_id: Uuid,
+    kind: GraphKind,
+    decision: GovernanceDecision,
+}
+
+#[derive(serde::Serialize)]
+struct PolicySweep {
+    policy_hash: String,
+    allowed: usize,
+    denied: usize,
+    graphs: Vec<GraphCheck>,          // base graphs, then multi-intent graphs, in store order
+}
+
+/// Apply the loaded policy to every stored graph, each on a blocking worker
+/// thread; every decision is audited as a `policy_sweep`
+async fn post_governance_check_all(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<PolicySweep>, ApiError> {
+    let policy = Arc::new(state.governance.lock()?.clone());
+    let base = state.graphs.lock()?.clone();
+    let multi = state.multi_graphs.lock()?.clone();
+
+    let mut checks = Vec::with_capacity(base.len() + multi.len());
+    for graph in base {
+        let policy = Arc::clone(&policy);
+        checks.push((graph.id, GraphKind::Base, tokio::task::spawn_blocking(move || policy.check_graph(&graph))));
+    }
+    for graph in multi {
+        let policy = Arc::clone(&policy);
+        checks.push((graph.id, GraphKind::Multi, tokio::task::spawn_blocking(move || policy.check_multi_graph(&graph))));
+    }
+
+    let policy_hash = snapshot_hash(&*policy);
+    let mut graphs = Vec::with_capacity(checks.len());
+    for (graph_id, kind, check) in checks {
+        let decision = check.await.map_err(ApiError::internal)?;
+        state.audit(&headers, graph_id, "policy_sweep", &decision, policy_hash.clone());
+        graphs.push(GraphCheck { graph_id, kind, decision });
+    }
+    let allowed = graphs.iter().filter(|g| g.decision.allowed).count();
+    tracing::info!(graphs = graphs.len(), allowed, "governance sweep finished");
+    Ok(Json(PolicySweep { policy_hash, allowed, denied: graphs.len() - allowed, graphs }))
+}
+
+/// Edge-level checks on a multi-intent graph; omitted rule fields use `EdgeRules::default()`
+async fn post_governance_edges(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Payload(rules): Payload<EdgeRules>,
+) -> Result<Json<GovernanceDecision>, ApiError> {
+    let decision = state.multi_graphs.lock()?.iter().find(|g| g.id == id)
+        .map(|g| check_edges(g, &rules))
+        .ok_or_else(|| ApiError::not_found("graph", id))?;
+    state.audit(&headers, id, "edge_check", &decision, snapshot_hash(&rules));
+    Ok(Json(decision))
+}
+
+/// Recorded governance decisions, oldest first (`?graph_id=&actor=&action=&allowed=&since=&limit=`)
+async fn get_governance_audit(
+    State(state): State<AppState>,
+    Query(q): Query<AuditQuery>,
+) -> Result<Json<Vec<AuditEntry>>, ApiError> {
+    Ok(Json(state.audit.lock()?.query(&q).into_iter().cloned().collect()))
+}
+
+async fn get_governance_policy(State(state): State<AppState>) -> Result<Json<GovernancePolicy>, ApiError> {
+    Ok(Json(state.governance.lock()?.clone()))
+}
+
+/// Replace the policy with the TOML body, or reload it from the policy file when the body is empty.
+/// While the policy sets `approver_roles`, only those roles may replace it with a body,
+/// so a caller cannot lift the restriction and then review their own proposals.
+async fn post_governance_policy(
+    State(state): State<AppState>,
+    caller: Option<Extension<Caller>>,
+    body: String,
+) -> Result<Json<GovernancePolicy>, ApiError> {
+    let policy = if body.trim().is_empty() {
+        let path = state.governance_path.as_ref()
+            .ok_or(ApiError::BadRequest("no policy file configured; send the policy as TOML".into()))?;
+        GovernancePolicy::load(path)
+    } else {
+        let roles = state.governance.lock()?.approver_roles.clone();
+        let role = reviewer_role(&caller);
+        if !is_approver(role, &roles) {
+            return Err(ApiError::Forbidden(format!("role {:?} may not replace the governance policy (allowed: {:?})", role, roles)));
+        }
+        GovernancePolicy::from_toml(&body)
+    }
+    .map_err(|e| ApiError::BadRequest(format!("{:#}", e)))?;
+
+    *state.governance.lock()? = policy.clone();
+    tracing::info!("governance policy replaced");
+    Ok(Json(policy))
+}
+
+/// Propose a node or edge addition; it is applied only once approved
+async fn post_proposal(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Payload(change): Payload<ProposedChange>,
+) -> Result<(StatusCode, Json<PendingChange>), ApiError> {
+    let unregistered = {
+        let graphs = state.multi_graphs.lock()?;
+        let graph = graphs.iter().find(|g| g.id == id).ok_or_else(|| ApiError::not_found("graph", id))?;
+        match &change {
+            ProposedChange::AddEdge(edge) => graph.check_edge_type(edge.id, SubjectKind::Edge, &edge.edge_type),
+            ProposedChange::AddNode(_) => None,
+        }
+    };
+    check_schema(match &change {
+        ProposedChange::AddNode(node) => validate_node(node),
+        ProposedChange::AddEdge(edge) => validate_edge(edge).into_iter().chain(unregistered).collect(),
+    })?;
+    let actor = header_str(&headers, "x-actor").unwrap_or("anonymous");
+    let pending = state.changes.lock()?.propose(id, change, actor)?.clone();
+    Ok((StatusCode::CREATED, Json(pending)))
+}
+
+#[derive(serde::Deserialize, Default)]
+struct ExtractPayload {
+    domain: Option<String>,           // only corpus docs in this domain
+    mesh: Option<String>,             // only docs tagged with this MeSH descriptor
+}
+
+#[derive(serde::Serialize)]
+struct ExtractionResponse {
+    report: ExtractionReport,
+    changes: Vec<PendingChange>,      // one proposal per edge not already in the graph or pending
+}
+
+/// Extract relations between the graph's nodes from the retrieval corpus and
+/// propose each as an edge, citing its documents; nothing is added until approved
+async fn post_extract_relations(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    payload: Option<Json<ExtractPayload>>,
+) -> Result<(StatusCode, Json<ExtractionResponse>), ApiError> {
+    let Json(payload) = payload.unwrap_or_default();
+    let graph = {
+        let graphs = state.multi_graphs.lock()?;
+        graphs.iter().find(|g| g.id == id).ok_or_else(|| ApiError::not_found("graph", id))?.clone()
+    };
+    let report = {
+        let backend = state.retrieval.lock()?;
+        let docs = backend.docs.iter()
+            .filter(|d| payload.domain.as_deref().is_none_or(|domain| d.domain.eq_ignore_ascii_case(domain)))
+            .filter(|d| payload.mesh.as_deref().is_none_or(|mesh| d.has_mesh(mesh)));
+        ExtractionPipeline::default().run(&graph, docs)
+    };
+    let actor = header_str(&headers, "x-actor").unwrap_or("anonymous");
+    let mut queue = state.changes.lock()?;
+    let mut changes = vec![];
+    for proposed in &report.proposed {
+        let edge = &proposed.edge;
+        let pending = queue.list(Some(id), Some(ChangeStatus::Proposed)).into_iter().any(|c| matches!(&c.change,
+            ProposedChange::AddEdge(e) if e.source_id == edge.source_id && e.target_id == edge.target_id
+                && e.edge_type == edge.edge_type && e.metadata.polarity == edge.metadata.polarity));
+        if !pending && validate_edge(edge).is_empty() {
+            changes.push(queue.propose(id, ProposedChange::AddEdge(edge.clone()), actor)?.clone());
+        }
+    }
+    Ok((StatusCode::CREATED, Json(ExtractionResponse { report, changes })))
+}
+
+#[derive(serde::Deserialize)]
+struct SuggestEdgesPayload {
+    #[serde(default = "default_top_k")]
+    top_k: usize,
+    #[serde(flatten)]
+    options: LinkPredictionOptions,
+}
+
+fn default_top_k() -> usize {
+    10
+}
+
+impl Default for SuggestEdgesPayload {
+    fn default() -> Self {
+        Self { top_k: default_top_k(), options: LinkPredictionOptions::default() }
+    }
+}
+
+#[derive(serde::Serialize)]
+struct SuggestionResponse {
+    suggestions: Vec<SuggestedEdge>,
+    changes: Vec<PendingChange>,      // one proposal per suggestion not already pending
+}
+
+/// Predict missing links and propose each as an edge; nothing is added until approved
+async fn post_suggest_edges(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    payload: Option<Json<SuggestEdgesPayload>>,
+) -> Result<(StatusCode, Json<SuggestionResponse>), ApiError> {
+    let Json(payload) = payload.unwrap_or_default();
+    let suggestions = {
+        let graphs = state.multi_graphs.lock()?;
+        let graph = graphs.iter().find(|g| g.id == id).ok_or_else(|| ApiError::not_found("graph", id))?;
+        graph.suggest_edges_with(payload.top_k, &payload.options)
+    };
+    let actor = header_str(&headers, "x-actor").unwrap_or("anonymous");
+    let mut queue = state.changes.lock()?;
+    let mut changes = vec![];
+    for suggestion in &suggestions {
+        let edge = &suggestion.edge;
+        let pending = queue.list(Some(id), Some(ChangeStatus::Proposed)).into_iter().any(|c| matches!(&c.change,
+            ProposedChange::AddEdge(e) if (e.source_id, e.target_id) == (edge.source_id, edge.target_id)
+                || (e.source_id, e.target_id) == (edge.target_id, edge.source_id)));
+        if !pending && validate_edge(edge).is_empty() {
+            changes.push(queue.propose(id, ProposedChange::AddEdge(edge.clone()), actor)?.clone());
+        }
+    }
+    Ok((StatusCode::CREATED, Json(SuggestionResponse { suggestions, changes })))
+}
+
+#[derive(serde::Deserialize)]
+struct ChangeParams {
+    graph_id: Option<Uuid>,
+    status: Option<ChangeStatus>,
+}
+
+async fn get_changes(State(state): State<AppState>, Query(params): Query<ChangeParams>) -> Result<Json<Vec<PendingChange>>, ApiError> {
+    Ok(Json(state.changes.lock()?.list(params.graph_id, params.status).into_iter().cloned().collect()))
+}
+
+impl From<ApprovalError> for ApiError {
+    fn from(e: ApprovalError) -> Self {
+        match e {
+            ApprovalError::NotFound(_) | ApprovalError::GraphMissing(_) => Self::NotFound(e.to_string()),
+            ApprovalError::Forbidden { .. } => Self::Forbidden(e.to_string()),
+            ApprovalError::NotPending { .. } | ApprovalError::DanglingEdge { .. } => Self::Conflict(e.to_string()),
+            ApprovalError::Log(_) => Self::internal(e),
+        }
+    }
+}
+
+/// Record a review in the audit log as an allowed (approve) or refused (reject) decision
+fn audit_review(state: &AppState, headers: &HeaderMap, change: &PendingChange) {
+    let decision = GovernanceDecision {
+        allowed: change.status == ChangeStatus::Approved,
+        reason: change.note.clone().unwrap_or_else(|| format!("Change {} {:?}", change.id, change.status)),
+        violations: vec![],
+        tags: vec![],
+        domains: vec![],
+    };
+    let action = if decision.allowed { "approve_change" } else { "reject_change" };
+    state.audit(headers, change.graph_id, action, &decision, snapshot_hash(&change.change));
+}
+
+/// Role of the reviewer's API key; requests outside a workspace have none
+fn reviewer_role(caller: &Option<Extension<Caller>>) -> Option<&str> {
+    caller.as_ref().and_then(|Extension(c)| c.role.as_deref())
+}
+
+/// Apply a proposed change; reviewers are named by `x-actor` and, when the
+/// policy sets `approver_roles`, must hold an API key bound to one of them
+async fn post_approve_change(
+    State(state): State<AppState>,
+    Path(change_id): Path<Uuid>,
+    caller: Option<Extension<Caller>>,
+    headers: HeaderMap,
+) -> Result<Json<PendingChange>, ApiError> {
+    let roles = state.governance.lock()?.approver_roles.clone();
+    let reviewer = Reviewer {
+        actor: header_str(&headers, "x-actor").unwrap_or("anonymous"),
+        role: reviewer_role(&caller),
+    };
+    // Like `AppState::edit_graph`, but `changes` must be locked after `multi_graphs`
+    let mut graphs = state.multi_graphs.lock()?;
+    let mut changes = state.changes.lock()?;
+    let graph_id = changes.pending_graph(change_id, &reviewer, &roles)?;
+    let graph = graphs.iter_mut().find(|g| g.id == graph_id).ok_or(ApprovalError::GraphMissing(graph_id))?;
+    let change = changes.get(change_id).ok_or(ApprovalError::NotFound(change_id))?.change.clone();
+    let mut edited = graph.clone();
+    change.apply_to(&mut edited)?;
+    // Saved before the graph changes: should the event log then fail, the
+    // change is approved but unapplied, and a retry cannot apply it twice
+    let approved = changes.mark_approved(change_id, &reviewer, &roles)?.clone();
+    let event = match change {
+        ProposedChange::AddNode(node) => GraphEvent::NodeAdded { node },
+        ProposedChange::AddEdge(edge) => GraphEvent::EdgeAdded { edge },
+    };
+    state.record_events(editor(&headers), graph_id, [event])?;
+    *graph = edited;
+    drop((changes, graphs));
+
+    state.record_provenance(
+        ProvenanceNote::new(approved.graph_id, approved.change.subject_id(), format!("proposal:{}", approved.id), approved.change.operation())
+            .with_rationale(format!("Proposed by {}, approved by {}", approved.proposed_by, reviewer.actor)),
+    )?;
+    audit_review(&state, &headers, &approved);
+    state.snapshot_metrics(approved.graph_id, SnapshotTrigger::Mutation);
+    Ok(Json(approved))
+}
+
+#[derive(serde::Deserialize, Default)]
+struct RejectPayload {
+    note: Option<String>,
+}
+
+async fn post_reject_change(
+    State(state): State<AppState>,
+    Path(change_id): Path<Uuid>,
+    caller: Option<Extension<Caller>>,
+    headers: HeaderMap,
+    payload: Option<Json<RejectPayload>>,
+) -> Result<Json<PendingChange>, ApiError> {
+    let roles = state.governance.lock()?.approver_roles.clone();
+    let reviewer = Reviewer {
+        actor: header_str(&headers, "x-actor").unwrap_or("anonymous"),
+        role: reviewer_role(&caller),
+    };
+    let note = payload.unwrap_or_default().0.note;
+    let rejected = state.changes.lock()?
+        .reject(change_id, &reviewer, &roles, note)?
+        .clone();
+
+    audit_review(&state, &headers, &rejected);
+    Ok(Json(rejected))
+}
+
+#[derive(serde::Deserialize)]
+struct TagParams {
+    label: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+struct SubjectTag {
+    subject_id: Uuid,
+    tag: GovernanceTag,
+}
+
+/// Governance tags on a multi-intent graph's nodes and edges, optionally by label
+async fn get_tags(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<TagParams>,
+) -> Result<Json<Vec<SubjectTag>>, ApiError> {
+    let graphs = state.multi_graphs.lock()?;
+    let graph = graphs.iter().find(|g| g.id == id).ok_or_else(|| ApiError::not_found("graph", id))?;
+    Ok(Json(graph.tagged(params.label.as_deref()).into_iter()
+        .map(|(subject_id, tag)| SubjectTag { subject_id, tag: tag.clone() })
+        .collect()))
+}
+
+#[derive(serde::Deserialize)]
+struct TagPayload {
+    label: String,
+    passed: bool,
+    details: Option<String>,
+}
+
+/// Attach a tag to a node or edge. The tag ID is derived from subject and
+/// label, so posting the same label again replaces the earlier tag.
+async fn post_tag(
+    State(state): State<AppState>,
+    Path((id, subject_id)): Path<(Uuid, Uuid)>,
+    headers: HeaderMap,
+    Payload(payload): Payload<TagPayload>,
+) -> Result<(StatusCode, Json<GovernanceTag>), ApiError> {
+    let tag = GovernanceTag {
+        id: Uuid::new_v5(&subject_id, payload.label.as_bytes()),
+        label: payload.label,
+        passed: payload.passed,
+        details: payload.details,
+    };
+    state.edit_graph(editor(&headers), id, |graph| {
+        if !graph.attach_tag(subject_id, tag.clone()) {
+            return Err(ApiError::NotFound(format!("no node or edge {}", subject_id)));
+        }
+        Ok(((), vec![GraphEvent::TagAttached { subject_id, tag: tag.clone() }]))
+    })?;
+    let rationale = format!("{} ({})", tag.label, if tag.passed { "passed" } else { "failed" });
+    state.record_provenance(ProvenanceNote::new(id, subject_id, curator(&headers), "tag").with_rationale(rationale))?;
+    state.snapshot_metrics(id, SnapshotTrigger::Mutation);
+    Ok((StatusCode::CREATED, Json(tag)))
+}
+
+async fn delete_tag(
+    State(state): State<AppState>,
+    Path((id, subject_id, tag_id)): Path<(Uuid, Uuid, Uuid)>,
+    headers: HeaderMap,
+) -> Result<Json<GovernanceTag>, ApiError> {
+    let tag = state.edit_graph(editor(&headers), id, |graph| {
+        let tag = graph.detach_tag(subject_id, tag_id).ok_or_else(|| ApiError::not_found("tag", tag_id))?;
+        Ok((tag, vec![GraphEvent::TagDetached { subject_id, tag_id }]))
+    })?;
+    state.record_provenance(ProvenanceNote::new(id, subject_id, curator(&headers), "untag").with_rationale(tag.label.clone()))?;
+    state.snapshot_metrics(id, SnapshotTrigger::Mutation);
+    Ok(Json(tag))
+}
+
+#[derive(serde::Deserialize)]
+struct ExportParams {
+    #[serde(default)]
+    profile: ExportProfile,
+}
+
+async fn get_export_preview(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<ExportParams>,
+) -> Result<Json<RedactionReport>, ApiError> {
+    let graphs = state.multi_graphs.lock()?;
+    let graph = graphs.iter().find(|g| g.id == id).ok_or_else(|| ApiError::not_found("graph", id))?;
+    Ok(Json(plan_redactions(graph, params.profile)))
+}
+
+#[derive(Debug, Clone, Copy, Default, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ExportFormat {
+    #[default]
+    Json,
+    Csv,
+    Cytoscape,            // Cytoscape.js elements array
+    Gexf,                 // dynamic GEXF for Gephi
+}
+
+#[derive(Debug, Clone, Copy, Default, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ExportTable {
+    #[default]
+    Nodes,
+    Edges,
+}
+
+#[derive(serde::Deserialize)]
+struct ExportDownloadParams {
+    #[serde(default)]
+    format: ExportFormat,
+    #[serde(default)]
+    table: ExportTable,   // CSV only: nodes.csv or edges.csv
+    #[serde(default)]
+    profile: ExportProfile,
+}
+
+/// The graph sanitized for `profile`, as JSON or as one CSV table
+async fn get_export(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<ExportDownloadParams>,
+) -> Result<([(header::HeaderName, &'static str); 2], Vec<u8>), ApiError> {
+    let graphs = state.multi_graphs.lock()?;
+    let graph = graphs.iter().find(|g| g.id == id).ok_or_else(|| ApiError::not_found("graph", id))?;
+    let (graph, _) = sanitize(graph, params.profile);
+    drop(graphs);
+    let internal = |e: anyhow::Error| ApiError::internal(format!("{:#}", e));
+    let (content_type, disposition, body) = match (params.format, params.table) {
+        (ExportFormat::Json, _) => ("application/json", "attachment; filename=\"graph.json\"", serde_json::to_vec(&graph).map_err(ApiError::internal)?),
+        (ExportFormat::Cytoscape, _) => ("application/json", "attachment; filename=\"graph.cyjs\"", serde_json::to_vec(&to_cytoscape(&graph)).map_err(ApiError::internal)?),
+        (ExportFormat::Gexf, _) => (GEXF_CONTENT_TYPE, "attachment; filename=\"graph.gexf\"", to_gexf(&graph).into_bytes()),
+        (ExportFormat::Csv, table) => {
+            let mut body = Vec::new();
+            match table {
+                ExportTable::Nodes => write_nodes_csv(&graph, &mut body).map_err(internal)?,
+                ExportTable::Edges => write_edges_csv(&graph, &mut body).map_err(internal)?,
+            }
+            let disposition = match table {
+                ExportTable::Nodes => "attachment; filename=\"nodes.csv\"",
+                ExportTable::Edges => "attachment; filename=\"edges.csv\"",
+            };
+            ("text/csv; charset=utf-8", disposition, body)
+        }
+    };
+    Ok(([(header::CONTENT_TYPE, content_type), (header::CONTENT_DISPOSITION, disposition)], body))
 }
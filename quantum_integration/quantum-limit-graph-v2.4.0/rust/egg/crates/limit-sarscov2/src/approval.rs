@@ -0,0 +1,250 @@
+// limit-sarscov2/src/approval.rs
+// Pending-changes queue: proposed node and edge additions wait for curator approval
+
+use serde::{Serialize, Deserialize};
+use std::{collections::HashMap, fs::{self, OpenOptions}, io::Write, path::{Path, PathBuf}};
+use anyhow::Context;
+use uuid::Uuid;
+
+use crate::{edges::GraphEdge, multi_intent_graph::{IntentNode, MultiIntentGraph}};
+
+/// A graph mutation awaiting review
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum ProposedChange {
+    AddNode(IntentNode),
+    AddEdge(GraphEdge),
+}
+
+impl ProposedChange {
+    /// ID of the node or edge the change adds
+    pub fn subject_id(&self) -> Uuid {
+        match self {
+            Self::AddNode(node) => node.id,
+            Self::AddEdge(edge) => edge.id,
+        }
+    }
+
+    pub fn operation(&self) -> &'static str {
+        match self {
+            Self::AddNode(_) => "add-node",
+            Self::AddEdge(_) => "add-edge",
+        }
+    }
+
+    /// Add the node or edge to `graph`. Edges are only added once both
+    /// endpoints are in the graph.
+    pub fn apply_to(&self, graph: &mut MultiIntentGraph) -> Result<(), ApprovalError> {
+        match self {
+            Self::AddNode(node) => graph.add_node(node.clone()),
+            Self::AddEdge(edge) => {
+                if let Some(&node) = [edge.source_id, edge.target_id].iter().find(|n| !graph.intent_nodes.contains_key(n)) {
+                    return Err(ApprovalError::DanglingEdge { edge: edge.id, node });
+                }
+                graph.add_edge(edge.clone());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeStatus {
+    Proposed,
+    Approved,
+    Rejected,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingChange {
+    pub id: Uuid,
+    pub graph_id: Uuid,
+    pub change: ProposedChange,
+    pub status: ChangeStatus,
+    pub proposed_by: String,
+    pub proposed_at: String,
+    pub reviewed_by: Option<String>,
+    pub reviewed_at: Option<String>,
+    pub note: Option<String>,         // reviewer's reason, mostly for rejections
+}
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum ApprovalError {
+    #[error("change {0} not found")]
+    NotFound(Uuid),
+    #[error("change {id} is already {status:?}")]
+    NotPending { id: Uuid, status: ChangeStatus },
+    #[error("role {role:?} may not review changes (allowed: {allowed:?})")]
+    Forbidden { role: Option<String>, allowed: Vec<String> },
+    #[error("graph {0} not found")]
+    GraphMissing(Uuid),
+    #[error("edge {edge} references node {node}, which is not in the graph")]
+    DanglingEdge { edge: Uuid, node: Uuid },
+    #[error("change log: {0}")]
+    Log(String),                      // the proposal or review could not be persisted
+}
+
+/// Reviewer identity. With a non-empty `approver_roles`, only those roles may
+/// approve or reject. The server takes the role from the reviewer's API key,
+/// never from the request.
+#[derive(Debug, Clone)]
+pub struct Reviewer<'a> {
+    pub actor: &'a str,
+    pub role: Option<&'a str>,
+}
+
+/// Whether `role` may review changes, or replace the policy naming the roles
+pub fn is_approver(role: Option<&str>, approver_roles: &[String]) -> bool {
+    approver_roles.is_empty() || role.is_some_and(|r| approver_roles.iter().any(|a| a == r))
+}
+
+/// Changes in proposal order. With a path, every proposal and review is
+/// appended to the file as the change's new state before it becomes visible;
+/// on reopening, the last line for each change wins.
+#[derive(Debug, Clone, Default)]
+pub struct ChangeQueue {
+    path: Option<PathBuf>,
+    changes: Vec<PendingChange>,
+}
+
+impl ChangeQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open (or create on first write) the JSON Lines log at `path`, restoring
+    /// earlier proposals with their latest status
+    pub fn open(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut changes: Vec<PendingChange> = vec![];
+        let mut position: HashMap<Uuid, usize> = HashMap::new();
+        if path.exists() {
+            let raw = fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?;
+            for (line_no, line) in raw.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+                let change: PendingChange = serde_json::from_str(line)
+                    .with_context(|| format!("parsing {} line {}", path.display(), line_no + 1))?;
+                match position.get(&change.id) {
+                    Some(&index) => changes[index] = change,
+                    None => {
+                        position.insert(change.id, changes.len());
+                        changes.push(change);
+                    }
+                }
+            }
+        }
+        let pending = changes.iter().filter(|c| c.status == ChangeStatus::Proposed).count();
+        tracing::info!(path = %path.display(), changes = changes.len(), pending, "change log opened");
+        Ok(Self { path: Some(path), changes })
+    }
+
+    /// Append `change` to the log, if there is one
+    fn persist(&self, change: &PendingChange) -> Result<(), ApprovalError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let line = serde_json::to_string(change).map_err(|e| ApprovalError::Log(e.to_string()))? + "\n";
+        OpenOptions::new().create(true).append(true).open(path)
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .map_err(|e| ApprovalError::Log(format!("appending to {}: {}", path.display(), e)))
+    }
+
+    pub fn propose(&mut self, graph_id: Uuid, change: ProposedChange, proposed_by: &str) -> Result<&PendingChange, ApprovalError> {
+        let change = PendingChange {
+            id: Uuid::new_v4(),
+            graph_id,
+            change,
+            status: ChangeStatus::Proposed,
+            proposed_by: proposed_by.into(),
+            proposed_at: chrono::Utc::now().to_rfc3339(),
+            reviewed_by: None,
+            reviewed_at: None,
+            note: None,
+        };
+        self.persist(&change)?;
+        tracing::info!(change = %change.id, graph = %graph_id, by = proposed_by, "change proposed");
+        self.changes.push(change);
+        Ok(self.changes.last().expect("just pushed"))
+    }
+
+    pub fn get(&self, id: Uuid) -> Option<&PendingChange> {
+        self.changes.iter().find(|c| c.id == id)
+    }
+
+    /// Changes in proposal order, optionally filtered by graph and status
+    pub fn list(&self, graph_id: Option<Uuid>, status: Option<ChangeStatus>) -> Vec<&PendingChange> {
+        self.changes.iter()
+            .filter(|c| graph_id.is_none_or(|g| c.graph_id == g))
+            .filter(|c| status.is_none_or(|s| c.status == s))
+            .collect()
+    }
+
+    /// Graph a still-pending change targets, after checking the reviewer's role
+    pub fn pending_graph(&self, id: Uuid, reviewer: &Reviewer, approver_roles: &[String]) -> Result<Uuid, ApprovalError> {
+        if !is_approver(reviewer.role, approver_roles) {
+            return Err(ApprovalError::Forbidden {
+                role: reviewer.role.map(str::to_string),
+                allowed: approver_roles.to_vec(),
+            });
+        }
+        let change = self.get(id).ok_or(ApprovalError::NotFound(id))?;
+        if change.status != ChangeStatus::Proposed {
+            return Err(ApprovalError::NotPending { id, status: change.status });
+        }
+        Ok(change.graph_id)
+    }
+
+    /// Apply a pending change to `graph` (the graph it was proposed for) and mark it approved.
+    /// Edges are only applied once both endpoints are in the graph.
+    pub fn approve(
+        &mut self,
+        id: Uuid,
+        reviewer: &Reviewer,
+        approver_roles: &[String],
+        graph: &mut MultiIntentGraph,
+    ) -> Result<&PendingChange, ApprovalError> {
+        let graph_id = self.pending_graph(id, reviewer, approver_roles)?;
+        if graph.id != graph_id {
+            return Err(ApprovalError::GraphMissing(graph_id));
+        }
+        self.get(id).ok_or(ApprovalError::NotFound(id))?.change.apply_to(graph)?;
+        self.mark_approved(id, reviewer, approver_roles)
+    }
+
+    /// Mark a pending change approved without touching any graph, for callers
+    /// that applied it themselves with `ProposedChange::apply_to`
+    pub fn mark_approved(&mut self, id: Uuid, reviewer: &Reviewer, approver_roles: &[String]) -> Result<&PendingChange, ApprovalError> {
+        self.pending_graph(id, reviewer, approver_roles)?;
+        let index = self.changes.iter().position(|c| c.id == id).ok_or(ApprovalError::NotFound(id))?;
+        let approved = self.review(index, ChangeStatus::Approved, reviewer, None)?;
+        tracing::info!(change = %id, by = reviewer.actor, "change approved");
+        Ok(approved)
+    }
+
+    /// Mark a pending change rejected; the graph is left untouched
+    pub fn reject(
+        &mut self,
+        id: Uuid,
+        reviewer: &Reviewer,
+        approver_roles: &[String],
+        note: Option<String>,
+    ) -> Result<&PendingChange, ApprovalError> {
+        self.pending_graph(id, reviewer, approver_roles)?;
+        let index = self.changes.iter().position(|c| c.id == id).ok_or(ApprovalError::NotFound(id))?;
+        let rejected = self.review(index, ChangeStatus::Rejected, reviewer, note)?;
+        tracing::info!(change = %id, by = reviewer.actor, "change rejected");
+        Ok(rejected)
+    }
+
+    /// Persist the review of change `index`, then record it in the queue
+    fn review(&mut self, index: usize, status: ChangeStatus, reviewer: &Reviewer, note: Option<String>) -> Result<&PendingChange, ApprovalError> {
+        let mut change = self.changes[index].clone();
+        change.status = status;
+        change.reviewed_by = Some(reviewer.actor.to_string());
+        change.reviewed_at = Some(chrono::Utc::now().to_rfc3339());
+        change.note = note;
+        self.persist(&change)?;
+        self.changes[index] = change;
+        Ok(&self.changes[index])
+    }
+}
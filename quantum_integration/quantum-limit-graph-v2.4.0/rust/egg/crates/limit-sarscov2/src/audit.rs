@@ -0,0 +1,124 @@
+// limit-sarscov2/src/audit.rs
+// Append-only governance audit log, persisted as JSON Lines
+
+use serde::{Serialize, Deserialize};
+use sha2::{Digest, Sha256};
+use std::{fs::{self, OpenOptions}, io::Write, path::{Path, PathBuf}};
+use anyhow::{Context, Result};
+use uuid::Uuid;
+
+use crate::governance::GovernanceDecision;
+
+/// One governance decision as it was made
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub id: Uuid,
+    pub graph_id: Uuid,
+    pub actor: String,            // caller identity, e.g. the `x-actor` header
+    pub action: String,           // "merge_check", "policy_check", "edge_check"
+    pub decision: GovernanceDecision,
+    pub policy_hash: String,      // SHA-256 of the rules applied, see `snapshot_hash`
+    pub recorded_at: String,
+}
+
+impl AuditEntry {
+    pub fn new(graph_id: Uuid, actor: &str, action: &str, decision: GovernanceDecision, policy_hash: String) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            graph_id,
+            actor: actor.into(),
+            action: action.into(),
+            decision,
+            policy_hash,
+            recorded_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// Filters for `AuditLog::query`; unset fields match everything
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuditQuery {
+    pub graph_id: Option<Uuid>,
+    pub actor: Option<String>,
+    pub action: Option<String>,
+    pub allowed: Option<bool>,
+    pub since: Option<String>,    // RFC 3339; entries recorded at or after
+    pub limit: Option<usize>,     // most recent entries only
+}
+
+/// Governance decisions in the order they were made. With a path, every
+/// entry is appended to the file before it becomes visible.
+#[derive(Debug, Clone, Default)]
+pub struct AuditLog {
+    path: Option<PathBuf>,
+    entries: Vec<AuditEntry>,
+}
+
+impl AuditLog {
+    /// Log that is lost on restart
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// Open (or create on first write) the JSON Lines log at `path`, loading earlier entries
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut entries = Vec::new();
+        if path.exists() {
+            let raw = fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?;
+            for (line_no, line) in raw.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+                let entry = serde_json::from_str(line)
+                    .with_context(|| format!("parsing {} line {}", path.display(), line_no + 1))?;
+                entries.push(entry);
+            }
+        }
+        tracing::info!(path = %path.display(), entries = entries.len(), "audit log opened");
+        Ok(Self { path: Some(path), entries })
+    }
+
+    pub fn record(&mut self, entry: AuditEntry) -> Result<()> {
+        if let Some(path) = &self.path {
+            let mut file = OpenOptions::new().create(true).append(true).open(path)
+                .with_context(|| format!("opening {}", path.display()))?;
+            writeln!(file, "{}", serde_json::to_string(&entry)?)
+                .with_context(|| format!("appending to {}", path.display()))?;
+        }
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    /// Matching entries, oldest first
+    pub fn query(&self, q: &AuditQuery) -> Vec<&AuditEntry> {
+        let matches: Vec<&AuditEntry> = self.entries.iter()
+            .filter(|e| q.graph_id.is_none_or(|id| e.graph_id == id))
+            .filter(|e| q.actor.as_ref().is_none_or(|a| &e.actor == a))
+            .filter(|e| q.action.as_ref().is_none_or(|a| &e.action == a))
+            .filter(|e| q.allowed.is_none_or(|a| e.decision.allowed == a))
+            .filter(|e| q.since.as_ref().is_none_or(|s| recorded_since(&e.recorded_at, s)))
+            .collect();
+        let skip = q.limit.map_or(0, |n| matches.len().saturating_sub(n));
+        matches.into_iter().skip(skip).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// SHA-256 (hex) of the rules' canonical JSON; object keys are sorted, so
+/// equal policies hash equally regardless of map order
+pub fn snapshot_hash<T: Serialize>(rules: &T) -> String {
+    let canonical = serde_json::to_value(rules).map(|v| v.to_string()).unwrap_or_default();
+    format!("{:x}", Sha256::digest(canonical.as_bytes()))
+}
+
+fn recorded_since(recorded_at: &str, since: &str) -> bool {
+    match (chrono::DateTime::parse_from_rfc3339(recorded_at), chrono::DateTime::parse_from_rfc3339(since)) {
+        (Ok(at), Ok(since)) => at >= since,
+        _ => recorded_at >= since,
+    }
+}
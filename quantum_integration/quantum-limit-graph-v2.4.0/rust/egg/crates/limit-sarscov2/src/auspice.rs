@@ -0,0 +1,295 @@
+// limit-sarscov2/src/auspice.rs
+// Nextstrain Auspice v2 tree JSON → lineage nodes, mutation annotations, temporal edges
+
+use serde::{Serialize, Deserialize};
+use std::{collections::{BTreeMap, HashMap}, fs, path::PathBuf};
+use uuid::Uuid;
+use anyhow::{Context, Result};
+
+use crate::{
+    domain::ResearchDomain,
+    edges::{EdgeMetadata, EdgeType, GraphEdge, Polarity},
+    multi_intent_graph::{IntentNode, MultiIntentGraph, NodeContent, NodeMetadata},
+    mutation::{Mutation, NUC},
+    nodes::GenomicsNode,
+    surveillance::lineage_node_id,
+};
+
+/// Namespace for temporal edge IDs between two lineages
+const AUSPICE_EDGE_NAMESPACE: Uuid = Uuid::from_u128(0x6c1b_2f0e_4d4a_4f5e_9a0c_c0d1_9000_0004);
+
+/// Node attributes holding the Pango lineage, in order of preference
+const LINEAGE_ATTRS: &[&str] = &["Nextclade_pango", "pango_lineage", "lineage", "clade_membership"];
+
+#[derive(Debug, Deserialize)]
+struct AuspiceJson {
+    #[serde(default)]
+    meta: AuspiceMeta,
+    tree: TreeNode,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct AuspiceMeta {
+    title: Option<String>,
+    updated: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TreeNode {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    node_attrs: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    branch_attrs: BranchAttrs,
+    #[serde(default)]
+    children: Vec<TreeNode>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct BranchAttrs {
+    #[serde(default)]
+    mutations: BTreeMap<String, Vec<String>>,   // gene (or "nuc") → ["N501Y", "H69-"]
+}
+
+impl TreeNode {
+    fn attr(&self, key: &str) -> Option<&serde_json::Value> {
+        self.node_attrs.get(key).map(|a| a.get("value").unwrap_or(a))
+    }
+
+    fn lineage(&self, attrs: &[String]) -> Option<String> {
+        attrs.iter()
+            .find_map(|k| self.attr(k).and_then(|v| v.as_str()))
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty() && !s.eq_ignore_ascii_case("unassigned"))
+    }
+
+    fn num_date(&self) -> Option<f64> {
+        self.attr("num_date").and_then(|v| v.as_f64())
+    }
+
+    fn tip_count(&self) -> usize {
+        if self.children.is_empty() { 1 } else { self.children.iter().map(|c| c.tip_count()).sum() }
+    }
+}
+
+/// A lineage as placed on the tree: the first (most basal) node carrying it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TreeLineage {
+    pub lineage: String,
+    pub node_id: Uuid,
+    pub tree_node: String,            // name of the defining tree node
+    pub date: Option<String>,         // ISO date of the defining node
+    pub tips: usize,
+    pub mutations: Vec<Mutation>,     // root-to-node mutations, reversions removed
+}
+
+/// Parent → child lineage step along the tree
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LineageTransition {
+    pub from: String,
+    pub to: String,
+    pub edge_id: Uuid,
+    pub mutations: Vec<Mutation>,     // acquired between the two defining nodes
+    pub date: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuspiceImport {
+    pub dataset: String,              // "nextstrain:<title>", recorded as evidence on nodes and edges
+    pub updated: Option<String>,
+    pub tree_nodes: usize,
+    pub lineages: Vec<TreeLineage>,
+    pub transitions: Vec<LineageTransition>,
+    pub unparsed_mutations: Vec<String>,
+}
+
+/// Reads an Auspice v2 JSON (`nextstrain export v2` output)
+#[derive(Debug, Clone)]
+pub struct AuspiceImporter {
+    pub path: PathBuf,
+    pub lineage_attrs: Vec<String>,
+    pub include_nucleotide: bool,     // annotate nodes with nuc mutations as well as amino-acid changes
+    pub intent: String,
+    pub min_tips: usize,              // lineages with fewer tips are folded into their parent
+}
+
+impl AuspiceImporter {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lineage_attrs: LINEAGE_ATTRS.iter().map(|a| a.to_string()).collect(),
+            include_nucleotide: false,
+            intent: "phylogeny".into(),
+            min_tips: 1,
+        }
+    }
+
+    /// Parse the tree into lineages and lineage-to-lineage transitions
+    pub fn read(&self) -> Result<AuspiceImport> {
+        let raw = fs::read_to_string(&self.path).with_context(|| format!("reading {}", self.path.display()))?;
+        let json: AuspiceJson = serde_json::from_str(&raw)
+            .with_context(|| format!("parsing {} as Auspice v2 JSON", self.path.display()))?;
+
+        let mut import = AuspiceImport {
+            dataset: format!("nextstrain:{}", json.meta.title.unwrap_or_else(|| {
+                self.path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default()
+            })),
+            updated: json.meta.updated,
+            ..Default::default()
+        };
+        let mut placed: HashMap<String, usize> = HashMap::new();
+        self.walk(&json.tree, None, &BTreeMap::new(), &mut placed, &mut import);
+        Ok(import)
+    }
+
+    /// Read the tree and merge it into `graph`: one Genomics node per lineage,
+    /// `Temporal` edges from parent to child lineage. Existing lineage nodes
+    /// (same name, e.g. from a surveillance import) keep their content and gain
+    /// the dataset as a source.
+    pub fn import_into(&self, graph: &mut MultiIntentGraph) -> Result<AuspiceImport> {
+        let import = self.read()?;
+        let now = chrono::Utc::now().to_rfc3339();
+
+        for l in &import.lineages {
+            if let Some(existing) = graph.intent_nodes.get_mut(&l.node_id) {
+                if !existing.metadata.sources.contains(&import.dataset) {
+                    existing.metadata.sources.push(import.dataset.clone());
+                }
+                continue;
+            }
+            graph.add_node(IntentNode {
+                id: l.node_id,
+                intent: self.intent.clone(),
+                domain: ResearchDomain::Genomics,
+                content: NodeContent::Variant(GenomicsNode {
+                    id: l.node_id,
+                    variant: l.lineage.clone(),
+                    mutations: l.mutations.clone(),
+                    unparsed_mutations: vec![],
+                }),
+                metadata: NodeMetadata {
+                    evidence_count: l.tips,
+                    confidence: 1.0,
+                    sources: vec![import.dataset.clone()],
+                    created_at: now.clone(),
+                    region: None,
+                    xrefs: vec![],
+                    embedding: None,
+                },
+            });
+        }
+
+        for t in &import.transitions {
+            graph.add_edge(GraphEdge {
+                id: t.edge_id,
+                edge_type: EdgeType::Temporal,
+                source_id: lineage_node_id(&t.from),
+                target_id: lineage_node_id(&t.to),
+                label: match &t.date {
+                    Some(d) => format!("{} → {} ({})", t.from, t.to, d),
+                    None => format!("{} → {}", t.from, t.to),
+                },
+                weight: 1.0,
+                metadata: EdgeMetadata {
+                    source_domain: "Genomics".into(),
+                    target_domain: "Genomics".into(),
+                    evidence_refs: vec![import.dataset.clone()],
+                    confidence: 1.0,
+                    created_at: now.clone(),
+                    polarity: Polarity::Supports,
+                },
+            });
+        }
+
+        tracing::info!(lineages = import.lineages.len(), transitions = import.transitions.len(), "auspice tree imported");
+        Ok(import)
+    }
+
+    fn walk(
+        &self,
+        node: &TreeNode,
+        parent: Option<usize>,                            // index into import.lineages
+        inherited: &BTreeMap<(String, u32), Mutation>,
+        placed: &mut HashMap<String, usize>,
+        import: &mut AuspiceImport,
+    ) {
+        import.tree_nodes += 1;
+        let mut mutations = inherited.clone();
+        for (gene, changes) in &node.branch_attrs.mutations {
+            if gene == NUC && !self.include_nucleotide {
+                continue;
+            }
+            for change in changes {
+                let token = format!("{}:{}", gene, change);
+                match token.parse::<Mutation>() {
+                    Ok(m) => apply_mutation(&mut mutations, m),
+                    Err(_) => import.unparsed_mutations.push(token),
+                }
+            }
+        }
+
+        let mut current = parent;
+        if let Some(lineage) = node.lineage(&self.lineage_attrs) {
+            let parent_lineage = parent.map(|p| import.lineages[p].lineage.clone());
+            let is_new = !placed.contains_key(&lineage) && parent_lineage.as_ref() != Some(&lineage);
+            if is_new && node.tip_count() >= self.min_tips {
+                let date = node.num_date().and_then(decimal_year_to_date);
+                let defining: Vec<Mutation> = mutations.values().cloned().collect();
+                if let Some(p) = parent {
+                    let acquired = defining.iter()
+                        .filter(|m| !import.lineages[p].mutations.contains(m))
+                        .cloned()
+                        .collect();
+                    import.transitions.push(LineageTransition {
+                        from: import.lineages[p].lineage.clone(),
+                        to: lineage.clone(),
+                        edge_id: Uuid::new_v5(&AUSPICE_EDGE_NAMESPACE, format!("{}>{}", import.lineages[p].lineage, lineage).as_bytes()),
+                        mutations: acquired,
+                        date: date.clone(),
+                    });
+                }
+                import.lineages.push(TreeLineage {
+                    node_id: lineage_node_id(&lineage),
+                    lineage: lineage.clone(),
+                    tree_node: node.name.clone(),
+                    date,
+                    tips: node.tip_count(),
+                    mutations: defining,
+                });
+                placed.insert(lineage, import.lineages.len() - 1);
+                current = Some(import.lineages.len() - 1);
+            } else if let Some(&i) = placed.get(&lineage) {
+                current = Some(i);
+            }
+        }
+
+        for child in &node.children {
+            self.walk(child, current, &mutations, placed, import);
+        }
+    }
+}
+
+/// Later mutations at a position replace earlier ones; a change back to the
+/// original residue is a reversion and removes it.
+fn apply_mutation(mutations: &mut BTreeMap<(String, u32), Mutation>, m: Mutation) {
+    let key = (m.gene.clone().unwrap_or_default(), m.position);
+    match mutations.remove(&key) {
+        Some(prev) if m.alternate == prev.reference && m.kind == prev.kind => {}
+        Some(prev) if m.kind == prev.kind => {
+            mutations.insert(key, Mutation { reference: prev.reference, ..m });
+        }
+        _ => {
+            mutations.insert(key, m);
+        }
+    }
+}
+
+/// Auspice `num_date` (e.g. 2021.87) → "2021-11-14"
+fn decimal_year_to_date(value: f64) -> Option<String> {
+    let year = value.floor() as i32;
+    let start = chrono::NaiveDate::from_ymd_opt(year, 1, 1)?;
+    let end = chrono::NaiveDate::from_ymd_opt(year + 1, 1, 1)?;
+    let days = ((value - year as f64) * (end - start).num_days() as f64).floor() as i64;
+    Some((start + chrono::Duration::days(days)).format("%Y-%m-%d").to_string())
+}
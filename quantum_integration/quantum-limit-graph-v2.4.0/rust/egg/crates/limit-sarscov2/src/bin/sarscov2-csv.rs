@@ -0,0 +1,56 @@
+// limit-sarscov2/src/bin/sarscov2-csv.rs
+// nodes.csv / edges.csv round-tripping for a serialized MultiIntentGraph
+//
+//   sarscov2-csv export <graph.json> <dir> [--profile public|internal]
+//   sarscov2-csv import <dir> <graph.json>
+//
+// `import` adds the rows to <graph.json> (created with an empty SARS-CoV-2 base
+// graph if missing), writes it back and prints both import summaries as JSON.
+
+use anyhow::{bail, Context, Result};
+use std::path::Path;
+
+use limit_sarscov2::{
+    csv_tables::{export_csv, import_csv},
+    domain::SarsCov2Graph,
+    export::{sanitize, ExportProfile},
+    multi_intent_graph::MultiIntentGraph,
+    nodes::VirusNode,
+};
+
+fn load(path: &Path) -> Result<MultiIntentGraph> {
+    let json = std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    serde_json::from_str(&json).with_context(|| format!("{} is not a serialized MultiIntentGraph", path.display()))
+}
+
+fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["export", graph, dir, rest @ ..] => {
+            let profile = match rest {
+                [] => ExportProfile::Public,
+                ["--profile", profile] => serde_json::from_value(serde_json::Value::String(profile.to_string()))
+                    .with_context(|| format!("unknown profile {:?}", profile))?,
+                _ => bail!("usage: sarscov2-csv export <graph.json> <dir> [--profile public|internal]"),
+            };
+            let (graph, report) = sanitize(&load(Path::new(graph))?, profile);
+            export_csv(&graph, dir)?;
+            eprintln!("exported {} nodes and {} edges to {} ({} elements removed, {} fields masked)",
+                graph.intent_nodes.len(), graph.edges.len(), dir, report.summary.elements_removed, report.summary.fields_masked);
+        }
+        ["import", dir, graph_path] => {
+            let graph_path = Path::new(graph_path);
+            let mut graph = if graph_path.exists() {
+                load(graph_path)?
+            } else {
+                let root = VirusNode { id: uuid::Uuid::new_v4(), name: "SARS-CoV-2".into(), genome_kb: 30.0 };
+                MultiIntentGraph::new(SarsCov2Graph::new(root))
+            };
+            let (nodes, edges, _) = import_csv(&mut graph, dir)?;
+            std::fs::write(graph_path, serde_json::to_string_pretty(&graph)?).with_context(|| format!("writing {}", graph_path.display()))?;
+            println!("{}", serde_json::to_string_pretty(&serde_json::json!({ "nodes": nodes, "edges": edges }))?);
+        }
+        _ => bail!("usage: sarscov2-csv export <graph.json> <dir> [--profile public|internal]\n       sarscov2-csv import <dir> <graph.json>"),
+    }
+    Ok(())
+}
@@ -0,0 +1,75 @@
+// limit-sarscov2/src/cache.rs
+// Small least-recently-used cache for repeated retrieval queries
+
+use serde::{Serialize, Deserialize};
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+/// Fixed-capacity LRU map. Recency is a monotonically increasing tick, so
+/// lookups and evictions are O(log n). A capacity of 0 disables caching.
+#[derive(Debug, Clone)]
+pub struct LruCache<K, V> {
+    capacity: usize,
+    entries: HashMap<K, (V, u64)>,
+    recency: BTreeMap<u64, K>,    // tick → key, oldest first
+    tick: u64,
+}
+
+impl<K: Clone + Eq + Hash, V> LruCache<K, V> {
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, entries: HashMap::new(), recency: BTreeMap::new(), tick: 0 }
+    }
+
+    /// Look up `key`, marking it as most recently used
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        self.tick += 1;
+        let (_, last_used) = self.entries.get_mut(key)?;
+        self.recency.remove(last_used);
+        *last_used = self.tick;
+        self.recency.insert(self.tick, key.clone());
+        self.entries.get(key).map(|(v, _)| v)
+    }
+
+    /// Insert or replace `key`, evicting the least recently used entry when full
+    pub fn put(&mut self, key: K, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.tick += 1;
+        if let Some((_, last_used)) = self.entries.remove(&key) {
+            self.recency.remove(&last_used);
+        } else if self.entries.len() >= self.capacity {
+            if let Some((_, oldest)) = self.recency.pop_first() {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.recency.insert(self.tick, key.clone());
+        self.entries.insert(key, (value, self.tick));
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub invalidations: u64,
+    pub entries: usize,
+    pub capacity: usize,
+}
@@ -0,0 +1,160 @@
+// limit-sarscov2/src/chunking.rs
+// Split long corpus documents into overlapping sentence/paragraph chunks
+
+use serde::{Serialize, Deserialize};
+use std::sync::OnceLock;
+use uuid::Uuid;
+use regex::Regex;
+
+use crate::retrieval::CorpusDoc;
+
+/// Namespace for content hashes of normalized document text
+const CONTENT_NAMESPACE: Uuid = Uuid::from_u128(0x6c1b_2f0e_4d4a_4f5e_9a0c_c0d1_9000_0005);
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ChunkStrategy {
+    Sentence,
+    #[default]
+    Paragraph,
+}
+
+/// Packs whole sentences or paragraphs into chunks of at most `max_chars`,
+/// repeating the last `overlap` units of each chunk at the start of the next.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Chunker {
+    pub strategy: ChunkStrategy,
+    pub max_chars: usize,
+    pub overlap: usize,           // sentences / paragraphs shared by neighbouring chunks
+}
+
+impl Default for Chunker {
+    fn default() -> Self {
+        Self { strategy: ChunkStrategy::Paragraph, max_chars: 1200, overlap: 1 }
+    }
+}
+
+impl Chunker {
+    pub fn sentences(max_chars: usize, overlap: usize) -> Self {
+        Self { strategy: ChunkStrategy::Sentence, max_chars, overlap }
+    }
+
+    pub fn paragraphs(max_chars: usize, overlap: usize) -> Self {
+        Self { strategy: ChunkStrategy::Paragraph, max_chars, overlap }
+    }
+
+    /// Chunks of `doc`, sharing its domain and source. Chunk IDs are derived
+    /// from the parent ID and the chunk text, so re-chunking the same document
+    /// gives the same IDs. Documents that fit in one chunk are returned as is.
+    pub fn chunk(&self, doc: &CorpusDoc) -> Vec<CorpusDoc> {
+        if doc.text.chars().count() <= self.max_chars {
+            return vec![doc.clone()];
+        }
+        self.chunk_text(&doc.text)
+            .into_iter()
+            .map(|text| CorpusDoc {
+                id: Uuid::new_v5(&doc.id, text.as_bytes()),
+                domain: doc.domain.clone(),
+                text,
+                source: doc.source.clone(),
+                mesh: doc.mesh.clone(),
+            })
+            .collect()
+    }
+
+    pub fn chunk_text(&self, text: &str) -> Vec<String> {
+        let units: Vec<String> = match self.strategy {
+            ChunkStrategy::Paragraph => split_paragraphs(text),
+            ChunkStrategy::Sentence => split_sentences(text),
+        }
+        .into_iter()
+        .flat_map(|u| split_long(&u, self.max_chars))
+        .collect();
+
+        let separator = match self.strategy {
+            ChunkStrategy::Paragraph => "\n\n",
+            ChunkStrategy::Sentence => " ",
+        };
+        let len = |units: &[String]| units.iter().map(|u| u.chars().count()).sum::<usize>()
+            + separator.len() * units.len().saturating_sub(1);
+
+        let mut chunks = vec![];
+        let mut start = 0;
+        while start < units.len() {
+            let mut end = start + 1;
+            while end < units.len() && len(&units[start..=end]) <= self.max_chars {
+                end += 1;
+            }
+            chunks.push(units[start..end].join(separator));
+            if end == units.len() {
+                break;
+            }
+            // Step back for overlap, but always make progress
+            start = end.saturating_sub(self.overlap).max(start + 1);
+        }
+        chunks
+    }
+}
+
+/// Hash of the whitespace- and case-normalized text, used to spot duplicate documents
+pub fn content_hash(text: &str) -> Uuid {
+    let normalized = text.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+    Uuid::new_v5(&CONTENT_NAMESPACE, normalized.as_bytes())
+}
+
+fn split_paragraphs(text: &str) -> Vec<String> {
+    static BLANK_LINE: OnceLock<Regex> = OnceLock::new();
+    BLANK_LINE.get_or_init(|| Regex::new(r"\n\s*\n").unwrap())
+        .split(text)
+        .map(|p| p.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|p| !p.is_empty())
+        .collect()
+}
+
+/// Sentence boundaries: terminal punctuation followed by whitespace and an
+/// uppercase letter or digit, except after common abbreviations.
+pub(crate) fn split_sentences(text: &str) -> Vec<String> {
+    const ABBREVIATIONS: &[&str] = &["al.", "e.g.", "i.e.", "fig.", "figs.", "ref.", "refs.", "vs.", "approx.", "no.", "dr.", "ca."];
+    static BOUNDARY: OnceLock<Regex> = OnceLock::new();
+    let boundary = BOUNDARY.get_or_init(|| Regex::new(r"[.!?][\)\]]?\s+[A-Z0-9(\[]").unwrap());
+
+    let flat = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mut sentences = vec![];
+    let mut start = 0;
+    for m in boundary.find_iter(&flat) {
+        // Keep the punctuation (and closing bracket) with the sentence it ends
+        let end = m.start() + m.as_str().trim_end_matches(|c: char| !c.is_whitespace()).trim_end().len();
+        let candidate = &flat[start..end];
+        let last_word = candidate.rsplit(' ').next().unwrap_or("").to_lowercase();
+        if ABBREVIATIONS.contains(&last_word.as_str()) {
+            continue;
+        }
+        sentences.push(candidate.trim().to_string());
+        start = end;
+    }
+    sentences.push(flat[start..].trim().to_string());
+    sentences.retain(|s| !s.is_empty());
+    sentences
+}
+
+/// Units longer than a chunk are cut at word boundaries
+fn split_long(unit: &str, max_chars: usize) -> Vec<String> {
+    if unit.chars().count() <= max_chars {
+        return vec![unit.to_string()];
+    }
+    let mut pieces = vec![];
+    let mut current = String::new();
+    for word in unit.split_whitespace() {
+        if !current.is_empty() && current.chars().count() + 1 + word.chars().count() > max_chars {
+            pieces.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() {
+        pieces.push(current);
+    }
+    pieces
+}
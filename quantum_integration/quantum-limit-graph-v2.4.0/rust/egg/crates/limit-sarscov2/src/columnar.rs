@@ -0,0 +1,131 @@
+// limit-sarscov2/src/columnar.rs
+// Arrow record batches and Parquet files of intent nodes and edges (feature "arrow")
+
+use anyhow::{Context, Result};
+use arrow::{
+    array::{ArrayRef, BooleanArray, DictionaryArray, Float32Array, ListBuilder, StringArray, StringBuilder, TimestampMillisecondArray, UInt64Array},
+    datatypes::{DataType, Field, Int32Type, Schema, TimeUnit},
+    record_batch::RecordBatch,
+};
+use parquet::arrow::ArrowWriter;
+use std::{fs::File, path::Path, sync::Arc};
+
+use crate::{edges::GraphEdge, multi_intent_graph::{IntentNode, MultiIntentGraph}};
+
+fn dictionary() -> DataType {
+    DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8))
+}
+
+fn string_list() -> DataType {
+    DataType::List(Arc::new(Field::new("item", DataType::Utf8, true)))
+}
+
+fn timestamp() -> DataType {
+    DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into()))
+}
+
+pub fn node_schema() -> Schema {
+    Schema::new(vec![
+        Field::new("id", DataType::Utf8, false),
+        Field::new("intent", DataType::Utf8, false),
+        Field::new("domain", dictionary(), false),
+        Field::new("label", DataType::Utf8, false),
+        Field::new("evidence_count", DataType::UInt64, false),
+        Field::new("confidence", DataType::Float32, false),
+        Field::new("sources", string_list(), false),
+        Field::new("created_at", timestamp(), true),
+    ])
+}
+
+pub fn edge_schema() -> Schema {
+    Schema::new(vec![
+        Field::new("id", DataType::Utf8, false),
+        Field::new("edge_type", dictionary(), false),
+        Field::new("source_id", DataType::Utf8, false),
+        Field::new("target_id", DataType::Utf8, false),
+        Field::new("label", DataType::Utf8, false),
+        Field::new("weight", DataType::Float32, false),
+        Field::new("confidence", DataType::Float32, false),
+        Field::new("source_domain", dictionary(), false),
+        Field::new("target_domain", dictionary(), false),
+        Field::new("evidence_refs", string_list(), false),
+        Field::new("created_at", timestamp(), true),
+        Field::new("refutes", DataType::Boolean, false),
+    ])
+}
+
+/// RFC 3339 timestamp in milliseconds; null when missing or unparseable
+fn millis(value: &str) -> Option<i64> {
+    chrono::DateTime::parse_from_rfc3339(value).ok().map(|t| t.timestamp_millis())
+}
+
+fn string_lists<'a>(lists: impl Iterator<Item = &'a Vec<String>>) -> ArrayRef {
+    let mut builder = ListBuilder::new(StringBuilder::new());
+    for list in lists {
+        for value in list {
+            builder.values().append_value(value);
+        }
+        builder.append(true);
+    }
+    Arc::new(builder.finish())
+}
+
+/// One row per intent node, ordered by ID
+pub fn nodes_batch(graph: &MultiIntentGraph) -> Result<RecordBatch> {
+    let mut nodes: Vec<&IntentNode> = graph.intent_nodes.values().collect();
+    nodes.sort_by_key(|n| n.id);
+    let domains: Vec<String> = nodes.iter().map(|n| format!("{:?}", n.domain)).collect();
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(StringArray::from_iter_values(nodes.iter().map(|n| n.id.to_string()))),
+        Arc::new(StringArray::from_iter_values(nodes.iter().map(|n| &n.intent))),
+        Arc::new(domains.iter().map(String::as_str).collect::<DictionaryArray<Int32Type>>()),
+        Arc::new(StringArray::from_iter_values(nodes.iter().map(|n| n.label()))),
+        Arc::new(UInt64Array::from_iter_values(nodes.iter().map(|n| n.metadata.evidence_count as u64))),
+        Arc::new(Float32Array::from_iter_values(nodes.iter().map(|n| n.metadata.confidence))),
+        string_lists(nodes.iter().map(|n| &n.metadata.sources)),
+        Arc::new(TimestampMillisecondArray::from(nodes.iter().map(|n| millis(&n.metadata.created_at)).collect::<Vec<_>>()).with_timezone("UTC")),
+    ];
+    RecordBatch::try_new(Arc::new(node_schema()), columns).context("building node record batch")
+}
+
+/// One row per edge, ordered by ID
+pub fn edges_batch(graph: &MultiIntentGraph) -> Result<RecordBatch> {
+    let mut edges: Vec<&GraphEdge> = graph.edges.values().collect();
+    edges.sort_by_key(|e| e.id);
+    let edge_types: Vec<String> = edges.iter().map(|e| e.edge_type.to_string()).collect();
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(StringArray::from_iter_values(edges.iter().map(|e| e.id.to_string()))),
+        Arc::new(edge_types.iter().map(String::as_str).collect::<DictionaryArray<Int32Type>>()),
+        Arc::new(StringArray::from_iter_values(edges.iter().map(|e| e.source_id.to_string()))),
+        Arc::new(StringArray::from_iter_values(edges.iter().map(|e| e.target_id.to_string()))),
+        Arc::new(StringArray::from_iter_values(edges.iter().map(|e| &e.label))),
+        Arc::new(Float32Array::from_iter_values(edges.iter().map(|e| e.weight))),
+        Arc::new(Float32Array::from_iter_values(edges.iter().map(|e| e.metadata.confidence))),
+        Arc::new(edges.iter().map(|e| e.metadata.source_domain.as_str()).collect::<DictionaryArray<Int32Type>>()),
+        Arc::new(edges.iter().map(|e| e.metadata.target_domain.as_str()).collect::<DictionaryArray<Int32Type>>()),
+        string_lists(edges.iter().map(|e| &e.metadata.evidence_refs)),
+        Arc::new(TimestampMillisecondArray::from(edges.iter().map(|e| millis(&e.metadata.created_at)).collect::<Vec<_>>()).with_timezone("UTC")),
+        Arc::new(BooleanArray::from(edges.iter().map(|e| e.is_refuting()).collect::<Vec<_>>())),
+    ];
+    RecordBatch::try_new(Arc::new(edge_schema()), columns).context("building edge record batch")
+}
+
+/// Write `batch` as a single-row-group Parquet file
+pub fn write_parquet(batch: &RecordBatch, path: impl AsRef<Path>) -> Result<()> {
+    let path = path.as_ref();
+    let file = File::create(path).with_context(|| format!("creating {}", path.display()))?;
+    let mut writer = ArrowWriter::try_new(file, batch.schema(), None)?;
+    writer.write(batch)?;
+    writer.close().with_context(|| format!("writing {}", path.display()))?;
+    Ok(())
+}
+
+/// `nodes.parquet` and `edges.parquet` in `dir`, which is created if needed
+pub fn export_parquet(graph: &MultiIntentGraph, dir: impl AsRef<Path>) -> Result<()> {
+    let dir = dir.as_ref();
+    std::fs::create_dir_all(dir).with_context(|| format!("creating {}", dir.display()))?;
+    write_parquet(&nodes_batch(graph)?, dir.join("nodes.parquet"))?;
+    write_parquet(&edges_batch(graph)?, dir.join("edges.parquet"))?;
+    tracing::info!(graph = %graph.id, dir = %dir.display(), nodes = graph.intent_nodes.len(), edges = graph.edges.len(), "parquet export written");
+    Ok(())
+}
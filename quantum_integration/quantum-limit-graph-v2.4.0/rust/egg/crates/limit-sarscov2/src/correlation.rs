@@ -0,0 +1,74 @@
+// limit-sarscov2/src/correlation.rs
+// Correlation IDs: one per API request, carried in its tracing span, error responses and provenance notes,
+// which also get the actor the request's API key is bound to
+
+use axum::{
+    extract::Request,
+    http::HeaderValue,
+    middleware::Next,
+    response::Response,
+};
+use std::sync::OnceLock;
+use tracing::Instrument;
+use uuid::Uuid;
+
+/// Header a caller may set to choose the ID, and that every response carries
+pub const HEADER: &str = "x-request-id";
+
+/// Longest caller-supplied ID kept; longer ones are replaced
+const MAX_LEN: usize = 128;
+
+/// Correlation ID of the request being served, also in its extensions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+/// What the task serving a request knows about it
+#[derive(Debug, Clone)]
+struct RequestContext {
+    id: RequestId,
+    actor: OnceLock<String>,          // set once the API key is checked
+}
+
+tokio::task_local! {
+    static CURRENT: RequestContext;
+}
+
+/// ID of the request this task is serving, if it came through `assign_request_id`
+pub fn current() -> Option<String> {
+    CURRENT.try_with(|c| c.id.0.clone()).ok()
+}
+
+/// Actor bound to the API key of the request this task is serving; `None`
+/// on routes without keys. Never taken from a request header.
+pub fn current_actor() -> Option<String> {
+    CURRENT.try_with(|c| c.actor.get().cloned()).ok().flatten()
+}
+
+/// Attribute the request this task is serving to `actor`; the first call wins
+pub fn set_actor(actor: &str) {
+    let _ = CURRENT.try_with(|c| c.actor.set(actor.to_string()));
+}
+
+/// Letters, digits and `-_.:`, so IDs are safe in logs, headers and RDF
+fn acceptable(id: &str) -> bool {
+    !id.is_empty() && id.len() <= MAX_LEN && id.chars().all(|c| c.is_ascii_alphanumeric() || "-_.:".contains(c))
+}
+
+/// Middleware: take the caller's `x-request-id` or make a fresh UUID, then
+/// serve the request inside a span carrying it and echo it in the response
+pub async fn assign_request_id(mut request: Request, next: Next) -> Response {
+    let id = request.headers().get(HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|v| acceptable(v))
+        .map(String::from)
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+    request.extensions_mut().insert(RequestId(id.clone()));
+    let span = tracing::info_span!("request", request_id = %id, method = %request.method(), path = %request.uri().path());
+    let context = RequestContext { id: RequestId(id.clone()), actor: OnceLock::new() };
+    let mut response = CURRENT.scope(context, next.run(request)).instrument(span).await;
+    if let Ok(value) = HeaderValue::from_str(&id) {
+        response.headers_mut().insert(HEADER, value);
+    }
+    response
+}
@@ -0,0 +1,148 @@
+// limit-sarscov2/src/cytoscape.rs
+// Cytoscape.js elements-array export with styling hints
+
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+
+use crate::{
+    domain::ResearchDomain,
+    edges::{GraphEdge, Polarity},
+    multi_intent_graph::{IntentNode, MultiIntentGraph},
+};
+
+/// One entry of a Cytoscape.js `elements` array. `classes` carries the
+/// styling hints (`domain-genomics intent-immune-escape`, `causal refutes`)
+/// so a stylesheet can select on them without reading `data`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CytoscapeElement {
+    pub group: CytoscapeGroup,
+    pub data: CytoscapeData,
+    pub classes: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CytoscapeGroup {
+    Nodes,
+    Edges,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CytoscapeData {
+    Node(NodeData),
+    Edge(EdgeData),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeData {
+    pub id: Uuid,
+    pub label: String,
+    pub domain: String,
+    pub intent: String,
+    pub confidence: f32,
+    pub evidence_count: usize,
+    pub color: String,            // the domain's palette color
+    pub size: f32,                // suggested diameter in px, grows with evidence
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EdgeData {
+    pub id: Uuid,
+    pub source: Uuid,
+    pub target: Uuid,
+    pub label: String,
+    pub edge_type: String,
+    pub weight: f32,
+    pub confidence: f32,
+    pub polarity: Polarity,
+    pub width: f32,               // suggested line width in px, from the weight
+}
+
+/// Palette color per domain (Tableau 10)
+pub fn domain_color(domain: &ResearchDomain) -> &'static str {
+    match domain {
+        ResearchDomain::Virology => "#1f77b4",
+        ResearchDomain::Immunology => "#2ca02c",
+        ResearchDomain::Genomics => "#9467bd",
+        ResearchDomain::Treatment => "#ff7f0e",
+        ResearchDomain::PublicHealth => "#d62728",
+    }
+}
+
+/// Lowercase, dash-separated form of a name, usable as a CSS class
+fn class_name(name: &str) -> String {
+    let mut class = String::new();
+    let mut previous = ' ';
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            // "PublicHealth" → "public-health", but "RBD" stays "rbd"
+            if c.is_ascii_uppercase() && (previous.is_ascii_lowercase() || previous.is_ascii_digit()) {
+                class.push('-');
+            }
+            class.push(c.to_ascii_lowercase());
+        } else if !class.is_empty() && !class.ends_with('-') {
+            class.push('-');
+        }
+        previous = c;
+    }
+    class.trim_end_matches('-').to_string()
+}
+
+fn node_element(node: &IntentNode) -> CytoscapeElement {
+    let domain = format!("{:?}", node.domain);
+    let mut classes = format!("domain-{}", class_name(&domain));
+    let intent = class_name(&node.intent);
+    if !intent.is_empty() {
+        classes.push_str(&format!(" intent-{}", intent));
+    }
+    CytoscapeElement {
+        group: CytoscapeGroup::Nodes,
+        classes,
+        data: CytoscapeData::Node(NodeData {
+            id: node.id,
+            label: node.label().to_string(),
+            domain,
+            intent: node.intent.clone(),
+            confidence: node.metadata.confidence,
+            evidence_count: node.metadata.evidence_count,
+            color: domain_color(&node.domain).to_string(),
+            size: 20.0 + 4.0 * (node.metadata.evidence_count as f32).sqrt().min(10.0),
+        }),
+    }
+}
+
+fn edge_element(edge: &GraphEdge) -> CytoscapeElement {
+    let polarity = match edge.metadata.polarity {
+        Polarity::Supports => "supports",
+        Polarity::Refutes => "refutes",
+    };
+    CytoscapeElement {
+        group: CytoscapeGroup::Edges,
+        classes: format!("{} {}", class_name(edge.edge_type.name()), polarity),
+        data: CytoscapeData::Edge(EdgeData {
+            id: edge.id,
+            source: edge.source_id,
+            target: edge.target_id,
+            label: edge.label.clone(),
+            edge_type: edge.edge_type.name().to_string(),
+            weight: edge.weight,
+            confidence: edge.metadata.confidence,
+            polarity: edge.metadata.polarity,
+            width: 1.0 + 4.0 * edge.weight.clamp(0.0, 1.0),
+        }),
+    }
+}
+
+/// The graph as a Cytoscape.js elements array: nodes first, then edges whose
+/// endpoints are both in the graph, each ordered by ID. Hyperedges are left
+/// out; Cytoscape has no n-ary edges.
+pub fn to_cytoscape(graph: &MultiIntentGraph) -> Vec<CytoscapeElement> {
+    let mut nodes: Vec<&IntentNode> = graph.intent_nodes.values().collect();
+    nodes.sort_by_key(|n| n.id);
+    let mut edges: Vec<&GraphEdge> = graph.edges.values()
+        .filter(|e| graph.intent_nodes.contains_key(&e.source_id) && graph.intent_nodes.contains_key(&e.target_id))
+        .collect();
+    edges.sort_by_key(|e| e.id);
+    nodes.into_iter().map(node_element).chain(edges.into_iter().map(edge_element)).collect()
+}
@@ -0,0 +1,156 @@
+// limit-sarscov2/src/decompose.rs
+// Free-text question decomposition into domain-tagged intent queries and a query plan
+
+use serde::{Serialize, Deserialize};
+use anyhow::Result;
+use uuid::Uuid;
+
+use crate::{
+    domain::ResearchDomain,
+    intents::{DetectedIntent, IntentDetector},
+    lineage::PangoLineage,
+    mutation::parse_mutations,
+    queries::{IntentQuery, MultiIntentQuestion, QueryPlan, QueryPlanner},
+};
+
+/// Therapies recognised by name (lowercase prefixes)
+const THERAPIES: [&str; 14] = [
+    "paxlovid", "nirmatrelvir", "ritonavir", "remdesivir", "molnupiravir", "ensitrelvir",
+    "dexamethasone", "tocilizumab", "baricitinib", "sotrovimab", "bebtelovimab",
+    "evusheld", "tixagevimab", "cilgavimab",
+];
+
+/// Vaccines and platforms recognised by name (lowercase prefixes)
+const VACCINES: [&str; 7] = ["mrna", "bnt162b2", "pfizer", "moderna", "novavax", "astrazeneca", "booster"];
+
+/// Named things a question is about
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct QuestionEntities {
+    pub lineages: Vec<String>,    // "BA.5", or the parent lineage of a WHO label
+    pub mutations: Vec<String>,   // "S:E484K"
+    pub therapies: Vec<String>,
+    pub vaccines: Vec<String>,
+}
+
+impl QuestionEntities {
+    pub fn extract(question: &str) -> Self {
+        let mut entities = Self {
+            mutations: parse_mutations(&question.replace(['?', '!'], " ")).iter().map(|m| m.to_string()).collect(),
+            ..Self::default()
+        };
+        for token in question.split(|c: char| c.is_whitespace() || ",;()/".contains(c)) {
+            let token = token.trim_end_matches(['.', ':', '?', '!', '\'', '"']);
+            if token.is_empty() {
+                continue;
+            }
+            let lower = token.to_lowercase();
+            if THERAPIES.iter().any(|t| lower.starts_with(t)) {
+                push_unique(&mut entities.therapies, token.to_string());
+            } else if VACCINES.iter().any(|v| lower.starts_with(v)) {
+                push_unique(&mut entities.vaccines, token.to_string());
+            } else if let Some(lineage) = PangoLineage::find_in(token) {
+                push_unique(&mut entities.lineages, lineage.to_string());
+            }
+        }
+        entities
+    }
+
+    /// Entities a query in `domain` should search for, most specific first
+    fn for_domain(&self, domain: &ResearchDomain) -> Vec<String> {
+        let lists: Vec<&Vec<String>> = match domain {
+            ResearchDomain::Genomics => vec![&self.mutations, &self.lineages],
+            ResearchDomain::Treatment => vec![&self.therapies, &self.lineages],
+            ResearchDomain::Immunology => vec![&self.vaccines, &self.lineages, &self.mutations],
+            ResearchDomain::Virology => vec![&self.mutations, &self.lineages],
+            ResearchDomain::PublicHealth => vec![&self.lineages],
+        };
+        lists.into_iter().flatten().cloned().collect()
+    }
+}
+
+fn push_unique(list: &mut Vec<String>, item: String) {
+    if !list.contains(&item) {
+        list.push(item);
+    }
+}
+
+/// Turns a question into intent queries. Implement this to plug in an LLM;
+/// `QuestionDecomposer` falls back to its rules when the hook fails or
+/// returns nothing.
+pub trait DecompositionHook: Send + Sync {
+    fn decompose(&self, question: &str, entities: &QuestionEntities) -> Result<Vec<IntentQuery>>;
+}
+
+/// A decomposed question, with the plan to answer it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Decomposition {
+    pub question: MultiIntentQuestion,
+    pub plan: QueryPlan,
+    pub entities: QuestionEntities,
+    pub decomposed_by: String,    // "rules" or the hook's name
+}
+
+/// Rule-based decomposition: intents come from the `IntentDetector`, and each
+/// intent's query text names the entities relevant to its domain, e.g.
+/// "Does BA.5 escape Paxlovid?" → Genomics "BA.5 immune escape",
+/// Treatment "Paxlovid BA.5 treatment efficacy".
+pub struct QuestionDecomposer {
+    pub detector: IntentDetector,
+    hook: Option<(String, Box<dyn DecompositionHook>)>,
+}
+
+impl Default for QuestionDecomposer {
+    fn default() -> Self {
+        Self::new(IntentDetector::default())
+    }
+}
+
+impl QuestionDecomposer {
+    pub fn new(detector: IntentDetector) -> Self {
+        Self { detector, hook: None }
+    }
+
+    /// Try `hook` before the rules
+    pub fn with_hook(mut self, name: impl Into<String>, hook: impl DecompositionHook + 'static) -> Self {
+        self.hook = Some((name.into(), Box::new(hook)));
+        self
+    }
+
+    pub fn decompose(&self, question: &str) -> Decomposition {
+        let entities = QuestionEntities::extract(question);
+        let from_hook = self.hook.as_ref().and_then(|(name, hook)| match hook.decompose(question, &entities) {
+            Ok(intents) if !intents.is_empty() => Some((name.clone(), intents)),
+            Ok(_) => None,
+            Err(e) => {
+                tracing::warn!(hook = %name, error = %format!("{:#}", e), "decomposition hook failed; using rules");
+                None
+            }
+        });
+        let (decomposed_by, intents) = from_hook.unwrap_or_else(|| ("rules".into(), self.rule_intents(question, &entities)));
+
+        let question = MultiIntentQuestion { id: Uuid::new_v4(), question: question.into(), intents };
+        let plan = QueryPlanner::plan(&question);
+        tracing::info!(question = %question.id, intents = question.intents.len(), by = %decomposed_by, "question decomposed");
+        Decomposition { question, plan, entities, decomposed_by }
+    }
+
+    fn rule_intents(&self, question: &str, entities: &QuestionEntities) -> Vec<IntentQuery> {
+        self.detector.detect(question)
+            .into_iter()
+            .map(|d| IntentQuery {
+                id: Uuid::new_v4(),
+                domain: format!("{:?}", d.domain),
+                text: sub_query(&d, entities).unwrap_or_else(|| question.into()),
+                intent: Some(d.intent),
+                confidence: Some(d.confidence),
+            })
+            .collect()
+    }
+}
+
+/// Domain entities followed by the intent's name; None when the question
+/// names nothing relevant to the domain
+fn sub_query(detected: &DetectedIntent, entities: &QuestionEntities) -> Option<String> {
+    let terms = entities.for_domain(&detected.domain);
+    (!terms.is_empty()).then(|| format!("{} {}", terms.join(" "), detected.intent.replace('_', " ")))
+}
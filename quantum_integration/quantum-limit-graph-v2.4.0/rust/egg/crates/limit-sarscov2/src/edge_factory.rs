@@ -0,0 +1,235 @@
+// limit-sarscov2/src/edge_factory.rs
+// Data-driven edge construction: relationship templates loaded from TOML instead of hardcoded builders
+
+use serde::{Serialize, Deserialize};
+use std::{collections::HashMap, fs, path::Path};
+use anyhow::{bail, Context, Result};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    domain::ResearchDomain,
+    edges::{EdgeType, GraphEdge},
+    multi_intent_graph::MultiIntentGraph,
+};
+
+fn default_edge_type() -> EdgeType {
+    EdgeType::Causal
+}
+
+/// One relationship kind: which domains it links and how its edges are labelled
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EdgeTemplate {
+    pub name: String,                     // "mutation_to_immune_escape"
+    pub source_domain: ResearchDomain,
+    pub target_domain: ResearchDomain,
+    pub label: String,                    // "{source} → immune escape"; also {target} and request vars
+    #[serde(default = "default_edge_type")]
+    pub edge_type: EdgeType,
+    pub default_strength: Option<f32>,    // used when a request gives none
+}
+
+/// `[[template]]` tables
+#[derive(Debug, Clone, Default, Deserialize)]
+struct TemplateFile {
+    #[serde(default, rename = "template")]
+    templates: Vec<EdgeTemplate>,
+}
+
+/// One edge to build from a template
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EdgeRequest {
+    pub pattern: String,                  // template name
+    pub source_id: Uuid,
+    pub target_id: Uuid,
+    pub source_name: Option<String>,      // {source}; the node's label when built into a graph
+    pub target_name: Option<String>,      // {target}; likewise
+    #[serde(default)]
+    pub evidence: Vec<String>,
+    pub strength: Option<f32>,            // confidence, or correlation for correlative edges
+    #[serde(default)]
+    pub vars: HashMap<String, String>,    // other label placeholders
+}
+
+#[derive(Debug, Error)]
+pub enum EdgeFactoryError {
+    #[error("no edge template named {0:?}")]
+    UnknownPattern(String),
+    #[error("template {pattern:?}: label needs {{{placeholder}}}, which the request does not give")]
+    MissingPlaceholder { pattern: String, placeholder: String },
+    #[error("template {pattern:?}: unclosed '{{' in label")]
+    UnclosedPlaceholder { pattern: String },
+    #[error("template {pattern:?}: no strength given and the template has no default")]
+    MissingStrength { pattern: String },
+    #[error("template {pattern:?}: node {node_id} not in graph")]
+    MissingNode { pattern: String, node_id: Uuid },
+    #[error("template {pattern:?}: node {node_id} is {found}, expected {expected}")]
+    WrongDomain { pattern: String, node_id: Uuid, expected: String, found: String },
+}
+
+/// Relationship templates by name. The default holds the patterns of the
+/// `edges::builders` functions that take no node structs.
+#[derive(Debug, Clone)]
+pub struct EdgeFactory {
+    templates: HashMap<String, EdgeTemplate>,
+}
+
+impl EdgeFactory {
+    pub fn new() -> Self {
+        Self { templates: HashMap::new() }
+    }
+
+    /// Templates from `[[template]]` tables; names must be unique and default strengths in [-1, 1]
+    pub fn from_toml(text: &str) -> Result<Self> {
+        let file: TemplateFile = toml::from_str(text).context("parsing edge templates TOML")?;
+        let mut factory = Self::new();
+        for template in file.templates {
+            if template.name.trim().is_empty() {
+                bail!("edge template with an empty name");
+            }
+            if template.label.trim().is_empty() {
+                bail!("edge template {:?} has an empty label", template.name);
+            }
+            if let Some(s) = template.default_strength.filter(|s| !(-1.0..=1.0).contains(s)) {
+                bail!("edge template {:?}: default_strength {} is outside [-1, 1]", template.name, s);
+            }
+            if factory.templates.contains_key(&template.name) {
+                bail!("edge template {:?} is defined twice", template.name);
+            }
+            factory.register(template);
+        }
+        Ok(factory)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        let factory = Self::from_toml(&text).with_context(|| format!("loading {}", path.display()))?;
+        tracing::info!(path = %path.display(), templates = factory.templates.len(), "edge templates loaded");
+        Ok(factory)
+    }
+
+    /// Add a template, replacing any with the same name
+    pub fn register(&mut self, template: EdgeTemplate) {
+        self.templates.insert(template.name.clone(), template);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&EdgeTemplate> {
+        self.templates.get(name)
+    }
+
+    /// Templates sorted by name
+    pub fn templates(&self) -> Vec<&EdgeTemplate> {
+        let mut templates: Vec<&EdgeTemplate> = self.templates.values().collect();
+        templates.sort_by(|a, b| a.name.cmp(&b.name));
+        templates
+    }
+
+    /// Edge for `request`, without checking its endpoints against a graph
+    pub fn build(&self, request: &EdgeRequest) -> Result<GraphEdge, EdgeFactoryError> {
+        let template = self.templates.get(&request.pattern)
+            .ok_or_else(|| EdgeFactoryError::UnknownPattern(request.pattern.clone()))?;
+        let strength = request.strength.or(template.default_strength)
+            .ok_or_else(|| EdgeFactoryError::MissingStrength { pattern: template.name.clone() })?;
+        let label = render_label(template, request)?;
+        let (source_domain, target_domain) = (format!("{:?}", template.source_domain), format!("{:?}", template.target_domain));
+        let (source, target, evidence) = (request.source_id, request.target_id, request.evidence.clone());
+        Ok(match template.edge_type {
+            EdgeType::Correlative => GraphEdge::new_correlative(source, target, label, source_domain, target_domain, evidence, strength),
+            ref other => GraphEdge {
+                edge_type: other.clone(),
+                ..GraphEdge::new_causal(source, target, label, source_domain, target_domain, evidence, strength)
+            },
+        })
+    }
+
+    /// Build every request against `graph` and add the edges. Endpoints must
+    /// exist in the template's domains; names default to the node labels.
+    /// Nothing is added unless every request builds.
+    pub fn add_to(&self, graph: &mut MultiIntentGraph, requests: &[EdgeRequest]) -> Result<Vec<Uuid>, EdgeFactoryError> {
+        let mut edges = Vec::with_capacity(requests.len());
+        for request in requests {
+            let template = self.templates.get(&request.pattern)
+                .ok_or_else(|| EdgeFactoryError::UnknownPattern(request.pattern.clone()))?;
+            let mut request = request.clone();
+            for (node_id, expected, name) in [
+                (request.source_id, &template.source_domain, &mut request.source_name),
+                (request.target_id, &template.target_domain, &mut request.target_name),
+            ] {
+                let node = graph.intent_nodes.get(&node_id)
+                    .ok_or_else(|| EdgeFactoryError::MissingNode { pattern: template.name.clone(), node_id })?;
+                let (expected, found) = (format!("{:?}", expected), format!("{:?}", node.domain));
+                if expected != found {
+                    return Err(EdgeFactoryError::WrongDomain { pattern: template.name.clone(), node_id, expected, found });
+                }
+                name.get_or_insert_with(|| node.label().to_string());
+            }
+            edges.push(self.build(&request)?);
+        }
+        let ids = edges.iter().map(|e| e.id).collect();
+        for edge in edges {
+            graph.add_edge(edge);
+        }
+        Ok(ids)
+    }
+}
+
+/// Fill `{source}`, `{target}` and `{var}` placeholders; `{{` and `}}` are literal braces
+fn render_label(template: &EdgeTemplate, request: &EdgeRequest) -> Result<String, EdgeFactoryError> {
+    let mut out = String::with_capacity(template.label.len());
+    let mut rest = template.label.as_str();
+    while let Some(i) = rest.find(['{', '}']) {
+        out.push_str(&rest[..i]);
+        let tail = &rest[i..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            out.push_str(&tail[..1]);
+            rest = &tail[2..];
+            continue;
+        }
+        if let Some(after) = tail.strip_prefix('}') {
+            out.push('}');
+            rest = after;
+            continue;
+        }
+        let end = tail.find('}').ok_or_else(|| EdgeFactoryError::UnclosedPlaceholder { pattern: template.name.clone() })?;
+        let placeholder = &tail[1..end];
+        let value = match placeholder {
+            "source" => request.source_name.as_deref(),
+            "target" => request.target_name.as_deref(),
+            var => request.vars.get(var).map(String::as_str),
+        };
+        out.push_str(value.ok_or_else(|| EdgeFactoryError::MissingPlaceholder {
+            pattern: template.name.clone(),
+            placeholder: placeholder.to_string(),
+        })?);
+        rest = &tail[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+impl Default for EdgeFactory {
+    /// `mutation_to_immune_escape`, `treatment_to_outcome`,
+    /// `variant_to_transmissibility` and `policy_to_transmission`, labelled as
+    /// their `edges::builders` functions label them
+    fn default() -> Self {
+        let mut factory = Self::new();
+        let defaults = [
+            ("mutation_to_immune_escape", ResearchDomain::Genomics, ResearchDomain::Immunology, "{source} → immune escape", EdgeType::Causal),
+            ("treatment_to_outcome", ResearchDomain::Treatment, ResearchDomain::PublicHealth, "{source} → reduced hospitalization", EdgeType::Correlative),
+            ("variant_to_transmissibility", ResearchDomain::Genomics, ResearchDomain::Virology, "{source} → increased transmissibility", EdgeType::Causal),
+            ("policy_to_transmission", ResearchDomain::PublicHealth, ResearchDomain::Virology, "{source} → reduced transmission", EdgeType::Correlative),
+        ];
+        for (name, source_domain, target_domain, label, edge_type) in defaults {
+            factory.register(EdgeTemplate {
+                name: name.into(),
+                source_domain,
+                target_domain,
+                label: label.into(),
+                edge_type,
+                default_strength: None,
+            });
+        }
+        factory
+    }
+}
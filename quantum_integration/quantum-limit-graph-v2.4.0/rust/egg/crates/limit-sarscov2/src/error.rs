@@ -0,0 +1,127 @@
+// limit-sarscov2/src/error.rs
+// Errors returned by the REST API, each mapped to an HTTP status and a JSON body
+
+use axum::{
+    async_trait,
+    extract::{rejection::JsonRejection, FromRequest, Request},
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use std::{fmt, sync::PoisonError};
+use thiserror::Error;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum ApiError {
+    #[error("{0}")]
+    NotFound(String),             // 404: graph, node, edge, trace or change missing
+    #[error("{0}")]
+    BadRequest(String),           // 400: unreadable query, parameter or body
+    #[error("{0}")]
+    Invalid(String),              // 422: well-formed payload that breaks a rule
+    #[error("{0}")]
+    Unauthorized(String),         // 401: missing or unknown API key
+    #[error("{0}")]
+    Forbidden(String),            // 403
+    #[error("{0}")]
+    Conflict(String),             // 409: the target is not in a state to accept the request
+    #[error("{0}")]
+    PayloadTooLarge(String),      // 413: body over the configured size limit
+    #[error("rate limit exceeded; retry in {retry_after}s")]
+    TooManyRequests { retry_after: u64 },  // 429: sent back as `Retry-After`
+    #[error("internal error (trace {trace_id})")]
+    Internal { trace_id: Uuid },  // 500: details are logged under `trace_id`, not returned
+}
+
+/// `{"error", "status"}`, plus `trace_id` for internal errors and the
+/// request's correlation ID
+#[derive(Serialize)]
+struct ErrorBody {
+    error: String,
+    status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    trace_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_id: Option<String>,
+}
+
+impl ApiError {
+    /// "graph 5f0c… not found"
+    pub fn not_found(what: &str, id: impl fmt::Display) -> Self {
+        Self::NotFound(format!("{} {} not found", what, id))
+    }
+
+    /// Log `error` under a fresh trace ID; only the ID reaches the caller
+    pub fn internal(error: impl fmt::Display) -> Self {
+        let trace_id = Uuid::new_v4();
+        tracing::error!(%trace_id, error = %error, "internal error");
+        Self::Internal { trace_id }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            Self::NotFound(_) => StatusCode::NOT_FOUND,
+            Self::BadRequest(_) => StatusCode::BAD_REQUEST,
+            Self::Invalid(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            Self::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            Self::Forbidden(_) => StatusCode::FORBIDDEN,
+            Self::Conflict(_) => StatusCode::CONFLICT,
+            Self::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::TooManyRequests { .. } => StatusCode::TOO_MANY_REQUESTS,
+            Self::Internal { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = self.status();
+        let trace_id = match &self {
+            Self::Internal { trace_id } => Some(*trace_id),
+            _ => None,
+        };
+        let body = ErrorBody { error: self.to_string(), status: status.as_u16(), trace_id, request_id: crate::correlation::current() };
+        let mut response = (status, Json(body)).into_response();
+        if let Self::TooManyRequests { retry_after } = self {
+            response.headers_mut().insert(header::RETRY_AFTER, HeaderValue::from(retry_after));
+        }
+        response
+    }
+}
+
+/// A store whose lock was poisoned by a panic in another request
+impl<T> From<PoisonError<T>> for ApiError {
+    fn from(_: PoisonError<T>) -> Self {
+        Self::internal("store lock poisoned by an earlier panic")
+    }
+}
+
+/// Malformed JSON is a 400; JSON of the wrong shape a 422; an oversized body a 413
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        match rejection.status() {
+            StatusCode::UNPROCESSABLE_ENTITY => Self::Invalid(rejection.body_text()),
+            StatusCode::PAYLOAD_TOO_LARGE => Self::PayloadTooLarge(rejection.body_text()),
+            _ => Self::BadRequest(rejection.body_text()),
+        }
+    }
+}
+
+/// JSON request body, rejected with an `ApiError` instead of axum's plain-text rejection
+pub struct Payload<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for Payload<T>
+where
+    Json<T>: FromRequest<S, Rejection = JsonRejection>,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, ApiError> {
+        let Json(value) = Json::<T>::from_request(req, state).await?;
+        Ok(Self(value))
+    }
+}
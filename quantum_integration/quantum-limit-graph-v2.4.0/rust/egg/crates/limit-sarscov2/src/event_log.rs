@@ -0,0 +1,246 @@
+// limit-sarscov2/src/event_log.rs
+// Append-only log of multi-intent graph mutations, persisted as JSON Lines
+
+use serde::{Serialize, Deserialize};
+use std::{collections::{hash_map::Entry, HashMap, HashSet}, fs::{self, OpenOptions}, io::Write, path::{Path, PathBuf}};
+use anyhow::{Context, Result};
+use uuid::Uuid;
+
+use crate::events::{EventError, GraphEvent};
+use crate::multi_intent_graph::MultiIntentGraph;
+
+/// Undo and redo of earlier operations on the same graph
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HistoryMarker {
+    Undone { operations: Vec<Uuid> },   // left out of replay from here on; most recent first
+    Redone { operations: Vec<Uuid> },   // put back
+}
+
+/// What a log entry records: a graph mutation or an undo/redo
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum LogEntry {
+    Mutation(Box<GraphEvent>),
+    History(HistoryMarker),
+}
+
+/// One graph mutation as it was made
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    pub seq: u64,                 // position in the whole log, from 1
+    pub graph_id: Uuid,
+    pub operation: Uuid,          // shared by the events of one request; undone together
+    pub actor: String,            // caller identity, e.g. the `x-actor` header
+    #[serde(default)]
+    pub session: String,          // editing session, e.g. the `x-session` header
+    pub recorded_at: String,
+    #[serde(flatten)]
+    pub entry: LogEntry,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum HistoryError {
+    #[error("nothing to {action} in session {session:?}")]
+    Nothing { action: &'static str, session: String },
+    #[error("cannot {action}: a later event no longer applies ({source})")]
+    Conflict { action: &'static str, source: EventError },
+    #[error(transparent)]
+    Persist(#[from] anyhow::Error),
+}
+
+/// A session's undoable operations, oldest first, and the ones it undid,
+/// the next to redo last
+#[derive(Debug, Default)]
+struct SessionHistory {
+    undo: Vec<Uuid>,
+    redo: Vec<Uuid>,
+}
+
+/// Mutations of every multi-intent graph in the order they were made. With a
+/// path, every event is appended to the file before it becomes visible, and
+/// the graphs can be rebuilt from it on restart.
+#[derive(Debug, Clone, Default)]
+pub struct EventLog {
+    path: Option<PathBuf>,
+    events: Vec<RecordedEvent>,
+}
+
+impl EventLog {
+    /// Log that is lost on restart
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// Open (or create on first write) the JSON Lines log at `path`, loading earlier events
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut events = Vec::new();
+        if path.exists() {
+            let raw = fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?;
+            for (line_no, line) in raw.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+                let event = serde_json::from_str(line)
+                    .with_context(|| format!("parsing {} line {}", path.display(), line_no + 1))?;
+                events.push(event);
+            }
+        }
+        tracing::info!(path = %path.display(), events = events.len(), "graph event log opened");
+        Ok(Self { path: Some(path), events })
+    }
+
+    /// Append the events of one operation on graph `graph_id`, returning the operation ID
+    pub fn record(&mut self, graph_id: Uuid, actor: &str, session: &str, events: impl IntoIterator<Item = GraphEvent>) -> Result<Uuid> {
+        let operation = Uuid::new_v4();
+        self.append(graph_id, operation, actor, session, events.into_iter().map(|e| LogEntry::Mutation(Box::new(e))))?;
+        Ok(operation)
+    }
+
+    fn append(&mut self, graph_id: Uuid, operation: Uuid, actor: &str, session: &str, entries: impl IntoIterator<Item = LogEntry>) -> Result<()> {
+        let next = self.events.last().map_or(1, |e| e.seq + 1);
+        let recorded_at = chrono::Utc::now().to_rfc3339();
+        let recorded: Vec<RecordedEvent> = entries.into_iter().enumerate()
+            .map(|(i, entry)| RecordedEvent {
+                seq: next + i as u64,
+                graph_id,
+                operation,
+                actor: actor.into(),
+                session: session.into(),
+                recorded_at: recorded_at.clone(),
+                entry,
+            })
+            .collect();
+        if let Some(path) = &self.path {
+            // One write for the whole operation, so a failure cannot leave half of it in the file
+            let mut lines = String::new();
+            for event in &recorded {
+                lines.push_str(&serde_json::to_string(event)?);
+                lines.push('\n');
+            }
+            let mut file = OpenOptions::new().create(true).append(true).open(path)
+                .with_context(|| format!("opening {}", path.display()))?;
+            file.write_all(lines.as_bytes())
+                .with_context(|| format!("appending to {}", path.display()))?;
+        }
+        self.events.extend(recorded);
+        Ok(())
+    }
+
+    /// Events of graph `graph_id` after sequence number `since` (all if `None`), oldest first
+    pub fn for_graph(&self, graph_id: Uuid, since: Option<u64>) -> Vec<&RecordedEvent> {
+        self.events.iter()
+            .filter(|e| e.graph_id == graph_id && since.is_none_or(|s| e.seq > s))
+            .collect()
+    }
+
+    /// Graph `graph_id` as it was after event `until` (the latest if `None`)
+    pub fn replay(&self, graph_id: Uuid, until: Option<u64>) -> Result<MultiIntentGraph, EventError> {
+        self.replay_with(graph_id, until, None)
+    }
+
+    /// Replay, leaving out undone operations as if `pending` had been recorded last
+    fn replay_with(&self, graph_id: Uuid, until: Option<u64>, pending: Option<&HistoryMarker>) -> Result<MultiIntentGraph, EventError> {
+        let events: Vec<&RecordedEvent> = self.events.iter()
+            .filter(|e| e.graph_id == graph_id && until.is_none_or(|u| e.seq <= u))
+            .collect();
+        let mut undone = HashSet::new();
+        let markers = events.iter().filter_map(|e| match &e.entry {
+            LogEntry::History(marker) => Some(marker),
+            LogEntry::Mutation(_) => None,
+        });
+        for marker in markers.chain(pending) {
+            match marker {
+                HistoryMarker::Undone { operations } => undone.extend(operations.iter().copied()),
+                HistoryMarker::Redone { operations } => undone.retain(|op| !operations.contains(op)),
+            }
+        }
+        let mutations = events.iter()
+            .filter(|e| !undone.contains(&e.operation))
+            .filter_map(|e| match &e.entry {
+                LogEntry::Mutation(event) => Some(&**event),
+                LogEntry::History(_) => None,
+            });
+        GraphEvent::replay(graph_id, mutations)
+    }
+
+    fn session_history(&self, graph_id: Uuid, session: &str) -> SessionHistory {
+        let mut history = SessionHistory::default();
+        let mut first_seq: HashMap<Uuid, u64> = HashMap::new();
+        for e in self.events.iter().filter(|e| e.graph_id == graph_id && e.session == session) {
+            match &e.entry {
+                LogEntry::Mutation(event) if matches!(**event, GraphEvent::GraphCreated { .. }) => {}
+                LogEntry::Mutation(_) => {
+                    if let Entry::Vacant(first) = first_seq.entry(e.operation) {
+                        first.insert(e.seq);
+                        history.undo.push(e.operation);
+                        history.redo.clear();
+                    }
+                }
+                LogEntry::History(HistoryMarker::Undone { operations }) => {
+                    history.undo.retain(|op| !operations.contains(op));
+                    history.redo.extend(operations);
+                }
+                LogEntry::History(HistoryMarker::Redone { operations }) => {
+                    history.redo.retain(|op| !operations.contains(op));
+                    history.undo.extend(operations);
+                    history.undo.sort_by_key(|op| first_seq.get(op).copied().unwrap_or(0));
+                }
+            }
+        }
+        history
+    }
+
+    /// Undo the last `steps` operations `session` made on graph `graph_id`.
+    /// Returns the graph rebuilt without them and their events.
+    pub fn undo(&mut self, graph_id: Uuid, actor: &str, session: &str, steps: usize) -> Result<(MultiIntentGraph, Vec<RecordedEvent>), HistoryError> {
+        let history = self.session_history(graph_id, session);
+        let operations: Vec<Uuid> = history.undo.iter().rev().take(steps).copied().collect();
+        self.apply_marker(graph_id, actor, session, "undo", HistoryMarker::Undone { operations })
+    }
+
+    /// Redo the last `steps` operations `session` undid on graph `graph_id`,
+    /// unless it has made other changes since
+    pub fn redo(&mut self, graph_id: Uuid, actor: &str, session: &str, steps: usize) -> Result<(MultiIntentGraph, Vec<RecordedEvent>), HistoryError> {
+        let history = self.session_history(graph_id, session);
+        let operations: Vec<Uuid> = history.redo.iter().rev().take(steps).copied().collect();
+        self.apply_marker(graph_id, actor, session, "redo", HistoryMarker::Redone { operations })
+    }
+
+    fn apply_marker(&mut self, graph_id: Uuid, actor: &str, session: &str, action: &'static str, marker: HistoryMarker) -> Result<(MultiIntentGraph, Vec<RecordedEvent>), HistoryError> {
+        let (HistoryMarker::Undone { operations } | HistoryMarker::Redone { operations }) = &marker;
+        if operations.is_empty() {
+            return Err(HistoryError::Nothing { action, session: session.into() });
+        }
+        let graph = self.replay_with(graph_id, None, Some(&marker))
+            .map_err(|source| HistoryError::Conflict { action, source })?;
+        let affected: Vec<RecordedEvent> = self.events.iter()
+            .filter(|e| e.graph_id == graph_id && operations.contains(&e.operation))
+            .cloned()
+            .collect();
+        self.append(graph_id, Uuid::new_v4(), actor, session, [LogEntry::History(marker)])?;
+        Ok((graph, affected))
+    }
+
+    /// Every graph in the log, rebuilt in the order they were created. A
+    /// graph whose history does not replay is logged and left out.
+    pub fn graphs(&self) -> Vec<MultiIntentGraph> {
+        let mut seen = HashSet::new();
+        let ids: Vec<Uuid> = self.events.iter().map(|e| e.graph_id).filter(|id| seen.insert(*id)).collect();
+        ids.into_iter()
+            .filter_map(|id| match self.replay(id, None) {
+                Ok(graph) => Some(graph),
+                Err(e) => {
+                    tracing::warn!(graph = %id, error = %e, "graph history does not replay; skipped");
+                    None
+                }
+            })
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
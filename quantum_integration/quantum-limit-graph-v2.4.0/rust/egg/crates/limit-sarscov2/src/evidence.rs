@@ -0,0 +1,281 @@
+// limit-sarscov2/src/evidence.rs
+// Resolve edge evidence references (DOIs, PubMed IDs) against Europe PMC
+
+use serde::{Serialize, Deserialize};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::OnceLock;
+use uuid::Uuid;
+use regex::Regex;
+use anyhow::{Context, Result};
+
+use crate::{
+    multi_intent_graph::MultiIntentGraph,
+    mesh::MeshTagger,
+    provenance::ProvenanceNote,
+    retrieval::{CorpusDoc, RetrievalBackend},
+};
+
+pub const EUROPE_PMC_URL: &str = "https://www.ebi.ac.uk/europepmc/webservices/rest";
+
+/// Namespace for evidence document IDs, so resolving a reference twice yields the same UUID
+const EVIDENCE_NAMESPACE: Uuid = Uuid::from_u128(0x6c1b_2f0e_4d4a_4f5e_9a0c_c0d1_9000_0002);
+
+/// A reference Europe PMC can look up
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum EvidenceId {
+    Doi(String),      // lowercased, without "doi:" or resolver prefix
+    Pmid(String),
+}
+
+impl EvidenceId {
+    /// Parse "doi:10.x/y", "10.x/y", "https://doi.org/10.x/y" or "pmid:123"
+    pub fn parse(reference: &str) -> Option<Self> {
+        let r = reference.trim();
+        let lower = r.to_ascii_lowercase();
+        for prefix in ["doi:", "https://doi.org/", "http://doi.org/", "https://dx.doi.org/", "http://dx.doi.org/"] {
+            if let Some(doi) = lower.strip_prefix(prefix) {
+                return doi.trim().starts_with("10.").then(|| EvidenceId::Doi(doi.trim().into()));
+            }
+        }
+        if lower.starts_with("10.") && lower.contains('/') {
+            return Some(EvidenceId::Doi(lower));
+        }
+        let pmid = lower.strip_prefix("pmid:")?.trim();
+        (!pmid.is_empty() && pmid.chars().all(|c| c.is_ascii_digit())).then(|| EvidenceId::Pmid(pmid.into()))
+    }
+
+    /// Canonical reference string, as stored in `CorpusDoc.source`
+    pub fn reference(&self) -> String {
+        match self {
+            EvidenceId::Doi(doi) => format!("doi:{}", doi),
+            EvidenceId::Pmid(pmid) => format!("pmid:{}", pmid),
+        }
+    }
+
+    fn query(&self) -> String {
+        match self {
+            EvidenceId::Doi(doi) => format!("DOI:\"{}\"", doi),
+            EvidenceId::Pmid(pmid) => format!("EXT_ID:{} AND SRC:MED", pmid),
+        }
+    }
+}
+
+/// Bibliographic record returned by Europe PMC
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvidenceRecord {
+    pub reference: String,        // canonical "doi:" / "pmid:" reference
+    pub title: String,
+    pub abstract_text: String,
+    pub journal: Option<String>,
+    pub year: Option<String>,
+    pub pmid: Option<String>,
+    pub pmcid: Option<String>,
+    pub doi: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    #[serde(rename = "resultList")]
+    result_list: ResultList,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResultList {
+    #[serde(default)]
+    result: Vec<SearchResult>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchResult {
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    abstract_text: String,
+    pmid: Option<String>,
+    pmcid: Option<String>,
+    doi: Option<String>,
+    pub_year: Option<String>,
+    journal_info: Option<JournalInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JournalInfo {
+    journal: Option<Journal>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Journal {
+    title: Option<String>,
+}
+
+/// Async client for the Europe PMC REST search API (which also covers PubMed)
+#[derive(Debug, Clone)]
+pub struct EuropePmcClient {
+    http: reqwest::Client,
+    base_url: String,
+}
+
+impl Default for EuropePmcClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EuropePmcClient {
+    pub fn new() -> Self {
+        Self::with_base_url(EUROPE_PMC_URL)
+    }
+
+    /// Point the client at a mirror or a local stub
+    pub fn with_base_url(base_url: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::builder()
+                .user_agent(concat!("limit-sarscov2/", env!("CARGO_PKG_VERSION")))
+                .timeout(std::time::Duration::from_secs(30))
+                .build()
+                .expect("static reqwest client configuration"),
+            base_url: base_url.into().trim_end_matches('/').into(),
+        }
+    }
+
+    /// Look up a single reference; `Ok(None)` if Europe PMC has no record of it
+    pub async fn fetch(&self, id: &EvidenceId) -> Result<Option<EvidenceRecord>> {
+        let response: SearchResponse = self.http
+            .get(format!("{}/search", self.base_url))
+            .query(&[
+                ("query", id.query().as_str()),
+                ("resultType", "core"),
+                ("format", "json"),
+                ("pageSize", "1"),
+            ])
+            .send()
+            .await
+            .with_context(|| format!("requesting {}", id.reference()))?
+            .error_for_status()
+            .with_context(|| format!("Europe PMC rejected {}", id.reference()))?
+            .json()
+            .await
+            .with_context(|| format!("decoding Europe PMC response for {}", id.reference()))?;
+
+        Ok(response.result_list.result.into_iter().next().map(|r| EvidenceRecord {
+            reference: id.reference(),
+            title: strip_markup(&r.title),
+            abstract_text: strip_markup(&r.abstract_text),
+            journal: r.journal_info.and_then(|j| j.journal).and_then(|j| j.title),
+            year: r.pub_year,
+            pmid: r.pmid,
+            pmcid: r.pmcid,
+            doi: r.doi,
+        }))
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ResolveReport {
+    pub references_seen: usize,
+    pub resolved: usize,
+    pub already_loaded: usize,
+    pub not_found: Vec<String>,
+    pub unsupported: Vec<String>,   // references that are neither DOIs nor PubMed IDs
+    pub errors: Vec<String>,
+}
+
+/// Fetch every DOI / PubMed ID cited in the graph's edge evidence and load the
+/// papers into `backend` as `CorpusDoc`s, with one provenance note per paper
+/// and citing edge, keyed to that edge.
+///
+/// Each document takes the source domain of the first edge (by id) citing it.
+/// References already present in the backend are not fetched again.
+pub async fn resolve_graph_evidence(
+    client: &EuropePmcClient,
+    graph: &MultiIntentGraph,
+    backend: &mut RetrievalBackend,
+) -> Result<(ResolveReport, Vec<ProvenanceNote>)> {
+    let mut report = ResolveReport::default();
+    let mut cited_by: BTreeMap<EvidenceId, (String, Vec<Uuid>)> = BTreeMap::new();
+    let mut unsupported = HashSet::new();
+
+    let mut edges: Vec<_> = graph.edges.values().collect();
+    edges.sort_by_key(|e| e.id);
+    for edge in edges {
+        for reference in &edge.metadata.evidence_refs {
+            match EvidenceId::parse(reference) {
+                Some(id) => cited_by.entry(id)
+                    .or_insert_with(|| (edge.metadata.source_domain.clone(), vec![]))
+                    .1
+                    .push(edge.id),
+                None => if unsupported.insert(reference.clone()) {
+                    report.unsupported.push(reference.clone());
+                },
+            }
+        }
+    }
+    report.references_seen = cited_by.len() + report.unsupported.len();
+
+    let loaded: HashSet<String> = backend.docs.iter()
+        .filter_map(|d| EvidenceId::parse(&d.source))
+        .map(|id| id.reference())
+        .collect();
+
+    let tagger = MeshTagger::default();
+    let mut docs = vec![];
+    let mut notes = vec![];
+    for (id, (domain, edge_ids)) in cited_by {
+        let reference = id.reference();
+        if loaded.contains(&reference) {
+            report.already_loaded += 1;
+            continue;
+        }
+        let record = match client.fetch(&id).await {
+            Ok(Some(record)) => record,
+            Ok(None) => {
+                report.not_found.push(reference);
+                continue;
+            }
+            Err(e) => {
+                report.errors.push(format!("{:#}", e));
+                continue;
+            }
+        };
+
+        let text = [record.title.as_str(), record.abstract_text.as_str()]
+            .iter()
+            .filter(|s| !s.is_empty())
+            .cloned()
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        docs.push(CorpusDoc {
+            id: Uuid::new_v5(&EVIDENCE_NAMESPACE, reference.as_bytes()),
+            domain,
+            mesh: tagger.tag(&text),
+            text,
+            source: reference.clone(),
+        });
+        let rationale = format!(
+            "Europe PMC: \"{}\"{}",
+            record.title,
+            record.journal.as_deref().map(|j| format!(" ({})", j)).unwrap_or_default(),
+        );
+        notes.extend(edge_ids.iter().map(|&edge_id| {
+            ProvenanceNote::new(graph.id, edge_id, reference.clone(), "resolve-evidence").with_rationale(rationale.clone())
+        }));
+    }
+
+    report.resolved = docs.len();
+    backend.extend(docs);
+    tracing::info!(resolved = report.resolved, not_found = report.not_found.len(), "edge evidence resolved");
+    Ok((report, notes))
+}
+
+/// Europe PMC titles and abstracts carry inline HTML (<i>, <sup>, <h4>)
+fn strip_markup(text: &str) -> String {
+    static INLINE: OnceLock<Regex> = OnceLock::new();
+    static BLOCK: OnceLock<Regex> = OnceLock::new();
+    let inline = INLINE.get_or_init(|| Regex::new(r"(?i)</?(i|b|em|strong|sup|sub|span)\b[^>]*>").unwrap());
+    let block = BLOCK.get_or_init(|| Regex::new(r"</?[a-zA-Z][^>]*>").unwrap());
+    block.replace_all(&inline.replace_all(text, ""), " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,214 @@
+// limit-sarscov2/src/executor.rs
+// Executes a multi-intent question against the retrieval backend and builds the resulting subgraph
+
+use serde::{Serialize, Deserialize};
+use std::collections::HashSet;
+use anyhow::{bail, Result};
+use uuid::Uuid;
+
+use crate::{
+    domain::{ResearchDomain, SarsCov2Graph},
+    multi_intent_graph::{IntentNode, MultiIntentGraph, NodeContent, NodeMetadata},
+    provenance::ProvenanceNote,
+    queries::{IntentQuery, MultiIntentQuestion, QueryPlan, QueryPlanner},
+    retrieval::{CorpusDoc, RetrievalBackend},
+    serendipity_trace::{HypothesisType, SerendipityTrace, StepBuilder},
+};
+
+/// Words never used on their own as search terms
+const STOPWORDS: [&str; 24] = [
+    "a", "an", "and", "are", "by", "does", "do", "for", "from", "how", "in", "is",
+    "it", "of", "on", "or", "the", "to", "what", "which", "who", "why", "with", "against",
+];
+
+/// What one intent query retrieved
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntentOutcome {
+    pub query_id: Uuid,
+    pub domain: ResearchDomain,
+    pub terms: Vec<String>,       // terms that matched at least one doc
+    pub node_ids: Vec<Uuid>,
+}
+
+/// Result of executing a question: the populated subgraph, its trace and provenance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryExecution {
+    pub question_id: Uuid,
+    pub plan: QueryPlan,
+    pub outcomes: Vec<IntentOutcome>,
+    pub graph: MultiIntentGraph,          // carries the trace in `serendipity_traces`
+    pub provenance: Vec<ProvenanceNote>,  // one "add" note per node, sourced from its doc
+}
+
+/// Runs each `IntentQuery` of a question through the backend's `*_from`
+/// constructors. The query text is tried verbatim first, then word by word,
+/// so a question like "Does BA.5 escape Paxlovid?" still finds "BA.5" docs.
+pub struct QueryExecutor<'a> {
+    pub backend: &'a RetrievalBackend,
+    pub max_nodes_per_intent: usize,
+}
+
+impl<'a> QueryExecutor<'a> {
+    pub fn new(backend: &'a RetrievalBackend) -> Self {
+        Self { backend, max_nodes_per_intent: 20 }
+    }
+
+    /// Execute `question` into a new graph over `base_graph`. Intents are run in
+    /// plan order (highest confidence first); each becomes one trace step.
+    pub fn execute(&self, question: &MultiIntentQuestion, session_id: &str, base_graph: SarsCov2Graph) -> Result<QueryExecution> {
+        let plan = QueryPlanner::plan(question);
+        let mut queries: Vec<&IntentQuery> = question.intents.iter().collect();
+        queries.sort_by(|a, b| b.confidence.unwrap_or(0.0).total_cmp(&a.confidence.unwrap_or(0.0)));
+
+        let mut graph = MultiIntentGraph::new(base_graph);
+        let mut trace = SerendipityTrace::new(session_id.into(), question.question.clone());
+        let mut outcomes = vec![];
+        let mut provenance = vec![];
+
+        for (i, query) in queries.into_iter().enumerate() {
+            let Some(domain) = parse_domain(&query.domain) else {
+                bail!("intent query {} has unknown domain {:?}", query.id, query.domain);
+            };
+            let intent = query.intent.clone().unwrap_or_else(|| format!("{:?}", domain).to_lowercase());
+            let mut outcome = IntentOutcome { query_id: query.id, domain: domain.clone(), terms: vec![], node_ids: vec![] };
+            let mut seen_docs = HashSet::new();
+            let mut sources = vec![];
+
+            for (t, term) in search_terms(&query.text).into_iter().enumerate() {
+                let hits = self.retrieve(&domain, &term)?;
+                let mut matched = false;
+                for (doc, content) in hits {
+                    if outcome.node_ids.len() >= self.max_nodes_per_intent || !seen_docs.insert(doc.id) {
+                        continue;
+                    }
+                    matched = true;
+                    let node = retrieved_node(&intent, &domain, content.clone(), doc, query.confidence.unwrap_or(0.5));
+                    provenance.push(
+                        ProvenanceNote::new(graph.id, node.id, doc.source.clone(), "add")
+                            .with_rationale(format!("Retrieved for intent '{}' by term '{}'", intent, term)),
+                    );
+                    add_to_base(&mut graph.base_graph, content);
+                    sources.push(doc.source.clone());
+                    outcome.node_ids.push(node.id);
+                    graph.add_node(node);
+                }
+                if matched {
+                    outcome.terms.push(term);
+                    // A verbatim match of the whole text is specific enough on its own
+                    if t == 0 {
+                        break;
+                    }
+                }
+            }
+
+            sources.sort();
+            sources.dedup();
+            trace.add_step(
+                StepBuilder::new(i + 1, hypothesis_for(query.intent.as_deref(), &domain), query.text.clone())
+                    .domains(vec![format!("{:?}", domain)])
+                    .evidence(outcome.node_ids.len())
+                    .confidence(query.confidence.unwrap_or(0.0))
+                    .evidence_refs(sources)
+                    .build(),
+            );
+            outcomes.push(outcome);
+        }
+
+        graph.add_trace(trace);
+        tracing::info!(
+            question = %question.id,
+            intents = outcomes.len(),
+            nodes = graph.intent_nodes.len(),
+            "question executed"
+        );
+        Ok(QueryExecution { question_id: question.id, plan, outcomes, graph, provenance })
+    }
+
+    /// Docs matching `term` in `domain`, paired with the node content the
+    /// backend's constructor built for each (both come from the same search)
+    pub(crate) fn retrieve(&self, domain: &ResearchDomain, term: &str) -> Result<Vec<(&'a CorpusDoc, NodeContent)>> {
+        let b = self.backend;
+        let (docs, contents): (Vec<&CorpusDoc>, Vec<NodeContent>) = match domain {
+            ResearchDomain::Virology => (b.keyword_search("Virology", term), b.virology_from(term)?.into_iter().map(NodeContent::Biology).collect()),
+            ResearchDomain::Immunology => (b.keyword_search("Immunology", term), b.immunology_from(term)?.into_iter().map(NodeContent::Immunology).collect()),
+            ResearchDomain::Genomics => (b.keyword_search("Genomics", term), b.genomics_from(term)?.into_iter().map(NodeContent::Variant).collect()),
+            ResearchDomain::Treatment => (b.keyword_search("Treatment", term), b.treatment_from(term)?.into_iter().map(NodeContent::Treatment).collect()),
+            ResearchDomain::PublicHealth => (b.keyword_search("PublicHealth", term), b.public_health_from(term)?.into_iter().map(NodeContent::PublicHealth).collect()),
+        };
+        Ok(docs.into_iter().zip(contents).collect())
+    }
+}
+
+/// "Virology", "public_health", "PublicHealth", ... (case-insensitive)
+pub(crate) fn parse_domain(domain: &str) -> Option<ResearchDomain> {
+    match domain.trim().to_ascii_lowercase().replace(['_', ' ', '-'], "").as_str() {
+        "virology" => Some(ResearchDomain::Virology),
+        "immunology" => Some(ResearchDomain::Immunology),
+        "genomics" => Some(ResearchDomain::Genomics),
+        "treatment" => Some(ResearchDomain::Treatment),
+        "publichealth" => Some(ResearchDomain::PublicHealth),
+        _ => None,
+    }
+}
+
+/// The whole text, then each word (punctuation trimmed, stopwords and
+/// one-letter words dropped) in order of appearance
+pub(crate) fn search_terms(text: &str) -> Vec<String> {
+    let mut terms = vec![text.trim().to_string()];
+    for word in text.split_whitespace() {
+        let word = word.trim_matches(|c: char| !c.is_alphanumeric());
+        if word.chars().count() < 2 || STOPWORDS.contains(&word.to_lowercase().as_str()) || terms.iter().any(|t| t == word) {
+            continue;
+        }
+        terms.push(word.to_string());
+    }
+    terms
+}
+
+/// Hypothesis type for a canonical intent, or the domain's default when none was detected
+pub(crate) fn hypothesis_for(intent: Option<&str>, domain: &ResearchDomain) -> HypothesisType {
+    match intent {
+        Some("treatment_efficacy") => HypothesisType::TreatmentResponse,
+        Some("transmission_reduction") => HypothesisType::PublicHealthImpact,
+        Some(intent) => HypothesisType::custom(intent),
+        None => match domain {
+            ResearchDomain::Virology => HypothesisType::Transmissibility,
+            ResearchDomain::Immunology => HypothesisType::VaccineEfficacy,
+            ResearchDomain::Genomics => HypothesisType::ImmuneEscape,
+            ResearchDomain::Treatment => HypothesisType::TreatmentResponse,
+            ResearchDomain::PublicHealth => HypothesisType::PublicHealthImpact,
+        },
+    }
+}
+
+/// Node for a retrieved doc, citing it as its single source
+pub(crate) fn retrieved_node(intent: &str, domain: &ResearchDomain, content: NodeContent, doc: &CorpusDoc, confidence: f32) -> IntentNode {
+    IntentNode {
+        id: Uuid::new_v4(),
+        intent: intent.to_string(),
+        domain: domain.clone(),
+        content,
+        metadata: NodeMetadata {
+            evidence_count: 1,
+            confidence,
+            sources: vec![doc.source.clone()],
+            created_at: chrono::Utc::now().to_rfc3339(),
+            region: None,
+            xrefs: vec![],
+            embedding: None,
+        },
+    }
+}
+
+pub(crate) fn add_to_base(base: &mut SarsCov2Graph, content: NodeContent) {
+    match content {
+        NodeContent::Biology(n) => base.add_virology(n),
+        NodeContent::Immunology(n) => base.add_immunology(n),
+        NodeContent::Variant(n) => base.add_genomics(n),
+        NodeContent::Treatment(n) => base.add_treatment(n),
+        NodeContent::PublicHealth(n) => base.add_public_health(n),
+        NodeContent::ClinicalTrial(n) => base.add_clinical_trial(n),
+        NodeContent::HostFactor(n) => base.add_host_factor(n),
+        NodeContent::Vaccine(n) => base.add_vaccine(n),
+    }
+}
@@ -0,0 +1,246 @@
+// limit-sarscov2/src/explorer.rs
+// Automated exploration agent: scheduler-driven retrieval loop that records its own serendipity trace
+
+use serde::{Serialize, Deserialize};
+use std::collections::{HashMap, HashSet};
+use anyhow::{bail, Result};
+use uuid::Uuid;
+
+use crate::{
+    domain::{ResearchDomain, SarsCov2Graph},
+    edges::GraphEdge,
+    executor::{add_to_base, hypothesis_for, parse_domain, retrieved_node, search_terms, QueryExecutor},
+    multi_intent_graph::{rd_curve_key, MultiIntentGraph},
+    provenance::ProvenanceNote,
+    rd::RDCurve,
+    retrieval::RetrievalBackend,
+    scheduler::{BudgetDecision, ExplorationScheduler, ScheduleTarget},
+    serendipity_trace::{SerendipityTrace, StepBuilder},
+};
+
+/// Confidence of a node found by exploration, and of the edge linking it to its seed
+const EXPLORED_CONFIDENCE: f32 = 0.5;
+const CO_RETRIEVAL_CONFIDENCE: f32 = 0.3;
+
+/// When the agent stops; fields left out of JSON keep their defaults
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExplorationBudget {
+    pub max_steps: usize,
+    pub max_nodes: usize,
+    pub max_nodes_per_step: usize,
+    pub max_dry_steps: usize,     // consecutive steps without new evidence
+}
+
+impl Default for ExplorationBudget {
+    fn default() -> Self {
+        Self { max_steps: 10, max_nodes: 50, max_nodes_per_step: 5, max_dry_steps: 3 }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StopReason {
+    StepBudget,
+    NodeBudget,
+    Exhausted,        // `max_dry_steps` steps in a row found nothing new
+    NoTargets,        // the scheduler had nothing to schedule
+    KneeReached,      // every target's intent is past its R-D knee
+}
+
+/// One target per domain, each pursuing that domain's default hypothesis
+pub fn default_targets() -> Vec<ScheduleTarget> {
+    [
+        ("transmissibility", "Virology"),
+        ("vaccine_efficacy", "Immunology"),
+        ("immune_escape", "Genomics"),
+        ("treatment_response", "Treatment"),
+        ("public_health_impact", "PublicHealth"),
+    ]
+    .into_iter()
+    .map(|(intent, domain)| ScheduleTarget { intent: intent.into(), domain: domain.into() })
+    .collect()
+}
+
+/// Result of an exploration: the graph it built, its provenance and why it stopped
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Exploration {
+    pub graph: MultiIntentGraph,          // carries the trace in `serendipity_traces`
+    pub provenance: Vec<ProvenanceNote>,  // one "explore" note per node
+    pub stop: StopReason,
+}
+
+/// Explores the corpus around a question without a human choosing the steps.
+/// Each step the scheduler picks a target (intent + domain), which fixes the
+/// hypothesis; the query is the label of the newest node not yet explored
+/// from (the question itself at first or once every node was used), searched
+/// in the target's domain. New docs become nodes linked to that seed node by
+/// a weak correlative edge, and the step goes into the trace with the
+/// scheduler's decision, until the budget runs out. A step's confidence is
+/// the share of its node allowance it filled.
+///
+/// With R-D curves attached, an intent is also dropped from the schedule once
+/// the documents it retrieved reach the part of its curve where each further
+/// document reduces distortion less than at the knee; every such check lands
+/// in the trace's `budget_decisions`.
+pub struct ExplorationAgent<'a> {
+    pub backend: &'a RetrievalBackend,
+    pub scheduler: ExplorationScheduler,
+    pub budget: ExplorationBudget,
+    rd_curves: HashMap<String, RDCurve>,  // keyed by intent or `rd_curve_key(intent, domain)`
+}
+
+impl<'a> ExplorationAgent<'a> {
+    pub fn new(backend: &'a RetrievalBackend, scheduler: ExplorationScheduler) -> Self {
+        Self { backend, scheduler, budget: ExplorationBudget::default(), rd_curves: HashMap::new() }
+    }
+
+    /// Curves for R-D budgeting, keyed like `MultiIntentGraph::rd_curves`; a
+    /// per-domain curve wins over the intent's own
+    pub fn with_rd_curves(mut self, curves: HashMap<String, RDCurve>) -> Self {
+        self.rd_curves = curves;
+        self
+    }
+
+    pub fn with_budget(mut self, budget: ExplorationBudget) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    pub fn explore(&mut self, question: &str, session_id: &str, base_graph: SarsCov2Graph) -> Result<Exploration> {
+        let executor = QueryExecutor::new(self.backend);
+        let mut graph = MultiIntentGraph::new(base_graph);
+        let mut trace = SerendipityTrace::new(session_id.into(), question.into());
+        let mut provenance = vec![];
+        let mut seen_docs = HashSet::new();
+        let mut frontier: Vec<Uuid> = vec![];     // nodes not yet used as a seed, oldest first
+        let mut dry_steps = 0;
+        let mut rates: HashMap<String, f32> = HashMap::new();   // docs retrieved per intent
+
+        let stop = loop {
+            if trace.steps.len() >= self.budget.max_steps {
+                break StopReason::StepBudget;
+            }
+            if graph.intent_nodes.len() >= self.budget.max_nodes {
+                break StopReason::NodeBudget;
+            }
+            if dry_steps >= self.budget.max_dry_steps {
+                break StopReason::Exhausted;
+            }
+            let Some(decision) = self.scheduler.schedule(&mut trace) else {
+                let targets = self.scheduler.targets();
+                break if !targets.is_empty() && targets.iter().all(|t| self.scheduler.is_exhausted(&t.intent)) {
+                    StopReason::KneeReached
+                } else {
+                    StopReason::NoTargets
+                };
+            };
+            let Some(domain) = parse_domain(&decision.domain) else {
+                bail!("schedule target {:?} has unknown domain {:?}", decision.intent, decision.domain);
+            };
+
+            let seed = frontier.pop();
+            let query = match seed {
+                Some(id) => graph.intent_nodes[&id].label().to_string(),
+                None => question.to_string(),
+            };
+            let room = self.budget.max_nodes_per_step.min(self.budget.max_nodes - graph.intent_nodes.len());
+            let mut added = vec![];
+            let mut sources = vec![];
+
+            for (t, term) in search_terms(&query).into_iter().enumerate() {
+                let before = added.len();
+                for (doc, content) in executor.retrieve(&domain, &term)? {
+                    if added.len() >= room || !seen_docs.insert(doc.id) {
+                        continue;
+                    }
+                    let node = retrieved_node(&decision.intent, &domain, content.clone(), doc, EXPLORED_CONFIDENCE);
+                    provenance.push(
+                        ProvenanceNote::new(graph.id, node.id, doc.source.clone(), "explore")
+                            .with_rationale(format!("Step {}: '{}' in {:?} for intent '{}' ({})", decision.step_number, term, domain, decision.intent, decision.reason)),
+                    );
+                    let edge = seed.and_then(|id| graph.intent_nodes.get(&id)).map(|seed| GraphEdge::new_correlative(
+                        seed.id,
+                        node.id,
+                        format!("{} co-retrieved with {}", seed.label(), node.label()),
+                        format!("{:?}", seed.domain),
+                        format!("{:?}", node.domain),
+                        vec![doc.source.clone()],
+                        CO_RETRIEVAL_CONFIDENCE,
+                    ));
+                    add_to_base(&mut graph.base_graph, content);
+                    sources.push(doc.source.clone());
+                    added.push(node.id);
+                    graph.add_node(node);
+                    if let Some(edge) = edge {
+                        graph.add_edge(edge);
+                    }
+                }
+                // A verbatim match of the whole query is specific enough on its own
+                if t == 0 && added.len() > before {
+                    break;
+                }
+            }
+
+            sources.sort();
+            sources.dedup();
+            let found = added.len();
+            trace.add_step(
+                StepBuilder::new(decision.step_number, hypothesis_for(Some(&decision.intent), &domain), query)
+                    .domains(vec![format!("{:?}", domain)])
+                    .evidence(found)
+                    .confidence(if room == 0 { 0.0 } else { found as f32 / room as f32 })
+                    .evidence_refs(sources)
+                    .build(),
+            );
+            frontier.extend(added);
+            dry_steps = if found == 0 { dry_steps + 1 } else { 0 };
+
+            let rate = rates.entry(decision.intent.clone()).or_insert(0.0);
+            *rate += found as f32;
+            if let Some(check) = self.check_budget(decision.step_number, &decision.intent, &domain, *rate) {
+                if check.exhausted {
+                    self.scheduler.exhaust(&check.intent);
+                }
+                trace.record_budget(check);
+            }
+        };
+
+        tracing::info!(
+            graph = %graph.id,
+            steps = trace.steps.len(),
+            nodes = graph.intent_nodes.len(),
+            stop = ?stop,
+            "exploration finished"
+        );
+        graph.add_trace(trace);
+        Ok(Exploration { graph, provenance, stop })
+    }
+    /// Compare the intent's marginal gain at `rate` with its curve's knee;
+    /// `None` without a curve or a knee
+    fn check_budget(&self, step_number: usize, intent: &str, domain: &ResearchDomain, rate: f32) -> Option<BudgetDecision> {
+        let curve = self.rd_curves.get(&rd_curve_key(intent, domain)).or_else(|| self.rd_curves.get(intent))?;
+        let knee = curve.knee()?;
+        let knee_gain = curve.knee_gain()?;
+        let marginal_gain = curve.marginal_gain(rate);
+        let (exhausted, reason) = match marginal_gain {
+            Some(gain) if gain < knee_gain => (true, format!(
+                "{:.3} distortion per document at rate {:.0}, below {:.3} at the knee (rate {:.1}); switching hypothesis",
+                gain, rate, knee_gain, knee.rate,
+            )),
+            Some(gain) => (false, format!("{:.3} distortion per document at rate {:.0}; knee gain {:.3}", gain, rate, knee_gain)),
+            None => (true, format!("rate {:.0} is past the measured curve; switching hypothesis", rate)),
+        };
+        Some(BudgetDecision {
+            step_number,
+            intent: intent.to_string(),
+            rate,
+            marginal_gain,
+            knee_rate: knee.rate,
+            knee_gain,
+            exhausted,
+            reason,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        })
+    }
+}
@@ -0,0 +1,229 @@
+// limit-sarscov2/src/export.rs
+// Export sanitizer: redaction rules applied before releasing a graph
+
+use serde::{Serialize, Deserialize};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::multi_intent_graph::MultiIntentGraph;
+
+/// Audience an export is prepared for
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportProfile {
+    #[default]
+    Public,       // withhold unpublished evidence, agent session details and elements failing governance
+    Internal,     // full graph, nothing redacted
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ElementKind {
+    Node,
+    Edge,
+    HyperEdge,
+    HypothesisPath,
+    Trace,
+}
+
+/// Element dropped from the export entirely
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemovedElement {
+    pub kind: ElementKind,
+    pub id: Uuid,
+    pub reason: String,
+}
+
+/// Field blanked on an element that is otherwise kept
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaskedField {
+    pub kind: ElementKind,
+    pub id: Uuid,
+    pub field: String,
+}
+
+/// Evidence reference stripped from a node or edge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WithheldEvidence {
+    pub kind: ElementKind,
+    pub id: Uuid,
+    pub reference: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedactionSummary {
+    pub elements_removed: usize,
+    pub fields_masked: usize,
+    pub evidence_withheld: usize,
+}
+
+/// Everything the sanitizer will change for a given profile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedactionReport {
+    pub graph_id: Uuid,
+    pub profile: ExportProfile,
+    pub summary: RedactionSummary,
+    pub removed: Vec<RemovedElement>,
+    pub masked: Vec<MaskedField>,
+    pub withheld: Vec<WithheldEvidence>,
+}
+
+/// Plan the redactions for `profile` without touching the graph
+pub fn plan_redactions(graph: &MultiIntentGraph, profile: ExportProfile) -> RedactionReport {
+    let mut removed = vec![];
+    let mut masked = vec![];
+    let mut withheld = vec![];
+
+    if profile == ExportProfile::Public {
+        // Sorted so repeated previews of the same graph diff cleanly
+        let mut nodes: Vec<_> = graph.intent_nodes.values().collect();
+        nodes.sort_by_key(|n| n.id);
+        let mut edges: Vec<_> = graph.edges.values().collect();
+        edges.sort_by_key(|e| e.id);
+
+        let failed_tag = |id: Uuid| graph.tags_for(id).iter()
+            .find(|t| !t.passed)
+            .map(|t| format!("Failed governance tag \"{}\"", t.label));
+
+        let mut removed_nodes = HashSet::new();
+        for node in nodes {
+            if let Some(reason) = failed_tag(node.id) {
+                removed_nodes.insert(node.id);
+                removed.push(RemovedElement { kind: ElementKind::Node, id: node.id, reason });
+                continue;
+            }
+            for r in node.metadata.sources.iter().filter(|r| !is_public_reference(r)) {
+                withheld.push(WithheldEvidence { kind: ElementKind::Node, id: node.id, reference: r.clone() });
+            }
+        }
+
+        let mut removed_edges = HashSet::new();
+        for edge in edges {
+            let blocked = failed_tag(edge.id).or_else(|| {
+                (removed_nodes.contains(&edge.source_id) || removed_nodes.contains(&edge.target_id))
+                    .then(|| "Connects a removed node".to_string())
+            });
+            if let Some(reason) = blocked {
+                removed_edges.insert(edge.id);
+                removed.push(RemovedElement { kind: ElementKind::Edge, id: edge.id, reason });
+                continue;
+            }
+            let (public, private): (Vec<&String>, Vec<&String>) = edge.metadata.evidence_refs
+                .iter()
+                .partition(|r| is_public_reference(r));
+            for r in private {
+                withheld.push(WithheldEvidence { kind: ElementKind::Edge, id: edge.id, reference: r.clone() });
+            }
+            if public.is_empty() {
+                removed_edges.insert(edge.id);
+                removed.push(RemovedElement {
+                    kind: ElementKind::Edge,
+                    id: edge.id,
+                    reason: "No publicly resolvable evidence".into(),
+                });
+            }
+        }
+
+        let mut hyperedges: Vec<_> = graph.hyperedges.values().collect();
+        hyperedges.sort_by_key(|h| h.id);
+        for hyperedge in hyperedges {
+            let blocked = failed_tag(hyperedge.id).or_else(|| {
+                hyperedge.endpoints().any(|n| removed_nodes.contains(n))
+                    .then(|| "Connects a removed node".to_string())
+            });
+            if let Some(reason) = blocked {
+                removed_edges.insert(hyperedge.id);
+                removed.push(RemovedElement { kind: ElementKind::HyperEdge, id: hyperedge.id, reason });
+                continue;
+            }
+            let (public, private): (Vec<&String>, Vec<&String>) = hyperedge.evidence_refs
+                .iter()
+                .partition(|r| is_public_reference(r));
+            for r in private {
+                withheld.push(WithheldEvidence { kind: ElementKind::HyperEdge, id: hyperedge.id, reference: r.clone() });
+            }
+            if public.is_empty() {
+                removed_edges.insert(hyperedge.id);
+                removed.push(RemovedElement {
+                    kind: ElementKind::HyperEdge,
+                    id: hyperedge.id,
+                    reason: "No publicly resolvable evidence".into(),
+                });
+            }
+        }
+
+        for path in &graph.hypothesis_paths {
+            let reason = if path.node_sequence.iter().any(|n| removed_nodes.contains(n)) {
+                "Depends on a removed node"
+            } else if path.edge_sequence.iter().any(|e| removed_edges.contains(e)) {
+                "Depends on a removed edge"
+            } else {
+                continue;
+            };
+            removed.push(RemovedElement { kind: ElementKind::HypothesisPath, id: path.id, reason: reason.into() });
+        }
+
+        for trace in &graph.serendipity_traces {
+            masked.push(MaskedField { kind: ElementKind::Trace, id: trace.id, field: "session_id".into() });
+        }
+    }
+
+    RedactionReport {
+        graph_id: graph.id,
+        profile,
+        summary: RedactionSummary {
+            elements_removed: removed.len(),
+            fields_masked: masked.len(),
+            evidence_withheld: withheld.len(),
+        },
+        removed,
+        masked,
+        withheld,
+    }
+}
+
+/// Produce the export for `profile` along with the report of what was redacted
+pub fn sanitize(graph: &MultiIntentGraph, profile: ExportProfile) -> (MultiIntentGraph, RedactionReport) {
+    let report = plan_redactions(graph, profile);
+    let mut out = graph.clone();
+
+    let removed: HashSet<Uuid> = report.removed.iter().map(|r| r.id).collect();
+    out.intent_nodes.retain(|id, _| !removed.contains(id));
+    out.edges.retain(|id, _| !removed.contains(id));
+    out.hyperedges.retain(|id, _| !removed.contains(id));
+    out.hypothesis_paths.retain(|p| !removed.contains(&p.id));
+    out.governance_tags.retain(|id, _| !removed.contains(id));
+    out.metadata.total_nodes = out.intent_nodes.len();
+    out.metadata.total_edges = out.edges.len();
+
+    for w in &report.withheld {
+        match w.kind {
+            ElementKind::Node => if let Some(n) = out.intent_nodes.get_mut(&w.id) {
+                n.metadata.sources.retain(|r| *r != w.reference);
+            },
+            ElementKind::Edge => if let Some(e) = out.edges.get_mut(&w.id) {
+                e.metadata.evidence_refs.retain(|r| *r != w.reference);
+            },
+            ElementKind::HyperEdge => if let Some(h) = out.hyperedges.get_mut(&w.id) {
+                h.evidence_refs.retain(|r| *r != w.reference);
+            },
+            _ => {}
+        }
+    }
+
+    for m in &report.masked {
+        if m.kind == ElementKind::Trace && m.field == "session_id" {
+            if let Some(t) = out.serendipity_traces.iter_mut().find(|t| t.id == m.id) {
+                t.session_id = "redacted".into();
+            }
+        }
+    }
+
+    (out, report)
+}
+
+/// Published, resolvable references: DOIs, PubMed IDs and URLs.
+/// Anything else (lab reports, internal dataset handles) stays in-house.
+pub fn is_public_reference(reference: &str) -> bool {
+    let r = reference.trim().to_ascii_lowercase();
+    ["doi:", "10.", "pmid:", "pmc", "https://", "http://"].iter().any(|p| r.starts_with(p))
+}
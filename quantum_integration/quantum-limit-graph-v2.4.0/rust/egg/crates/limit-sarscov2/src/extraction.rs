@@ -0,0 +1,249 @@
+// limit-sarscov2/src/extraction.rs
+// Relation extraction: corpus statements ("Paxlovid reduces hospitalization") → proposed edges
+
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use anyhow::Result;
+
+use crate::{
+    chunking::split_sentences,
+    edges::{EdgeType, GraphEdge, Polarity},
+    multi_intent_graph::MultiIntentGraph,
+    retrieval::CorpusDoc,
+};
+
+/// Most words between two node mentions for a cue to join them
+pub const MAX_GAP_WORDS: usize = 8;
+
+/// Relation cues: (phrase, edge type). Matched as whole words between two mentions.
+const CUES: &[(&str, EdgeType)] = &[
+    ("reduces", EdgeType::Causal), ("reduced", EdgeType::Causal), ("reduce", EdgeType::Causal),
+    ("decreases", EdgeType::Causal), ("decreased", EdgeType::Causal), ("lowers", EdgeType::Causal), ("lowered", EdgeType::Causal),
+    ("increases", EdgeType::Causal), ("increased", EdgeType::Causal), ("increase", EdgeType::Causal),
+    ("enhances", EdgeType::Causal), ("enhanced", EdgeType::Causal), ("improves", EdgeType::Causal), ("improved", EdgeType::Causal),
+    ("causes", EdgeType::Causal), ("caused", EdgeType::Causal), ("induces", EdgeType::Causal), ("induced", EdgeType::Causal),
+    ("inhibits", EdgeType::Causal), ("inhibited", EdgeType::Causal), ("blocks", EdgeType::Causal), ("blocked", EdgeType::Causal),
+    ("prevents", EdgeType::Causal), ("prevented", EdgeType::Causal), ("drives", EdgeType::Causal), ("confers", EdgeType::Causal),
+    ("escapes", EdgeType::Causal), ("escaped", EdgeType::Causal), ("evades", EdgeType::Causal), ("evaded", EdgeType::Causal),
+    ("effect on", EdgeType::Causal),
+    ("associated with", EdgeType::Correlative), ("correlates with", EdgeType::Correlative), ("correlated with", EdgeType::Correlative),
+    ("linked to", EdgeType::Correlative),
+];
+
+/// Words before a cue that turn the statement into evidence against it
+const NEGATIONS: &[&str] = &["not", "no", "never", "neither", "nor", "without", "failed"];
+/// Words that make a statement tentative
+const HEDGES: &[&str] = &["may", "might", "could", "possibly", "potentially", "suggest", "suggests", "appear", "appears"];
+
+/// A relation between two graph nodes stated in a document
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtractedRelation {
+    pub source_id: Uuid,
+    pub target_id: Uuid,
+    pub edge_type: EdgeType,
+    pub polarity: Polarity,           // `Refutes` for negated statements
+    pub cue: String,                  // "reduces"
+    pub sentence: String,
+    pub confidence: f32,
+}
+
+/// Finds relations between the graph's nodes in a document. Implement this to
+/// plug in a model-backed extractor next to (or instead of) the patterns.
+pub trait RelationExtractor: Send + Sync {
+    fn extract(&self, doc: &CorpusDoc, graph: &MultiIntentGraph) -> Result<Vec<ExtractedRelation>>;
+}
+
+/// Rule-based extractor: within one sentence, two node mentions (labels or
+/// their synonyms, longest first) joined by a cue from `CUES` at most
+/// `MAX_GAP_WORDS` words apart give a relation from the first to the second.
+/// A negation before the cue makes it refuting; a hedge lowers its confidence.
+#[derive(Debug, Clone)]
+pub struct PatternExtractor {
+    pub confidence: f32,              // of a plain statement
+    pub hedged_confidence: f32,
+}
+
+impl Default for PatternExtractor {
+    fn default() -> Self {
+        Self { confidence: 0.5, hedged_confidence: 0.3 }
+    }
+}
+
+impl RelationExtractor for PatternExtractor {
+    fn extract(&self, doc: &CorpusDoc, graph: &MultiIntentGraph) -> Result<Vec<ExtractedRelation>> {
+        let names: Vec<(Uuid, String)> = graph.intent_nodes.values()
+            .flat_map(|n| std::iter::once(n.label().to_string()).chain(graph.synonyms.equivalents(n.label())).map(move |name| (n.id, name)))
+            .map(|(id, name)| (id, name.trim().to_lowercase()))
+            .filter(|(_, name)| !name.is_empty())
+            .collect();
+
+        let mut relations = vec![];
+        for sentence in split_sentences(&doc.text) {
+            let lower = sentence.to_lowercase();
+            let mentions = mentions(&lower, &names);
+            for (i, a) in mentions.iter().enumerate() {
+                for b in mentions[i + 1..].iter().filter(|b| b.2 != a.2) {
+                    let gap = &lower[a.1..b.0];
+                    let words: Vec<&str> = gap.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()).collect();
+                    if words.len() > MAX_GAP_WORDS {
+                        break;
+                    }
+                    let Some((cue, edge_type, at)) = find_cue(&words) else { continue };
+                    let hedged = words.iter().any(|w| HEDGES.contains(w));
+                    relations.push(ExtractedRelation {
+                        source_id: a.2,
+                        target_id: b.2,
+                        edge_type,
+                        polarity: if words[..at].iter().any(|w| NEGATIONS.contains(w)) { Polarity::Refutes } else { Polarity::Supports },
+                        cue,
+                        sentence: sentence.clone(),
+                        confidence: if hedged { self.hedged_confidence } else { self.confidence },
+                    });
+                }
+            }
+        }
+        Ok(relations)
+    }
+}
+
+/// Non-overlapping whole-word mentions `(start, end, node)` in `text`, in
+/// order; a longer name wins over one inside it
+fn mentions(text: &str, names: &[(Uuid, String)]) -> Vec<(usize, usize, Uuid)> {
+    let mut found: Vec<(usize, usize, Uuid)> = vec![];
+    for (id, name) in names {
+        for (start, _) in text.match_indices(name.as_str()) {
+            let end = start + name.len();
+            let before = text[..start].chars().next_back();
+            let after = text[end..].chars().next();
+            if !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric) {
+                found.push((start, end, *id));
+            }
+        }
+    }
+    found.sort_by(|a, b| (b.1 - b.0).cmp(&(a.1 - a.0)).then(a.0.cmp(&b.0)));
+    let mut kept: Vec<(usize, usize, Uuid)> = vec![];
+    for m in found {
+        if !kept.iter().any(|k| m.0 < k.1 && k.0 < m.1) {
+            kept.push(m);
+        }
+    }
+    kept.sort_by_key(|m| m.0);
+    kept
+}
+
+/// First cue in `words`, with the index of its first word
+fn find_cue(words: &[&str]) -> Option<(String, EdgeType, usize)> {
+    (0..words.len()).find_map(|at| {
+        CUES.iter()
+            .find(|(cue, _)| {
+                let parts: Vec<&str> = cue.split(' ').collect();
+                words.get(at..at + parts.len()) == Some(&parts[..])
+            })
+            .map(|(cue, edge_type)| (cue.to_string(), edge_type.clone(), at))
+    })
+}
+
+/// An edge proposed by extraction, with the statements it came from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposedEdge {
+    pub edge: GraphEdge,
+    pub extractors: Vec<String>,
+    pub sentences: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExtractionReport {
+    pub docs_scanned: usize,
+    pub relations_found: usize,
+    pub proposed: Vec<ProposedEdge>,
+    pub already_in_graph: usize,          // relations the graph already has an edge for
+    pub errors: Vec<String>,
+}
+
+/// Runs extractors over documents and turns what they find into edges with
+/// the documents as evidence. Edges are only proposed: route them through
+/// the approval queue (`ChangeQueue`) rather than adding them to the graph.
+pub struct ExtractionPipeline {
+    extractors: Vec<(String, Box<dyn RelationExtractor>)>,
+}
+
+impl Default for ExtractionPipeline {
+    fn default() -> Self {
+        Self::new().with_extractor("patterns", PatternExtractor::default())
+    }
+}
+
+impl ExtractionPipeline {
+    /// Pipeline with no extractors
+    pub fn new() -> Self {
+        Self { extractors: vec![] }
+    }
+
+    pub fn with_extractor(mut self, name: impl Into<String>, extractor: impl RelationExtractor + 'static) -> Self {
+        self.extractors.push((name.into(), Box::new(extractor)));
+        self
+    }
+
+    /// One proposed edge per (source, target, type, polarity) found in `docs`
+    /// and not already in `graph`, evidence and sentences merged across docs,
+    /// confidence the highest any statement gave. A failing extractor is
+    /// reported and skipped.
+    pub fn run<'a>(&self, graph: &MultiIntentGraph, docs: impl IntoIterator<Item = &'a CorpusDoc>) -> ExtractionReport {
+        let mut report = ExtractionReport::default();
+        for doc in docs {
+            report.docs_scanned += 1;
+            for (name, extractor) in &self.extractors {
+                let relations = match extractor.extract(doc, graph) {
+                    Ok(relations) => relations,
+                    Err(e) => {
+                        report.errors.push(format!("{} on {}: {:#}", name, doc.id, e));
+                        continue;
+                    }
+                };
+                for relation in relations {
+                    report.relations_found += 1;
+                    self.add(graph, &mut report, name, doc, relation);
+                }
+            }
+        }
+        report
+    }
+
+    fn add(&self, graph: &MultiIntentGraph, report: &mut ExtractionReport, extractor: &str, doc: &CorpusDoc, relation: ExtractedRelation) {
+        let (Some(source), Some(target)) = (graph.intent_nodes.get(&relation.source_id), graph.intent_nodes.get(&relation.target_id)) else {
+            report.errors.push(format!("{} on {}: relation between unknown nodes {} and {}", extractor, doc.id, relation.source_id, relation.target_id));
+            return;
+        };
+        let same = |e: &GraphEdge| e.source_id == relation.source_id && e.target_id == relation.target_id
+            && e.edge_type == relation.edge_type && e.metadata.polarity == relation.polarity;
+        if graph.edges.values().any(same) {
+            report.already_in_graph += 1;
+            return;
+        }
+        if let Some(proposed) = report.proposed.iter_mut().find(|p| same(&p.edge)) {
+            let edge = &mut proposed.edge;
+            if !edge.metadata.evidence_refs.contains(&doc.source) {
+                edge.metadata.evidence_refs.push(doc.source.clone());
+            }
+            edge.metadata.confidence = edge.metadata.confidence.max(relation.confidence);
+            edge.weight = edge.metadata.confidence;
+            if !proposed.extractors.iter().any(|e| e == extractor) {
+                proposed.extractors.push(extractor.to_string());
+            }
+            if !proposed.sentences.contains(&relation.sentence) {
+                proposed.sentences.push(relation.sentence);
+            }
+            return;
+        }
+        let label = format!("{} {} {}", source.label(), relation.cue, target.label());
+        let (source_domain, target_domain) = (format!("{:?}", source.domain), format!("{:?}", target.domain));
+        let evidence = vec![doc.source.clone()];
+        let mut edge = match relation.edge_type {
+            EdgeType::Correlative => GraphEdge::new_correlative(relation.source_id, relation.target_id, label, source_domain, target_domain, evidence, relation.confidence),
+            _ => GraphEdge::new_causal(relation.source_id, relation.target_id, label, source_domain, target_domain, evidence, relation.confidence),
+        };
+        edge.edge_type = relation.edge_type;
+        edge.metadata.polarity = relation.polarity;
+        report.proposed.push(ProposedEdge { edge, extractors: vec![extractor.to_string()], sentences: vec![relation.sentence] });
+    }
+}
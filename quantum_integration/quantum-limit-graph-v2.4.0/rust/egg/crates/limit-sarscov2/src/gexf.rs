@@ -0,0 +1,140 @@
+// limit-sarscov2/src/gexf.rs
+// GEXF export for Gephi, with node and edge lifetimes from `created_at`
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::{
+    edges::{GraphEdge, Polarity},
+    multi_intent_graph::{IntentNode, MultiIntentGraph},
+};
+
+pub const GEXF_CONTENT_TYPE: &str = "application/gexf+xml";
+
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value).ok().map(|t| t.with_timezone(&Utc))
+}
+
+fn format_time(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c if (c as u32) < 0x20 && !matches!(c, '\t' | '\n' | '\r') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn start_attr(time: Option<DateTime<Utc>>) -> String {
+    time.map(|t| format!(" start=\"{}\"", format_time(t))).unwrap_or_default()
+}
+
+/// The graph as a dynamic GEXF 1.2 document. Each node exists from its
+/// `created_at` and each edge from the later of its own `created_at` and its
+/// endpoints', so Gephi's timeline replays how the graph grew; elements with
+/// an unparseable timestamp are present throughout. Nodes also carry a
+/// dynamic `degree` attribute that steps up as edges arrive. Edges are
+/// written only when both endpoints are in the graph; hyperedges are left out.
+pub fn to_gexf(graph: &MultiIntentGraph) -> String {
+    let mut nodes: Vec<&IntentNode> = graph.intent_nodes.values().collect();
+    nodes.sort_by_key(|n| n.id);
+    let mut edges: Vec<&GraphEdge> = graph.edges.values()
+        .filter(|e| graph.intent_nodes.contains_key(&e.source_id) && graph.intent_nodes.contains_key(&e.target_id))
+        .collect();
+    edges.sort_by_key(|e| e.id);
+
+    let node_start: HashMap<Uuid, Option<DateTime<Utc>>> = nodes.iter().map(|n| (n.id, parse_time(&n.metadata.created_at))).collect();
+    let edge_start = |e: &GraphEdge| -> Option<DateTime<Utc>> {
+        let own = parse_time(&e.metadata.created_at)?;
+        Some([node_start[&e.source_id], node_start[&e.target_id]].into_iter().flatten().fold(own, DateTime::max))
+    };
+    // When each node gains an edge, for the dynamic degree
+    let mut arrivals: HashMap<Uuid, Vec<DateTime<Utc>>> = HashMap::new();
+    for edge in &edges {
+        if let Some(t) = edge_start(edge) {
+            arrivals.entry(edge.source_id).or_default().push(t);
+            if edge.target_id != edge.source_id {
+                arrivals.entry(edge.target_id).or_default().push(t);
+            }
+        }
+    }
+
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<gexf xmlns=\"http://www.gexf.net/1.2draft\" version=\"1.2\">\n");
+    out.push_str(&format!("  <meta lastmodifieddate=\"{}\">\n", escape(graph.metadata.last_updated.get(..10).unwrap_or(&graph.metadata.last_updated))));
+    out.push_str("    <creator>limit-sarscov2</creator>\n");
+    out.push_str(&format!("    <description>Multi-intent graph {}</description>\n", graph.id));
+    out.push_str("  </meta>\n");
+    out.push_str("  <graph mode=\"dynamic\" defaultedgetype=\"directed\" timeformat=\"datetime\">\n");
+    out.push_str("    <attributes class=\"node\" mode=\"static\">\n");
+    for (i, (title, kind)) in [("domain", "string"), ("intent", "string"), ("confidence", "float"), ("evidence_count", "integer")].iter().enumerate() {
+        out.push_str(&format!("      <attribute id=\"{}\" title=\"{}\" type=\"{}\"/>\n", i, title, kind));
+    }
+    out.push_str("    </attributes>\n");
+    out.push_str("    <attributes class=\"node\" mode=\"dynamic\">\n");
+    out.push_str("      <attribute id=\"degree\" title=\"degree\" type=\"integer\"/>\n");
+    out.push_str("    </attributes>\n");
+    out.push_str("    <attributes class=\"edge\" mode=\"static\">\n");
+    for (i, (title, kind)) in [("edge_type", "string"), ("confidence", "float"), ("polarity", "string")].iter().enumerate() {
+        out.push_str(&format!("      <attribute id=\"{}\" title=\"{}\" type=\"{}\"/>\n", i, title, kind));
+    }
+    out.push_str("    </attributes>\n");
+
+    out.push_str("    <nodes>\n");
+    for node in &nodes {
+        out.push_str(&format!("      <node id=\"{}\" label=\"{}\"{}>\n", node.id, escape(node.label()), start_attr(node_start[&node.id])));
+        out.push_str("        <attvalues>\n");
+        let values = [format!("{:?}", node.domain), node.intent.clone(), node.metadata.confidence.to_string(), node.metadata.evidence_count.to_string()];
+        for (i, value) in values.iter().enumerate() {
+            out.push_str(&format!("          <attvalue for=\"{}\" value=\"{}\"/>\n", i, escape(value)));
+        }
+        let mut times = arrivals.remove(&node.id).unwrap_or_default();
+        times.sort();
+        for (i, start) in times.iter().enumerate() {
+            if times.get(i + 1) == Some(start) {
+                continue;   // several edges at once: one step to the final count
+            }
+            let degree = i + 1;
+            let end = times[i + 1..].iter().find(|t| *t > start).map(|t| format!(" end=\"{}\"", format_time(*t))).unwrap_or_default();
+            out.push_str(&format!("          <attvalue for=\"degree\" value=\"{}\" start=\"{}\"{}/>\n", degree, format_time(*start), end));
+        }
+        out.push_str("        </attvalues>\n");
+        out.push_str("      </node>\n");
+    }
+    out.push_str("    </nodes>\n");
+
+    out.push_str("    <edges>\n");
+    for edge in &edges {
+        out.push_str(&format!(
+            "      <edge id=\"{}\" source=\"{}\" target=\"{}\" label=\"{}\" weight=\"{}\"{}>\n",
+            edge.id, edge.source_id, edge.target_id, escape(&edge.label), edge.weight, start_attr(edge_start(edge)),
+        ));
+        out.push_str("        <attvalues>\n");
+        let polarity = match edge.metadata.polarity {
+            Polarity::Supports => "supports",
+            Polarity::Refutes => "refutes",
+        };
+        let values = [edge.edge_type.name().to_string(), edge.metadata.confidence.to_string(), polarity.to_string()];
+        for (i, value) in values.iter().enumerate() {
+            out.push_str(&format!("          <attvalue for=\"{}\" value=\"{}\"/>\n", i, escape(value)));
+        }
+        out.push_str("        </attvalues>\n");
+        out.push_str("      </edge>\n");
+    }
+    out.push_str("    </edges>\n");
+    out.push_str("  </graph>\n");
+    out.push_str("</gexf>\n");
+    out
+}
@@ -1,29 +1,347 @@
 // limit-sarscov2/src/governance.rs
 use serde::{Serialize, Deserialize};
-use crate::domain::SarsCov2Graph;
+use std::{collections::{BTreeMap, HashMap}, fs, path::Path};
+use anyhow::{Context, Result};
+use uuid::Uuid;
+
+use crate::{
+    domain::{ResearchDomain, SarsCov2Graph},
+    edges::{EdgeType, GraphEdge},
+    geo::GeoScope,
+    multi_intent_graph::MultiIntentGraph,
+    provenance::GovernanceTag,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EvidenceThresholds {
     pub virology_min: usize,
     pub genomics_min: usize,
     pub treatment_min: usize,
+    #[serde(default)]
+    pub immunology_min: usize,
+    #[serde(default)]
+    pub public_health_min: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GovernanceDecision {
     pub allowed: bool,
     pub reason: String,
+    #[serde(default)]
+    pub violations: Vec<String>,  // every failed rule; `reason` is the first
+    #[serde(default)]
+    pub tags: Vec<GovernanceTag>, // one failed tag per violating node or edge
+    #[serde(default)]
+    pub domains: Vec<DomainCheck>,    // every domain minimum checked, passed or not
 }
 
-pub fn check_merge_allowed(graph: &SarsCov2Graph, t: &EvidenceThresholds) -> GovernanceDecision {
-    if graph.virology.len() < t.virology_min {
-        return GovernanceDecision { allowed: false, reason: format!("Insufficient virology evidence: {} < {}", graph.virology.len(), t.virology_min) };
+/// A domain's node count against its minimum, over the whole graph or one region
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DomainCheck {
+    pub domain: ResearchDomain,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<GeoScope>,     // None: the whole graph
+    pub have: usize,
+    pub need: usize,
+    pub passed: bool,
+}
+
+/// Collects every violation before deciding, so callers see all of them
+#[derive(Default)]
+struct Violations {
+    messages: Vec<String>,
+    tags: Vec<GovernanceTag>,
+    domains: Vec<DomainCheck>,
+}
+
+impl Violations {
+    /// Record a domain minimum; `message` becomes a violation when it is not met
+    fn domain(&mut self, domain: &ResearchDomain, region: Option<&GeoScope>, have: usize, need: usize, message: impl FnOnce() -> String) {
+        let passed = have >= need;
+        if !passed {
+            self.messages.push(message());
+        }
+        self.domains.push(DomainCheck { domain: domain.clone(), region: region.cloned(), have, need, passed });
     }
-    if graph.genomics.len() < t.genomics_min {
-        return GovernanceDecision { allowed: false, reason: format!("Insufficient genomics evidence: {} < {}", graph.genomics.len(), t.genomics_min) };
+
+    /// Violation attached to a node or edge; the tag ID is stable per subject and rule
+    fn subject(&mut self, subject: Uuid, label: &str, message: String) {
+        self.tags.push(GovernanceTag {
+            id: Uuid::new_v5(&subject, label.as_bytes()),
+            label: label.into(),
+            passed: false,
+            details: Some(message.clone()),
+        });
+        self.messages.push(message);
+    }
+
+    fn decide(self, allowed_reason: &str) -> GovernanceDecision {
+        let Violations { messages, tags, domains } = self;
+        match messages.first() {
+            Some(first) => GovernanceDecision { allowed: false, reason: first.clone(), violations: messages, tags, domains },
+            None => GovernanceDecision { allowed: true, reason: allowed_reason.into(), violations: messages, tags, domains },
+        }
+    }
+}
+
+/// Minimum node count per research domain
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DomainThresholds {
+    pub virology: usize,
+    pub immunology: usize,
+    pub genomics: usize,
+    pub treatment: usize,
+    pub public_health: usize,
+}
+
+impl DomainThresholds {
+    pub fn get(&self, domain: &ResearchDomain) -> usize {
+        match domain {
+            ResearchDomain::Virology => self.virology,
+            ResearchDomain::Immunology => self.immunology,
+            ResearchDomain::Genomics => self.genomics,
+            ResearchDomain::Treatment => self.treatment,
+            ResearchDomain::PublicHealth => self.public_health,
+        }
     }
-    if graph.treatment.len() < t.treatment_min {
-        return GovernanceDecision { allowed: false, reason: format!("Insufficient treatment evidence: {} < {}", graph.treatment.len(), t.treatment_min) };
+}
+
+impl From<&EvidenceThresholds> for DomainThresholds {
+    fn from(t: &EvidenceThresholds) -> Self {
+        Self {
+            virology: t.virology_min,
+            immunology: t.immunology_min,
+            genomics: t.genomics_min,
+            treatment: t.treatment_min,
+            public_health: t.public_health_min,
+        }
+    }
+}
+
+/// Provenance every node and edge must carry
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProvenanceRequirements {
+    pub node_sources: usize,          // minimum `metadata.sources` per intent node
+    pub edge_evidence_refs: usize,    // minimum distinct evidence references per edge
+    pub cross_domain_sources: bool,   // both endpoints of a cross-domain edge must record sources
+}
+
+/// Edge-level rules for `check_edges`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EdgeRules {
+    pub causal_min_confidence: f32,   // causal edges below this are flagged
+    pub require_evidence: bool,       // flag edges without evidence references
+    pub cross_domain_sources: bool,   // flag cross-domain edges whose endpoints record no sources
+}
+
+impl Default for EdgeRules {
+    fn default() -> Self {
+        Self { causal_min_confidence: 0.5, require_evidence: true, cross_domain_sources: true }
+    }
+}
+
+/// Per-edge limits shared by `check_edges` and `GovernancePolicy::check_multi_graph`
+struct EdgeLimits {
+    min_confidence: Option<f32>,
+    min_evidence_refs: usize,
+    cross_domain_sources: bool,
+}
+
+impl EdgeLimits {
+    /// Minimum confidence applies to the edge's net support, so a relationship
+    /// contradicted by refuting edges can fall below it; refuting edges
+    /// themselves are only held to the evidence rules
+    fn check(&self, graph: &MultiIntentGraph, edge: &GraphEdge, net_support: &HashMap<Uuid, f32>, out: &mut Violations) {
+        if let (Some(min), Some(&net)) = (self.min_confidence, net_support.get(&edge.id)) {
+            if net < min {
+                let refuted = graph.refutations_of(edge).len();
+                let detail = if refuted > 0 {
+                    format!("net support {:.2} (confidence {:.2}, {} refuting edges)", net, edge.metadata.confidence, refuted)
+                } else {
+                    format!("confidence {:.2}", net)
+                };
+                out.subject(edge.id, "low-confidence-edge", format!(
+                    "{} edge {} ({}) has {} < {:.2}",
+                    edge.edge_type, edge.id, edge.label, detail, min,
+                ));
+            }
+        }
+        let refs = edge.distinct_evidence().len();
+        if refs < self.min_evidence_refs {
+            out.subject(edge.id, "missing-evidence", format!(
+                "Edge {} ({}) has {} evidence references, {} required",
+                edge.id, edge.label, refs, self.min_evidence_refs,
+            ));
+        }
+        if self.cross_domain_sources && edge.is_cross_domain() {
+            let unsourced: Vec<String> = [edge.source_id, edge.target_id].iter()
+                .filter(|id| graph.intent_nodes.get(id).is_none_or(|n| n.metadata.sources.is_empty()))
+                .map(|id| id.to_string())
+                .collect();
+            if !unsourced.is_empty() {
+                out.subject(edge.id, "cross-domain-unsourced", format!(
+                    "Cross-domain edge {} ({} → {}) links nodes without sources: {}",
+                    edge.id, edge.metadata.source_domain, edge.metadata.target_domain, unsourced.join(", "),
+                ));
+            }
+        }
+    }
+}
+
+fn sorted_edges(graph: &MultiIntentGraph) -> Vec<&GraphEdge> {
+    let mut edges: Vec<&GraphEdge> = graph.edges.values().collect();
+    edges.sort_by_key(|e| e.id);
+    edges
+}
+
+/// Edge-level governance over a multi-intent graph: weak causal edges (by net
+/// support, after refuting evidence), edges without evidence and cross-domain
+/// edges lacking provenance. Every violation is reported and tagged.
+pub fn check_edges(graph: &MultiIntentGraph, rules: &EdgeRules) -> GovernanceDecision {
+    let mut out = Violations::default();
+    let net_support = graph.net_supports();
+    for edge in sorted_edges(graph) {
+        EdgeLimits {
+            min_confidence: (edge.edge_type == EdgeType::Causal).then_some(rules.causal_min_confidence),
+            min_evidence_refs: rules.require_evidence as usize,
+            cross_domain_sources: rules.cross_domain_sources,
+        }
+        .check(graph, edge, &net_support, &mut out);
+    }
+    out.decide("Merge allowed: edges satisfy governance rules")
+}
+
+/// Merge rules loaded from a TOML file, e.g.
+///
+/// ```toml
+/// approver_roles = ["curator"]
+///
+/// [thresholds]
+/// virology = 2
+/// genomics = 1
+///
+/// [edge_confidence]
+/// Causal = 0.6
+///
+/// [provenance]
+/// edge_evidence_refs = 1
+///
+/// [regional_thresholds.ID]
+/// public_health = 2
+/// ```
+///
+/// Omitted sections and fields impose no requirement.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GovernancePolicy {
+    pub thresholds: DomainThresholds,
+    pub edge_confidence: HashMap<EdgeType, f32>,  // minimum edge confidence per type
+    pub provenance: ProvenanceRequirements,
+    pub approver_roles: Vec<String>,              // API key roles allowed to review proposed changes; empty = anyone
+    pub regional_thresholds: BTreeMap<GeoScope, DomainThresholds>,  // minimum node count per domain within a region
+}
+
+const DOMAINS: [ResearchDomain; 5] = [
+    ResearchDomain::Virology,
+    ResearchDomain::Immunology,
+    ResearchDomain::Genomics,
+    ResearchDomain::Treatment,
+    ResearchDomain::PublicHealth,
+];
+
+impl GovernancePolicy {
+    pub fn from_toml(text: &str) -> Result<Self> {
+        toml::from_str(text).context("parsing governance policy TOML")
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        let policy = Self::from_toml(&text).with_context(|| format!("loading {}", path.display()))?;
+        tracing::info!(path = %path.display(), "governance policy loaded");
+        Ok(policy)
+    }
+
+    /// Domain thresholds against the base graph's node lists
+    pub fn check_graph(&self, graph: &SarsCov2Graph) -> GovernanceDecision {
+        let mut out = Violations::default();
+        for (domain, count) in DOMAINS.iter().zip(base_counts(graph)) {
+            self.check_domain(domain, count, &mut out);
+        }
+        out.decide("Merge allowed: policy satisfied")
+    }
+
+    /// Domain thresholds, overall and per region, edge confidence minimums
+    /// and provenance requirements
+    pub fn check_multi_graph(&self, graph: &MultiIntentGraph) -> GovernanceDecision {
+        let mut out = Violations::default();
+        for domain in &DOMAINS {
+            self.check_domain(domain, graph.nodes_by_domain(domain.clone()).len(), &mut out);
+        }
+        for (region, thresholds) in &self.regional_thresholds {
+            let nodes = graph.nodes_in_region(region);
+            for domain in &DOMAINS {
+                let count = nodes.iter().filter(|n| std::mem::discriminant(&n.domain) == std::mem::discriminant(domain)).count();
+                let min = thresholds.get(domain);
+                out.domain(domain, Some(region), count, min, || format!("Insufficient {:?} evidence in {}: {} < {}", domain, region, count, min));
+            }
+        }
+
+        let mut nodes: Vec<_> = graph.intent_nodes.values().collect();
+        nodes.sort_by_key(|n| n.id);
+        for node in nodes {
+            if node.metadata.sources.len() < self.provenance.node_sources {
+                out.subject(node.id, "missing-sources", format!(
+                    "Node {} ({}) has {} sources, policy requires {}",
+                    node.id, node.label(), node.metadata.sources.len(), self.provenance.node_sources,
+                ));
+            }
+        }
+
+        let net_support = graph.net_supports();
+        for edge in sorted_edges(graph) {
+            EdgeLimits {
+                min_confidence: self.edge_confidence.get(&edge.edge_type).copied(),
+                min_evidence_refs: self.provenance.edge_evidence_refs,
+                cross_domain_sources: self.provenance.cross_domain_sources,
+            }
+            .check(graph, edge, &net_support, &mut out);
+        }
+        out.decide("Merge allowed: policy satisfied")
+    }
+
+    fn check_domain(&self, domain: &ResearchDomain, count: usize, out: &mut Violations) {
+        let min = self.thresholds.get(domain);
+        out.domain(domain, None, count, min, || format!("Insufficient {:?} evidence: {} < {}", domain, count, min));
+    }
+}
+
+/// Node count per domain of a base graph, in `DOMAINS` order
+fn base_counts(graph: &SarsCov2Graph) -> [usize; 5] {
+    [
+        graph.virology.len(),
+        graph.immunology.len(),
+        graph.genomics.len(),
+        graph.treatment.len(),
+        graph.public_health.len(),
+    ]
+}
+
+/// Every domain minimum in `t` against the base graph; all failures are
+/// reported, with the per-domain counts in `domains`
+pub fn check_merge_allowed(graph: &SarsCov2Graph, t: &EvidenceThresholds) -> GovernanceDecision {
+    let thresholds = DomainThresholds::from(t);
+    let mut out = Violations::default();
+    for (domain, count) in DOMAINS.iter().zip(base_counts(graph)) {
+        let min = thresholds.get(domain);
+        let name = match domain {
+            ResearchDomain::PublicHealth => "public health".to_string(),
+            other => format!("{:?}", other).to_lowercase(),
+        };
+        out.domain(domain, None, count, min, || format!("Insufficient {} evidence: {} < {}", name, count, min));
     }
-    GovernanceDecision { allowed: true, reason: "Merge allowed: thresholds satisfied".into() }
+    out.decide("Merge allowed: thresholds satisfied")
 }
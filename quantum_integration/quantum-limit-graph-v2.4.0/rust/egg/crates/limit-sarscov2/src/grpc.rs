@@ -0,0 +1,501 @@
+// limit-sarscov2/src/grpc.rs
+// gRPC service (proto/sarscov2.proto) over the same AppState as the REST API
+
+use futures_util::{stream::{self, BoxStream}, StreamExt};
+use tonic::{Request, Response, Status, Streaming};
+use uuid::Uuid;
+
+use crate::{
+    api::AppState,
+    domain::ResearchDomain,
+    error::ApiError,
+    events::GraphEvent,
+    edges::{EdgeMetadata, EdgeType, GraphEdge, Polarity},
+    geo::{GeoScope, GeoScopeError},
+    import::{import_records, ImportRecord, ImportSummary, ParsedLine},
+    multi_intent_graph::{IntentNode, MultiIntentGraph, NodeContent, NodeMetadata},
+    ontology::{OntologyRef, OntologyRefError},
+    nodes::{ClinicalTrialNode, GenomicsNode, HostFactorNode, ImmunologyNode, ProteinRegion, PublicHealthNode, TreatmentNode, TrialArm, VaccineNode, VirologyNode},
+    serendipity_trace::{ExplorationStep, SerendipityTrace},
+    metrics_history::SnapshotTrigger,
+};
+
+pub mod pb {
+    tonic::include_proto!("sarscov2.v1");
+}
+
+pub use pb::knowledge_graph_server::KnowledgeGraphServer;
+
+/// Implements `sarscov2.v1.KnowledgeGraph`; serve with `KnowledgeGraphServer::new`
+#[derive(Clone)]
+pub struct GrpcService {
+    state: AppState,
+}
+
+impl GrpcService {
+    pub fn new(state: AppState) -> Self {
+        Self { state }
+    }
+
+    pub fn into_server(self) -> KnowledgeGraphServer<Self> {
+        KnowledgeGraphServer::new(self)
+    }
+
+    fn with_graph<T>(&self, graph_id: &str, f: impl FnOnce(&MultiIntentGraph) -> T) -> Result<T, Status> {
+        let id = parse_id(graph_id, "graph_id")?;
+        let graphs = self.state.multi_graphs.lock().map_err(ApiError::from)?;
+        let graph = graphs.iter().find(|g| g.id == id).ok_or_else(|| Status::not_found(format!("graph {} not found", id)))?;
+        Ok(f(graph))
+    }
+}
+
+/// Same status an `ApiError` maps to over REST
+impl From<ApiError> for Status {
+    fn from(e: ApiError) -> Self {
+        match e {
+            ApiError::NotFound(m) => Status::not_found(m),
+            ApiError::BadRequest(m) | ApiError::Invalid(m) => Status::invalid_argument(m),
+            ApiError::Unauthorized(m) => Status::unauthenticated(m),
+            ApiError::Forbidden(m) => Status::permission_denied(m),
+            ApiError::Conflict(m) => Status::failed_precondition(m),
+            ApiError::PayloadTooLarge(m) => Status::resource_exhausted(m),
+            ApiError::TooManyRequests { .. } => Status::resource_exhausted(e.to_string()),
+            ApiError::Internal { .. } => Status::internal(e.to_string()),
+        }
+    }
+}
+
+fn parse_id(value: &str, field: &str) -> Result<Uuid, Status> {
+    value.parse().map_err(|_| Status::invalid_argument(format!("{} {:?} is not a UUID", field, value)))
+}
+
+#[tonic::async_trait]
+impl pb::knowledge_graph_server::KnowledgeGraph for GrpcService {
+    async fn list_graphs(&self, _: Request<pb::ListGraphsRequest>) -> Result<Response<pb::ListGraphsResponse>, Status> {
+        let graphs = self.state.multi_graphs.lock().map_err(ApiError::from)?.iter()
+            .map(|g| pb::GraphSummary {
+                id: g.id.to_string(),
+                nodes: g.intent_nodes.len() as u64,
+                edges: g.edges.len() as u64,
+                traces: g.serendipity_traces.len() as u64,
+            })
+            .collect();
+        Ok(Response::new(pb::ListGraphsResponse { graphs }))
+    }
+
+    async fn get_graph(&self, request: Request<pb::GraphRequest>) -> Result<Response<pb::Graph>, Status> {
+        let graph = self.with_graph(&request.get_ref().graph_id, |g| pb::Graph {
+            id: g.id.to_string(),
+            nodes: sorted_nodes(g).map(pb::Node::from).collect(),
+            edges: sorted_edges(g).map(pb::Edge::from).collect(),
+            traces: g.serendipity_traces.iter().map(pb::Trace::from).collect(),
+        })?;
+        Ok(Response::new(graph))
+    }
+
+    type StreamNodesStream = BoxStream<'static, Result<pb::Node, Status>>;
+
+    async fn stream_nodes(&self, request: Request<pb::GraphRequest>) -> Result<Response<Self::StreamNodesStream>, Status> {
+        let nodes: Vec<pb::Node> = self.with_graph(&request.get_ref().graph_id, |g| sorted_nodes(g).map(pb::Node::from).collect())?;
+        Ok(Response::new(stream::iter(nodes.into_iter().map(Ok)).boxed()))
+    }
+
+    type StreamEdgesStream = BoxStream<'static, Result<pb::Edge, Status>>;
+
+    async fn stream_edges(&self, request: Request<pb::GraphRequest>) -> Result<Response<Self::StreamEdgesStream>, Status> {
+        let edges: Vec<pb::Edge> = self.with_graph(&request.get_ref().graph_id, |g| sorted_edges(g).map(pb::Edge::from).collect())?;
+        Ok(Response::new(stream::iter(edges.into_iter().map(Ok)).boxed()))
+    }
+
+    async fn get_trace(&self, request: Request<pb::TraceRequest>) -> Result<Response<pb::Trace>, Status> {
+        let id = parse_id(&request.get_ref().trace_id, "trace_id")?;
+        let trace = self.state.find_trace(id)?.ok_or_else(|| Status::not_found(format!("trace {} not found", id)))?;
+        Ok(Response::new(pb::Trace::from(&trace)))
+    }
+
+    async fn get_rd_curves(&self, request: Request<pb::GraphRequest>) -> Result<Response<pb::RdCurves>, Status> {
+        let id = parse_id(&request.get_ref().graph_id, "graph_id")?;
+        let mut curves: Vec<pb::RdCurve> = self.state.rd_curves_of(id)?.into_iter()
+            .map(|(intent, curve)| pb::RdCurve {
+                intent,
+                points: curve.points.iter().map(|p| pb::RdPoint { rate: p.rate, distortion: p.distortion }).collect(),
+            })
+            .collect();
+        curves.sort_by(|a, b| a.intent.cmp(&b.intent));
+        Ok(Response::new(pb::RdCurves { curves }))
+    }
+
+    async fn import_records(&self, request: Request<Streaming<pb::ImportRecord>>) -> Result<Response<pb::ImportSummary>, Status> {
+        let metadata = |name: &str| request.metadata().get(name).and_then(|v| v.to_str().ok()).map(str::to_string);
+        let actor = metadata("x-actor").unwrap_or_else(|| "anonymous".into());
+        let session = metadata("x-session").unwrap_or_else(|| actor.clone());
+        let mut incoming = request.into_inner();
+        let mut graph_id = None;
+        let mut records: Vec<ParsedLine> = vec![];
+        while let Some(message) = incoming.next().await {
+            let message = message?;
+            if graph_id.is_none() {
+                graph_id = Some(parse_id(&message.graph_id, "graph_id")?);
+            }
+            let index = records.len() + 1;
+            let record = match message.record {
+                Some(pb::import_record::Record::Node(node)) => IntentNode::try_from(node).map(ImportRecord::Node),
+                Some(pb::import_record::Record::Edge(edge)) => GraphEdge::try_from(edge).map(ImportRecord::Edge),
+                None => Err("record has neither a node nor an edge".into()),
+            };
+            records.push((index, record));
+        }
+        let graph_id = graph_id.ok_or_else(|| Status::invalid_argument("empty import stream"))?;
+
+        let (summary, notes) = self.state.edit_graph((&actor, &session), graph_id, |graph| {
+            let (summary, notes) = import_records(graph, records);
+            let events = notes.iter().filter_map(|n| GraphEvent::added(graph, n.subject_id)).collect();
+            Ok(((summary, notes), events))
+        })?;
+        for note in notes {
+            self.state.record_provenance(note)?;
+        }
+        if summary.nodes_accepted + summary.edges_accepted > 0 {
+            self.state.snapshot_metrics(graph_id, SnapshotTrigger::Mutation);
+        }
+        Ok(Response::new(pb::ImportSummary::from(summary)))
+    }
+}
+
+fn sorted_nodes(graph: &MultiIntentGraph) -> impl Iterator<Item = &IntentNode> {
+    let mut nodes: Vec<&IntentNode> = graph.intent_nodes.values().collect();
+    nodes.sort_by_key(|n| n.id);
+    nodes.into_iter()
+}
+
+fn sorted_edges(graph: &MultiIntentGraph) -> impl Iterator<Item = &GraphEdge> {
+    let mut edges: Vec<&GraphEdge> = graph.edges.values().collect();
+    edges.sort_by_key(|e| e.id);
+    edges.into_iter()
+}
+
+impl From<&ResearchDomain> for pb::Domain {
+    fn from(domain: &ResearchDomain) -> Self {
+        match domain {
+            ResearchDomain::Virology => Self::Virology,
+            ResearchDomain::Immunology => Self::Immunology,
+            ResearchDomain::Genomics => Self::Genomics,
+            ResearchDomain::Treatment => Self::Treatment,
+            ResearchDomain::PublicHealth => Self::PublicHealth,
+        }
+    }
+}
+
+impl From<&EdgeType> for pb::EdgeType {
+    fn from(edge_type: &EdgeType) -> Self {
+        match edge_type {
+            EdgeType::Causal => Self::Causal,
+            EdgeType::Correlative => Self::Correlative,
+            EdgeType::Mechanistic => Self::Mechanistic,
+            EdgeType::Temporal => Self::Temporal,
+            EdgeType::Inhibitory => Self::Inhibitory,
+            EdgeType::Preventive => Self::Preventive,
+            EdgeType::Synergistic => Self::Synergistic,
+            _ => Self::Custom,
+        }
+    }
+}
+
+impl From<&IntentNode> for pb::Node {
+    fn from(node: &IntentNode) -> Self {
+        use pb::node::Content;
+        let content = match &node.content {
+            NodeContent::Biology(n) => Content::Virology(pb::VirologyContent {
+                id: n.id.to_string(),
+                topic: n.topic.clone(),
+                details: n.details.clone(),
+                regions: n.regions.iter()
+                    .map(|r| pb::ProteinRegion {
+                        gene: r.gene.clone(),
+                        name: r.name.clone(),
+                        start: r.start,
+                        end: r.end,
+                        pdb_id: r.pdb_id.clone().unwrap_or_default(),
+                    })
+                    .collect(),
+            }),
+            NodeContent::Immunology(n) => Content::Immunology(pb::ImmunologyContent { id: n.id.to_string(), topic: n.topic.clone(), details: n.details.clone() }),
+            NodeContent::Variant(n) => Content::Genomics(pb::GenomicsContent { id: n.id.to_string(), variant: n.variant.clone(), mutations: n.notations() }),
+            NodeContent::Treatment(n) => Content::Treatment(pb::TreatmentContent { id: n.id.to_string(), therapy: n.therapy.clone(), mechanism: n.mechanism.clone() }),
+            NodeContent::PublicHealth(n) => Content::PublicHealth(pb::PublicHealthContent {
+                id: n.id.to_string(),
+                policy: n.policy.clone(),
+                effect: n.effect.clone(),
+                region: n.region.as_ref().map(GeoScope::to_string).unwrap_or_default(),
+            }),
+            NodeContent::ClinicalTrial(n) => Content::ClinicalTrial(pb::ClinicalTrialContent::from(n)),
+            NodeContent::HostFactor(n) => Content::HostFactor(pb::HostFactorContent { id: n.id.to_string(), factor: n.factor.clone(), category: variant_name(n.category), details: n.details.clone() }),
+            NodeContent::Vaccine(n) => Content::Vaccine(pb::VaccineContent {
+                id: n.id.to_string(),
+                name: n.name.clone(),
+                platform: variant_name(n.platform),
+                doses: n.doses as u32,
+                ve_infection: n.ve_infection,
+                ve_severe: n.ve_severe,
+                variant_context: n.variant_context.clone().unwrap_or_default(),
+            }),
+        };
+        Self {
+            id: node.id.to_string(),
+            intent: node.intent.clone(),
+            domain: pb::Domain::from(&node.domain) as i32,
+            content: Some(content),
+            evidence_count: node.metadata.evidence_count as u64,
+            confidence: node.metadata.confidence,
+            sources: node.metadata.sources.clone(),
+            created_at: node.metadata.created_at.clone(),
+            region: node.metadata.region.as_ref().map(GeoScope::to_string).unwrap_or_default(),
+            xrefs: node.metadata.xrefs.iter().map(OntologyRef::curie).collect(),
+        }
+    }
+}
+
+/// snake_case name of a unit enum variant, as in JSON
+fn variant_name(value: impl serde::Serialize) -> String {
+    serde_json::to_value(value).ok().and_then(|v| v.as_str().map(String::from)).unwrap_or_default()
+}
+
+fn parse_variant<T: serde::de::DeserializeOwned>(name: &str, field: &str) -> Result<T, String> {
+    serde_json::from_value(name.into()).map_err(|_| format!("unknown {} {:?}", field, name))
+}
+
+impl From<&ClinicalTrialNode> for pb::ClinicalTrialContent {
+    fn from(trial: &ClinicalTrialNode) -> Self {
+        Self {
+            id: trial.id.to_string(),
+            registry_id: trial.registry_id.clone(),
+            title: trial.title.clone(),
+            phase: variant_name(trial.phase),
+            design: variant_name(trial.design),
+            arms: trial.arms.iter()
+                .map(|a| pb::TrialArm {
+                    name: a.name.clone(),
+                    treatment_id: a.treatment_id.map(|id| id.to_string()).unwrap_or_default(),
+                    participants: a.participants.unwrap_or(0),
+                })
+                .collect(),
+            outcome: variant_name(trial.outcome),
+            status: variant_name(trial.status),
+        }
+    }
+}
+
+fn trial_from_pb(trial: pb::ClinicalTrialContent, node_id: Uuid) -> Result<ClinicalTrialNode, String> {
+    let arms = trial.arms.into_iter()
+        .map(|a| {
+            let treatment_id = if a.treatment_id.is_empty() {
+                None
+            } else {
+                Some(a.treatment_id.parse().map_err(|_| format!("arm treatment_id {:?} is not a UUID", a.treatment_id))?)
+            };
+            Ok(TrialArm { name: a.name, treatment_id, participants: (a.participants > 0).then_some(a.participants) })
+        })
+        .collect::<Result<_, String>>()?;
+    Ok(ClinicalTrialNode {
+        id: content_id(&trial.id, node_id)?,
+        registry_id: trial.registry_id,
+        title: trial.title,
+        phase: parse_variant(&trial.phase, "phase")?,
+        design: parse_variant(&trial.design, "design")?,
+        arms,
+        outcome: if trial.outcome.is_empty() { Default::default() } else { parse_variant(&trial.outcome, "outcome")? },
+        status: parse_variant(&trial.status, "status")?,
+    })
+}
+
+/// Nested content IDs may be empty, in which case the node's ID is used
+/// Empty for no region
+fn parse_region(code: &str) -> Result<Option<GeoScope>, String> {
+    if code.is_empty() {
+        return Ok(None);
+    }
+    code.parse().map(Some).map_err(|e: GeoScopeError| e.to_string())
+}
+
+fn content_id(id: &str, node_id: Uuid) -> Result<Uuid, String> {
+    if id.is_empty() {
+        return Ok(node_id);
+    }
+    id.parse().map_err(|_| format!("content id {:?} is not a UUID", id))
+}
+
+impl TryFrom<pb::Node> for IntentNode {
+    type Error = String;
+
+    fn try_from(node: pb::Node) -> Result<Self, String> {
+        use pb::node::Content;
+        let id: Uuid = node.id.parse().map_err(|_| format!("node id {:?} is not a UUID", node.id))?;
+        let domain = match pb::Domain::try_from(node.domain) {
+            Ok(pb::Domain::Virology) => ResearchDomain::Virology,
+            Ok(pb::Domain::Immunology) => ResearchDomain::Immunology,
+            Ok(pb::Domain::Genomics) => ResearchDomain::Genomics,
+            Ok(pb::Domain::Treatment) => ResearchDomain::Treatment,
+            Ok(pb::Domain::PublicHealth) => ResearchDomain::PublicHealth,
+            Ok(pb::Domain::Unspecified) | Err(_) => return Err(format!("node {} has no domain", id)),
+        };
+        let content = match node.content.ok_or_else(|| format!("node {} has no content", id))? {
+            Content::Virology(c) => NodeContent::Biology(VirologyNode {
+                id: content_id(&c.id, id)?,
+                topic: c.topic,
+                details: c.details,
+                regions: c.regions.into_iter()
+                    .map(|r| ProteinRegion { gene: r.gene, name: r.name, start: r.start, end: r.end, pdb_id: (!r.pdb_id.is_empty()).then_some(r.pdb_id) })
+                    .collect(),
+            }),
+            Content::Immunology(c) => NodeContent::Immunology(ImmunologyNode { id: content_id(&c.id, id)?, topic: c.topic, details: c.details }),
+            Content::Genomics(c) => NodeContent::Variant(GenomicsNode::from_notations(content_id(&c.id, id)?, c.variant, c.mutations)),
+            Content::Treatment(c) => NodeContent::Treatment(TreatmentNode { id: content_id(&c.id, id)?, therapy: c.therapy, mechanism: c.mechanism }),
+            Content::PublicHealth(c) => NodeContent::PublicHealth(PublicHealthNode {
+                id: content_id(&c.id, id)?,
+                policy: c.policy,
+                effect: c.effect,
+                region: parse_region(&c.region)?,
+            }),
+            Content::ClinicalTrial(c) => NodeContent::ClinicalTrial(trial_from_pb(c, id)?),
+            Content::HostFactor(c) => NodeContent::HostFactor(HostFactorNode {
+                id: content_id(&c.id, id)?,
+                factor: c.factor,
+                category: parse_variant(&c.category, "category")?,
+                details: c.details,
+            }),
+            Content::Vaccine(c) => NodeContent::Vaccine(VaccineNode {
+                id: content_id(&c.id, id)?,
+                name: c.name,
+                platform: parse_variant(&c.platform, "platform")?,
+                doses: u8::try_from(c.doses).map_err(|_| format!("vaccine doses {} out of range", c.doses))?,
+                ve_infection: c.ve_infection,
+                ve_severe: c.ve_severe,
+                variant_context: (!c.variant_context.is_empty()).then_some(c.variant_context),
+            }),
+        };
+        Ok(Self {
+            id,
+            intent: node.intent,
+            domain,
+            content,
+            metadata: NodeMetadata {
+                evidence_count: node.evidence_count as usize,
+                confidence: node.confidence,
+                sources: node.sources,
+                created_at: node.created_at,
+                region: parse_region(&node.region)?,
+                xrefs: node.xrefs.iter()
+                    .map(|curie| curie.parse().map_err(|e: OntologyRefError| e.to_string()))
+                    .collect::<Result<_, _>>()?,
+                embedding: None,
+            },
+        })
+    }
+}
+
+impl From<&GraphEdge> for pb::Edge {
+    fn from(edge: &GraphEdge) -> Self {
+        Self {
+            id: edge.id.to_string(),
+            edge_type: pb::EdgeType::from(&edge.edge_type) as i32,
+            source_id: edge.source_id.to_string(),
+            target_id: edge.target_id.to_string(),
+            label: edge.label.clone(),
+            weight: edge.weight,
+            source_domain: edge.metadata.source_domain.clone(),
+            target_domain: edge.metadata.target_domain.clone(),
+            evidence_refs: edge.metadata.evidence_refs.clone(),
+            confidence: edge.metadata.confidence,
+            created_at: edge.metadata.created_at.clone(),
+            refutes: edge.is_refuting(),
+            custom_type: if edge.edge_type.is_custom() { edge.edge_type.name().to_string() } else { String::new() },
+        }
+    }
+}
+
+impl TryFrom<pb::Edge> for GraphEdge {
+    type Error = String;
+
+    fn try_from(edge: pb::Edge) -> Result<Self, String> {
+        let uuid = |value: &str, field: &str| value.parse::<Uuid>().map_err(|_| format!("edge {} {:?} is not a UUID", field, value));
+        let id = uuid(&edge.id, "id")?;
+        let edge_type = match pb::EdgeType::try_from(edge.edge_type) {
+            Ok(pb::EdgeType::Causal) => EdgeType::Causal,
+            Ok(pb::EdgeType::Correlative) => EdgeType::Correlative,
+            Ok(pb::EdgeType::Mechanistic) => EdgeType::Mechanistic,
+            Ok(pb::EdgeType::Temporal) => EdgeType::Temporal,
+            Ok(pb::EdgeType::Inhibitory) => EdgeType::Inhibitory,
+            Ok(pb::EdgeType::Preventive) => EdgeType::Preventive,
+            Ok(pb::EdgeType::Synergistic) => EdgeType::Synergistic,
+            Ok(pb::EdgeType::Custom) => EdgeType::parse(&edge.custom_type)
+                .map_err(|e| format!("edge {} custom_type: {}", id, e))?,
+            Ok(pb::EdgeType::Unspecified) | Err(_) => return Err(format!("edge {} has no edge_type", id)),
+        };
+        Ok(Self {
+            id,
+            edge_type,
+            source_id: uuid(&edge.source_id, "source_id")?,
+            target_id: uuid(&edge.target_id, "target_id")?,
+            label: edge.label,
+            weight: edge.weight,
+            metadata: EdgeMetadata {
+                source_domain: edge.source_domain,
+                target_domain: edge.target_domain,
+                evidence_refs: edge.evidence_refs,
+                confidence: edge.confidence,
+                created_at: edge.created_at,
+                polarity: if edge.refutes { Polarity::Refutes } else { Polarity::Supports },
+            },
+        })
+    }
+}
+
+impl From<&ExplorationStep> for pb::ExplorationStep {
+    fn from(step: &ExplorationStep) -> Self {
+        Self {
+            id: step.id.to_string(),
+            step_number: step.step_number as u64,
+            hypothesis: step.hypothesis.to_string(),
+            query: step.query.clone(),
+            domains_explored: step.domains_explored.clone(),
+            evidence_found: step.evidence_found as u64,
+            confidence: step.confidence,
+            timestamp: step.timestamp.clone(),
+            evidence_refs: step.evidence_refs.clone(),
+        }
+    }
+}
+
+impl From<&SerendipityTrace> for pb::Trace {
+    fn from(trace: &SerendipityTrace) -> Self {
+        Self {
+            id: trace.id.to_string(),
+            session_id: trace.session_id.clone(),
+            question: trace.question.clone(),
+            steps: trace.steps.iter().map(pb::ExplorationStep::from).collect(),
+            total_evidence: trace.total_evidence as u64,
+            cross_domain_jumps: trace.cross_domain_jumps as u64,
+            created_at: trace.created_at.clone(),
+        }
+    }
+}
+
+impl From<ImportSummary> for pb::ImportSummary {
+    fn from(summary: ImportSummary) -> Self {
+        Self {
+            import_id: summary.import_id.to_string(),
+            graph_id: summary.graph_id.to_string(),
+            records: summary.records as u64,
+            nodes_accepted: summary.nodes_accepted as u64,
+            edges_accepted: summary.edges_accepted as u64,
+            rejected: summary.rejected as u64,
+            rejections: summary.rejections.into_iter()
+                .map(|r| pb::RejectedRecord {
+                    index: r.line as u64,
+                    id: r.id.map(|id| id.to_string()).unwrap_or_default(),
+                    reason: r.reason,
+                })
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,296 @@
+// limit-sarscov2/src/hypothesis_gen.rs
+// Generator-proposed hypothesis paths (e.g. from an LLM): validation, dedup and provenance
+
+use serde::{Serialize, Deserialize};
+use std::future::Future;
+use uuid::Uuid;
+use anyhow::Result;
+
+use crate::{
+    edges::{EdgeType, GraphEdge},
+    multi_intent_graph::{HypothesisPath, MultiIntentGraph, PathIssue},
+    provenance::ProvenanceNote,
+    serendipity_trace::HypothesisType,
+};
+
+/// Most candidates kept from one generator call
+pub const MAX_CANDIDATES: usize = 20;
+
+/// A hypothesis a generator proposes: a description and the nodes it runs
+/// through. Edges may be left out; each step then takes the strongest edge
+/// joining its nodes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HypothesisCandidate {
+    pub hypothesis_type: HypothesisType,
+    pub description: String,
+    pub node_sequence: Vec<Uuid>,
+    #[serde(default)]
+    pub edge_sequence: Vec<Uuid>,
+    #[serde(default)]
+    pub rationale: Option<String>,     // the generator's reasoning, kept in the provenance note
+}
+
+/// Proposes hypotheses for a question from a subgraph. Implement this to plug
+/// in an LLM; `generate_hypotheses` checks what comes back against the graph.
+pub trait HypothesisGenerator: Send + Sync {
+    /// Name recorded as the source of accepted paths ("llm:<name>")
+    fn name(&self) -> &str;
+
+    fn generate(&self, subgraph: &MultiIntentGraph, question: &str) -> impl Future<Output = Result<Vec<HypothesisCandidate>>> + Send;
+}
+
+/// Why a candidate was not accepted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum CandidateRejection {
+    NoEdge { step: usize, from: Uuid, to: Uuid },   // nothing joins consecutive nodes
+    Invalid { issues: Vec<PathIssue> },
+    Duplicate { path_id: Uuid },                    // same nodes as an existing or earlier path
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RejectedCandidate {
+    pub candidate: HypothesisCandidate,
+    pub rejection: CandidateRejection,
+}
+
+/// Outcome of one generator call. Accepted paths are not added to the graph;
+/// add them with `MultiIntentGraph::add_hypothesis_path` and record `notes`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenerationReport {
+    pub generator: String,
+    pub question: String,
+    pub accepted: Vec<HypothesisPath>,
+    pub rejected: Vec<RejectedCandidate>,
+    pub truncated: usize,               // candidates past `MAX_CANDIDATES`
+    pub notes: Vec<ProvenanceNote>,     // one "generate-hypothesis" note per accepted path
+}
+
+/// Ask `generator` for hypotheses about `question` over the subgraph induced
+/// by `focus` (the whole graph when empty), then check the candidates against
+/// the full graph with `review_candidates`
+pub async fn generate_hypotheses<G: HypothesisGenerator>(
+    generator: &G,
+    graph: &MultiIntentGraph,
+    focus: &[Uuid],
+    question: &str,
+) -> Result<GenerationReport> {
+    let subgraph = if focus.is_empty() { graph.clone() } else { graph.subgraph(focus) };
+    let candidates = generator.generate(&subgraph, question).await?;
+    let report = review_candidates(graph, generator.name(), question, candidates);
+    tracing::info!(
+        graph = %graph.id,
+        generator = %report.generator,
+        accepted = report.accepted.len(),
+        rejected = report.rejected.len(),
+        "hypotheses generated"
+    );
+    Ok(report)
+}
+
+/// Turn candidates into hypothesis paths: fill in missing edges, validate the
+/// path structure, drop repeats of existing paths (and of each other), score
+/// the rest from their edges and stamp each with a provenance note.
+/// Path IDs are derived from the type and edges as in
+/// `generate_hypothesis_paths`, so both find the same path under one ID.
+pub fn review_candidates(
+    graph: &MultiIntentGraph,
+    generator: &str,
+    question: &str,
+    candidates: Vec<HypothesisCandidate>,
+) -> GenerationReport {
+    let truncated = candidates.len().saturating_sub(MAX_CANDIDATES);
+    let mut report = GenerationReport {
+        generator: generator.to_string(),
+        question: question.to_string(),
+        accepted: vec![],
+        rejected: vec![],
+        truncated,
+        notes: vec![],
+    };
+    for candidate in candidates.into_iter().take(MAX_CANDIDATES) {
+        match review(graph, &report.accepted, &candidate) {
+            Ok(path) => {
+                let mut rationale = format!("{} (question: {:?})", path.description, question);
+                if let Some(reason) = &candidate.rationale {
+                    rationale.push_str(&format!("; {}", reason));
+                }
+                report.notes.push(ProvenanceNote::new(graph.id, path.id, format!("llm:{}", generator), "generate-hypothesis").with_rationale(rationale));
+                report.accepted.push(path);
+            }
+            Err(rejection) => report.rejected.push(RejectedCandidate { candidate, rejection }),
+        }
+    }
+    report
+}
+
+fn review(graph: &MultiIntentGraph, accepted: &[HypothesisPath], candidate: &HypothesisCandidate) -> Result<HypothesisPath, CandidateRejection> {
+    let missing: Vec<PathIssue> = candidate.node_sequence.iter()
+        .filter(|id| !graph.intent_nodes.contains_key(id))
+        .map(|id| PathIssue::MissingNode { node: *id })
+        .collect();
+    if !missing.is_empty() {
+        return Err(CandidateRejection::Invalid { issues: missing });
+    }
+    let edge_sequence = if candidate.edge_sequence.is_empty() {
+        candidate.node_sequence.windows(2)
+            .enumerate()
+            .map(|(step, pair)| connecting_edge(graph, pair[0], pair[1]).map(|e| e.id).ok_or(CandidateRejection::NoEdge { step, from: pair[0], to: pair[1] }))
+            .collect::<Result<Vec<Uuid>, _>>()?
+    } else {
+        candidate.edge_sequence.clone()
+    };
+    let key = edge_sequence.iter().map(|e| e.to_string()).collect::<Vec<_>>().join(",");
+    let mut path = HypothesisPath {
+        id: Uuid::new_v5(&Uuid::NAMESPACE_OID, format!("{}:{}", candidate.hypothesis_type, key).as_bytes()),
+        hypothesis_type: candidate.hypothesis_type.clone(),
+        description: candidate.description.trim().to_string(),
+        node_sequence: candidate.node_sequence.clone(),
+        edge_sequence,
+        total_confidence: 0.0,
+        evidence_coverage: 0.0,
+    };
+    let issues = graph.validate_path(&path);
+    if !issues.is_empty() {
+        return Err(CandidateRejection::Invalid { issues });
+    }
+    if let Some(existing) = graph.hypothesis_paths.iter().chain(accepted).find(|p| p.id == path.id || p.node_sequence == path.node_sequence) {
+        return Err(CandidateRejection::Duplicate { path_id: existing.id });
+    }
+    path.total_confidence = graph.path_confidence(&path).unwrap_or(0.0);
+    path.evidence_coverage = graph.path_evidence_coverage(&path).unwrap_or(0.0);
+    if path.description.is_empty() {
+        let labels: Vec<&str> = path.node_sequence.iter().filter_map(|id| graph.intent_nodes.get(id).map(|n| n.label())).collect();
+        path.description = format!("{}: {}", path.hypothesis_type, labels.join(" → "));
+    }
+    Ok(path)
+}
+
+/// Strongest supporting edge from `from` to `to`, or a correlative one the
+/// other way round, ties by ID
+fn connecting_edge(graph: &MultiIntentGraph, from: Uuid, to: Uuid) -> Option<&GraphEdge> {
+    graph.edges.values()
+        .filter(|e| !e.is_refuting())
+        .filter(|e| (e.source_id == from && e.target_id == to) || (e.edge_type == EdgeType::Correlative && e.source_id == to && e.target_id == from))
+        .max_by(|a, b| graph.net_support(a).total_cmp(&graph.net_support(b)).then(b.id.cmp(&a.id)))
+}
+
+#[cfg(feature = "llm")]
+pub use http::HttpHypothesisGenerator;
+
+#[cfg(feature = "llm")]
+mod http {
+    use serde::{Serialize, Deserialize};
+    use uuid::Uuid;
+    use anyhow::{Context, Result};
+
+    use super::{HypothesisCandidate, HypothesisGenerator};
+    use crate::multi_intent_graph::MultiIntentGraph;
+
+    /// Most nodes sent to the service; larger subgraphs keep the best-connected ones
+    pub const MAX_PROMPT_NODES: usize = 200;
+
+    #[derive(Serialize)]
+    struct GenerateRequest<'a> {
+        question: &'a str,
+        nodes: Vec<PromptNode<'a>>,
+        edges: Vec<PromptEdge<'a>>,
+        max_candidates: usize,
+    }
+
+    #[derive(Serialize)]
+    struct PromptNode<'a> {
+        id: Uuid,
+        domain: String,
+        intent: &'a str,
+        label: &'a str,
+    }
+
+    #[derive(Serialize)]
+    struct PromptEdge<'a> {
+        id: Uuid,
+        source: Uuid,
+        target: Uuid,
+        label: &'a str,
+        confidence: f32,
+    }
+
+    #[derive(Deserialize)]
+    struct GenerateResponse {
+        hypotheses: Vec<HypothesisCandidate>,
+    }
+
+    /// Reference generator: POSTs the question and a compact JSON view of the
+    /// subgraph (`{question, nodes, edges, max_candidates}`) to a service
+    /// fronting an LLM, which answers `{"hypotheses": [HypothesisCandidate]}`
+    #[derive(Debug, Clone)]
+    pub struct HttpHypothesisGenerator {
+        name: String,
+        http: reqwest::Client,
+        endpoint: String,
+        api_key: Option<String>,      // sent as a bearer token
+    }
+
+    impl HttpHypothesisGenerator {
+        pub fn new(name: impl Into<String>, endpoint: impl Into<String>) -> Self {
+            Self {
+                name: name.into(),
+                http: reqwest::Client::builder()
+                    .user_agent(concat!("limit-sarscov2/", env!("CARGO_PKG_VERSION")))
+                    .timeout(std::time::Duration::from_secs(120))
+                    .build()
+                    .expect("static reqwest client configuration"),
+                endpoint: endpoint.into(),
+                api_key: None,
+            }
+        }
+
+        pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+            self.api_key = Some(api_key.into());
+            self
+        }
+    }
+
+    impl HypothesisGenerator for HttpHypothesisGenerator {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        async fn generate(&self, subgraph: &MultiIntentGraph, question: &str) -> Result<Vec<HypothesisCandidate>> {
+            let mut degree: Vec<(usize, Uuid)> = subgraph.intent_nodes.keys()
+                .map(|id| (subgraph.edges.values().filter(|e| e.source_id == *id || e.target_id == *id).count(), *id))
+                .collect();
+            degree.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+            let kept: Vec<Uuid> = degree.into_iter().take(MAX_PROMPT_NODES).map(|(_, id)| id).collect();
+            let mut edges: Vec<PromptEdge> = subgraph.edges.values()
+                .filter(|e| !e.is_refuting() && kept.contains(&e.source_id) && kept.contains(&e.target_id))
+                .map(|e| PromptEdge { id: e.id, source: e.source_id, target: e.target_id, label: &e.label, confidence: e.metadata.confidence })
+                .collect();
+            edges.sort_by_key(|e| e.id);
+            let request = GenerateRequest {
+                question,
+                nodes: kept.iter()
+                    .map(|id| &subgraph.intent_nodes[id])
+                    .map(|n| PromptNode { id: n.id, domain: format!("{:?}", n.domain), intent: &n.intent, label: n.label() })
+                    .collect(),
+                edges,
+                max_candidates: super::MAX_CANDIDATES,
+            };
+
+            let mut call = self.http.post(&self.endpoint).json(&request);
+            if let Some(key) = &self.api_key {
+                call = call.bearer_auth(key);
+            }
+            let response: GenerateResponse = call
+                .send()
+                .await
+                .with_context(|| format!("requesting hypotheses from {}", self.endpoint))?
+                .error_for_status()
+                .with_context(|| format!("{} rejected the hypothesis request", self.endpoint))?
+                .json()
+                .await
+                .with_context(|| format!("decoding hypotheses from {}", self.endpoint))?;
+            Ok(response.hypotheses)
+        }
+    }
+}
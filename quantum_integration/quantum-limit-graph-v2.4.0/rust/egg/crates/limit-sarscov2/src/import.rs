@@ -0,0 +1,224 @@
+// limit-sarscov2/src/import.rs
+// Bulk import of intent nodes and edges from JSON Lines, validated record by record
+
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+
+use crate::{
+    edges::GraphEdge,
+    multi_intent_graph::{IntentNode, MultiIntentGraph},
+    provenance::ProvenanceNote,
+    validate::{SubjectKind, validate_edge, validate_node},
+};
+
+/// Longest accepted line; longer lines are rejected without being buffered
+pub const MAX_LINE_BYTES: usize = 1 << 20;
+/// Rejections listed individually in an `ImportSummary`; the rest are only counted
+pub const MAX_REPORTED_REJECTIONS: usize = 1000;
+
+#[derive(Debug, Clone)]
+pub enum ImportRecord {
+    Node(IntentNode),
+    Edge(GraphEdge),
+}
+
+impl ImportRecord {
+    /// A bare `IntentNode` or `GraphEdge` object; objects with a `source_id` are edges
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let value: serde_json::Value = serde_json::from_str(line).map_err(|e| format!("invalid JSON: {}", e))?;
+        if !value.is_object() {
+            return Err("expected a JSON object".into());
+        }
+        if value.get("source_id").is_some() {
+            serde_json::from_value(value).map(Self::Edge).map_err(|e| format!("invalid edge: {}", e))
+        } else {
+            serde_json::from_value(value).map(Self::Node).map_err(|e| format!("invalid node: {}", e))
+        }
+    }
+
+    pub fn id(&self) -> Uuid {
+        match self {
+            Self::Node(node) => node.id,
+            Self::Edge(edge) => edge.id,
+        }
+    }
+}
+
+/// A parsed line: its 1-based number and the record or why it failed to parse
+pub type ParsedLine = (usize, Result<ImportRecord, String>);
+
+/// Splits a byte stream into lines and parses each one as it completes, so a
+/// large body is never held in memory as text
+#[derive(Debug, Default)]
+pub struct JsonlReader {
+    pending: Vec<u8>,
+    line: usize,
+    oversized: bool,              // discarding the rest of a line over MAX_LINE_BYTES
+    records: Vec<ParsedLine>,
+}
+
+impl JsonlReader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, mut chunk: &[u8]) {
+        while let Some(end) = chunk.iter().position(|&b| b == b'\n') {
+            self.buffer(&chunk[..end]);
+            self.end_line();
+            chunk = &chunk[end + 1..];
+        }
+        self.buffer(chunk);
+    }
+
+    /// Parsed lines in order; blank lines are skipped
+    pub fn finish(mut self) -> Vec<ParsedLine> {
+        if !self.pending.is_empty() || self.oversized {
+            self.end_line();
+        }
+        self.records
+    }
+
+    fn buffer(&mut self, bytes: &[u8]) {
+        if self.oversized {
+            return;
+        }
+        if self.pending.len() + bytes.len() > MAX_LINE_BYTES {
+            self.oversized = true;
+            self.pending.clear();
+        } else {
+            self.pending.extend_from_slice(bytes);
+        }
+    }
+
+    fn end_line(&mut self) {
+        self.line += 1;
+        let parsed = if std::mem::take(&mut self.oversized) {
+            Some(Err(format!("line exceeds {} bytes", MAX_LINE_BYTES)))
+        } else {
+            match std::str::from_utf8(&self.pending) {
+                Err(_) => Some(Err("line is not valid UTF-8".into())),
+                Ok(text) if text.trim().is_empty() => None,
+                Ok(text) => Some(ImportRecord::parse(text.trim())),
+            }
+        };
+        self.pending.clear();
+        if let Some(parsed) = parsed {
+            self.records.push((self.line, parsed));
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RejectedRecord {
+    pub line: usize,
+    pub id: Option<Uuid>,         // None when the line did not parse
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportSummary {
+    pub import_id: Uuid,          // provenance notes of accepted records cite "import:<import_id>"
+    pub graph_id: Uuid,
+    pub records: usize,
+    pub nodes_accepted: usize,
+    pub edges_accepted: usize,
+    pub rejected: usize,
+    pub rejections: Vec<RejectedRecord>,   // the first MAX_REPORTED_REJECTIONS
+}
+
+impl ImportSummary {
+    fn reject(&mut self, line: usize, id: Option<Uuid>, reason: String) {
+        self.rejected += 1;
+        if self.rejections.len() < MAX_REPORTED_REJECTIONS {
+            self.rejections.push(RejectedRecord { line, id, reason });
+        }
+    }
+}
+
+/// Add valid records to `graph` in line order, rejecting the rest. An edge's
+/// endpoints must already be in the graph or on an earlier line. Returns the
+/// summary and one "add" provenance note per accepted record.
+pub fn import_records(graph: &mut MultiIntentGraph, records: Vec<ParsedLine>) -> (ImportSummary, Vec<ProvenanceNote>) {
+    let mut summary = ImportSummary {
+        import_id: Uuid::new_v4(),
+        graph_id: graph.id,
+        records: records.len(),
+        nodes_accepted: 0,
+        edges_accepted: 0,
+        rejected: 0,
+        rejections: vec![],
+    };
+    let source = format!("import:{}", summary.import_id);
+    let mut notes = vec![];
+
+    for (line, parsed) in records {
+        let record = match parsed {
+            Ok(record) => record,
+            Err(reason) => {
+                summary.reject(line, None, reason);
+                continue;
+            }
+        };
+        let id = record.id();
+        if let Err(reason) = validate(graph, &record) {
+            summary.reject(line, Some(id), reason);
+            continue;
+        }
+        match record {
+            ImportRecord::Node(node) => {
+                graph.add_node(node);
+                summary.nodes_accepted += 1;
+            }
+            ImportRecord::Edge(edge) => {
+                graph.add_edge(edge);
+                summary.edges_accepted += 1;
+            }
+        }
+        notes.push(ProvenanceNote::new(graph.id, id, source.clone(), "add").with_rationale(format!("Bulk import, line {}", line)));
+    }
+
+    tracing::info!(
+        graph = %graph.id,
+        import = %summary.import_id,
+        nodes = summary.nodes_accepted,
+        edges = summary.edges_accepted,
+        rejected = summary.rejected,
+        "bulk import finished"
+    );
+    (summary, notes)
+}
+
+fn validate(graph: &MultiIntentGraph, record: &ImportRecord) -> Result<(), String> {
+    let issues = match record {
+        ImportRecord::Node(node) => {
+            if graph.intent_nodes.contains_key(&node.id) {
+                return Err(format!("node {} already exists", node.id));
+            }
+            validate_node(node)
+        }
+        ImportRecord::Edge(edge) => {
+            if graph.edges.contains_key(&edge.id) {
+                return Err(format!("edge {} already exists", edge.id));
+            }
+            for (end, id, declared) in [
+                ("source", edge.source_id, &edge.metadata.source_domain),
+                ("target", edge.target_id, &edge.metadata.target_domain),
+            ] {
+                let Some(node) = graph.intent_nodes.get(&id) else {
+                    return Err(format!("{} node {} is not in the graph", end, id));
+                };
+                let actual = format!("{:?}", node.domain);
+                if *declared != actual {
+                    return Err(format!("{}_domain is {:?} but node {} is {}", end, declared, id, actual));
+                }
+            }
+            validate_edge(edge).into_iter().chain(graph.check_edge_type(edge.id, SubjectKind::Edge, &edge.edge_type)).collect()
+        }
+    };
+    if issues.is_empty() {
+        Ok(())
+    } else {
+        Err(issues.iter().map(|i| i.rule.to_string()).collect::<Vec<_>>().join("; "))
+    }
+}
@@ -0,0 +1,244 @@
+// limit-sarscov2/src/ingest.rs
+// CORD-19 ingestion: metadata.csv + JSON full texts → CorpusDoc → RetrievalBackend
+
+use serde::{Serialize, Deserialize};
+use std::{collections::{HashMap, HashSet}, fs, path::{Path, PathBuf}};
+use uuid::Uuid;
+use anyhow::{Context, Result};
+
+use crate::{chunking::Chunker, domain::ResearchDomain, mesh::MeshTagger, retrieval::{CorpusDoc, RetrievalBackend}};
+
+/// Namespace for CORD-19 document IDs, so re-ingesting yields the same UUIDs
+const CORD19_NAMESPACE: Uuid = Uuid::from_u128(0x6c1b_2f0e_4d4a_4f5e_9a0c_c0d1_9000_0001);
+
+/// Row of CORD-19 `metadata.csv` (only the columns we use)
+#[derive(Debug, Clone, Deserialize)]
+pub struct Cord19Record {
+    pub cord_uid: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default, rename = "abstract")]
+    pub abstract_text: String,
+    #[serde(default)]
+    pub doi: String,
+    #[serde(default)]
+    pub pubmed_id: String,
+    #[serde(default)]
+    pub publish_time: String,
+    #[serde(default)]
+    pub pdf_json_files: String,   // "; "-separated relative paths
+    #[serde(default)]
+    pub pmc_json_files: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct FullTextParse {
+    #[serde(default)]
+    body_text: Vec<Paragraph>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Paragraph {
+    text: String,
+}
+
+/// Keyword-based assignment of papers to research domains
+#[derive(Debug, Clone)]
+pub struct DomainClassifier {
+    keywords: Vec<(ResearchDomain, Vec<String>)>,
+}
+
+impl Default for DomainClassifier {
+    fn default() -> Self {
+        let table: [(ResearchDomain, &[&str]); 5] = [
+            (ResearchDomain::Virology, &["spike", "ace2", "replication", "viral entry", "protease", "rbd", "receptor binding", "virion", "tmprss2"]),
+            (ResearchDomain::Immunology, &["antibod", "neutraliz", "t cell", "t-cell", "immun", "cytokine", "vaccin", "interferon"]),
+            (ResearchDomain::Genomics, &["variant", "mutation", "lineage", "genom", "sequenc", "phylogen", "omicron", "delta"]),
+            (ResearchDomain::Treatment, &["treatment", "therap", "antiviral", "remdesivir", "paxlovid", "nirmatrelvir", "dexamethasone", "clinical trial", "monoclonal"]),
+            (ResearchDomain::PublicHealth, &["mask", "distancing", "lockdown", "transmission", "policy", "surveillance", "incidence", "ventilation", "quarantine"]),
+        ];
+        Self {
+            keywords: table.iter()
+                .map(|(d, kws)| (d.clone(), kws.iter().map(|k| k.to_string()).collect()))
+                .collect(),
+        }
+    }
+}
+
+impl DomainClassifier {
+    /// Domain with the most keyword hits, or None if nothing matches
+    pub fn classify(&self, text: &str) -> Option<ResearchDomain> {
+        let text = text.to_lowercase();
+        self.keywords.iter()
+            .map(|(d, kws)| (d, kws.iter().map(|k| text.matches(k.as_str()).count()).sum::<usize>()))
+            .filter(|(_, hits)| *hits > 0)
+            .max_by_key(|(_, hits)| *hits)
+            .map(|(d, _)| d.clone())
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IngestReport {
+    pub rows_read: usize,
+    pub docs_loaded: usize,
+    pub full_texts_loaded: usize,
+    pub duplicates_skipped: usize,
+    pub empty_skipped: usize,
+    pub unclassified_skipped: usize,
+    #[serde(default)]
+    pub mesh_tagged: usize,                   // docs given at least one MeSH descriptor
+    #[serde(default)]
+    pub mesh_classified: usize,               // docs whose domain came from their MeSH descriptors
+    #[serde(default)]
+    pub chunks_loaded: usize,                 // backend entries added (one per doc when not chunking)
+    #[serde(default)]
+    pub content_duplicates_skipped: usize,    // docs/chunks whose text was already loaded
+    pub per_domain: HashMap<String, usize>,
+    pub errors: Vec<String>,
+}
+
+/// Reads a CORD-19 release directory (containing `metadata.csv` and `document_parses/`)
+#[derive(Debug, Clone)]
+pub struct Cord19Ingestor {
+    pub root: PathBuf,
+    pub include_full_text: bool,
+    pub limit: Option<usize>,
+    pub classifier: DomainClassifier,
+    pub tagger: Option<MeshTagger>,   // MeSH-tag papers and classify by descriptor; keywords are the fallback
+    pub chunker: Option<Chunker>,     // split papers before loading; None keeps one doc per paper
+}
+
+impl Cord19Ingestor {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            include_full_text: true,
+            limit: None,
+            classifier: DomainClassifier::default(),
+            tagger: Some(MeshTagger::default()),
+            chunker: Some(Chunker::default()),
+        }
+    }
+
+    /// Parse the release into corpus documents
+    pub fn read(&self) -> Result<(Vec<CorpusDoc>, IngestReport)> {
+        let metadata = self.root.join("metadata.csv");
+        let mut reader = csv::Reader::from_path(&metadata)
+            .with_context(|| format!("opening {}", metadata.display()))?;
+
+        let mut report = IngestReport::default();
+        let mut seen = HashSet::new();
+        let mut docs = vec![];
+
+        for row in reader.deserialize::<Cord19Record>() {
+            if self.limit.is_some_and(|l| docs.len() >= l) {
+                break;
+            }
+            report.rows_read += 1;
+            let record = match row {
+                Ok(r) => r,
+                Err(e) => {
+                    report.errors.push(format!("row {}: {}", report.rows_read, e));
+                    continue;
+                }
+            };
+            // metadata.csv repeats a cord_uid when a paper has several sources
+            if !seen.insert(record.cord_uid.clone()) {
+                report.duplicates_skipped += 1;
+                continue;
+            }
+
+            let mut text = [record.title.trim(), record.abstract_text.trim()]
+                .iter()
+                .filter(|s| !s.is_empty())
+                .cloned()
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            if self.include_full_text {
+                match self.full_text(&record) {
+                    Ok(Some(body)) => {
+                        text.push_str("\n\n");
+                        text.push_str(&body);
+                        report.full_texts_loaded += 1;
+                    }
+                    Ok(None) => {}
+                    Err(e) => report.errors.push(format!("{}: {}", record.cord_uid, e)),
+                }
+            }
+            if text.trim().is_empty() {
+                report.empty_skipped += 1;
+                continue;
+            }
+
+            // Title and abstract carry the topic; body text would drown it out
+            let summary = format!("{} {}", record.title, record.abstract_text);
+            let mesh = self.tagger.as_ref().map(|t| t.tag(&summary)).unwrap_or_default();
+            let by_mesh = self.tagger.as_ref().and_then(|t| t.classify(&mesh));
+            report.mesh_tagged += usize::from(!mesh.is_empty());
+            report.mesh_classified += usize::from(by_mesh.is_some());
+            let Some(domain) = by_mesh.or_else(|| self.classifier.classify(&summary)) else {
+                report.unclassified_skipped += 1;
+                continue;
+            };
+            let domain = format!("{:?}", domain);
+            *report.per_domain.entry(domain.clone()).or_insert(0) += 1;
+
+            docs.push(CorpusDoc {
+                id: Uuid::new_v5(&CORD19_NAMESPACE, record.cord_uid.as_bytes()),
+                domain,
+                text,
+                source: source_ref(&record),
+                mesh,
+            });
+        }
+
+        report.docs_loaded = docs.len();
+        Ok((docs, report))
+    }
+
+    /// Parse the release and bulk-load it into `backend`
+    pub fn load_into(&self, backend: &mut RetrievalBackend) -> Result<IngestReport> {
+        let (docs, mut report) = self.read()?;
+        let entries: Vec<CorpusDoc> = match &self.chunker {
+            Some(chunker) => docs.iter().flat_map(|d| chunker.chunk(d)).collect(),
+            None => docs,
+        };
+        let total = entries.len();
+        report.chunks_loaded = backend.extend(entries);
+        report.content_duplicates_skipped = total - report.chunks_loaded;
+        tracing::info!(docs = report.docs_loaded, chunks = report.chunks_loaded, rows = report.rows_read, "CORD-19 corpus loaded");
+        Ok(report)
+    }
+
+    /// Body text from the first available parse, preferring PMC over PDF
+    fn full_text(&self, record: &Cord19Record) -> Result<Option<String>> {
+        let candidates = record.pmc_json_files.split(';')
+            .chain(record.pdf_json_files.split(';'))
+            .map(str::trim)
+            .filter(|p| !p.is_empty());
+
+        for rel in candidates {
+            let path = self.root.join(Path::new(rel));
+            if !path.exists() {
+                continue;
+            }
+            let raw = fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?;
+            let parse: FullTextParse = serde_json::from_str(&raw).with_context(|| format!("parsing {}", path.display()))?;
+            let body = parse.body_text.into_iter().map(|p| p.text).collect::<Vec<_>>().join("\n\n");
+            if !body.trim().is_empty() {
+                return Ok(Some(body));
+            }
+        }
+        Ok(None)
+    }
+}
+
+fn source_ref(record: &Cord19Record) -> String {
+    if !record.doi.trim().is_empty() {
+        format!("doi:{}", record.doi.trim())
+    } else if !record.pubmed_id.trim().is_empty() {
+        format!("pmid:{}", record.pubmed_id.trim())
+    } else {
+        format!("cord19:{}", record.cord_uid)
+    }
+}
@@ -0,0 +1,237 @@
+// limit-sarscov2/src/intents.rs
+// Canonical research intents and automatic intent detection from question text
+
+use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::{
+    domain::ResearchDomain,
+    provenance::ProvenanceNote,
+    queries::{IntentQuery, MultiIntentQuestion},
+    scheduler::ScheduleTarget,
+};
+
+/// A canonical intent known to the registry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntentDefinition {
+    pub name: String,             // "transmissibility", "vaccine_efficacy", etc.
+    pub domain: ResearchDomain,
+    pub description: String,
+    pub keywords: Vec<String>,    // lowercase stems, matched as token prefixes
+}
+
+/// Registry of canonical intents that questions are mapped onto
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntentRegistry {
+    intents: Vec<IntentDefinition>,
+}
+
+impl IntentRegistry {
+    pub fn new() -> Self {
+        Self { intents: vec![] }
+    }
+
+    /// Register an intent, replacing any existing intent with the same name
+    pub fn register(&mut self, intent: IntentDefinition) {
+        self.intents.retain(|i| i.name != intent.name);
+        self.intents.push(intent);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&IntentDefinition> {
+        self.intents.iter().find(|i| i.name == name)
+    }
+
+    pub fn intents(&self) -> &[IntentDefinition] {
+        &self.intents
+    }
+
+    /// Exploration scheduler targets, one per registered intent
+    pub fn schedule_targets(&self) -> Vec<ScheduleTarget> {
+        self.intents.iter()
+            .map(|i| ScheduleTarget { intent: i.name.clone(), domain: format!("{:?}", i.domain) })
+            .collect()
+    }
+}
+
+impl Default for IntentRegistry {
+    /// The SARS-CoV-2 intents used throughout the multi-intent graph
+    fn default() -> Self {
+        let mut registry = Self::new();
+        let defaults: [(&str, ResearchDomain, &str, &[&str]); 5] = [
+            (
+                "transmissibility",
+                ResearchDomain::Virology,
+                "how easily the virus spreads, ACE2 binding and viral fitness",
+                &["transmissib", "transmit", "spread", "contagious", "infectiv", "ace2", "binding", "fitness", "r0"],
+            ),
+            (
+                "vaccine_efficacy",
+                ResearchDomain::Immunology,
+                "vaccine protection, booster effectiveness and breakthrough infection",
+                &["vaccin", "booster", "efficacy", "effectiveness", "breakthrough", "mrna", "dose"],
+            ),
+            (
+                "immune_escape",
+                ResearchDomain::Genomics,
+                "mutations that evade neutralizing antibodies or prior immunity",
+                &["escape", "evad", "evasion", "neutraliz", "antibod", "mutation", "variant", "reinfection"],
+            ),
+            (
+                "treatment_efficacy",
+                ResearchDomain::Treatment,
+                "antiviral and therapeutic effectiveness against infection",
+                &["treatment", "therap", "drug", "antiviral", "paxlovid", "remdesivir", "molnupiravir", "nirmatrelvir", "monoclonal", "hospitaliz"],
+            ),
+            (
+                "transmission_reduction",
+                ResearchDomain::PublicHealth,
+                "public health measures that reduce community transmission",
+                &["mask", "mandate", "policy", "ventilation", "distancing", "lockdown", "quarantine", "intervention", "community"],
+            ),
+        ];
+
+        for (name, domain, description, keywords) in defaults {
+            registry.register(IntentDefinition {
+                name: name.into(),
+                domain,
+                description: description.into(),
+                keywords: keywords.iter().map(|k| k.to_string()).collect(),
+            });
+        }
+        registry
+    }
+}
+
+/// An intent detected in a question, with its confidence score
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectedIntent {
+    pub intent: String,
+    pub domain: ResearchDomain,
+    pub confidence: f32,          // 0.0 to 1.0
+    pub matched_terms: Vec<String>,
+}
+
+/// Maps raw question text onto canonical intents from an `IntentRegistry`.
+///
+/// Scores combine keyword hits (token prefix match against the intent's
+/// stems) with a character-trigram cosine similarity between the question
+/// and the intent's description, which catches paraphrases the keyword
+/// list misses.
+#[derive(Debug, Clone)]
+pub struct IntentDetector {
+    pub registry: IntentRegistry,
+    pub min_confidence: f32,
+    pub keyword_weight: f32,      // remainder goes to trigram similarity
+}
+
+impl Default for IntentDetector {
+    fn default() -> Self {
+        Self::new(IntentRegistry::default())
+    }
+}
+
+impl IntentDetector {
+    /// `min_confidence` sits above `keyword_weight * 0.5`, the score of a
+    /// single keyword hit, so one generic term ("dose", "policy") needs a
+    /// close description match or a second keyword to count.
+    pub fn new(registry: IntentRegistry) -> Self {
+        Self { registry, min_confidence: 0.45, keyword_weight: 0.75 }
+    }
+
+    /// Detect intents in the question, strongest first
+    pub fn detect(&self, question: &str) -> Vec<DetectedIntent> {
+        let tokens = tokenize(question);
+        let question_grams = trigrams(question);
+
+        let mut detected: Vec<DetectedIntent> = self.registry.intents()
+            .iter()
+            .filter_map(|def| {
+                let matched_terms: Vec<String> = def.keywords.iter()
+                    .filter(|k| tokens.iter().any(|t| t.starts_with(k.as_str())))
+                    .cloned()
+                    .collect();
+                let keyword_score = 1.0 - 0.5f32.powi(matched_terms.len() as i32);
+                let profile = format!("{} {} {}", def.name.replace('_', " "), def.description, def.keywords.join(" "));
+                let similarity = cosine(&question_grams, &trigrams(&profile));
+                let confidence = self.keyword_weight * keyword_score + (1.0 - self.keyword_weight) * similarity;
+
+                (confidence >= self.min_confidence).then(|| DetectedIntent {
+                    intent: def.name.clone(),
+                    domain: def.domain.clone(),
+                    confidence,
+                    matched_terms,
+                })
+            })
+            .collect();
+
+        detected.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+        detected
+    }
+
+    /// Build a `MultiIntentQuestion` with one `IntentQuery` per detected intent
+    pub fn to_question(&self, question: &str) -> MultiIntentQuestion {
+        let intents = self.detect(question)
+            .into_iter()
+            .map(|d| IntentQuery {
+                id: Uuid::new_v4(),
+                domain: format!("{:?}", d.domain),
+                text: question.into(),
+                intent: Some(d.intent),
+                confidence: Some(d.confidence),
+            })
+            .collect();
+
+        MultiIntentQuestion {
+            id: Uuid::new_v4(),
+            question: question.into(),
+            intents,
+        }
+    }
+
+    /// Detect intents for a research session and record the outcome as a provenance note
+    pub fn detect_for_session(&self, session_id: &str, question: &str) -> (MultiIntentQuestion, ProvenanceNote) {
+        let question = self.to_question(question);
+        let detected = question.intents.iter()
+            .map(|q| format!("{} ({:.2})", q.intent.as_deref().unwrap_or(&q.domain), q.confidence.unwrap_or(0.0)))
+            .collect::<Vec<_>>();
+
+        let note = ProvenanceNote::new(
+            Uuid::nil(),
+            question.id,
+            format!("session:{} question:{}", session_id, question.id),
+            "detect-intents",
+        )
+        .with_rationale(if detected.is_empty() {
+            "No intents above confidence threshold".into()
+        } else {
+            format!("Detected intents: {}", detected.join(", "))
+        });
+        (question, note)
+    }
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_lowercase())
+        .collect()
+}
+
+fn trigrams(text: &str) -> HashMap<String, f32> {
+    let mut grams = HashMap::new();
+    for token in tokenize(text) {
+        let padded: Vec<char> = format!(" {} ", token).chars().collect();
+        for w in padded.windows(3) {
+            *grams.entry(w.iter().collect::<String>()).or_insert(0.0) += 1.0;
+        }
+    }
+    grams
+}
+
+fn cosine(a: &HashMap<String, f32>, b: &HashMap<String, f32>) -> f32 {
+    let dot: f32 = a.iter().filter_map(|(k, v)| b.get(k).map(|w| v * w)).sum();
+    let norm_a = a.values().map(|v| v * v).sum::<f32>().sqrt();
+    let norm_b = b.values().map(|v| v * v).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 { 0.0 } else { dot / (norm_a * norm_b) }
+}
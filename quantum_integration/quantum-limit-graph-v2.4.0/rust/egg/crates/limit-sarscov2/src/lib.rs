@@ -1,24 +1,117 @@
-pub mod domain;
-pub mod nodes;
+// Graph model types live in limit-sarscov2-core; re-exported so existing paths keep working
+pub use limit_sarscov2_core::{
+    domain, nodes, provenance, metrics, rd, multi_intent_graph, serendipity_trace, trace_cluster, trace_diagnostics,
+    edges, scheduler, mutation, lineage, lineage_tree, genome, graph_query, dedup, confidence, support, propagation, bayes,
+    intervention, counterfactual, geo, temporal, validate, events, diff, search, fuzzy, synonyms, ontology, summary, quantum, qubo, embeddings, link_prediction, similarity, prelude,
+};
+
 pub mod queries;
-pub mod provenance;
 pub mod retrieval;
-pub mod metrics;
-pub mod rd;
 pub mod governance;
 pub mod api;
-pub mod multi_intent_graph;
-pub mod serendipity_trace;
-pub mod edges;
+pub mod error;
+pub mod intents;
+pub mod edge_factory;
+pub mod export;
+pub mod ingest;
+pub mod mesh;
+pub mod evidence;
+pub mod surveillance;
+pub mod auspice;
+pub mod chunking;
+pub mod cache;
+pub mod replay;
+pub mod rd_harness;
+pub mod audit;
+pub mod event_log;
+pub mod workspace;
+pub mod limits;
+pub mod correlation;
+pub mod approval;
+pub mod prov;
+pub mod signing;
+pub mod telemetry;
+pub mod metrics_history;
+pub mod executor;
+pub mod explorer;
+pub mod decompose;
+pub mod hypothesis_gen;
+pub mod extraction;
+pub mod report;
+pub mod rdf;
+pub mod sparql;
+pub mod import;
+pub mod csv_tables;
+pub mod cytoscape;
+pub mod gexf;
+pub mod trace_store;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+#[cfg(feature = "arrow")]
+pub mod columnar;
 
 pub use domain::{ResearchDomain, SarsCov2Graph};
-pub use nodes::{VirusNode, VirologyNode, ImmunologyNode, GenomicsNode, TreatmentNode, PublicHealthNode};
-pub use queries::{IntentQuery, MultiIntentQuestion, QueryPlan};
-pub use provenance::{ProvenanceNote, GovernanceTag};
+pub use nodes::{VirusNode, VirologyNode, ImmunologyNode, GenomicsNode, TreatmentNode, PublicHealthNode, ClinicalTrialNode, HostFactorNode, VaccineNode};
+pub use queries::{IntentQuery, MultiIntentQuestion, QueryPlan, QueryPlanner};
+pub use executor::{QueryExecutor, QueryExecution, IntentOutcome};
+pub use explorer::{ExplorationAgent, ExplorationBudget, Exploration, StopReason, default_targets};
+pub use decompose::{QuestionDecomposer, QuestionEntities, Decomposition, DecompositionHook};
+pub use extraction::{ExtractionPipeline, ExtractionReport, ExtractedRelation, PatternExtractor, ProposedEdge, RelationExtractor};
+pub use report::{ReportSummary, SummaryEnhancer, enhance_summary};
+pub use hypothesis_gen::{HypothesisGenerator, HypothesisCandidate, CandidateRejection, GenerationReport, generate_hypotheses, review_candidates};
+pub use provenance::{ProvenanceNote, NoteSignature, GovernanceTag, ProvenanceIndex, ProvenanceLineage, Citation, CitationKind};
 pub use retrieval::{CorpusDoc, RetrievalBackend};
 pub use metrics::{DomainCoverage, Serendipity, SARSCoV2Metrics};
-pub use rd::{RDPoint, RDCurve, rd_from_batches};
-pub use governance::{EvidenceThresholds, GovernanceDecision, check_merge_allowed};
-pub use multi_intent_graph::{MultiIntentGraph, HypothesisPath, IntentNode};
-pub use serendipity_trace::{SerendipityTrace, ExplorationStep, HypothesisType};
-pub use edges::{EdgeType, CausalEdge, CorrelativeEdge, GraphEdge};
+pub use rd::{RDPoint, RDCurve, rd_from_batches, Knee, KneeOptions, KneeMethod};
+pub use governance::{EvidenceThresholds, GovernanceDecision, DomainCheck, GovernancePolicy, DomainThresholds, ProvenanceRequirements, EdgeRules, check_edges, check_merge_allowed};
+pub use multi_intent_graph::{MultiIntentGraph, HypothesisPath, IntentNode, PathIssue, PathPolicy, ConfidenceAggregator, HypothesisDomains, Removal, DanglingEdges};
+pub use serendipity_trace::{SerendipityTrace, ExplorationStep, HypothesisType, TraceComparison, TraceMatch, TraceMergeError, TraceSearch, search_traces};
+pub use trace_cluster::{TraceCluster, TraceClusterOptions, cluster_traces};
+pub use trace_diagnostics::{DiagnosticOptions, TraceDiagnostics, TraceIssue, TraceIssueKind};
+pub use dedup::{DedupOptions, DuplicateCandidate, NodeMerge, find_duplicates};
+pub use edges::{EdgeType, CausalEdge, CorrelativeEdge, GraphEdge, HyperEdge};
+pub use intents::{IntentRegistry, IntentDefinition, IntentDetector, DetectedIntent};
+pub use edge_factory::{EdgeFactory, EdgeFactoryError, EdgeRequest, EdgeTemplate};
+pub use scheduler::{BudgetDecision, ExplorationScheduler, SchedulerPolicy, SchedulingDecision, ScheduleTarget};
+pub use mutation::{Mutation, MutationKind, MutationParseError, parse_mutations};
+pub use export::{ExportProfile, RedactionReport, plan_redactions, sanitize};
+pub use lineage::{PangoLineage, AliasTable, LineageError};
+pub use ingest::{Cord19Ingestor, DomainClassifier, IngestReport};
+pub use mesh::{MeshDescriptor, MeshTagger};
+pub use evidence::{EuropePmcClient, EvidenceId, EvidenceRecord, ResolveReport, resolve_graph_evidence};
+pub use surveillance::{SurveillanceImporter, SurveillanceFormat, SurveillanceRecord, SurveillanceSummary, LineageFrequency};
+pub use auspice::{AuspiceImporter, AuspiceImport, TreeLineage, LineageTransition};
+pub use chunking::{Chunker, ChunkStrategy, content_hash};
+pub use replay::{TraceReplay, StepReplay, DriftStatus, replay_trace};
+pub use rd_harness::{RdHarness, DomainSweep, DomainRdResult};
+pub use audit::{AuditLog, AuditEntry, AuditQuery, snapshot_hash};
+pub use events::{EventError, GraphEvent};
+pub use diff::{ChangeEntry, ChangeKind, GraphDiff};
+pub use search::{SearchHit, SearchOptions, SearchResults};
+pub use fuzzy::NameMatch;
+pub use synonyms::{SynonymGroup, SynonymTable};
+pub use ontology::OntologyRef;
+pub use summary::{GraphSummary, SummaryFilter};
+pub use quantum::{QuantumWalkError, QuantumWalkOptions, QuantumWalkReport, WalkHit, WalkPath};
+pub use qubo::{PathSelection, PathSelectionOptions, Qubo, QuboError, QuboSolver, SelectionReport, SimulatedAnnealing, select_hypothesis_paths};
+pub use embeddings::{EmbeddingOptions, EmbeddingReport, cosine_similarity};
+pub use link_prediction::{LinkPredictionOptions, LinkPredictor, SuggestedEdge};
+pub use similarity::{SimilarNode, SimilarityOptions};
+pub use event_log::{EventLog, HistoryError, HistoryMarker, LogEntry, RecordedEvent};
+pub use limits::RateLimiter;
+pub use correlation::RequestId;
+pub use workspace::{ApiKeyConfig, ApiKeys, Caller, Workspace, WorkspaceConfig, WorkspacesConfig};
+pub use prov::{ProvFormat, export_prov};
+pub use signing::{NoteSigner, TrustedKeys, SignatureStatus, VerificationReport, verify_notes};
+pub use approval::{ChangeQueue, PendingChange, ProposedChange, ChangeStatus, ApprovalError, Reviewer};
+pub use cache::{LruCache, CacheStats};
+pub use telemetry::{GraphGauges, MultiGraphGauges, RequestMetrics, StoreGauges, render_prometheus};
+pub use metrics_history::{MetricsHistory, MetricsSnapshot, SnapshotTrigger};
+pub use trace_store::TraceStore;
+pub use rdf::{Term, Triple, project, to_ntriples};
+pub use sparql::{SparqlQuery, SparqlResult, SparqlError};
+pub use import::{JsonlReader, ImportRecord, ImportSummary, RejectedRecord, import_records};
+pub use error::{ApiError, Payload};
+pub use csv_tables::{NodeRow, EdgeRow, read_nodes_csv, read_edges_csv, write_nodes_csv, write_edges_csv, export_csv, import_csv};
+pub use cytoscape::{CytoscapeData, CytoscapeElement, CytoscapeGroup, EdgeData, NodeData, domain_color, to_cytoscape};
+pub use gexf::{GEXF_CONTENT_TYPE, to_gexf};
@@ -0,0 +1,127 @@
+// limit-sarscov2/src/limits.rs
+// Per-caller rate limiting and request body size limits for the REST API
+
+use axum::{
+    body::Body,
+    extract::{ConnectInfo, DefaultBodyLimit, Request, State},
+    http::header,
+    middleware::{self, Next},
+    response::Response,
+    Router,
+};
+use http_body_util::{LengthLimitError, Limited};
+use std::{collections::HashMap, net::SocketAddr, sync::{Arc, Mutex}, time::{Duration, Instant}};
+
+use crate::error::ApiError;
+use crate::workspace::ApiKeys;
+
+/// Buckets kept before idle full ones are dropped
+const MAX_IDLE_BUCKETS: usize = 4096;
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Token bucket per caller: `per_minute` requests a minute on average, up
+/// to `burst` at once. Callers are told apart by API key when it is one of
+/// `known_keys`, else by address; made-up keys do not get fresh buckets.
+#[derive(Debug)]
+pub struct RateLimiter {
+    per_minute: u32,
+    burst: u32,
+    known_keys: ApiKeys,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl RateLimiter {
+    pub fn new(per_minute: u32, burst: u32) -> Self {
+        Self { per_minute: per_minute.max(1), burst: burst.max(1), known_keys: ApiKeys::default(), buckets: Mutex::new(HashMap::new()) }
+    }
+
+    /// Give each of `keys` its own bucket; other callers are limited by address
+    pub fn with_keys(mut self, keys: ApiKeys) -> Self {
+        self.known_keys = keys;
+        self
+    }
+
+    /// Take a token for `caller`, or the wait until one is free
+    pub fn check(&self, caller: &str, now: Instant) -> Result<(), Duration> {
+        let per_sec = f64::from(self.per_minute) / 60.0;
+        let burst = f64::from(self.burst);
+        let mut buckets = self.buckets.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if buckets.len() > MAX_IDLE_BUCKETS {
+            buckets.retain(|_, b| b.tokens + now.duration_since(b.updated).as_secs_f64() * per_sec < burst);
+        }
+        let bucket = buckets.entry(caller.to_string()).or_insert(Bucket { tokens: burst, updated: now });
+        bucket.tokens = (bucket.tokens + now.duration_since(bucket.updated).as_secs_f64() * per_sec).min(burst);
+        bucket.updated = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / per_sec))
+        }
+    }
+}
+
+/// "key:<digest prefix>" for a known API key (`x-api-key` or bearer), else
+/// "ip:<address>", else "anonymous"
+fn caller(request: &Request, known_keys: &ApiKeys) -> String {
+    let header = |name: &str| request.headers().get(name).and_then(|v| v.to_str().ok());
+    if let Some(key) = header("x-api-key").or_else(|| header("authorization").and_then(|v| v.strip_prefix("Bearer "))) {
+        if known_keys.accepts(key.trim()) {
+            return format!("key:{}", &ApiKeys::digest(key.trim())[..16]);
+        }
+    }
+    match request.extensions().get::<ConnectInfo<SocketAddr>>() {
+        Some(ConnectInfo(addr)) => format!("ip:{}", addr.ip()),
+        None => "anonymous".into(),
+    }
+}
+
+/// Middleware: 429 with `Retry-After` once the caller's bucket is empty
+pub async fn limit_rate(State(limiter): State<Arc<RateLimiter>>, request: Request, next: Next) -> Result<Response, ApiError> {
+    let caller = caller(&request, &limiter.known_keys);
+    if let Err(wait) = limiter.check(&caller, Instant::now()) {
+        tracing::warn!(%caller, path = %request.uri().path(), "rate limited");
+        return Err(ApiError::TooManyRequests { retry_after: wait.as_secs().max(1) });
+    }
+    Ok(next.run(request).await)
+}
+
+/// Middleware: 413 for a declared `Content-Length` over `max_bytes`; other
+/// bodies fail with `LengthLimitError` once they stream past it
+pub async fn limit_body(State(max_bytes): State<usize>, request: Request, next: Next) -> Result<Response, ApiError> {
+    let declared = request.headers().get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok());
+    if declared.is_some_and(|n| n > max_bytes as u64) {
+        return Err(ApiError::PayloadTooLarge(format!("request body is over the {} byte limit", max_bytes)));
+    }
+    Ok(next.run(request.map(|body| Body::new(Limited::new(body, max_bytes)))).await)
+}
+
+/// Whether a body read failed on the size limit
+pub fn is_too_large(e: &axum::Error) -> bool {
+    let mut source: Option<&(dyn std::error::Error + 'static)> = Some(e);
+    while let Some(err) = source {
+        if err.is::<LengthLimitError>() {
+            return true;
+        }
+        source = err.source();
+    }
+    false
+}
+
+/// Apply the body limit and, if given, the rate limiter to every route of `app`
+pub fn apply(app: Router, max_body_bytes: usize, limiter: Option<Arc<RateLimiter>>) -> Router {
+    let app = app
+        .layer(middleware::from_fn_with_state(max_body_bytes, limit_body))
+        .layer(DefaultBodyLimit::max(max_body_bytes));
+    match limiter {
+        Some(limiter) => app.layer(middleware::from_fn_with_state(limiter, limit_rate)),
+        None => app,
+    }
+}
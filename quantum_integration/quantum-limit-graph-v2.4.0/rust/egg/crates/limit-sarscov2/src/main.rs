@@ -3,7 +3,7 @@ use axum::Router;
 use std::net::SocketAddr;
 use tracing_subscriber::EnvFilter;
 
-use limit_sarscov2::{api, domain::SarsCov2Graph, nodes::VirusNode};
+use limit_sarscov2::{api, governance::GovernancePolicy, nodes::VirusNode};
 
 #[tokio::main]
 async fn main() {
@@ -12,14 +12,124 @@ async fn main() {
     let root = VirusNode { id: uuid::Uuid::new_v4(), name: "SARS-CoV-2".into(), genome_kb: 30.0 };
     let graph = limit_sarscov2::domain::SarsCov2Graph::new(root);
 
+    // Governance policy: $SARSCOV2_GOVERNANCE_POLICY, else ./governance.toml if present
+    let governance_path = std::env::var_os("SARSCOV2_GOVERNANCE_POLICY")
+        .map(std::path::PathBuf::from)
+        .or_else(|| Some(std::path::PathBuf::from("governance.toml")).filter(|p| p.exists()));
+    let governance = match &governance_path {
+        Some(path) => GovernancePolicy::load(path).expect("invalid governance policy"),
+        None => GovernancePolicy::default(),
+    };
+
+    // Governance audit log: $SARSCOV2_AUDIT_LOG, else ./governance-audit.jsonl
+    let audit_path = std::env::var("SARSCOV2_AUDIT_LOG").unwrap_or_else(|_| "governance-audit.jsonl".into());
+    let audit = limit_sarscov2::AuditLog::open(&audit_path).expect("unreadable governance audit log");
+
+    // Graph event log: $SARSCOV2_EVENT_LOG, else ./graph-events.jsonl; multi-intent graphs are rebuilt from it
+    let events_path = std::env::var("SARSCOV2_EVENT_LOG").unwrap_or_else(|_| "graph-events.jsonl".into());
+    let events = limit_sarscov2::EventLog::open(&events_path).expect("unreadable graph event log");
+    let multi_graphs = events.graphs();
+
+    // Pending changes: $SARSCOV2_CHANGES_LOG, else ./pending-changes.jsonl; proposals survive restarts
+    let changes_path = std::env::var("SARSCOV2_CHANGES_LOG").unwrap_or_else(|_| "pending-changes.jsonl".into());
+    let changes = limit_sarscov2::ChangeQueue::open(&changes_path).expect("unreadable pending changes log");
+
+    // Provenance signing: hex seed in $SARSCOV2_SIGNING_KEY; extra verifier keys as
+    // "id=hex,id=hex" in $SARSCOV2_TRUSTED_KEYS
+    let signer = std::env::var("SARSCOV2_SIGNING_KEY").ok().map(|seed| {
+        let key_id = std::env::var("SARSCOV2_SIGNING_KEY_ID").unwrap_or_else(|_| "server".into());
+        limit_sarscov2::NoteSigner::from_seed_hex(key_id, &seed).expect("invalid SARSCOV2_SIGNING_KEY")
+    });
+    let mut trusted_keys = limit_sarscov2::TrustedKeys::new();
+    if let Some(signer) = &signer {
+        trusted_keys.add(signer.key_id(), signer.verifying_key());
+    }
+    for entry in std::env::var("SARSCOV2_TRUSTED_KEYS").unwrap_or_default().split(',').filter(|e| !e.trim().is_empty()) {
+        let (key_id, public_hex) = entry.split_once('=').expect("SARSCOV2_TRUSTED_KEYS entries are id=hex");
+        trusted_keys.add_hex(key_id.trim(), public_hex).expect("invalid key in SARSCOV2_TRUSTED_KEYS");
+    }
+
     let state = api::AppState {
         graphs: std::sync::Arc::new(std::sync::Mutex::new(vec![graph])),
-        provenance: std::sync::Arc::new(std::sync::Mutex::new(vec![])),
-        rd_curves: std::sync::Arc::new(std::sync::Mutex::new(vec![])),
+        provenance: std::sync::Arc::new(std::sync::Mutex::new(Default::default())),
+        rd_curves: std::sync::Arc::new(std::sync::Mutex::new(Default::default())),
+        multi_graphs: std::sync::Arc::new(std::sync::Mutex::new(multi_graphs)),
+        retrieval: std::sync::Arc::new(std::sync::Mutex::new(limit_sarscov2::RetrievalBackend::new(vec![]))),
+        traces: std::sync::Arc::new(std::sync::Mutex::new(Default::default())),
+        governance: std::sync::Arc::new(std::sync::Mutex::new(governance)),
+        governance_path,
+        audit: std::sync::Arc::new(std::sync::Mutex::new(audit)),
+        changes: std::sync::Arc::new(std::sync::Mutex::new(changes)),
+        events: std::sync::Arc::new(std::sync::Mutex::new(events)),
+        signer: signer.map(std::sync::Arc::new),
+        trusted_keys: std::sync::Arc::new(trusted_keys),
+        requests: Default::default(),
+        metrics_history: Default::default(),
     };
 
-    let app: Router = api::router(state);
+    // Metrics history sampling: every $SARSCOV2_METRICS_INTERVAL_SECS (default 300; 0 disables).
+    // Snapshots are also taken whenever a graph is mutated over the API.
+    let interval_secs: u64 = std::env::var("SARSCOV2_METRICS_INTERVAL_SECS").ok()
+        .map(|v| v.parse().expect("SARSCOV2_METRICS_INTERVAL_SECS must be a number of seconds"))
+        .unwrap_or(300);
+    if interval_secs > 0 {
+        api::spawn_metrics_sampler(state.clone(), std::time::Duration::from_secs(interval_secs));
+    }
+
+    // Workspaces: $SARSCOV2_WORKSPACES names a TOML file of `[[workspace]]` tables, each
+    // served under /w/<name> with its own graphs, policy, logs and API keys. The unprefixed
+    // routes and gRPC have no key check, so they are not served alongside workspaces.
+    let workspaces: Vec<limit_sarscov2::Workspace> = match std::env::var_os("SARSCOV2_WORKSPACES") {
+        Some(path) => limit_sarscov2::WorkspacesConfig::load(path).expect("invalid workspaces file")
+            .workspaces.iter()
+            .map(|config| limit_sarscov2::Workspace::open(config, &state).expect("cannot open workspace"))
+            .collect(),
+        None => vec![],
+    };
+
+    // gRPC (feature "grpc") on $SARSCOV2_GRPC_ADDR, default 0.0.0.0:50051, sharing the REST state
+    #[cfg(feature = "grpc")]
+    if !workspaces.is_empty() {
+        tracing::warn!("gRPC is not served when workspaces are configured");
+    } else {
+        let grpc_addr: SocketAddr = std::env::var("SARSCOV2_GRPC_ADDR").unwrap_or_else(|_| "0.0.0.0:50051".into())
+            .parse().expect("SARSCOV2_GRPC_ADDR must be host:port");
+        let service = limit_sarscov2::grpc::GrpcService::new(state.clone()).into_server();
+        tracing::info!("Starting gRPC on {}", grpc_addr);
+        tokio::spawn(async move {
+            if let Err(e) = tonic::transport::Server::builder().add_service(service).serve(grpc_addr).await {
+                tracing::error!(error = %e, "gRPC server stopped");
+            }
+        });
+    }
+
+    if interval_secs > 0 {
+        for ws in &workspaces {
+            api::spawn_metrics_sampler(ws.state.clone(), std::time::Duration::from_secs(interval_secs));
+        }
+    }
+
+    // Limits: request bodies up to $SARSCOV2_MAX_BODY_BYTES (default 32 MiB); with
+    // $SARSCOV2_RATE_LIMIT set, that many requests a minute per workspace API key or
+    // address, in bursts of up to $SARSCOV2_RATE_BURST (default the per-minute rate)
+    let env_number = |name: &str| std::env::var(name).ok()
+        .map(|v| v.parse::<u32>().unwrap_or_else(|_| panic!("{} must be a whole number", name)));
+    let max_body_bytes = env_number("SARSCOV2_MAX_BODY_BYTES").map_or(32 << 20, |n| n as usize);
+    let limiter = env_number("SARSCOV2_RATE_LIMIT").filter(|&n| n > 0).map(|per_minute| {
+        let burst = env_number("SARSCOV2_RATE_BURST").unwrap_or(per_minute);
+        let keys = limit_sarscov2::ApiKeys::union(workspaces.iter().map(|ws| ws.keys.as_ref()));
+        std::sync::Arc::new(limit_sarscov2::RateLimiter::new(per_minute, burst).with_keys(keys))
+    });
+
+    let app: Router = if workspaces.is_empty() {
+        api::router(state)
+    } else {
+        limit_sarscov2::workspace::router(&workspaces)
+    };
+    let app = limit_sarscov2::limits::apply(app, max_body_bytes, limiter)
+        .layer(axum::middleware::from_fn(limit_sarscov2::correlation::assign_request_id));
     let addr: SocketAddr = "0.0.0.0:8080".parse().unwrap();
     tracing::info!("Starting API on {}", addr);
-    axum::serve(tokio::net::TcpListener::bind(addr).await.unwrap(), app).await.unwrap();
+    let service = app.into_make_service_with_connect_info::<SocketAddr>();
+    axum::serve(tokio::net::TcpListener::bind(addr).await.unwrap(), service).await.unwrap();
 }
@@ -0,0 +1,152 @@
+// limit-sarscov2/src/mesh.rs
+// MeSH descriptor tagging of corpus documents: dictionary lookup of entry terms
+
+use serde::{Serialize, Deserialize};
+
+use crate::domain::ResearchDomain;
+
+/// A MeSH descriptor, e.g. `D000086382` "COVID-19"
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MeshDescriptor {
+    pub ui: String,            // descriptor unique ID, "D000086382"
+    pub heading: String,       // preferred heading, "COVID-19"
+}
+
+impl MeshDescriptor {
+    /// Whether `term` is this descriptor's UI or heading (case-insensitive)
+    pub fn matches(&self, term: &str) -> bool {
+        let term = term.trim();
+        self.ui.eq_ignore_ascii_case(term) || self.heading.eq_ignore_ascii_case(term)
+    }
+}
+
+/// Descriptors the default tagger knows: (UI, heading, research domain, entry terms).
+/// Entry terms are matched case-insensitively at word boundaries; the heading
+/// itself is always an entry term.
+const DICTIONARY: &[(&str, &str, Option<ResearchDomain>, &[&str])] = &[
+    ("D000086382", "COVID-19", None, &["covid-19", "covid19", "2019-ncov infection", "coronavirus disease 2019"]),
+    ("D000086402", "SARS-CoV-2", Some(ResearchDomain::Virology), &["sars-cov-2", "2019-ncov", "severe acute respiratory syndrome coronavirus 2"]),
+    ("D064370", "Spike Glycoprotein, Coronavirus", Some(ResearchDomain::Virology), &["spike protein", "spike glycoprotein", "s protein"]),
+    ("D000085962", "Angiotensin-Converting Enzyme 2", Some(ResearchDomain::Virology), &["ace2", "ace-2", "angiotensin-converting enzyme 2"]),
+    ("D053586", "Virus Internalization", Some(ResearchDomain::Virology), &["viral entry", "virus entry", "membrane fusion"]),
+    ("D014779", "Virus Replication", Some(ResearchDomain::Virology), &["viral replication", "virus replication"]),
+    ("D000914", "Antibodies, Viral", Some(ResearchDomain::Immunology), &["antibody", "antibodies", "viral antibodies"]),
+    ("D057134", "Antibodies, Neutralizing", Some(ResearchDomain::Immunology), &["neutralizing antibody", "neutralizing antibodies", "neutralising antibodies"]),
+    ("D013601", "T-Lymphocytes", Some(ResearchDomain::Immunology), &["t cell", "t cells", "t-cell", "t-cells", "t lymphocytes"]),
+    ("D000086663", "COVID-19 Vaccines", Some(ResearchDomain::Immunology), &["covid-19 vaccine", "covid-19 vaccines", "mrna vaccine", "bnt162b2", "mrna-1273"]),
+    ("D007114", "Immunization", Some(ResearchDomain::Immunology), &["vaccination", "immunization", "booster dose"]),
+    ("D009154", "Mutation", Some(ResearchDomain::Genomics), &["mutation", "mutations", "substitution", "substitutions"]),
+    ("D010802", "Phylogeny", Some(ResearchDomain::Genomics), &["phylogeny", "phylogenetic", "phylogenetics"]),
+    ("D000073336", "Whole Genome Sequencing", Some(ResearchDomain::Genomics), &["whole genome sequencing", "genome sequencing", "genomic sequencing"]),
+    ("D016679", "Genome, Viral", Some(ResearchDomain::Genomics), &["viral genome", "genome"]),
+    ("D011480", "Protease Inhibitors", Some(ResearchDomain::Treatment), &["protease inhibitor", "protease inhibitors", "nirmatrelvir", "paxlovid"]),
+    ("C000606551", "remdesivir", Some(ResearchDomain::Treatment), &["remdesivir", "veklury"]),
+    ("D003907", "Dexamethasone", Some(ResearchDomain::Treatment), &["dexamethasone"]),
+    ("D000998", "Antiviral Agents", Some(ResearchDomain::Treatment), &["antiviral", "antivirals", "antiviral agents"]),
+    ("D000911", "Antibodies, Monoclonal", Some(ResearchDomain::Treatment), &["monoclonal antibody", "monoclonal antibodies"]),
+    ("D016032", "Randomized Controlled Trials as Topic", Some(ResearchDomain::Treatment), &["randomized controlled trial", "randomised controlled trial", "rct"]),
+    ("D000086783", "Physical Distancing", Some(ResearchDomain::PublicHealth), &["physical distancing", "social distancing"]),
+    ("D000088302", "Masks", Some(ResearchDomain::PublicHealth), &["mask", "masks", "face mask", "face masks", "face covering"]),
+    ("D011795", "Quarantine", Some(ResearchDomain::PublicHealth), &["quarantine", "lockdown", "stay-at-home order"]),
+    ("D000073640", "Contact Tracing", Some(ResearchDomain::PublicHealth), &["contact tracing"]),
+    ("D062665", "Epidemiological Monitoring", Some(ResearchDomain::PublicHealth), &["surveillance", "epidemiological monitoring", "wastewater monitoring"]),
+];
+
+/// Dictionary-based MeSH tagger: a descriptor is assigned when any of its
+/// entry terms occurs in the text as whole words
+#[derive(Debug, Clone)]
+pub struct MeshTagger {
+    entries: Vec<(MeshDescriptor, Option<ResearchDomain>, Vec<String>)>,
+}
+
+impl Default for MeshTagger {
+    fn default() -> Self {
+        let mut tagger = Self { entries: vec![] };
+        for (ui, heading, domain, terms) in DICTIONARY {
+            let terms: Vec<String> = terms.iter().map(|t| t.to_string()).collect();
+            tagger.add(MeshDescriptor { ui: ui.to_string(), heading: heading.to_string() }, domain.clone(), &terms);
+        }
+        tagger
+    }
+}
+
+impl MeshTagger {
+    /// Tagger with no descriptors
+    pub fn empty() -> Self {
+        Self { entries: vec![] }
+    }
+
+    /// Add `descriptor` with its entry terms, or add the terms to it if known
+    pub fn add(&mut self, descriptor: MeshDescriptor, domain: Option<ResearchDomain>, terms: &[String]) {
+        let terms = std::iter::once(&descriptor.heading)
+            .chain(terms)
+            .map(|t| t.trim().to_lowercase())
+            .filter(|t| !t.is_empty());
+        match self.entries.iter_mut().find(|(d, _, _)| d.ui == descriptor.ui) {
+            Some((_, _, known)) => {
+                for term in terms {
+                    if !known.contains(&term) {
+                        known.push(term);
+                    }
+                }
+            }
+            None => {
+                let mut kept: Vec<String> = vec![];
+                for term in terms {
+                    if !kept.contains(&term) {
+                        kept.push(term);
+                    }
+                }
+                self.entries.push((descriptor, domain, kept));
+            }
+        }
+    }
+
+    pub fn descriptors(&self) -> Vec<&MeshDescriptor> {
+        self.entries.iter().map(|(d, _, _)| d).collect()
+    }
+
+    /// Descriptor with UI or heading `term`
+    pub fn lookup(&self, term: &str) -> Option<&MeshDescriptor> {
+        self.entries.iter().map(|(d, _, _)| d).find(|d| d.matches(term))
+    }
+
+    /// Descriptors whose entry terms occur in `text`, in dictionary order
+    pub fn tag(&self, text: &str) -> Vec<MeshDescriptor> {
+        let text = text.to_lowercase();
+        self.entries.iter()
+            .filter(|(_, _, terms)| terms.iter().any(|t| contains_words(&text, t)))
+            .map(|(d, _, _)| d.clone())
+            .collect()
+    }
+
+    /// Research domain most of `descriptors` belong to, ties going to the one
+    /// seen first; None if none has a domain
+    pub fn classify(&self, descriptors: &[MeshDescriptor]) -> Option<ResearchDomain> {
+        let mut counts: Vec<(&ResearchDomain, usize)> = vec![];
+        let domains = descriptors.iter()
+            .filter_map(|m| self.entries.iter().find(|(d, _, _)| d.ui == m.ui))
+            .filter_map(|(_, domain, _)| domain.as_ref());
+        for domain in domains {
+            match counts.iter_mut().find(|(d, _)| std::mem::discriminant(*d) == std::mem::discriminant(domain)) {
+                Some((_, n)) => *n += 1,
+                None => counts.push((domain, 1)),
+            }
+        }
+        counts.iter()
+            .fold(None, |best: Option<&(&ResearchDomain, usize)>, c| match best {
+                Some(top) if top.1 >= c.1 => best,
+                _ => Some(c),
+            })
+            .map(|(d, _)| (*d).clone())
+    }
+}
+
+/// Whether `term` occurs in `text` (both lowercase) not inside a longer word
+fn contains_words(text: &str, term: &str) -> bool {
+    text.match_indices(term).any(|(at, _)| {
+        let before = text[..at].chars().next_back();
+        let after = text[at + term.len()..].chars().next();
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
+}
@@ -0,0 +1,93 @@
+// limit-sarscov2/src/metrics_history.rs
+// Snapshots of graph metrics over time, for plotting coverage and serendipity across a campaign
+
+use serde::{Serialize, Deserialize};
+use std::collections::{HashMap, VecDeque};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{domain::SarsCov2Graph, metrics::SARSCoV2Metrics, multi_intent_graph::{GraphStatistics, MultiIntentGraph}};
+
+/// Snapshots kept per graph before the oldest are dropped
+pub const DEFAULT_MAX_SNAPSHOTS: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SnapshotTrigger {
+    Mutation,     // taken right after the graph changed
+    Schedule,     // periodic sampler
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsSnapshot {
+    pub graph_id: Uuid,
+    pub recorded_at: DateTime<Utc>,
+    pub trigger: SnapshotTrigger,
+    pub metrics: SARSCoV2Metrics,
+    pub statistics: Option<GraphStatistics>,   // multi-intent graphs only
+}
+
+impl MetricsSnapshot {
+    pub fn of_graph(graph: &SarsCov2Graph, trigger: SnapshotTrigger) -> Self {
+        Self {
+            graph_id: graph.id,
+            recorded_at: Utc::now(),
+            trigger,
+            metrics: SARSCoV2Metrics::compute(graph),
+            statistics: None,
+        }
+    }
+
+    /// Metrics of the base graph plus the multi-intent statistics
+    pub fn of_multi_graph(graph: &MultiIntentGraph, trigger: SnapshotTrigger) -> Self {
+        Self {
+            graph_id: graph.id,
+            recorded_at: Utc::now(),
+            trigger,
+            metrics: SARSCoV2Metrics::compute(&graph.base_graph),
+            statistics: Some(graph.statistics()),
+        }
+    }
+}
+
+/// Per-graph snapshot series in recording order, capped at `max_per_graph`
+#[derive(Debug, Clone)]
+pub struct MetricsHistory {
+    series: HashMap<Uuid, VecDeque<MetricsSnapshot>>,
+    max_per_graph: usize,
+}
+
+impl Default for MetricsHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_SNAPSHOTS)
+    }
+}
+
+impl MetricsHistory {
+    pub fn new(max_per_graph: usize) -> Self {
+        Self { series: HashMap::new(), max_per_graph: max_per_graph.max(1) }
+    }
+
+    pub fn record(&mut self, snapshot: MetricsSnapshot) {
+        let series = self.series.entry(snapshot.graph_id).or_default();
+        series.push_back(snapshot);
+        while series.len() > self.max_per_graph {
+            series.pop_front();
+        }
+    }
+
+    /// Snapshots of `graph_id` recorded at or after `since`, oldest first
+    pub fn since(&self, graph_id: Uuid, since: Option<DateTime<Utc>>) -> Vec<&MetricsSnapshot> {
+        self.series.get(&graph_id)
+            .map(|s| s.iter().filter(|snap| since.is_none_or(|t| snap.recorded_at >= t)).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn len(&self, graph_id: Uuid) -> usize {
+        self.series.get(&graph_id).map_or(0, VecDeque::len)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.series.values().all(VecDeque::is_empty)
+    }
+}
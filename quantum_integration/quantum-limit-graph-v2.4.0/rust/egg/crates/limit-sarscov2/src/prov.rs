@@ -0,0 +1,255 @@
+// limit-sarscov2/src/prov.rs
+// W3C PROV-O export of provenance notes and the graph entities they describe
+
+use serde::{Serialize, Deserialize};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use crate::{multi_intent_graph::MultiIntentGraph, provenance::ProvenanceNote};
+
+/// Vocabulary for this graph's activity classes and properties
+pub const SC2_NS: &str = "https://w3id.org/sarscov2-kg/ns#";
+
+pub(crate) const PREFIXES: [(&str, &str); 5] = [
+    ("prov", "http://www.w3.org/ns/prov#"),
+    ("rdfs", "http://www.w3.org/2000/01/rdf-schema#"),
+    ("dcterms", "http://purl.org/dc/terms/"),
+    ("xsd", "http://www.w3.org/2001/XMLSchema#"),
+    ("sc2", SC2_NS),
+];
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ProvFormat {
+    #[default]
+    Turtle,
+    JsonLd,
+}
+
+impl ProvFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Turtle => "text/turtle",
+            Self::JsonLd => "application/ld+json",
+        }
+    }
+}
+
+/// PROV activity class for a note's `operation`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProvActivity {
+    Add,      // "add", "add-node", "add-edge"
+    Merge,
+    Split,
+    Remove,   // "remove", "delete": the subject is invalidated
+    Other,    // e.g. "resolve-evidence": the subject is influenced, not generated
+}
+
+impl ProvActivity {
+    pub fn from_operation(operation: &str) -> Self {
+        let op = operation.trim().to_ascii_lowercase();
+        if op.starts_with("add") {
+            Self::Add
+        } else if op.starts_with("merge") {
+            Self::Merge
+        } else if op.starts_with("split") {
+            Self::Split
+        } else if op.starts_with("remove") || op.starts_with("delete") {
+            Self::Remove
+        } else {
+            Self::Other
+        }
+    }
+
+    fn class(&self) -> Option<&'static str> {
+        match self {
+            Self::Add => Some("sc2:Add"),
+            Self::Merge => Some("sc2:Merge"),
+            Self::Split => Some("sc2:Split"),
+            Self::Remove => Some("sc2:Remove"),
+            Self::Other => None,
+        }
+    }
+
+    /// Add, merge and split produce the subject
+    fn generates(&self) -> bool {
+        matches!(self, Self::Add | Self::Merge | Self::Split)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Object {
+    Iri(String),      // prefixed name or <absolute IRI>
+    Literal(String),
+}
+
+/// Statements grouped by subject, in insertion order of first mention
+#[derive(Default)]
+struct Statements {
+    order: Vec<String>,
+    by_subject: BTreeMap<String, Vec<(&'static str, Object)>>,
+}
+
+impl Statements {
+    fn add(&mut self, subject: &str, predicate: &'static str, object: Object) {
+        let entries = self.by_subject.entry(subject.to_string()).or_insert_with(|| {
+            self.order.push(subject.to_string());
+            vec![]
+        });
+        if !entries.iter().any(|(p, o)| *p == predicate && *o == object) {
+            entries.push((predicate, object));
+        }
+    }
+}
+
+pub(crate) fn uuid_iri(id: Uuid) -> String {
+    format!("urn:uuid:{}", id)
+}
+
+fn urn(id: Uuid) -> String {
+    format!("<{}>", uuid_iri(id))
+}
+
+/// IRI for a cited source: DOIs resolve via doi.org, URLs stay as they are,
+/// anything else gets a stable name-based URN
+pub(crate) fn source_iri(source: &str) -> String {
+    let s = source.trim();
+    let lower = s.to_ascii_lowercase();
+    if let Some(doi) = lower.strip_prefix("doi:").or_else(|| lower.starts_with("10.").then_some(lower.as_str())) {
+        return format!("https://doi.org/{}", doi.trim());
+    }
+    if lower.starts_with("https://") || lower.starts_with("http://") {
+        return s.to_string();
+    }
+    uuid_iri(Uuid::new_v5(&Uuid::NAMESPACE_URL, s.as_bytes()))
+}
+
+/// PROV-O statements for `notes` about entities of `graph`
+fn statements(graph: &MultiIntentGraph, notes: &[&ProvenanceNote]) -> Statements {
+    let mut st = Statements::default();
+    let graph_iri = urn(graph.id);
+    st.add(&graph_iri, "a", Object::Iri("prov:Collection".into()));
+    st.add(&graph_iri, "rdfs:label", Object::Literal("SARS-CoV-2 multi-intent graph".into()));
+
+    for note in notes {
+        let activity = urn(note.id);
+        let kind = ProvActivity::from_operation(&note.operation);
+        st.add(&activity, "a", Object::Iri("prov:Activity".into()));
+        if let Some(class) = kind.class() {
+            st.add(&activity, "a", Object::Iri(class.into()));
+        }
+        st.add(&activity, "sc2:operation", Object::Literal(note.operation.clone()));
+        if let Some(r) = &note.rationale {
+            st.add(&activity, "rdfs:comment", Object::Literal(r.clone()));
+        }
+
+        let source = format!("<{}>", source_iri(&note.source));
+        st.add(&source, "a", Object::Iri("prov:Entity".into()));
+        st.add(&source, "rdfs:label", Object::Literal(note.source.clone()));
+        st.add(&activity, "prov:used", Object::Iri(source.clone()));
+        if let Some(actor) = &note.actor {
+            let agent = format!("<{}>", source_iri(&format!("actor:{}", actor)));
+            st.add(&agent, "a", Object::Iri("prov:Agent".into()));
+            st.add(&agent, "rdfs:label", Object::Literal(actor.clone()));
+            st.add(&activity, "prov:wasAssociatedWith", Object::Iri(agent));
+        }
+
+        if note.subject_id.is_nil() {
+            continue;
+        }
+        let subject = urn(note.subject_id);
+        st.add(&subject, "a", Object::Iri("prov:Entity".into()));
+        if let Some(node) = graph.intent_nodes.get(&note.subject_id) {
+            st.add(&subject, "a", Object::Iri("sc2:Node".into()));
+            st.add(&subject, "rdfs:label", Object::Literal(node.label().to_string()));
+        } else if let Some(edge) = graph.edges.get(&note.subject_id) {
+            st.add(&subject, "a", Object::Iri("sc2:Edge".into()));
+            st.add(&subject, "rdfs:label", Object::Literal(edge.label.clone()));
+        }
+        st.add(&subject, "dcterms:isPartOf", Object::Iri(graph_iri.clone()));
+        st.add(&graph_iri, "prov:hadMember", Object::Iri(subject.clone()));
+        if kind == ProvActivity::Remove {
+            st.add(&subject, "prov:wasInvalidatedBy", Object::Iri(activity.clone()));
+            continue;
+        }
+        if kind.generates() {
+            st.add(&subject, "prov:wasGeneratedBy", Object::Iri(activity.clone()));
+        } else {
+            st.add(&subject, "prov:wasInfluencedBy", Object::Iri(activity.clone()));
+        }
+        st.add(&subject, "prov:wasDerivedFrom", Object::Iri(source));
+    }
+    st
+}
+
+/// Export the notes recorded for `graph` as PROV-O. Notes of other graphs are skipped.
+pub fn export_prov<'a>(graph: &MultiIntentGraph, notes: impl IntoIterator<Item = &'a ProvenanceNote>, format: ProvFormat) -> String {
+    let notes: Vec<&ProvenanceNote> = notes.into_iter().filter(|n| n.graph_id == graph.id).collect();
+    let st = statements(graph, &notes);
+    tracing::info!(graph = %graph.id, notes = notes.len(), ?format, "provenance exported as PROV-O");
+    match format {
+        ProvFormat::Turtle => to_turtle(&st),
+        ProvFormat::JsonLd => serde_json::to_string_pretty(&to_json_ld(&st)).unwrap_or_default(),
+    }
+}
+
+fn to_turtle(st: &Statements) -> String {
+    let mut out = String::new();
+    for (prefix, iri) in PREFIXES {
+        out.push_str(&format!("@prefix {}: <{}> .\n", prefix, iri));
+    }
+    for subject in &st.order {
+        out.push('\n');
+        out.push_str(subject);
+        let entries = &st.by_subject[subject];
+        for (i, (predicate, object)) in entries.iter().enumerate() {
+            let object = match object {
+                Object::Iri(iri) => iri.clone(),
+                Object::Literal(text) => turtle_literal(text),
+            };
+            let end = if i + 1 == entries.len() { " ." } else { " ;" };
+            out.push_str(&format!("\n    {} {}{}", predicate, object, end));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn turtle_literal(text: &str) -> String {
+    let escaped = text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n").replace('\r', "\\r");
+    format!("\"{}\"", escaped)
+}
+
+fn to_json_ld(st: &Statements) -> Value {
+    let context: Map<String, Value> = PREFIXES.iter()
+        .map(|(p, iri)| (p.to_string(), Value::String(iri.to_string())))
+        .collect();
+    let id = |iri: &str| iri.trim_start_matches('<').trim_end_matches('>').to_string();
+
+    let nodes: Vec<Value> = st.order.iter()
+        .map(|subject| {
+            let mut obj = Map::new();
+            obj.insert("@id".into(), Value::String(id(subject)));
+            let mut types = vec![];
+            for (predicate, object) in &st.by_subject[subject] {
+                let value = match object {
+                    Object::Iri(iri) if *predicate == "a" => {
+                        types.push(Value::String(id(iri)));
+                        continue;
+                    }
+                    Object::Iri(iri) => json!({ "@id": id(iri) }),
+                    Object::Literal(text) => Value::String(text.clone()),
+                };
+                match obj.get_mut(*predicate) {
+                    Some(Value::Array(values)) => values.push(value),
+                    Some(existing) => *existing = Value::Array(vec![existing.take(), value]),
+                    None => { obj.insert(predicate.to_string(), value); }
+                }
+            }
+            obj.insert("@type".into(), Value::Array(types));
+            Value::Object(obj)
+        })
+        .collect();
+    json!({ "@context": context, "@graph": nodes })
+}
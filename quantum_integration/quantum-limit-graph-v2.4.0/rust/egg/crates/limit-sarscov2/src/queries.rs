@@ -6,6 +6,10 @@ pub struct IntentQuery {
     pub id: Uuid,
     pub domain: String,          // "Virology", "Genomics"
     pub text: String,            // e.g., "Which mutations increase transmissibility?"
+    #[serde(default)]
+    pub intent: Option<String>,  // canonical intent, when detected
+    #[serde(default)]
+    pub confidence: Option<f32>, // detection confidence
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,3 +25,41 @@ pub struct QueryPlan {
     pub description: String,     // "Decompose into virology+genomics sub-intents"
     pub steps: Vec<String>,
 }
+
+/// Turns a multi-intent question into an ordered retrieval plan
+pub struct QueryPlanner;
+
+impl QueryPlanner {
+    /// One retrieval step per intent (highest confidence first), plus a
+    /// cross-domain linking step when more than one domain is involved
+    pub fn plan(question: &MultiIntentQuestion) -> QueryPlan {
+        let mut intents: Vec<&IntentQuery> = question.intents.iter().collect();
+        intents.sort_by(|a, b| b.confidence.unwrap_or(0.0).total_cmp(&a.confidence.unwrap_or(0.0)));
+
+        let mut domains: Vec<String> = vec![];
+        for q in &intents {
+            if !domains.contains(&q.domain) {
+                domains.push(q.domain.clone());
+            }
+        }
+
+        let mut steps: Vec<String> = intents.iter()
+            .map(|q| match &q.intent {
+                Some(intent) => format!("Retrieve {} evidence for intent '{}': {}", q.domain, intent, q.text),
+                None => format!("Retrieve {} evidence: {}", q.domain, q.text),
+            })
+            .collect();
+        if domains.len() > 1 {
+            steps.push(format!("Link cross-domain evidence across {}", domains.join(", ")));
+        }
+
+        QueryPlan {
+            id: Uuid::new_v4(),
+            description: format!(
+                "Decompose into {} sub-intents",
+                domains.iter().map(|d| d.to_lowercase()).collect::<Vec<_>>().join("+")
+            ),
+            steps,
+        }
+    }
+}
@@ -0,0 +1,121 @@
+// limit-sarscov2/src/rd_harness.rs
+// Measure per-domain RD curves by sweeping the retrieval budget against gold evidence sets
+
+use serde::{Serialize, Deserialize};
+use std::collections::HashSet;
+
+use crate::{
+    domain::ResearchDomain,
+    multi_intent_graph::MultiIntentGraph,
+    rd::{RDCurve, RDPoint},
+    retrieval::{CorpusDoc, RetrievalBackend},
+};
+
+/// Queries to run in one domain and the evidence they should find
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DomainSweep {
+    pub domain: ResearchDomain,
+    pub queries: Vec<String>,
+    pub gold: HashSet<String>,        // doc sources (DOIs, URLs) or doc IDs
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DomainRdResult {
+    pub domain: ResearchDomain,
+    pub curve: RDCurve,
+    pub gold_size: usize,
+    pub final_coverage: f32,          // coverage at the largest budget
+    pub knee: Option<RDPoint>,
+}
+
+/// Sweeps the per-query retrieval budget k. At each k the top k hits of every
+/// query are pooled; distortion is 1 − the share of the gold set they cover.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RdHarness {
+    pub budgets: Vec<usize>,
+}
+
+impl Default for RdHarness {
+    fn default() -> Self {
+        Self { budgets: vec![1, 2, 4, 8, 16, 32, 64] }
+    }
+}
+
+impl RdHarness {
+    pub fn new(budgets: Vec<usize>) -> Self {
+        Self { budgets }
+    }
+
+    /// RD curve for one domain; rate is the budget k
+    pub fn sweep(&self, backend: &RetrievalBackend, sweep: &DomainSweep) -> RDCurve {
+        let gold = normalize_gold(&sweep.gold);
+        let domain = format!("{:?}", sweep.domain);
+        let hits: Vec<Vec<&CorpusDoc>> = sweep.queries.iter().map(|q| backend.keyword_search(&domain, q)).collect();
+        RDCurve::new(
+            self.budgets.iter()
+                .map(|&k| RDPoint { rate: k as f32, distortion: 1.0 - coverage(&gold, hits.iter().flat_map(|h| h.iter().take(k))) })
+                .collect(),
+        )
+    }
+
+    /// Sweep every domain, store each curve under `rd_curve_key(intent, domain)`
+    /// and the pooled curve over all domains under `intent`
+    pub fn run(
+        &self,
+        backend: &RetrievalBackend,
+        graph: &mut MultiIntentGraph,
+        intent: &str,
+        sweeps: &[DomainSweep],
+    ) -> Vec<DomainRdResult> {
+        let results: Vec<DomainRdResult> = sweeps.iter()
+            .map(|sweep| {
+                let curve = self.sweep(backend, sweep);
+                DomainRdResult {
+                    domain: sweep.domain.clone(),
+                    gold_size: sweep.gold.len(),
+                    final_coverage: curve.points.last().map(|p| 1.0 - p.distortion).unwrap_or(0.0),
+                    knee: curve.knee(),
+                    curve,
+                }
+            })
+            .collect();
+        for r in &results {
+            graph.add_domain_rd_curve(intent, &r.domain, r.curve.clone());
+        }
+
+        // Intent-level curve: all domains' queries against the union of their gold sets
+        let gold = normalize_gold(&sweeps.iter().flat_map(|s| s.gold.iter().cloned()).collect());
+        let hits: Vec<Vec<&CorpusDoc>> = sweeps.iter()
+            .flat_map(|s| {
+                let domain = format!("{:?}", s.domain);
+                s.queries.iter().map(move |q| (domain.clone(), q))
+            })
+            .map(|(domain, q)| backend.keyword_search(&domain, q))
+            .collect();
+        let pooled = RDCurve::new(
+            self.budgets.iter()
+                .map(|&k| RDPoint { rate: k as f32, distortion: 1.0 - coverage(&gold, hits.iter().flat_map(|h| h.iter().take(k))) })
+                .collect(),
+        );
+        graph.add_rd_curve(intent.to_string(), pooled);
+
+        tracing::info!(intent, domains = results.len(), budgets = self.budgets.len(), "rd sweep finished");
+        results
+    }
+}
+
+fn normalize_gold(gold: &HashSet<String>) -> HashSet<String> {
+    gold.iter().map(|g| g.trim().to_lowercase()).filter(|g| !g.is_empty()).collect()
+}
+
+/// Share of `gold` matched by the docs' source or ID; an empty gold set counts as covered
+fn coverage<'a>(gold: &HashSet<String>, docs: impl Iterator<Item = &'a &'a CorpusDoc>) -> f32 {
+    if gold.is_empty() {
+        return 1.0;
+    }
+    let found: HashSet<String> = docs
+        .flat_map(|d| [d.source.trim().to_lowercase(), d.id.to_string()])
+        .filter(|key| gold.contains(key))
+        .collect();
+    found.len() as f32 / gold.len() as f32
+}
@@ -0,0 +1,164 @@
+// limit-sarscov2/src/rdf.rs
+// RDF projection of a multi-intent graph: nodes, edges and cited sources in the sc2 vocabulary
+
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+
+use crate::{
+    multi_intent_graph::MultiIntentGraph,
+    prov::{source_iri, uuid_iri, SC2_NS},
+};
+
+pub const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+pub const RDFS_LABEL: &str = "http://www.w3.org/2000/01/rdf-schema#label";
+pub const XSD_NS: &str = "http://www.w3.org/2001/XMLSchema#";
+const DCTERMS_SOURCE: &str = "http://purl.org/dc/terms/source";
+const DCTERMS_IS_PART_OF: &str = "http://purl.org/dc/terms/isPartOf";
+const OBO_HAS_DBXREF: &str = "http://www.geneontology.org/formats/oboInOwl#hasDbXref";
+const SKOS_EXACT_MATCH: &str = "http://www.w3.org/2004/02/skos/core#exactMatch";
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Term {
+    Iri { value: String },
+    Literal { value: String, datatype: Option<String> },   // None for plain strings
+}
+
+impl Term {
+    pub fn iri(value: impl Into<String>) -> Self {
+        Self::Iri { value: value.into() }
+    }
+
+    pub fn text(value: impl Into<String>) -> Self {
+        Self::Literal { value: value.into(), datatype: None }
+    }
+
+    pub fn typed(value: impl Into<String>, xsd_type: &str) -> Self {
+        Self::Literal { value: value.into(), datatype: Some(format!("{}{}", XSD_NS, xsd_type)) }
+    }
+
+    /// N-Triples form: `<iri>`, `"text"` or `"1"^^<datatype>`
+    pub fn to_ntriples(&self) -> String {
+        match self {
+            Self::Iri { value } => format!("<{}>", value),
+            Self::Literal { value, datatype } => {
+                let escaped = value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n").replace('\r', "\\r");
+                match datatype {
+                    Some(dt) => format!("\"{}\"^^<{}>", escaped, dt),
+                    None => format!("\"{}\"", escaped),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Triple {
+    pub subject: Term,
+    pub predicate: Term,
+    pub object: Term,
+}
+
+fn sc2(local: &str) -> Term {
+    Term::iri(format!("{}{}", SC2_NS, local))
+}
+
+fn uuid_term(id: Uuid) -> Term {
+    Term::iri(uuid_iri(id))
+}
+
+/// Triples describing `graph`. Nodes are `sc2:Node` plus a class per domain
+/// (`sc2:Genomics`); edges are `sc2:Edge` plus a class per type (`sc2:Causal`)
+/// with `sc2:source`/`sc2:target`, and each edge is also asserted directly
+/// between its endpoints (`?g sc2:causal ?i`) for one-hop patterns, except
+/// refuting edges, which are also `sc2:Refutation`. Hyperedges
+/// are `sc2:HyperEdge` resources with one `sc2:source`/`sc2:target` per endpoint.
+/// Node xrefs are `oboInOwl:hasDbXref` CURIEs, and `skos:exactMatch` OBO
+/// PURLs for known prefixes.
+pub fn project(graph: &MultiIntentGraph) -> Vec<Triple> {
+    let mut triples = vec![];
+    let mut add = |subject: &Term, predicate: Term, object: Term| {
+        triples.push(Triple { subject: subject.clone(), predicate, object });
+    };
+    let graph_iri = uuid_term(graph.id);
+    add(&graph_iri, Term::iri(RDF_TYPE), sc2("Graph"));
+
+    let mut nodes: Vec<_> = graph.intent_nodes.values().collect();
+    nodes.sort_by_key(|n| n.id);
+    for node in nodes {
+        let s = uuid_term(node.id);
+        let domain = format!("{:?}", node.domain);
+        add(&s, Term::iri(RDF_TYPE), sc2("Node"));
+        add(&s, Term::iri(RDF_TYPE), sc2(&domain));
+        add(&s, Term::iri(RDFS_LABEL), Term::text(node.label()));
+        add(&s, sc2("intent"), Term::text(&node.intent));
+        add(&s, sc2("domain"), Term::text(domain));
+        add(&s, sc2("confidence"), Term::typed(node.metadata.confidence.to_string(), "decimal"));
+        add(&s, sc2("evidenceCount"), Term::typed(node.metadata.evidence_count.to_string(), "integer"));
+        for source in &node.metadata.sources {
+            add(&s, Term::iri(DCTERMS_SOURCE), Term::iri(source_iri(source)));
+        }
+        for xref in &node.metadata.xrefs {
+            add(&s, Term::iri(OBO_HAS_DBXREF), Term::text(xref.curie()));
+            if let Some(iri) = xref.iri() {
+                add(&s, Term::iri(SKOS_EXACT_MATCH), Term::iri(iri));
+            }
+        }
+        add(&s, Term::iri(DCTERMS_IS_PART_OF), graph_iri.clone());
+    }
+
+    let mut edges: Vec<_> = graph.edges.values().collect();
+    edges.sort_by_key(|e| e.id);
+    for edge in edges {
+        let s = uuid_term(edge.id);
+        let edge_type = edge.edge_type.name().to_string();
+        add(&s, Term::iri(RDF_TYPE), sc2("Edge"));
+        add(&s, Term::iri(RDF_TYPE), sc2(&edge_type));
+        add(&s, sc2("source"), uuid_term(edge.source_id));
+        add(&s, sc2("target"), uuid_term(edge.target_id));
+        add(&s, Term::iri(RDFS_LABEL), Term::text(&edge.label));
+        add(&s, sc2("confidence"), Term::typed(edge.metadata.confidence.to_string(), "decimal"));
+        add(&s, sc2("weight"), Term::typed(edge.weight.to_string(), "decimal"));
+        for reference in &edge.metadata.evidence_refs {
+            add(&s, sc2("evidence"), Term::iri(source_iri(reference)));
+        }
+        add(&s, Term::iri(DCTERMS_IS_PART_OF), graph_iri.clone());
+        if edge.is_refuting() {
+            add(&s, Term::iri(RDF_TYPE), sc2("Refutation"));
+            continue;
+        }
+        let mut relation = edge_type;
+        relation[..1].make_ascii_lowercase();
+        add(&uuid_term(edge.source_id), sc2(&relation), uuid_term(edge.target_id));
+    }
+
+    // Hyperedges are reified: one resource with every source and target, no direct relation triples
+    let mut hyperedges: Vec<_> = graph.hyperedges.values().collect();
+    hyperedges.sort_by_key(|h| h.id);
+    for hyperedge in hyperedges {
+        let s = uuid_term(hyperedge.id);
+        add(&s, Term::iri(RDF_TYPE), sc2("HyperEdge"));
+        add(&s, Term::iri(RDF_TYPE), sc2(hyperedge.edge_type.name()));
+        for &source in &hyperedge.source_ids {
+            add(&s, sc2("source"), uuid_term(source));
+        }
+        for &target in &hyperedge.target_ids {
+            add(&s, sc2("target"), uuid_term(target));
+        }
+        add(&s, Term::iri(RDFS_LABEL), Term::text(&hyperedge.label));
+        add(&s, sc2("confidence"), Term::typed(hyperedge.confidence.to_string(), "decimal"));
+        add(&s, sc2("weight"), Term::typed(hyperedge.weight.to_string(), "decimal"));
+        for reference in &hyperedge.evidence_refs {
+            add(&s, sc2("evidence"), Term::iri(source_iri(reference)));
+        }
+        add(&s, Term::iri(DCTERMS_IS_PART_OF), graph_iri.clone());
+    }
+    triples
+}
+
+/// N-Triples serialization, one triple per line
+pub fn to_ntriples(triples: &[Triple]) -> String {
+    triples.iter()
+        .map(|t| format!("{} {} {} .\n", t.subject.to_ntriples(), t.predicate.to_ntriples(), t.object.to_ntriples()))
+        .collect()
+}
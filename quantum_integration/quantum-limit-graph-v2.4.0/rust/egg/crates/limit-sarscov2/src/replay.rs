@@ -0,0 +1,90 @@
+// limit-sarscov2/src/replay.rs
+// Re-run a serendipity trace's queries against the current corpus and report evidence drift
+
+use serde::{Serialize, Deserialize};
+use std::collections::{BTreeSet, HashSet};
+use uuid::Uuid;
+
+use crate::{retrieval::RetrievalBackend, serendipity_trace::{HypothesisType, SerendipityTrace}};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DriftStatus {
+    Stable,
+    Increased,
+    Decreased,
+    Vanished,       // evidence was recorded but the query now finds nothing
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StepReplay {
+    pub step_number: usize,
+    pub hypothesis: HypothesisType,
+    pub query: String,
+    pub domains: Vec<String>,             // domains searched: the step's, or every corpus domain if it had none
+    pub recorded_evidence: usize,
+    pub current_evidence: usize,
+    pub drift: i64,                       // current − recorded
+    pub relative_drift: Option<f32>,      // drift / recorded; None when nothing was recorded
+    pub status: DriftStatus,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceReplay {
+    pub trace_id: Uuid,
+    pub replayed_at: String,
+    pub corpus_size: usize,
+    pub steps: Vec<StepReplay>,
+    pub total_recorded: usize,
+    pub total_current: usize,
+    pub steps_drifted: usize,             // steps whose status is not Stable
+}
+
+/// Re-execute each step's query with `RetrievalBackend::keyword_search` over the
+/// step's domains and compare the number of matching documents with the
+/// evidence count recorded at exploration time.
+pub fn replay_trace(trace: &SerendipityTrace, backend: &RetrievalBackend) -> TraceReplay {
+    let corpus_domains: BTreeSet<&str> = backend.docs.iter().map(|d| d.domain.as_str()).collect();
+    let steps: Vec<StepReplay> = trace.steps.iter()
+        .map(|step| {
+            let domains: Vec<String> = if step.domains_explored.is_empty() {
+                corpus_domains.iter().map(|d| d.to_string()).collect()
+            } else {
+                step.domains_explored.clone()
+            };
+            let matched: HashSet<Uuid> = domains.iter()
+                .flat_map(|d| backend.keyword_search(d, &step.query))
+                .map(|doc| doc.id)
+                .collect();
+            let (recorded, current) = (step.evidence_found, matched.len());
+            let drift = current as i64 - recorded as i64;
+            StepReplay {
+                step_number: step.step_number,
+                hypothesis: step.hypothesis.clone(),
+                query: step.query.clone(),
+                domains,
+                recorded_evidence: recorded,
+                current_evidence: current,
+                drift,
+                relative_drift: (recorded > 0).then(|| drift as f32 / recorded as f32),
+                status: match (recorded, current) {
+                    (r, 0) if r > 0 => DriftStatus::Vanished,
+                    (r, c) if c > r => DriftStatus::Increased,
+                    (r, c) if c < r => DriftStatus::Decreased,
+                    _ => DriftStatus::Stable,
+                },
+            }
+        })
+        .collect();
+
+    tracing::info!(trace = %trace.id, steps = steps.len(), "trace replayed");
+    TraceReplay {
+        trace_id: trace.id,
+        replayed_at: chrono::Utc::now().to_rfc3339(),
+        corpus_size: backend.docs.len(),
+        total_recorded: steps.iter().map(|s| s.recorded_evidence).sum(),
+        total_current: steps.iter().map(|s| s.current_evidence).sum(),
+        steps_drifted: steps.iter().filter(|s| s.status != DriftStatus::Stable).count(),
+        steps,
+    }
+}
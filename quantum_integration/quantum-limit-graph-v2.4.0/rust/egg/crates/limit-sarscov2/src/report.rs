@@ -0,0 +1,113 @@
+// limit-sarscov2/src/report.rs
+// Report narratives: templated graph summaries, optionally rewritten by a language model
+
+use serde::{Serialize, Deserialize};
+use std::future::Future;
+use anyhow::Result;
+
+use crate::summary::GraphSummary;
+
+/// Rewrites a summary's templated narrative into fluent prose. Implement this
+/// to plug in an LLM; `enhance_summary` keeps the template when it fails.
+pub trait SummaryEnhancer: Send + Sync {
+    fn name(&self) -> &str;
+
+    fn enhance(&self, summary: &GraphSummary) -> impl Future<Output = Result<String>> + Send;
+}
+
+/// A summary with the enhancer's narrative next to the templated one, which
+/// stays the reference for what the graph actually contains
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportSummary {
+    #[serde(flatten)]
+    pub summary: GraphSummary,
+    pub enhanced_narrative: Option<String>,
+    pub enhanced_by: Option<String>,
+}
+
+/// Ask `enhancer` for a narrative; on an error or an empty answer the report
+/// carries only the templated narrative
+pub async fn enhance_summary<E: SummaryEnhancer>(enhancer: &E, summary: GraphSummary) -> ReportSummary {
+    match enhancer.enhance(&summary).await {
+        Ok(text) if !text.trim().is_empty() => ReportSummary {
+            summary,
+            enhanced_narrative: Some(text.trim().to_string()),
+            enhanced_by: Some(enhancer.name().to_string()),
+        },
+        Ok(_) => ReportSummary { summary, enhanced_narrative: None, enhanced_by: None },
+        Err(e) => {
+            tracing::warn!(enhancer = enhancer.name(), error = %format!("{:#}", e), "summary enhancement failed; using template");
+            ReportSummary { summary, enhanced_narrative: None, enhanced_by: None }
+        }
+    }
+}
+
+#[cfg(feature = "llm")]
+pub use http::HttpSummaryEnhancer;
+
+#[cfg(feature = "llm")]
+mod http {
+    use serde::Deserialize;
+    use anyhow::{Context, Result};
+
+    use super::SummaryEnhancer;
+    use crate::summary::GraphSummary;
+
+    #[derive(Deserialize)]
+    struct EnhanceResponse {
+        narrative: String,
+    }
+
+    /// Reference enhancer: POSTs the summary as JSON to a service fronting an
+    /// LLM, which answers `{"narrative": "..."}`
+    #[derive(Debug, Clone)]
+    pub struct HttpSummaryEnhancer {
+        name: String,
+        http: reqwest::Client,
+        endpoint: String,
+        api_key: Option<String>,      // sent as a bearer token
+    }
+
+    impl HttpSummaryEnhancer {
+        pub fn new(name: impl Into<String>, endpoint: impl Into<String>) -> Self {
+            Self {
+                name: name.into(),
+                http: reqwest::Client::builder()
+                    .user_agent(concat!("limit-sarscov2/", env!("CARGO_PKG_VERSION")))
+                    .timeout(std::time::Duration::from_secs(60))
+                    .build()
+                    .expect("static reqwest client configuration"),
+                endpoint: endpoint.into(),
+                api_key: None,
+            }
+        }
+
+        pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+            self.api_key = Some(api_key.into());
+            self
+        }
+    }
+
+    impl SummaryEnhancer for HttpSummaryEnhancer {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        async fn enhance(&self, summary: &GraphSummary) -> Result<String> {
+            let mut call = self.http.post(&self.endpoint).json(summary);
+            if let Some(key) = &self.api_key {
+                call = call.bearer_auth(key);
+            }
+            let response: EnhanceResponse = call
+                .send()
+                .await
+                .with_context(|| format!("requesting a narrative from {}", self.endpoint))?
+                .error_for_status()
+                .with_context(|| format!("{} rejected the summary", self.endpoint))?
+                .json()
+                .await
+                .with_context(|| format!("decoding the narrative from {}", self.endpoint))?;
+            Ok(response.narrative)
+        }
+    }
+}
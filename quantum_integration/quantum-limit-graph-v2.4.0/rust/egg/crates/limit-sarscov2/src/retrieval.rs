@@ -1,10 +1,20 @@
 // limit-sarscov2/src/retrieval.rs
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Mutex;
 use uuid::Uuid;
 use regex::Regex;
 use anyhow::Result;
 
 use crate::nodes::{VirologyNode, GenomicsNode, TreatmentNode, ImmunologyNode, PublicHealthNode};
+use crate::mutation::parse_mutations;
+use crate::chunking::{content_hash, Chunker};
+use crate::cache::{CacheStats, LruCache};
+use crate::synonyms::SynonymTable;
+use crate::mesh::{MeshDescriptor, MeshTagger};
+
+/// Default number of (domain, query) results kept by the query cache
+pub const DEFAULT_CACHE_CAPACITY: usize = 512;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CorpusDoc {
@@ -12,26 +22,122 @@ pub struct CorpusDoc {
     pub domain: String,        // "Virology", "Genomics", "Treatment", etc.
     pub text: String,
     pub source: String,        // DOI, URL, dataset ref
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mesh: Vec<MeshDescriptor>,   // MeSH descriptors, from `MeshTagger` or the source's indexing
+}
+
+impl CorpusDoc {
+    /// Whether the doc is tagged with MeSH descriptor `term` (UI or heading)
+    pub fn has_mesh(&self, term: &str) -> bool {
+        self.mesh.iter().any(|m| m.matches(term))
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct RetrievalBackend {
     pub docs: Vec<CorpusDoc>,
+    content_hashes: HashSet<Uuid>,   // of every doc loaded through new/extend
+    cache: QueryCache,
+    synonyms: SynonymTable,          // custom aliases searched alongside each query
 }
 
 impl RetrievalBackend {
-    pub fn new(docs: Vec<CorpusDoc>) -> Self { Self { docs } }
+    pub fn new(docs: Vec<CorpusDoc>) -> Self {
+        Self::with_cache_capacity(docs, DEFAULT_CACHE_CAPACITY)
+    }
+
+    /// Backend whose query cache holds up to `capacity` results (0 disables it)
+    pub fn with_cache_capacity(docs: Vec<CorpusDoc>, capacity: usize) -> Self {
+        let mut backend = Self { docs: vec![], content_hashes: HashSet::new(), cache: QueryCache::new(capacity), synonyms: SynonymTable::default() };
+        backend.extend(docs);
+        backend
+    }
+
+    /// Drop cached query results. Adding or removing docs does this
+    /// automatically; call it after editing docs in place.
+    pub fn invalidate_cache(&self) {
+        self.cache.invalidate();
+    }
+
+    pub fn cache_stats(&self) -> CacheStats {
+        self.cache.stats()
+    }
+
+    pub fn synonyms(&self) -> &SynonymTable {
+        &self.synonyms
+    }
+
+    /// Replace the custom synonyms, dropping cached results
+    pub fn set_synonyms(&mut self, synonyms: SynonymTable) {
+        self.synonyms = synonyms;
+        self.cache.invalidate();
+    }
+
+    /// Bulk-load documents (e.g. from an ingestion pipeline), skipping any whose
+    /// normalized text is already loaded. Returns the number of docs added.
+    pub fn extend(&mut self, docs: impl IntoIterator<Item = CorpusDoc>) -> usize {
+        let before = self.docs.len();
+        for doc in docs {
+            if self.content_hashes.insert(content_hash(&doc.text)) {
+                self.docs.push(doc);
+            }
+        }
+        self.docs.len() - before
+    }
+
+    /// Chunk each document before loading it; duplicate chunks are skipped
+    pub fn extend_chunked(&mut self, docs: impl IntoIterator<Item = CorpusDoc>, chunker: &Chunker) -> usize {
+        self.extend(docs.into_iter().flat_map(|d| chunker.chunk(&d)))
+    }
 
     pub fn filter_domain(&self, domain: &str) -> Vec<&CorpusDoc> {
         self.docs.iter().filter(|d| d.domain.eq_ignore_ascii_case(domain)).collect()
     }
 
+    /// Docs tagged with MeSH descriptor `term`, by UI (`D000086382`) or heading (`COVID-19`)
+    pub fn filter_mesh(&self, term: &str) -> Vec<&CorpusDoc> {
+        self.docs.iter().filter(|d| d.has_mesh(term)).collect()
+    }
+
+    /// Tag every doc without MeSH descriptors using `tagger`. Returns the
+    /// number of docs that got at least one descriptor.
+    pub fn tag_mesh(&mut self, tagger: &MeshTagger) -> usize {
+        let mut tagged = 0;
+        for doc in self.docs.iter_mut().filter(|d| d.mesh.is_empty()) {
+            doc.mesh = tagger.tag(&doc.text);
+            tagged += usize::from(!doc.mesh.is_empty());
+        }
+        tagged
+    }
+
+    /// `keyword_search` restricted to docs tagged with MeSH descriptor `mesh`
+    pub fn keyword_search_mesh(&self, domain: &str, query: &str, mesh: &str) -> Vec<&CorpusDoc> {
+        self.keyword_search(domain, query).into_iter().filter(|d| d.has_mesh(mesh)).collect()
+    }
+
+    /// Docs in `domain` containing `query` or one of its synonyms verbatim
+    /// ("Paxlovid" also finds "nirmatrelvir/ritonavir"). Results are cached
+    /// per (domain, query) until the corpus or the synonyms change.
     pub fn keyword_search(&self, domain: &str, query: &str) -> Vec<&CorpusDoc> {
-        let re = Regex::new(&regex::escape(query)).unwrap();
-        self.filter_domain(domain)
-            .into_iter()
-            .filter(|d| re.is_match(&d.text))
-            .collect()
+        let key = (domain.to_ascii_lowercase(), query.to_string());
+        let indices = match self.cache.get(&key, self.docs.len()) {
+            Some(indices) => indices,
+            None => {
+                let names: Vec<String> = std::iter::once(query.to_string())
+                    .chain(self.synonyms.equivalents(query))
+                    .map(|n| regex::escape(&n))
+                    .collect();
+                let re = Regex::new(&names.join("|")).unwrap();
+                let indices: Vec<usize> = self.docs.iter()
+                    .enumerate()
+                    .filter(|(_, d)| d.domain.eq_ignore_ascii_case(domain) && re.is_match(&d.text))
+                    .map(|(i, _)| i)
+                    .collect();
+                self.cache.put(key, indices.clone(), self.docs.len());
+                indices
+            }
+        };
+        indices.into_iter().filter_map(|i| self.docs.get(i)).collect()
     }
 
     pub fn virology_from(&self, query: &str) -> Result<Vec<VirologyNode>> {
@@ -41,6 +147,7 @@ impl RetrievalBackend {
                 id: Uuid::new_v4(),
                 topic: "Spike-ACE2 binding".into(),
                 details: format!("Evidence: {} | Source: {}", summarize(&d.text), d.source),
+                regions: vec![],
             })
             .collect())
     }
@@ -51,7 +158,8 @@ impl RetrievalBackend {
             .map(|d| GenomicsNode {
                 id: Uuid::new_v4(),
                 variant: variant.into(),
-                mutations: extract_mutations(&d.text),
+                mutations: parse_mutations(&d.text),
+                unparsed_mutations: vec![],
             })
             .collect())
     }
@@ -85,22 +193,89 @@ impl RetrievalBackend {
                 id: Uuid::new_v4(),
                 policy: policy.into(),
                 effect: summarize(&d.text),
+                region: None,
             })
             .collect())
     }
 }
 
+/// Thread-safe LRU of doc indices per (domain, query). The cache remembers
+/// the corpus size its entries were computed against and empties itself when
+/// that changes, whether docs came in through `extend` or `docs` directly.
+#[derive(Debug)]
+struct QueryCache {
+    state: Mutex<CacheState>,
+}
+
+#[derive(Debug)]
+struct CacheState {
+    lru: LruCache<(String, String), Vec<usize>>,
+    corpus_size: usize,
+    hits: u64,
+    misses: u64,
+    invalidations: u64,
+}
+
+impl QueryCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            state: Mutex::new(CacheState { lru: LruCache::new(capacity), corpus_size: 0, hits: 0, misses: 0, invalidations: 0 }),
+        }
+    }
+
+    fn get(&self, key: &(String, String), corpus_size: usize) -> Option<Vec<usize>> {
+        let mut state = self.state.lock().unwrap();
+        if state.corpus_size != corpus_size {
+            if !state.lru.is_empty() {
+                state.lru.clear();
+                state.invalidations += 1;
+            }
+            state.corpus_size = corpus_size;
+        }
+        let hit = state.lru.get(key).cloned();
+        if hit.is_some() { state.hits += 1 } else { state.misses += 1 }
+        hit
+    }
+
+    fn put(&self, key: (String, String), indices: Vec<usize>, corpus_size: usize) {
+        let mut state = self.state.lock().unwrap();
+        if state.corpus_size == corpus_size {
+            state.lru.put(key, indices);
+        }
+    }
+
+    fn invalidate(&self) {
+        let mut state = self.state.lock().unwrap();
+        if !state.lru.is_empty() {
+            state.lru.clear();
+            state.invalidations += 1;
+        }
+    }
+
+    fn stats(&self) -> CacheStats {
+        let state = self.state.lock().unwrap();
+        CacheStats {
+            hits: state.hits,
+            misses: state.misses,
+            invalidations: state.invalidations,
+            entries: state.lru.len(),
+            capacity: state.lru.capacity(),
+        }
+    }
+}
+
+impl Clone for QueryCache {
+    /// A cloned backend starts with an empty cache of the same capacity
+    fn clone(&self) -> Self {
+        Self::new(self.state.lock().unwrap().lru.capacity())
+    }
+}
+
 fn summarize(text: &str) -> String {
     let max = 240;
     text.chars().take(max).collect::<String>()
 }
 
-fn extract_mutations(text: &str) -> Vec<String> {
-    // Simple stub; replace with a proper parser (e.g., regex for AA changes)
-    let candidates = ["N501Y", "E484K", "D614G", "P681R"];
-    candidates.iter().filter(|m| text.contains(*m)).map(|m| m.to_string()).collect()
-}
-
 fn infer_mechanism(text: &str) -> String {
     if text.contains("protease") { "Protease inhibitor".into() }
     else if text.contains("polymerase") { "Polymerase inhibitor".into() }
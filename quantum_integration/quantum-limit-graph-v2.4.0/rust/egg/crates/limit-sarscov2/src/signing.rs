@@ -0,0 +1,158 @@
+// limit-sarscov2/src/signing.rs
+// Ed25519 signing of provenance notes and a verification pass for tamper evidence
+
+use serde::{Serialize, Deserialize};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use std::collections::HashMap;
+use anyhow::{anyhow, Context, Result};
+use uuid::Uuid;
+
+use crate::provenance::{NoteSignature, ProvenanceNote};
+
+pub const ALGORITHM: &str = "ed25519";
+
+/// Signs notes with one Ed25519 key
+#[derive(Debug, Clone)]
+pub struct NoteSigner {
+    key_id: String,
+    key: SigningKey,
+}
+
+impl NoteSigner {
+    pub fn new(key_id: impl Into<String>, key: SigningKey) -> Self {
+        Self { key_id: key_id.into(), key }
+    }
+
+    /// Signer from a hex-encoded 32-byte secret seed
+    pub fn from_seed_hex(key_id: impl Into<String>, seed_hex: &str) -> Result<Self> {
+        let seed: [u8; 32] = hex::decode(seed_hex.trim())
+            .context("decoding signing key seed")?
+            .try_into()
+            .map_err(|b: Vec<u8>| anyhow!("signing key seed must be 32 bytes, got {}", b.len()))?;
+        Ok(Self::new(key_id, SigningKey::from_bytes(&seed)))
+    }
+
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    pub fn verifying_key(&self) -> VerifyingKey {
+        self.key.verifying_key()
+    }
+
+    /// Sign (or re-sign) `note` over its canonical bytes
+    pub fn sign(&self, note: &mut ProvenanceNote) {
+        let signature = self.key.sign(&note.canonical_bytes());
+        note.signature = Some(NoteSignature {
+            key_id: self.key_id.clone(),
+            algorithm: ALGORITHM.into(),
+            signature: hex::encode(signature.to_bytes()),
+        });
+    }
+}
+
+/// Public keys a verifier accepts, by key ID
+#[derive(Debug, Clone, Default)]
+pub struct TrustedKeys {
+    keys: HashMap<String, VerifyingKey>,
+}
+
+impl TrustedKeys {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, key_id: impl Into<String>, key: VerifyingKey) {
+        self.keys.insert(key_id.into(), key);
+    }
+
+    /// Add a hex-encoded 32-byte public key
+    pub fn add_hex(&mut self, key_id: impl Into<String>, public_hex: &str) -> Result<()> {
+        let bytes: [u8; 32] = hex::decode(public_hex.trim())
+            .context("decoding public key")?
+            .try_into()
+            .map_err(|b: Vec<u8>| anyhow!("public key must be 32 bytes, got {}", b.len()))?;
+        let key = VerifyingKey::from_bytes(&bytes).context("invalid Ed25519 public key")?;
+        self.add(key_id, key);
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureStatus {
+    Valid,
+    Unsigned,
+    Tampered,         // signature does not match the note's current contents
+    UnknownKey,       // signed with a key ID that is not trusted
+    Malformed,        // unsupported algorithm or undecodable signature
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NoteVerification {
+    pub note_id: Uuid,
+    pub subject_id: Uuid,
+    pub key_id: Option<String>,
+    pub status: SignatureStatus,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerificationReport {
+    pub checked: usize,
+    pub valid: usize,
+    pub unsigned: usize,
+    pub tampered: Vec<Uuid>,          // note IDs
+    pub unverifiable: Vec<Uuid>,      // unknown key or malformed signature
+    pub notes: Vec<NoteVerification>,
+}
+
+pub fn verify_note(note: &ProvenanceNote, trusted: &TrustedKeys) -> SignatureStatus {
+    let Some(sig) = &note.signature else {
+        return SignatureStatus::Unsigned;
+    };
+    if sig.algorithm != ALGORITHM {
+        return SignatureStatus::Malformed;
+    }
+    let Some(key) = trusted.keys.get(&sig.key_id) else {
+        return SignatureStatus::UnknownKey;
+    };
+    let Some(signature) = hex::decode(&sig.signature).ok().and_then(|b| Signature::from_slice(&b).ok()) else {
+        return SignatureStatus::Malformed;
+    };
+    match key.verify(&note.canonical_bytes(), &signature) {
+        Ok(()) => SignatureStatus::Valid,
+        Err(_) => SignatureStatus::Tampered,
+    }
+}
+
+/// Check every note's signature against `trusted`
+pub fn verify_notes<'a>(notes: impl IntoIterator<Item = &'a ProvenanceNote>, trusted: &TrustedKeys) -> VerificationReport {
+    let notes: Vec<NoteVerification> = notes.into_iter()
+        .map(|n| NoteVerification {
+            note_id: n.id,
+            subject_id: n.subject_id,
+            key_id: n.signature.as_ref().map(|s| s.key_id.clone()),
+            status: verify_note(n, trusted),
+        })
+        .collect();
+    let with = |status: SignatureStatus| notes.iter().filter(move |n| n.status == status);
+    let report = VerificationReport {
+        checked: notes.len(),
+        valid: with(SignatureStatus::Valid).count(),
+        unsigned: with(SignatureStatus::Unsigned).count(),
+        tampered: with(SignatureStatus::Tampered).map(|n| n.note_id).collect(),
+        unverifiable: notes.iter()
+            .filter(|n| matches!(n.status, SignatureStatus::UnknownKey | SignatureStatus::Malformed))
+            .map(|n| n.note_id)
+            .collect(),
+        notes,
+    };
+    if !report.tampered.is_empty() {
+        tracing::warn!(tampered = report.tampered.len(), "provenance notes failed signature verification");
+    }
+    report
+}
@@ -0,0 +1,450 @@
+// limit-sarscov2/src/sparql.rs
+// Minimal SPARQL (SELECT/ASK over basic graph patterns) evaluated against the RDF projection
+
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use thiserror::Error;
+
+use crate::{
+    prov::PREFIXES,
+    rdf::{Term, Triple, RDF_TYPE, XSD_NS},
+};
+
+/// `application/sparql-results+json`
+pub const RESULTS_CONTENT_TYPE: &str = "application/sparql-results+json";
+
+#[derive(Debug, Error, PartialEq)]
+pub enum SparqlError {
+    #[error("syntax error at offset {offset}: {message}")]
+    Syntax { offset: usize, message: String },
+    #[error("unknown prefix '{0}:'")]
+    UnknownPrefix(String),
+    #[error("selected variable ?{0} does not appear in WHERE")]
+    UnboundVariable(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum PatternTerm {
+    Var(String),
+    Const(Term),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Form {
+    Select { vars: Option<Vec<String>>, distinct: bool },   // None for SELECT *
+    Ask,
+}
+
+/// A parsed query: prefixes, SELECT or ASK, one basic graph pattern, optional LIMIT
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparqlQuery {
+    form: Form,
+    patterns: Vec<[PatternTerm; 3]>,
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SparqlResult {
+    Select { vars: Vec<String>, rows: Vec<BTreeMap<String, Term>> },
+    Ask(bool),
+}
+
+impl SparqlResult {
+    /// SPARQL 1.1 Query Results JSON
+    pub fn to_json(&self) -> Value {
+        match self {
+            Self::Ask(answer) => json!({ "head": {}, "boolean": answer }),
+            Self::Select { vars, rows } => {
+                let bindings: Vec<Value> = rows.iter()
+                    .map(|row| {
+                        let binding: Map<String, Value> = row.iter()
+                            .map(|(var, term)| {
+                                let value = match term {
+                                    Term::Iri { value } => json!({ "type": "uri", "value": value }),
+                                    Term::Literal { value, datatype: Some(dt) } => json!({ "type": "literal", "value": value, "datatype": dt }),
+                                    Term::Literal { value, datatype: None } => json!({ "type": "literal", "value": value }),
+                                };
+                                (var.clone(), value)
+                            })
+                            .collect();
+                        Value::Object(binding)
+                    })
+                    .collect();
+                json!({ "head": { "vars": vars }, "results": { "bindings": bindings } })
+            }
+        }
+    }
+}
+
+type Solution = HashMap<String, Term>;
+
+impl SparqlQuery {
+    /// Parse a query. The prefixes of the PROV-O export (`prov`, `rdfs`,
+    /// `dcterms`, `xsd`, `sc2`) plus `rdf` are predeclared.
+    pub fn parse(text: &str) -> Result<Self, SparqlError> {
+        let tokens = tokenize(text)?;
+        let mut prefixes: HashMap<String, String> = PREFIXES.iter().map(|(p, iri)| (p.to_string(), iri.to_string())).collect();
+        prefixes.insert("rdf".into(), "http://www.w3.org/1999/02/22-rdf-syntax-ns#".into());
+        let query = Parser { tokens, pos: 0, len: text.len(), prefixes }.query()?;
+
+        if let Form::Select { vars: Some(vars), .. } = &query.form {
+            let used = query.pattern_vars();
+            if let Some(v) = vars.iter().find(|v| !used.contains(v)) {
+                return Err(SparqlError::UnboundVariable(v.clone()));
+            }
+        }
+        Ok(query)
+    }
+
+    /// Variables in order of first appearance in the pattern
+    fn pattern_vars(&self) -> Vec<String> {
+        let mut vars: Vec<String> = vec![];
+        for term in self.patterns.iter().flatten() {
+            if let PatternTerm::Var(v) = term {
+                if !vars.contains(v) {
+                    vars.push(v.clone());
+                }
+            }
+        }
+        vars
+    }
+
+    pub fn execute(&self, triples: &[Triple]) -> SparqlResult {
+        let mut by_predicate: HashMap<&Term, Vec<&Triple>> = HashMap::new();
+        for t in triples {
+            by_predicate.entry(&t.predicate).or_default().push(t);
+        }
+
+        // Join patterns most-bound first, so each step filters as much as it can
+        let mut remaining: Vec<&[PatternTerm; 3]> = self.patterns.iter().collect();
+        let mut bound: HashSet<String> = HashSet::new();
+        let mut solutions: Vec<Solution> = vec![HashMap::new()];
+        while !remaining.is_empty() && !solutions.is_empty() {
+            let boundness = |p: &[PatternTerm; 3]| p.iter().filter(|t| match t {
+                PatternTerm::Const(_) => true,
+                PatternTerm::Var(v) => bound.contains(v),
+            }).count();
+            let next = (0..remaining.len()).max_by_key(|&i| (boundness(remaining[i]), usize::MAX - i)).expect("non-empty");
+            let pattern = remaining.remove(next);
+
+            solutions = solutions.iter()
+                .flat_map(|solution| {
+                    let candidates: Vec<&Triple> = match resolve(&pattern[1], solution) {
+                        Some(p) => by_predicate.get(&p).cloned().unwrap_or_default(),
+                        None => triples.iter().collect(),
+                    };
+                    candidates.into_iter().filter_map(|t| unify(pattern, t, solution)).collect::<Vec<_>>()
+                })
+                .collect();
+            bound.extend(pattern.iter().filter_map(|t| match t {
+                PatternTerm::Var(v) => Some(v.clone()),
+                PatternTerm::Const(_) => None,
+            }));
+        }
+
+        match &self.form {
+            Form::Ask => SparqlResult::Ask(!solutions.is_empty()),
+            Form::Select { vars, distinct } => {
+                let vars = vars.clone().unwrap_or_else(|| self.pattern_vars());
+                let mut seen = HashSet::new();
+                let rows = solutions.into_iter()
+                    .map(|s| vars.iter().filter_map(|v| s.get(v).map(|t| (v.clone(), t.clone()))).collect::<BTreeMap<_, _>>())
+                    .filter(|row| !distinct || seen.insert(row.clone()))
+                    .take(self.limit.unwrap_or(usize::MAX))
+                    .collect();
+                SparqlResult::Select { vars, rows }
+            }
+        }
+    }
+}
+
+fn resolve(term: &PatternTerm, solution: &Solution) -> Option<Term> {
+    match term {
+        PatternTerm::Const(t) => Some(t.clone()),
+        PatternTerm::Var(v) => solution.get(v).cloned(),
+    }
+}
+
+fn unify(pattern: &[PatternTerm; 3], triple: &Triple, solution: &Solution) -> Option<Solution> {
+    let mut extended = solution.clone();
+    for (term, value) in pattern.iter().zip([&triple.subject, &triple.predicate, &triple.object]) {
+        match term {
+            PatternTerm::Const(t) if t != value => return None,
+            PatternTerm::Const(_) => {}
+            PatternTerm::Var(v) => match extended.get(v) {
+                Some(existing) if existing != value => return None,
+                Some(_) => {}
+                None => {
+                    extended.insert(v.clone(), value.clone());
+                }
+            },
+        }
+    }
+    Some(extended)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),                 // keyword or `a`
+    Iri(String),                  // <...>
+    PName(String, String),        // prefix, local
+    Var(String),
+    Literal(String, Option<String>),   // value, datatype IRI or prefixed name (resolved by the parser)
+    Number(String),
+    Punct(char),                  // { } . ; , * ( )
+}
+
+fn tokenize(text: &str) -> Result<Vec<(usize, Token)>, SparqlError> {
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let take_while = |from: usize, f: &dyn Fn(char) -> bool| chars[from..].iter().take_while(|&&(_, c)| f(c)).count();
+    let collect = |from: usize, len: usize| chars[from..from + len].iter().map(|&(_, c)| c).collect::<String>();
+    let name_char = |c: char| c.is_alphanumeric() || c == '_' || c == '-';
+
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let (offset, c) = chars[i];
+        let syntax = |message: &str| SparqlError::Syntax { offset, message: message.into() };
+        let (token, width) = match c {
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '#' => {
+                i += take_while(i, &|c| c != '\n');
+                continue;
+            }
+            '{' | '}' | ';' | ',' | '*' | '(' | ')' => (Token::Punct(c), 1),
+            '.' if !chars.get(i + 1).is_some_and(|&(_, n)| n.is_ascii_digit()) => (Token::Punct('.'), 1),
+            '<' => {
+                let len = take_while(i + 1, &|c| c != '>' && !c.is_whitespace());
+                if chars.get(i + 1 + len).map(|&(_, c)| c) != Some('>') {
+                    return Err(syntax("unterminated IRI"));
+                }
+                (Token::Iri(collect(i + 1, len)), len + 2)
+            }
+            '?' | '$' => {
+                let len = take_while(i + 1, &name_char);
+                if len == 0 {
+                    return Err(syntax("empty variable name"));
+                }
+                (Token::Var(collect(i + 1, len)), len + 1)
+            }
+            '"' | '\'' => {
+                let len = take_while(i + 1, &|q| q != c);
+                if i + 1 + len >= chars.len() {
+                    return Err(syntax("unterminated string"));
+                }
+                let value = collect(i + 1, len);
+                let mut width = len + 2;
+                let mut datatype = None;
+                let rest = i + width;
+                if chars.get(rest).map(|&(_, c)| c) == Some('@') {
+                    width += 1 + take_while(rest + 1, &name_char);      // language tags are dropped
+                } else if chars.get(rest).map(|&(_, c)| c) == Some('^') && chars.get(rest + 1).map(|&(_, c)| c) == Some('^') {
+                    // `<iri>` may contain dots; a prefixed name ends at the triple's punctuation
+                    let dt_len = match chars.get(rest + 2) {
+                        Some((_, '<')) => {
+                            let len = take_while(rest + 3, &|c| c != '>' && !c.is_whitespace());
+                            if chars.get(rest + 3 + len).map(|&(_, c)| c) != Some('>') {
+                                return Err(syntax("unterminated datatype IRI"));
+                            }
+                            len + 2
+                        }
+                        _ => take_while(rest + 2, &|c| !c.is_whitespace() && !"{}.;,".contains(c)),
+                    };
+                    datatype = Some(collect(rest + 2, dt_len));
+                    width += 2 + dt_len;
+                }
+                (Token::Literal(value, datatype), width)
+            }
+            c if c.is_ascii_digit() || ((c == '-' || c == '+' || c == '.') && chars.get(i + 1).is_some_and(|&(_, n)| n.is_ascii_digit())) => {
+                let len = 1 + take_while(i + 1, &|c| c.is_ascii_digit() || c == '.');
+                let len = if collect(i, len).ends_with('.') { len - 1 } else { len };   // "1 ." ends a triple
+                (Token::Number(collect(i, len)), len)
+            }
+            c if name_char(c) || c == ':' => {
+                let len = take_while(i, &|c| name_char(c) || c == ':' || c == '.');
+                let mut word = collect(i, len);
+                while word.ends_with('.') {
+                    word.pop();
+                }
+                let width = word.chars().count();
+                match word.split_once(':') {
+                    Some((prefix, local)) => (Token::PName(prefix.into(), local.into()), width),
+                    None => (Token::Word(word), width),
+                }
+            }
+            other => return Err(syntax(&format!("unexpected character '{}'", other))),
+        };
+        tokens.push((offset, token));
+        i += width;
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+    len: usize,
+    prefixes: HashMap<String, String>,
+}
+
+impl Parser {
+    fn error<T>(&self, message: impl Into<String>) -> Result<T, SparqlError> {
+        let offset = self.tokens.get(self.pos).map_or(self.len, |(o, _)| *o);
+        Err(SparqlError::Syntax { offset, message: message.into() })
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(_, t)| t)
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let hit = matches!(self.peek(), Some(Token::Word(w)) if w.eq_ignore_ascii_case(keyword));
+        self.pos += hit as usize;
+        hit
+    }
+
+    fn eat_punct(&mut self, p: char) -> bool {
+        let hit = self.peek() == Some(&Token::Punct(p));
+        self.pos += hit as usize;
+        hit
+    }
+
+    fn expect_punct(&mut self, p: char) -> Result<(), SparqlError> {
+        if self.eat_punct(p) { Ok(()) } else { self.error(format!("expected '{}'", p)) }
+    }
+
+    fn expand(&self, prefix: &str, local: &str) -> Result<String, SparqlError> {
+        self.prefixes.get(prefix)
+            .map(|ns| format!("{}{}", ns, local))
+            .ok_or_else(|| SparqlError::UnknownPrefix(prefix.into()))
+    }
+
+    fn query(mut self) -> Result<SparqlQuery, SparqlError> {
+        while self.eat_keyword("PREFIX") {
+            let Some(Token::PName(prefix, local)) = self.peek().cloned() else {
+                return self.error("expected 'name:' after PREFIX");
+            };
+            if !local.is_empty() {
+                return self.error("expected 'name:' after PREFIX");
+            }
+            self.pos += 1;
+            let Some(Token::Iri(iri)) = self.peek().cloned() else {
+                return self.error("expected <iri> after prefix name");
+            };
+            self.pos += 1;
+            self.prefixes.insert(prefix, iri);
+        }
+
+        let form = if self.eat_keyword("ASK") {
+            Form::Ask
+        } else if self.eat_keyword("SELECT") {
+            let distinct = self.eat_keyword("DISTINCT");
+            let vars = if self.eat_punct('*') {
+                None
+            } else {
+                let mut vars = vec![];
+                while let Some(Token::Var(v)) = self.peek() {
+                    vars.push(v.clone());
+                    self.pos += 1;
+                }
+                if vars.is_empty() {
+                    return self.error("expected variables or * after SELECT");
+                }
+                Some(vars)
+            };
+            Form::Select { vars, distinct }
+        } else {
+            return self.error("expected SELECT or ASK (only basic graph patterns are supported)");
+        };
+
+        self.eat_keyword("WHERE");
+        self.expect_punct('{')?;
+        let mut patterns = vec![];
+        while !self.eat_punct('}') {
+            if self.peek().is_none() {
+                return self.error("expected '}'");
+            }
+            if let Some(Token::Word(w)) = self.peek() {
+                if ["FILTER", "OPTIONAL", "UNION", "MINUS", "BIND"].iter().any(|k| w.eq_ignore_ascii_case(k)) {
+                    return self.error(format!("{} is not supported (only basic graph patterns)", w.to_ascii_uppercase()));
+                }
+            }
+            let subject = self.term()?;
+            loop {
+                let predicate = self.predicate()?;
+                loop {
+                    patterns.push([subject.clone(), predicate.clone(), self.term()?]);
+                    if !self.eat_punct(',') {
+                        break;
+                    }
+                }
+                if !self.eat_punct(';') || matches!(self.peek(), Some(Token::Punct('.' | '}'))) {
+                    break;
+                }
+            }
+            if self.peek().is_none() {
+                return self.error("expected '}'");
+            }
+            if !self.eat_punct('.') && self.peek() != Some(&Token::Punct('}')) {
+                return self.error("expected '.' or '}' (FILTER, OPTIONAL and UNION are not supported)");
+            }
+        }
+        if patterns.is_empty() {
+            return self.error("empty WHERE pattern");
+        }
+
+        let limit = if self.eat_keyword("LIMIT") {
+            match self.peek().cloned() {
+                Some(Token::Number(n)) => {
+                    self.pos += 1;
+                    Some(n.parse().or_else(|_| self.error("expected a whole number after LIMIT"))?)
+                }
+                _ => return self.error("expected a whole number after LIMIT"),
+            }
+        } else {
+            None
+        };
+        if self.pos < self.tokens.len() {
+            return self.error("unexpected input after query");
+        }
+        Ok(SparqlQuery { form, patterns, limit })
+    }
+
+    fn predicate(&mut self) -> Result<PatternTerm, SparqlError> {
+        if self.eat_keyword("a") {
+            return Ok(PatternTerm::Const(Term::iri(RDF_TYPE)));
+        }
+        self.term()
+    }
+
+    fn term(&mut self) -> Result<PatternTerm, SparqlError> {
+        let term = match self.peek().cloned() {
+            Some(Token::Var(v)) => PatternTerm::Var(v),
+            Some(Token::Iri(iri)) => PatternTerm::Const(Term::iri(iri)),
+            Some(Token::PName(prefix, local)) => PatternTerm::Const(Term::iri(self.expand(&prefix, &local)?)),
+            Some(Token::Literal(value, datatype)) => {
+                let datatype = match datatype {
+                    None => None,
+                    Some(dt) if dt.starts_with('<') && dt.ends_with('>') => Some(dt[1..dt.len() - 1].to_string()),
+                    Some(dt) => match dt.split_once(':') {
+                        Some((prefix, local)) => Some(self.expand(prefix, local)?),
+                        None => return self.error(format!("invalid datatype '{}'", dt)),
+                    },
+                };
+                // xsd:string is how plain literals are typed in RDF 1.1
+                let datatype = datatype.filter(|dt| *dt != format!("{}string", XSD_NS));
+                PatternTerm::Const(Term::Literal { value, datatype })
+            }
+            Some(Token::Number(n)) => {
+                let xsd = if n.contains('.') { "decimal" } else { "integer" };
+                PatternTerm::Const(Term::typed(n.trim_start_matches('+'), xsd))
+            }
+            _ => return self.error("expected a variable, IRI or literal"),
+        };
+        self.pos += 1;
+        Ok(term)
+    }
+}
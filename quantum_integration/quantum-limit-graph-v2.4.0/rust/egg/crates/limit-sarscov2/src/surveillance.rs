@@ -0,0 +1,366 @@
+// limit-sarscov2/src/surveillance.rs
+// Variant surveillance metadata (GISAID / Nextstrain / covariants exports) → GenomicsNodes + lineage frequencies
+
+use serde::{Serialize, Deserialize};
+use std::{collections::{BTreeMap, HashMap}, fs, path::{Path, PathBuf}};
+use uuid::Uuid;
+use anyhow::{bail, Context, Result};
+
+use crate::{lineage::PangoLineage, mutation::Mutation, nodes::GenomicsNode};
+
+/// Namespace for lineage node IDs, so re-importing updates the same nodes
+const SURVEILLANCE_NAMESPACE: Uuid = Uuid::from_u128(0x6c1b_2f0e_4d4a_4f5e_9a0c_c0d1_9000_0003);
+
+/// Header names accepted for each field, compared case-insensitively
+const LINEAGE_COLUMNS: &[&str] = &["pango lineage", "pango_lineage", "nextclade_pango", "lineage"];
+const MUTATION_COLUMNS: &[&str] = &["aa substitutions", "aasubstitutions", "aadeletions", "aainsertions", "mutations"];
+const DATE_COLUMNS: &[&str] = &["collection date", "collection_date", "date"];
+const COUNTRY_COLUMNS: &[&str] = &["country", "location"];
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SurveillanceFormat {
+    Tsv,      // GISAID metadata.tsv, Nextstrain open metadata
+    Csv,
+    Json,     // array of flat records
+}
+
+impl SurveillanceFormat {
+    /// Guess the format from the file extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "tsv" | "tab" | "txt" => Some(Self::Tsv),
+            "csv" => Some(Self::Csv),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+/// One sequenced sample
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SurveillanceRecord {
+    pub lineage: PangoLineage,
+    pub mutations: Vec<Mutation>,
+    pub collection_date: Option<String>,   // YYYY-MM-DD, or YYYY-MM when the day is unknown
+    pub country: Option<String>,
+}
+
+/// Flat JSON record; `mutations` may be a list or a comma-separated string
+#[derive(Debug, Deserialize)]
+struct JsonRecord {
+    #[serde(alias = "pango_lineage", alias = "Pango lineage")]
+    lineage: Option<String>,
+    #[serde(default, alias = "aaSubstitutions", alias = "AA Substitutions")]
+    mutations: Option<MutationList>,
+    #[serde(alias = "date", alias = "Collection date")]
+    collection_date: Option<String>,
+    country: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum MutationList {
+    List(Vec<String>),
+    Joined(String),
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SurveillanceImportReport {
+    pub rows_read: usize,
+    pub records_loaded: usize,
+    pub missing_lineage: usize,
+    pub invalid_lineage: usize,
+    pub unparsed_mutations: usize,
+    pub errors: Vec<String>,
+}
+
+/// Frequency statistics for one lineage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LineageFrequency {
+    pub lineage: String,
+    pub sequences: usize,
+    pub frequency: f32,                             // share of all imported sequences
+    pub countries: BTreeMap<String, usize>,
+    pub first_seen: Option<String>,
+    pub last_seen: Option<String>,
+    pub mutation_frequencies: BTreeMap<String, f32>, // share of this lineage's sequences carrying each mutation
+    pub consensus: Vec<String>,                     // mutations at or above the consensus threshold
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SurveillanceSummary {
+    pub total_sequences: usize,
+    pub lineages: Vec<LineageFrequency>,            // most sequenced first
+    pub countries: BTreeMap<String, usize>,
+}
+
+impl SurveillanceSummary {
+    /// One `GenomicsNode` per lineage, carrying its consensus mutations.
+    /// Node IDs derive from the lineage name, so repeated imports line up.
+    pub fn genomics_nodes(&self) -> Vec<GenomicsNode> {
+        self.lineages.iter()
+            .map(|l| GenomicsNode::from_notations(lineage_node_id(&l.lineage), l.lineage.clone(), &l.consensus))
+            .collect()
+    }
+
+    pub fn get(&self, lineage: &str) -> Option<&LineageFrequency> {
+        self.lineages.iter().find(|l| l.lineage == lineage)
+    }
+}
+
+/// Stable node ID for a lineage
+pub fn lineage_node_id(lineage: &str) -> Uuid {
+    Uuid::new_v5(&SURVEILLANCE_NAMESPACE, lineage.as_bytes())
+}
+
+/// Reads surveillance metadata exports
+#[derive(Debug, Clone)]
+pub struct SurveillanceImporter {
+    pub path: PathBuf,
+    pub format: Option<SurveillanceFormat>,   // None = from extension
+    pub min_sequences: usize,                 // lineages below this are left out of the summary
+    pub consensus_threshold: f32,
+}
+
+impl SurveillanceImporter {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            format: None,
+            min_sequences: 1,
+            consensus_threshold: 0.5,
+        }
+    }
+
+    /// Parse the export into per-sample records
+    pub fn read(&self) -> Result<(Vec<SurveillanceRecord>, SurveillanceImportReport)> {
+        let format = match self.format.or_else(|| SurveillanceFormat::from_path(&self.path)) {
+            Some(f) => f,
+            None => bail!("cannot infer surveillance format of {}", self.path.display()),
+        };
+        let mut report = SurveillanceImportReport::default();
+        let rows = match format {
+            SurveillanceFormat::Tsv => self.read_delimited(b'\t')?,
+            SurveillanceFormat::Csv => self.read_delimited(b',')?,
+            SurveillanceFormat::Json => self.read_json()?,
+        };
+
+        let mut records = vec![];
+        for (i, row) in rows.into_iter().enumerate() {
+            report.rows_read += 1;
+            let raw = match row {
+                Ok(r) => r,
+                Err(e) => {
+                    report.errors.push(format!("row {}: {}", i + 1, e));
+                    continue;
+                }
+            };
+            let Some(lineage) = raw.lineage.filter(|l| !l.trim().is_empty() && !l.eq_ignore_ascii_case("unassigned")) else {
+                report.missing_lineage += 1;
+                continue;
+            };
+            let lineage = match PangoLineage::parse(lineage.trim()) {
+                Ok(l) => l,
+                Err(_) => {
+                    report.invalid_lineage += 1;
+                    continue;
+                }
+            };
+
+            let mut mutations = vec![];
+            for token in raw.mutations {
+                match normalize_mutation(&token).parse::<Mutation>() {
+                    Ok(m) if !mutations.contains(&m) => mutations.push(m),
+                    Ok(_) => {}
+                    Err(_) => report.unparsed_mutations += 1,
+                }
+            }
+
+            records.push(SurveillanceRecord {
+                lineage,
+                mutations,
+                collection_date: raw.collection_date.and_then(|d| normalize_date(&d)),
+                country: raw.country.and_then(|c| normalize_country(&c)),
+            });
+        }
+
+        report.records_loaded = records.len();
+        Ok((records, report))
+    }
+
+    /// Lineage frequencies over the imported records
+    pub fn summarize(&self, records: &[SurveillanceRecord]) -> SurveillanceSummary {
+        let mut groups: HashMap<String, Vec<&SurveillanceRecord>> = HashMap::new();
+        let mut countries = BTreeMap::new();
+        for r in records {
+            groups.entry(r.lineage.to_string()).or_default().push(r);
+            if let Some(c) = &r.country {
+                *countries.entry(c.clone()).or_insert(0) += 1;
+            }
+        }
+
+        let total = records.len();
+        let mut lineages: Vec<LineageFrequency> = groups.into_iter()
+            .filter(|(_, rs)| rs.len() >= self.min_sequences)
+            .map(|(lineage, rs)| {
+                let n = rs.len();
+                let mut per_country = BTreeMap::new();
+                let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+                for r in &rs {
+                    if let Some(c) = &r.country {
+                        *per_country.entry(c.clone()).or_insert(0) += 1;
+                    }
+                    for m in &r.mutations {
+                        *counts.entry(m.to_string()).or_insert(0) += 1;
+                    }
+                }
+                let dates = rs.iter().filter_map(|r| r.collection_date.as_deref());
+                let mutation_frequencies: BTreeMap<String, f32> = counts.into_iter()
+                    .map(|(m, c)| (m, c as f32 / n as f32))
+                    .collect();
+                let consensus = mutation_frequencies.iter()
+                    .filter(|(_, f)| **f >= self.consensus_threshold)
+                    .map(|(m, _)| m.clone())
+                    .collect();
+
+                LineageFrequency {
+                    lineage,
+                    sequences: n,
+                    frequency: if total == 0 { 0.0 } else { n as f32 / total as f32 },
+                    countries: per_country,
+                    first_seen: dates.clone().min().map(String::from),
+                    last_seen: dates.max().map(String::from),
+                    mutation_frequencies,
+                    consensus,
+                }
+            })
+            .collect();
+
+        lineages.sort_by(|a, b| b.sequences.cmp(&a.sequences).then_with(|| a.lineage.cmp(&b.lineage)));
+        SurveillanceSummary { total_sequences: total, lineages, countries }
+    }
+
+    /// Read, summarize, and build GenomicsNodes in one go
+    pub fn import(&self) -> Result<(Vec<GenomicsNode>, SurveillanceSummary, SurveillanceImportReport)> {
+        let (records, report) = self.read()?;
+        let summary = self.summarize(&records);
+        tracing::info!(records = report.records_loaded, lineages = summary.lineages.len(), "surveillance metadata imported");
+        Ok((summary.genomics_nodes(), summary, report))
+    }
+
+    fn read_delimited(&self, delimiter: u8) -> Result<Vec<Result<RawRecord>>> {
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(delimiter)
+            .flexible(true)
+            .from_path(&self.path)
+            .with_context(|| format!("opening {}", self.path.display()))?;
+
+        let headers: Vec<String> = reader.headers()?.iter().map(|h| h.trim().to_ascii_lowercase()).collect();
+        let find = |names: &[&str]| names.iter().find_map(|n| headers.iter().position(|h| h == n));
+        let lineage = find(LINEAGE_COLUMNS)
+            .with_context(|| format!("{} has no lineage column", self.path.display()))?;
+        let mutation_cols: Vec<usize> = headers.iter().enumerate()
+            .filter(|(_, h)| MUTATION_COLUMNS.contains(&h.as_str()))
+            .map(|(i, _)| i)
+            .collect();
+        let date = find(DATE_COLUMNS);
+        let country = find(COUNTRY_COLUMNS);
+
+        Ok(reader.records()
+            .map(|row| {
+                let row = row?;
+                let field = |i: Option<usize>| i.and_then(|i| row.get(i)).map(str::to_string).filter(|s| !s.trim().is_empty());
+                Ok(RawRecord {
+                    lineage: field(Some(lineage)),
+                    mutations: mutation_cols.iter()
+                        .filter_map(|i| row.get(*i))
+                        .flat_map(split_mutations)
+                        .collect(),
+                    collection_date: field(date),
+                    country: field(country),
+                })
+            })
+            .collect())
+    }
+
+    fn read_json(&self) -> Result<Vec<Result<RawRecord>>> {
+        let raw = fs::read_to_string(&self.path).with_context(|| format!("reading {}", self.path.display()))?;
+        let values: Vec<serde_json::Value> = serde_json::from_str(&raw)
+            .with_context(|| format!("parsing {}", self.path.display()))?;
+
+        Ok(values.into_iter()
+            .map(|v| {
+                let r: JsonRecord = serde_json::from_value(v)?;
+                Ok(RawRecord {
+                    lineage: r.lineage,
+                    mutations: match r.mutations {
+                        Some(MutationList::List(l)) => l,
+                        Some(MutationList::Joined(s)) => split_mutations(&s),
+                        None => vec![],
+                    },
+                    collection_date: r.collection_date,
+                    country: r.country,
+                })
+            })
+            .collect())
+    }
+}
+
+/// Fields pulled out of one row, before validation
+struct RawRecord {
+    lineage: Option<String>,
+    mutations: Vec<String>,
+    collection_date: Option<String>,
+    country: Option<String>,
+}
+
+/// "(Spike_L452R,NSP3_T183I)" or "S:L452R,ORF1a:T3255I" → individual tokens
+fn split_mutations(field: &str) -> Vec<String> {
+    field.trim_matches(|c| c == '(' || c == ')' || c == '"')
+        .split([',', ';'])
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// GISAID writes "Spike_L452R" / "NS3_Q57H"; the parser expects "S:L452R" / "ORF3a:Q57H"
+fn normalize_mutation(token: &str) -> String {
+    let Some((gene, change)) = token.split_once('_').filter(|_| !token.contains(':')) else {
+        return token.to_string();
+    };
+    let gene = match gene.to_ascii_uppercase().as_str() {
+        "NS3" => "ORF3a".to_string(),
+        "NS6" => "ORF6".to_string(),
+        "NS7A" => "ORF7a".to_string(),
+        "NS7B" => "ORF7b".to_string(),
+        "NS8" => "ORF8".to_string(),
+        _ => gene.to_string(),
+    };
+    format!("{}:{}", gene, change)
+}
+
+/// Keep full or month-precision ISO dates; GISAID marks unknown parts with "XX"
+fn normalize_date(date: &str) -> Option<String> {
+    let parts: Vec<&str> = date.trim().split('-').take_while(|p| !p.eq_ignore_ascii_case("xx")).collect();
+    match parts.as_slice() {
+        [y, m, d] => chrono::NaiveDate::parse_from_str(&format!("{}-{}-{}", y, m, d), "%Y-%m-%d")
+            .ok()
+            .map(|d| d.format("%Y-%m-%d").to_string()),
+        [y, m] => chrono::NaiveDate::parse_from_str(&format!("{}-{}-01", y, m), "%Y-%m-%d")
+            .ok()
+            .map(|d| d.format("%Y-%m").to_string()),
+        _ => None,
+    }
+}
+
+/// GISAID "Location" is "Europe / Germany / Berlin"; keep the country
+fn normalize_country(location: &str) -> Option<String> {
+    let parts: Vec<&str> = location.split('/').map(str::trim).filter(|p| !p.is_empty()).collect();
+    match parts.as_slice() {
+        [] => None,
+        [country] => Some(country.to_string()),
+        [_, country, ..] => Some(country.to_string()),
+    }
+}
@@ -0,0 +1,209 @@
+// limit-sarscov2/src/telemetry.rs
+// Request counters and latency histograms, and Prometheus text exposition of server and graph metrics
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    middleware::Next,
+    response::Response,
+};
+use std::{collections::BTreeMap, fmt::Write, sync::{Arc, Mutex}, time::Instant};
+use uuid::Uuid;
+
+use crate::{domain::SarsCov2Graph, multi_intent_graph::MultiIntentGraph};
+
+/// Upper bounds (seconds) of the request latency histogram buckets
+pub const LATENCY_BUCKETS: [f64; 10] = [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0];
+
+#[derive(Debug, Clone, Default)]
+struct RouteStats {
+    by_status: BTreeMap<u16, u64>,
+    buckets: [u64; LATENCY_BUCKETS.len()],    // non-cumulative; summed when rendered
+    sum_seconds: f64,
+    count: u64,
+}
+
+/// Per-route request counts and latencies. Routes are labelled by their
+/// pattern (`/graph/:id`), not the concrete path, to keep label sets small.
+#[derive(Debug, Default)]
+pub struct RequestMetrics {
+    routes: Mutex<BTreeMap<(String, String), RouteStats>>,  // (method, route)
+}
+
+impl RequestMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn observe(&self, method: &str, route: &str, status: u16, seconds: f64) {
+        let mut routes = self.routes.lock().unwrap();
+        let stats = routes.entry((method.to_string(), route.to_string())).or_default();
+        *stats.by_status.entry(status).or_insert(0) += 1;
+        if let Some(i) = LATENCY_BUCKETS.iter().position(|&le| seconds <= le) {
+            stats.buckets[i] += 1;
+        }
+        stats.sum_seconds += seconds;
+        stats.count += 1;
+    }
+
+    /// Requests seen so far, across routes
+    pub fn total(&self) -> u64 {
+        self.routes.lock().unwrap().values().map(|s| s.count).sum()
+    }
+}
+
+/// Middleware recording every request into `metrics`
+pub async fn track_requests(State(metrics): State<Arc<RequestMetrics>>, request: Request, next: Next) -> Response {
+    let method = request.method().to_string();
+    let route = request.extensions().get::<MatchedPath>()
+        .map(|p| p.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".into());
+    let started = Instant::now();
+    let response = next.run(request).await;
+    metrics.observe(&method, &route, response.status().as_u16(), started.elapsed().as_secs_f64());
+    response
+}
+
+/// What the server holds, as seen at scrape time. Plain values, so each store
+/// can be read under its own short lock and released before the next.
+#[derive(Debug, Clone, Default)]
+pub struct StoreGauges {
+    pub graphs: Vec<GraphGauges>,
+    pub multi_graphs: Vec<MultiGraphGauges>,
+    pub recorded_traces: usize,
+    pub provenance_notes: usize,
+    pub pending_changes: usize,
+}
+
+/// Gauges of one base graph
+#[derive(Debug, Clone)]
+pub struct GraphGauges {
+    pub graph_id: Uuid,
+    pub nodes: usize,
+}
+
+impl GraphGauges {
+    pub fn of(graph: &SarsCov2Graph) -> Self {
+        Self { graph_id: graph.id, nodes: base_graph_nodes(graph) }
+    }
+}
+
+/// Gauges of one multi-intent graph
+#[derive(Debug, Clone)]
+pub struct MultiGraphGauges {
+    pub graph_id: Uuid,
+    pub nodes: usize,
+    pub edges: usize,
+    pub avg_trace_diversity: f32,
+    pub last_updated: Option<i64>,    // Unix seconds; None when unparseable
+}
+
+impl MultiGraphGauges {
+    pub fn of(graph: &MultiIntentGraph) -> Self {
+        Self {
+            graph_id: graph.id,
+            nodes: graph.intent_nodes.len(),
+            edges: graph.edges.len(),
+            avg_trace_diversity: graph.statistics().avg_trace_diversity,
+            last_updated: chrono::DateTime::parse_from_rfc3339(&graph.metadata.last_updated).ok().map(|t| t.timestamp()),
+        }
+    }
+}
+
+/// Prometheus text format (version 0.0.4)
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Text exposition builder: one HELP/TYPE header per family, then its samples
+#[derive(Default)]
+struct Exposition {
+    out: String,
+}
+
+impl Exposition {
+    fn family(&mut self, name: &str, kind: &str, help: &str) {
+        let _ = writeln!(self.out, "# HELP {} {}", name, help);
+        let _ = writeln!(self.out, "# TYPE {} {}", name, kind);
+    }
+
+    fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: f64) {
+        self.out.push_str(name);
+        if !labels.is_empty() {
+            let labels: Vec<String> = labels.iter().map(|(k, v)| format!("{}=\"{}\"", k, escape_label(v))).collect();
+            let _ = write!(self.out, "{{{}}}", labels.join(","));
+        }
+        let _ = writeln!(self.out, " {}", value);
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+fn base_graph_nodes(graph: &SarsCov2Graph) -> usize {
+    1 + graph.virology.len() + graph.immunology.len() + graph.genomics.len() + graph.treatment.len() + graph.public_health.len()
+}
+
+/// Render request metrics and store gauges. Graph series are labelled by `graph_id`.
+pub fn render_prometheus(requests: &RequestMetrics, store: &StoreGauges) -> String {
+    let mut ex = Exposition::default();
+    let routes = requests.routes.lock().unwrap();
+
+    ex.family("sarscov2_http_requests_total", "counter", "HTTP requests by method, route pattern and status");
+    for ((method, route), stats) in routes.iter() {
+        for (status, count) in &stats.by_status {
+            let status = status.to_string();
+            ex.sample("sarscov2_http_requests_total", &[("method", method), ("route", route), ("status", &status)], *count as f64);
+        }
+    }
+
+    ex.family("sarscov2_http_request_duration_seconds", "histogram", "HTTP request latency by method and route pattern");
+    for ((method, route), stats) in routes.iter() {
+        let mut cumulative = 0;
+        for (le, n) in LATENCY_BUCKETS.iter().zip(stats.buckets) {
+            cumulative += n;
+            let le = le.to_string();
+            ex.sample("sarscov2_http_request_duration_seconds_bucket", &[("method", method), ("route", route), ("le", &le)], cumulative as f64);
+        }
+        ex.sample("sarscov2_http_request_duration_seconds_bucket", &[("method", method), ("route", route), ("le", "+Inf")], stats.count as f64);
+        ex.sample("sarscov2_http_request_duration_seconds_sum", &[("method", method), ("route", route)], stats.sum_seconds);
+        ex.sample("sarscov2_http_request_duration_seconds_count", &[("method", method), ("route", route)], stats.count as f64);
+    }
+    drop(routes);
+
+    ex.family("sarscov2_graphs_stored", "gauge", "Graphs held by the server");
+    ex.sample("sarscov2_graphs_stored", &[("kind", "base")], store.graphs.len() as f64);
+    ex.sample("sarscov2_graphs_stored", &[("kind", "multi_intent")], store.multi_graphs.len() as f64);
+
+    ex.family("sarscov2_graph_nodes", "gauge", "Nodes per graph");
+    for g in &store.graphs {
+        ex.sample("sarscov2_graph_nodes", &[("graph_id", &g.graph_id.to_string()), ("kind", "base")], g.nodes as f64);
+    }
+    for g in &store.multi_graphs {
+        ex.sample("sarscov2_graph_nodes", &[("graph_id", &g.graph_id.to_string()), ("kind", "multi_intent")], g.nodes as f64);
+    }
+
+    ex.family("sarscov2_graph_edges", "gauge", "Edges per multi-intent graph");
+    for g in &store.multi_graphs {
+        ex.sample("sarscov2_graph_edges", &[("graph_id", &g.graph_id.to_string())], g.edges as f64);
+    }
+
+    ex.family("sarscov2_graph_avg_trace_diversity", "gauge", "Mean serendipity trace diversity per multi-intent graph");
+    for g in &store.multi_graphs {
+        ex.sample("sarscov2_graph_avg_trace_diversity", &[("graph_id", &g.graph_id.to_string())], g.avg_trace_diversity as f64);
+    }
+
+    ex.family("sarscov2_graph_last_updated_timestamp_seconds", "gauge", "Unix time of the last mutation per multi-intent graph; alert on staleness to catch ingestion stalls");
+    for g in &store.multi_graphs {
+        if let Some(t) = g.last_updated {
+            ex.sample("sarscov2_graph_last_updated_timestamp_seconds", &[("graph_id", &g.graph_id.to_string())], t as f64);
+        }
+    }
+
+    ex.family("sarscov2_recorded_traces", "gauge", "Serendipity traces recorded over HTTP");
+    ex.sample("sarscov2_recorded_traces", &[], store.recorded_traces as f64);
+    ex.family("sarscov2_provenance_notes", "gauge", "Provenance notes held by the server");
+    ex.sample("sarscov2_provenance_notes", &[], store.provenance_notes as f64);
+    ex.family("sarscov2_pending_changes", "gauge", "Proposed changes awaiting review");
+    ex.sample("sarscov2_pending_changes", &[], store.pending_changes as f64);
+
+    ex.out
+}
@@ -0,0 +1,88 @@
+// limit-sarscov2/src/trace_store.rs
+// Serendipity traces kept by the server, indexed by the graph they explored and their session
+
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::serendipity_trace::SerendipityTrace;
+
+/// Traces in recording order, with lookups by ID, graph and session
+#[derive(Debug, Clone, Default)]
+pub struct TraceStore {
+    traces: Vec<SerendipityTrace>,
+    position: HashMap<Uuid, usize>,           // trace ID → index in `traces`
+    graph_of: HashMap<Uuid, Uuid>,            // trace ID → graph it belongs to
+    by_graph: HashMap<Uuid, Vec<usize>>,
+    by_session: HashMap<String, Vec<usize>>,
+}
+
+impl TraceStore {
+    /// Store `trace`, belonging to `graph_id` if given. A trace already stored
+    /// is replaced and keeps its place; its graph is kept unless one is given.
+    pub fn insert(&mut self, trace: SerendipityTrace, graph_id: Option<Uuid>) {
+        let index = match self.position.get(&trace.id) {
+            Some(&index) => {
+                self.traces[index] = trace;
+                index
+            }
+            None => {
+                let index = self.traces.len();
+                self.position.insert(trace.id, index);
+                self.by_session.entry(trace.session_id.clone()).or_default().push(index);
+                self.traces.push(trace);
+                index
+            }
+        };
+        let id = self.traces[index].id;
+        if let Some(graph_id) = graph_id {
+            if let Some(previous) = self.graph_of.insert(id, graph_id) {
+                if let Some(indices) = self.by_graph.get_mut(&previous) {
+                    indices.retain(|&i| i != index);
+                }
+            }
+            self.by_graph.entry(graph_id).or_default().push(index);
+        }
+    }
+
+    pub fn get(&self, id: Uuid) -> Option<&SerendipityTrace> {
+        self.position.get(&id).map(|&i| &self.traces[i])
+    }
+
+    /// Trace `id` for appending steps. Its ID and session must not change.
+    pub fn get_mut(&mut self, id: Uuid) -> Option<&mut SerendipityTrace> {
+        self.position.get(&id).map(|&i| &mut self.traces[i])
+    }
+
+    /// Graph trace `id` belongs to, if it was stored with one
+    pub fn graph_of(&self, id: Uuid) -> Option<Uuid> {
+        self.graph_of.get(&id).copied()
+    }
+
+    /// Traces of `graph_id`, in recording order
+    pub fn for_graph(&self, graph_id: Uuid) -> Vec<&SerendipityTrace> {
+        self.indexed(self.by_graph.get(&graph_id))
+    }
+
+    /// Traces of session `session_id`, in recording order
+    pub fn for_session(&self, session_id: &str) -> Vec<&SerendipityTrace> {
+        self.indexed(self.by_session.get(session_id))
+    }
+
+    fn indexed(&self, indices: Option<&Vec<usize>>) -> Vec<&SerendipityTrace> {
+        let mut indices = indices.cloned().unwrap_or_default();
+        indices.sort_unstable();
+        indices.into_iter().map(|i| &self.traces[i]).collect()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &SerendipityTrace> {
+        self.traces.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.traces.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.traces.is_empty()
+    }
+}
@@ -0,0 +1,259 @@
+// limit-sarscov2/src/workspace.rs
+// Workspaces: each team's graphs, provenance, traces, governance policy and API keys, served under /w/:workspace
+
+use axum::{
+    extract::{Request, State},
+    middleware::{self, Next},
+    response::Response,
+    Router,
+};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::{collections::{HashMap, HashSet}, fs, path::{Component, Path, PathBuf}, sync::{Arc, Mutex}};
+use anyhow::{bail, Context, Result};
+
+use crate::api::{self, AppState};
+use crate::approval::ChangeQueue;
+use crate::audit::AuditLog;
+use crate::error::ApiError;
+use crate::event_log::EventLog;
+use crate::governance::GovernancePolicy;
+
+/// One workspace in the workspaces file
+#[derive(Debug, Clone, Deserialize)]
+pub struct WorkspaceConfig {
+    pub name: String,                         // URL segment: lowercase letters, digits, '-' and '_'
+    #[serde(default)]
+    pub api_keys: Vec<ApiKeyConfig>,          // accepted keys; at least one
+    pub governance_policy: Option<PathBuf>,   // governance TOML; the default policy if unset
+    pub audit_log: Option<PathBuf>,           // default ./<name>-governance-audit.jsonl
+    pub event_log: Option<PathBuf>,           // default ./<name>-graph-events.jsonl
+    pub changes_log: Option<PathBuf>,         // default ./<name>-pending-changes.jsonl
+}
+
+/// An `api_keys` entry: the SHA-256 (hex) of a key, or a table binding the
+/// key to the actor its holder's writes are attributed to and the role they
+/// review proposed changes as
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum ApiKeyConfig {
+    Digest(String),
+    Bound {
+        digest: String,
+        #[serde(default)]
+        actor: Option<String>,
+        #[serde(default)]
+        role: Option<String>,
+    },
+}
+
+impl ApiKeyConfig {
+    pub fn digest(&self) -> &str {
+        match self {
+            Self::Digest(digest) | Self::Bound { digest, .. } => digest,
+        }
+    }
+
+    /// The bound actor, else `key:` and the digest's first 12 hex digits
+    pub fn actor(&self) -> String {
+        match self {
+            Self::Bound { actor: Some(actor), .. } => actor.clone(),
+            _ => digest_actor(self.digest()),
+        }
+    }
+
+    pub fn role(&self) -> Option<&str> {
+        match self {
+            Self::Digest(_) => None,
+            Self::Bound { role, .. } => role.as_deref(),
+        }
+    }
+}
+
+/// Actor of a key bound to none
+fn digest_actor(digest: &str) -> String {
+    format!("key:{}", &digest.to_ascii_lowercase()[..12.min(digest.len())])
+}
+
+impl WorkspaceConfig {
+    fn default_path(&self, suffix: &str) -> PathBuf {
+        PathBuf::from(format!("{}-{}", self.name, suffix))
+    }
+
+    pub fn audit_log_path(&self) -> PathBuf {
+        self.audit_log.clone().unwrap_or_else(|| self.default_path("governance-audit.jsonl"))
+    }
+
+    pub fn event_log_path(&self) -> PathBuf {
+        self.event_log.clone().unwrap_or_else(|| self.default_path("graph-events.jsonl"))
+    }
+
+    pub fn changes_log_path(&self) -> PathBuf {
+        self.changes_log.clone().unwrap_or_else(|| self.default_path("pending-changes.jsonl"))
+    }
+}
+
+/// `path` without `.` components, so "./a.jsonl" and "a.jsonl" compare equal
+fn normalized(path: &Path) -> PathBuf {
+    path.components().filter(|c| !matches!(c, Component::CurDir)).collect()
+}
+
+/// `[[workspace]]` tables
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct WorkspacesConfig {
+    #[serde(default, rename = "workspace")]
+    pub workspaces: Vec<WorkspaceConfig>,
+}
+
+impl WorkspacesConfig {
+    pub fn from_toml(text: &str) -> Result<Self> {
+        let config: Self = toml::from_str(text).context("parsing workspaces TOML")?;
+        let mut names = HashSet::new();
+        let mut logs: HashMap<PathBuf, &str> = HashMap::new();
+        for ws in &config.workspaces {
+            let valid = !ws.name.is_empty()
+                && ws.name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+            if !valid {
+                bail!("workspace name {:?} must be lowercase letters, digits, '-' or '_'", ws.name);
+            }
+            if !names.insert(ws.name.as_str()) {
+                bail!("workspace {:?} is defined twice", ws.name);
+            }
+            if ws.api_keys.is_empty() {
+                bail!("workspace {:?} has no api_keys", ws.name);
+            }
+            if let Some(bad) = ws.api_keys.iter().map(ApiKeyConfig::digest).find(|k| k.len() != 64 || !k.chars().all(|c| c.is_ascii_hexdigit())) {
+                bail!("workspace {:?}: API key digest {:?} is not SHA-256 hex", ws.name, bad);
+            }
+            if let Some(bad) = ws.api_keys.iter().map(ApiKeyConfig::actor).find(|a| a.trim().is_empty() || a.len() > 128) {
+                bail!("workspace {:?}: API key actor {:?} must be 1 to 128 characters", ws.name, bad);
+            }
+            for path in [ws.audit_log_path(), ws.event_log_path(), ws.changes_log_path()] {
+                if let Some(other) = logs.insert(normalized(&path), &ws.name) {
+                    bail!("workspace {:?}: log {} is already used by workspace {:?}", ws.name, path.display(), other);
+                }
+            }
+        }
+        Ok(config)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        Self::from_toml(&text).with_context(|| format!("loading {}", path.display()))
+    }
+}
+
+/// Who a request's API key says the caller is; `require_api_key` adds it to
+/// the request's extensions
+#[derive(Debug, Clone, Default)]
+pub struct Caller {
+    pub actor: String,                // stamped on the provenance notes the request makes
+    pub role: Option<String>,         // bound to the key in `api_keys`
+}
+
+/// API keys a workspace accepts, held as SHA-256 digests with the caller each is bound to
+#[derive(Debug, Clone, Default)]
+pub struct ApiKeys {
+    digests: HashMap<String, Caller>,
+}
+
+impl ApiKeys {
+    /// Keys bound to no actor or role
+    pub fn from_digests<'a>(digests: impl IntoIterator<Item = &'a String>) -> Self {
+        Self { digests: digests.into_iter().map(|d| (d.to_ascii_lowercase(), Caller { actor: digest_actor(d), role: None })).collect() }
+    }
+
+    pub fn from_config(keys: &[ApiKeyConfig]) -> Self {
+        let caller = |k: &ApiKeyConfig| Caller { actor: k.actor(), role: k.role().map(str::to_string) };
+        Self { digests: keys.iter().map(|k| (k.digest().to_ascii_lowercase(), caller(k))).collect() }
+    }
+
+    /// SHA-256 (hex) of `key`, as listed in `api_keys`
+    pub fn digest(key: &str) -> String {
+        format!("{:x}", Sha256::digest(key.as_bytes()))
+    }
+
+    pub fn accepts(&self, key: &str) -> bool {
+        self.digests.contains_key(&Self::digest(key))
+    }
+
+    /// The caller holding `key`, if it is accepted
+    pub fn caller(&self, key: &str) -> Option<Caller> {
+        self.digests.get(&Self::digest(key)).cloned()
+    }
+
+    /// Keys accepted by any of `sets`
+    pub fn union<'a>(sets: impl IntoIterator<Item = &'a ApiKeys>) -> Self {
+        Self { digests: sets.into_iter().flat_map(|keys| keys.digests.iter().map(|(d, c)| (d.clone(), c.clone()))).collect() }
+    }
+}
+
+/// Middleware: 401 unless the request carries one of the workspace's keys,
+/// as `x-api-key` or `Authorization: Bearer <key>`; the key's `Caller` is
+/// passed on as a request extension and its actor to the request's provenance notes
+pub async fn require_api_key(State(keys): State<Arc<ApiKeys>>, mut request: Request, next: Next) -> Result<Response, ApiError> {
+    let caller = {
+        let header = |name: &str| request.headers().get(name).and_then(|v| v.to_str().ok());
+        match header("x-api-key").or_else(|| header("authorization").and_then(|v| v.strip_prefix("Bearer "))) {
+            None => return Err(ApiError::Unauthorized("API key required".into())),
+            Some(key) => keys.caller(key.trim()).ok_or_else(|| ApiError::Unauthorized("unknown API key".into()))?,
+        }
+    };
+    crate::correlation::set_actor(&caller.actor);
+    request.extensions_mut().insert(caller);
+    Ok(next.run(request).await)
+}
+
+/// One team's isolated stores
+#[derive(Clone)]
+pub struct Workspace {
+    pub name: String,
+    pub state: AppState,
+    pub keys: Arc<ApiKeys>,
+}
+
+impl Workspace {
+    /// Load the workspace's policy, open its audit, event and change logs and
+    /// rebuild its graphs. From `shared` it takes what all workspaces share: the
+    /// retrieval corpus, the signing and trusted keys and the request metrics.
+    pub fn open(config: &WorkspaceConfig, shared: &AppState) -> Result<Self> {
+        let governance = match &config.governance_policy {
+            Some(path) => GovernancePolicy::load(path)?,
+            None => GovernancePolicy::default(),
+        };
+        let audit = AuditLog::open(config.audit_log_path())?;
+        let events = EventLog::open(config.event_log_path())?;
+        let changes = ChangeQueue::open(config.changes_log_path())?;
+        let multi_graphs = events.graphs();
+        tracing::info!(workspace = %config.name, graphs = multi_graphs.len(), keys = config.api_keys.len(), "workspace opened");
+
+        let state = AppState {
+            graphs: Default::default(),
+            provenance: Default::default(),
+            rd_curves: Default::default(),
+            multi_graphs: Arc::new(Mutex::new(multi_graphs)),
+            retrieval: shared.retrieval.clone(),
+            traces: Default::default(),
+            governance: Arc::new(Mutex::new(governance)),
+            governance_path: config.governance_policy.clone(),
+            audit: Arc::new(Mutex::new(audit)),
+            changes: Arc::new(Mutex::new(changes)),
+            events: Arc::new(Mutex::new(events)),
+            signer: shared.signer.clone(),
+            trusted_keys: shared.trusted_keys.clone(),
+            requests: shared.requests.clone(),
+            metrics_history: Default::default(),
+        };
+        Ok(Self { name: config.name.clone(), state, keys: Arc::new(ApiKeys::from_config(&config.api_keys)) })
+    }
+}
+
+/// The REST API of every workspace under `/w/<name>`, each behind its API keys
+pub fn router(workspaces: &[Workspace]) -> Router {
+    workspaces.iter().fold(Router::new(), |app, ws| {
+        let routes = api::router(ws.state.clone())
+            .layer(middleware::from_fn_with_state(ws.keys.clone(), require_api_key));
+        app.nest(&format!("/w/{}", ws.name), routes)
+    })
+}
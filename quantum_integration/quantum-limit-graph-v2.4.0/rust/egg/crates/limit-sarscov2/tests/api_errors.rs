@@ -0,0 +1,95 @@
+// limit-sarscov2/tests/api_errors.rs
+// Status codes and JSON error bodies of the REST handlers
+
+mod common;
+
+use axum::http::{Method, StatusCode};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use common::{fixture, get, post, proposed_node, send, state};
+use limit_sarscov2::api;
+
+fn assert_error_body(body: &Value, status: StatusCode, contains: &str) {
+    assert_eq!(body["status"], json!(status.as_u16()), "body {}", body);
+    let error = body["error"].as_str().expect("error message");
+    assert!(error.contains(contains), "{:?} does not mention {:?}", error, contains);
+    assert!(body.get("trace_id").is_none());
+}
+
+#[tokio::test]
+async fn unknown_graph_is_404() {
+    let app = api::router(state(vec![fixture().graph]));
+    let missing = Uuid::new_v4();
+    let reply = get(&app, &format!("/multi-graph/{}/summary", missing)).await;
+    assert_eq!(reply.status, StatusCode::NOT_FOUND);
+    assert_error_body(&reply.body, StatusCode::NOT_FOUND, &format!("graph {} not found", missing));
+}
+
+#[tokio::test]
+async fn unknown_change_is_404() {
+    let app = api::router(state(vec![]));
+    let reply = post(&app, &format!("/governance/approve/{}", Uuid::new_v4()), &[], json!({})).await;
+    assert_eq!(reply.status, StatusCode::NOT_FOUND);
+    assert_error_body(&reply.body, StatusCode::NOT_FOUND, "not found");
+}
+
+#[tokio::test]
+async fn malformed_json_is_400_and_wrong_shape_is_422() {
+    let f = fixture();
+    let app = api::router(state(vec![f.graph.clone()]));
+    let uri = format!("/multi-graph/{}/proposals", f.graph.id);
+
+    let reply = send(&app, Method::POST, &uri, &[("content-type", "application/json")], None).await;
+    assert_eq!(reply.status, StatusCode::BAD_REQUEST);
+    assert_eq!(reply.body["status"], json!(400));
+
+    let reply = post(&app, &uri, &[], json!({ "kind": "add_node", "value": { "id": "not-a-uuid" } })).await;
+    assert_eq!(reply.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(reply.body["status"], json!(422));
+}
+
+#[tokio::test]
+async fn schema_violation_is_422() {
+    let f = fixture();
+    let app = api::router(state(vec![f.graph.clone()]));
+    let reply = post(&app, &format!("/multi-graph/{}/proposals", f.graph.id), &[], proposed_node(1.5)).await;
+    assert_eq!(reply.status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_error_body(&reply.body, StatusCode::UNPROCESSABLE_ENTITY, "confidence");
+}
+
+#[tokio::test]
+async fn approving_twice_is_409() {
+    let f = fixture();
+    let app = api::router(state(vec![f.graph.clone()]));
+    let proposed = post(&app, &format!("/multi-graph/{}/proposals", f.graph.id), &[("x-actor", "ana")], proposed_node(0.6)).await;
+    assert_eq!(proposed.status, StatusCode::CREATED);
+    let approve = format!("/governance/approve/{}", proposed.body["id"].as_str().unwrap());
+
+    let first = post(&app, &approve, &[("x-actor", "ben")], json!({})).await;
+    assert_eq!(first.status, StatusCode::OK);
+    assert_eq!(first.body["status"], json!("approved"));
+
+    let second = post(&app, &approve, &[("x-actor", "ben")], json!({})).await;
+    assert_eq!(second.status, StatusCode::CONFLICT);
+    assert_error_body(&second.body, StatusCode::CONFLICT, "already Approved");
+}
+
+#[tokio::test]
+async fn reviewer_without_an_approver_role_is_403() {
+    let f = fixture();
+    let state = state(vec![f.graph.clone()]);
+    state.governance.lock().unwrap().approver_roles = vec!["curator".into()];
+    let app = api::router(state);
+    let proposed = post(&app, &format!("/multi-graph/{}/proposals", f.graph.id), &[], proposed_node(0.6)).await;
+    let approve = format!("/governance/approve/{}", proposed.body["id"].as_str().unwrap());
+
+    let reply = post(&app, &approve, &[("x-role", "guest")], json!({})).await;
+    assert_eq!(reply.status, StatusCode::FORBIDDEN);
+    assert_error_body(&reply.body, StatusCode::FORBIDDEN, "may not review");
+
+    let summary = get(&app, &format!("/multi-graph/{}/summary", f.graph.id)).await;
+    assert_eq!(summary.status, StatusCode::OK);
+    let changes = get(&app, "/governance/changes?status=proposed").await;
+    assert_eq!(changes.body.as_array().map(Vec::len), Some(1), "change stays pending");
+}
@@ -0,0 +1,59 @@
+// limit-sarscov2/tests/approval.rs
+// Pending changes persisted across restarts
+
+mod common;
+
+use axum::http::StatusCode;
+use serde_json::json;
+use std::{path::PathBuf, sync::{Arc, Mutex}};
+use uuid::Uuid;
+
+use common::{fixture, get, post, proposed_node, state};
+use limit_sarscov2::{api, ChangeQueue, ChangeStatus, ProposedChange, Reviewer};
+
+fn scratch_log() -> PathBuf {
+    std::env::temp_dir().join(format!("pending-changes-{}.jsonl", Uuid::new_v4()))
+}
+
+#[test]
+fn reopened_queue_keeps_latest_status() {
+    let path = scratch_log();
+    let change: ProposedChange = serde_json::from_value(proposed_node(0.6)).unwrap();
+    let graph_id = Uuid::new_v4();
+    let reviewer = Reviewer { actor: "ben", role: None };
+
+    let mut queue = ChangeQueue::open(&path).unwrap();
+    let kept = queue.propose(graph_id, change.clone(), "ana").unwrap().id;
+    let rejected = queue.propose(graph_id, change, "ana").unwrap().id;
+    queue.reject(rejected, &reviewer, &[], Some("duplicate".into())).unwrap();
+
+    let reopened = ChangeQueue::open(&path).unwrap();
+    assert_eq!(reopened.list(None, None).len(), 2);
+    assert_eq!(reopened.get(kept).unwrap().status, ChangeStatus::Proposed);
+    let reviewed = reopened.get(rejected).unwrap();
+    assert_eq!(reviewed.status, ChangeStatus::Rejected);
+    assert_eq!(reviewed.reviewed_by.as_deref(), Some("ben"));
+    assert_eq!(reviewed.note.as_deref(), Some("duplicate"));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn proposal_survives_a_restart_and_can_then_be_approved() {
+    let path = scratch_log();
+    let f = fixture();
+    let before = state(vec![f.graph.clone()]);
+    let before = api::AppState { changes: Arc::new(Mutex::new(ChangeQueue::open(&path).unwrap())), ..before };
+    let proposed = post(&api::router(before), &format!("/multi-graph/{}/proposals", f.graph.id), &[], proposed_node(0.6)).await;
+    assert_eq!(proposed.status, StatusCode::CREATED);
+
+    let after = state(vec![f.graph.clone()]);
+    let after = api::AppState { changes: Arc::new(Mutex::new(ChangeQueue::open(&path).unwrap())), ..after };
+    let app = api::router(after);
+    let pending = get(&app, "/governance/changes?status=proposed").await;
+    assert_eq!(pending.body[0]["id"], proposed.body["id"]);
+
+    let approved = post(&app, &format!("/governance/approve/{}", proposed.body["id"].as_str().unwrap()), &[], json!({})).await;
+    assert_eq!(approved.status, StatusCode::OK);
+    assert_eq!(ChangeQueue::open(&path).unwrap().list(None, Some(ChangeStatus::Approved)).len(), 1);
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,76 @@
+// limit-sarscov2/tests/cache.rs
+// LRU eviction order, recency refresh on get, replacement and a disabled cache
+
+use limit_sarscov2::LruCache;
+
+#[test]
+fn evicts_the_least_recently_used_entry_at_capacity() {
+    let mut cache = LruCache::new(2);
+    cache.put("ba.5", 1);
+    cache.put("ba.2", 2);
+    cache.put("xbb", 3);
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.get(&"ba.5"), None);
+    assert_eq!(cache.get(&"ba.2"), Some(&2));
+    assert_eq!(cache.get(&"xbb"), Some(&3));
+}
+
+#[test]
+fn get_refreshes_an_entrys_recency() {
+    let mut cache = LruCache::new(2);
+    cache.put("ba.5", 1);
+    cache.put("ba.2", 2);
+    // ba.5 is now the most recently used, so ba.2 goes next
+    assert_eq!(cache.get(&"ba.5"), Some(&1));
+    cache.put("xbb", 3);
+    assert_eq!(cache.get(&"ba.2"), None);
+    assert_eq!(cache.get(&"ba.5"), Some(&1));
+    assert_eq!(cache.get(&"xbb"), Some(&3));
+
+    // A miss does not disturb the order
+    assert_eq!(cache.get(&"jn.1"), None);
+    cache.put("jn.1", 4);
+    assert_eq!(cache.get(&"ba.5"), None);
+    assert_eq!(cache.get(&"xbb"), Some(&3));
+}
+
+#[test]
+fn replacing_a_key_updates_it_without_evicting() {
+    let mut cache = LruCache::new(2);
+    cache.put("ba.5", 1);
+    cache.put("ba.2", 2);
+    cache.put("ba.5", 10);
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.get(&"ba.2"), Some(&2));
+    assert_eq!(cache.get(&"ba.5"), Some(&10));
+
+    // The replacement counts as a use: ba.2 is the older of the two
+    let mut cache = LruCache::new(2);
+    cache.put("ba.5", 1);
+    cache.put("ba.2", 2);
+    cache.put("ba.5", 10);
+    cache.put("xbb", 3);
+    assert_eq!(cache.get(&"ba.2"), None);
+    assert_eq!(cache.get(&"ba.5"), Some(&10));
+}
+
+#[test]
+fn zero_capacity_disables_caching_and_clear_empties() {
+    let mut disabled = LruCache::new(0);
+    disabled.put("ba.5", 1);
+    assert!(disabled.is_empty());
+    assert_eq!(disabled.get(&"ba.5"), None);
+
+    let mut cache = LruCache::new(3);
+    cache.put("ba.5", 1);
+    cache.put("ba.2", 2);
+    cache.clear();
+    assert!(cache.is_empty());
+    assert_eq!(cache.capacity(), 3);
+    assert_eq!(cache.get(&"ba.5"), None);
+    // Still usable at full capacity after clearing
+    for (i, key) in ["ba.5", "ba.2", "xbb"].into_iter().enumerate() {
+        cache.put(key, i);
+    }
+    assert_eq!(cache.len(), 3);
+}
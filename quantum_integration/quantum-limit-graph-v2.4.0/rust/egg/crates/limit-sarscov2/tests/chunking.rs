@@ -0,0 +1,83 @@
+// limit-sarscov2/tests/chunking.rs
+// Chunk boundaries and overlap, short and empty input, and stable chunk IDs
+
+use uuid::Uuid;
+
+use limit_sarscov2::{Chunker, CorpusDoc};
+
+const SENTENCES: &str = "Omicron spreads fast. Vaccines still help. Boosters restore titres. Masks reduce spread.";
+
+fn doc(text: &str) -> CorpusDoc {
+    CorpusDoc { id: Uuid::new_v4(), domain: "PublicHealth".into(), text: text.into(), source: "doi:10.1000/test".into(), mesh: vec![] }
+}
+
+#[test]
+fn sentence_chunks_break_between_sentences_and_share_the_overlap() {
+    let chunks = Chunker::sentences(45, 1).chunk_text(SENTENCES);
+    assert_eq!(chunks, [
+        "Omicron spreads fast. Vaccines still help.",
+        "Vaccines still help. Boosters restore titres.",
+        "Boosters restore titres. Masks reduce spread.",
+    ]);
+    assert!(chunks.iter().all(|c| c.chars().count() <= 45));
+
+    // Without overlap every sentence appears once
+    assert_eq!(Chunker::sentences(45, 0).chunk_text(SENTENCES), [
+        "Omicron spreads fast. Vaccines still help.",
+        "Boosters restore titres. Masks reduce spread.",
+    ]);
+}
+
+#[test]
+fn overlap_never_stalls_the_chunker() {
+    // Each chunk holds one sentence; an overlap of two would step backwards
+    let chunks = Chunker::sentences(25, 2).chunk_text(SENTENCES);
+    assert_eq!(chunks, ["Omicron spreads fast.", "Vaccines still help.", "Boosters restore titres.", "Masks reduce spread."]);
+}
+
+#[test]
+fn sentences_do_not_break_after_abbreviations() {
+    let chunks = Chunker::sentences(40, 0).chunk_text("Titres fell (Smith et al. 2023). Boosters e.g. BA.5 bivalent helped.");
+    assert_eq!(chunks, ["Titres fell (Smith et al. 2023).", "Boosters e.g. BA.5 bivalent helped."]);
+}
+
+#[test]
+fn paragraph_chunks_rejoin_with_blank_lines() {
+    let text = "Spike binding rose.\n\n  Neutralization fell\nsharply.\n\n\nBoosters helped.";
+    assert_eq!(Chunker::paragraphs(50, 0).chunk_text(text), [
+        "Spike binding rose.\n\nNeutralization fell sharply.",
+        "Boosters helped.",
+    ]);
+}
+
+#[test]
+fn units_longer_than_a_chunk_are_cut_at_word_boundaries() {
+    let chunks = Chunker::paragraphs(12, 0).chunk_text("spike binding affinity rose sharply");
+    assert_eq!(chunks, ["spike", "binding", "affinity", "rose sharply"]);
+}
+
+#[test]
+fn short_and_empty_input() {
+    let chunker = Chunker::sentences(45, 1);
+    assert_eq!(chunker.chunk_text("Omicron spreads fast."), ["Omicron spreads fast."]);
+    assert!(chunker.chunk_text("").is_empty());
+    assert!(chunker.chunk_text("  \n\n ").is_empty());
+
+    // Documents that fit are returned unchanged, empty ones included
+    for text in ["Omicron spreads fast.", ""] {
+        let short = doc(text);
+        let chunks = chunker.chunk(&short);
+        assert_eq!(chunks.len(), 1);
+        assert_eq!((chunks[0].id, chunks[0].text.as_str()), (short.id, text));
+    }
+}
+
+#[test]
+fn document_chunks_keep_their_source_and_stable_ids() {
+    let long = doc(SENTENCES);
+    let chunks = Chunker::sentences(45, 1).chunk(&long);
+    assert_eq!(chunks.len(), 3);
+    assert!(chunks.iter().all(|c| c.domain == long.domain && c.source == long.source && c.id != long.id));
+    let again = Chunker::sentences(45, 1).chunk(&long);
+    assert_eq!(chunks.iter().map(|c| c.id).collect::<Vec<_>>(), again.iter().map(|c| c.id).collect::<Vec<_>>());
+}
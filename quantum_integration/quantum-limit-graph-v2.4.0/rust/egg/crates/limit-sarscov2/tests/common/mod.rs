@@ -0,0 +1,140 @@
+// limit-sarscov2/tests/common/mod.rs
+// Shared harness for handler tests: in-memory server state, a two-node graph and a oneshot request helper
+
+#![allow(dead_code)]
+
+use axum::{body::Body, http::{HeaderMap, Method, Request, StatusCode}, Router};
+use http_body_util::BodyExt;
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+use tower::ServiceExt;
+use uuid::Uuid;
+
+use limit_sarscov2::{
+    api::AppState, approval::ProposedChange, domain::SarsCov2Graph, edges::GraphEdge,
+    multi_intent_graph::{IntentNode, MultiIntentGraphBuilder, NodeContent, NodeMetadata},
+    nodes::{ImmunologyNode, VirologyNode, VirusNode}, AuditLog, EventLog, GraphEvent, MultiIntentGraph, ResearchDomain, RetrievalBackend,
+};
+
+/// Graph with spike binding (virology) → neutralization (immunology)
+pub struct Fixture {
+    pub graph: MultiIntentGraph,
+    pub spike: Uuid,
+    pub antibody: Uuid,
+    pub edge: Uuid,
+}
+
+pub fn fixture() -> Fixture {
+    let root = VirusNode { id: Uuid::new_v4(), name: "SARS-CoV-2".into(), genome_kb: 30.0 };
+    let spike = VirologyNode { id: Uuid::new_v4(), topic: "Spike-ACE2 binding".into(), details: "RBD affinity".into(), regions: vec![] };
+    let antibody = ImmunologyNode { id: Uuid::new_v4(), topic: "Antibody neutralization".into(), details: "RBD-directed".into() };
+    let edge = GraphEdge::new_causal(
+        spike.id, antibody.id, "escapes".into(), "Virology".into(), "Immunology".into(), vec!["PMID:1".into()], 0.8,
+    );
+    let (spike_id, antibody_id, edge_id) = (spike.id, antibody.id, edge.id);
+    let graph = MultiIntentGraphBuilder::new(SarsCov2Graph::new(root))
+        .with_biology_node(spike, "immune_escape", 3, 0.8)
+        .with_immunology_node(antibody, "immune_escape", 2, 0.7)
+        .with_edge(edge)
+        .build();
+    Fixture { graph, spike: spike_id, antibody: antibody_id, edge: edge_id }
+}
+
+/// `add_node` proposal body for a fresh immunology node
+pub fn proposed_node(confidence: f32) -> Value {
+    serde_json::to_value(ProposedChange::AddNode(immunology_node("T-cell response", confidence))).unwrap()
+}
+
+/// Fresh immunology node, not in any graph
+pub fn immunology_node(topic: &str, confidence: f32) -> IntentNode {
+    let topic = ImmunologyNode { id: Uuid::new_v4(), topic: topic.into(), details: "CD8".into() };
+    IntentNode {
+        id: topic.id,
+        intent: "immune_escape".into(),
+        domain: ResearchDomain::Immunology,
+        content: NodeContent::Immunology(topic),
+        metadata: NodeMetadata {
+            evidence_count: 1,
+            confidence,
+            sources: vec![],
+            created_at: chrono::Utc::now().to_rfc3339(),
+            region: None,
+            xrefs: vec![],
+            embedding: None,
+        },
+    }
+}
+
+/// Server state over `graphs`, with every graph's creation in an in-memory event log
+pub fn state(graphs: Vec<MultiIntentGraph>) -> AppState {
+    state_with_events(graphs, EventLog::in_memory())
+}
+
+/// Server state over `graphs`, recording their creation in `events`
+pub fn state_with_events(graphs: Vec<MultiIntentGraph>, mut events: EventLog) -> AppState {
+    for graph in &graphs {
+        events.record(graph.id, "seed", "seed", [GraphEvent::GraphCreated { graph: Box::new(graph.clone()) }]).unwrap();
+    }
+    AppState {
+        graphs: Default::default(),
+        provenance: Default::default(),
+        rd_curves: Default::default(),
+        multi_graphs: Arc::new(Mutex::new(graphs)),
+        retrieval: Arc::new(Mutex::new(RetrievalBackend::new(vec![]))),
+        traces: Default::default(),
+        governance: Default::default(),
+        governance_path: None,
+        audit: Arc::new(Mutex::new(AuditLog::in_memory())),
+        changes: Default::default(),
+        events: Arc::new(Mutex::new(events)),
+        signer: None,
+        trusted_keys: Default::default(),
+        requests: Default::default(),
+        metrics_history: Default::default(),
+    }
+}
+
+pub struct Reply {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Value,                  // `Null` when the body is not JSON
+}
+
+/// Send one request through `app`; `body` goes as JSON
+pub async fn send(app: &Router, method: Method, uri: &str, headers: &[(&str, &str)], body: Option<Value>) -> Reply {
+    let mut request = Request::builder().method(method).uri(uri);
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+    let request = match body {
+        Some(body) => request.header("content-type", "application/json").body(Body::from(body.to_string())),
+        None => request.body(Body::empty()),
+    }
+    .unwrap();
+    reply(app, request).await
+}
+
+/// POST a plain-text body, e.g. a TOML policy
+pub async fn post_text(app: &Router, uri: &str, headers: &[(&str, &str)], body: &str) -> Reply {
+    let mut request = Request::builder().method(Method::POST).uri(uri).header("content-type", "text/plain");
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+    reply(app, request.body(Body::from(body.to_string())).unwrap()).await
+}
+
+async fn reply(app: &Router, request: Request<Body>) -> Reply {
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let headers = response.headers().clone();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    Reply { status, headers, body: serde_json::from_slice(&bytes).unwrap_or(Value::Null) }
+}
+
+pub async fn get(app: &Router, uri: &str) -> Reply {
+    send(app, Method::GET, uri, &[], None).await
+}
+
+pub async fn post(app: &Router, uri: &str, headers: &[(&str, &str)], body: Value) -> Reply {
+    send(app, Method::POST, uri, headers, Some(body)).await
+}
@@ -0,0 +1,123 @@
+// limit-sarscov2/tests/csv_tables.rs
+// nodes.csv / edges.csv round trips, malformed rows and endpoint name resolution
+
+mod common;
+
+use serde_json::Value;
+use uuid::Uuid;
+
+use common::fixture;
+use limit_sarscov2::{
+    domain::SarsCov2Graph, import::ParsedLine, import_records, multi_intent_graph::MultiIntentGraphBuilder, nodes::VirusNode,
+    read_edges_csv, read_nodes_csv, write_edges_csv, write_nodes_csv, ImportRecord, MultiIntentGraph,
+};
+
+fn empty_graph() -> MultiIntentGraph {
+    MultiIntentGraphBuilder::new(SarsCov2Graph::new(VirusNode { id: Uuid::new_v4(), name: "SARS-CoV-2".into(), genome_kb: 30.0 })).build()
+}
+
+fn json(value: &impl serde::Serialize) -> Value {
+    serde_json::to_value(value).unwrap()
+}
+
+/// Line numbers and errors of the rows that did not parse
+fn errors(parsed: &[ParsedLine]) -> Vec<(usize, String)> {
+    parsed.iter().filter_map(|(line, r)| r.as_ref().err().map(|e| (*line, e.clone()))).collect()
+}
+
+fn edge_endpoints(parsed: &[ParsedLine]) -> Vec<(Uuid, Uuid)> {
+    parsed.iter()
+        .filter_map(|(_, r)| match r {
+            Ok(ImportRecord::Edge(e)) => Some((e.source_id, e.target_id)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn exported_tables_import_into_an_equal_graph() {
+    let f = fixture();
+    let (mut nodes_csv, mut edges_csv) = (vec![], vec![]);
+    write_nodes_csv(&f.graph, &mut nodes_csv).unwrap();
+    write_edges_csv(&f.graph, &mut edges_csv).unwrap();
+
+    let mut copy = empty_graph();
+    let nodes = read_nodes_csv(nodes_csv.as_slice());
+    let edges = read_edges_csv(edges_csv.as_slice(), &copy, &nodes);
+    let (node_summary, _) = import_records(&mut copy, nodes);
+    let (edge_summary, _) = import_records(&mut copy, edges);
+    assert_eq!((node_summary.nodes_accepted, node_summary.rejected), (2, 0));
+    assert_eq!((edge_summary.edges_accepted, edge_summary.rejected), (1, 0));
+    assert_eq!(json(&copy.intent_nodes), json(&f.graph.intent_nodes));
+    assert_eq!(json(&copy.edges), json(&f.graph.edges));
+}
+
+#[test]
+fn malformed_rows_are_reported_by_line() {
+    let csv = "\
+id,intent,domain,label,details,confidence,evidence_count
+,immune_escape,Immunology,T-cell response,CD8,0.6,1
+,immune_escape,Astrology,Horoscope,,0.5,1
+not-a-uuid,immune_escape,Virology,Spike,RBD,0.5,1
+,immune_escape,Virology,Spike,RBD,high,1
+,immune_escape,Virology,\"Spike
+";
+    let parsed = read_nodes_csv(csv.as_bytes());
+    assert!(matches!(parsed[0], (2, Ok(ImportRecord::Node(_)))));
+    let errors = errors(&parsed);
+    let lines: Vec<usize> = errors.iter().map(|(line, _)| *line).collect();
+    assert_eq!(lines, [3, 4, 5, 6], "{:?}", errors);
+    assert_eq!(errors[0].1, "unknown domain \"Astrology\"");
+    assert_eq!(errors[1].1, "id \"not-a-uuid\" is not a UUID");
+    assert_eq!(errors[2].1, "confidence \"high\" is not a number");
+    assert!(errors[3].1.starts_with("malformed row"), "{}", errors[3].1);
+
+    // Rejected rows reach the import summary with their line numbers
+    let mut graph = empty_graph();
+    let (summary, _) = import_records(&mut graph, parsed);
+    assert_eq!(summary.nodes_accepted, 1);
+    let rejected: Vec<(usize, Option<Uuid>)> = summary.rejections.iter().map(|r| (r.line, r.id)).collect();
+    assert_eq!(rejected, [(3, None), (4, None), (5, None), (6, None)]);
+}
+
+#[test]
+fn endpoint_names_resolve_approximately() {
+    let f = fixture();
+    let csv = "\
+edge_type,source_id,target_id,label,confidence,evidence_refs
+Causal,Spike ACE2 bindng,antibody neutralisation,escapes,0.7,PMID:5
+Causal,Interferon signalling,Antibody neutralization,primes,0.7,PMID:6
+";
+    let parsed = read_edges_csv(csv.as_bytes(), &f.graph, &[]);
+    assert_eq!(edge_endpoints(&parsed), [(f.spike, f.antibody)]);
+    let Ok(ImportRecord::Edge(edge)) = &parsed[0].1 else { panic!("{:?}", parsed[0].1) };
+    assert_eq!((edge.metadata.source_domain.as_str(), edge.metadata.target_domain.as_str()), ("Virology", "Immunology"));
+    assert_eq!(errors(&parsed), [(3, "source_id \"Interferon signalling\" is neither a UUID nor the name of a node".to_string())]);
+}
+
+#[test]
+fn tied_names_are_ambiguous_unless_a_domain_decides() {
+    let f = fixture();
+    // nodes.csv adds an immunology node with the same label as the virology spike node
+    let nodes_csv = "id,intent,domain,label,details\n,immune_escape,Immunology,Spike-ACE2 binding,antibody view\n";
+    let nodes = read_nodes_csv(nodes_csv.as_bytes());
+    let Ok(ImportRecord::Node(twin)) = &nodes[0].1 else { panic!("{:?}", nodes[0].1) };
+    let twin = twin.id;
+
+    let edges_csv = "\
+edge_type,source_id,target_id,source_domain,label,confidence,evidence_refs
+Causal,Spike-ACE2 binding,Antibody neutralization,,escapes,0.7,PMID:5
+Causal,Spike-ACE2 binding,Antibody neutralization,Virology,escapes,0.7,PMID:5
+Causal,Spike-ACE2 binding,Antibody neutralization,immunology,escapes,0.7,PMID:5
+";
+    let parsed = read_edges_csv(edges_csv.as_bytes(), &f.graph, &nodes);
+    let errors = errors(&parsed);
+    assert_eq!(errors.len(), 1);
+    let (line, message) = &errors[0];
+    assert_eq!(*line, 2);
+    assert!(message.starts_with("source_id \"Spike-ACE2 binding\" is ambiguous: "), "{}", message);
+    // Both tied candidates are listed, in ID order
+    let (first, second) = if f.spike < twin { (f.spike, twin) } else { (twin, f.spike) };
+    assert!(message.ends_with(&format!("Spike-ACE2 binding ({}), Spike-ACE2 binding ({})", first, second)), "{}", message);
+    assert_eq!(edge_endpoints(&parsed), [(f.spike, f.antibody), (twin, f.antibody)]);
+}
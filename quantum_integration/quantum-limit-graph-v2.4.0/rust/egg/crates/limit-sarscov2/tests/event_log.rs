@@ -0,0 +1,83 @@
+// limit-sarscov2/tests/event_log.rs
+// Graphs rebuilt from the event log match the live ones, and failed log writes leave graphs untouched
+
+mod common;
+
+use axum::http::{Method, StatusCode};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+use common::{fixture, get, post, proposed_node, send, state_with_events};
+use limit_sarscov2::{api, EventLog, MultiIntentGraph};
+
+fn scratch_log() -> PathBuf {
+    std::env::temp_dir().join(format!("graph-events-{}.jsonl", Uuid::new_v4()))
+}
+
+/// The graph as JSON, without the timestamps replay refreshes
+fn comparable(graph: &MultiIntentGraph) -> Value {
+    let mut value = serde_json::to_value(graph).unwrap();
+    value.as_object_mut().unwrap().remove("metadata");
+    value
+}
+
+#[tokio::test]
+async fn reopened_log_rebuilds_the_live_graph() {
+    let path = scratch_log();
+    let f = fixture();
+    let state = state_with_events(vec![f.graph.clone()], EventLog::open(&path).unwrap());
+    let app = api::router(state.clone());
+    let base = format!("/multi-graph/{}", f.graph.id);
+
+    let steps = [
+        post(&app, &format!("{}/edge-types", base), &[], json!({ "name": "Modulates", "description": "changes activity" })).await,
+        post(&app, &format!("{}/synonyms", base), &[], json!({ "canonical": "Spike-ACE2 binding", "aliases": ["RBD binding"] })).await,
+        post(&app, &format!("{}/tags/{}", base, f.spike), &[], json!({ "label": "reviewed", "passed": true })).await,
+        post(&app, &format!("{}/edges/{}/refutations", base, f.edge), &[], json!({ "label": "no escape", "evidence_refs": ["PMID:2"], "confidence": 0.4 })).await,
+        post(&app, &format!("{}/hyperedges", base), &[], json!({
+            "edge_type": "Modulates", "source_ids": [f.spike], "target_ids": [f.antibody], "label": "jointly", "confidence": 0.5,
+        })).await,
+        post(&app, &format!("{}/confidence/propagate", base), &[], json!({ "full": true })).await,
+    ];
+    for reply in &steps {
+        assert!(reply.status.is_success(), "{} {}", reply.status, reply.body);
+    }
+    let proposed = post(&app, &format!("{}/proposals", base), &[], proposed_node(0.6)).await;
+    let approve = format!("/governance/approve/{}", proposed.body["id"].as_str().unwrap());
+    assert_eq!(post(&app, &approve, &[], json!({})).await.status, StatusCode::OK);
+    let removed = send(&app, Method::DELETE, &format!("{}/nodes/{}?dangling=delete", base, f.antibody), &[], None).await;
+    assert_eq!(removed.status, StatusCode::OK);
+
+    let live = state.multi_graphs.lock().unwrap()[0].clone();
+    let rebuilt = EventLog::open(&path).unwrap().graphs();
+    assert_eq!(rebuilt.len(), 1);
+    assert_eq!(comparable(&rebuilt[0]), comparable(&live));
+    assert!(!live.intent_nodes.contains_key(&f.antibody));
+    assert_eq!(live.intent_nodes.len(), 2, "spike and the approved node");
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn failed_log_write_leaves_the_graph_unchanged() {
+    let path = scratch_log();
+    let f = fixture();
+    let state = state_with_events(vec![f.graph.clone()], EventLog::open(&path).unwrap());
+    let app = api::router(state.clone());
+    let before = comparable(&state.multi_graphs.lock().unwrap()[0]);
+
+    // A directory where the log file was: every append fails
+    std::fs::remove_file(&path).unwrap();
+    std::fs::create_dir(&path).unwrap();
+
+    let reply = post(&app, &format!("/multi-graph/{}/tags/{}", f.graph.id, f.spike), &[], json!({ "label": "reviewed", "passed": true })).await;
+    assert_eq!(reply.status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert!(reply.body["trace_id"].is_string());
+    let reply = send(&app, Method::DELETE, &format!("/multi-graph/{}/edges/{}", f.graph.id, f.edge), &[], None).await;
+    assert_eq!(reply.status, StatusCode::INTERNAL_SERVER_ERROR);
+
+    assert_eq!(comparable(&state.multi_graphs.lock().unwrap()[0]), before);
+    let tags = get(&app, &format!("/multi-graph/{}/tags", f.graph.id)).await;
+    assert_eq!(tags.body, json!([]));
+    std::fs::remove_dir(&path).unwrap();
+}
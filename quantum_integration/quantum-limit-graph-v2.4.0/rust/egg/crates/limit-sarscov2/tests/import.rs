@@ -0,0 +1,113 @@
+// limit-sarscov2/tests/import.rs
+// JSON Lines reading across chunk boundaries, and per-record rejections on import
+
+mod common;
+
+use uuid::Uuid;
+
+use common::{fixture, immunology_node};
+use limit_sarscov2::{edges::GraphEdge, import::MAX_LINE_BYTES, import_records, ImportRecord, JsonlReader};
+
+fn line(record: &impl serde::Serialize) -> String {
+    serde_json::to_string(record).unwrap() + "\n"
+}
+
+/// Line numbers and ids of what the reader parsed, with the errors of lines that did not
+fn read(chunks: &[&[u8]]) -> Vec<(usize, Result<Uuid, String>)> {
+    let mut reader = JsonlReader::new();
+    for chunk in chunks {
+        reader.push(chunk);
+    }
+    reader.finish().into_iter().map(|(line, parsed)| (line, parsed.map(|r| r.id()))).collect()
+}
+
+#[test]
+fn record_split_across_chunks_is_reassembled() {
+    let node = immunology_node("Interferon β signalling", 0.6);
+    let text = line(&node);
+    // Split inside the JSON and inside the two-byte "β"
+    let beta = text.find('β').unwrap();
+    let bytes = text.as_bytes();
+    let chunks = [&bytes[..10], &bytes[10..beta + 1], &bytes[beta + 1..]];
+    assert_eq!(read(&chunks), [(1, Ok(node.id))]);
+}
+
+#[test]
+fn trailing_line_without_newline_is_read() {
+    let (first, last) = (immunology_node("first", 0.5), immunology_node("last", 0.5));
+    let text = line(&first) + "\n   \n" + line(&last).trim_end();
+    // Blank lines are skipped but still counted
+    assert_eq!(read(&[text.as_bytes()]), [(1, Ok(first.id)), (4, Ok(last.id))]);
+    assert_eq!(read(&[line(&first).as_bytes()]), [(1, Ok(first.id))], "no empty record after the last newline");
+}
+
+#[test]
+fn unreadable_lines_are_reported_by_number() {
+    let node = immunology_node("ok", 0.5);
+    let oversized = format!("{{\"pad\": \"{}\"}}\n", "x".repeat(MAX_LINE_BYTES));
+    let mut body: Vec<u8> = vec![];
+    body.extend(b"{not json\n");
+    body.extend(b"[1, 2]\n");
+    body.extend(b"{\"id\": \"not a node\"}\n");
+    body.extend(b"{\"source_id\": 1}\n");
+    body.extend([0xff, 0xfe, b'\n']);
+    body.extend(oversized.as_bytes());
+    body.extend(line(&node).as_bytes());
+
+    let parsed = read(&[&body[..MAX_LINE_BYTES / 2], &body[MAX_LINE_BYTES / 2..]]);
+    let errors: Vec<(usize, &str)> = parsed.iter()
+        .filter_map(|(n, r)| r.as_ref().err().map(|e| (*n, e.as_str())))
+        .collect();
+    assert_eq!(errors.len(), 6, "{:?}", errors);
+    assert!(errors[0].1.starts_with("invalid JSON"), "{}", errors[0].1);
+    assert_eq!(errors[1].1, "expected a JSON object");
+    assert!(errors[2].1.starts_with("invalid node"), "{}", errors[2].1);
+    assert!(errors[3].1.starts_with("invalid edge"), "{}", errors[3].1);
+    assert_eq!(errors[4].1, "line is not valid UTF-8");
+    assert_eq!(errors[5], (6, format!("line exceeds {} bytes", MAX_LINE_BYTES).as_str()));
+    assert_eq!(parsed.last().unwrap(), &(7, Ok(node.id)), "reading resumes after an oversized line");
+}
+
+#[test]
+fn invalid_records_are_rejected_with_line_and_id() {
+    let f = fixture();
+    let mut graph = f.graph.clone();
+    let fresh = immunology_node("T-cell response", 0.6);
+    let bad = immunology_node("Overconfident", 1.5);
+    let edge = |source, target, source_domain: &str| GraphEdge::new_causal(
+        source, target, "primes".into(), source_domain.into(), "Immunology".into(), vec!["PMID:9".into()], 0.7,
+    );
+    let accepted_edge = edge(f.spike, fresh.id, "Virology");
+    let dangling = edge(f.spike, Uuid::new_v4(), "Virology");
+    let wrong_domain = edge(f.antibody, fresh.id, "Virology");
+
+    let body = [
+        line(&fresh),                               // 1: accepted
+        line(&bad),                                 // 2: confidence out of range
+        "{oops\n".into(),                           // 3: does not parse
+        line(&f.graph.intent_nodes[&f.spike]),      // 4: already in the graph
+        line(&accepted_edge),                       // 5: endpoints from the graph and line 1
+        line(&dangling),                            // 6: target missing
+        line(&wrong_domain),                        // 7: source is Immunology
+    ].concat();
+    let mut reader = JsonlReader::new();
+    reader.push(body.as_bytes());
+    let records = reader.finish();
+    assert!(matches!(records[0].1, Ok(ImportRecord::Node(_))));
+    assert!(matches!(records[4].1, Ok(ImportRecord::Edge(_))));
+
+    let (summary, notes) = import_records(&mut graph, records);
+    assert_eq!((summary.records, summary.nodes_accepted, summary.edges_accepted, summary.rejected), (7, 1, 1, 5));
+    let rejections: Vec<(usize, Option<Uuid>)> = summary.rejections.iter().map(|r| (r.line, r.id)).collect();
+    assert_eq!(rejections, [(2, Some(bad.id)), (3, None), (4, Some(f.spike)), (6, Some(dangling.id)), (7, Some(wrong_domain.id))]);
+    let reasons: Vec<&str> = summary.rejections.iter().map(|r| r.reason.as_str()).collect();
+    assert!(reasons[0].contains("confidence 1.5"), "{}", reasons[0]);
+    assert!(reasons[2].contains("already exists"), "{}", reasons[2]);
+    assert!(reasons[3].starts_with("target node"), "{}", reasons[3]);
+    assert!(reasons[4].starts_with("source_domain is \"Virology\""), "{}", reasons[4]);
+
+    assert!(graph.intent_nodes.contains_key(&fresh.id) && graph.edges.contains_key(&accepted_edge.id));
+    assert!(!graph.intent_nodes.contains_key(&bad.id));
+    assert_eq!(notes.len(), 2);
+    assert!(notes.iter().all(|n| n.source == format!("import:{}", summary.import_id)));
+}
@@ -0,0 +1,40 @@
+// limit-sarscov2/tests/intents.rs
+// Intent detection thresholds, ordering and matched terms
+
+use limit_sarscov2::{DetectedIntent, IntentDetector};
+
+fn names(detected: &[DetectedIntent]) -> Vec<&str> {
+    detected.iter().map(|d| d.intent.as_str()).collect()
+}
+
+#[test]
+fn one_generic_keyword_detects_nothing() {
+    let detector = IntentDetector::default();
+    for question in ["What dose of coffee is safe?", "Is the new office policy fair?", "Which variant of chess is fastest?"] {
+        assert!(detector.detect(question).is_empty(), "{question}: {:?}", names(&detector.detect(question)));
+    }
+}
+
+#[test]
+fn two_keywords_detect_the_intent() {
+    let detected = IntentDetector::default().detect("Does a booster dose help?");
+    assert_eq!(names(&detected), ["vaccine_efficacy"]);
+    assert_eq!(detected[0].matched_terms, ["booster", "dose"]);
+    assert!(detected[0].confidence >= 0.45);
+}
+
+#[test]
+fn intents_come_strongest_first() {
+    let detected = IntentDetector::default()
+        .detect("Do Omicron variant mutations escape antibody neutralization after a booster vaccine?");
+    assert_eq!(names(&detected), ["immune_escape", "vaccine_efficacy"]);
+    assert!(detected[0].confidence > detected[1].confidence);
+    assert_eq!(detected[0].matched_terms, ["escape", "neutraliz", "antibod", "mutation", "variant"]);
+    assert_eq!(detected[1].matched_terms, ["vaccin", "booster"]);
+}
+
+#[test]
+fn min_confidence_is_adjustable() {
+    let detector = IntentDetector { min_confidence: 0.3, ..IntentDetector::default() };
+    assert_eq!(names(&detector.detect("What dose of coffee is safe?")), ["vaccine_efficacy"]);
+}
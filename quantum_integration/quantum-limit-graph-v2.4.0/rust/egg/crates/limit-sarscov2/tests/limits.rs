@@ -0,0 +1,70 @@
+// limit-sarscov2/tests/limits.rs
+// Rate limiting and body size limits applied around the API router
+
+mod common;
+
+use axum::http::{header, Method, StatusCode};
+use serde_json::json;
+use std::sync::Arc;
+
+use common::{fixture, post, send, state};
+use limit_sarscov2::{api, limits, ApiKeys, RateLimiter};
+
+fn limited(per_minute: u32, burst: u32, keys: &[&str]) -> axum::Router {
+    let digests: Vec<String> = keys.iter().map(|k| ApiKeys::digest(k)).collect();
+    let limiter = RateLimiter::new(per_minute, burst).with_keys(ApiKeys::from_digests(&digests));
+    limits::apply(api::router(state(vec![])), 1 << 20, Some(Arc::new(limiter)))
+}
+
+#[tokio::test]
+async fn over_the_limit_is_429_with_retry_after() {
+    let app = limited(60, 2, &[]);
+    for _ in 0..2 {
+        assert_eq!(send(&app, Method::GET, "/traces", &[], None).await.status, StatusCode::OK);
+    }
+    let reply = send(&app, Method::GET, "/traces", &[], None).await;
+    assert_eq!(reply.status, StatusCode::TOO_MANY_REQUESTS);
+    let retry_after: u64 = reply.headers[header::RETRY_AFTER].to_str().unwrap().parse().unwrap();
+    assert!((1..=2).contains(&retry_after), "Retry-After {}", retry_after);
+    assert_eq!(reply.body["status"], json!(429));
+}
+
+#[tokio::test]
+async fn unknown_keys_share_the_callers_bucket() {
+    let app = limited(60, 2, &[]);
+    for key in ["made-up-1", "made-up-2"] {
+        assert_eq!(send(&app, Method::GET, "/traces", &[("x-api-key", key)], None).await.status, StatusCode::OK);
+    }
+    let reply = send(&app, Method::GET, "/traces", &[("authorization", "Bearer made-up-3")], None).await;
+    assert_eq!(reply.status, StatusCode::TOO_MANY_REQUESTS);
+}
+
+#[tokio::test]
+async fn known_key_has_its_own_bucket() {
+    let app = limited(60, 1, &["team-key"]);
+    assert_eq!(send(&app, Method::GET, "/traces", &[], None).await.status, StatusCode::OK);
+    assert_eq!(send(&app, Method::GET, "/traces", &[], None).await.status, StatusCode::TOO_MANY_REQUESTS);
+    let keyed = send(&app, Method::GET, "/traces", &[("x-api-key", "team-key")], None).await;
+    assert_eq!(keyed.status, StatusCode::OK);
+    let again = send(&app, Method::GET, "/traces", &[("x-api-key", "team-key")], None).await;
+    assert_eq!(again.status, StatusCode::TOO_MANY_REQUESTS);
+}
+
+#[tokio::test]
+async fn oversized_body_is_413() {
+    let f = fixture();
+    let app = limits::apply(api::router(state(vec![f.graph.clone()])), 256, None);
+    let uri = format!("/multi-graph/{}/proposals", f.graph.id);
+    let body = json!({ "kind": "add_node", "value": { "padding": "x".repeat(1024) } });
+
+    // Declared length over the limit
+    let length = body.to_string().len().to_string();
+    let reply = send(&app, Method::POST, &uri, &[("content-length", length.as_str())], Some(body.clone())).await;
+    assert_eq!(reply.status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(reply.body["status"], json!(413));
+
+    // No declared length: cut off while streaming
+    let reply = post(&app, &uri, &[], body).await;
+    assert_eq!(reply.status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(reply.body["status"], json!(413));
+}
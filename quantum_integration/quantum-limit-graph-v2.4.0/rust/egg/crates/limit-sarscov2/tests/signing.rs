@@ -0,0 +1,88 @@
+// limit-sarscov2/tests/signing.rs
+// Provenance note signatures: verification, tampering, unknown keys and notes signed before request IDs and actors
+
+use uuid::Uuid;
+
+use limit_sarscov2::{signing::verify_note, verify_notes, NoteSigner, ProvenanceNote, SignatureStatus, TrustedKeys};
+
+fn signer(key_id: &str, seed: u8) -> NoteSigner {
+    NoteSigner::from_seed_hex(key_id, &hex::encode([seed; 32])).unwrap()
+}
+
+fn trusting(signer: &NoteSigner) -> TrustedKeys {
+    let mut trusted = TrustedKeys::new();
+    trusted.add(signer.key_id(), signer.verifying_key());
+    trusted
+}
+
+fn note() -> ProvenanceNote {
+    let mut note = ProvenanceNote::new(Uuid::new_v4(), Uuid::new_v4(), "doi:10.1000/spike", "add")
+        .with_rationale("Spike-ACE2 binding");
+    note.request_id = Some("req-1".into());
+    note.actor = Some("ana".into());
+    note
+}
+
+#[test]
+fn signed_note_verifies() {
+    let signer = signer("lab-1", 7);
+    let mut note = note();
+    signer.sign(&mut note);
+    assert_eq!(verify_note(&note, &trusting(&signer)), SignatureStatus::Valid);
+}
+
+#[test]
+fn changing_a_covered_field_fails_verification() {
+    let signer = signer("lab-1", 7);
+    let trusted = trusting(&signer);
+    let mut signed = note();
+    signer.sign(&mut signed);
+
+    let edits: [fn(&mut ProvenanceNote); 6] = [
+        |n| n.source = "doi:10.1000/other".into(),
+        |n| n.rationale = Some("Something else".into()),
+        |n| n.rationale = None,
+        |n| n.request_id = Some("req-2".into()),
+        |n| n.request_id = None,
+        |n| n.actor = Some("mallory".into()),
+    ];
+    for (i, edit) in edits.iter().enumerate() {
+        let mut tampered = signed.clone();
+        edit(&mut tampered);
+        assert_eq!(verify_note(&tampered, &trusted), SignatureStatus::Tampered, "edit {}", i);
+    }
+
+    let mut unattributed = signed.clone();
+    unattributed.actor = None;
+    let report = verify_notes([&signed, &unattributed], &trusted);
+    assert_eq!(report.valid, 1);
+    assert_eq!(report.tampered.len(), 1);
+}
+
+#[test]
+fn unknown_key_id_is_rejected() {
+    let trusted = trusting(&signer("lab-1", 7));
+    let mut note = note();
+    signer("lab-2", 9).sign(&mut note);
+    assert_eq!(verify_note(&note, &trusted), SignatureStatus::UnknownKey);
+
+    // A known key ID does not help a signature made with another key
+    signer("lab-1", 9).sign(&mut note);
+    assert_eq!(verify_note(&note, &trusted), SignatureStatus::Tampered);
+
+    let report = verify_notes([&note], &TrustedKeys::new());
+    assert_eq!(report.unverifiable, [note.id]);
+}
+
+#[test]
+fn note_signed_before_request_id_and_actor_still_verifies() {
+    let signer = signer("lab-1", 7);
+    let mut legacy = ProvenanceNote::new(Uuid::new_v4(), Uuid::new_v4(), "doi:10.1000/spike", "add");
+    signer.sign(&mut legacy);
+
+    // As stored before the fields existed: they are absent from the JSON
+    let json = serde_json::to_value(&legacy).unwrap();
+    assert!(json.get("request_id").is_none() && json.get("actor").is_none());
+    let reloaded: ProvenanceNote = serde_json::from_value(json).unwrap();
+    assert_eq!(verify_note(&reloaded, &trusting(&signer)), SignatureStatus::Valid);
+}
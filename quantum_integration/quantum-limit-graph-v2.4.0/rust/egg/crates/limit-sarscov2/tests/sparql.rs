@@ -0,0 +1,161 @@
+// limit-sarscov2/tests/sparql.rs
+// SELECT/ASK parsing and evaluation over the RDF projection of the two-node fixture
+
+mod common;
+
+use serde_json::json;
+use uuid::Uuid;
+
+use common::fixture;
+use limit_sarscov2::{project, SparqlError, SparqlQuery, SparqlResult, Term, Triple};
+
+fn run(triples: &[Triple], text: &str) -> SparqlResult {
+    SparqlQuery::parse(text).unwrap_or_else(|e| panic!("{}: {}", text, e)).execute(triples)
+}
+
+/// Values bound to `var`, in row order
+fn column(triples: &[Triple], text: &str, var: &str) -> Vec<Term> {
+    match run(triples, text) {
+        SparqlResult::Select { rows, .. } => rows.into_iter().filter_map(|mut row| row.remove(var)).collect(),
+        other => panic!("{}: expected a SELECT result, got {:?}", text, other),
+    }
+}
+
+fn ask(triples: &[Triple], text: &str) -> bool {
+    match run(triples, text) {
+        SparqlResult::Ask(answer) => answer,
+        other => panic!("{}: expected an ASK result, got {:?}", text, other),
+    }
+}
+
+fn uuid(id: Uuid) -> Term {
+    Term::iri(format!("urn:uuid:{}", id))
+}
+
+fn syntax_offset(text: &str) -> usize {
+    match SparqlQuery::parse(text) {
+        Err(SparqlError::Syntax { offset, .. }) => offset,
+        other => panic!("{}: expected a syntax error, got {:?}", text, other),
+    }
+}
+
+#[test]
+fn syntax_errors_report_their_offset() {
+    assert_eq!(syntax_offset("DESCRIBE ?n"), 0);
+    assert_eq!(syntax_offset("SELECT WHERE { ?n a sc2:Node }"), 7);
+    assert_eq!(syntax_offset("SELECT ?n WHERE { ?n a <urn:open }"), 23);
+    assert_eq!(syntax_offset("SELECT ?n WHERE { ?n rdfs:label \"open }"), 32);
+    assert_eq!(syntax_offset("SELECT ?n WHERE { }"), 19);
+    assert_eq!(syntax_offset("SELECT ?n WHERE { ?n a sc2:Node"), 31, "end of input");
+    assert_eq!(syntax_offset("SELECT ?n WHERE { ?n a sc2:Node } LIMIT many"), 40);
+    assert_eq!(syntax_offset("SELECT ?n WHERE { ?n a sc2:Node } ORDER BY ?n"), 34);
+    assert_eq!(syntax_offset("SELECT ? WHERE { ?n a sc2:Node }"), 7);
+}
+
+#[test]
+fn unknown_prefixes_and_unbound_variables_are_rejected() {
+    assert_eq!(SparqlQuery::parse("SELECT ?n WHERE { ?n a foo:Bar }"), Err(SparqlError::UnknownPrefix("foo".into())));
+    assert_eq!(
+        SparqlQuery::parse("SELECT ?n ?missing WHERE { ?n a sc2:Node }"),
+        Err(SparqlError::UnboundVariable("missing".into())),
+    );
+    // Declared prefixes add to the predeclared ones
+    let declared = "PREFIX ex: <https://w3id.org/sarscov2-kg/ns#> SELECT ?n WHERE { ?n a ex:Node }";
+    assert!(SparqlQuery::parse(declared).is_ok());
+}
+
+#[test]
+fn filter_is_a_syntax_error() {
+    let texts = [
+        "SELECT ?n WHERE { ?n sc2:confidence ?c FILTER(?c > \"0.5\"^^xsd:decimal) }",
+        "SELECT ?n WHERE { ?n sc2:confidence ?c . FILTER(?c > 0.5) }",
+        "SELECT ?n WHERE { ?n sc2:confidence ?c . FILTER(isLiteral(?c)) }",
+    ];
+    for text in texts {
+        assert!(matches!(SparqlQuery::parse(text), Err(SparqlError::Syntax { .. })), "{}", text);
+    }
+    let Err(SparqlError::Syntax { offset, message }) = SparqlQuery::parse(texts[2]) else { unreachable!() };
+    assert_eq!((offset, message.as_str()), (41, "FILTER is not supported (only basic graph patterns)"));
+}
+
+#[test]
+fn joins_follow_edges_in_either_direction() {
+    let f = fixture();
+    let triples = project(&f.graph);
+    let forward = "SELECT ?label WHERE { ?v a sc2:Virology ; sc2:causal ?i . ?i rdfs:label ?label }";
+    assert_eq!(column(&triples, forward, "label"), [Term::text("Antibody neutralization")]);
+
+    // Bind the object and ask for the subject
+    let reverse = format!("SELECT ?s WHERE {{ ?s sc2:causal <urn:uuid:{}> }}", f.antibody);
+    assert_eq!(column(&triples, &reverse, "s"), [uuid(f.spike)]);
+    let reified = format!("SELECT ?e WHERE {{ ?e sc2:source <urn:uuid:{}> ; sc2:target ?t . ?t a sc2:Immunology }}", f.spike);
+    assert_eq!(column(&triples, &reified, "e"), [uuid(f.edge)]);
+
+    assert!(ask(&triples, &format!("ASK {{ <urn:uuid:{}> sc2:causal ?x }}", f.spike)));
+    assert!(!ask(&triples, &format!("ASK {{ <urn:uuid:{}> sc2:causal ?x }}", f.antibody)));
+}
+
+#[test]
+fn quoted_literals_match_plain_strings() {
+    let f = fixture();
+    let triples = project(&f.graph);
+    for literal in ["\"Spike-ACE2 binding\"", "'Spike-ACE2 binding'", "\"Spike-ACE2 binding\"@en", "\"Spike-ACE2 binding\"^^xsd:string"] {
+        let text = format!("SELECT ?n WHERE {{ ?n rdfs:label {} }}", literal);
+        assert_eq!(column(&triples, &text, "n"), [uuid(f.spike)], "{}", literal);
+    }
+    assert!(column(&triples, "SELECT ?n WHERE { ?n rdfs:label \"spike-ace2 binding\" }", "n").is_empty(), "case-sensitive");
+}
+
+#[test]
+fn typed_literals_match_only_their_datatype() {
+    let f = fixture();
+    let triples = project(&f.graph);
+    // The spike node and the edge both have confidence 0.8
+    let mut expected = vec![uuid(f.spike), uuid(f.edge)];
+    expected.sort();
+    for literal in ["0.8", "\"0.8\"^^xsd:decimal", "\"0.8\"^^<http://www.w3.org/2001/XMLSchema#decimal>"] {
+        let text = format!("SELECT ?s WHERE {{ ?s sc2:confidence {} }}", literal);
+        let mut found = column(&triples, &text, "s");
+        found.sort();
+        assert_eq!(found, expected, "{}", literal);
+    }
+    assert!(!ask(&triples, "ASK { ?s sc2:confidence \"0.8\" }"), "plain string");
+    assert!(!ask(&triples, "ASK { ?s sc2:confidence \"0.8\"^^xsd:double }"));
+    assert!(ask(&triples, "ASK { ?s sc2:evidenceCount 3 }"), "integer");
+    assert!(!ask(&triples, "ASK { ?s sc2:evidenceCount 3.0 }"), "decimal is not integer");
+}
+
+#[test]
+fn limit_distinct_and_select_star() {
+    let f = fixture();
+    let triples = project(&f.graph);
+    assert_eq!(column(&triples, "SELECT ?n WHERE { ?n a sc2:Node }", "n").len(), 2);
+    assert_eq!(column(&triples, "SELECT ?n WHERE { ?n a sc2:Node } LIMIT 1", "n").len(), 1);
+    assert!(column(&triples, "SELECT ?n WHERE { ?n a sc2:Node } LIMIT 0", "n").is_empty());
+
+    // Every node and edge is part of the graph: one row per subject, one distinct graph
+    let parts = "SELECT ?g WHERE { ?s dcterms:isPartOf ?g }";
+    assert_eq!(column(&triples, parts, "g").len(), 3);
+    assert_eq!(column(&triples, &parts.replace("SELECT", "SELECT DISTINCT"), "g"), [uuid(f.graph.id)]);
+
+    let SparqlResult::Select { vars, rows } = run(&triples, "SELECT * WHERE { ?e a sc2:Causal ; rdfs:label ?label }") else {
+        panic!("expected a SELECT result");
+    };
+    assert_eq!(vars, ["e", "label"]);
+    assert_eq!(rows.len(), 1);
+}
+
+#[test]
+fn results_serialize_as_sparql_json() {
+    let f = fixture();
+    let triples = project(&f.graph);
+    let select = run(&triples, "SELECT ?e ?c WHERE { ?e a sc2:Edge ; sc2:confidence ?c }").to_json();
+    assert_eq!(select, json!({
+        "head": { "vars": ["e", "c"] },
+        "results": { "bindings": [{
+            "e": { "type": "uri", "value": format!("urn:uuid:{}", f.edge) },
+            "c": { "type": "literal", "value": "0.8", "datatype": "http://www.w3.org/2001/XMLSchema#decimal" },
+        }] },
+    }));
+    assert_eq!(run(&triples, "ASK { ?n a sc2:Treatment }").to_json(), json!({ "head": {}, "boolean": false }));
+}
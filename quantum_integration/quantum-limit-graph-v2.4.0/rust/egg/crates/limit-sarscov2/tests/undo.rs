@@ -0,0 +1,124 @@
+// limit-sarscov2/tests/undo.rs
+// Undo and redo of a session's operations over the API
+
+mod common;
+
+use axum::{http::StatusCode, Router};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use common::{fixture, get, post, state, Fixture, Reply};
+use limit_sarscov2::api;
+
+async fn tag(app: &Router, f: &Fixture, subject: Uuid, label: &str, session: &str) -> Reply {
+    let uri = format!("/multi-graph/{}/tags/{}", f.graph.id, subject);
+    post(app, &uri, &[("x-session", session)], json!({ "label": label, "passed": true })).await
+}
+
+async fn step(app: &Router, f: &Fixture, action: &str, headers: &[(&str, &str)]) -> Reply {
+    post(app, &format!("/multi-graph/{}/{}", f.graph.id, action), headers, json!({})).await
+}
+
+/// Labels of the tags on `subject`, sorted
+async fn tags(app: &Router, f: &Fixture, subject: Uuid) -> Vec<String> {
+    let reply = get(app, &format!("/multi-graph/{}/tags", f.graph.id)).await;
+    let mut labels: Vec<String> = reply.body.as_array().unwrap().iter()
+        .filter(|t| t["subject_id"] == json!(subject))
+        .map(|t| t["tag"]["label"].as_str().unwrap().to_string())
+        .collect();
+    labels.sort();
+    labels
+}
+
+fn setup() -> (Router, Fixture) {
+    let f = fixture();
+    (api::router(state(vec![f.graph.clone()])), f)
+}
+
+#[tokio::test]
+async fn undone_operation_can_be_redone_and_undone_again() {
+    let (app, f) = setup();
+    tag(&app, &f, f.spike, "first", "s1").await;
+    tag(&app, &f, f.spike, "second", "s1").await;
+
+    let undone = step(&app, &f, "undo", &[("x-session", "s1")]).await;
+    assert_eq!(undone.status, StatusCode::OK);
+    assert_eq!(tags(&app, &f, f.spike).await, ["first"]);
+
+    assert_eq!(step(&app, &f, "redo", &[("x-session", "s1")]).await.status, StatusCode::OK);
+    assert_eq!(tags(&app, &f, f.spike).await, ["first", "second"]);
+
+    // The redone operation is the latest again, so it is what the next undo reverts
+    assert_eq!(step(&app, &f, "undo", &[("x-session", "s1")]).await.status, StatusCode::OK);
+    assert_eq!(tags(&app, &f, f.spike).await, ["first"]);
+    assert_eq!(step(&app, &f, "undo", &[("x-session", "s1")]).await.status, StatusCode::OK);
+    assert!(tags(&app, &f, f.spike).await.is_empty());
+}
+
+#[tokio::test]
+async fn new_edit_clears_redo() {
+    let (app, f) = setup();
+    tag(&app, &f, f.spike, "first", "s1").await;
+    step(&app, &f, "undo", &[("x-session", "s1")]).await;
+    tag(&app, &f, f.spike, "replacement", "s1").await;
+
+    let redo = step(&app, &f, "redo", &[("x-session", "s1")]).await;
+    assert_eq!(redo.status, StatusCode::CONFLICT);
+    assert!(redo.body["error"].as_str().unwrap().contains("nothing to redo"), "{}", redo.body);
+    assert_eq!(tags(&app, &f, f.spike).await, ["replacement"]);
+}
+
+#[tokio::test]
+async fn undo_that_a_later_operation_depends_on_is_409() {
+    let (app, f) = setup();
+    let uri = format!("/multi-graph/{}/edges/{}/refutations", f.graph.id, f.edge);
+    let support = post(&app, &uri, &[("x-session", "s1")], json!({ "label": "no escape", "evidence_refs": ["PMID:2"], "confidence": 0.4 })).await;
+    assert_eq!(support.status, StatusCode::CREATED);
+    let refutation: Uuid = serde_json::from_value(support.body["refuting"][0].clone()).unwrap();
+    // Another session builds on the refuting edge
+    assert_eq!(tag(&app, &f, refutation, "checked", "s2").await.status, StatusCode::CREATED);
+
+    let undo = step(&app, &f, "undo", &[("x-session", "s1")]).await;
+    assert_eq!(undo.status, StatusCode::CONFLICT);
+    assert!(undo.body["error"].as_str().unwrap().contains("no longer applies"), "{}", undo.body);
+    assert_eq!(tags(&app, &f, refutation).await, ["checked"], "graph unchanged");
+}
+
+#[tokio::test]
+async fn sessions_undo_only_their_own_operations() {
+    let (app, f) = setup();
+    tag(&app, &f, f.spike, "mine", "s1").await;
+    tag(&app, &f, f.spike, "theirs", "s2").await;
+
+    assert_eq!(step(&app, &f, "undo", &[("x-session", "s1")]).await.status, StatusCode::OK);
+    assert_eq!(tags(&app, &f, f.spike).await, ["theirs"]);
+    assert_eq!(step(&app, &f, "undo", &[("x-session", "s1")]).await.status, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn session_defaults_to_the_actor() {
+    let (app, f) = setup();
+    let uri = format!("/multi-graph/{}/tags/{}", f.graph.id, f.spike);
+    post(&app, &uri, &[("x-actor", "alice")], json!({ "label": "alice's", "passed": true })).await;
+
+    let undone = step(&app, &f, "undo", &[("x-actor", "alice")]).await;
+    assert_eq!(undone.status, StatusCode::OK);
+    let events: Vec<Value> = serde_json::from_value(undone.body).unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["actor"], "alice");
+    assert_eq!(events[0]["session"], "alice");
+}
+
+#[tokio::test]
+async fn undo_without_a_session_or_actor_is_400() {
+    let (app, f) = setup();
+    // Anonymous edits share one session, so nobody may undo them anonymously
+    post(&app, &format!("/multi-graph/{}/tags/{}", f.graph.id, f.spike), &[], json!({ "label": "anon", "passed": true })).await;
+
+    for action in ["undo", "redo"] {
+        let reply = step(&app, &f, action, &[]).await;
+        assert_eq!(reply.status, StatusCode::BAD_REQUEST, "{}", action);
+        assert!(reply.body["error"].as_str().unwrap().contains("x-session"), "{}", reply.body);
+    }
+    assert_eq!(tags(&app, &f, f.spike).await, ["anon"]);
+}
@@ -0,0 +1,184 @@
+// limit-sarscov2/tests/workspaces.rs
+// Workspace configuration checks, API key enforcement and isolation between workspaces
+
+mod common;
+
+use axum::{http::{Method, StatusCode}, middleware};
+use serde_json::json;
+use std::sync::Arc;
+
+use common::{fixture, post_text, proposed_node, send, state};
+use limit_sarscov2::{correlation, workspace, ApiKeyConfig, ApiKeys, MultiIntentGraph, Workspace, WorkspacesConfig};
+
+fn digest(key: &str) -> String {
+    ApiKeys::digest(key)
+}
+
+fn workspace(name: &str, key: &str, graphs: Vec<MultiIntentGraph>) -> Workspace {
+    Workspace { name: name.into(), state: state(graphs), keys: Arc::new(ApiKeys::from_digests(&[digest(key)])) }
+}
+
+#[test]
+fn workspace_without_keys_is_rejected() {
+    let toml = "[[workspace]]\nname = \"open-lab\"\n";
+    let e = WorkspacesConfig::from_toml(toml).unwrap_err();
+    assert!(e.to_string().contains("no api_keys"), "{}", e);
+}
+
+#[test]
+fn shared_log_paths_are_rejected() {
+    let toml = format!(
+        "[[workspace]]\nname = \"a\"\napi_keys = [\"{0}\"]\nevent_log = \"events.jsonl\"\n\n\
+         [[workspace]]\nname = \"b\"\napi_keys = [\"{0}\"]\nevent_log = \"./events.jsonl\"\n",
+        digest("k"),
+    );
+    let e = WorkspacesConfig::from_toml(&toml).unwrap_err();
+    assert!(e.to_string().contains("already used by workspace \"a\""), "{}", e);
+
+    // One workspace's default audit log named as another's event log
+    let toml = format!(
+        "[[workspace]]\nname = \"a\"\napi_keys = [\"{0}\"]\n\n\
+         [[workspace]]\nname = \"b\"\napi_keys = [\"{0}\"]\nevent_log = \"a-governance-audit.jsonl\"\n",
+        digest("k"),
+    );
+    assert!(WorkspacesConfig::from_toml(&toml).is_err());
+
+    let toml = format!("[[workspace]]\nname = \"a\"\napi_keys = [\"{0}\"]\n\n[[workspace]]\nname = \"b\"\napi_keys = [\"{0}\"]\n", digest("k"));
+    let config = WorkspacesConfig::from_toml(&toml).unwrap();
+    assert_eq!(config.workspaces[1].event_log_path().to_str(), Some("b-graph-events.jsonl"));
+}
+
+#[tokio::test]
+async fn missing_or_wrong_key_is_401() {
+    let f = fixture();
+    let app = workspace::router(&[workspace("sanger", "sanger-key", vec![f.graph.clone()])]);
+    let uri = format!("/w/sanger/multi-graph/{}/summary", f.graph.id);
+
+    let reply = send(&app, Method::GET, &uri, &[], None).await;
+    assert_eq!(reply.status, StatusCode::UNAUTHORIZED);
+    assert_eq!(reply.body["error"], "API key required");
+
+    let reply = send(&app, Method::GET, &uri, &[("x-api-key", "guess")], None).await;
+    assert_eq!(reply.status, StatusCode::UNAUTHORIZED);
+    assert_eq!(reply.body["error"], "unknown API key");
+
+    let reply = send(&app, Method::GET, &uri, &[("authorization", "Bearer sanger-key")], None).await;
+    assert_eq!(reply.status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn workspaces_do_not_see_each_others_graphs_or_keys() {
+    let (a, b) = (fixture(), fixture());
+    let app = workspace::router(&[
+        workspace("alpha", "alpha-key", vec![a.graph.clone()]),
+        workspace("beta", "beta-key", vec![b.graph.clone()]),
+    ]);
+
+    let own = send(&app, Method::GET, &format!("/w/alpha/multi-graph/{}/summary", a.graph.id), &[("x-api-key", "alpha-key")], None).await;
+    assert_eq!(own.status, StatusCode::OK);
+
+    // Beta's graph is not in alpha
+    let other = send(&app, Method::GET, &format!("/w/alpha/multi-graph/{}/summary", b.graph.id), &[("x-api-key", "alpha-key")], None).await;
+    assert_eq!(other.status, StatusCode::NOT_FOUND);
+
+    // Alpha's key does not open beta
+    let crossed = send(&app, Method::GET, &format!("/w/beta/multi-graph/{}/summary", b.graph.id), &[("x-api-key", "alpha-key")], None).await;
+    assert_eq!(crossed.status, StatusCode::UNAUTHORIZED);
+
+    // No unprefixed routes
+    let root = send(&app, Method::GET, &format!("/multi-graph/{}/summary", a.graph.id), &[("x-api-key", "alpha-key")], None).await;
+    assert_eq!(root.status, StatusCode::NOT_FOUND);
+}
+
+#[test]
+fn keys_may_be_bound_to_roles() {
+    let toml = format!(
+        "[[workspace]]\nname = \"a\"\napi_keys = [\"{}\", {{ digest = \"{}\", role = \"curator\" }}]\n",
+        digest("plain"), digest("curate"),
+    );
+    let config = WorkspacesConfig::from_toml(&toml).unwrap();
+    let keys = ApiKeys::from_config(&config.workspaces[0].api_keys);
+    assert_eq!(keys.caller("plain").unwrap().role, None);
+    assert_eq!(keys.caller("curate").unwrap().role.as_deref(), Some("curator"));
+    assert!(keys.caller("other").is_none());
+}
+
+#[tokio::test]
+async fn reviewer_role_comes_from_the_key_not_the_request() {
+    let f = fixture();
+    let ws = Workspace {
+        name: "lab".into(),
+        state: state(vec![f.graph.clone()]),
+        keys: Arc::new(ApiKeys::from_config(&[
+            ApiKeyConfig::Digest(digest("member")),
+            ApiKeyConfig::Bound { digest: digest("curator"), actor: None, role: Some("curator".into()) },
+        ])),
+    };
+    ws.state.governance.lock().unwrap().approver_roles = vec!["curator".into()];
+    let app = workspace::router(&[ws]);
+
+    let proposed = send(&app, Method::POST, &format!("/w/lab/multi-graph/{}/proposals", f.graph.id), &[("x-api-key", "member")], Some(proposed_node(0.6))).await;
+    assert_eq!(proposed.status, StatusCode::CREATED);
+    let approve = format!("/w/lab/governance/approve/{}", proposed.body["id"].as_str().unwrap());
+
+    let claimed = send(&app, Method::POST, &approve, &[("x-api-key", "member"), ("x-role", "curator")], Some(json!({}))).await;
+    assert_eq!(claimed.status, StatusCode::FORBIDDEN);
+
+    let approved = send(&app, Method::POST, &approve, &[("x-api-key", "curator")], Some(json!({}))).await;
+    assert_eq!(approved.status, StatusCode::OK);
+    assert_eq!(approved.body["status"], "approved");
+}
+
+#[tokio::test]
+async fn only_approvers_may_replace_a_policy_naming_approvers() {
+    let ws = Workspace {
+        name: "lab".into(),
+        state: state(vec![]),
+        keys: Arc::new(ApiKeys::from_config(&[
+            ApiKeyConfig::Digest(digest("member")),
+            ApiKeyConfig::Bound { digest: digest("curator"), actor: None, role: Some("curator".into()) },
+        ])),
+    };
+    ws.state.governance.lock().unwrap().approver_roles = vec!["curator".into()];
+    let governance = ws.state.governance.clone();
+    let app = workspace::router(&[ws]);
+
+    // Lifting the restriction would let a member approve their own proposals
+    let lifted = post_text(&app, "/w/lab/governance/policy", &[("x-api-key", "member")], "approver_roles = []\n").await;
+    assert_eq!(lifted.status, StatusCode::FORBIDDEN);
+    assert_eq!(governance.lock().unwrap().approver_roles, ["curator"]);
+
+    let replaced = post_text(&app, "/w/lab/governance/policy", &[("x-api-key", "curator")], "approver_roles = [\"curator\", \"lead\"]\n").await;
+    assert_eq!(replaced.status, StatusCode::OK);
+    assert_eq!(governance.lock().unwrap().approver_roles, ["curator", "lead"]);
+}
+
+#[tokio::test]
+async fn notes_name_the_keys_actor_not_the_request() {
+    let f = fixture();
+    let ws = Workspace {
+        name: "lab".into(),
+        state: state(vec![f.graph.clone()]),
+        keys: Arc::new(ApiKeys::from_config(&[
+            ApiKeyConfig::Bound { digest: digest("ana-key"), actor: Some("ana".into()), role: None },
+            ApiKeyConfig::Digest(digest("shared")),
+        ])),
+    };
+    let app = workspace::router(&[ws]).layer(middleware::from_fn(correlation::assign_request_id));
+    let graph = format!("/w/lab/multi-graph/{}", f.graph.id);
+
+    let tag = |label: &str| json!({ "label": label, "passed": true });
+    let tags = format!("{}/tags/{}", graph, f.spike);
+    let tagged = send(&app, Method::POST, &tags, &[("x-api-key", "ana-key"), ("x-actor", "mallory")], Some(tag("reviewed"))).await;
+    assert_eq!(tagged.status, StatusCode::CREATED);
+    send(&app, Method::POST, &tags, &[("x-api-key", "shared"), ("x-session", "shared")], Some(tag("checked"))).await;
+    let undone = send(&app, Method::POST, &format!("{}/undo", graph), &[("x-api-key", "shared"), ("x-session", "shared")], None).await;
+    assert_eq!(undone.status, StatusCode::OK);
+
+    let notes = send(&app, Method::GET, &format!("/w/lab/provenance/{}", f.graph.id), &[("x-api-key", "ana-key")], None).await;
+    let made: Vec<(&str, &str)> = notes.body.as_array().unwrap().iter()
+        .map(|n| (n["operation"].as_str().unwrap(), n["actor"].as_str().unwrap()))
+        .collect();
+    let shared = format!("key:{}", &digest("shared")[..12]);
+    assert_eq!(made, [("tag", "ana"), ("tag", shared.as_str()), ("undo", shared.as_str())]);
+}